@@ -1,4 +1,4 @@
-use tauri_app_lib::scanner::UnifiedScanner;
+use tauri_app_lib::engine::UnifiedScanner;
 use std::path::PathBuf;
 
 #[tokio::main]
@@ -23,6 +23,9 @@ async fn main() {
                 println!("Version: {}", version);
             }
             println!("Scan Method: {}", result.scan_method);
+            for warning in &result.warnings {
+                println!("Warning: {}", warning);
+            }
             println!("\nFound {} endpoints:\n", result.endpoints.len());
             
             for (i, endpoint) in result.endpoints.iter().enumerate() {
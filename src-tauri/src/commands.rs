@@ -1,22 +1,78 @@
-use crate::{database, http_client, scanner, scenario, security, types::*};
+use crate::{database, http_client, request_log, scanner, scenario, security, types::*};
+use crate::error::AppError;
 use scenario::yaml::{
     ScenarioImportPreview, ProjectImportPreview,
     parse_scenario_yaml, parse_project_scenarios_yaml,
-    scenario_to_yaml_string, project_scenarios_to_yaml_string,
+    scenario_to_yaml_string, project_scenarios_to_yaml_string, ProjectYamlExport, ProjectYamlWriter,
     yaml_to_scenario_with_steps, create_import_preview, create_project_import_preview,
-    generate_yaml_template, generate_yaml_template_with_ai,
+    generate_yaml_template, generate_yaml_template_with_ai, CopilotToolPolicy,
+    AI_CONTEXT_ENDPOINT_LIMIT, AI_YAML_BACKEND,
 };
 use scenario::performance::{
     PerformanceTestConfig, PerformanceTestRun, PerformanceTestType,
-    CreatePerformanceTestInput, Stage, Threshold,
+    CreatePerformanceTestInput, RawSamplePage, UpdatePerformanceTestInput,
 };
+use scenario::schedule::{ScenarioSchedule, ScheduleSummary, ScheduledRunEvent};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tauri_plugin_dialog::DialogExt;
 use uuid::Uuid;
 
+/// Add auth headers for a linked endpoint that requires auth. Placeholders are filled from the
+/// project and request variables; a header whose value can't be resolved is left out.
+fn with_endpoint_auth(mut request: ApiRequest) -> Result<ApiRequest, AppError> {
+    let endpoint = match request.endpoint_id.as_deref().map(database::get_endpoint) {
+        Some(Ok(endpoint)) => endpoint,
+        _ => return Ok(request),
+    };
+    let mut headers = request.headers.take().unwrap_or_default();
+    let missing = http_client::missing_auth_headers(&endpoint, &headers);
+    if !missing.is_empty() {
+        let mut variables = match endpoint.project_id.as_deref() {
+            Some(project_id) => database::get_project(project_id)?.map(|p| p.variables).unwrap_or_default(),
+            None => HashMap::new(),
+        };
+        variables.extend(request.variables.clone().unwrap_or_default());
+        for (name, template) in missing {
+            let value = http_client::resolve_template(&template, &variables);
+            if value.contains("{{") {
+                log::warn!("[Command] Endpoint {} requires auth but {} is unresolved: {}", endpoint.id, name, template);
+                continue;
+            }
+            headers.insert(name, value);
+        }
+    }
+    request.headers = Some(headers);
+    Ok(request)
+}
+
 #[tauri::command]
-pub async fn execute_http_request(request: ApiRequest) -> Result<ApiResponse, String> {
+pub async fn execute_http_request(request: ApiRequest) -> Result<ApiResponse, AppError> {
+    let validate_schema = request.validate_schema;
+    let skip_auth = request.skip_auth;
+
+    // Expand a saved request reference into a concrete request
+    let request = match request.saved_request_id {
+        Some(ref saved_id) => {
+            let saved = database::get_saved_request(saved_id)?
+                .ok_or_else(|| AppError::NotFound(format!("Saved request not found: {}", saved_id)))?;
+
+            let mut variables = HashMap::new();
+            if let Some(project_id) = saved.project_id.as_deref() {
+                if let Some(base_url) = database::get_project(project_id)?.and_then(|p| p.base_url) {
+                    variables.insert("baseUrl".to_string(), serde_json::Value::String(base_url));
+                }
+            }
+            variables.extend(request.variables.clone().unwrap_or_default());
+            // Resolving builds a scenario executor, whose blocking client lives on the blocking pool
+            tauri::async_runtime::spawn_blocking(move || http_client::request_from_saved(&saved, &variables))
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to resolve saved request: {}", e)))??
+        }
+        None => request,
+    };
+    let request = if skip_auth { request } else { with_endpoint_auth(request)? };
+
     log::info!("[Command] execute_http_request called: {} {}", request.method, request.endpoint);
     log::debug!("[Command] Request details: method={}, endpoint={}, has_headers={}, has_params={}", 
         request.method, 
@@ -25,6 +81,7 @@ pub async fn execute_http_request(request: ApiRequest) -> Result<ApiResponse, St
         !request.parameters.is_null());
     
     let start = std::time::Instant::now();
+    let history_request = request.clone();
     
     // Wrap blocking HTTP client in spawn_blocking to avoid tokio runtime conflicts
     // Blocking client needs to be created and dropped in blocking thread pool
@@ -37,7 +94,7 @@ pub async fn execute_http_request(request: ApiRequest) -> Result<ApiResponse, St
     .map_err(|e| {
         let error = format!("Failed to execute request in blocking thread: {}", e);
         log::error!("[Command] Async runtime error: {}", error);
-        error
+        AppError::Internal(error)
     })?;
     
     let duration = start.elapsed();
@@ -50,54 +107,310 @@ pub async fn execute_http_request(request: ApiRequest) -> Result<ApiResponse, St
             log::error!("[Command] Request failed after {}ms: {}", duration.as_millis(), e);
         }
     }
+
+    // Optional check against the linked endpoint's scanned response schema
+    let mut result = result;
+    if let (true, Ok(response), Some(endpoint_id)) = (validate_schema, result.as_mut(), history_request.endpoint_id.as_deref()) {
+        match database::get_endpoint(endpoint_id) {
+            Ok(endpoint) => {
+                let validation = scanner::schema_validation::validate_endpoint_response(&endpoint, response.status, &response.data);
+                if !validation.valid {
+                    log::info!("[Command] Response deviates from schema in {} place(s)", validation.deviations.len());
+                }
+                response.schema_validation = Some(validation);
+            }
+            Err(e) => log::warn!("[Command] Schema validation skipped: {}", e),
+        }
+    }
+
+    // Persist to history; a storage failure should not fail the request itself
+    let entry = RequestHistoryEntry {
+        id: Uuid::new_v4().to_string(),
+        endpoint_id: history_request.endpoint_id,
+        method: history_request.method,
+        url: history_request.endpoint,
+        headers: history_request.headers.unwrap_or_default(),
+        body: history_request.parameters,
+        status: result.as_ref().ok().map(|r| r.status),
+        duration_ms: duration.as_millis() as u64,
+        error: result.as_ref().err().map(|e| e.to_string()),
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    if let Err(e) = database::save_request_history_entry(&entry) {
+        log::warn!("[Command] Failed to save request history: {}", e);
+    }
     
     result
 }
 
+#[tauri::command]
+pub async fn get_request_history(
+    endpoint_id: Option<String>,
+    limit: usize,
+) -> Result<Vec<RequestHistoryEntry>, AppError> {
+    database::get_request_history(endpoint_id.as_deref(), limit)
+}
+
+#[tauri::command]
+pub async fn clear_request_history(endpoint_id: Option<String>) -> Result<(), AppError> {
+    database::clear_request_history(endpoint_id.as_deref())
+}
+
+#[tauri::command]
+pub async fn create_saved_request(input: CreateSavedRequestInput) -> Result<SavedRequest, AppError> {
+    let now = chrono::Utc::now().timestamp();
+    let saved = SavedRequest {
+        id: Uuid::new_v4().to_string(),
+        project_id: input.project_id,
+        endpoint_id: input.endpoint_id,
+        name: input.name,
+        method: input.method.to_uppercase(),
+        url: input.url,
+        headers: input.headers.unwrap_or_default(),
+        params: input.params.unwrap_or(serde_json::Value::Null),
+        body: input.body.unwrap_or(serde_json::Value::Null),
+        created_at: now,
+        updated_at: now,
+    };
+
+    database::save_saved_request(&saved)?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn get_saved_requests(project_id: Option<String>) -> Result<Vec<SavedRequest>, AppError> {
+    database::get_saved_requests(project_id.as_deref())
+}
+
+#[tauri::command]
+pub async fn update_saved_request(input: UpdateSavedRequestInput) -> Result<SavedRequest, AppError> {
+    let existing = database::get_saved_request(&input.id)?
+        .ok_or_else(|| AppError::NotFound(format!("Saved request not found: {}", input.id)))?;
+
+    let updated = SavedRequest {
+        name: input.name.unwrap_or(existing.name),
+        method: input.method.map(|m| m.to_uppercase()).unwrap_or(existing.method),
+        url: input.url.unwrap_or(existing.url),
+        headers: input.headers.unwrap_or(existing.headers),
+        params: input.params.unwrap_or(existing.params),
+        body: input.body.unwrap_or(existing.body),
+        updated_at: chrono::Utc::now().timestamp(),
+        ..existing
+    };
+
+    database::save_saved_request(&updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_saved_request(id: String) -> Result<(), AppError> {
+    database::delete_saved_request(&id)
+}
+
+/// curl for a request. By default `{{ }}` placeholders are kept verbatim; with `resolved` they
+/// are filled from the project, the scenario (when `scenario_id` is given) and `variables`, in
+/// increasing precedence, and the linked endpoint's auth header is added.
 #[tauri::command]
 pub async fn generate_curl_command(
     url: String,
     method: String,
-    body: Option<serde_json::Value>
-) -> Result<String, String> {
-    Ok(http_client::generate_curl(&url, &method, body.as_ref()))
+    body: Option<serde_json::Value>,
+    headers: Option<HashMap<String, String>>,
+    resolved: Option<bool>,
+    variables: Option<HashMap<String, serde_json::Value>>,
+    scenario_id: Option<String>,
+    endpoint_id: Option<String>,
+) -> Result<String, AppError> {
+    let headers = headers.unwrap_or_default();
+    if !resolved.unwrap_or(false) {
+        let mut headers: Vec<(String, String)> = headers.into_iter().collect();
+        headers.sort();
+        return Ok(http_client::generate_curl_with_headers(&url, &method, &headers, body.as_ref()));
+    }
+
+    let endpoint = match endpoint_id.as_deref() {
+        Some(id) => Some(database::get_endpoint(id)?),
+        None => None,
+    };
+    let scenario = match scenario_id.as_deref() {
+        Some(id) => Some(
+            database::get_test_scenario(id)?.ok_or_else(|| AppError::NotFound(format!("Scenario not found: {}", id)))?,
+        ),
+        None => None,
+    };
+
+    let project_id = scenario
+        .as_ref()
+        .map(|s| s.project_id.clone())
+        .or_else(|| endpoint.as_ref().and_then(|e| e.project_id.clone()));
+    let mut all_variables = HashMap::new();
+    if let Some(project) = project_id.as_deref().map(database::get_project).transpose()?.flatten() {
+        if let Some(base_url) = project.base_url {
+            all_variables.insert("baseUrl".to_string(), serde_json::Value::String(base_url));
+        }
+        all_variables.extend(project.variables);
+    }
+    if let Some(obj) = scenario.as_ref().and_then(|s| s.variables.as_object()) {
+        all_variables.extend(obj.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    all_variables.extend(variables.unwrap_or_default());
+
+    Ok(http_client::generate_resolved_curl(
+        &url,
+        &method,
+        &headers,
+        body.as_ref(),
+        &all_variables,
+        endpoint.as_ref(),
+    ))
+}
+
+/// Create an endpoint from a curl command line
+#[tauri::command]
+pub async fn import_curl(project_id: String, curl_command: String) -> Result<ApiEndpoint, AppError> {
+    let parsed = http_client::parse_curl(&curl_command).map_err(AppError::Validation)?;
+
+    // Tolerate scheme-less URLs like `curl localhost:3000/users`
+    let url_str = if parsed.url.contains("://") {
+        parsed.url.clone()
+    } else {
+        format!("http://{}", parsed.url)
+    };
+    let url = reqwest::Url::parse(&url_str)
+        .map_err(|e| AppError::Validation(format!("Invalid URL in curl command: {}", e)))?;
+
+    let path = url.path().to_string();
+    let parameters = http_client::curl_parameters(&parsed, &url);
+    let version = scanner::parsers::version_from_path(&path);
+    let category = path
+        .split('/')
+        .find(|s| !s.is_empty())
+        .unwrap_or("api")
+        .to_string();
+
+    let endpoint = ApiEndpoint {
+        id: format!("{}-{}-{}",
+            project_id,
+            parsed.method,
+            path.replace('/', "-").replace(['{', '}'], "")
+        ),
+        project_id: Some(project_id),
+        name: format!("{} {}", parsed.method, path),
+        method: parsed.method.clone(),
+        path,
+        service: url.host_str().unwrap_or("imported").to_string(),
+        description: "Imported from curl".to_string(),
+        parameters,
+        category,
+        explanation: None,
+        responses: None,
+        version,
+        user_modified: false,
+        stale: false,
+        auth_required: false,
+        auth_type: None,
+        warnings: Vec::new(),
+    };
+
+    database::save_endpoint(endpoint.clone())
+        .map_err(|e| e.with_context("Failed to save endpoint"))?;
+    log::info!("[Command] Imported endpoint from curl: {}", endpoint.name);
+
+    Ok(endpoint)
 }
 
+/// Create endpoints from an OpenAPI 3 or Swagger 2 document (JSON or YAML)
 #[tauri::command]
-pub async fn get_all_endpoints() -> Result<Vec<ApiEndpoint>, String> {
+pub async fn import_openapi(project_id: String, content: String) -> Result<Vec<ApiEndpoint>, AppError> {
+    let endpoints = scanner::openapi::endpoints_from_openapi(&content, &project_id).map_err(AppError::Parse)?;
+
+    for endpoint in &endpoints {
+        database::save_endpoint(endpoint.clone())
+            .map_err(|e| e.with_context("Failed to save endpoint"))?;
+    }
+    log::info!("[Command] Imported {} endpoints from OpenAPI document", endpoints.len());
+
+    Ok(endpoints)
+}
+
+/// Probe a base URL without source: discovery paths, OpenAPI document (importable with
+/// import_openapi) and framework hints. TLS is verified unless `verify_tls` is false.
+#[tauri::command]
+pub async fn probe_remote_api(base_url: String, verify_tls: Option<bool>) -> Result<crate::remote_probe::RemoteProbe, AppError> {
+    let base_url = http_client::normalize_base_url(&base_url).map_err(AppError::Validation)?;
+    log::info!("[Command] probe_remote_api: {}", base_url);
+
+    let probe = crate::remote_probe::probe(&base_url, verify_tls.unwrap_or(true)).await;
+    if !probe.reachable {
+        return Err(AppError::Validation(format!("No response from {}", base_url)));
+    }
+    Ok(probe)
+}
+
+#[tauri::command]
+pub async fn get_all_endpoints() -> Result<Vec<ApiEndpoint>, AppError> {
     database::get_all_endpoints()
 }
 
 #[tauri::command]
-pub async fn save_endpoint(endpoint: ApiEndpoint) -> Result<(), String> {
+pub async fn save_endpoint(mut endpoint: ApiEndpoint) -> Result<(), AppError> {
+    // Saved from the editor: later scans must not overwrite it
+    endpoint.user_modified = true;
     database::save_endpoint(endpoint)
 }
 
+/// Delete several endpoints at once; returns how many were removed
+#[tauri::command]
+pub async fn delete_endpoints(ids: Vec<String>) -> Result<usize, AppError> {
+    database::delete_endpoints(&ids)
+}
+
+/// Set the category of several endpoints at once; returns how many were updated
+#[tauri::command]
+pub async fn update_endpoints_category(ids: Vec<String>, category: String) -> Result<usize, AppError> {
+    let category = category.trim();
+    if category.is_empty() {
+        return Err(AppError::Validation("Category must not be empty".to_string()));
+    }
+    database::update_endpoints_category(&ids, category)
+}
+
+/// Set the service of several endpoints at once; returns how many were updated
 #[tauri::command]
-pub async fn get_all_test_suites() -> Result<Vec<TestSuite>, String> {
+pub async fn update_endpoints_service(ids: Vec<String>, service: String) -> Result<usize, AppError> {
+    let service = service.trim();
+    if service.is_empty() {
+        return Err(AppError::Validation("Service must not be empty".to_string()));
+    }
+    database::update_endpoints_service(&ids, service)
+}
+
+#[tauri::command]
+pub async fn get_all_test_suites() -> Result<Vec<TestSuite>, AppError> {
     database::get_all_test_suites()
 }
 
+/// Run a query from the SQL console; writes are rejected unless `read_only` is false
 #[tauri::command]
-pub async fn execute_sql_query(db_path: String, query: String) -> Result<QueryResult, String> {
-    database::execute_sql_query(db_path, query)
+pub async fn execute_sql_query(db_path: String, query: String, read_only: Option<bool>) -> Result<QueryResult, AppError> {
+    database::execute_sql_query(db_path, query, read_only.unwrap_or(true))
 }
 
 #[tauri::command]
-pub async fn export_response(filename: String, content: String) -> Result<String, String> {
+pub async fn export_response(filename: String, content: String) -> Result<String, AppError> {
     use std::fs;
     let downloads = dirs::download_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
     let path = downloads.join(filename);
 
     fs::write(&path, content)
-        .map_err(|e| format!("Failed to save file: {}", e))?;
+        .map_err(|e| AppError::Io(format!("Failed to save file: {}", e)))?;
 
     Ok(path.to_string_lossy().to_string())
 }
 
 // Project management commands
 #[tauri::command]
-pub async fn open_folder_dialog(app: tauri::AppHandle) -> Result<Option<String>, String> {
+pub async fn open_folder_dialog(app: tauri::AppHandle) -> Result<Option<String>, AppError> {
     let (tx, rx) = std::sync::mpsc::channel();
     
     app.dialog()
@@ -107,11 +420,11 @@ pub async fn open_folder_dialog(app: tauri::AppHandle) -> Result<Option<String>,
         });
     
     rx.recv()
-        .map_err(|e| format!("Failed to receive folder path: {}", e))
+        .map_err(|e| AppError::Internal(format!("Failed to receive folder path: {}", e)))
 }
 
 #[tauri::command]
-pub async fn create_project(path: String) -> Result<Project, String> {
+pub async fn create_project(path: String) -> Result<Project, AppError> {
     let path_buf = PathBuf::from(&path);
     
     // Extract project name from path
@@ -128,149 +441,254 @@ pub async fn create_project(path: String) -> Result<Project, String> {
         created_at: chrono::Utc::now().timestamp(),
         last_scanned: None,
         base_url: None,
+        verify_tls: true,
+        user_agent: None,
+        default_headers: HashMap::new(),
+        request_logging: false,
+        services: Vec::new(),
+        request_signing: None,
+        variables: HashMap::new(),
+        idempotency: None,
+        body_limits: BodyLimits::default(),
     };
     
     database::save_project(project.clone())
-        .map_err(|e| format!("Failed to save project: {}", e))?;
+        .map_err(|e| e.with_context("Failed to save project"))?;
     
     Ok(project)
 }
 
 #[tauri::command]
-pub async fn get_all_projects() -> Result<Vec<Project>, String> {
+pub async fn get_all_projects() -> Result<Vec<Project>, AppError> {
     database::get_all_projects()
 }
 
 #[tauri::command]
-pub async fn delete_project(project_id: String) -> Result<(), String> {
+pub async fn delete_project(project_id: String) -> Result<(), AppError> {
     database::delete_project(project_id)
 }
 
 #[tauri::command]
-pub async fn update_project_base_url(project_id: String, base_url: Option<String>) -> Result<(), String> {
+pub async fn update_project_base_url(project_id: String, base_url: Option<String>) -> Result<(), AppError> {
+    let base_url = base_url
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| http_client::normalize_base_url(&url))
+        .transpose()
+        .map_err(AppError::Validation)?;
     database::update_project_base_url(&project_id, base_url)
 }
 
 #[tauri::command]
-pub async fn get_active_project() -> Result<Option<Project>, String> {
+pub async fn update_project_verify_tls(project_id: String, verify_tls: bool) -> Result<(), AppError> {
+    database::update_project_verify_tls(&project_id, verify_tls)
+}
+
+/// Set the User-Agent and always-sent headers for a project's test traffic
+#[tauri::command]
+pub async fn update_project_client_defaults(
+    project_id: String,
+    user_agent: Option<String>,
+    default_headers: HashMap<String, String>,
+) -> Result<(), AppError> {
+    let user_agent = user_agent.filter(|ua| !ua.trim().is_empty());
+    database::update_project_client_defaults(&project_id, user_agent.as_deref(), &default_headers)
+}
+
+/// Turn per-project request/response capture for scenario runs on or off
+#[tauri::command]
+pub async fn update_project_request_logging(project_id: String, enabled: bool) -> Result<(), AppError> {
+    database::update_project_request_logging(&project_id, enabled)
+}
+
+/// Set or clear the HMAC signing applied to a project's scenario requests
+#[tauri::command]
+pub async fn update_project_request_signing(
+    project_id: String,
+    signing: Option<crate::request_signing::RequestSigningConfig>,
+) -> Result<(), AppError> {
+    let mut signing = signing;
+    if let Some(signing) = &mut signing {
+        // Responses only ever carry the masked secret; getting it back means "unchanged"
+        if signing.secret == crate::request_signing::MASKED_SECRET {
+            signing.secret = database::get_project(&project_id)?
+                .and_then(|p| p.request_signing)
+                .map(|stored| stored.secret)
+                .ok_or_else(|| AppError::Validation("Missing signing secret".to_string()))?;
+        }
+        signing.validate().map_err(AppError::Validation)?;
+    }
+    database::update_project_request_signing(&project_id, signing.as_ref())
+}
+
+/// Set or clear the auto-generated idempotency key header for a project's scenario requests
+#[tauri::command]
+pub async fn update_project_idempotency(
+    project_id: String,
+    idempotency: Option<IdempotencyConfig>,
+) -> Result<(), AppError> {
+    if let Some(idempotency) = &idempotency {
+        idempotency.validate().map_err(AppError::Validation)?;
+    }
+    database::update_project_idempotency(&project_id, idempotency.as_ref())
+}
+
+/// Set how much of each request/response body a project's scenario runs read and store
+#[tauri::command]
+pub async fn update_project_body_limits(project_id: String, body_limits: BodyLimits) -> Result<(), AppError> {
+    body_limits.validate().map_err(AppError::Validation)?;
+    database::update_project_body_limits(&project_id, &body_limits)
+}
+
+/// Replace the project-wide variables available to every scenario of the project
+#[tauri::command]
+pub async fn update_project_variables(
+    project_id: String,
+    variables: HashMap<String, serde_json::Value>,
+) -> Result<(), AppError> {
+    database::update_project_variables(&project_id, &variables)
+}
+
+/// Merge variables from a `.env` file or JSON object into the project's variables
+#[tauri::command]
+pub async fn import_variables(
+    project_id: String,
+    content: String,
+    format: String,
+) -> Result<scenario::variable_import::VariableImportResult, AppError> {
+    let parsed = scenario::variable_import::parse_variables(&content, &format)
+        .map_err(AppError::Validation)?;
+    let mut project = database::get_project(&project_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", project_id)))?;
+
+    let imported_count = parsed.variables.len();
+    project.variables.extend(parsed.variables);
+    database::update_project_variables(&project_id, &project.variables)?;
+    log::info!("[Command] Imported {} variable(s) into project {} ({} skipped)",
+        imported_count, project_id, parsed.skipped.len());
+
+    Ok(scenario::variable_import::VariableImportResult {
+        imported_count,
+        skipped: parsed.skipped,
+    })
+}
+
+/// Replace the named service base URLs request steps can target with `service`
+#[tauri::command]
+pub async fn update_project_services(project_id: String, services: Vec<ProjectService>) -> Result<(), AppError> {
+    let mut seen = std::collections::HashSet::new();
+    for service in &services {
+        let name = service.name.trim();
+        if name.is_empty() || service.base_url.trim().is_empty() {
+            return Err(AppError::Validation("Service name and base URL are required".to_string()));
+        }
+        if !seen.insert(name.to_string()) {
+            return Err(AppError::Validation(format!("Duplicate service name: {}", name)));
+        }
+    }
+    let services: Vec<ProjectService> = services
+        .into_iter()
+        .map(|s| {
+            Ok(ProjectService {
+                name: s.name.trim().to_string(),
+                base_url: http_client::normalize_base_url(&s.base_url)?,
+            })
+        })
+        .collect::<Result<_, String>>()
+        .map_err(AppError::Validation)?;
+    database::save_project_services(&project_id, &services)
+}
+
+/// Get the most recent lines of a project's request log (default 200)
+#[tauri::command]
+pub async fn get_project_request_log(project_id: String, lines: Option<usize>) -> Result<Vec<String>, AppError> {
+    let lines = lines.unwrap_or(200).clamp(1, 5000);
+    request_log::RequestLogger::for_project(&project_id).tail(lines).map_err(AppError::Io)
+}
+
+/// Get aggregated counts for the project dashboard
+#[tauri::command]
+pub async fn get_project_stats(project_id: String) -> Result<ProjectStats, AppError> {
+    database::get_project_stats(&project_id)
+}
+
+#[tauri::command]
+pub async fn get_active_project() -> Result<Option<Project>, AppError> {
     database::get_active_project()
 }
 
 #[tauri::command]
-pub async fn set_active_project(project_id: Option<String>) -> Result<(), String> {
+pub async fn set_active_project(project_id: Option<String>) -> Result<(), AppError> {
     database::set_active_project(project_id.as_deref())
 }
 
 #[tauri::command]
-pub async fn ensure_project_exists(project: Project) -> Result<(), String> {
+pub async fn ensure_project_exists(mut project: Project) -> Result<(), AppError> {
     // Check if project exists
     match database::get_project(&project.id)? {
         Some(_) => Ok(()), // Project already exists
         None => {
             // Project doesn't exist, save it
+            project.base_url = project.base_url
+                .filter(|url| !url.trim().is_empty())
+                .map(|url| http_client::normalize_base_url(&url))
+                .transpose()
+                .map_err(AppError::Validation)?;
             database::save_project(project)
-                .map_err(|e| format!("Failed to save project: {}", e))?;
+                .map_err(|e| e.with_context("Failed to save project"))?;
             Ok(())
         }
     }
 }
 
 #[tauri::command]
-pub async fn get_endpoints_by_project(project_id: String) -> Result<Vec<ApiEndpoint>, String> {
+pub async fn get_endpoints_by_project(project_id: String) -> Result<Vec<ApiEndpoint>, AppError> {
     database::get_endpoints_by_project(project_id)
 }
 
 #[tauri::command]
-pub async fn scan_project(project_id: String, project_path: String) -> Result<Vec<ApiEndpoint>, String> {
+pub async fn get_endpoints_by_version(project_id: String, version: String) -> Result<Vec<ApiEndpoint>, AppError> {
+    database::get_endpoints_by_version(&project_id, &version)
+}
+
+#[tauri::command]
+pub async fn scan_project(
+    project_id: String,
+    project_path: String,
+    max_file_size: Option<u64>,
+    dedupe: Option<scanner::DedupeKeyOptions>,
+    workers: Option<usize>,
+) -> Result<ProjectScanResult, AppError> {
     let path = PathBuf::from(&project_path);
-    
-    // Clear existing endpoints for this project before scanning
-    database::clear_project_endpoints(&project_id)
-        .map_err(|e| format!("Failed to clear old endpoints: {}", e))?;
-    
+
     // Perform scan
-    let scanner = scanner::UnifiedScanner::new(path.clone());
+    let mut scanner = scanner::UnifiedScanner::new(path.clone());
+    if let Some(max_file_size) = max_file_size {
+        scanner = scanner.with_max_file_size(max_file_size);
+    }
+    if let Some(dedupe) = dedupe {
+        scanner = scanner.with_dedupe(dedupe);
+    }
+    if let Some(workers) = workers {
+        scanner = scanner.with_workers(workers);
+    }
     let scan_result = scanner.scan().await
-        .map_err(|e| format!("Scan failed: {}", e))?;
-
-    // Convert ScannedEndpoint to ApiEndpoint
-    let service_detector = scanner::ServiceDetector::new(
-        path.clone(),
-        Some(scan_result.framework_info.clone()),
-    );
-
-    let mut api_endpoints = Vec::new();
-    
-    for scanned_endpoint in scan_result.endpoints {
-        let file_path = PathBuf::from(&scanned_endpoint.file_path);
-        let service = service_detector.detect_service_from_path(&file_path);
-        
-        // Generate ID from project_id, method and path
-        let id = format!("{}-{}-{}", 
-            project_id,
-            scanned_endpoint.method.to_uppercase(),
-            scanned_endpoint.path.replace('/', "-").replace('{', "").replace('}', "")
-        );
-        
-        // Convert parameters
-        let parameters: Vec<ApiParameter> = scanned_endpoint.parameters
-            .into_iter()
-            .map(|p| ApiParameter {
-                name: p.name,
-                param_type: p.param_type,
-                required: p.required,
-                description: String::new(),
-                example: p.example,
-                default_value: p.default_value,
-            })
-            .collect();
-
-        // Convert responses
-        let responses: Vec<ApiResponseDefinition> = scanned_endpoint.responses
-            .into_iter()
-            .map(|r| ApiResponseDefinition {
-                status_code: r.status_code,
-                description: r.description,
-                content_type: r.content_type,
-                schema: r.schema.map(|s| serde_json::to_value(&s).unwrap_or_default()),
-                example: r.example,
-            })
-            .collect();
+        .map_err(|e| AppError::Io(format!("Scan failed: {}", e)))?;
+    for warning in &scan_result.warnings {
+        log::warn!("[Command] scan_project: {}", warning);
+    }
+    let warnings = scan_result.warnings.clone();
 
-        // Generate category from path
-        let category = scanned_endpoint.path
-            .split('/')
-            .filter(|s| !s.is_empty())
-            .next()
-            .unwrap_or("api")
-            .to_string();
-
-        let api_endpoint = ApiEndpoint {
-            id,
-            project_id: Some(project_id.clone()),
-            name: format!("{} {}", scanned_endpoint.method, scanned_endpoint.path),
-            method: scanned_endpoint.method,
-            path: scanned_endpoint.path,
-            service,
-            description: scanned_endpoint.business_logic.description,
-            parameters,
-            category,
-            explanation: Some(scanned_endpoint.business_logic.summary),
-            responses: Some(responses),
-        };
+    let api_endpoints = scanner::api_endpoints_from_scan(&project_id, &path, scan_result);
 
-        // Save to database
-        database::save_endpoint(api_endpoint.clone())
-            .map_err(|e| format!("Failed to save endpoint: {}", e))?;
+    // Merge into the stored endpoints so manual edits survive the re-scan
+    let api_endpoints = database::save_scanned_endpoints(&project_id, api_endpoints)
+        .map_err(|e| e.with_context("Failed to save endpoints"))?;
 
-        api_endpoints.push(api_endpoint);
-    }
-    
     // Update last_scanned timestamp
     database::update_project_last_scanned(&project_id)
-        .map_err(|e| format!("Failed to update project timestamp: {}", e))?;
+        .map_err(|e| e.with_context("Failed to update project timestamp"))?;
 
-    Ok(api_endpoints)
+    Ok(ProjectScanResult { endpoints: api_endpoints, warnings })
 }
 
 // Security testing commands
@@ -280,7 +698,7 @@ pub async fn create_security_test_case(
     name: String,
     endpoint_id: Option<String>,
     scans: Vec<security::types::ScanConfig>,
-) -> Result<security::types::SecurityTestCase, String> {
+) -> Result<security::types::SecurityTestCase, AppError> {
     let now = chrono::Utc::now().timestamp();
     let test_case = security::types::SecurityTestCase {
         id: Uuid::new_v4().to_string(),
@@ -299,12 +717,12 @@ pub async fn create_security_test_case(
 #[tauri::command]
 pub async fn get_security_test_cases(
     project_id: String,
-) -> Result<Vec<security::types::SecurityTestCase>, String> {
+) -> Result<Vec<security::types::SecurityTestCase>, AppError> {
     database::get_security_test_cases_by_project(&project_id)
 }
 
 #[tauri::command]
-pub async fn delete_security_test_case(id: String) -> Result<(), String> {
+pub async fn delete_security_test_case(id: String) -> Result<(), AppError> {
     database::delete_security_test_case(&id)
 }
 
@@ -315,8 +733,18 @@ pub async fn run_security_test(
     method: String,
     params: HashMap<String, serde_json::Value>,
     headers: HashMap<String, String>,
-) -> Result<security::types::SecurityTestRun, String> {
-    let run = security::scanner::run_security_test(&test_case, &url, &method, &params, &headers);
+) -> Result<security::types::SecurityTestRun, AppError> {
+    // Parameter fuzzing derives its inputs from the linked endpoint's parameter metadata
+    let parameters = match &test_case.endpoint_id {
+        Some(endpoint_id) => database::get_endpoint(endpoint_id)
+            .map(|endpoint| endpoint.parameters)
+            .unwrap_or_else(|e| {
+                log::warn!("[Command] No parameter metadata for security test: {}", e);
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+    let run = security::scanner::run_security_test(&test_case, &url, &method, &params, &headers, &parameters);
     database::save_security_test_run(&run)?;
     Ok(run)
 }
@@ -324,7 +752,7 @@ pub async fn run_security_test(
 #[tauri::command]
 pub async fn get_security_test_runs(
     test_case_id: String,
-) -> Result<Vec<security::types::SecurityTestRun>, String> {
+) -> Result<Vec<security::types::SecurityTestRun>, AppError> {
     database::get_security_test_runs(&test_case_id)
 }
 
@@ -338,7 +766,7 @@ pub async fn create_test_scenario(
     name: String,
     description: Option<String>,
     priority: Option<String>,
-) -> Result<scenario::types::TestScenario, String> {
+) -> Result<scenario::types::TestScenario, AppError> {
     let now = chrono::Utc::now().timestamp();
     let scenario = scenario::types::TestScenario {
         id: Uuid::new_v4().to_string(),
@@ -349,6 +777,9 @@ pub async fn create_test_scenario(
         variables: serde_json::json!({}),
         pre_script: None,
         post_script: None,
+        chaos: None,
+        session_login: None,
+        environments: Default::default(),
         created_at: now,
         updated_at: now,
     };
@@ -360,23 +791,23 @@ pub async fn create_test_scenario(
 #[tauri::command]
 pub async fn get_test_scenarios(
     project_id: String,
-) -> Result<Vec<scenario::types::TestScenario>, String> {
+) -> Result<Vec<scenario::types::TestScenario>, AppError> {
     database::get_test_scenarios_by_project(&project_id)
 }
 
 #[tauri::command]
 pub async fn get_test_scenario(
     scenario_id: String,
-) -> Result<Option<scenario::types::TestScenario>, String> {
+) -> Result<Option<scenario::types::TestScenario>, AppError> {
     database::get_test_scenario(&scenario_id)
 }
 
 #[tauri::command]
 pub async fn update_test_scenario(
     request: scenario::types::UpdateScenarioRequest,
-) -> Result<scenario::types::TestScenario, String> {
+) -> Result<scenario::types::TestScenario, AppError> {
     let existing = database::get_test_scenario(&request.id)?
-        .ok_or_else(|| "Scenario not found".to_string())?;
+        .ok_or_else(|| AppError::NotFound("Scenario not found".to_string()))?;
 
     let now = chrono::Utc::now().timestamp();
     let updated = scenario::types::TestScenario {
@@ -388,6 +819,9 @@ pub async fn update_test_scenario(
         variables: request.variables.unwrap_or(existing.variables),
         pre_script: request.pre_script.or(existing.pre_script),
         post_script: request.post_script.or(existing.post_script),
+        chaos: request.chaos.or(existing.chaos),
+        session_login: request.session_login.or(existing.session_login),
+        environments: request.environments.unwrap_or(existing.environments),
         created_at: existing.created_at,
         updated_at: now,
     };
@@ -397,17 +831,50 @@ pub async fn update_test_scenario(
 }
 
 #[tauri::command]
-pub async fn delete_test_scenario(scenario_id: String) -> Result<(), String> {
+pub async fn delete_test_scenario(scenario_id: String) -> Result<(), AppError> {
+    if scenario::schedule::stop_task(&scenario_id) {
+        log::info!("[Command] Stopped schedule of deleted scenario {}", scenario_id);
+    }
     database::delete_test_scenario(&scenario_id)
 }
 
+/// Add auth header templates (e.g. `Bearer {{ authToken }}`) to a new request step whose
+/// endpoint requires auth, unless the step sets `skipAuth` or already has the header
+fn scaffold_endpoint_auth(config: &mut serde_json::Value) {
+    if config.get("skipAuth").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return;
+    }
+    let endpoint = match config.get("endpointId").and_then(|v| v.as_str()).map(database::get_endpoint) {
+        Some(Ok(endpoint)) => endpoint,
+        _ => return,
+    };
+    let existing: HashMap<String, String> = config.get("headers")
+        .and_then(|h| serde_json::from_value(h.clone()).ok())
+        .unwrap_or_default();
+    let missing = http_client::missing_auth_headers(&endpoint, &existing);
+    if missing.is_empty() {
+        return;
+    }
+    let mut headers = existing;
+    headers.extend(missing);
+    config["headers"] = serde_json::json!(headers);
+}
+
 #[tauri::command]
 pub async fn add_test_scenario_step(
     request: scenario::types::CreateStepRequest,
-) -> Result<scenario::types::TestScenarioStep, String> {
+) -> Result<scenario::types::TestScenarioStep, AppError> {
     // Get existing steps to determine order
     let existing_steps = database::get_test_scenario_steps(&request.scenario_id)?;
     let max_order = existing_steps.iter().map(|s| s.step_order).max().unwrap_or(-1);
+    scenario::types::validate_unique_step_names(
+        existing_steps.iter().map(|s| s.name.as_str()).chain(std::iter::once(request.name.as_str())),
+    ).map_err(AppError::Validation)?;
+
+    let mut config = request.config;
+    if request.step_type == scenario::types::TestStepType::Request {
+        scaffold_endpoint_auth(&mut config);
+    }
 
     let step = scenario::types::TestScenarioStep {
         id: Uuid::new_v4().to_string(),
@@ -415,8 +882,10 @@ pub async fn add_test_scenario_step(
         step_order: max_order + 1,
         step_type: request.step_type,
         name: request.name,
-        config: request.config,
+        config,
         enabled: true,
+        enabled_if: None,
+        group: None,
     };
 
     database::save_test_scenario_step(step.clone())?;
@@ -426,17 +895,17 @@ pub async fn add_test_scenario_step(
 #[tauri::command]
 pub async fn get_test_scenario_steps(
     scenario_id: String,
-) -> Result<Vec<scenario::types::TestScenarioStep>, String> {
+) -> Result<Vec<scenario::types::TestScenarioStep>, AppError> {
     database::get_test_scenario_steps(&scenario_id)
 }
 
 #[tauri::command]
 pub async fn update_test_scenario_step(
     request: scenario::types::UpdateStepRequest,
-) -> Result<scenario::types::TestScenarioStep, String> {
+) -> Result<scenario::types::TestScenarioStep, AppError> {
     // Get the step directly by ID
     let existing = database::get_test_scenario_step_by_id(&request.id)?
-        .ok_or_else(|| "Step not found".to_string())?;
+        .ok_or_else(|| AppError::NotFound("Step not found".to_string()))?;
 
     let updated = scenario::types::TestScenarioStep {
         id: existing.id,
@@ -446,36 +915,317 @@ pub async fn update_test_scenario_step(
         name: request.name.unwrap_or(existing.name),
         config: request.config.unwrap_or(existing.config),
         enabled: request.enabled.unwrap_or(existing.enabled),
+        enabled_if: match request.enabled_if {
+            Some(expr) if expr.trim().is_empty() => None,
+            Some(expr) => Some(expr),
+            None => existing.enabled_if,
+        },
+        group: match request.group {
+            Some(group) if group.trim().is_empty() => None,
+            Some(group) => Some(group),
+            None => existing.group,
+        },
     };
 
+    let siblings = database::get_test_scenario_steps(&updated.scenario_id)?;
+    scenario::types::validate_unique_step_names(
+        siblings.iter()
+            .filter(|s| s.id != updated.id)
+            .map(|s| s.name.as_str())
+            .chain(std::iter::once(updated.name.as_str())),
+    ).map_err(AppError::Validation)?;
+
     database::save_test_scenario_step(updated.clone())?;
     Ok(updated)
 }
 
 #[tauri::command]
-pub async fn delete_test_scenario_step(step_id: String) -> Result<(), String> {
+pub async fn delete_test_scenario_step(step_id: String) -> Result<(), AppError> {
     database::delete_test_scenario_step(&step_id)
 }
 
 #[tauri::command]
 pub async fn reorder_test_scenario_steps(
     request: scenario::types::ReorderStepsRequest,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     database::reorder_test_scenario_steps(&request.scenario_id, &request.step_ids)
 }
 
+#[tauri::command]
+pub async fn set_test_scenario_steps_enabled(
+    request: scenario::types::SetStepsEnabledRequest,
+) -> Result<(), AppError> {
+    database::set_test_scenario_steps_enabled(&request.scenario_id, &request.step_ids, request.enabled)
+}
+
+#[tauri::command]
+pub async fn create_assertion_template(
+    request: scenario::types::CreateAssertionTemplateRequest,
+) -> Result<scenario::types::AssertionTemplate, AppError> {
+    let existing = database::get_assertion_templates(&request.project_id)?;
+    if existing.iter().any(|t| t.name == request.name) {
+        return Err(AppError::Validation(format!("Assertion template already exists: {}", request.name)));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let template = scenario::types::AssertionTemplate {
+        id: Uuid::new_v4().to_string(),
+        project_id: request.project_id,
+        name: request.name,
+        description: request.description,
+        assertions: request.assertions,
+        parameters: request.parameters.unwrap_or_default(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    database::save_assertion_template(&template)?;
+    Ok(template)
+}
+
+#[tauri::command]
+pub async fn get_assertion_templates(
+    project_id: String,
+) -> Result<Vec<scenario::types::AssertionTemplate>, AppError> {
+    database::get_assertion_templates(&project_id)
+}
+
+#[tauri::command]
+pub async fn update_assertion_template(
+    request: scenario::types::UpdateAssertionTemplateRequest,
+) -> Result<scenario::types::AssertionTemplate, AppError> {
+    let existing = database::get_assertion_template(&request.id)?
+        .ok_or_else(|| AppError::NotFound(format!("Assertion template not found: {}", request.id)))?;
+
+    if let Some(name) = &request.name {
+        let siblings = database::get_assertion_templates(&existing.project_id)?;
+        if siblings.iter().any(|t| &t.name == name && t.id != existing.id) {
+            return Err(AppError::Validation(format!("Assertion template already exists: {}", name)));
+        }
+    }
+
+    let updated = scenario::types::AssertionTemplate {
+        name: request.name.unwrap_or(existing.name),
+        description: request.description.or(existing.description),
+        assertions: request.assertions.unwrap_or(existing.assertions),
+        parameters: request.parameters.unwrap_or(existing.parameters),
+        updated_at: chrono::Utc::now().timestamp(),
+        ..existing
+    };
+
+    database::save_assertion_template(&updated)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_assertion_template(id: String) -> Result<(), AppError> {
+    database::delete_assertion_template(&id)
+}
+
+/// Store the expected example response for an endpoint and status code
+#[tauri::command]
+pub async fn save_golden_response(
+    endpoint_id: String,
+    status_code: u16,
+    body: serde_json::Value,
+) -> Result<scenario::types::GoldenResponse, AppError> {
+    let golden = scenario::types::GoldenResponse {
+        endpoint_id,
+        status_code,
+        body,
+        updated_at: chrono::Utc::now().timestamp(),
+    };
+    database::save_golden_response(&golden)?;
+    Ok(golden)
+}
+
+#[tauri::command]
+pub async fn get_golden_responses(
+    endpoint_id: String,
+) -> Result<Vec<scenario::types::GoldenResponse>, AppError> {
+    database::get_golden_responses(&endpoint_id)
+}
+
+#[tauri::command]
+pub async fn delete_golden_response(endpoint_id: String, status_code: u16) -> Result<(), AppError> {
+    database::delete_golden_response(&endpoint_id, status_code)
+}
+
+/// Candidate assertions for an endpoint response, built from its documented schema
+#[tauri::command]
+pub async fn suggest_assertions(
+    endpoint_id: String,
+    status: u16,
+) -> Result<Vec<scenario::types::Assertion>, AppError> {
+    let endpoint = database::get_endpoint(&endpoint_id)?;
+    let schema = endpoint.responses
+        .unwrap_or_default()
+        .into_iter()
+        .find(|r| r.status_code == status)
+        .and_then(|r| r.schema)
+        .and_then(|schema| serde_json::from_value::<scanner::ResponseSchema>(schema).ok());
+    Ok(scenario::yaml::suggest_assertions_from_schema(status, schema.as_ref()))
+}
+
+/// Attach a response schema inferred from a pasted sample body to an endpoint
+#[tauri::command]
+pub async fn infer_endpoint_response_schema(
+    endpoint_id: String,
+    status_code: u16,
+    sample: String,
+) -> Result<ApiEndpoint, AppError> {
+    let schema = scanner::infer_schema_from_sample(&sample).map_err(AppError::Parse)?;
+    let example: serde_json::Value = serde_json::from_str(&sample)?;
+    let schema = serde_json::to_value(&schema)?;
+
+    let mut endpoint = database::get_endpoint(&endpoint_id)?;
+    let mut responses = endpoint.responses.take().unwrap_or_default();
+    if let Some(existing) = responses.iter_mut().find(|r| r.status_code == status_code) {
+        existing.schema = Some(schema);
+        existing.example = Some(example);
+    } else {
+        responses.push(ApiResponseDefinition {
+            status_code,
+            description: String::new(),
+            content_type: "application/json".to_string(),
+            schema: Some(schema),
+            example: Some(example),
+        });
+        responses.sort_by_key(|r| r.status_code);
+    }
+    endpoint.responses = Some(responses);
+
+    database::save_endpoint(endpoint.clone())?;
+    Ok(endpoint)
+}
+
+/// Stop a running scenario; the run is saved with status "stopped" and its partial results
+#[tauri::command]
+pub async fn stop_scenario_run(run_id: String) -> Result<(), AppError> {
+    log::info!("[Command] stop_scenario_run called for run_id: {}", run_id);
+    if scenario::executor::stop_scenario_run(&run_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!("No running scenario with run id: {}", run_id)))
+    }
+}
+
+/// Check every endpoint of a project with one example request each and store the results.
+/// Only GET/HEAD/OPTIONS are sent unless `include_unsafe_methods` is set.
+#[tauri::command]
+pub async fn get_endpoint_health(
+    project_id: String,
+    include_unsafe_methods: Option<bool>,
+) -> Result<Vec<crate::health::EndpointHealth>, AppError> {
+    let project = database::get_project(&project_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", project_id)))?;
+    let endpoints: Vec<ApiEndpoint> = database::get_endpoints_by_project(project_id.clone())?
+        .into_iter()
+        .filter(|e| !e.stale)
+        .collect();
+    log::info!("[Command] get_endpoint_health: checking {} endpoints for project {}", endpoints.len(), project_id);
+
+    let results = crate::health::check_endpoints(&project, endpoints, include_unsafe_methods.unwrap_or(false)).await;
+    database::save_endpoint_health(&project_id, &results)?;
+    Ok(results)
+}
+
+/// Stop a running health check; endpoints already checked are still returned and stored
+#[tauri::command]
+pub async fn cancel_endpoint_health(project_id: String) -> Result<(), AppError> {
+    if crate::health::cancel_health_check(&project_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!("No health check running for project: {}", project_id)))
+    }
+}
+
+/// Past health results for one endpoint, newest first
+#[tauri::command]
+pub async fn get_endpoint_health_history(
+    endpoint_id: String,
+    limit: Option<usize>,
+) -> Result<Vec<crate::health::EndpointHealth>, AppError> {
+    database::get_endpoint_health_history(&endpoint_id, limit.unwrap_or(50))
+}
+
+#[tauri::command]
+pub async fn get_assertion_snapshots(
+    scenario_id: String,
+) -> Result<Vec<scenario::types::AssertionSnapshot>, AppError> {
+    database::get_assertion_snapshots(&scenario_id)
+}
+
+#[tauri::command]
+pub async fn delete_assertion_snapshot(scenario_id: String, step_id: String, name: String) -> Result<(), AppError> {
+    database::delete_assertion_snapshot(&scenario_id, &step_id, &name)
+}
+
+/// Build status + body-diff assertions from a golden response (defaults to the first 2xx golden)
+#[tauri::command]
+pub async fn generate_golden_assertions(
+    endpoint_id: String,
+    status_code: Option<u16>,
+) -> Result<Vec<scenario::types::Assertion>, AppError> {
+    let goldens = database::get_golden_responses(&endpoint_id)?;
+    let golden = match status_code {
+        Some(code) => goldens.iter().find(|g| g.status_code == code),
+        None => goldens.iter()
+            .find(|g| (200..300).contains(&g.status_code))
+            .or_else(|| goldens.first()),
+    };
+    golden
+        .map(|g| g.to_assertions())
+        .ok_or_else(|| AppError::NotFound(format!("No golden response stored for endpoint: {}", endpoint_id)))
+}
+
+/// `options` carries the per-run overrides, e.g. `{ "dryRun": true, "maxSteps": 500 }`
 #[tauri::command]
 pub async fn run_test_scenario(
     app: tauri::AppHandle,
     scenario_id: String,
-) -> Result<scenario::types::TestScenarioRun, String> {
-    log::info!("[Command] run_test_scenario called for scenario_id: {}", scenario_id);
+    options: Option<ScenarioRunOptions>,
+) -> Result<scenario::types::TestScenarioRun, AppError> {
+    let options = ScenarioRunOptions {
+        trigger: scenario::types::RunTrigger::Manual,
+        ..options.unwrap_or_default()
+    };
+    execute_scenario_run(app, scenario_id, options).await
+}
+
+/// Per-run overrides of `execute_scenario_run`
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct ScenarioRunOptions {
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(rename = "verifyTls", default)]
+    verify_tls: Option<bool>,
+    #[serde(rename = "updateSnapshots", default)]
+    update_snapshots: Option<bool>,
+    #[serde(rename = "dryRun", default)]
+    dry_run: Option<bool>,
+    #[serde(default)]
+    environment: Option<String>,
+    #[serde(rename = "maxSteps", default)]
+    max_steps: Option<usize>, // Step executions before the run stops (default 10,000)
+    #[serde(skip)]
+    trigger: scenario::types::RunTrigger, // Set by the caller, never by the frontend
+}
+
+/// Run a scenario with its project settings and save the run; shared by manual and scheduled runs
+async fn execute_scenario_run(
+    app: tauri::AppHandle,
+    scenario_id: String,
+    options: ScenarioRunOptions,
+) -> Result<scenario::types::TestScenarioRun, AppError> {
+    let ScenarioRunOptions { seed, verify_tls, update_snapshots, dry_run, environment, max_steps, trigger } = options;
+    log::info!("[Command] run_test_scenario called for scenario_id: {} (seed: {:?})", scenario_id, seed);
     
     let scenario = database::get_test_scenario(&scenario_id)?
         .ok_or_else(|| {
             let error = format!("Scenario not found: {}", scenario_id);
             log::error!("[Command] {}", error);
-            error
+            AppError::NotFound(error)
         })?;
     
     log::info!("[Command] Scenario found: {} ({} steps)", scenario.name, scenario_id);
@@ -488,11 +1238,42 @@ pub async fn run_test_scenario(
         .ok_or_else(|| {
             let error = format!("Project not found: {}", scenario.project_id);
             log::error!("[Command] {}", error);
-            error
+            AppError::NotFound(error)
         })?;
     
-    let base_url = project.base_url.clone();
-    log::info!("[Command] Project base URL: {:?}", base_url);
+    // The selected environment's baseUrl/variables override the project and scenario defaults
+    let (scenario, base_url) = scenario.resolve_environment(environment.as_deref(), project.base_url.clone()).map_err(AppError::Validation)?;
+    log::info!("[Command] Base URL: {:?} (environment: {:?})", base_url, environment);
+
+    // Run-level override wins; otherwise only loopback/dev hosts stay permissive
+    let verify_tls = verify_tls.unwrap_or_else(|| {
+        project.verify_tls && !scenario::executor::is_local_base_url(base_url.as_deref())
+    });
+    log::info!("[Command] TLS verification: {}", verify_tls);
+
+    let assertion_templates = database::get_assertion_templates(&scenario.project_id)?;
+    let request_logger = if project.request_logging {
+        Some(request_log::RequestLogger::for_project(&project.id))
+    } else {
+        None
+    };
+    let snapshots = database::get_assertion_snapshots(&scenario_id)?;
+    let executor = scenario::executor::ScenarioExecutor::new()
+        .with_base_url(base_url)
+        .with_variables(project.variables.clone())
+        .with_seed(seed)
+        .with_tls_verification(verify_tls)
+        .with_client_defaults(http_client::ClientDefaults::from_project(&project))
+        .with_services(project.services.clone())
+        .with_request_signing(project.request_signing.clone())
+        .with_idempotency(project.idempotency.clone())
+        .with_body_limits(project.body_limits)
+        .with_assertion_templates(assertion_templates)
+        .with_request_logger(request_logger)
+        .with_run_log(Some(scenario::run_log::RunLog::new()))
+        .with_snapshots(snapshots, update_snapshots.unwrap_or(false))
+        .with_dry_run(dry_run.unwrap_or(false))
+        .with_max_executed_steps(max_steps.unwrap_or(scenario::executor::DEFAULT_MAX_EXECUTED_STEPS));
     
     // Run scenario in a spawned task to avoid blocking
     log::info!("[Command] Spawning blocking task to execute scenario");
@@ -501,16 +1282,17 @@ pub async fn run_test_scenario(
     let steps_clone = steps.clone();
     
     let start = std::time::Instant::now();
-    let run = tauri::async_runtime::spawn_blocking(move || {
+    let mut run = tauri::async_runtime::spawn_blocking(move || {
         log::info!("[Command] Blocking task started for scenario: {}", scenario_clone.name);
-        scenario::executor::run_scenario(&scenario_clone, &steps_clone, Some(&app_clone), base_url)
+        let mut executor = executor;
+        executor.execute_scenario(&scenario_clone, &steps_clone, Some(&app_clone))
     })
     .await
     .map_err(|e| {
         let error = format!("Failed to execute scenario: {}", e);
         log::error!("[Command] Async runtime error: {}", error);
         log::error!("[Command] Error details: {:?}", e);
-        error
+        AppError::Internal(error)
     })?;
     
     let duration = start.elapsed();
@@ -518,14 +1300,20 @@ pub async fn run_test_scenario(
     log::info!("[Command] Scenario result: status={:?}, passed={}/{}", 
         run.status, run.passed_steps, run.total_steps);
     
+    run.trigger = trigger;
     database::save_test_scenario_run(&run)
         .map_err(|e| {
-            let error = format!("Failed to save scenario run: {}", e);
+            let error = e.with_context("Failed to save scenario run");
             log::error!("[Command] {}", error);
             error
         })?;
     
     log::info!("[Command] Scenario run saved to database");
+
+    if !run.snapshot_updates.is_empty() {
+        database::save_assertion_snapshots(&run.snapshot_updates)?;
+        log::info!("[Command] Wrote {} assertion snapshot(s)", run.snapshot_updates.len());
+    }
     
     Ok(run)
 }
@@ -533,10 +1321,158 @@ pub async fn run_test_scenario(
 #[tauri::command]
 pub async fn get_test_scenario_runs(
     scenario_id: String,
-) -> Result<Vec<scenario::types::TestScenarioRun>, String> {
+) -> Result<Vec<scenario::types::TestScenarioRun>, AppError> {
     database::get_test_scenario_runs(&scenario_id)
 }
 
+/// Semantic checks on a scenario before running it: undefined variables, unused extractions,
+/// unknown assertion operators/sources, dangling step references and empty URLs
+#[tauri::command]
+pub async fn lint_scenario(scenario_id: String) -> Result<Vec<scenario::lint::LintIssue>, AppError> {
+    let scenario = database::get_test_scenario(&scenario_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Scenario not found: {}", scenario_id)))?;
+    let steps = database::get_test_scenario_steps(&scenario_id)?;
+    let project_variables = database::get_project(&scenario.project_id)?
+        .map(|p| p.variables)
+        .unwrap_or_default();
+    let templates = database::get_assertion_templates(&scenario.project_id)?;
+
+    Ok(scenario::lint::lint_scenario(&scenario, &steps, &project_variables, &templates))
+}
+
+// ============================================================================
+// Scenario Schedule Commands
+// ============================================================================
+
+/// Run a scenario every `interval_minutes` in the background, starting now; replaces any
+/// existing schedule for the scenario
+#[tauri::command]
+pub async fn start_scenario_schedule(
+    app: tauri::AppHandle,
+    scenario_id: String,
+    interval_minutes: u32,
+) -> Result<ScenarioSchedule, AppError> {
+    scenario::schedule::validate_interval(interval_minutes).map_err(AppError::Validation)?;
+    database::get_test_scenario(&scenario_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Scenario not found: {}", scenario_id)))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let created_at = database::get_scenario_schedule(&scenario_id)?
+        .map(|s| s.created_at)
+        .unwrap_or(now);
+    let mut schedule = ScenarioSchedule {
+        scenario_id,
+        interval_minutes,
+        enabled: true,
+        created_at,
+        updated_at: now,
+        active: false,
+    };
+    database::save_scenario_schedule(&schedule)?;
+
+    spawn_schedule(app, &schedule, true);
+    schedule.active = true;
+    log::info!("[Command] Scheduled scenario {} every {} minute(s)", schedule.scenario_id, interval_minutes);
+
+    Ok(schedule)
+}
+
+/// Stop and disable a scenario's schedule; the definition is kept for restarting
+#[tauri::command]
+pub async fn stop_scenario_schedule(scenario_id: String) -> Result<ScenarioSchedule, AppError> {
+    let mut schedule = database::get_scenario_schedule(&scenario_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Schedule not found for scenario: {}", scenario_id)))?;
+
+    scenario::schedule::stop_task(&scenario_id);
+    schedule.enabled = false;
+    schedule.active = false;
+    schedule.updated_at = chrono::Utc::now().timestamp();
+    database::save_scenario_schedule(&schedule)?;
+    log::info!("[Command] Stopped schedule for scenario {}", scenario_id);
+
+    Ok(schedule)
+}
+
+#[tauri::command]
+pub async fn get_scenario_schedules() -> Result<Vec<ScenarioSchedule>, AppError> {
+    database::get_scenario_schedules()
+}
+
+/// Pass rate and uptime over a scenario's most recent scheduled runs (default 50); manual
+/// runs are left out
+#[tauri::command]
+pub async fn get_scenario_schedule_summary(
+    scenario_id: String,
+    limit: Option<usize>,
+) -> Result<ScheduleSummary, AppError> {
+    let limit = limit.unwrap_or(scenario::schedule::DEFAULT_SUMMARY_WINDOW);
+    let runs = database::get_scheduled_scenario_runs(&scenario_id, limit)?;
+
+    Ok(scenario::schedule::summarize_runs(&runs))
+}
+
+/// Restart the background tasks of enabled schedules; called once at startup, first run
+/// after one interval
+pub fn resume_scenario_schedules(app: tauri::AppHandle) {
+    let schedules = match database::get_scenario_schedules() {
+        Ok(schedules) => schedules,
+        Err(e) => {
+            log::error!("[Schedule] Failed to load schedules: {}", e);
+            return;
+        }
+    };
+
+    for schedule in schedules.iter().filter(|s| s.enabled) {
+        spawn_schedule(app.clone(), schedule, false);
+    }
+    log::info!("[Schedule] Resumed {} schedule(s)", schedules.iter().filter(|s| s.enabled).count());
+}
+
+fn spawn_schedule(app: tauri::AppHandle, schedule: &ScenarioSchedule, run_now: bool) {
+    use tauri::Emitter;
+
+    let scenario_id = schedule.scenario_id.clone();
+    let period = std::time::Duration::from_secs(u64::from(schedule.interval_minutes) * 60);
+    let handle = tauri::async_runtime::spawn(async move {
+        let start = if run_now {
+            tokio::time::Instant::now()
+        } else {
+            tokio::time::Instant::now() + period
+        };
+        let mut ticker = tokio::time::interval_at(start, period);
+        // A run longer than the interval delays the next one instead of stacking them
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            log::info!("[Schedule] Running scenario {}", scenario_id);
+            let options = ScenarioRunOptions {
+                trigger: scenario::types::RunTrigger::Schedule,
+                ..Default::default()
+            };
+            let event = match execute_scenario_run(app.clone(), scenario_id.clone(), options).await {
+                Ok(run) => ScheduledRunEvent {
+                    scenario_id: scenario_id.clone(),
+                    run_id: Some(run.id),
+                    status: Some(run.status),
+                    error: None,
+                },
+                Err(e) => {
+                    log::error!("[Schedule] Scheduled run of {} failed: {}", scenario_id, e);
+                    ScheduledRunEvent {
+                        scenario_id: scenario_id.clone(),
+                        run_id: None,
+                        status: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+            let _ = app.emit("scheduled-run-completed", event);
+        }
+    });
+    scenario::schedule::register_task(&schedule.scenario_id, handle);
+}
+
 // ============================================================================
 // YAML Export/Import Commands
 // ============================================================================
@@ -546,22 +1482,22 @@ pub async fn get_test_scenario_runs(
 pub async fn export_scenario_yaml(
     scenario_id: String,
     base_url: Option<String>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let scenario = database::get_test_scenario(&scenario_id)?
-        .ok_or_else(|| "Scenario not found".to_string())?;
+        .ok_or_else(|| AppError::NotFound("Scenario not found".to_string()))?;
     let steps = database::get_test_scenario_steps(&scenario_id)?;
     
-    scenario_to_yaml_string(&scenario, &steps, base_url)
+    scenario_to_yaml_string(&scenario, &steps, base_url).map_err(AppError::Parse)
 }
 
 /// Export all scenarios in a project to YAML string
 #[tauri::command]
 pub async fn export_project_scenarios_yaml(
     project_id: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // Get project info
     let project = database::get_project(&project_id)?
-        .ok_or_else(|| "Project not found".to_string())?;
+        .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
     
     // Get all scenarios for the project
     let scenarios = database::get_test_scenarios_by_project(&project_id)?;
@@ -579,15 +1515,50 @@ pub async fn export_project_scenarios_yaml(
             .map(|(s, steps)| (*s, steps.as_slice()))
             .collect();
     
-    project_scenarios_to_yaml_string(&project.name, project.base_url, scenarios_refs)
+    project_scenarios_to_yaml_string(&project.name, project.base_url, scenarios_refs).map_err(AppError::Parse)
+}
+
+/// Scenarios loaded from the DB per batch by the streaming YAML export
+const YAML_EXPORT_BATCH_SIZE: usize = 50;
+
+/// Export all scenarios of a project to a YAML file, writing them in batches so
+/// memory stays flat for large projects
+#[tauri::command]
+pub async fn export_project_scenarios_yaml_to_file(
+    project_id: String,
+    path: String,
+) -> Result<ProjectYamlExport, AppError> {
+    let project = database::get_project(&project_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", project_id)))?;
+
+    let file = std::fs::File::create(&path)
+        .map_err(|e| AppError::Io(format!("Failed to write {}: {}", path, e)))?;
+    let mut writer = ProjectYamlWriter::new(std::io::BufWriter::new(file), &project.name, project.base_url.clone()).map_err(AppError::Io)?;
+
+    let mut offset = 0;
+    loop {
+        let batch = database::get_test_scenarios_page(&project_id, YAML_EXPORT_BATCH_SIZE, offset)?;
+        if batch.is_empty() {
+            break;
+        }
+        offset += batch.len();
+        for scenario in &batch {
+            let steps = database::get_test_scenario_steps(&scenario.id)?;
+            writer.write_scenario(scenario, &steps).map_err(AppError::Io)?;
+        }
+    }
+
+    let scenario_count = writer.finish().map_err(AppError::Io)?;
+    log::info!("[Command] Exported {} scenarios to {}", scenario_count, path);
+    Ok(ProjectYamlExport { path, scenario_count })
 }
 
 /// Preview a scenario import from YAML (dry run)
 #[tauri::command]
 pub async fn preview_scenario_yaml_import(
     yaml_content: String,
-) -> Result<ScenarioImportPreview, String> {
-    let yaml = parse_scenario_yaml(&yaml_content)?;
+) -> Result<ScenarioImportPreview, AppError> {
+    let yaml = parse_scenario_yaml(&yaml_content).map_err(AppError::Parse)?;
     Ok(create_import_preview(&yaml))
 }
 
@@ -595,8 +1566,8 @@ pub async fn preview_scenario_yaml_import(
 #[tauri::command]
 pub async fn preview_project_scenarios_yaml_import(
     yaml_content: String,
-) -> Result<ProjectImportPreview, String> {
-    let yaml = parse_project_scenarios_yaml(&yaml_content)?;
+) -> Result<ProjectImportPreview, AppError> {
+    let yaml = parse_project_scenarios_yaml(&yaml_content).map_err(AppError::Parse)?;
     Ok(create_project_import_preview(&yaml))
 }
 
@@ -605,50 +1576,161 @@ pub async fn preview_project_scenarios_yaml_import(
 pub async fn import_scenario_yaml(
     project_id: String,
     yaml_content: String,
-) -> Result<scenario::types::TestScenario, String> {
-    let yaml = parse_scenario_yaml(&yaml_content)?;
-    let (scenario, steps) = yaml_to_scenario_with_steps(&yaml, &project_id);
+) -> Result<scenario::types::TestScenario, AppError> {
+    let yaml = parse_scenario_yaml(&yaml_content).map_err(AppError::Parse)?;
+    let (scenario, steps) = yaml_to_scenario_with_steps(&yaml, &project_id).map_err(AppError::Validation)?;
     
-    // Save scenario
-    database::save_test_scenario(scenario.clone())?;
-    
-    // Save steps
-    for step in steps {
-        database::save_test_scenario_step(step)?;
-    }
+    // Save scenario and steps together; a failing step leaves nothing behind
+    database::save_test_scenario_with_steps(&scenario, &steps)?;
     
     Ok(scenario)
 }
 
-/// Import multiple scenarios from project YAML
+/// Import multiple scenarios from project YAML in one transaction, emitting
+/// `scenario-import-progress` after each scenario; nothing is kept if any of them fails
 #[tauri::command]
 pub async fn import_project_scenarios_yaml(
+    app: tauri::AppHandle,
     project_id: String,
     yaml_content: String,
-) -> Result<Vec<scenario::types::TestScenario>, String> {
-    let yaml = parse_project_scenarios_yaml(&yaml_content)?;
-    let mut imported_scenarios = Vec::new();
-    
-    for scenario_yaml in &yaml.scenarios {
-        let (scenario, steps) = yaml_to_scenario_with_steps(scenario_yaml, &project_id);
-        
-        // Save scenario
-        database::save_test_scenario(scenario.clone())?;
-        
-        // Save steps
-        for step in steps {
-            database::save_test_scenario_step(step)?;
-        }
-        
-        imported_scenarios.push(scenario);
+) -> Result<scenario::yaml::ProjectImportSummary, AppError> {
+    use tauri::Emitter;
+
+    let yaml = parse_project_scenarios_yaml(&yaml_content).map_err(AppError::Parse)?;
+
+    // Validate every scenario before saving any of them
+    let converted = yaml.scenarios.iter()
+        .map(|scenario_yaml| yaml_to_scenario_with_steps(scenario_yaml, &project_id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(AppError::Validation)?;
+
+    let total = converted.len();
+    database::import_test_scenarios(&converted, |index, scenario| {
+        let _ = app.emit("scenario-import-progress", scenario::yaml::ScenarioImportProgress {
+            name: scenario.name.clone(),
+            index: index + 1,
+            total,
+        });
+    })?;
+
+    let steps_count = converted.iter().map(|(_, steps)| steps.len()).sum();
+    log::info!("[Command] Imported {} scenarios ({} steps) into project {}", total, steps_count, project_id);
+
+    Ok(scenario::yaml::ProjectImportSummary {
+        scenarios: converted.into_iter().map(|(scenario, _)| scenario).collect(),
+        scenarios_count: total,
+        steps_count,
+    })
+}
+
+/// Export a scenario as `.http` (REST Client) content
+#[tauri::command]
+pub async fn export_http_file(scenario_id: String) -> Result<String, AppError> {
+    let scenario = database::get_test_scenario(&scenario_id)?
+        .ok_or_else(|| AppError::NotFound("Scenario not found".to_string()))?;
+    let steps = database::get_test_scenario_steps(&scenario_id)?;
+
+    Ok(scenario::http_file::scenario_to_http_file(&scenario, &steps))
+}
+
+/// Export a scenario run's step results as CSV, one row per step (and per CSV data row)
+#[tauri::command]
+pub async fn export_run_csv(run_id: String) -> Result<String, AppError> {
+    let run = database::get_test_scenario_run(&run_id)?
+        .ok_or_else(|| AppError::NotFound("Run not found".to_string()))?;
+
+    scenario::run_export::run_to_csv(&run).map_err(AppError::Parse)
+}
+
+/// Import a `.http` (REST Client) file as a new scenario of request steps; returns its id
+#[tauri::command]
+pub async fn import_http_file(project_id: String, content: String) -> Result<String, AppError> {
+    let file = scenario::http_file::parse_http_file(&content).map_err(AppError::Parse)?;
+    let (scenario, steps) = scenario::http_file::http_file_to_scenario(&file, &project_id, "Imported .http requests");
+
+    database::save_test_scenario_with_steps(&scenario, &steps)?;
+    log::info!("[Command] Imported {} requests from .http file into scenario {}", file.requests.len(), scenario.id);
+
+    Ok(scenario.id)
+}
+
+/// Import a HAR capture as a new scenario of request steps, optionally only the requests to
+/// `filter_host`. Repeated auth values become variables unless `parameterize` is false.
+#[tauri::command]
+pub async fn import_har(
+    project_id: String,
+    har_json: String,
+    filter_host: Option<String>,
+    parameterize: Option<bool>,
+) -> Result<String, AppError> {
+    let har = scenario::har::parse_har(&har_json).map_err(AppError::Parse)?;
+    let name = match filter_host.as_deref() {
+        Some(host) => format!("Imported HAR ({})", host),
+        None => "Imported HAR".to_string(),
+    };
+    let (scenario, steps) = scenario::har::har_to_scenario(
+        &har,
+        &project_id,
+        &name,
+        filter_host.as_deref(),
+        parameterize.unwrap_or(true),
+    ).map_err(AppError::Validation)?;
+
+    let count = steps.len();
+    database::save_test_scenario_with_steps(&scenario, &steps)?;
+    log::info!("[Command] Imported {} HAR entries into scenario {}", count, scenario.id);
+
+    Ok(scenario.id)
+}
+
+/// Render one request step as curl, a `.http` entry or JSON, with project and scenario
+/// variables (plus any overrides) resolved so the output is runnable as-is
+#[tauri::command]
+pub async fn export_step(
+    step_id: String,
+    format: String,
+    variables: Option<HashMap<String, serde_json::Value>>,
+) -> Result<String, AppError> {
+    let step = database::get_test_scenario_step_by_id(&step_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Step not found: {}", step_id)))?;
+    if step.step_type != scenario::types::TestStepType::Request {
+        return Err(AppError::Validation(format!(
+            "Only request steps can be exported; '{}' is a {} step", step.name, step.step_type.as_str()
+        )));
     }
-    
-    Ok(imported_scenarios)
+    let test_scenario = database::get_test_scenario(&step.scenario_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Scenario not found: {}", step.scenario_id)))?;
+    let project = database::get_project(&test_scenario.project_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", test_scenario.project_id)))?;
+
+    // Same precedence as a run (project, scenario, baseUrl), then the caller's overrides
+    let mut resolved = project.variables.clone();
+    if let Some(vars) = test_scenario.variables.as_object() {
+        resolved.extend(vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    let base_url = project.base_url.clone().unwrap_or_else(|| "http://localhost:8080".to_string());
+    resolved.insert("baseUrl".to_string(), serde_json::Value::String(base_url));
+    resolved.extend(variables.unwrap_or_default());
+
+    // The blocking client must be created and dropped on the blocking pool
+    let request = tauri::async_runtime::spawn_blocking(move || {
+        scenario::executor::ScenarioExecutor::new()
+            .with_base_url(project.base_url.clone())
+            .with_services(project.services.clone())
+            .with_variables(resolved)
+            .resolve_request_step(&step)
+            .map(|request| (step.name, request))
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Failed to resolve step: {}", e)))?;
+    let (name, request) = request.map_err(AppError::Validation)?;
+
+    scenario::step_export::render_step_request(&name, &request, &format).map_err(AppError::Validation)
 }
 
 /// Get YAML template for AI tools
 #[tauri::command]
-pub async fn get_yaml_template() -> Result<String, String> {
+pub async fn get_yaml_template() -> Result<String, AppError> {
     Ok(generate_yaml_template())
 }
 
@@ -662,7 +1744,8 @@ pub async fn generate_yaml_with_ai(
     user_prompt: String,
     project_id: Option<String>,
     base_url: Option<String>,
-) -> Result<GenerateYamlWithAIResponse, String> {
+    tool_policy: Option<CopilotToolPolicy>,
+) -> Result<GenerateYamlWithAIResponse, AppError> {
     log::info!("[Command] generate_yaml_with_ai called for project: {}", project_path);
     
     // Get endpoints if project_id is provided
@@ -685,6 +1768,7 @@ pub async fn generate_yaml_with_ai(
         &user_prompt,
         endpoints.as_deref(),
         base_url.as_deref(),
+        &tool_policy.unwrap_or_default(),
     ).await;
     
     match result {
@@ -695,12 +1779,23 @@ pub async fn generate_yaml_with_ai(
             
             // Save generated YAML to database if project_id is provided
             if let Some(ref pid) = project_id {
+                let now = chrono::Utc::now().timestamp();
                 let yaml_file = YamlFile {
                     id: Uuid::new_v4().to_string(),
                     project_id: pid.clone(),
                     scenario_id: None,
                     content: yaml.clone(),
-                    created_at: chrono::Utc::now().timestamp(),
+                    created_at: now,
+                    provenance: Some(YamlProvenance {
+                        prompt: user_prompt.clone(),
+                        endpoint_ids: endpoints.iter().flatten()
+                            .take(AI_CONTEXT_ENDPOINT_LIMIT)
+                            .map(|e| e.id.clone())
+                            .collect(),
+                        backend: AI_YAML_BACKEND.to_string(),
+                        base_url: base_url.clone(),
+                        generated_at: now,
+                    }),
                 };
                 
                 if let Err(e) = database::save_yaml_file(yaml_file) {
@@ -712,30 +1807,24 @@ pub async fn generate_yaml_with_ai(
                 // Auto-import as test scenario
                 log::info!("[Command] Auto-importing generated YAML as test scenario");
                 match parse_scenario_yaml(&yaml) {
-                    Ok(parsed_yaml) => {
-                        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed_yaml, pid);
-                        
-                        // Save scenario
-                        match database::save_test_scenario(scenario.clone()) {
-                            Ok(_) => {
-                                log::info!("[Command] Test scenario saved: {} ({})", scenario.name, scenario.id);
-                                
-                                // Save steps
-                                let mut steps_saved = 0;
-                                for step in steps {
-                                    if let Ok(_) = database::save_test_scenario_step(step) {
-                                        steps_saved += 1;
-                                    }
+                    Ok(parsed_yaml) => match yaml_to_scenario_with_steps(&parsed_yaml, pid) {
+                        Ok((scenario, steps)) => {
+                            // Save scenario and steps in one transaction
+                            match database::save_test_scenario_with_steps(&scenario, &steps) {
+                                Ok(_) => {
+                                    log::info!("[Command] Test scenario saved: {} ({}) with {} steps", scenario.name, scenario.id, steps.len());
+                                    
+                                    created_scenario = Some(scenario);
+                                }
+                                Err(e) => {
+                                    log::warn!("[Command] Failed to save auto-imported scenario: {}", e);
                                 }
-                                log::info!("[Command] {} steps saved for scenario {}", steps_saved, scenario.id);
-                                
-                                created_scenario = Some(scenario);
-                            }
-                            Err(e) => {
-                                log::warn!("[Command] Failed to save auto-imported scenario: {}", e);
                             }
                         }
-                    }
+                        Err(e) => {
+                            log::warn!("[Command] Generated YAML not auto-imported: {}", e);
+                        }
+                    },
                     Err(e) => {
                         log::warn!("[Command] Failed to parse generated YAML for auto-import: {}", e);
                     }
@@ -749,14 +1838,14 @@ pub async fn generate_yaml_with_ai(
         }
         Err(e) => {
             log::error!("[Command] AI generation failed: {}", e);
-            Err(e)
+            Err(AppError::Internal(e))
         }
     }
 }
 
 /// Get all YAML files for a project
 #[tauri::command]
-pub async fn get_yaml_files(project_id: String) -> Result<Vec<YamlFile>, String> {
+pub async fn get_yaml_files(project_id: String) -> Result<Vec<YamlFile>, AppError> {
     database::get_yaml_files_by_project(&project_id)
 }
 
@@ -766,7 +1855,7 @@ pub async fn save_yaml_file(
     project_id: String,
     content: String,
     scenario_id: Option<String>,
-) -> Result<YamlFile, String> {
+) -> Result<YamlFile, AppError> {
     log::info!("[Command] save_yaml_file called - project_id: {}, scenario_id: {:?}, content_length: {}", 
         project_id, scenario_id, content.len());
     
@@ -776,6 +1865,7 @@ pub async fn save_yaml_file(
         scenario_id: scenario_id.clone(),
         content: content.clone(),
         created_at: chrono::Utc::now().timestamp(),
+        provenance: None,
     };
     
     match database::save_yaml_file(yaml_file.clone()) {
@@ -794,7 +1884,7 @@ pub async fn save_yaml_file(
 
 /// Delete a YAML file
 #[tauri::command]
-pub async fn delete_yaml_file(id: String) -> Result<(), String> {
+pub async fn delete_yaml_file(id: String) -> Result<(), AppError> {
     database::delete_yaml_file(&id)
 }
 
@@ -803,27 +1893,42 @@ pub async fn delete_yaml_file(id: String) -> Result<(), String> {
 pub async fn update_scenario_from_yaml(
     scenario_id: String,
     yaml_content: String,
-) -> Result<scenario::types::TestScenario, String> {
-    log::info!("[Command] update_scenario_from_yaml called - scenario_id: {}, content_length: {}", 
-        scenario_id, yaml_content.len());
+    mode: Option<String>,
+) -> Result<scenario::types::TestScenario, AppError> {
+    log::info!("[Command] update_scenario_from_yaml called - scenario_id: {}, content_length: {}, mode: {:?}", 
+        scenario_id, yaml_content.len(), mode);
+
+    let merge = match mode.as_deref().unwrap_or("replace") {
+        "replace" => false,
+        "merge" => true,
+        other => return Err(AppError::Validation(format!("Invalid mode '{}': expected replace or merge", other))),
+    };
     
     // 1. Verify scenario exists
     let existing_scenario = database::get_test_scenario(&scenario_id)?
-        .ok_or_else(|| format!("Scenario not found: {}", scenario_id))?;
+        .ok_or_else(|| AppError::NotFound(format!("Scenario not found: {}", scenario_id)))?;
     
     log::info!("[Command] Found existing scenario: {} (project_id: {})", 
         existing_scenario.name, existing_scenario.project_id);
     
     // 2. Parse YAML
-    let yaml = parse_scenario_yaml(&yaml_content)?;
+    let yaml = parse_scenario_yaml(&yaml_content).map_err(AppError::Parse)?;
     
-    // 3. Delete old steps
+    // 3. Work out step changes; merge keeps ids of steps matched by name
     let old_steps = database::get_test_scenario_steps(&scenario_id)?;
-    log::info!("[Command] Deleting {} old steps", old_steps.len());
-    for step in old_steps {
-        database::delete_test_scenario_step(&step.id)?;
-    }
-    
+    let (new_steps, removed_step_ids) = if merge {
+        scenario::yaml::merge_yaml_steps(&yaml.steps, &old_steps, &scenario_id).map_err(AppError::Validation)?
+    } else {
+        let steps = yaml
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step_yaml)| scenario::yaml::yaml_to_step(step_yaml, &scenario_id, i as i32))
+            .collect::<Vec<_>>();
+        scenario::types::validate_step_references(&steps).map_err(AppError::Validation)?;
+        (steps, old_steps.iter().map(|s| s.id.clone()).collect::<Vec<_>>())
+    };
+
     // 4. Update scenario with YAML data (keep existing ID, project_id, created_at)
     let now = chrono::Utc::now().timestamp();
     let updated_scenario = scenario::types::TestScenario {
@@ -835,26 +1940,18 @@ pub async fn update_scenario_from_yaml(
         variables: serde_json::to_value(&yaml.variables).unwrap_or(serde_json::json!({})),
         pre_script: yaml.pre_script.clone(),
         post_script: yaml.post_script.clone(),
+        chaos: yaml.chaos.clone(),
+        session_login: yaml.session_login.clone(),
+        environments: yaml.environments.clone(),
         created_at: existing_scenario.created_at,
         updated_at: now,
     };
     
-    database::save_test_scenario(updated_scenario.clone())?;
+    // 5. Delete removed steps and save the scenario with its steps in one transaction
+    log::info!("[Command] Deleting {} old steps, saving {} steps", removed_step_ids.len(), new_steps.len());
+    database::replace_test_scenario_steps(&updated_scenario, &removed_step_ids, &new_steps)?;
     log::info!("[Command] Scenario updated: {}", updated_scenario.name);
     
-    // 5. Create new steps from YAML
-    let new_steps: Vec<scenario::types::TestScenarioStep> = yaml
-        .steps
-        .iter()
-        .enumerate()
-        .map(|(i, step_yaml)| scenario::yaml::yaml_to_step(step_yaml, &scenario_id, i as i32))
-        .collect();
-    
-    log::info!("[Command] Creating {} new steps", new_steps.len());
-    for step in new_steps {
-        database::save_test_scenario_step(step)?;
-    }
-    
     log::info!("[Command] Scenario updated successfully - id: {}, steps_count: {}", 
         updated_scenario.id, yaml.steps.len());
     
@@ -867,7 +1964,7 @@ pub async fn preview_csv_file(
     file_path: String,
     quote_char: Option<String>,
     delimiter: Option<String>,
-) -> Result<scenario::types::CsvPreview, String> {
+) -> Result<scenario::types::CsvPreview, AppError> {
     log::info!("[Command] preview_csv_file called: {}", file_path);
     
     let csv_config = scenario::types::CsvConfig {
@@ -877,7 +1974,26 @@ pub async fn preview_csv_file(
     };
     
     scenario::csv_reader::preview_csv_file(&file_path, &csv_config, 10)
-        .map_err(|e| format!("Failed to preview CSV: {}", e))
+        .map_err(|e| AppError::Parse(format!("Failed to preview CSV: {}", e)))
+}
+
+/// Get CSV header row for column autocompletion
+#[tauri::command]
+pub async fn get_csv_headers(
+    file_path: String,
+    quote_char: Option<String>,
+    delimiter: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    log::info!("[Command] get_csv_headers called: {}", file_path);
+
+    let csv_config = scenario::types::CsvConfig {
+        file_name: file_path.clone(),
+        quote_char: quote_char.and_then(|s| s.chars().next()),
+        delimiter: delimiter.and_then(|s| s.chars().next()),
+    };
+
+    scenario::csv_reader::read_csv_headers(&file_path, &csv_config)
+        .map_err(|e| AppError::Parse(format!("Failed to read CSV headers: {}", e)))
 }
 
 // ============================================================================
@@ -888,9 +2004,12 @@ pub async fn preview_csv_file(
 #[tauri::command]
 pub async fn create_performance_test(
     input: CreatePerformanceTestInput,
-) -> Result<PerformanceTestConfig, String> {
+) -> Result<PerformanceTestConfig, AppError> {
     log::info!("[Command] create_performance_test called for scenario: {}", input.scenario_id);
     
+    let scenario_mix = input.scenario_mix.unwrap_or_default();
+    scenario::performance::validate_scenario_mix(&scenario_mix).map_err(AppError::Validation)?;
+
     let now = chrono::Utc::now().timestamp();
     
     let config = PerformanceTestConfig {
@@ -902,7 +2021,12 @@ pub async fn create_performance_test(
         duration_secs: input.duration_secs,
         iterations: input.iterations,
         stages: input.stages,
+        warmup_secs: input.warmup_secs,
         thresholds: input.thresholds.unwrap_or_default(),
+        http2_prior_knowledge: input.http2_prior_knowledge.unwrap_or(false),
+        store_raw_samples: input.store_raw_samples.unwrap_or(false),
+        max_in_flight: input.max_in_flight,
+        scenario_mix,
         created_at: now,
         updated_at: now,
     };
@@ -917,7 +2041,7 @@ pub async fn create_performance_test(
 #[tauri::command]
 pub async fn get_performance_tests(
     scenario_id: String,
-) -> Result<Vec<PerformanceTestConfig>, String> {
+) -> Result<Vec<PerformanceTestConfig>, AppError> {
     database::get_performance_test_configs(&scenario_id)
 }
 
@@ -925,7 +2049,7 @@ pub async fn get_performance_tests(
 #[tauri::command]
 pub async fn get_performance_test(
     config_id: String,
-) -> Result<Option<PerformanceTestConfig>, String> {
+) -> Result<Option<PerformanceTestConfig>, AppError> {
     database::get_performance_test_config(&config_id)
 }
 
@@ -933,31 +2057,34 @@ pub async fn get_performance_test(
 #[tauri::command]
 pub async fn update_performance_test(
     config_id: String,
-    name: Option<String>,
-    test_type: Option<String>,
-    vus: Option<u32>,
-    duration_secs: Option<u64>,
-    iterations: Option<u64>,
-    stages: Option<Vec<Stage>>,
-    thresholds: Option<Vec<Threshold>>,
-) -> Result<PerformanceTestConfig, String> {
+    input: UpdatePerformanceTestInput,
+) -> Result<PerformanceTestConfig, AppError> {
     log::info!("[Command] update_performance_test called: {}", config_id);
     
+    if let Some(ref mix) = input.scenario_mix {
+        scenario::performance::validate_scenario_mix(mix).map_err(AppError::Validation)?;
+    }
+    
     let existing = database::get_performance_test_config(&config_id)?
-        .ok_or_else(|| "Performance test config not found".to_string())?;
+        .ok_or_else(|| AppError::NotFound("Performance test config not found".to_string()))?;
     
     let now = chrono::Utc::now().timestamp();
     
     let updated = PerformanceTestConfig {
         id: existing.id,
         scenario_id: existing.scenario_id,
-        name: name.unwrap_or(existing.name),
-        test_type: test_type.map(|t| PerformanceTestType::from_str(&t)).unwrap_or(existing.test_type),
-        vus: vus.or(existing.vus),
-        duration_secs: duration_secs.or(existing.duration_secs),
-        iterations: iterations.or(existing.iterations),
-        stages: stages.or(existing.stages),
-        thresholds: thresholds.unwrap_or(existing.thresholds),
+        name: input.name.unwrap_or(existing.name),
+        test_type: input.test_type.map(|t| PerformanceTestType::from_str(&t)).unwrap_or(existing.test_type),
+        vus: input.vus.or(existing.vus),
+        duration_secs: input.duration_secs.or(existing.duration_secs),
+        iterations: input.iterations.or(existing.iterations),
+        stages: input.stages.or(existing.stages),
+        warmup_secs: input.warmup_secs.or(existing.warmup_secs),
+        thresholds: input.thresholds.unwrap_or(existing.thresholds),
+        http2_prior_knowledge: input.http2_prior_knowledge.unwrap_or(existing.http2_prior_knowledge),
+        store_raw_samples: input.store_raw_samples.unwrap_or(existing.store_raw_samples),
+        max_in_flight: input.max_in_flight.or(existing.max_in_flight),
+        scenario_mix: input.scenario_mix.unwrap_or(existing.scenario_mix),
         created_at: existing.created_at,
         updated_at: now,
     };
@@ -972,7 +2099,7 @@ pub async fn update_performance_test(
 #[tauri::command]
 pub async fn delete_performance_test(
     config_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     log::info!("[Command] delete_performance_test called: {}", config_id);
     database::delete_performance_test_config(&config_id)
 }
@@ -982,41 +2109,63 @@ pub async fn delete_performance_test(
 pub async fn run_performance_test(
     app: tauri::AppHandle,
     config_id: String,
-) -> Result<PerformanceTestRun, String> {
+) -> Result<PerformanceTestRun, AppError> {
     log::info!("[Command] run_performance_test called for config: {}", config_id);
     
     // Get the config
     let config = database::get_performance_test_config(&config_id)?
-        .ok_or_else(|| format!("Performance test config not found: {}", config_id))?;
+        .ok_or_else(|| AppError::NotFound(format!("Performance test config not found: {}", config_id)))?;
     
     // Get the scenario
     let scenario = database::get_test_scenario(&config.scenario_id)?
-        .ok_or_else(|| format!("Scenario not found: {}", config.scenario_id))?;
+        .ok_or_else(|| AppError::NotFound(format!("Scenario not found: {}", config.scenario_id)))?;
     
     // Get the steps
     let steps = database::get_test_scenario_steps(&config.scenario_id)?;
     
     // Get the project for base URL
     let project = database::get_project(&scenario.project_id)?
-        .ok_or_else(|| format!("Project not found: {}", scenario.project_id))?;
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", scenario.project_id)))?;
     
+    let client_defaults = http_client::ClientDefaults::from_project(&project);
     let base_url = project.base_url;
     
+    // Load every mixed scenario up front so a missing one fails before any load is sent
+    let mut scenario_mix = Vec::new();
+    for entry in &config.scenario_mix {
+        let mixed = database::get_test_scenario(&entry.scenario_id)?
+            .ok_or_else(|| AppError::NotFound(format!("Scenario in mix not found: {}", entry.scenario_id)))?;
+        let mixed_steps = database::get_test_scenario_steps(&entry.scenario_id)?;
+        scenario_mix.push((mixed, mixed_steps, entry.weight));
+    }
+    
     log::info!("[Command] Running performance test: {} on scenario: {}", config.name, scenario.name);
     log::info!("[Command] Base URL: {:?}, VUs: {:?}, Duration: {:?}s", 
         base_url, config.vus, config.duration_secs);
     
     // Run the performance test
-    let run = scenario::performance::run_performance_test(
+    let mut run = scenario::performance::run_performance_test(
         scenario,
         steps,
         config,
         base_url,
+        client_defaults,
+        scenario_mix,
         Some(app),
     ).await;
     
     // Save the run result
     database::save_performance_test_run(&run)?;
+    if let Some(samples) = run.raw_samples.take() {
+        // Large sample sets take a while to write; keep them off the async runtime threads
+        let run_id = run.id.clone();
+        let stored = tauri::async_runtime::spawn_blocking(move || {
+            database::save_performance_raw_samples(&run_id, &samples).map(|_| samples.len())
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to store raw samples: {}", e)))??;
+        log::info!("[Command] Stored {} raw samples for run {}", stored, run.id);
+    }
     
     log::info!("[Command] Performance test completed: status={:?}, requests={}, p95={}ms",
         run.status,
@@ -1031,7 +2180,7 @@ pub async fn run_performance_test(
 #[tauri::command]
 pub async fn get_performance_test_runs(
     config_id: String,
-) -> Result<Vec<PerformanceTestRun>, String> {
+) -> Result<Vec<PerformanceTestRun>, AppError> {
     database::get_performance_test_runs(&config_id)
 }
 
@@ -1039,16 +2188,27 @@ pub async fn get_performance_test_runs(
 #[tauri::command]
 pub async fn get_performance_test_run(
     run_id: String,
-) -> Result<Option<PerformanceTestRun>, String> {
+) -> Result<Option<PerformanceTestRun>, AppError> {
     database::get_performance_test_run(&run_id)
 }
 
+/// Page through raw request samples of a run created with store_raw_samples
+#[tauri::command]
+pub async fn get_performance_raw_samples(
+    run_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<RawSamplePage, AppError> {
+    let limit = limit.unwrap_or(1000).clamp(1, 10_000);
+    database::get_performance_raw_samples(&run_id, offset.unwrap_or(0), limit)
+}
+
 // Request tabs commands
 #[tauri::command]
 pub async fn save_request_tabs(
     project_id: String,
     tabs: Vec<RequestTab>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     database::save_request_tabs(&project_id, tabs)
 }
 
@@ -1057,14 +2217,14 @@ pub async fn save_request_tab(
     project_id: String,
     tab: RequestTab,
     tab_order: i32,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     database::save_single_request_tab(&project_id, &tab, tab_order)
 }
 
 #[tauri::command]
 pub async fn get_request_tabs(
     project_id: String,
-) -> Result<Vec<RequestTab>, String> {
+) -> Result<Vec<RequestTab>, AppError> {
     database::get_request_tabs(&project_id)
 }
 
@@ -1072,20 +2232,20 @@ pub async fn get_request_tabs(
 pub async fn save_request_tab_state(
     project_id: String,
     active_tab_id: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     database::save_request_tab_state(&project_id, active_tab_id)
 }
 
 #[tauri::command]
 pub async fn get_request_tab_state(
     project_id: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, AppError> {
     database::get_request_tab_state(&project_id)
 }
 
 #[tauri::command]
 pub async fn delete_request_tab(
     tab_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     database::delete_request_tab(&tab_id)
 }
@@ -1,12 +1,15 @@
-use crate::types::{ApiEndpoint, TestSuite, QueryResult, Project, YamlFile};
+use crate::types::{ApiEndpoint, TestSuite, QueryResult, Project, ProjectStats, IdempotencyConfig, BodyLimits, YamlFile, YamlProvenance, RequestHistoryEntry, SavedRequest};
 use crate::security::types::{SecurityTestCase, SecurityTestRun, ScanConfig};
-use crate::scenario::types::{TestScenario, TestScenarioStep, TestScenarioRun, TestStepType, ScenarioRunStatus};
+use crate::scenario::types::{TestScenario, TestScenarioStep, TestScenarioRun, TestStepType, ScenarioRunStatus, RunTrigger, TestStepResult, StepGroupSummary, AssertionTemplate, GoldenResponse, AssertionSnapshot};
+use crate::scenario::schedule::ScenarioSchedule;
+use crate::health::EndpointHealth;
 use crate::scenario::performance::{
     PerformanceTestConfig, PerformanceTestRun, PerformanceTestType, PerformanceRunStatus,
-    Stage, Threshold,
+    RawSamplePage, RequestMetric, Stage, Threshold,
 };
 use rusqlite::{Connection, Result};
 use std::path::PathBuf;
+use crate::error::AppError;
 
 pub fn get_db_path() -> PathBuf {
     let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -19,6 +22,10 @@ pub fn get_db_path() -> PathBuf {
 pub fn init_database() -> Result<()> {
     let conn = Connection::open(get_db_path())?;
 
+    // WAL lets reads continue while large writes (e.g. raw perf samples) are in progress;
+    // the mode is stored in the database file, so setting it once here covers every connection
+    let _ = conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0));
+
     // Projects table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS projects (
@@ -54,12 +61,59 @@ pub fn init_database() -> Result<()> {
     // Add project_id column if it doesn't exist (migration for existing databases)
     let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN project_id TEXT", []);
 
+    // Re-scan merge flags (migration)
+    let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN user_modified INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN stale INTEGER DEFAULT 0", []);
+
+    // Scanned auth requirement (migration)
+    let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN auth_required INTEGER DEFAULT 0", []);
+    let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN auth_type TEXT", []);
+
+    // Scanner warnings such as possible N+1 queries, JSON array (migration)
+    let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN warnings TEXT", []);
+
     // Add base_url column to projects table if it doesn't exist (migration)
     let _ = conn.execute("ALTER TABLE projects ADD COLUMN base_url TEXT", []);
 
+    // Add verify_tls column to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN verify_tls INTEGER DEFAULT 1", []);
+
+    // Add user_agent and default_headers columns to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN user_agent TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN default_headers TEXT DEFAULT '{}'", []);
+
+    // Add request_logging column to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN request_logging INTEGER DEFAULT 0", []);
+
+    // Add request_signing column to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN request_signing TEXT", []);
+    // Add variables column to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN variables TEXT DEFAULT '{}'", []);
+
+    // Add idempotency column to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN idempotency TEXT", []);
+    // Add body_limits column to projects table (migration)
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN body_limits TEXT", []);
+
+    // Named service base URLs for multi-service projects
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS project_services (
+            project_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            base_url TEXT NOT NULL,
+            position INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (project_id, name),
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
     // Add responses column to endpoints table if it doesn't exist (migration)
     let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN responses TEXT DEFAULT '[]'", []);
 
+    // Add version column to endpoints table (migration)
+    let _ = conn.execute("ALTER TABLE endpoints ADD COLUMN version TEXT", []);
+
     conn.execute(
         "CREATE TABLE IF NOT EXISTS test_suites (
             id TEXT PRIMARY KEY,
@@ -139,6 +193,21 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Add chaos column to test_scenarios (migration)
+    let _ = conn.execute("ALTER TABLE test_scenarios ADD COLUMN chaos TEXT", []);
+
+    // Add session_login column to test_scenarios (migration)
+    let _ = conn.execute("ALTER TABLE test_scenarios ADD COLUMN session_login TEXT", []);
+
+    // Add environments column to test_scenarios (migration)
+    let _ = conn.execute("ALTER TABLE test_scenarios ADD COLUMN environments TEXT", []);
+
+    // Add enabled_if column to test_scenario_steps (migration)
+    let _ = conn.execute("ALTER TABLE test_scenario_steps ADD COLUMN enabled_if TEXT", []);
+
+    // Add step_group column to test_scenario_steps (migration)
+    let _ = conn.execute("ALTER TABLE test_scenario_steps ADD COLUMN step_group TEXT", []);
+
     // Test scenario runs table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS test_scenario_runs (
@@ -160,6 +229,13 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Add seed column to test_scenario_runs (migration)
+    let _ = conn.execute("ALTER TABLE test_scenario_runs ADD COLUMN seed INTEGER", []);
+    let _ = conn.execute("ALTER TABLE test_scenario_runs ADD COLUMN logs TEXT", []);
+
+    // Add run_trigger column to test_scenario_runs (migration); older runs count as manual
+    let _ = conn.execute("ALTER TABLE test_scenario_runs ADD COLUMN run_trigger TEXT DEFAULT 'manual'", []);
+
     // YAML files table - stores generated YAML content
     conn.execute(
         "CREATE TABLE IF NOT EXISTS yaml_files (
@@ -174,6 +250,13 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Add AI provenance columns to yaml_files (migration)
+    let _ = conn.execute("ALTER TABLE yaml_files ADD COLUMN ai_prompt TEXT", []);
+    let _ = conn.execute("ALTER TABLE yaml_files ADD COLUMN ai_endpoint_ids TEXT", []);
+    let _ = conn.execute("ALTER TABLE yaml_files ADD COLUMN ai_backend TEXT", []);
+    let _ = conn.execute("ALTER TABLE yaml_files ADD COLUMN ai_base_url TEXT", []);
+    let _ = conn.execute("ALTER TABLE yaml_files ADD COLUMN ai_generated_at INTEGER", []);
+
     // Performance test configurations table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS performance_test_configs (
@@ -193,6 +276,21 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Add http2_prior_knowledge column to performance_test_configs (migration)
+    let _ = conn.execute("ALTER TABLE performance_test_configs ADD COLUMN http2_prior_knowledge INTEGER DEFAULT 0", []);
+
+    // Add warmup_secs column to performance_test_configs (migration)
+    let _ = conn.execute("ALTER TABLE performance_test_configs ADD COLUMN warmup_secs INTEGER", []);
+
+    // Add store_raw_samples column to performance_test_configs (migration)
+    let _ = conn.execute("ALTER TABLE performance_test_configs ADD COLUMN store_raw_samples INTEGER DEFAULT 0", []);
+
+    // Add max_in_flight column to performance_test_configs (migration)
+    let _ = conn.execute("ALTER TABLE performance_test_configs ADD COLUMN max_in_flight INTEGER", []);
+
+    // Add scenario_mix column to performance_test_configs (migration)
+    let _ = conn.execute("ALTER TABLE performance_test_configs ADD COLUMN scenario_mix TEXT DEFAULT '[]'", []);
+
     // Performance test runs table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS performance_test_runs (
@@ -213,6 +311,36 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Add time_series column to performance_test_runs (migration)
+    let _ = conn.execute("ALTER TABLE performance_test_runs ADD COLUMN time_series TEXT DEFAULT '[]'", []);
+
+    // Raw request samples for performance runs (opt-in per config)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS performance_raw_samples (
+            run_id TEXT NOT NULL,
+            seq INTEGER NOT NULL,
+            step_id TEXT NOT NULL,
+            step_name TEXT NOT NULL,
+            method TEXT NOT NULL,
+            url TEXT NOT NULL,
+            status INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            vu_id INTEGER NOT NULL,
+            iteration INTEGER NOT NULL,
+            timestamp INTEGER NOT NULL,
+            PRIMARY KEY (run_id, seq),
+            FOREIGN KEY (run_id) REFERENCES performance_test_runs(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Add queue_wait_ms column to performance_raw_samples (migration)
+    let _ = conn.execute("ALTER TABLE performance_raw_samples ADD COLUMN queue_wait_ms INTEGER DEFAULT 0", []);
+
+    // Add scenario_id column to performance_raw_samples (migration)
+    let _ = conn.execute("ALTER TABLE performance_raw_samples ADD COLUMN scenario_id TEXT DEFAULT ''", []);
+
     // Request tabs table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS request_tabs (
@@ -245,6 +373,124 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Request history table - manual executions from the request panel
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS request_history (
+            id TEXT PRIMARY KEY,
+            endpoint_id TEXT,
+            method TEXT NOT NULL,
+            url TEXT NOT NULL,
+            headers TEXT NOT NULL DEFAULT '{}',
+            body TEXT NOT NULL DEFAULT 'null',
+            status INTEGER,
+            duration_ms INTEGER NOT NULL DEFAULT 0,
+            error TEXT,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    let _ = conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_request_history_endpoint ON request_history(endpoint_id, created_at)",
+        [],
+    );
+
+    // Endpoint health checks - one row per endpoint per check, kept for trends
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS endpoint_health_checks (
+            project_id TEXT NOT NULL,
+            endpoint_id TEXT NOT NULL,
+            method TEXT NOT NULL,
+            path TEXT NOT NULL,
+            url TEXT NOT NULL,
+            status INTEGER,
+            latency_ms INTEGER,
+            reachable INTEGER NOT NULL,
+            schema_valid INTEGER,
+            skipped INTEGER NOT NULL DEFAULT 0,
+            error TEXT,
+            checked_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    let _ = conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_endpoint_health_endpoint ON endpoint_health_checks(endpoint_id, checked_at)",
+        [],
+    );
+
+    // Saved requests table - named request presets independent of scenarios
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS saved_requests (
+            id TEXT PRIMARY KEY,
+            project_id TEXT,
+            endpoint_id TEXT,
+            name TEXT NOT NULL,
+            method TEXT NOT NULL,
+            url TEXT NOT NULL,
+            headers TEXT NOT NULL DEFAULT '{}',
+            params TEXT NOT NULL DEFAULT 'null',
+            body TEXT NOT NULL DEFAULT 'null',
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Assertion templates table - named assertion sets reused across steps
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS assertion_templates (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            description TEXT,
+            assertions TEXT NOT NULL DEFAULT '[]',
+            parameters TEXT NOT NULL DEFAULT '{}',
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            UNIQUE (project_id, name),
+            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Golden responses table - expected example response per endpoint and status code
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS golden_responses (
+            endpoint_id TEXT NOT NULL,
+            status_code INTEGER NOT NULL,
+            body TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (endpoint_id, status_code)
+        )",
+        [],
+    )?;
+
+    // Assertion snapshots table - stored bodies for snapshot assertions
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS assertion_snapshots (
+            scenario_id TEXT NOT NULL,
+            step_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            body TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (scenario_id, step_id, name)
+        )",
+        [],
+    )?;
+
+    // Scenario schedules table - repeating runs resumed at startup
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS scenario_schedules (
+            scenario_id TEXT PRIMARY KEY,
+            interval_minutes INTEGER NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            FOREIGN KEY (scenario_id) REFERENCES test_scenarios(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
     // App state table - stores application-level state like active project
     conn.execute(
         "CREATE TABLE IF NOT EXISTS app_state (
@@ -257,12 +503,12 @@ pub fn init_database() -> Result<()> {
     Ok(())
 }
 
-pub fn get_all_endpoints() -> Result<Vec<ApiEndpoint>, String> {
+pub fn get_all_endpoints() -> Result<Vec<ApiEndpoint>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB connection error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
 
-    let mut stmt = conn.prepare("SELECT id, project_id, name, method, path, service, description, category, parameters, explanation, responses FROM endpoints")
-        .map_err(|e| format!("Prepare error: {}", e))?;
+    let mut stmt = conn.prepare("SELECT id, project_id, name, method, path, service, description, category, parameters, explanation, responses, version, user_modified, stale, auth_required, auth_type, warnings FROM endpoints")
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let endpoints = stmt.query_map([], |row| {
         let params_json: String = row.get(8)?;
@@ -285,31 +531,43 @@ pub fn get_all_endpoints() -> Result<Vec<ApiEndpoint>, String> {
             parameters,
             explanation: row.get(9)?,
             responses: Some(responses),
+            version: row.get(11)?,
+            user_modified: row.get::<_, Option<i32>>(12)?.unwrap_or(0) != 0,
+            stale: row.get::<_, Option<i32>>(13)?.unwrap_or(0) != 0,
+            auth_required: row.get::<_, Option<i32>>(14)?.unwrap_or(0) != 0,
+            auth_type: row.get(15)?,
+            warnings: row
+                .get::<_, Option<String>>(16)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(endpoints)
 }
 
-pub fn save_endpoint(endpoint: ApiEndpoint) -> Result<(), String> {
+pub fn save_endpoint(endpoint: ApiEndpoint) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let params_json = serde_json::to_string(&endpoint.parameters)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     let responses_json = serde_json::to_string(&endpoint.responses.unwrap_or_default())
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    let warnings_json = serde_json::to_string(&endpoint.warnings)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     let now = chrono::Utc::now().timestamp();
 
     conn.execute(
         "INSERT OR REPLACE INTO endpoints
-        (id, project_id, name, method, path, service, description, category, parameters, explanation, responses, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        (id, project_id, name, method, path, service, description, category, parameters, explanation, responses, version, user_modified, stale, auth_required, auth_type, warnings, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             endpoint.id,
             endpoint.project_id,
@@ -322,44 +580,73 @@ pub fn save_endpoint(endpoint: ApiEndpoint) -> Result<(), String> {
             params_json,
             endpoint.explanation,
             responses_json,
+            endpoint.version,
+            endpoint.user_modified as i32,
+            endpoint.stale as i32,
+            endpoint.auth_required as i32,
+            endpoint.auth_type,
+            warnings_json,
             now
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
 // Project management functions
-pub fn save_project(project: Project) -> Result<(), String> {
+pub fn save_project(project: Project) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let default_headers_json = serde_json::to_string(&project.default_headers)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let request_signing_json = project.request_signing.as_ref()
+        .map(crate::request_signing::RequestSigningConfig::to_stored_json)
+        .transpose()
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let variables_json = serde_json::to_string(&project.variables)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let idempotency_json = project.idempotency.as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let body_limits_json = serde_json::to_string(&project.body_limits)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
-        "INSERT OR REPLACE INTO projects (id, name, path, created_at, last_scanned, base_url)
-        VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT OR REPLACE INTO projects (id, name, path, created_at, last_scanned, base_url, verify_tls, user_agent, default_headers, request_logging, request_signing, variables, idempotency, body_limits)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             project.id,
             project.name,
             project.path,
             project.created_at,
             project.last_scanned,
-            project.base_url
+            project.base_url,
+            project.verify_tls as i32,
+            project.user_agent,
+            default_headers_json,
+            project.request_logging as i32,
+            request_signing_json,
+            variables_json,
+            idempotency_json,
+            body_limits_json
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_all_projects() -> Result<Vec<Project>, String> {
+pub fn get_all_projects() -> Result<Vec<Project>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB connection error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
 
-    let mut stmt = conn.prepare("SELECT id, name, path, created_at, last_scanned, base_url FROM projects ORDER BY created_at DESC")
-        .map_err(|e| format!("Prepare error: {}", e))?;
+    let mut stmt = conn.prepare("SELECT id, name, path, created_at, last_scanned, base_url, verify_tls, user_agent, default_headers, request_logging, request_signing, variables, idempotency, body_limits FROM projects ORDER BY created_at DESC")
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
-    let projects = stmt.query_map([], |row| {
+    let mut projects = stmt.query_map([], |row| {
         Ok(Project {
             id: row.get(0)?,
             name: row.get(1)?,
@@ -367,22 +654,43 @@ pub fn get_all_projects() -> Result<Vec<Project>, String> {
             created_at: row.get(3)?,
             last_scanned: row.get(4)?,
             base_url: row.get(5)?,
+            verify_tls: row.get::<_, Option<i32>>(6)?.unwrap_or(1) != 0,
+            user_agent: row.get(7)?,
+            default_headers: row.get::<_, Option<String>>(8)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            request_logging: row.get::<_, Option<i32>>(9)?.unwrap_or(0) != 0,
+            services: Vec::new(),
+            request_signing: row.get::<_, Option<String>>(10)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            variables: row.get::<_, Option<String>>(11)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            idempotency: row.get::<_, Option<String>>(12)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            body_limits: row.get::<_, Option<String>>(13)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    for project in projects.iter_mut() {
+        project.services = load_project_services(&conn, &project.id)?;
+    }
 
     Ok(projects)
 }
 
 /// Get a single project by ID
-pub fn get_project(project_id: &str) -> Result<Option<Project>, String> {
+pub fn get_project(project_id: &str) -> Result<Option<Project>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB connection error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
 
-    let mut stmt = conn.prepare("SELECT id, name, path, created_at, last_scanned, base_url FROM projects WHERE id = ?")
-        .map_err(|e| format!("Prepare error: {}", e))?;
+    let mut stmt = conn.prepare("SELECT id, name, path, created_at, last_scanned, base_url, verify_tls, user_agent, default_headers, request_logging, request_signing, variables, idempotency, body_limits FROM projects WHERE id = ?")
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let project_result = stmt.query_row([project_id], |row| {
         Ok(Project {
@@ -392,46 +700,123 @@ pub fn get_project(project_id: &str) -> Result<Option<Project>, String> {
             created_at: row.get(3)?,
             last_scanned: row.get(4)?,
             base_url: row.get(5)?,
+            verify_tls: row.get::<_, Option<i32>>(6)?.unwrap_or(1) != 0,
+            user_agent: row.get(7)?,
+            default_headers: row.get::<_, Option<String>>(8)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            request_logging: row.get::<_, Option<i32>>(9)?.unwrap_or(0) != 0,
+            services: Vec::new(),
+            request_signing: row.get::<_, Option<String>>(10)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            variables: row.get::<_, Option<String>>(11)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            idempotency: row.get::<_, Option<String>>(12)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            body_limits: row.get::<_, Option<String>>(13)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
         })
     });
 
     match project_result {
-        Ok(p) => Ok(Some(p)),
+        Ok(mut p) => {
+            p.services = load_project_services(&conn, &p.id)?;
+            Ok(Some(p))
+        }
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
-pub fn delete_project(project_id: String) -> Result<(), String> {
+pub fn delete_project(project_id: String) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     // Delete associated endpoints first
     conn.execute(
         "DELETE FROM endpoints WHERE project_id = ?",
         rusqlite::params![project_id],
     )
-    .map_err(|e| format!("Delete endpoints error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete endpoints error: {}", e)))?;
+
+    conn.execute(
+        "DELETE FROM project_services WHERE project_id = ?",
+        rusqlite::params![project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Delete project error: {}", e)))?;
+
+    // Foreign keys are not enforced on these connections, so ON DELETE CASCADE never fires
+    conn.execute(
+        "DELETE FROM saved_requests WHERE project_id = ?",
+        rusqlite::params![project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Delete saved requests error: {}", e)))?;
 
     // Delete project
     conn.execute(
         "DELETE FROM projects WHERE id = ?",
         rusqlite::params![project_id],
     )
-    .map_err(|e| format!("Delete project error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete project error: {}", e)))?;
+
+    Ok(())
+}
+
+fn load_project_services(conn: &Connection, project_id: &str) -> Result<Vec<crate::types::ProjectService>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT name, base_url FROM project_services WHERE project_id = ? ORDER BY position"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let services = stmt.query_map([project_id], |row| {
+        Ok(crate::types::ProjectService {
+            name: row.get(0)?,
+            base_url: row.get(1)?,
+        })
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(services)
+}
+
+/// Replace the named service base URLs of a project
+pub fn save_project_services(project_id: &str, services: &[crate::types::ProjectService]) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+
+    tx.execute(
+        "DELETE FROM project_services WHERE project_id = ?",
+        rusqlite::params![project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+
+    for (position, service) in services.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO project_services (project_id, name, base_url, position) VALUES (?, ?, ?, ?)",
+            rusqlite::params![project_id, service.name, service.base_url, position as i64],
+        )
+        .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+    }
 
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
     Ok(())
 }
 
-pub fn get_endpoints_by_project(project_id: String) -> Result<Vec<ApiEndpoint>, String> {
+pub fn get_endpoints_by_project(project_id: String) -> Result<Vec<ApiEndpoint>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB connection error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, project_id, name, method, path, service, description, category, parameters, explanation, responses 
+        "SELECT id, project_id, name, method, path, service, description, category, parameters, explanation, responses, version, user_modified, stale, auth_required, auth_type, warnings
          FROM endpoints WHERE project_id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let endpoints = stmt.query_map([&project_id], |row| {
         let params_json: String = row.get(8)?;
@@ -454,31 +839,197 @@ pub fn get_endpoints_by_project(project_id: String) -> Result<Vec<ApiEndpoint>,
             parameters,
             explanation: row.get(9)?,
             responses: Some(responses),
+            version: row.get(11)?,
+            user_modified: row.get::<_, Option<i32>>(12)?.unwrap_or(0) != 0,
+            stale: row.get::<_, Option<i32>>(13)?.unwrap_or(0) != 0,
+            auth_required: row.get::<_, Option<i32>>(14)?.unwrap_or(0) != 0,
+            auth_type: row.get(15)?,
+            warnings: row
+                .get::<_, Option<String>>(16)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(endpoints)
 }
 
-pub fn clear_project_endpoints(project_id: &str) -> Result<(), String> {
+pub fn get_endpoint(id: &str) -> Result<ApiEndpoint, AppError> {
+    get_all_endpoints()?
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| AppError::NotFound(format!("Endpoint not found: {}", id)))
+}
+
+pub fn get_endpoints_by_version(project_id: &str, version: &str) -> Result<Vec<ApiEndpoint>, AppError> {
+    let endpoints = get_endpoints_by_project(project_id.to_string())?;
+    let version = crate::scanner::parsers::normalize_version(version);
+
+    Ok(endpoints
+        .into_iter()
+        .filter(|e| e.version.as_deref() == Some(version.as_str()))
+        .collect())
+}
+
+/// Save a fresh scan of a project by merging it into the stored endpoints. Matched endpoints
+/// keep their ids, user-modified ones keep their edits, and stored endpoints the scan no
+/// longer finds are marked stale instead of deleted. Returns the scanned endpoints as saved.
+pub fn save_scanned_endpoints(project_id: &str, scanned: Vec<ApiEndpoint>) -> Result<Vec<ApiEndpoint>, AppError> {
+    let existing = get_endpoints_by_project(project_id.to_string())?;
+    let (merged, stale) = merge_scanned_endpoints(existing, scanned);
+
+    for endpoint in merged.iter().chain(stale.iter()) {
+        save_endpoint(endpoint.clone())?;
+    }
+    if !stale.is_empty() {
+        log::info!("[DB] Marked {} endpoint(s) stale after re-scan of {}", stale.len(), project_id);
+    }
+
+    Ok(merged)
+}
+
+/// Match scanned endpoints to stored ones by `METHOD:path` (in order, for duplicates);
+/// returns the merged scan and the stored endpoints left unmatched, marked stale
+fn merge_scanned_endpoints(existing: Vec<ApiEndpoint>, scanned: Vec<ApiEndpoint>) -> (Vec<ApiEndpoint>, Vec<ApiEndpoint>) {
+    let mut by_key: std::collections::HashMap<String, Vec<ApiEndpoint>> = std::collections::HashMap::new();
+    for endpoint in existing {
+        by_key.entry(endpoint_scan_key(&endpoint)).or_default().push(endpoint);
+    }
+
+    let merged = scanned
+        .into_iter()
+        .map(|scanned| {
+            let stored = by_key.get_mut(&endpoint_scan_key(&scanned))
+                .filter(|stored| !stored.is_empty())
+                .map(|stored| stored.remove(0));
+            match stored {
+                Some(stored) => merge_endpoint(stored, scanned),
+                None => scanned,
+            }
+        })
+        .collect();
+
+    let mut stale: Vec<ApiEndpoint> = by_key.into_values().flatten().collect();
+    stale.sort_by(|a, b| a.id.cmp(&b.id));
+    for endpoint in &mut stale {
+        endpoint.stale = true;
+    }
+    (merged, stale)
+}
+
+fn endpoint_scan_key(endpoint: &ApiEndpoint) -> String {
+    format!("{}:{}", endpoint.method.to_uppercase(), endpoint.path)
+}
+
+/// A user-modified endpoint keeps its fields and only gains newly discovered parameters and
+/// auth requirements; any other endpoint takes the scan as-is. Both keep the stored id.
+fn merge_endpoint(stored: ApiEndpoint, scanned: ApiEndpoint) -> ApiEndpoint {
+    if !stored.user_modified {
+        return ApiEndpoint { id: stored.id, stale: false, ..scanned };
+    }
+
+    let mut parameters = stored.parameters;
+    for param in scanned.parameters {
+        if !parameters.iter().any(|p| p.name == param.name) {
+            parameters.push(param);
+        }
+    }
+    ApiEndpoint {
+        parameters,
+        responses: stored.responses.or(scanned.responses),
+        version: scanned.version,
+        service: scanned.service,
+        stale: false,
+        auth_required: stored.auth_required || scanned.auth_required,
+        auth_type: stored.auth_type.or(scanned.auth_type),
+        warnings: scanned.warnings,
+        ..stored
+    }
+}
+
+/// Delete endpoints by id in one statement; returns how many were removed
+pub fn delete_endpoints(ids: &[String]) -> Result<usize, AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    delete_endpoints_in(&mut conn, ids)
+}
+
+/// Move endpoints to another category; returns how many were updated
+pub fn update_endpoints_category(ids: &[String], category: &str) -> Result<usize, AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    set_endpoints_column_in(&mut conn, ids, "category", category)
+}
+
+/// Move endpoints to another service; returns how many were updated
+pub fn update_endpoints_service(ids: &[String], service: &str) -> Result<usize, AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    set_endpoints_column_in(&mut conn, ids, "service", service)
+}
+
+/// `?, ?, ?` for an `IN (...)` list of `count` parameters
+fn sql_placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn delete_endpoints_in(conn: &mut Connection, ids: &[String]) -> Result<usize, AppError> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    let deleted = tx.execute(
+        &format!("DELETE FROM endpoints WHERE id IN ({})", sql_placeholders(ids.len())),
+        rusqlite::params_from_iter(ids),
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
+    Ok(deleted)
+}
+
+/// Bulk edits count as manual edits, so later re-scans keep them. `column` is never user input.
+fn set_endpoints_column_in(conn: &mut Connection, ids: &[String], column: &str, value: &str) -> Result<usize, AppError> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    let params = [value.to_string(), chrono::Utc::now().timestamp().to_string()]
+        .into_iter()
+        .chain(ids.iter().cloned());
+    let updated = tx.execute(
+        &format!(
+            "UPDATE endpoints SET {} = ?, user_modified = 1, updated_at = ? WHERE id IN ({})",
+            column,
+            sql_placeholders(ids.len())
+        ),
+        rusqlite::params_from_iter(params),
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
+    Ok(updated)
+}
+
+pub fn clear_project_endpoints(project_id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "DELETE FROM endpoints WHERE project_id = ?",
         rusqlite::params![project_id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn update_project_last_scanned(project_id: &str) -> Result<(), String> {
+pub fn update_project_last_scanned(project_id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let now = chrono::Utc::now().timestamp();
 
@@ -486,93 +1037,306 @@ pub fn update_project_last_scanned(project_id: &str) -> Result<(), String> {
         "UPDATE projects SET last_scanned = ? WHERE id = ?",
         rusqlite::params![now, project_id],
     )
-    .map_err(|e| format!("Update error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn update_project_base_url(project_id: &str, base_url: Option<String>) -> Result<(), String> {
+pub fn update_project_base_url(project_id: &str, base_url: Option<String>) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "UPDATE projects SET base_url = ? WHERE id = ?",
         rusqlite::params![base_url, project_id],
     )
-    .map_err(|e| format!("Update error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_all_test_suites() -> Result<Vec<TestSuite>, String> {
+pub fn update_project_verify_tls(project_id: &str, verify_tls: bool) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
-    let mut stmt = conn.prepare("SELECT id, name, description, endpoints, category FROM test_suites")
-        .map_err(|e| format!("Prepare error: {}", e))?;
+    conn.execute(
+        "UPDATE projects SET verify_tls = ? WHERE id = ?",
+        rusqlite::params![verify_tls as i32, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
 
-    let suites = stmt.query_map([], |row| {
-        let endpoints_json: String = row.get(3)?;
-        let endpoints: Vec<String> = serde_json::from_str(&endpoints_json)
-            .unwrap_or_default();
+    Ok(())
+}
 
-        Ok(TestSuite {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            description: row.get(2)?,
-            endpoints,
-            category: row.get(4)?,
-        })
-    })
-    .map_err(|e| format!("Query error: {}", e))?
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+pub fn update_project_client_defaults(
+    project_id: &str,
+    user_agent: Option<&str>,
+    default_headers: &std::collections::HashMap<String, String>,
+) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
-    Ok(suites)
+    let default_headers_json = serde_json::to_string(default_headers)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    conn.execute(
+        "UPDATE projects SET user_agent = ?, default_headers = ? WHERE id = ?",
+        rusqlite::params![user_agent, default_headers_json, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+
+    Ok(())
 }
 
-pub fn execute_sql_query(db_path: String, query: String) -> Result<QueryResult, String> {
-    let conn = Connection::open(&db_path)
-        .map_err(|e| format!("DB connection error: {}", e))?;
+pub fn update_project_request_signing(
+    project_id: &str,
+    signing: Option<&crate::request_signing::RequestSigningConfig>,
+) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
-    let mut stmt = conn.prepare(&query)
-        .map_err(|e| format!("SQL error: {}", e))?;
+    let signing_json = signing
+        .map(crate::request_signing::RequestSigningConfig::to_stored_json)
+        .transpose()
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    conn.execute(
+        "UPDATE projects SET request_signing = ? WHERE id = ?",
+        rusqlite::params![signing_json, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
 
-    let column_count = stmt.column_count();
-    let columns: Vec<String> = stmt.column_names()
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
+    Ok(())
+}
 
-    let rows: Vec<Vec<serde_json::Value>> = stmt.query_map([], |row| {
-        let mut values = Vec::new();
-        for i in 0..column_count {
-            // Get the raw SQLite value type and convert appropriately
-            let value = match row.get_ref(i) {
-                Ok(value_ref) => {
-                    use rusqlite::types::ValueRef;
-                    match value_ref {
-                        ValueRef::Null => serde_json::Value::Null,
-                        ValueRef::Integer(i) => serde_json::json!(i),
-                        ValueRef::Real(f) => serde_json::json!(f),
-                        ValueRef::Text(s) => {
-                            serde_json::json!(String::from_utf8_lossy(s))
-                        },
-                        ValueRef::Blob(_) => {
-                            // For binary data, return a placeholder string
-                            serde_json::json!("<binary data>")
-                        }
-                    }
-                },
-                Err(_) => serde_json::Value::Null
-            };
+pub fn update_project_idempotency(
+    project_id: &str,
+    idempotency: Option<&IdempotencyConfig>,
+) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let idempotency_json = idempotency
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    conn.execute(
+        "UPDATE projects SET idempotency = ? WHERE id = ?",
+        rusqlite::params![idempotency_json, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+
+    Ok(())
+}
+
+pub fn update_project_body_limits(project_id: &str, body_limits: &BodyLimits) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let body_limits_json = serde_json::to_string(body_limits)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    conn.execute(
+        "UPDATE projects SET body_limits = ? WHERE id = ?",
+        rusqlite::params![body_limits_json, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+
+    Ok(())
+}
+
+pub fn update_project_variables(
+    project_id: &str,
+    variables: &std::collections::HashMap<String, serde_json::Value>,
+) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let variables_json = serde_json::to_string(variables)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    conn.execute(
+        "UPDATE projects SET variables = ? WHERE id = ?",
+        rusqlite::params![variables_json, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+
+    Ok(())
+}
+
+pub fn update_project_request_logging(project_id: &str, enabled: bool) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "UPDATE projects SET request_logging = ? WHERE id = ?",
+        rusqlite::params![enabled as i32, project_id],
+    )
+    .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+
+    Ok(())
+}
+
+/// Compute project overview counts with SQL aggregates
+pub fn get_project_stats(project_id: &str) -> Result<ProjectStats, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
+    project_stats_in(&conn, project_id)
+}
+
+fn project_stats_in(conn: &Connection, project_id: &str) -> Result<ProjectStats, AppError> {
+    let last_scanned: Option<i64> = match conn.query_row(
+        "SELECT last_scanned FROM projects WHERE id = ?",
+        [project_id],
+        |row| row.get(0),
+    ) {
+        Ok(v) => v,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(AppError::NotFound(format!("Project not found: {}", project_id)));
+        }
+        Err(e) => return Err(AppError::Db(format!("Query error: {}", e))),
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT UPPER(method), COUNT(*) FROM endpoints WHERE project_id = ? GROUP BY UPPER(method)",
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let method_counts = stmt.query_map([project_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collect error: {}", e)))?;
+
+    let total_endpoints = method_counts.iter().map(|(_, c)| c).sum();
+    let endpoints_by_method = method_counts.into_iter().collect();
+
+    let endpoints_requiring_auth: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM endpoints WHERE project_id = ? AND auth_required = 1",
+        [project_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
+
+    let scenario_count: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM test_scenarios WHERE project_id = ?",
+        [project_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
+
+    let security_test_case_count: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM security_test_cases WHERE project_id = ?",
+        [project_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
+
+    let last_run = conn.query_row(
+        "SELECT r.passed_steps, r.total_steps FROM test_scenario_runs r
+         JOIN test_scenarios s ON r.scenario_id = s.id
+         WHERE s.project_id = ?
+         ORDER BY r.started_at DESC LIMIT 1",
+        [project_id],
+        |row| Ok((row.get::<_, u32>(0)?, row.get::<_, u32>(1)?)),
+    );
+
+    // A run without steps has no pass rate
+    let last_run_pass_rate = match last_run {
+        Ok((passed, total)) if total > 0 => Some(passed as f64 / total as f64 * 100.0),
+        Ok(_) | Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(AppError::Db(format!("Query error: {}", e))),
+    };
+
+    Ok(ProjectStats {
+        project_id: project_id.to_string(),
+        total_endpoints,
+        endpoints_by_method,
+        endpoints_requiring_auth,
+        scenario_count,
+        security_test_case_count,
+        last_scanned,
+        last_run_pass_rate,
+    })
+}
+
+pub fn get_all_test_suites() -> Result<Vec<TestSuite>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare("SELECT id, name, description, endpoints, category FROM test_suites")
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let suites = stmt.query_map([], |row| {
+        let endpoints_json: String = row.get(3)?;
+        let endpoints: Vec<String> = serde_json::from_str(&endpoints_json)
+            .unwrap_or_default();
+
+        Ok(TestSuite {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            endpoints,
+            category: row.get(4)?,
+        })
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(suites)
+}
+
+/// Run a SQL console query; in read-only mode statements that would write are rejected
+pub fn execute_sql_query(db_path: String, query: String, read_only: bool) -> Result<QueryResult, AppError> {
+    let conn = Connection::open(&db_path)
+        .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
+
+    if read_only {
+        conn.execute_batch("PRAGMA query_only = ON")
+            .map_err(|e| AppError::Db(format!("DB connection error: {}", e)))?;
+    }
+
+    let mut stmt = conn.prepare(&query)
+        .map_err(|e| AppError::Db(format!("SQL error: {}", e)))?;
+
+    if read_only && !stmt.readonly() {
+        return Err(AppError::Validation("Read-only mode: only statements that don't modify the database can run. Disable read-only mode to execute writes.".to_string()));
+    }
+
+    let column_count = stmt.column_count();
+    let columns: Vec<String> = stmt.column_names()
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let rows: Vec<Vec<serde_json::Value>> = stmt.query_map([], |row| {
+        let mut values = Vec::new();
+        for i in 0..column_count {
+            // Get the raw SQLite value type and convert appropriately
+            let value = match row.get_ref(i) {
+                Ok(value_ref) => {
+                    use rusqlite::types::ValueRef;
+                    match value_ref {
+                        ValueRef::Null => serde_json::Value::Null,
+                        ValueRef::Integer(i) => serde_json::json!(i),
+                        ValueRef::Real(f) => serde_json::json!(f),
+                        ValueRef::Text(s) => {
+                            serde_json::json!(String::from_utf8_lossy(s))
+                        },
+                        ValueRef::Blob(_) => {
+                            // For binary data, return a placeholder string
+                            serde_json::json!("<binary data>")
+                        }
+                    }
+                },
+                Err(_) => serde_json::Value::Null
+            };
             values.push(value);
         }
         Ok(values)
     })
-    .map_err(|e| format!("Query execution error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query execution error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Row collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Row collection error: {}", e)))?;
 
     let row_count = rows.len();
 
@@ -584,12 +1348,12 @@ pub fn execute_sql_query(db_path: String, query: String) -> Result<QueryResult,
 }
 
 // Security test case functions
-pub fn save_security_test_case(test_case: SecurityTestCase) -> Result<(), String> {
+pub fn save_security_test_case(test_case: SecurityTestCase) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let scans_json = serde_json::to_string(&test_case.scans)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
         "INSERT OR REPLACE INTO security_test_cases 
@@ -605,20 +1369,20 @@ pub fn save_security_test_case(test_case: SecurityTestCase) -> Result<(), String
             test_case.updated_at
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_security_test_cases_by_project(project_id: &str) -> Result<Vec<SecurityTestCase>, String> {
+pub fn get_security_test_cases_by_project(project_id: &str) -> Result<Vec<SecurityTestCase>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
         "SELECT id, project_id, name, endpoint_id, scans, created_at, updated_at 
          FROM security_test_cases WHERE project_id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let cases = stmt.query_map([project_id], |row| {
         let scans_json: String = row.get(4)?;
@@ -634,16 +1398,16 @@ pub fn get_security_test_cases_by_project(project_id: &str) -> Result<Vec<Securi
             updated_at: row.get(6)?,
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(cases)
 }
 
-pub fn delete_security_test_case(id: &str) -> Result<(), String> {
+pub fn delete_security_test_case(id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "DELETE FROM security_test_runs WHERE test_case_id = ?",
@@ -654,17 +1418,17 @@ pub fn delete_security_test_case(id: &str) -> Result<(), String> {
         "DELETE FROM security_test_cases WHERE id = ?",
         rusqlite::params![id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn save_security_test_run(run: &SecurityTestRun) -> Result<(), String> {
+pub fn save_security_test_run(run: &SecurityTestRun) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let results_json = serde_json::to_string(&run.results)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     let status_str = match run.status {
         crate::security::types::ScanStatus::Pass => "Pass",
@@ -691,20 +1455,20 @@ pub fn save_security_test_run(run: &SecurityTestRun) -> Result<(), String> {
             run.completed_at
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_security_test_runs(test_case_id: &str) -> Result<Vec<SecurityTestRun>, String> {
+pub fn get_security_test_runs(test_case_id: &str) -> Result<Vec<SecurityTestRun>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
         "SELECT id, test_case_id, status, total_scans, completed_scans, total_requests, total_alerts, results, started_at, completed_at 
          FROM security_test_runs WHERE test_case_id = ? ORDER BY started_at DESC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let runs = stmt.query_map([test_case_id], |row| {
         let status_str: String = row.get(2)?;
@@ -732,9 +1496,9 @@ pub fn get_security_test_runs(test_case_id: &str) -> Result<Vec<SecurityTestRun>
             completed_at: row.get(9)?,
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(runs)
 }
@@ -744,17 +1508,32 @@ pub fn get_security_test_runs(test_case_id: &str) -> Result<Vec<SecurityTestRun>
 // ============================================================================
 
 /// Save a test scenario to the database
-pub fn save_test_scenario(scenario: TestScenario) -> Result<(), String> {
+pub fn save_test_scenario(scenario: TestScenario) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    insert_scenario(&conn, &scenario)
+}
 
+fn insert_scenario(conn: &Connection, scenario: &TestScenario) -> Result<(), AppError> {
     let variables_json = serde_json::to_string(&scenario.variables)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let chaos_json = match &scenario.chaos {
+        Some(chaos) => Some(serde_json::to_string(chaos)
+            .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?),
+        None => None,
+    };
+    let session_login_json = match &scenario.session_login {
+        Some(login) => Some(serde_json::to_string(login)
+            .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?),
+        None => None,
+    };
+    let environments_json = serde_json::to_string(&scenario.environments)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
         "INSERT OR REPLACE INTO test_scenarios 
-        (id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        (id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at, chaos, session_login, environments)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             scenario.id,
             scenario.project_id,
@@ -765,60 +1544,91 @@ pub fn save_test_scenario(scenario: TestScenario) -> Result<(), String> {
             scenario.pre_script,
             scenario.post_script,
             scenario.created_at,
-            scenario.updated_at
+            scenario.updated_at,
+            chaos_json,
+            session_login_json,
+            environments_json
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
 /// Get all test scenarios for a project
-pub fn get_test_scenarios_by_project(project_id: &str) -> Result<Vec<TestScenario>, String> {
+pub fn get_test_scenarios_by_project(project_id: &str) -> Result<Vec<TestScenario>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at 
+        "SELECT id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at, chaos, session_login, environments 
          FROM test_scenarios WHERE project_id = ? ORDER BY created_at DESC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
-    let scenarios = stmt.query_map([project_id], |row| {
-        let variables_json: String = row.get(5)?;
-        let variables: serde_json::Value = serde_json::from_str(&variables_json)
-            .unwrap_or(serde_json::json!({}));
+    let scenarios = stmt.query_map([project_id], scenario_from_row)
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
-        Ok(TestScenario {
-            id: row.get(0)?,
-            project_id: row.get(1)?,
-            name: row.get(2)?,
-            description: row.get(3)?,
-            priority: row.get(4)?,
-            variables,
-            pre_script: row.get(6)?,
-            post_script: row.get(7)?,
-            created_at: row.get(8)?,
-            updated_at: row.get(9)?,
-        })
-    })
-    .map_err(|e| format!("Query error: {}", e))?
+    Ok(scenarios)
+}
+
+/// One page of a project's scenarios, in the same order as `get_test_scenarios_by_project`
+pub fn get_test_scenarios_page(project_id: &str, limit: usize, offset: usize) -> Result<Vec<TestScenario>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at, chaos, session_login, environments 
+         FROM test_scenarios WHERE project_id = ? ORDER BY created_at DESC, id LIMIT ? OFFSET ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let scenarios = stmt.query_map(rusqlite::params![project_id, limit as i64, offset as i64], scenario_from_row)
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(scenarios)
 }
 
+fn scenario_from_row(row: &rusqlite::Row) -> rusqlite::Result<TestScenario> {
+    let variables_json: String = row.get(5)?;
+    let variables: serde_json::Value = serde_json::from_str(&variables_json)
+        .unwrap_or(serde_json::json!({}));
+
+    Ok(TestScenario {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        name: row.get(2)?,
+        description: row.get(3)?,
+        priority: row.get(4)?,
+        variables,
+        pre_script: row.get(6)?,
+        post_script: row.get(7)?,
+        chaos: row.get::<_, Option<String>>(10)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        session_login: row.get::<_, Option<String>>(11)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        environments: row.get::<_, Option<String>>(12)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
 /// Get a single test scenario by ID
-pub fn get_test_scenario(scenario_id: &str) -> Result<Option<TestScenario>, String> {
+pub fn get_test_scenario(scenario_id: &str) -> Result<Option<TestScenario>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at 
+        "SELECT id, project_id, name, description, priority, variables, pre_script, post_script, created_at, updated_at, chaos, session_login, environments 
          FROM test_scenarios WHERE id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let scenario = stmt.query_row([scenario_id], |row| {
         let variables_json: String = row.get(5)?;
@@ -834,6 +1644,13 @@ pub fn get_test_scenario(scenario_id: &str) -> Result<Option<TestScenario>, Stri
             variables,
             pre_script: row.get(6)?,
             post_script: row.get(7)?,
+            chaos: row.get::<_, Option<String>>(10)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            session_login: row.get::<_, Option<String>>(11)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            environments: row.get::<_, Option<String>>(12)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
             created_at: row.get(8)?,
             updated_at: row.get(9)?,
         })
@@ -842,14 +1659,14 @@ pub fn get_test_scenario(scenario_id: &str) -> Result<Option<TestScenario>, Stri
     match scenario {
         Ok(s) => Ok(Some(s)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
 /// Delete a test scenario and all its steps
-pub fn delete_test_scenario(scenario_id: &str) -> Result<(), String> {
+pub fn delete_test_scenario(scenario_id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     // Delete associated runs first
     conn.execute(
@@ -863,28 +1680,43 @@ pub fn delete_test_scenario(scenario_id: &str) -> Result<(), String> {
         rusqlite::params![scenario_id],
     ).ok();
 
+    // Delete associated snapshots
+    conn.execute(
+        "DELETE FROM assertion_snapshots WHERE scenario_id = ?",
+        rusqlite::params![scenario_id],
+    ).ok();
+
+    // Delete the schedule so it is not resumed at startup
+    conn.execute(
+        "DELETE FROM scenario_schedules WHERE scenario_id = ?",
+        rusqlite::params![scenario_id],
+    ).ok();
+
     // Delete scenario
     conn.execute(
         "DELETE FROM test_scenarios WHERE id = ?",
         rusqlite::params![scenario_id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     Ok(())
 }
 
 /// Save a test scenario step
-pub fn save_test_scenario_step(step: TestScenarioStep) -> Result<(), String> {
+pub fn save_test_scenario_step(step: TestScenarioStep) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    insert_step(&conn, &step)
+}
 
+fn insert_step(conn: &Connection, step: &TestScenarioStep) -> Result<(), AppError> {
     let config_json = serde_json::to_string(&step.config)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
         "INSERT OR REPLACE INTO test_scenario_steps 
-        (id, scenario_id, step_order, step_type, name, config, enabled)
-        VALUES (?, ?, ?, ?, ?, ?, ?)",
+        (id, scenario_id, step_order, step_type, name, config, enabled, enabled_if, step_group)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             step.id,
             step.scenario_id,
@@ -892,24 +1724,111 @@ pub fn save_test_scenario_step(step: TestScenarioStep) -> Result<(), String> {
             step.step_type.as_str(),
             step.name,
             config_json,
-            step.enabled as i32
+            step.enabled as i32,
+            step.enabled_if,
+            step.group
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
+/// Save several scenarios with their steps in one transaction: either all are stored or,
+/// on the first failure, none are. `on_progress` is called after each scenario with its index.
+pub fn import_test_scenarios(
+    scenarios: &[(TestScenario, Vec<TestScenarioStep>)],
+    on_progress: impl FnMut(usize, &TestScenario),
+) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    import_scenarios_in(&mut conn, scenarios, on_progress)
+}
+
+/// Save one scenario and its steps in one transaction, so an import never leaves a
+/// scenario with only some of its steps
+pub fn save_test_scenario_with_steps(scenario: &TestScenario, steps: &[TestScenarioStep]) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    save_scenario_with_steps_in(&mut conn, scenario, steps)
+}
+
+fn save_scenario_with_steps_in(
+    conn: &mut Connection,
+    scenario: &TestScenario,
+    steps: &[TestScenarioStep],
+) -> Result<(), AppError> {
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    insert_scenario(&tx, scenario)?;
+    for step in steps {
+        insert_step(&tx, step)
+            .map_err(|e| e.with_context(format!("Step '{}'", step.name)))?;
+    }
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))
+}
+
+/// Update a scenario from YAML in one transaction: delete `removed_step_ids`, then store the
+/// scenario and `steps`. A failure leaves the scenario and all its old steps in place.
+pub fn replace_test_scenario_steps(
+    scenario: &TestScenario,
+    removed_step_ids: &[String],
+    steps: &[TestScenarioStep],
+) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    replace_scenario_steps_in(&mut conn, scenario, removed_step_ids, steps)
+}
+
+fn replace_scenario_steps_in(
+    conn: &mut Connection,
+    scenario: &TestScenario,
+    removed_step_ids: &[String],
+    steps: &[TestScenarioStep],
+) -> Result<(), AppError> {
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    for step_id in removed_step_ids {
+        tx.execute("DELETE FROM test_scenario_steps WHERE id = ?", rusqlite::params![step_id])
+            .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+    }
+    insert_scenario(&tx, scenario)?;
+    for step in steps {
+        insert_step(&tx, step)
+            .map_err(|e| e.with_context(format!("Step '{}'", step.name)))?;
+    }
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))
+}
+
+fn import_scenarios_in(
+    conn: &mut Connection,
+    scenarios: &[(TestScenario, Vec<TestScenarioStep>)],
+    mut on_progress: impl FnMut(usize, &TestScenario),
+) -> Result<(), AppError> {
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    for (index, (scenario, steps)) in scenarios.iter().enumerate() {
+        insert_scenario(&tx, scenario)
+            .map_err(|e| e.with_context(format!("Scenario '{}'", scenario.name)))?;
+        for step in steps {
+            insert_step(&tx, step)
+                .map_err(|e| e.with_context(format!("Scenario '{}', step '{}'", scenario.name, step.name)))?;
+        }
+        on_progress(index, scenario);
+    }
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))
+}
+
 /// Get all steps for a scenario
-pub fn get_test_scenario_steps(scenario_id: &str) -> Result<Vec<TestScenarioStep>, String> {
+pub fn get_test_scenario_steps(scenario_id: &str) -> Result<Vec<TestScenarioStep>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, scenario_id, step_order, step_type, name, config, enabled 
+        "SELECT id, scenario_id, step_order, step_type, name, config, enabled, enabled_if, step_group 
          FROM test_scenario_steps WHERE scenario_id = ? ORDER BY step_order ASC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let steps = stmt.query_map([scenario_id], |row| {
         let config_json: String = row.get(5)?;
@@ -926,25 +1845,27 @@ pub fn get_test_scenario_steps(scenario_id: &str) -> Result<Vec<TestScenarioStep
             name: row.get(4)?,
             config,
             enabled: enabled != 0,
+            enabled_if: row.get(7)?,
+            group: row.get(8)?,
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(steps)
 }
 
 /// Get a test scenario step by ID
-pub fn get_test_scenario_step_by_id(step_id: &str) -> Result<Option<TestScenarioStep>, String> {
+pub fn get_test_scenario_step_by_id(step_id: &str) -> Result<Option<TestScenarioStep>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, scenario_id, step_order, step_type, name, config, enabled 
+        "SELECT id, scenario_id, step_order, step_type, name, config, enabled, enabled_if, step_group 
          FROM test_scenario_steps WHERE id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let step_result = stmt.query_row([step_id], |row| {
         let config_json: String = row.get(5)?;
@@ -961,62 +1882,121 @@ pub fn get_test_scenario_step_by_id(step_id: &str) -> Result<Option<TestScenario
             name: row.get(4)?,
             config,
             enabled: enabled != 0,
+            enabled_if: row.get(7)?,
+            group: row.get(8)?,
         })
     });
 
     match step_result {
         Ok(step) => Ok(Some(step)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
 /// Delete a test scenario step
-pub fn delete_test_scenario_step(step_id: &str) -> Result<(), String> {
+pub fn delete_test_scenario_step(step_id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "DELETE FROM test_scenario_steps WHERE id = ?",
         rusqlite::params![step_id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     Ok(())
 }
 
 /// Reorder steps in a scenario
-pub fn reorder_test_scenario_steps(scenario_id: &str, step_ids: &[String]) -> Result<(), String> {
-    let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+/// Reorder steps atomically; fails without changes if any id is not a step of the scenario
+pub fn reorder_test_scenario_steps(scenario_id: &str, step_ids: &[String]) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    reorder_steps_in(&mut conn, scenario_id, step_ids)
+}
+
+/// Enable or disable several steps of a scenario at once, atomically
+pub fn set_test_scenario_steps_enabled(scenario_id: &str, step_ids: &[String], enabled: bool) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    set_steps_enabled_in(&mut conn, scenario_id, step_ids, enabled)
+}
+
+fn reorder_steps_in(conn: &mut Connection, scenario_id: &str, step_ids: &[String]) -> Result<(), AppError> {
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    ensure_steps_belong_to_scenario(&tx, scenario_id, step_ids)?;
 
     for (index, step_id) in step_ids.iter().enumerate() {
-        conn.execute(
+        tx.execute(
             "UPDATE test_scenario_steps SET step_order = ? WHERE id = ? AND scenario_id = ?",
             rusqlite::params![index as i32, step_id, scenario_id],
         )
-        .map_err(|e| format!("Update error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
     }
 
-    Ok(())
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))
+}
+
+fn set_steps_enabled_in(conn: &mut Connection, scenario_id: &str, step_ids: &[String], enabled: bool) -> Result<(), AppError> {
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    ensure_steps_belong_to_scenario(&tx, scenario_id, step_ids)?;
+
+    for step_id in step_ids {
+        tx.execute(
+            "UPDATE test_scenario_steps SET enabled = ? WHERE id = ? AND scenario_id = ?",
+            rusqlite::params![enabled as i32, step_id, scenario_id],
+        )
+        .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
+    }
+
+    tx.commit().map_err(|e| AppError::Db(format!("Commit error: {}", e)))
+}
+
+fn ensure_steps_belong_to_scenario(conn: &Connection, scenario_id: &str, step_ids: &[String]) -> Result<(), AppError> {
+    let mut stmt = conn.prepare("SELECT id FROM test_scenario_steps WHERE scenario_id = ?")
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+    let known: std::collections::HashSet<String> = stmt.query_map([scenario_id], |row| row.get(0))
+        .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    let unknown: Vec<&str> = step_ids
+        .iter()
+        .filter(|id| !known.contains(id.as_str()))
+        .map(|id| id.as_str())
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("Invalid step ids for scenario {}: {}", scenario_id, unknown.join(", "))))
+    }
 }
 
 /// Save a test scenario run
-pub fn save_test_scenario_run(run: &TestScenarioRun) -> Result<(), String> {
+pub fn save_test_scenario_run(run: &TestScenarioRun) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    insert_scenario_run(&conn, run)
+}
 
+fn insert_scenario_run(conn: &Connection, run: &TestScenarioRun) -> Result<(), AppError> {
     let results_json = serde_json::to_string(&run.results)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
     
     let variables_json = serde_json::to_string(&run.variables)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    let logs_json = serde_json::to_string(&run.logs)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
         "INSERT INTO test_scenario_runs 
         (id, scenario_id, status, total_steps, passed_steps, failed_steps, skipped_steps, 
-         duration_ms, started_at, completed_at, error_message, results, variables)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+         duration_ms, started_at, completed_at, error_message, results, variables, seed, logs, run_trigger)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             run.id,
             run.scenario_id,
@@ -1030,145 +2010,229 @@ pub fn save_test_scenario_run(run: &TestScenarioRun) -> Result<(), String> {
             run.completed_at,
             run.error_message,
             results_json,
-            variables_json
+            variables_json,
+            run.seed.map(|s| s as i64),
+            logs_json,
+            run.trigger.as_str()
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
 /// Get test scenario runs for a scenario
-pub fn get_test_scenario_runs(scenario_id: &str) -> Result<Vec<TestScenarioRun>, String> {
+pub fn get_test_scenario_runs(scenario_id: &str) -> Result<Vec<TestScenarioRun>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
         "SELECT id, scenario_id, status, total_steps, passed_steps, failed_steps, skipped_steps,
-                duration_ms, started_at, completed_at, error_message, results, variables
+                duration_ms, started_at, completed_at, error_message, results, variables, seed, logs, run_trigger
          FROM test_scenario_runs WHERE scenario_id = ? ORDER BY started_at DESC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
-    let runs = stmt.query_map([scenario_id], |row| {
-        let status_str: String = row.get(2)?;
-        let results_json: String = row.get(11)?;
-        let variables_json: String = row.get(12)?;
+    let runs = stmt.query_map([scenario_id], row_to_test_scenario_run)
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
-        Ok(TestScenarioRun {
-            id: row.get(0)?,
-            scenario_id: row.get(1)?,
-            status: ScenarioRunStatus::from_str(&status_str),
-            total_steps: row.get(3)?,
-            passed_steps: row.get(4)?,
-            failed_steps: row.get(5)?,
-            skipped_steps: row.get(6)?,
-            duration_ms: row.get(7)?,
-            started_at: row.get(8)?,
-            completed_at: row.get(9)?,
-            error_message: row.get(10)?,
-            results: serde_json::from_str(&results_json).unwrap_or_default(),
-            variables: serde_json::from_str(&variables_json).unwrap_or_default(),
-        })
-    })
-    .map_err(|e| format!("Query error: {}", e))?
+    Ok(runs)
+}
+
+/// Get the most recent runs started by a scenario's schedule, newest first
+pub fn get_scheduled_scenario_runs(scenario_id: &str, limit: usize) -> Result<Vec<TestScenarioRun>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    scheduled_runs_in(&conn, scenario_id, limit)
+}
+
+fn scheduled_runs_in(conn: &Connection, scenario_id: &str, limit: usize) -> Result<Vec<TestScenarioRun>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, scenario_id, status, total_steps, passed_steps, failed_steps, skipped_steps,
+                duration_ms, started_at, completed_at, error_message, results, variables, seed, logs, run_trigger
+         FROM test_scenario_runs WHERE scenario_id = ? AND run_trigger = 'schedule'
+         ORDER BY started_at DESC LIMIT ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let runs = stmt.query_map(rusqlite::params![scenario_id, limit as i64], row_to_test_scenario_run)
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(runs)
 }
 
+/// Get a single test scenario run by id
+pub fn get_test_scenario_run(run_id: &str) -> Result<Option<TestScenarioRun>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, scenario_id, status, total_steps, passed_steps, failed_steps, skipped_steps,
+                duration_ms, started_at, completed_at, error_message, results, variables, seed, logs, run_trigger
+         FROM test_scenario_runs WHERE id = ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    match stmt.query_row([run_id], row_to_test_scenario_run) {
+        Ok(run) => Ok(Some(run)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
+    }
+}
+
+fn row_to_test_scenario_run(row: &rusqlite::Row) -> rusqlite::Result<TestScenarioRun> {
+    let status_str: String = row.get(2)?;
+    let results_json: String = row.get(11)?;
+    let variables_json: String = row.get(12)?;
+    let results: Vec<TestStepResult> = serde_json::from_str(&results_json).unwrap_or_default();
+
+    Ok(TestScenarioRun {
+        id: row.get(0)?,
+        scenario_id: row.get(1)?,
+        status: ScenarioRunStatus::from_str(&status_str),
+        total_steps: row.get(3)?,
+        passed_steps: row.get(4)?,
+        failed_steps: row.get(5)?,
+        skipped_steps: row.get(6)?,
+        duration_ms: row.get(7)?,
+        started_at: row.get(8)?,
+        completed_at: row.get(9)?,
+        error_message: row.get(10)?,
+        groups: StepGroupSummary::from_results(&results),
+        results,
+        variables: serde_json::from_str(&variables_json).unwrap_or_default(),
+        seed: row.get::<_, Option<i64>>(13)?.map(|s| s as u64),
+        snapshot_updates: Vec::new(),
+        logs: row.get::<_, Option<String>>(14)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        trigger: RunTrigger::parse(row.get::<_, Option<String>>(15)?.as_deref()),
+    })
+}
+
 // ============================================================================
 // YAML Files Functions
 // ============================================================================
 
 /// Save a YAML file to the database
-pub fn save_yaml_file(yaml_file: YamlFile) -> Result<(), String> {
+pub fn save_yaml_file(yaml_file: YamlFile) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    insert_yaml_file(&conn, &yaml_file)
+}
+
+fn insert_yaml_file(conn: &Connection, yaml_file: &YamlFile) -> Result<(), AppError> {
+    let provenance = yaml_file.provenance.as_ref();
+    let endpoint_ids_json = match provenance {
+        Some(p) => Some(serde_json::to_string(&p.endpoint_ids)
+            .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?),
+        None => None,
+    };
 
     conn.execute(
         "INSERT OR REPLACE INTO yaml_files 
-        (id, project_id, scenario_id, content, created_at)
-        VALUES (?, ?, ?, ?, ?)",
+        (id, project_id, scenario_id, content, created_at, ai_prompt, ai_endpoint_ids, ai_backend, ai_base_url, ai_generated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             yaml_file.id,
             yaml_file.project_id,
             yaml_file.scenario_id,
             yaml_file.content,
-            yaml_file.created_at
+            yaml_file.created_at,
+            provenance.map(|p| p.prompt.clone()),
+            endpoint_ids_json,
+            provenance.map(|p| p.backend.clone()),
+            provenance.and_then(|p| p.base_url.clone()),
+            provenance.map(|p| p.generated_at)
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
-/// Get all YAML files for a project
-pub fn get_yaml_files_by_project(project_id: &str) -> Result<Vec<YamlFile>, String> {
-    let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+/// Map a yaml_files row selected with YAML_FILE_COLUMNS
+fn row_to_yaml_file(row: &rusqlite::Row) -> rusqlite::Result<YamlFile> {
+    let backend: Option<String> = row.get(7)?;
+    let provenance = match backend {
+        Some(backend) => {
+            let endpoint_ids: Option<String> = row.get(6)?;
+            Some(YamlProvenance {
+                prompt: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                endpoint_ids: endpoint_ids
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                backend,
+                base_url: row.get(8)?,
+                generated_at: row.get::<_, Option<i64>>(9)?.unwrap_or(0),
+            })
+        }
+        None => None,
+    };
 
-    let mut stmt = conn.prepare(
-        "SELECT id, project_id, scenario_id, content, created_at 
-         FROM yaml_files WHERE project_id = ? ORDER BY created_at DESC"
-    )
-    .map_err(|e| format!("Prepare error: {}", e))?;
-
-    let files = stmt.query_map([project_id], |row| {
-        Ok(YamlFile {
-            id: row.get(0)?,
-            project_id: row.get(1)?,
-            scenario_id: row.get(2)?,
-            content: row.get(3)?,
-            created_at: row.get(4)?,
-        })
+    Ok(YamlFile {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        scenario_id: row.get(2)?,
+        content: row.get(3)?,
+        created_at: row.get(4)?,
+        provenance,
     })
-    .map_err(|e| format!("Query error: {}", e))?
+}
+
+const YAML_FILE_COLUMNS: &str =
+    "id, project_id, scenario_id, content, created_at, ai_prompt, ai_endpoint_ids, ai_backend, ai_base_url, ai_generated_at";
+
+/// Get all YAML files for a project
+pub fn get_yaml_files_by_project(project_id: &str) -> Result<Vec<YamlFile>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM yaml_files WHERE project_id = ? ORDER BY created_at DESC",
+        YAML_FILE_COLUMNS
+    ))
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let files = stmt.query_map([project_id], row_to_yaml_file)
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(files)
 }
 
 /// Get a single YAML file by ID
-pub fn get_yaml_file(id: &str) -> Result<Option<YamlFile>, String> {
+pub fn get_yaml_file(id: &str) -> Result<Option<YamlFile>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, project_id, scenario_id, content, created_at 
-         FROM yaml_files WHERE id = ?"
-    )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM yaml_files WHERE id = ?", YAML_FILE_COLUMNS))
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
-    let yaml_file = stmt.query_row([id], |row| {
-        Ok(YamlFile {
-            id: row.get(0)?,
-            project_id: row.get(1)?,
-            scenario_id: row.get(2)?,
-            content: row.get(3)?,
-            created_at: row.get(4)?,
-        })
-    });
+    let yaml_file = stmt.query_row([id], row_to_yaml_file);
 
     match yaml_file {
         Ok(f) => Ok(Some(f)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
 /// Delete a YAML file by ID
-pub fn delete_yaml_file(id: &str) -> Result<(), String> {
+pub fn delete_yaml_file(id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "DELETE FROM yaml_files WHERE id = ?",
         rusqlite::params![id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     Ok(())
 }
@@ -1178,20 +2242,23 @@ pub fn delete_yaml_file(id: &str) -> Result<(), String> {
 // ============================================================================
 
 /// Save a performance test configuration
-pub fn save_performance_test_config(config: PerformanceTestConfig) -> Result<(), String> {
+pub fn save_performance_test_config(config: PerformanceTestConfig) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let stages_json = serde_json::to_string(&config.stages.unwrap_or_default())
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     let thresholds_json = serde_json::to_string(&config.thresholds)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    let scenario_mix_json = serde_json::to_string(&config.scenario_mix)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
         "INSERT OR REPLACE INTO performance_test_configs 
-        (id, scenario_id, name, test_type, vus, duration_secs, iterations, stages, thresholds, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        (id, scenario_id, name, test_type, vus, duration_secs, iterations, stages, thresholds, created_at, updated_at, http2_prior_knowledge, warmup_secs, store_raw_samples, max_in_flight, scenario_mix)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             config.id,
             config.scenario_id,
@@ -1203,24 +2270,29 @@ pub fn save_performance_test_config(config: PerformanceTestConfig) -> Result<(),
             stages_json,
             thresholds_json,
             config.created_at,
-            config.updated_at
+            config.updated_at,
+            config.http2_prior_knowledge as i32,
+            config.warmup_secs,
+            config.store_raw_samples as i32,
+            config.max_in_flight,
+            scenario_mix_json
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
 /// Get all performance test configs for a scenario
-pub fn get_performance_test_configs(scenario_id: &str) -> Result<Vec<PerformanceTestConfig>, String> {
+pub fn get_performance_test_configs(scenario_id: &str) -> Result<Vec<PerformanceTestConfig>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, scenario_id, name, test_type, vus, duration_secs, iterations, stages, thresholds, created_at, updated_at 
+        "SELECT id, scenario_id, name, test_type, vus, duration_secs, iterations, stages, thresholds, created_at, updated_at, http2_prior_knowledge, warmup_secs, store_raw_samples, max_in_flight, scenario_mix 
          FROM performance_test_configs WHERE scenario_id = ? ORDER BY created_at DESC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let configs = stmt.query_map([scenario_id], |row| {
         let test_type_str: String = row.get(3)?;
@@ -1239,28 +2311,36 @@ pub fn get_performance_test_configs(scenario_id: &str) -> Result<Vec<Performance
             duration_secs: row.get(5)?,
             iterations: row.get(6)?,
             stages,
+            warmup_secs: row.get(12)?,
             thresholds,
+            http2_prior_knowledge: row.get::<_, Option<i32>>(11)?.unwrap_or(0) != 0,
+            store_raw_samples: row.get::<_, Option<i32>>(13)?.unwrap_or(0) != 0,
+            max_in_flight: row.get(14)?,
+            scenario_mix: row
+                .get::<_, Option<String>>(15)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
             created_at: row.get(9)?,
             updated_at: row.get(10)?,
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(configs)
 }
 
 /// Get a single performance test config by ID
-pub fn get_performance_test_config(config_id: &str) -> Result<Option<PerformanceTestConfig>, String> {
+pub fn get_performance_test_config(config_id: &str) -> Result<Option<PerformanceTestConfig>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, scenario_id, name, test_type, vus, duration_secs, iterations, stages, thresholds, created_at, updated_at 
+        "SELECT id, scenario_id, name, test_type, vus, duration_secs, iterations, stages, thresholds, created_at, updated_at, http2_prior_knowledge, warmup_secs, store_raw_samples, max_in_flight, scenario_mix 
          FROM performance_test_configs WHERE id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let config = stmt.query_row([config_id], |row| {
         let test_type_str: String = row.get(3)?;
@@ -1279,7 +2359,15 @@ pub fn get_performance_test_config(config_id: &str) -> Result<Option<Performance
             duration_secs: row.get(5)?,
             iterations: row.get(6)?,
             stages,
+            warmup_secs: row.get(12)?,
             thresholds,
+            http2_prior_knowledge: row.get::<_, Option<i32>>(11)?.unwrap_or(0) != 0,
+            store_raw_samples: row.get::<_, Option<i32>>(13)?.unwrap_or(0) != 0,
+            max_in_flight: row.get(14)?,
+            scenario_mix: row
+                .get::<_, Option<String>>(15)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
             created_at: row.get(9)?,
             updated_at: row.get(10)?,
         })
@@ -1288,16 +2376,20 @@ pub fn get_performance_test_config(config_id: &str) -> Result<Option<Performance
     match config {
         Ok(c) => Ok(Some(c)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
 /// Delete a performance test config and its runs
-pub fn delete_performance_test_config(config_id: &str) -> Result<(), String> {
+pub fn delete_performance_test_config(config_id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
-    // Delete associated runs first
+    // Delete associated runs and their raw samples first
+    conn.execute(
+        "DELETE FROM performance_raw_samples WHERE run_id IN (SELECT id FROM performance_test_runs WHERE config_id = ?)",
+        rusqlite::params![config_id],
+    ).ok();
     conn.execute(
         "DELETE FROM performance_test_runs WHERE config_id = ?",
         rusqlite::params![config_id],
@@ -1308,26 +2400,29 @@ pub fn delete_performance_test_config(config_id: &str) -> Result<(), String> {
         "DELETE FROM performance_test_configs WHERE id = ?",
         rusqlite::params![config_id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     Ok(())
 }
 
 /// Save a performance test run
-pub fn save_performance_test_run(run: &PerformanceTestRun) -> Result<(), String> {
+pub fn save_performance_test_run(run: &PerformanceTestRun) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let metrics_json = serde_json::to_string(&run.metrics)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     let threshold_results_json = serde_json::to_string(&run.threshold_results)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    let time_series_json = serde_json::to_string(&run.time_series)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     conn.execute(
         "INSERT INTO performance_test_runs 
-        (id, config_id, scenario_id, status, started_at, completed_at, duration_ms, max_vus_reached, metrics, threshold_results, error_message)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        (id, config_id, scenario_id, status, started_at, completed_at, duration_ms, max_vus_reached, metrics, threshold_results, error_message, time_series)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             run.id,
             run.config_id,
@@ -1339,24 +2434,25 @@ pub fn save_performance_test_run(run: &PerformanceTestRun) -> Result<(), String>
             run.max_vus_reached,
             metrics_json,
             threshold_results_json,
-            run.error_message
+            run.error_message,
+            time_series_json
         ],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
 /// Get performance test runs for a config
-pub fn get_performance_test_runs(config_id: &str) -> Result<Vec<PerformanceTestRun>, String> {
+pub fn get_performance_test_runs(config_id: &str) -> Result<Vec<PerformanceTestRun>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
         "SELECT id, config_id, scenario_id, status, started_at, completed_at, duration_ms, max_vus_reached, metrics, threshold_results, error_message 
          FROM performance_test_runs WHERE config_id = ? ORDER BY started_at DESC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let runs = stmt.query_map([config_id], |row| {
         let status_str: String = row.get(3)?;
@@ -1375,25 +2471,27 @@ pub fn get_performance_test_runs(config_id: &str) -> Result<Vec<PerformanceTestR
             metrics: serde_json::from_str(&metrics_json).ok(),
             threshold_results: serde_json::from_str(&threshold_results_json).unwrap_or_default(),
             error_message: row.get(10)?,
+            time_series: Vec::new(), // Loaded only for single-run lookups
+            raw_samples: None,
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(runs)
 }
 
 /// Get a single performance test run by ID
-pub fn get_performance_test_run(run_id: &str) -> Result<Option<PerformanceTestRun>, String> {
+pub fn get_performance_test_run(run_id: &str) -> Result<Option<PerformanceTestRun>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, config_id, scenario_id, status, started_at, completed_at, duration_ms, max_vus_reached, metrics, threshold_results, error_message 
+        "SELECT id, config_id, scenario_id, status, started_at, completed_at, duration_ms, max_vus_reached, metrics, threshold_results, error_message, time_series 
          FROM performance_test_runs WHERE id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let run = stmt.query_row([run_id], |row| {
         let status_str: String = row.get(3)?;
@@ -1412,20 +2510,140 @@ pub fn get_performance_test_run(run_id: &str) -> Result<Option<PerformanceTestRu
             metrics: serde_json::from_str(&metrics_json).ok(),
             threshold_results: serde_json::from_str(&threshold_results_json).unwrap_or_default(),
             error_message: row.get(10)?,
+            time_series: row.get::<_, Option<String>>(11)?
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            raw_samples: None,
         })
     });
 
     match run {
         Ok(r) => Ok(Some(r)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
+/// Raw samples per multi-row INSERT; 14 columns each keeps a statement far below SQLite's
+/// bound-parameter limit
+const RAW_SAMPLE_INSERT_ROWS: usize = 500;
+
+/// Raw samples per transaction; committing in chunks keeps each write lock short so other
+/// writers get in while millions of samples are stored
+pub const RAW_SAMPLE_COMMIT_ROWS: usize = 20_000;
+
+/// Store the raw request samples of a performance run
+pub fn save_performance_raw_samples(run_id: &str, samples: &[RequestMetric]) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    save_raw_samples_in(&mut conn, run_id, samples)
+}
+
+/// Insert samples with multi-row INSERTs, committing every `RAW_SAMPLE_COMMIT_ROWS`;
+/// `seq` is the sample's position in `samples`
+fn save_raw_samples_in(conn: &mut Connection, run_id: &str, samples: &[RequestMetric]) -> Result<(), AppError> {
+    use rusqlite::types::Value;
+
+    for (chunk_index, chunk) in samples.chunks(RAW_SAMPLE_COMMIT_ROWS).enumerate() {
+        let tx = conn.transaction()
+            .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+
+        for (batch_index, batch) in chunk.chunks(RAW_SAMPLE_INSERT_ROWS).enumerate() {
+            let first_seq = chunk_index * RAW_SAMPLE_COMMIT_ROWS + batch_index * RAW_SAMPLE_INSERT_ROWS;
+            let sql = format!(
+                "INSERT OR REPLACE INTO performance_raw_samples
+                (run_id, seq, step_id, step_name, method, url, status, duration_ms, success, vu_id, iteration, timestamp, queue_wait_ms, scenario_id)
+                VALUES {}",
+                vec!["(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"; batch.len()].join(", ")
+            );
+            // Full batches share one cached statement; only the last batch prepares its own
+            let mut stmt = tx.prepare_cached(&sql)
+                .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+            let values = batch.iter().enumerate().flat_map(|(i, sample)| {
+                [
+                    Value::Text(run_id.to_string()),
+                    Value::Integer((first_seq + i) as i64),
+                    Value::Text(sample.step_id.clone()),
+                    Value::Text(sample.step_name.clone()),
+                    Value::Text(sample.method.clone()),
+                    Value::Text(sample.url.clone()),
+                    Value::Integer(sample.status as i64),
+                    Value::Integer(sample.duration_ms as i64),
+                    Value::Integer(sample.success as i64),
+                    Value::Integer(sample.vu_id as i64),
+                    Value::Integer(sample.iteration as i64),
+                    Value::Integer(sample.timestamp),
+                    Value::Integer(sample.queue_wait_ms as i64),
+                    Value::Text(sample.scenario_id.clone()),
+                ]
+            });
+            stmt.execute(rusqlite::params_from_iter(values))
+                .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+        }
+
+        tx.commit()
+            .map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Get a page of raw request samples for a performance run, in recorded order
+pub fn get_performance_raw_samples(run_id: &str, offset: usize, limit: usize) -> Result<RawSamplePage, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let total: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM performance_raw_samples WHERE run_id = ?",
+        [run_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT step_id, step_name, method, url, status, duration_ms, success, vu_id, iteration, timestamp, queue_wait_ms, scenario_id 
+         FROM performance_raw_samples WHERE run_id = ? ORDER BY seq ASC LIMIT ? OFFSET ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let samples = stmt.query_map(rusqlite::params![run_id, limit as i64, offset as i64], |row| {
+        Ok(RequestMetric {
+            step_id: row.get(0)?,
+            step_name: row.get(1)?,
+            method: row.get(2)?,
+            url: row.get(3)?,
+            status: row.get(4)?,
+            duration_ms: row.get::<_, i64>(5)? as u64,
+            success: row.get::<_, i32>(6)? != 0,
+            vu_id: row.get(7)?,
+            iteration: row.get::<_, i64>(8)? as u64,
+            timestamp: row.get(9)?,
+            queue_wait_ms: row.get::<_, Option<i64>>(10)?.unwrap_or(0) as u64,
+            server_timing: std::collections::HashMap::new(),
+            scenario_id: row.get::<_, Option<String>>(11)?.unwrap_or_default(),
+        })
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(RawSamplePage {
+        run_id: run_id.to_string(),
+        samples,
+        total: total as u64,
+        offset,
+        limit,
+    })
+}
+
 // Request tabs functions
-pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>) -> Result<(), String> {
+pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>) -> Result<(), AppError> {
     let mut conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     // Validate project exists
     let project_exists: bool = conn.query_row(
@@ -1433,15 +2651,15 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
         rusqlite::params![project_id],
         |row| row.get(0)
     )
-    .map_err(|e| format!("Query error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
 
     if !project_exists {
-        return Err(format!("Project with id '{}' does not exist", project_id));
+        return Err(AppError::NotFound(format!("Project with id '{}' does not exist", project_id)));
     }
 
     // Start transaction
     let tx = conn.transaction()
-        .map_err(|e| format!("Transaction error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
 
     // Collect tab IDs for deletion check
     let tab_ids: Vec<String> = tabs.iter().map(|t| t.id.clone()).collect();
@@ -1450,7 +2668,7 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
     for (index, tab) in tabs.iter().enumerate() {
         let endpoint_id = tab.endpoint.as_ref().map(|e| e.id.clone());
         let endpoint_json = serde_json::to_string(&tab.endpoint)
-            .map_err(|e| format!("Serialization error: {}", e))?;
+            .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
         // Check if tab exists
         let tab_exists: bool = tx.query_row(
@@ -1458,7 +2676,7 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
             rusqlite::params![tab.id, project_id],
             |row| row.get(0)
         )
-        .map_err(|e| format!("Query error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
 
         if tab_exists {
             // UPDATE existing tab
@@ -1482,7 +2700,7 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
                     project_id
                 ],
             )
-            .map_err(|e| format!("Update error: {}", e))?;
+            .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
         } else {
             // INSERT new tab
             tx.execute(
@@ -1505,7 +2723,7 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
                     tab.updated_at
                 ],
             )
-            .map_err(|e| format!("Insert error: {}", e))?;
+            .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
         }
     }
 
@@ -1525,7 +2743,7 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
             }
             Ok(ids)
         })
-        .map_err(|e| format!("Query error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
 
         // Find IDs to delete (exist in DB but not in new list)
         for existing_id in existing_ids {
@@ -1534,7 +2752,7 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
                     "DELETE FROM request_tabs WHERE id = ? AND project_id = ?",
                     rusqlite::params![existing_id, project_id],
                 )
-                .map_err(|e| format!("Delete error: {}", e))?;
+                .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
             }
         }
     } else {
@@ -1543,11 +2761,11 @@ pub fn save_request_tabs(project_id: &str, tabs: Vec<crate::types::RequestTab>)
             "DELETE FROM request_tabs WHERE project_id = ?",
             rusqlite::params![project_id],
         )
-        .map_err(|e| format!("Delete error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
     }
 
     tx.commit()
-        .map_err(|e| format!("Commit error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
 
     Ok(())
 }
@@ -1557,9 +2775,9 @@ pub fn save_single_request_tab(
     project_id: &str,
     tab: &crate::types::RequestTab,
     tab_order: i32,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     // Validate project exists
     let project_exists: bool = conn.query_row(
@@ -1567,19 +2785,19 @@ pub fn save_single_request_tab(
         rusqlite::params![project_id],
         |row| row.get(0),
     )
-    .map_err(|e| format!("Query error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
 
     if !project_exists {
-        return Err(format!("Project with id '{}' does not exist", project_id));
+        return Err(AppError::NotFound(format!("Project with id '{}' does not exist", project_id)));
     }
 
     // Start transaction
     let tx = conn.transaction()
-        .map_err(|e| format!("Transaction error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
 
     let endpoint_id = tab.endpoint.as_ref().map(|e| e.id.clone());
     let endpoint_json = serde_json::to_string(&tab.endpoint)
-        .map_err(|e| format!("Serialization error: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
 
     // Check if tab exists
     let tab_exists: bool = tx.query_row(
@@ -1587,7 +2805,7 @@ pub fn save_single_request_tab(
         rusqlite::params![tab.id, project_id],
         |row| row.get(0),
     )
-    .map_err(|e| format!("Query error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
 
     if tab_exists {
         // UPDATE existing tab
@@ -1611,7 +2829,7 @@ pub fn save_single_request_tab(
                 project_id
             ],
         )
-        .map_err(|e| format!("Update error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
     } else {
         // INSERT new tab
         tx.execute(
@@ -1634,24 +2852,24 @@ pub fn save_single_request_tab(
                 tab.updated_at
             ],
         )
-        .map_err(|e| format!("Insert error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
     }
 
     tx.commit()
-        .map_err(|e| format!("Commit error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_request_tabs(project_id: &str) -> Result<Vec<crate::types::RequestTab>, String> {
+pub fn get_request_tabs(project_id: &str) -> Result<Vec<crate::types::RequestTab>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
         "SELECT id, endpoint_id, endpoint_json, method, url, body_json, headers_json, active_tab, name, tab_order, created_at, updated_at 
          FROM request_tabs WHERE project_id = ? ORDER BY tab_order ASC"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let tabs = stmt.query_map([project_id], |row| {
         let endpoint_json: String = row.get(2)?;
@@ -1675,16 +2893,16 @@ pub fn get_request_tabs(project_id: &str) -> Result<Vec<crate::types::RequestTab
             curl_command: None, // Runtime state
         })
     })
-    .map_err(|e| format!("Query error: {}", e))?
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
     .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| format!("Collection error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
 
     Ok(tabs)
 }
 
-pub fn save_request_tab_state(project_id: &str, active_tab_id: Option<String>) -> Result<(), String> {
+pub fn save_request_tab_state(project_id: &str, active_tab_id: Option<String>) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     // Validate project exists
     let project_exists: bool = conn.query_row(
@@ -1692,10 +2910,10 @@ pub fn save_request_tab_state(project_id: &str, active_tab_id: Option<String>) -
         rusqlite::params![project_id],
         |row| row.get(0)
     )
-    .map_err(|e| format!("Query error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?;
 
     if !project_exists {
-        return Err(format!("Project with id '{}' does not exist", project_id));
+        return Err(AppError::NotFound(format!("Project with id '{}' does not exist", project_id)));
     }
 
     conn.execute(
@@ -1703,19 +2921,19 @@ pub fn save_request_tab_state(project_id: &str, active_tab_id: Option<String>) -
          VALUES (?, ?)",
         rusqlite::params![project_id, active_tab_id],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_request_tab_state(project_id: &str) -> Result<Option<String>, String> {
+pub fn get_request_tab_state(project_id: &str) -> Result<Option<String>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare(
         "SELECT active_tab_id FROM request_tab_state WHERE project_id = ?"
     )
-    .map_err(|e| format!("Prepare error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let result = stmt.query_row([project_id], |row| {
         Ok(row.get::<_, Option<String>>(0)?)
@@ -1725,19 +2943,19 @@ pub fn get_request_tab_state(project_id: &str) -> Result<Option<String>, String>
         Ok(Some(id)) => Ok(Some(id)),
         Ok(None) => Ok(None),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
-pub fn delete_request_tab(tab_id: &str) -> Result<(), String> {
+pub fn delete_request_tab(tab_id: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "DELETE FROM request_tabs WHERE id = ?",
         rusqlite::params![tab_id],
     )
-    .map_err(|e| format!("Delete error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
 
     // Also clear active_tab_id in state if it was this tab
     conn.execute(
@@ -1749,34 +2967,34 @@ pub fn delete_request_tab(tab_id: &str) -> Result<(), String> {
     Ok(())
 }
 
-pub fn update_request_tab_order(project_id: &str, tab_orders: Vec<(String, i32)>) -> Result<(), String> {
+pub fn update_request_tab_order(project_id: &str, tab_orders: Vec<(String, i32)>) -> Result<(), AppError> {
     let mut conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let tx = conn.transaction()
-        .map_err(|e| format!("Transaction error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
 
     for (tab_id, order) in tab_orders {
         tx.execute(
             "UPDATE request_tabs SET tab_order = ? WHERE id = ? AND project_id = ?",
             rusqlite::params![order, tab_id, project_id],
         )
-        .map_err(|e| format!("Update error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Update error: {}", e)))?;
     }
 
     tx.commit()
-        .map_err(|e| format!("Commit error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
 
     Ok(())
 }
 
 // App state functions
-pub fn get_app_state(key: &str) -> Result<Option<String>, String> {
+pub fn get_app_state(key: &str) -> Result<Option<String>, AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     let mut stmt = conn.prepare("SELECT value FROM app_state WHERE key = ?")
-        .map_err(|e| format!("Prepare error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
 
     let result = stmt.query_row([key], |row| {
         Ok(row.get::<_, String>(0)?)
@@ -1785,24 +3003,24 @@ pub fn get_app_state(key: &str) -> Result<Option<String>, String> {
     match result {
         Ok(value) => Ok(Some(value)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Query error: {}", e)),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
     }
 }
 
-pub fn set_app_state(key: &str, value: &str) -> Result<(), String> {
+pub fn set_app_state(key: &str, value: &str) -> Result<(), AppError> {
     let conn = Connection::open(get_db_path())
-        .map_err(|e| format!("DB error: {}", e))?;
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
 
     conn.execute(
         "INSERT OR REPLACE INTO app_state (key, value) VALUES (?, ?)",
         rusqlite::params![key, value],
     )
-    .map_err(|e| format!("Insert error: {}", e))?;
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
 
     Ok(())
 }
 
-pub fn get_active_project() -> Result<Option<Project>, String> {
+pub fn get_active_project() -> Result<Option<Project>, AppError> {
     let active_project_id = match get_app_state("active_project_id")? {
         Some(id) => id,
         None => return Ok(None),
@@ -1811,15 +3029,1059 @@ pub fn get_active_project() -> Result<Option<Project>, String> {
     get_project(&active_project_id)
 }
 
-pub fn set_active_project(project_id: Option<&str>) -> Result<(), String> {
+pub fn set_active_project(project_id: Option<&str>) -> Result<(), AppError> {
     match project_id {
         Some(id) => set_app_state("active_project_id", id),
         None => {
             let conn = Connection::open(get_db_path())
-                .map_err(|e| format!("DB error: {}", e))?;
+                .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
             conn.execute("DELETE FROM app_state WHERE key = ?", rusqlite::params!["active_project_id"])
-                .map_err(|e| format!("Delete error: {}", e))?;
+                .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
             Ok(())
         }
     }
 }
+
+// ============================================================================
+// Request History Functions
+// ============================================================================
+
+/// Save a manually executed request
+pub fn save_request_history_entry(entry: &RequestHistoryEntry) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let headers_json = serde_json::to_string(&entry.headers)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let body_json = serde_json::to_string(&entry.body)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    conn.execute(
+        "INSERT INTO request_history 
+        (id, endpoint_id, method, url, headers, body, status, duration_ms, error, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            entry.id,
+            entry.endpoint_id,
+            entry.method,
+            entry.url,
+            headers_json,
+            body_json,
+            entry.status,
+            entry.duration_ms as i64,
+            entry.error,
+            entry.created_at
+        ],
+    )
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+
+    Ok(())
+}
+
+/// Get the most recent history entries, optionally for a single endpoint
+pub fn get_request_history(endpoint_id: Option<&str>, limit: usize) -> Result<Vec<RequestHistoryEntry>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, endpoint_id, method, url, headers, body, status, duration_ms, error, created_at 
+         FROM request_history WHERE (?1 IS NULL OR endpoint_id = ?1)
+         ORDER BY created_at DESC LIMIT ?2"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let entries = stmt.query_map(rusqlite::params![endpoint_id, limit as i64], |row| {
+        let headers_json: String = row.get(4)?;
+        let body_json: String = row.get(5)?;
+        let duration_ms: i64 = row.get(7)?;
+
+        Ok(RequestHistoryEntry {
+            id: row.get(0)?,
+            endpoint_id: row.get(1)?,
+            method: row.get(2)?,
+            url: row.get(3)?,
+            headers: serde_json::from_str(&headers_json).unwrap_or_default(),
+            body: serde_json::from_str(&body_json).unwrap_or(serde_json::Value::Null),
+            status: row.get(6)?,
+            duration_ms: duration_ms as u64,
+            error: row.get(8)?,
+            created_at: row.get(9)?,
+        })
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(entries)
+}
+
+/// Store the results of one health check for a project
+pub fn save_endpoint_health(project_id: &str, results: &[EndpointHealth]) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO endpoint_health_checks 
+            (project_id, endpoint_id, method, path, url, status, latency_ms, reachable, schema_valid, skipped, error, checked_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+        for health in results {
+            stmt.execute(rusqlite::params![
+                project_id,
+                health.endpoint_id,
+                health.method,
+                health.path,
+                health.url,
+                health.status,
+                health.latency_ms.map(|ms| ms as i64),
+                health.reachable as i32,
+                health.schema_valid.map(|valid| valid as i32),
+                health.skipped as i32,
+                health.error,
+                health.checked_at
+            ])
+            .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+        }
+    }
+    tx.commit()
+        .map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
+
+    Ok(())
+}
+
+/// Most recent health results for an endpoint, newest first
+pub fn get_endpoint_health_history(endpoint_id: &str, limit: usize) -> Result<Vec<EndpointHealth>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT endpoint_id, method, path, url, status, latency_ms, reachable, schema_valid, skipped, error, checked_at 
+         FROM endpoint_health_checks WHERE endpoint_id = ? ORDER BY checked_at DESC LIMIT ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let history = stmt.query_map(rusqlite::params![endpoint_id, limit as i64], |row| {
+        Ok(EndpointHealth {
+            endpoint_id: row.get(0)?,
+            method: row.get(1)?,
+            path: row.get(2)?,
+            url: row.get(3)?,
+            status: row.get(4)?,
+            latency_ms: row.get::<_, Option<i64>>(5)?.map(|ms| ms as u64),
+            reachable: row.get::<_, i32>(6)? != 0,
+            schema_valid: row.get::<_, Option<i32>>(7)?.map(|valid| valid != 0),
+            skipped: row.get::<_, i32>(8)? != 0,
+            error: row.get(9)?,
+            checked_at: row.get(10)?,
+        })
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(history)
+}
+
+/// Clear request history, optionally only for a single endpoint
+pub fn clear_request_history(endpoint_id: Option<&str>) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "DELETE FROM request_history WHERE (?1 IS NULL OR endpoint_id = ?1)",
+        rusqlite::params![endpoint_id],
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Saved Request Functions
+// ============================================================================
+
+/// Insert or update a saved request
+pub fn save_saved_request(request: &SavedRequest) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let headers_json = serde_json::to_string(&request.headers)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let params_json = serde_json::to_string(&request.params)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let body_json = serde_json::to_string(&request.body)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO saved_requests 
+        (id, project_id, endpoint_id, name, method, url, headers, params, body, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            request.id,
+            request.project_id,
+            request.endpoint_id,
+            request.name,
+            request.method,
+            request.url,
+            headers_json,
+            params_json,
+            body_json,
+            request.created_at,
+            request.updated_at
+        ],
+    )
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+
+    Ok(())
+}
+
+fn row_to_saved_request(row: &rusqlite::Row) -> rusqlite::Result<SavedRequest> {
+    let headers_json: String = row.get(6)?;
+    let params_json: String = row.get(7)?;
+    let body_json: String = row.get(8)?;
+
+    Ok(SavedRequest {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        endpoint_id: row.get(2)?,
+        name: row.get(3)?,
+        method: row.get(4)?,
+        url: row.get(5)?,
+        headers: serde_json::from_str(&headers_json).unwrap_or_default(),
+        params: serde_json::from_str(&params_json).unwrap_or(serde_json::Value::Null),
+        body: serde_json::from_str(&body_json).unwrap_or(serde_json::Value::Null),
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+/// Get saved requests, optionally for a single project
+pub fn get_saved_requests(project_id: Option<&str>) -> Result<Vec<SavedRequest>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, endpoint_id, name, method, url, headers, params, body, created_at, updated_at 
+         FROM saved_requests WHERE (?1 IS NULL OR project_id = ?1) ORDER BY name ASC"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let requests = stmt.query_map(rusqlite::params![project_id], row_to_saved_request)
+        .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(requests)
+}
+
+/// Get a single saved request by ID
+pub fn get_saved_request(id: &str) -> Result<Option<SavedRequest>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, endpoint_id, name, method, url, headers, params, body, created_at, updated_at 
+         FROM saved_requests WHERE id = ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    match stmt.query_row([id], row_to_saved_request) {
+        Ok(request) => Ok(Some(request)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
+    }
+}
+
+/// Delete a saved request
+pub fn delete_saved_request(id: &str) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "DELETE FROM saved_requests WHERE id = ?",
+        rusqlite::params![id],
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Assertion Template Functions
+// ============================================================================
+
+/// Insert or update an assertion template
+pub fn save_assertion_template(template: &AssertionTemplate) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let assertions_json = serde_json::to_string(&template.assertions)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+    let parameters_json = serde_json::to_string(&template.parameters)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO assertion_templates 
+        (id, project_id, name, description, assertions, parameters, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            template.id,
+            template.project_id,
+            template.name,
+            template.description,
+            assertions_json,
+            parameters_json,
+            template.created_at,
+            template.updated_at
+        ],
+    )
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+
+    Ok(())
+}
+
+fn row_to_assertion_template(row: &rusqlite::Row) -> rusqlite::Result<AssertionTemplate> {
+    let assertions_json: String = row.get(4)?;
+    let parameters_json: String = row.get(5)?;
+
+    Ok(AssertionTemplate {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        name: row.get(2)?,
+        description: row.get(3)?,
+        assertions: serde_json::from_str(&assertions_json).unwrap_or_default(),
+        parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// Get all assertion templates for a project
+pub fn get_assertion_templates(project_id: &str) -> Result<Vec<AssertionTemplate>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, name, description, assertions, parameters, created_at, updated_at 
+         FROM assertion_templates WHERE project_id = ? ORDER BY name ASC"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let templates = stmt.query_map([project_id], row_to_assertion_template)
+        .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(templates)
+}
+
+/// Get a single assertion template by ID
+pub fn get_assertion_template(id: &str) -> Result<Option<AssertionTemplate>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, name, description, assertions, parameters, created_at, updated_at 
+         FROM assertion_templates WHERE id = ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    match stmt.query_row([id], row_to_assertion_template) {
+        Ok(template) => Ok(Some(template)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
+    }
+}
+
+/// Delete an assertion template
+pub fn delete_assertion_template(id: &str) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "DELETE FROM assertion_templates WHERE id = ?",
+        rusqlite::params![id],
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Golden Response Functions
+// ============================================================================
+
+/// Insert or replace the golden response for an endpoint and status code
+pub fn save_golden_response(golden: &GoldenResponse) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let body_json = serde_json::to_string(&golden.body)
+        .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO golden_responses (endpoint_id, status_code, body, updated_at)
+        VALUES (?, ?, ?, ?)",
+        rusqlite::params![golden.endpoint_id, golden.status_code, body_json, golden.updated_at],
+    )
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+
+    Ok(())
+}
+
+fn row_to_golden_response(row: &rusqlite::Row) -> rusqlite::Result<GoldenResponse> {
+    let body_json: String = row.get(2)?;
+
+    Ok(GoldenResponse {
+        endpoint_id: row.get(0)?,
+        status_code: row.get(1)?,
+        body: serde_json::from_str(&body_json).unwrap_or(serde_json::Value::Null),
+        updated_at: row.get(3)?,
+    })
+}
+
+/// Get all golden responses for an endpoint, ordered by status code
+pub fn get_golden_responses(endpoint_id: &str) -> Result<Vec<GoldenResponse>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT endpoint_id, status_code, body, updated_at 
+         FROM golden_responses WHERE endpoint_id = ? ORDER BY status_code ASC"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let goldens = stmt.query_map([endpoint_id], row_to_golden_response)
+        .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(goldens)
+}
+
+/// Delete the golden response for an endpoint and status code
+pub fn delete_golden_response(endpoint_id: &str, status_code: u16) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "DELETE FROM golden_responses WHERE endpoint_id = ? AND status_code = ?",
+        rusqlite::params![endpoint_id, status_code],
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Assertion Snapshot Functions
+// ============================================================================
+
+/// Insert or replace snapshots recorded by a scenario run
+pub fn save_assertion_snapshots(snapshots: &[AssertionSnapshot]) -> Result<(), AppError> {
+    let mut conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let tx = conn.transaction()
+        .map_err(|e| AppError::Db(format!("Transaction error: {}", e)))?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO assertion_snapshots (scenario_id, step_id, name, body, updated_at)
+            VALUES (?, ?, ?, ?, ?)"
+        )
+        .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+        for snapshot in snapshots {
+            let body_json = serde_json::to_string(&snapshot.body)
+                .map_err(|e| AppError::Parse(format!("Serialization error: {}", e)))?;
+            stmt.execute(rusqlite::params![
+                snapshot.scenario_id,
+                snapshot.step_id,
+                snapshot.name,
+                body_json,
+                snapshot.updated_at
+            ])
+            .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+        }
+    }
+    tx.commit()
+        .map_err(|e| AppError::Db(format!("Commit error: {}", e)))?;
+
+    Ok(())
+}
+
+/// Get all snapshots stored for a scenario
+pub fn get_assertion_snapshots(scenario_id: &str) -> Result<Vec<AssertionSnapshot>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT scenario_id, step_id, name, body, updated_at 
+         FROM assertion_snapshots WHERE scenario_id = ? ORDER BY step_id, name"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let snapshots = stmt.query_map([scenario_id], |row| {
+        let body_json: String = row.get(3)?;
+        Ok(AssertionSnapshot {
+            scenario_id: row.get(0)?,
+            step_id: row.get(1)?,
+            name: row.get(2)?,
+            body: serde_json::from_str(&body_json).unwrap_or(serde_json::Value::Null),
+            updated_at: row.get(4)?,
+        })
+    })
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(snapshots)
+}
+
+/// Delete a single stored snapshot
+pub fn delete_assertion_snapshot(scenario_id: &str, step_id: &str, name: &str) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "DELETE FROM assertion_snapshots WHERE scenario_id = ? AND step_id = ? AND name = ?",
+        rusqlite::params![scenario_id, step_id, name],
+    )
+    .map_err(|e| AppError::Db(format!("Delete error: {}", e)))?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Scenario Schedule Functions
+// ============================================================================
+
+/// Insert or replace the schedule of a scenario
+pub fn save_scenario_schedule(schedule: &ScenarioSchedule) -> Result<(), AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO scenario_schedules (scenario_id, interval_minutes, enabled, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![
+            schedule.scenario_id,
+            schedule.interval_minutes,
+            schedule.enabled as i32,
+            schedule.created_at,
+            schedule.updated_at
+        ],
+    )
+    .map_err(|e| AppError::Db(format!("Insert error: {}", e)))?;
+
+    Ok(())
+}
+
+/// Get the schedule of a scenario
+pub fn get_scenario_schedule(scenario_id: &str) -> Result<Option<ScenarioSchedule>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT scenario_id, interval_minutes, enabled, created_at, updated_at
+         FROM scenario_schedules WHERE scenario_id = ?"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    match stmt.query_row([scenario_id], row_to_scenario_schedule) {
+        Ok(schedule) => Ok(Some(schedule)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(AppError::Db(format!("Query error: {}", e))),
+    }
+}
+
+/// Get all scenario schedules
+pub fn get_scenario_schedules() -> Result<Vec<ScenarioSchedule>, AppError> {
+    let conn = Connection::open(get_db_path())
+        .map_err(|e| AppError::Db(format!("DB error: {}", e)))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT scenario_id, interval_minutes, enabled, created_at, updated_at
+         FROM scenario_schedules ORDER BY created_at"
+    )
+    .map_err(|e| AppError::Db(format!("Prepare error: {}", e)))?;
+
+    let schedules = stmt.query_map([], row_to_scenario_schedule)
+    .map_err(|e| AppError::Db(format!("Query error: {}", e)))?
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| AppError::Db(format!("Collection error: {}", e)))?;
+
+    Ok(schedules)
+}
+
+fn row_to_scenario_schedule(row: &rusqlite::Row) -> rusqlite::Result<ScenarioSchedule> {
+    let scenario_id: String = row.get(0)?;
+    Ok(ScenarioSchedule {
+        active: crate::scenario::schedule::is_task_active(&scenario_id),
+        scenario_id,
+        interval_minutes: row.get(1)?,
+        enabled: row.get::<_, i32>(2)? != 0,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE test_scenario_steps (
+                id TEXT PRIMARY KEY,
+                scenario_id TEXT NOT NULL,
+                step_order INTEGER NOT NULL,
+                enabled INTEGER DEFAULT 1
+            );
+            INSERT INTO test_scenario_steps (id, scenario_id, step_order) VALUES
+                ('a', 's1', 0), ('b', 's1', 1), ('c', 's1', 2), ('x', 's2', 0);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn step_rows(conn: &Connection) -> Vec<(String, i32, i32)> {
+        let mut stmt = conn
+            .prepare("SELECT id, step_order, enabled FROM test_scenario_steps WHERE scenario_id = 's1' ORDER BY id")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn endpoint(id: &str, method: &str, path: &str, params: &[&str], user_modified: bool) -> ApiEndpoint {
+        ApiEndpoint {
+            id: id.to_string(),
+            project_id: Some("p1".to_string()),
+            name: format!("{} {}", method, path),
+            method: method.to_string(),
+            path: path.to_string(),
+            service: "api".to_string(),
+            description: "scanned".to_string(),
+            parameters: params.iter().map(|name| crate::types::ApiParameter {
+                name: name.to_string(),
+                param_type: "string".to_string(),
+                required: false,
+                description: String::new(),
+                example: None,
+                default_value: None,
+                validation: None,
+            }).collect(),
+            category: "users".to_string(),
+            explanation: None,
+            responses: None,
+            version: None,
+            user_modified,
+            stale: false,
+            auth_required: false,
+            auth_type: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_scanned_endpoints_keeps_user_edits() {
+        let mut edited = endpoint("old-get", "GET", "/users", &["page"], true);
+        edited.description = "Lists users (hand-written)".to_string();
+        let existing = vec![
+            edited,
+            endpoint("old-post", "POST", "/users", &["name"], false),
+            endpoint("old-delete", "DELETE", "/users/{id}", &[], false),
+        ];
+        let scanned = vec![
+            endpoint("new-get", "get", "/users", &["page", "limit"], false),
+            endpoint("new-post", "POST", "/users", &["name", "email"], false),
+            endpoint("new-put", "PUT", "/users/{id}", &[], false),
+        ];
+
+        let (merged, stale) = merge_scanned_endpoints(existing, scanned);
+
+        assert_eq!(merged[0].id, "old-get");
+        assert_eq!(merged[0].description, "Lists users (hand-written)");
+        assert!(merged[0].user_modified);
+        let names: Vec<&str> = merged[0].parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["page", "limit"]);
+
+        assert_eq!(merged[1].id, "old-post");
+        assert_eq!(merged[1].parameters.len(), 2);
+        assert_eq!(merged[2].id, "new-put");
+
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].id, "old-delete");
+        assert!(stale[0].stale);
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_yaml_file_provenance_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE yaml_files (
+                id TEXT PRIMARY KEY, project_id TEXT NOT NULL, scenario_id TEXT,
+                content TEXT NOT NULL, created_at INTEGER NOT NULL,
+                ai_prompt TEXT, ai_endpoint_ids TEXT, ai_backend TEXT, ai_base_url TEXT, ai_generated_at INTEGER
+            );",
+        )
+        .unwrap();
+
+        let generated = YamlFile {
+            id: "y1".to_string(),
+            project_id: "p1".to_string(),
+            scenario_id: None,
+            content: "name: Login".to_string(),
+            created_at: 10,
+            provenance: Some(YamlProvenance {
+                prompt: "test the login flow".to_string(),
+                endpoint_ids: vec!["e1".to_string(), "e2".to_string()],
+                backend: "copilot-cli".to_string(),
+                base_url: Some("http://localhost:3000".to_string()),
+                generated_at: 10,
+            }),
+        };
+        let manual = YamlFile { id: "y2".to_string(), provenance: None, ..generated.clone() };
+        insert_yaml_file(&conn, &generated).unwrap();
+        insert_yaml_file(&conn, &manual).unwrap();
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM yaml_files ORDER BY id", YAML_FILE_COLUMNS)).unwrap();
+        let files: Vec<YamlFile> = stmt.query_map([], row_to_yaml_file).unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(files[0].provenance, generated.provenance);
+        assert_eq!(files[1].provenance, None);
+    }
+
+    #[test]
+    fn test_bulk_endpoint_updates_and_delete() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE endpoints (
+                id TEXT PRIMARY KEY, service TEXT NOT NULL, category TEXT,
+                user_modified INTEGER DEFAULT 0, updated_at INTEGER
+            );
+            INSERT INTO endpoints (id, service, category) VALUES
+                ('a', 'api', 'users'), ('b', 'api', 'users'), ('c', 'api', 'orders');",
+        )
+        .unwrap();
+        let rows = |conn: &Connection| -> Vec<(String, String, String, i32)> {
+            let mut stmt = conn.prepare("SELECT id, service, category, user_modified FROM endpoints ORDER BY id").unwrap();
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap()
+        };
+
+        assert_eq!(set_endpoints_column_in(&mut conn, &[], "category", "x").unwrap(), 0);
+        assert_eq!(delete_endpoints_in(&mut conn, &[]).unwrap(), 0);
+
+        assert_eq!(set_endpoints_column_in(&mut conn, &ids(&["a", "c", "missing"]), "category", "admin").unwrap(), 2);
+        assert_eq!(set_endpoints_column_in(&mut conn, &ids(&["b"]), "service", "billing").unwrap(), 1);
+        assert_eq!(rows(&conn), vec![
+            ("a".to_string(), "api".to_string(), "admin".to_string(), 1),
+            ("b".to_string(), "billing".to_string(), "users".to_string(), 1),
+            ("c".to_string(), "api".to_string(), "admin".to_string(), 1),
+        ]);
+
+        assert_eq!(delete_endpoints_in(&mut conn, &ids(&["a", "b", "missing"])).unwrap(), 2);
+        assert_eq!(rows(&conn).len(), 1);
+    }
+
+    const RAW_SAMPLES_TABLE: &str = "CREATE TABLE performance_raw_samples (
+        run_id TEXT NOT NULL, seq INTEGER NOT NULL, step_id TEXT NOT NULL, step_name TEXT NOT NULL,
+        method TEXT NOT NULL, url TEXT NOT NULL, status INTEGER NOT NULL, duration_ms INTEGER NOT NULL,
+        success INTEGER NOT NULL, vu_id INTEGER NOT NULL, iteration INTEGER NOT NULL,
+        timestamp INTEGER NOT NULL, queue_wait_ms INTEGER DEFAULT 0, scenario_id TEXT DEFAULT '',
+        PRIMARY KEY (run_id, seq)
+    )";
+
+    fn raw_sample(i: usize) -> RequestMetric {
+        let failed = i % 10 == 9;
+        RequestMetric {
+            step_id: format!("step-{}", i % 3),
+            step_name: "Get user".to_string(),
+            method: "GET".to_string(),
+            url: format!("http://localhost/users/{}", i),
+            status: if failed { 500 } else { 200 },
+            duration_ms: i as u64 % 250,
+            success: !failed,
+            vu_id: (i % 50) as u32,
+            iteration: i as u64 / 50,
+            timestamp: 1_700_000_000_000 + i as i64,
+            queue_wait_ms: 1,
+            server_timing: std::collections::HashMap::new(),
+            scenario_id: "s1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_save_raw_samples_in_batches() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute(RAW_SAMPLES_TABLE, []).unwrap();
+        // Spans several commit chunks and ends on a partial insert batch
+        let count = RAW_SAMPLE_COMMIT_ROWS * 2 + RAW_SAMPLE_INSERT_ROWS + 7;
+        let samples: Vec<RequestMetric> = (0..count).map(raw_sample).collect();
+        save_raw_samples_in(&mut conn, "run-1", &samples).unwrap();
+
+        let (rows, max_seq): (i64, i64) = conn
+            .query_row("SELECT COUNT(*), MAX(seq) FROM performance_raw_samples WHERE run_id = 'run-1'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((rows, max_seq), (count as i64, count as i64 - 1));
+
+        let (url, status, success): (String, u16, bool) = conn
+            .query_row("SELECT url, status, success FROM performance_raw_samples WHERE seq = 40019", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+        assert_eq!((url.as_str(), status, success), ("http://localhost/users/40019", 500, false));
+
+        save_raw_samples_in(&mut conn, "run-2", &[]).unwrap();
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with --ignored --nocapture"]
+    fn bench_save_one_million_raw_samples() {
+        let path = std::env::temp_dir().join(format!("lookapi-raw-samples-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut conn = Connection::open(&path).unwrap();
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0)).unwrap();
+        conn.execute(RAW_SAMPLES_TABLE, []).unwrap();
+
+        let samples: Vec<RequestMetric> = (0..1_000_000).map(raw_sample).collect();
+        let start = std::time::Instant::now();
+        save_raw_samples_in(&mut conn, "run-1", &samples).unwrap();
+        let elapsed = start.elapsed();
+        println!(
+            "stored {} raw samples in {:?} ({:.0} rows/s)",
+            samples.len(),
+            elapsed,
+            samples.len() as f64 / elapsed.as_secs_f64()
+        );
+
+        drop(conn);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_import_scenarios_rolls_back_on_failure() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE test_scenarios (
+                id TEXT PRIMARY KEY, project_id TEXT NOT NULL, name TEXT NOT NULL, description TEXT,
+                priority TEXT, variables TEXT, pre_script TEXT, post_script TEXT, created_at INTEGER,
+                updated_at INTEGER, chaos TEXT, session_login TEXT, environments TEXT
+            );
+            CREATE TABLE test_scenario_steps (
+                id TEXT PRIMARY KEY, scenario_id TEXT NOT NULL, step_order INTEGER NOT NULL,
+                step_type TEXT NOT NULL, name TEXT NOT NULL, config TEXT, enabled INTEGER,
+                enabled_if TEXT, step_group TEXT
+            );
+            CREATE TRIGGER reject_boom BEFORE INSERT ON test_scenario_steps WHEN NEW.name = 'boom'
+            BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
+        )
+        .unwrap();
+        let yaml = |name: &str, step: &str| {
+            let yaml = crate::scenario::yaml::parse_scenario_yaml(&format!(
+                "name: {}\nsteps:\n  - name: {}\n    delay:\n      duration: 1\n",
+                name, step
+            ))
+            .unwrap();
+            crate::scenario::yaml::yaml_to_scenario_with_steps(&yaml, "p1").unwrap()
+        };
+        let count = |conn: &Connection, table: &str| -> i64 {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+        };
+
+        let mut progress = Vec::new();
+        let error = import_scenarios_in(&mut conn, &[yaml("First", "ok"), yaml("Second", "boom")], |i, s| {
+            progress.push((i, s.name.clone()))
+        })
+        .unwrap_err();
+        assert_eq!(error.kind(), "db");
+        assert!(error.message().contains("Scenario 'Second', step 'boom'"), "{}", error);
+        assert_eq!(progress, vec![(0, "First".to_string())]);
+        assert_eq!(count(&conn, "test_scenarios"), 0);
+        assert_eq!(count(&conn, "test_scenario_steps"), 0);
+
+        import_scenarios_in(&mut conn, &[yaml("First", "ok"), yaml("Second", "fine")], |_, _| {}).unwrap();
+        assert_eq!(count(&conn, "test_scenarios"), 2);
+        assert_eq!(count(&conn, "test_scenario_steps"), 2);
+
+        // A single scenario failing mid-batch keeps neither the scenario nor its earlier steps
+        let (scenario, steps) = {
+            let yaml = crate::scenario::yaml::parse_scenario_yaml(
+                "name: Third\nsteps:\n  - name: one\n    delay:\n      duration: 1\n  - name: boom\n    delay:\n      duration: 1\n  - name: three\n    delay:\n      duration: 1\n",
+            )
+            .unwrap();
+            crate::scenario::yaml::yaml_to_scenario_with_steps(&yaml, "p1").unwrap()
+        };
+        let error = save_scenario_with_steps_in(&mut conn, &scenario, &steps).unwrap_err();
+        assert!(error.message().contains("Step 'boom'"), "{}", error);
+        assert_eq!(count(&conn, "test_scenarios"), 2);
+        assert_eq!(count(&conn, "test_scenario_steps"), 2);
+
+        // Updating from YAML: the deletes are undone when a new step fails to insert
+        let old_ids: Vec<String> = conn
+            .prepare("SELECT id FROM test_scenario_steps")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let error = replace_scenario_steps_in(&mut conn, &scenario, &old_ids, &steps).unwrap_err();
+        assert!(error.message().contains("Step 'boom'"), "{}", error);
+        assert_eq!(count(&conn, "test_scenarios"), 2);
+        assert_eq!(count(&conn, "test_scenario_steps"), 2);
+    }
+
+    #[test]
+    fn test_scheduled_runs_exclude_manual_runs() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE test_scenario_runs (
+                id TEXT PRIMARY KEY, scenario_id TEXT NOT NULL, status TEXT NOT NULL,
+                total_steps INTEGER NOT NULL, passed_steps INTEGER, failed_steps INTEGER,
+                skipped_steps INTEGER, duration_ms INTEGER, started_at INTEGER NOT NULL,
+                completed_at INTEGER, error_message TEXT, results TEXT NOT NULL DEFAULT '[]',
+                variables TEXT DEFAULT '{}', seed INTEGER, logs TEXT, run_trigger TEXT DEFAULT 'manual'
+            );",
+        )
+        .unwrap();
+        let run = |id: &str, started_at: i64, trigger: RunTrigger| TestScenarioRun {
+            id: id.to_string(),
+            scenario_id: "s1".to_string(),
+            status: ScenarioRunStatus::Passed,
+            total_steps: 1,
+            passed_steps: 1,
+            failed_steps: 0,
+            skipped_steps: 0,
+            duration_ms: Some(10),
+            started_at,
+            completed_at: Some(started_at),
+            error_message: None,
+            results: Vec::new(),
+            variables: std::collections::HashMap::new(),
+            seed: None,
+            groups: Vec::new(),
+            snapshot_updates: Vec::new(),
+            logs: Vec::new(),
+            trigger,
+        };
+        insert_scenario_run(&conn, &run("a", 1, RunTrigger::Schedule)).unwrap();
+        insert_scenario_run(&conn, &run("b", 2, RunTrigger::Manual)).unwrap();
+        insert_scenario_run(&conn, &run("c", 3, RunTrigger::Schedule)).unwrap();
+        // Stored before the trigger column existed
+        conn.execute(
+            "INSERT INTO test_scenario_runs (id, scenario_id, status, total_steps, started_at) VALUES ('d', 's1', 'passed', 1, 4)",
+            [],
+        )
+        .unwrap();
+
+        let ids = |runs: Vec<TestScenarioRun>| runs.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(ids(scheduled_runs_in(&conn, "s1", 50).unwrap()), vec!["c", "a"]);
+        assert_eq!(ids(scheduled_runs_in(&conn, "s1", 1).unwrap()), vec!["c"]);
+    }
+
+    #[test]
+    fn test_project_stats() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE projects (id TEXT PRIMARY KEY, last_scanned INTEGER);
+            CREATE TABLE endpoints (id TEXT PRIMARY KEY, project_id TEXT, method TEXT, responses TEXT, auth_required INTEGER DEFAULT 0);
+            CREATE TABLE test_scenarios (id TEXT PRIMARY KEY, project_id TEXT);
+            CREATE TABLE security_test_cases (id TEXT PRIMARY KEY, project_id TEXT);
+            CREATE TABLE test_scenario_runs (id TEXT PRIMARY KEY, scenario_id TEXT, passed_steps INTEGER, total_steps INTEGER, started_at INTEGER);
+            INSERT INTO projects VALUES ('p1', 100);
+            INSERT INTO endpoints VALUES ('e1', 'p1', 'get', '[{\"statusCode\": 401}]', 1);
+            INSERT INTO endpoints VALUES ('e2', 'p1', 'GET', '[{\"statusCode\":401}]', 0);
+            INSERT INTO endpoints VALUES ('e3', 'p1', 'POST', NULL, 1);
+            INSERT INTO test_scenarios VALUES ('s1', 'p1');
+            INSERT INTO test_scenario_runs VALUES ('r1', 's1', 3, 4, 1);
+            INSERT INTO test_scenario_runs VALUES ('r2', 's1', 0, 0, 2);",
+        )
+        .unwrap();
+
+        let stats = project_stats_in(&conn, "p1").unwrap();
+        assert_eq!(stats.total_endpoints, 3);
+        assert_eq!(stats.endpoints_by_method["GET"], 2);
+        // Counted from the stored flag, not from the response definitions
+        assert_eq!(stats.endpoints_requiring_auth, 2);
+        assert_eq!(stats.last_scanned, Some(100));
+        // The latest run had no steps
+        assert_eq!(stats.last_run_pass_rate, None);
+
+        conn.execute("DELETE FROM test_scenario_runs WHERE id = 'r2'", []).unwrap();
+        assert_eq!(project_stats_in(&conn, "p1").unwrap().last_run_pass_rate, Some(75.0));
+        assert!(matches!(project_stats_in(&conn, "missing"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_reorder_and_bulk_enable_are_atomic() {
+        let mut conn = steps_db();
+        let original = step_rows(&conn);
+
+        // Foreign step id: rejected before anything is written
+        let error = reorder_steps_in(&mut conn, "s1", &ids(&["c", "x", "a"])).unwrap_err();
+        assert!(matches!(&error, AppError::Validation(m) if m.starts_with("Invalid step ids")));
+        assert_eq!(step_rows(&conn), original);
+
+        // Failure part-way through the updates rolls back the earlier ones
+        conn.execute_batch(
+            "CREATE TRIGGER fail_on_b BEFORE UPDATE ON test_scenario_steps WHEN NEW.id = 'b'
+             BEGIN SELECT RAISE(ABORT, 'boom'); END;",
+        )
+        .unwrap();
+        assert!(reorder_steps_in(&mut conn, "s1", &ids(&["c", "b", "a"])).is_err());
+        assert!(set_steps_enabled_in(&mut conn, "s1", &ids(&["a", "b"]), false).is_err());
+        assert_eq!(step_rows(&conn), original);
+
+        conn.execute_batch("DROP TRIGGER fail_on_b;").unwrap();
+        reorder_steps_in(&mut conn, "s1", &ids(&["c", "b", "a"])).unwrap();
+        set_steps_enabled_in(&mut conn, "s1", &ids(&["a", "b"]), false).unwrap();
+        assert_eq!(
+            step_rows(&conn),
+            vec![("a".to_string(), 2, 0), ("b".to_string(), 1, 0), ("c".to_string(), 0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_sql_console_read_only_mode() {
+        let path = std::env::temp_dir().join(format!("lookapi-sql-console-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT); INSERT INTO users (name) VALUES ('ann');")
+            .unwrap();
+        let db_path = path.to_string_lossy().to_string();
+        let run = |query: &str, read_only: bool| execute_sql_query(db_path.clone(), query.to_string(), read_only);
+        let count = || run("SELECT COUNT(*) FROM users", true).unwrap().rows[0][0].clone();
+
+        for write in ["DELETE FROM users", "INSERT INTO users (name) VALUES ('bob')"] {
+            assert!(matches!(run(write, true), Err(AppError::Validation(_))), "{} ran in read-only mode", write);
+        }
+        let result = run("SELECT id, name FROM users", true).unwrap();
+        assert_eq!(result.columns, vec!["id", "name"]);
+        assert_eq!(result.rows, vec![vec![serde_json::json!(1), serde_json::json!("ann")]]);
+        assert_eq!(count(), serde_json::json!(1));
+
+        run("INSERT INTO users (name) VALUES ('bob')", false).unwrap();
+        assert_eq!(count(), serde_json::json!(2));
+        run("DELETE FROM users", false).unwrap();
+        assert_eq!(count(), serde_json::json!(0));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,77 @@
+//! Public entry points for embedding LookAPI's scanning and scenario execution
+//!
+//! Everything here works from a plain Rust program: nothing needs an `AppHandle` or a running
+//! Tauri app, and nothing touches the app database. The Tauri `commands` are thin wrappers that
+//! add persistence and frontend events on top. Items not re-exported here are internal and may
+//! change between versions.
+//!
+//! ```no_run
+//! use tauri_app_lib::engine::{parse_scenario_yaml, yaml_to_scenario_with_steps, ScenarioExecutor};
+//!
+//! let yaml = parse_scenario_yaml(&std::fs::read_to_string("login.yaml").unwrap()).unwrap();
+//! let (scenario, steps) = yaml_to_scenario_with_steps(&yaml, "my-project").unwrap();
+//! let run = ScenarioExecutor::new()
+//!     .with_base_url(Some("http://localhost:3000".to_string()))
+//!     .execute_scenario(&scenario, &steps, None);
+//! println!("{:?}: {} steps", run.status, run.results.len());
+//! ```
+
+// Scanning
+pub use crate::scanner::{
+    api_endpoints_from_scan, DedupeKeyOptions, FrameworkInfo, ScanResult, ScannedEndpoint, UnifiedScanner,
+};
+pub use crate::scanner::openapi::endpoints_from_openapi;
+pub use crate::scanner::parsers::{default_category, CategoryFn};
+
+// Scenario execution
+pub use crate::http_client::ClientDefaults;
+pub use crate::request_signing::RequestSigningConfig;
+pub use crate::scenario::events::RunEvents;
+pub use crate::scenario::executor::{run_scenario, ScenarioExecutor};
+pub use crate::scenario::types::{
+    Assertion, ScenarioEnvironment, ScenarioRunStatus, StepRequest, StepResponse, StepResultStatus, TestScenario,
+    TestScenarioRun, TestScenarioStep, TestStepResult, TestStepType,
+};
+
+// YAML conversion
+pub use crate::scenario::yaml::{
+    parse_project_scenarios_yaml, parse_scenario_yaml, scenario_to_yaml_string, yaml_to_scenario,
+    yaml_to_scenario_with_steps, ScenarioYaml,
+};
+
+// Shared types
+pub use crate::types::{ApiEndpoint, IdempotencyConfig, Project, ProjectService};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct CollectedEvents(Mutex<Vec<String>>);
+
+    impl RunEvents for CollectedEvents {
+        fn emit_json(&self, event: &str, _payload: serde_json::Value) {
+            self.0.lock().unwrap().push(event.to_string());
+        }
+    }
+
+    #[test]
+    fn test_runs_yaml_scenario_without_tauri() {
+        let yaml = parse_scenario_yaml(
+            "name: Wait\nsteps:\n  - name: Pause\n    delay:\n      duration: 1\n",
+        )
+        .unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&yaml, "p1").unwrap();
+
+        let events = CollectedEvents::default();
+        let run = ScenarioExecutor::new().execute_scenario(&scenario, &steps, Some(&events));
+
+        assert_eq!(run.status, ScenarioRunStatus::Passed);
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            vec!["scenario-started", "step-started", "step-completed", "scenario-completed"]
+        );
+        assert!(scenario_to_yaml_string(&scenario, &steps, None).unwrap().contains("Pause"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Error type returned by Tauri commands
+//!
+//! The database and HTTP helpers return `AppError` with the kind set where the error
+//! happens. Other helpers still return `Result<_, String>`; commands pick the kind for
+//! those with `.map_err(AppError::Validation)` and friends, and anything left over
+//! converts to `Internal`. The frontend receives `{ "kind": "db", "message": "..." }`.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AppError {
+    #[error("{0}")]
+    Db(String),
+    #[error("{0}")]
+    Io(String),
+    #[error("{0}")]
+    Network(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("{0}")]
+    Parse(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl AppError {
+    /// Stable category name sent to the frontend
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Db(_) => "db",
+            AppError::Io(_) => "io",
+            AppError::Network(_) => "network",
+            AppError::Validation(_) => "validation",
+            AppError::Parse(_) => "parse",
+            AppError::NotFound(_) => "notFound",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Same kind, with "context: " put in front of the message
+    pub fn with_context(self, context: impl std::fmt::Display) -> Self {
+        let wrap = |message: String| format!("{}: {}", context, message);
+        match self {
+            AppError::Db(m) => AppError::Db(wrap(m)),
+            AppError::Io(m) => AppError::Io(wrap(m)),
+            AppError::Network(m) => AppError::Network(wrap(m)),
+            AppError::Validation(m) => AppError::Validation(wrap(m)),
+            AppError::Parse(m) => AppError::Parse(wrap(m)),
+            AppError::NotFound(m) => AppError::NotFound(wrap(m)),
+            AppError::Internal(m) => AppError::Internal(wrap(m)),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Db(m)
+            | AppError::Io(m)
+            | AppError::Network(m)
+            | AppError::Validation(m)
+            | AppError::Parse(m)
+            | AppError::NotFound(m)
+            | AppError::Internal(m) => m,
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", self.message())?;
+        state.end()
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::from(message.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(error: rusqlite::Error) -> Self {
+        match error {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(error.to_string()),
+            other => AppError::Db(other.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(error.to_string()),
+            _ => AppError::Io(error.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        AppError::Network(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::Parse(error.to_string())
+    }
+}
+
+impl From<serde_yaml::Error> for AppError {
+    fn from(error: serde_yaml::Error) -> Self {
+        AppError::Parse(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untyped_messages_are_internal() {
+        assert_eq!(AppError::from("Query error: database is locked".to_string()).kind(), "internal");
+        assert_eq!(AppError::from("Scenario not found: abc").kind(), "internal");
+        assert_eq!(AppError::from(rusqlite::Error::QueryReturnedNoRows).kind(), "notFound");
+    }
+
+    #[test]
+    fn test_with_context_keeps_kind() {
+        let error = AppError::Db("Insert error: locked".to_string()).with_context("Step 'login'");
+        assert_eq!(error, AppError::Db("Step 'login': Insert error: locked".to_string()));
+    }
+
+    #[test]
+    fn test_serializes_kind_and_message() {
+        let error = AppError::NotFound("Project not found: p1".to_string());
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({ "kind": "notFound", "message": "Project not found: p1" })
+        );
+        assert_eq!(error.to_string(), "Project not found: p1");
+    }
+}
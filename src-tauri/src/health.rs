@@ -0,0 +1,319 @@
+//! Endpoint health rollup for a project
+//!
+//! Sends one quick request per scanned endpoint, with path, query and body parameters
+//! filled from their examples (or generated ones), and reports whether the endpoint
+//! answered, its status and latency, and whether the body still matches the stored
+//! response schema. Checks run concurrently up to `MAX_CONCURRENT_CHECKS` and can be
+//! cancelled per project; endpoints not checked before cancelling are left out.
+//! Only safe methods are sent unless the caller opts in, so a health check never
+//! creates or deletes data by accident.
+
+use crate::http_client::{self, ClientDefaults};
+use crate::scanner::parsers::ExampleGenerator;
+use crate::scanner::schema_validation::validate_endpoint_response;
+use crate::types::{ApiEndpoint, ApiParameter, Project};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Requests in flight at once during a health check
+pub const MAX_CONCURRENT_CHECKS: usize = 8;
+
+/// Per-request timeout; a slow endpoint counts as unreachable
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const SAFE_METHODS: &[&str] = &["GET", "HEAD", "OPTIONS"];
+
+/// Health of one endpoint from a single check
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EndpointHealth {
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: String,
+    pub method: String,
+    pub path: String,
+    pub url: String,
+    pub status: Option<u16>,
+    #[serde(rename = "latencyMs")]
+    pub latency_ms: Option<u64>,
+    pub reachable: bool, // Answered without a connection error, timeout or 5xx
+    #[serde(rename = "schemaValid")]
+    pub schema_valid: Option<bool>, // None when there is no stored schema for the status
+    pub skipped: bool, // Unsafe method not sent
+    pub error: Option<String>,
+    #[serde(rename = "checkedAt")]
+    pub checked_at: i64,
+}
+
+fn running_checks() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static RUNNING: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Ask a running health check to stop launching requests; false if none is active
+pub fn cancel_health_check(project_id: &str) -> bool {
+    let running = running_checks().lock().unwrap_or_else(|e| e.into_inner());
+    match running.get(project_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Keeps a project's check registered as cancelable until it finishes
+struct CheckRegistration(String);
+
+impl CheckRegistration {
+    fn new(project_id: &str, flag: Arc<AtomicBool>) -> Self {
+        running_checks()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(project_id.to_string(), flag);
+        Self(project_id.to_string())
+    }
+}
+
+impl Drop for CheckRegistration {
+    fn drop(&mut self) {
+        running_checks()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.0);
+    }
+}
+
+/// Example value for a parameter: its example, its default, or a generated one
+fn parameter_value(param: &ApiParameter) -> serde_json::Value {
+    param
+        .example
+        .clone()
+        .or_else(|| param.default_value.clone())
+        .or_else(|| ExampleGenerator::generate_example(&param.param_type, &param.name, &param.validation))
+        .unwrap_or_else(|| serde_json::Value::String("1".to_string()))
+}
+
+fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// URL and JSON body for a check. Parameters named in the path (`{id}` or `:id`) fill it in;
+/// the other required parameters go to the query string for GET-like methods and the body
+/// otherwise.
+pub fn example_request(endpoint: &ApiEndpoint, base_url: &str) -> (String, Option<serde_json::Value>) {
+    let mut path = endpoint.path.clone();
+    let mut rest = Vec::new();
+    for param in &endpoint.parameters {
+        let value = value_text(&parameter_value(param));
+        let braced = format!("{{{}}}", param.name);
+        let colon = format!(":{}", param.name);
+        if path.contains(&braced) {
+            path = path.replace(&braced, &encode_component(&value));
+        } else if path.split('/').any(|segment| segment == colon) {
+            path = path
+                .split('/')
+                .map(|segment| if segment == colon { encode_component(&value) } else { segment.to_string() })
+                .collect::<Vec<_>>()
+                .join("/");
+        } else if param.required {
+            rest.push(param);
+        }
+    }
+
+    let mut url = format!("{}{}", base_url.trim_end_matches('/'), path);
+    let method = endpoint.method.to_uppercase();
+    if SAFE_METHODS.contains(&method.as_str()) {
+        if !rest.is_empty() {
+            let query: Vec<String> = rest
+                .iter()
+                .map(|p| format!("{}={}", encode_component(&p.name), encode_component(&value_text(&parameter_value(p)))))
+                .collect();
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+        (url, None)
+    } else {
+        let body: serde_json::Map<String, serde_json::Value> =
+            rest.iter().map(|p| (p.name.clone(), parameter_value(p))).collect();
+        (url, Some(serde_json::Value::Object(body)))
+    }
+}
+
+/// Percent-encode a value for a path segment or query component
+fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Check every endpoint of a project; results are in endpoint order
+pub async fn check_endpoints(
+    project: &Project,
+    endpoints: Vec<ApiEndpoint>,
+    include_unsafe_methods: bool,
+) -> Vec<EndpointHealth> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let _registration = CheckRegistration::new(&project.id, cancelled.clone());
+
+    let base_url = project.base_url.clone().unwrap_or_else(|| "http://localhost:8080".to_string());
+    let client_defaults = ClientDefaults::from_project(project);
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .danger_accept_invalid_certs(!project.verify_tls)
+        .user_agent(client_defaults.user_agent())
+        .default_headers(client_defaults.header_map())
+        .build()
+        .unwrap_or_default();
+
+    let mut results: Vec<(usize, EndpointHealth)> = futures::stream::iter(endpoints.into_iter().enumerate())
+        .take_while(|_| std::future::ready(!cancelled.load(Ordering::SeqCst)))
+        .map(|(index, endpoint)| {
+            let client = client.clone();
+            let base_url = base_url.clone();
+            let variables = project.variables.clone();
+            async move {
+                (index, check_endpoint(&client, &endpoint, &base_url, &variables, include_unsafe_methods).await)
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_CHECKS)
+        .collect()
+        .await;
+
+    if cancelled.load(Ordering::SeqCst) {
+        log::info!("[Health] Check for project {} cancelled after {} endpoints", project.id, results.len());
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, health)| health).collect()
+}
+
+async fn check_endpoint(
+    client: &reqwest::Client,
+    endpoint: &ApiEndpoint,
+    base_url: &str,
+    variables: &HashMap<String, serde_json::Value>,
+    include_unsafe_methods: bool,
+) -> EndpointHealth {
+    let method = endpoint.method.to_uppercase();
+    let (url, body) = example_request(endpoint, base_url);
+    let mut health = EndpointHealth {
+        endpoint_id: endpoint.id.clone(),
+        method: method.clone(),
+        path: endpoint.path.clone(),
+        url: url.clone(),
+        status: None,
+        latency_ms: None,
+        reachable: false,
+        schema_valid: None,
+        skipped: false,
+        error: None,
+        checked_at: chrono::Utc::now().timestamp(),
+    };
+
+    if !include_unsafe_methods && !SAFE_METHODS.contains(&method.as_str()) {
+        health.skipped = true;
+        return health;
+    }
+
+    let http_method = match reqwest::Method::from_bytes(method.as_bytes()) {
+        Ok(m) => m,
+        Err(_) => {
+            health.error = Some(format!("Unsupported method: {}", method));
+            return health;
+        }
+    };
+    let mut request = client.request(http_method, &url);
+    for (name, template) in http_client::missing_auth_headers(endpoint, &HashMap::new()) {
+        let value = http_client::resolve_template(&template, variables);
+        if !value.contains("{{") {
+            request = request.header(name, value);
+        }
+    }
+    if let Some(body) = &body {
+        request = request.json(body);
+    }
+
+    let start = Instant::now();
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            health.latency_ms = Some(start.elapsed().as_millis() as u64);
+            health.status = Some(status);
+            health.reachable = status < 500;
+            let text = response.text().await.unwrap_or_default();
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) {
+                let validation = validate_endpoint_response(endpoint, status, &json);
+                if validation.schema_found {
+                    health.schema_valid = Some(validation.valid);
+                }
+            }
+        }
+        Err(e) => {
+            health.latency_ms = Some(start.elapsed().as_millis() as u64);
+            health.error = Some(if e.is_timeout() {
+                format!("Timed out after {}s", CHECK_TIMEOUT.as_secs())
+            } else {
+                format!("Request failed: {}", e)
+            });
+        }
+    }
+    health
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(method: &str, path: &str, parameters: serde_json::Value) -> ApiEndpoint {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("p1-{}-{}", method, path), "name": path, "method": method, "path": path,
+            "service": "api", "description": "", "parameters": parameters, "category": "users",
+            "explanation": null, "responses": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_example_request() {
+        let get = endpoint("GET", "/users/{id}/posts", serde_json::json!([
+            { "name": "id", "type": "integer", "required": true, "description": "", "example": 7, "defaultValue": null },
+            { "name": "q", "type": "string", "required": true, "description": "", "example": "a b", "defaultValue": null },
+            { "name": "page", "type": "integer", "required": false, "description": "", "example": 2, "defaultValue": null }
+        ]));
+        assert_eq!(
+            example_request(&get, "http://localhost:3000/"),
+            ("http://localhost:3000/users/7/posts?q=a%20b".to_string(), None)
+        );
+
+        let post = endpoint("POST", "/teams/:teamId/members", serde_json::json!([
+            { "name": "teamId", "type": "string", "required": true, "description": "", "example": null, "defaultValue": "t-1" },
+            { "name": "email", "type": "string", "required": true, "description": "", "example": null, "defaultValue": null }
+        ]));
+        let (url, body) = example_request(&post, "http://localhost:3000");
+        assert_eq!(url, "http://localhost:3000/teams/t-1/members");
+        assert_eq!(body, Some(serde_json::json!({ "email": "user@example.com" })));
+    }
+
+    #[test]
+    fn test_unsafe_methods_are_skipped() {
+        let client = reqwest::Client::new();
+        let delete = endpoint("DELETE", "/users/{id}", serde_json::json!([]));
+        let health = futures::executor::block_on(
+            check_endpoint(&client, &delete, "http://127.0.0.1:9", &HashMap::new(), false),
+        );
+        assert!(health.skipped);
+        assert!(!health.reachable);
+        assert_eq!(health.status, None);
+
+        assert!(!cancel_health_check("no-such-project"));
+    }
+}
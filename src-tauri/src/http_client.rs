@@ -1,8 +1,14 @@
-use crate::types::{ApiRequest, ApiResponse};
+use crate::error::AppError;
+use crate::scenario::executor::ScenarioExecutor;
+use crate::scenario::types::{RequestStepConfig, TestScenarioStep, TestStepType};
+use crate::types::{ApiEndpoint, ApiParameter, ApiRequest, ApiResponse, Project, SavedRequest};
+use regex::Regex;
 use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::HashMap;
 use std::time::Instant;
 
-pub fn execute_request(request: ApiRequest) -> Result<ApiResponse, String> {
+pub fn execute_request(request: ApiRequest) -> Result<ApiResponse, AppError> {
     log::info!("[HTTP] Creating blocking client");
     let client = Client::new();
     let start = Instant::now();
@@ -35,7 +41,7 @@ pub fn execute_request(request: ApiRequest) -> Result<ApiResponse, String> {
         _ => {
             let error_msg = format!("Unsupported method: {}", request.method);
             log::error!("[HTTP] {}", error_msg);
-            return Err(error_msg);
+            return Err(AppError::Validation(error_msg));
         },
     };
 
@@ -98,7 +104,7 @@ pub fn execute_request(request: ApiRequest) -> Result<ApiResponse, String> {
                 log::debug!("[HTTP] Request body at failure: {:?}", request.parameters);
             }
             
-            error_msg
+            AppError::Network(error_msg)
         })?;
 
     let send_duration = send_start.elapsed().as_millis();
@@ -129,7 +135,7 @@ pub fn execute_request(request: ApiRequest) -> Result<ApiResponse, String> {
             let error_msg = format!("Failed to parse response JSON: {}", e);
             log::error!("[HTTP] {}", error_msg);
             log::error!("[HTTP] Error chain: {}", get_error_chain(&e));
-            error_msg
+            AppError::Parse(error_msg)
         })?;
     let parse_duration = parse_start.elapsed().as_millis();
     log::debug!("[HTTP] Body parsed in {}ms", parse_duration);
@@ -147,9 +153,81 @@ pub fn execute_request(request: ApiRequest) -> Result<ApiResponse, String> {
         headers,
         duration,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        schema_validation: None,
     })
 }
 
+/// User-Agent sent by scenario and performance runs unless the project overrides it
+pub const DEFAULT_USER_AGENT: &str = concat!("LookAPI/", env!("CARGO_PKG_VERSION"));
+
+/// User-Agent and always-sent headers for test traffic; per-request headers still win
+#[derive(Debug, Clone, Default)]
+pub struct ClientDefaults {
+    pub user_agent: Option<String>,
+    pub headers: HashMap<String, String>,
+}
+
+impl ClientDefaults {
+    pub fn from_project(project: &Project) -> Self {
+        Self {
+            user_agent: project.user_agent.clone(),
+            headers: project.default_headers.clone(),
+        }
+    }
+
+    pub fn user_agent(&self) -> String {
+        self.user_agent
+            .as_ref()
+            .filter(|ua| !ua.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string())
+    }
+
+    /// Header map for the client builder; invalid names or values are skipped with a warning
+    pub fn header_map(&self) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in &self.headers {
+            match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+                (Ok(name), Ok(value)) => {
+                    map.insert(name, value);
+                }
+                _ => log::warn!("[HTTP] Skipping invalid default header: {}", name),
+            }
+        }
+        map
+    }
+}
+
+/// Normalize a user-entered base URL: assume `http://` when the scheme is missing, drop
+/// trailing slashes, and reject anything that isn't a plain http(s) origin with optional path
+pub fn normalize_base_url(input: &str) -> Result<String, String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Base URL is empty".to_string());
+    }
+
+    let with_scheme = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        log::warn!("[HTTP] Base URL '{}' has no scheme, assuming http://", trimmed);
+        format!("http://{}", trimmed)
+    };
+
+    let url = reqwest::Url::parse(&with_scheme)
+        .map_err(|e| format!("Invalid base URL '{}': {}", trimmed, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Invalid base URL '{}': scheme must be http or https, not {}", trimmed, url.scheme()));
+    }
+    if !url.has_host() {
+        return Err(format!("Invalid base URL '{}': missing host", trimmed));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(format!("Invalid base URL '{}': remove the query string or fragment", trimmed));
+    }
+
+    Ok(with_scheme.trim_end_matches('/').to_string())
+}
+
 fn get_error_chain(error: &dyn std::error::Error) -> String {
     let mut chain = vec![error.to_string()];
     let mut source = error.source();
@@ -160,12 +238,591 @@ fn get_error_chain(error: &dyn std::error::Error) -> String {
     chain.join(" -> ")
 }
 
+/// Build an executable request from a saved request. Placeholders are resolved the way a
+/// scenario run resolves them, and `params` always go to the query string, next to any body.
+/// Uses a scenario executor, so call it off the async runtime.
+pub fn request_from_saved(
+    saved: &SavedRequest,
+    variables: &HashMap<String, serde_json::Value>,
+) -> Result<ApiRequest, AppError> {
+    let query = match &saved.params {
+        serde_json::Value::Null => None,
+        serde_json::Value::Object(map) => Some(
+            map.iter()
+                .map(|(k, v)| {
+                    let value = match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (k.clone(), value)
+                })
+                .collect(),
+        ),
+        other => {
+            return Err(AppError::Validation(format!(
+                "Saved request params must be an object of query parameters, got {}",
+                other
+            )))
+        }
+    };
+    let config = RequestStepConfig {
+        endpoint_id: saved.endpoint_id.clone(),
+        url: saved.url.clone(),
+        method: saved.method.to_uppercase(),
+        headers: Some(saved.headers.clone()),
+        params: None,
+        body: (!saved.body.is_null()).then(|| saved.body.clone()),
+        query,
+        service: None,
+        body_file: None,
+        extract_variables: None,
+        assertions: None,
+        with_items_from_csv: None,
+        follow_redirects: None,
+        max_redirects: None,
+    };
+    let step = TestScenarioStep {
+        id: saved.id.clone(),
+        scenario_id: String::new(),
+        step_order: 0,
+        step_type: TestStepType::Request,
+        name: saved.name.clone(),
+        config: serde_json::to_value(&config).map_err(|e| AppError::Parse(format!("Invalid saved request: {}", e)))?,
+        enabled: true,
+        enabled_if: None,
+        group: None,
+    };
+    let resolved = ScenarioExecutor::new()
+        .with_variables(variables.clone())
+        .resolve_request_step(&step)
+        .map_err(AppError::Validation)?;
+
+    Ok(ApiRequest {
+        endpoint: resolved.url,
+        method: resolved.method,
+        parameters: resolved.body.unwrap_or(serde_json::Value::Null),
+        headers: Some(resolved.headers),
+        endpoint_id: saved.endpoint_id.clone(),
+        saved_request_id: Some(saved.id.clone()),
+        variables: None,
+        validate_schema: false,
+        skip_auth: false,
+    })
+}
+
+/// Header templates for an auth scheme: `Authorization: Bearer {{ authToken }}` by default
+/// (JWT, OAuth2, ...), `X-API-Key: {{ apiKey }}` for API keys, `Basic {{ basicAuth }}` for basic
+pub fn auth_header_templates(auth_type: Option<&str>) -> Vec<(String, String)> {
+    let kind = auth_type.unwrap_or("bearer").to_lowercase().replace(['-', '_'], "");
+    match kind.as_str() {
+        "apikey" => vec![("X-API-Key".to_string(), "{{ apiKey }}".to_string())],
+        "basic" => vec![("Authorization".to_string(), "Basic {{ basicAuth }}".to_string())],
+        _ => vec![("Authorization".to_string(), "Bearer {{ authToken }}".to_string())],
+    }
+}
+
+/// Auth header templates an endpoint needs that `existing` doesn't already set (case-insensitive)
+pub fn missing_auth_headers(endpoint: &ApiEndpoint, existing: &HashMap<String, String>) -> Vec<(String, String)> {
+    if !endpoint.auth_required {
+        return Vec::new();
+    }
+    auth_header_templates(endpoint.auth_type.as_deref())
+        .into_iter()
+        .filter(|(name, _)| !existing.keys().any(|k| k.eq_ignore_ascii_case(name)))
+        .collect()
+}
+
+/// Replace {{name}} placeholders; unknown names are left untouched
+pub fn resolve_template(input: &str, variables: &HashMap<String, serde_json::Value>) -> String {
+    let re = Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap();
+    re.replace_all(input, |cap: &regex::Captures| {
+        match variables.get(&cap[1]) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+            None => cap[0].to_string(),
+        }
+    })
+    .into_owned()
+}
+
+fn resolve_template_in_json(
+    value: &serde_json::Value,
+    variables: &HashMap<String, serde_json::Value>,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => serde_json::Value::String(resolve_template(s, variables)),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), resolve_template_in_json(v, variables)))
+                .collect(),
+        ),
+        serde_json::Value::Array(arr) => serde_json::Value::Array(
+            arr.iter().map(|v| resolve_template_in_json(v, variables)).collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
 pub fn generate_curl(url: &str, method: &str, body: Option<&serde_json::Value>) -> String {
-    let mut curl = format!("curl -X {} '{}'", method, url);
+    generate_curl_with_headers(url, method, &[], body)
+}
+
+/// curl command with explicit headers; bodies get a JSON Content-Type unless a header sets one
+pub fn generate_curl_with_headers(
+    url: &str,
+    method: &str,
+    headers: &[(String, String)],
+    body: Option<&serde_json::Value>,
+) -> String {
+    let mut curl = format!("curl -X {} {}", method, shell_quote(url));
+
+    for (name, value) in headers {
+        curl.push_str(&format!(" -H {}", shell_quote(&format!("{}: {}", name, value))));
+    }
 
     if let Some(body) = body {
-        curl.push_str(&format!(" -H 'Content-Type: application/json' -d '{}'", body));
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")) {
+            curl.push_str(" -H 'Content-Type: application/json'");
+        }
+        curl.push_str(&format!(" -d {}", shell_quote(&body.to_string())));
     }
 
     curl
 }
+
+/// Runnable curl: `{{name}}` placeholders resolved from `variables`, a relative URL prefixed
+/// with `baseUrl`, and the auth headers `endpoint` requires added. An auth header whose value
+/// can't be resolved is left out, as it is when sending the request.
+pub fn generate_resolved_curl(
+    url: &str,
+    method: &str,
+    headers: &HashMap<String, String>,
+    body: Option<&serde_json::Value>,
+    variables: &HashMap<String, serde_json::Value>,
+    endpoint: Option<&ApiEndpoint>,
+) -> String {
+    let mut url = resolve_template(url, variables);
+    if url.starts_with('/') {
+        if let Some(serde_json::Value::String(base_url)) = variables.get("baseUrl") {
+            url = format!("{}{}", base_url.trim_end_matches('/'), url);
+        }
+    }
+
+    let mut resolved_headers: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.clone(), resolve_template(value, variables)))
+        .collect();
+    resolved_headers.sort();
+    if let Some(endpoint) = endpoint {
+        for (name, template) in missing_auth_headers(endpoint, headers) {
+            let value = resolve_template(&template, variables);
+            if value.contains("{{") {
+                log::warn!("[HTTP] Endpoint {} requires auth but {} is unresolved: {}", endpoint.id, name, template);
+                continue;
+            }
+            resolved_headers.push((name, value));
+        }
+    }
+
+    let body = body.map(|b| resolve_template_in_json(b, variables));
+    generate_curl_with_headers(&url, &method.to_uppercase(), &resolved_headers, body.as_ref())
+}
+
+/// Single-quote a shell word, escaping embedded single quotes as '\''
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// A curl invocation broken down into its request parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedCurl {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    pub form_fields: Vec<(String, String)>, // -F/--form multipart fields
+}
+
+/// Parse a curl command line (the inverse of `generate_curl`)
+pub fn parse_curl(command: &str) -> Result<ParsedCurl, String> {
+    let tokens = split_shell_words(command)?;
+    let mut iter = tokens.into_iter();
+
+    match iter.next() {
+        Some(ref first) if first == "curl" => {}
+        _ => return Err("Command must start with 'curl'".to_string()),
+    }
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut form_fields = Vec::new();
+    let mut data_as_query = false;
+
+    while let Some(token) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("Missing value for {}", flag));
+
+        match token.as_str() {
+            "-X" | "--request" => method = Some(value(&token)?.to_uppercase()),
+            "-H" | "--header" => {
+                let header = value(&token)?;
+                if let Some((name, val)) = header.split_once(':') {
+                    headers.push((name.trim().to_string(), val.trim().to_string()));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" | "--data-urlencode" => {
+                data.push(value(&token)?);
+            }
+            "-F" | "--form" => {
+                let field = value(&token)?;
+                if let Some((name, val)) = field.split_once('=') {
+                    form_fields.push((name.to_string(), val.to_string()));
+                }
+            }
+            "-A" | "--user-agent" => headers.push(("User-Agent".to_string(), value(&token)?)),
+            "-b" | "--cookie" => headers.push(("Cookie".to_string(), value(&token)?)),
+            "-e" | "--referer" => headers.push(("Referer".to_string(), value(&token)?)),
+            "--url" => url = Some(value(&token)?),
+            "-G" | "--get" => data_as_query = true,
+            "-I" | "--head" => method = Some("HEAD".to_string()),
+            // Flags that take a value we don't need
+            "-u" | "--user" | "-o" | "--output" | "-m" | "--max-time" | "--connect-timeout" | "-x" | "--proxy" => {
+                value(&token)?;
+            }
+            other if other.starts_with('-') => {
+                log::debug!("[HTTP] Ignoring curl flag: {}", other);
+            }
+            other => {
+                if url.is_none() {
+                    url = Some(other.to_string());
+                }
+            }
+        }
+    }
+
+    let mut url = url.ok_or_else(|| "No URL found in curl command".to_string())?;
+    let mut body = if data.is_empty() { None } else { Some(data.join("&")) };
+
+    if data_as_query {
+        if let Some(query) = body.take() {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{}{}{}", url, separator, query);
+        }
+    }
+
+    let method = method.unwrap_or_else(|| {
+        if body.is_some() || !form_fields.is_empty() { "POST" } else { "GET" }.to_string()
+    });
+
+    Ok(ParsedCurl { method, url, headers, body, form_fields })
+}
+
+/// Derive endpoint parameters from the query string, JSON/form body and multipart fields
+pub fn curl_parameters(parsed: &ParsedCurl, url: &reqwest::Url) -> Vec<ApiParameter> {
+    let mut parameters: Vec<ApiParameter> = url.query_pairs()
+        .map(|(name, value)| curl_parameter(&name, infer_json_value(&value), "Query parameter"))
+        .collect();
+
+    if let Some(ref body) = parsed.body {
+        match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(serde_json::Value::Object(map)) => {
+                for (name, value) in map {
+                    parameters.push(curl_parameter(&name, value, "Body field"));
+                }
+            }
+            Ok(_) => {}
+            Err(_) => {
+                // application/x-www-form-urlencoded
+                let form = reqwest::Url::parse(&format!("http://form.local/?{}", body)).ok();
+                for (name, value) in form.iter().flat_map(|u| u.query_pairs()) {
+                    parameters.push(curl_parameter(&name, infer_json_value(&value), "Form field"));
+                }
+            }
+        }
+    }
+
+    for (name, value) in &parsed.form_fields {
+        let example = if value.starts_with('@') {
+            serde_json::Value::String(value.clone())
+        } else {
+            infer_json_value(value)
+        };
+        parameters.push(curl_parameter(name, example, "Form field"));
+    }
+
+    parameters
+}
+
+fn curl_parameter(name: &str, example: serde_json::Value, description: &str) -> ApiParameter {
+    let param_type = match example {
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(ref n) if n.is_f64() => "number",
+        serde_json::Value::Number(_) => "integer",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+        _ => "string",
+    };
+    ApiParameter {
+        name: name.to_string(),
+        param_type: param_type.to_string(),
+        required: false,
+        description: description.to_string(),
+        example: Some(example),
+        default_value: None,
+        validation: None,
+    }
+}
+
+fn infer_json_value(raw: &str) -> serde_json::Value {
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(value @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_))) => value,
+        _ => serde_json::Value::String(raw.to_string()),
+    }
+}
+
+/// Split a command line into words, honouring quotes, escapes and line continuations
+fn split_shell_words(input: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => current.push(ch),
+                            Some('\n') => {}
+                            Some(ch) => {
+                                current.push('\\');
+                                current.push(ch);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') | Some('\r') => {}
+                Some(ch) => {
+                    in_word = true;
+                    current.push(ch);
+                }
+                None => {}
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_defaults() {
+        let defaults = ClientDefaults::default();
+        assert!(defaults.user_agent().starts_with("LookAPI/"));
+
+        let mut headers = HashMap::new();
+        headers.insert("X-Test-Traffic".to_string(), "lookapi".to_string());
+        headers.insert("Bad Header".to_string(), "x".to_string());
+        let defaults = ClientDefaults {
+            user_agent: Some("Acme-QA/2.0".to_string()),
+            headers,
+        };
+        assert_eq!(defaults.user_agent(), "Acme-QA/2.0");
+        let map = defaults.header_map();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("x-test-traffic").unwrap(), "lookapi");
+    }
+
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(normalize_base_url("localhost:3000").unwrap(), "http://localhost:3000");
+        assert_eq!(normalize_base_url(" https://api.example.com/ ").unwrap(), "https://api.example.com");
+        assert_eq!(normalize_base_url("https://api.example.com/v1//").unwrap(), "https://api.example.com/v1");
+        assert_eq!(normalize_base_url("127.0.0.1:8080/api").unwrap(), "http://127.0.0.1:8080/api");
+
+        assert!(normalize_base_url("ftp://files.example.com").unwrap_err().contains("http or https"));
+        assert!(normalize_base_url("").is_err());
+        assert!(normalize_base_url("http://").is_err());
+        assert!(normalize_base_url("https://api.example.com/?debug=1").is_err());
+        assert!(normalize_base_url("http://exa mple.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_curl_json_body() {
+        let parsed = parse_curl(
+            "curl -X POST 'http://localhost:3000/api/users?notify=true' \\\n  -H 'Content-Type: application/json' \\\n  --data-raw '{\"name\": \"Jane\", \"age\": 30}'",
+        ).unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.url, "http://localhost:3000/api/users?notify=true");
+        assert_eq!(parsed.headers, vec![("Content-Type".to_string(), "application/json".to_string())]);
+
+        let url = reqwest::Url::parse(&parsed.url).unwrap();
+        let params = curl_parameters(&parsed, &url);
+        let names: Vec<_> = params.iter().map(|p| (p.name.as_str(), p.param_type.as_str())).collect();
+        assert_eq!(names, vec![("notify", "boolean"), ("age", "integer"), ("name", "string")]);
+    }
+
+    #[test]
+    fn test_parse_curl_form_data() {
+        let parsed = parse_curl("curl https://api.example.com/login -d \"email=a@b.com&remember=1\" -F avatar=@me.png").unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.body.as_deref(), Some("email=a@b.com&remember=1"));
+
+        let url = reqwest::Url::parse(&parsed.url).unwrap();
+        let params = curl_parameters(&parsed, &url);
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[2].example, Some(serde_json::json!("@me.png")));
+    }
+
+    #[test]
+    fn test_request_from_saved_resolves_variables() {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), "Bearer {{token}}".to_string());
+        let saved = SavedRequest {
+            id: "saved-1".to_string(),
+            project_id: None,
+            endpoint_id: None,
+            name: "Create user".to_string(),
+            method: "post".to_string(),
+            url: "{{baseUrl}}/users".to_string(),
+            headers,
+            params: serde_json::Value::Null,
+            body: serde_json::json!({"name": "{{name}}", "age": 30}),
+            created_at: 0,
+            updated_at: 0,
+        };
+
+        let mut variables = HashMap::new();
+        variables.insert("baseUrl".to_string(), serde_json::json!("http://localhost:3000"));
+        variables.insert("token".to_string(), serde_json::json!("abc"));
+
+        let request = request_from_saved(&saved, &variables).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.endpoint, "http://localhost:3000/users");
+        assert_eq!(request.headers.unwrap()["Authorization"], "Bearer abc");
+        // Unknown variables are left for the user to notice
+        assert_eq!(request.parameters, serde_json::json!({"name": "{{name}}", "age": 30}));
+    }
+
+    #[test]
+    fn test_request_from_saved_sends_params_as_query_next_to_body() {
+        let saved = SavedRequest {
+            id: "saved-2".to_string(),
+            project_id: None,
+            endpoint_id: None,
+            name: "Update user".to_string(),
+            method: "put".to_string(),
+            url: "{{baseUrl}}/users/{{user.id}}".to_string(),
+            headers: HashMap::new(),
+            params: serde_json::json!({"notify": true, "tag": "{{tag}}"}),
+            body: serde_json::json!({"name": "Ann"}),
+            created_at: 0,
+            updated_at: 0,
+        };
+
+        let mut variables = HashMap::new();
+        variables.insert("baseUrl".to_string(), serde_json::json!("http://localhost:3000"));
+        variables.insert("user".to_string(), serde_json::json!({"id": "a b"}));
+        variables.insert("tag".to_string(), serde_json::json!("vip"));
+
+        let request = request_from_saved(&saved, &variables).unwrap();
+        assert_eq!(request.endpoint, "http://localhost:3000/users/a%20b?notify=true&tag=vip");
+        assert_eq!(request.parameters, serde_json::json!({"name": "Ann"}));
+
+        let mut invalid = saved.clone();
+        invalid.params = serde_json::json!(["notify"]);
+        assert!(matches!(request_from_saved(&invalid, &variables), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_generate_resolved_curl() {
+        let endpoint: ApiEndpoint = serde_json::from_value(serde_json::json!({
+            "id": "p1-POST-orders", "name": "POST /orders", "method": "POST", "path": "/orders",
+            "service": "api", "description": "", "parameters": [], "category": "orders",
+            "explanation": null, "responses": null, "authRequired": true, "authType": "JWT"
+        })).unwrap();
+        let mut headers = HashMap::new();
+        headers.insert("X-Tenant".to_string(), "{{tenant}}".to_string());
+        let body = serde_json::json!({"sku": "{{sku}}"});
+
+        let mut variables = HashMap::new();
+        variables.insert("baseUrl".to_string(), serde_json::json!("http://localhost:3000/"));
+        variables.insert("tenant".to_string(), serde_json::json!("acme"));
+        variables.insert("sku".to_string(), serde_json::json!("A-1"));
+        variables.insert("authToken".to_string(), serde_json::json!("t0k"));
+
+        let curl = generate_resolved_curl("/orders", "post", &headers, Some(&body), &variables, Some(&endpoint));
+        assert_eq!(
+            curl,
+            "curl -X POST 'http://localhost:3000/orders' -H 'X-Tenant: acme' -H 'Authorization: Bearer t0k' \
+             -H 'Content-Type: application/json' -d '{\"sku\":\"A-1\"}'"
+        );
+
+        // Without a token the auth header is left out rather than sent as a placeholder
+        variables.remove("authToken");
+        let curl = generate_resolved_curl("/orders", "POST", &headers, None, &variables, Some(&endpoint));
+        assert_eq!(curl, "curl -X POST 'http://localhost:3000/orders' -H 'X-Tenant: acme'");
+
+        // The default mode keeps placeholders verbatim
+        assert_eq!(generate_curl("{{baseUrl}}/orders", "GET", None), "curl -X GET '{{baseUrl}}/orders'");
+    }
+
+    #[test]
+    fn test_missing_auth_headers() {
+        let mut endpoint: ApiEndpoint = serde_json::from_value(serde_json::json!({
+            "id": "p1-GET-users", "name": "GET /users", "method": "GET", "path": "/users",
+            "service": "api", "description": "", "parameters": [], "category": "users",
+            "explanation": null, "responses": null, "authRequired": true, "authType": "JWT"
+        })).unwrap();
+        let mut existing = HashMap::new();
+        assert_eq!(
+            missing_auth_headers(&endpoint, &existing),
+            vec![("Authorization".to_string(), "Bearer {{ authToken }}".to_string())]
+        );
+
+        existing.insert("authorization".to_string(), "Bearer mine".to_string());
+        assert!(missing_auth_headers(&endpoint, &existing).is_empty());
+
+        endpoint.auth_type = Some("api_key".to_string());
+        assert_eq!(missing_auth_headers(&endpoint, &existing)[0].0, "X-API-Key");
+        assert_eq!(auth_header_templates(Some("basic"))[0].1, "Basic {{ basicAuth }}");
+
+        endpoint.auth_required = false;
+        assert!(missing_auth_headers(&endpoint, &HashMap::new()).is_empty());
+    }
+}
@@ -1,6 +1,12 @@
 pub mod commands;
 pub mod database;
+pub mod engine;
+pub mod error;
+pub mod health;
 pub mod http_client;
+pub mod remote_probe;
+pub mod request_log;
+pub mod request_signing;
 pub mod scanner;
 pub mod scenario;
 pub mod security;
@@ -30,11 +36,27 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            commands::resume_scenario_schedules(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::execute_http_request,
+            commands::get_request_history,
+            commands::clear_request_history,
+            commands::create_saved_request,
+            commands::get_saved_requests,
+            commands::update_saved_request,
+            commands::delete_saved_request,
             commands::generate_curl_command,
+            commands::import_curl,
+            commands::import_openapi,
+            commands::probe_remote_api,
             commands::get_all_endpoints,
             commands::save_endpoint,
+            commands::delete_endpoints,
+            commands::update_endpoints_category,
+            commands::update_endpoints_service,
             commands::get_all_test_suites,
             commands::execute_sql_query,
             commands::export_response,
@@ -45,10 +67,22 @@ pub fn run() {
             commands::get_all_projects,
             commands::delete_project,
             commands::update_project_base_url,
+            commands::update_project_verify_tls,
+            commands::update_project_client_defaults,
+            commands::update_project_request_logging,
+            commands::update_project_services,
+            commands::update_project_request_signing,
+            commands::update_project_idempotency,
+            commands::update_project_body_limits,
+            commands::update_project_variables,
+            commands::import_variables,
+            commands::get_project_request_log,
+            commands::get_project_stats,
             commands::get_active_project,
             commands::set_active_project,
             commands::ensure_project_exists,
             commands::get_endpoints_by_project,
+            commands::get_endpoints_by_version,
             // Security testing commands
             commands::create_security_test_case,
             commands::get_security_test_cases,
@@ -66,15 +100,43 @@ pub fn run() {
             commands::update_test_scenario_step,
             commands::delete_test_scenario_step,
             commands::reorder_test_scenario_steps,
+            commands::set_test_scenario_steps_enabled,
+            commands::create_assertion_template,
+            commands::get_assertion_templates,
+            commands::update_assertion_template,
+            commands::delete_assertion_template,
+            commands::save_golden_response,
+            commands::get_golden_responses,
+            commands::delete_golden_response,
+            commands::infer_endpoint_response_schema,
+            commands::suggest_assertions,
+            commands::stop_scenario_run,
+            commands::get_endpoint_health,
+            commands::cancel_endpoint_health,
+            commands::get_endpoint_health_history,
+            commands::get_assertion_snapshots,
+            commands::delete_assertion_snapshot,
+            commands::generate_golden_assertions,
             commands::run_test_scenario,
             commands::get_test_scenario_runs,
+            commands::lint_scenario,
+            commands::start_scenario_schedule,
+            commands::stop_scenario_schedule,
+            commands::get_scenario_schedules,
+            commands::get_scenario_schedule_summary,
             // YAML export/import commands
             commands::export_scenario_yaml,
             commands::export_project_scenarios_yaml,
+            commands::export_project_scenarios_yaml_to_file,
             commands::preview_scenario_yaml_import,
             commands::preview_project_scenarios_yaml_import,
             commands::import_scenario_yaml,
             commands::import_project_scenarios_yaml,
+            commands::import_http_file,
+            commands::import_har,
+            commands::export_step,
+            commands::export_http_file,
+            commands::export_run_csv,
             commands::get_yaml_template,
             commands::generate_yaml_with_ai,
             commands::get_yaml_files,
@@ -83,6 +145,7 @@ pub fn run() {
             commands::update_scenario_from_yaml,
             // CSV commands
             commands::preview_csv_file,
+            commands::get_csv_headers,
             // Performance testing commands
             commands::create_performance_test,
             commands::get_performance_tests,
@@ -92,6 +155,7 @@ pub fn run() {
             commands::run_performance_test,
             commands::get_performance_test_runs,
             commands::get_performance_test_run,
+            commands::get_performance_raw_samples,
             // Request tabs commands
             commands::save_request_tabs,
             commands::save_request_tab,
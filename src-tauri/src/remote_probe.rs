@@ -0,0 +1,306 @@
+//! Black-box probing of a remote API
+//!
+//! For projects without source: fetches a fixed list of well-known discovery paths under a
+//! base URL and reports whether it looks like an API, whether an OpenAPI/Swagger document is
+//! published (with its endpoint count and content, ready for `import_openapi`), and which
+//! framework the response headers and cookies hint at. Requests are bounded by
+//! `PROBE_TIMEOUT` each and bodies are read up to `MAX_PROBE_BODY_BYTES`. The client honours
+//! the caller's TLS verification choice and the system proxy environment (`HTTPS_PROXY` etc.).
+
+use crate::http_client::DEFAULT_USER_AGENT;
+use crate::scanner::openapi::endpoints_from_openapi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Paths fetched under the base URL; OpenAPI candidates are tried in this order
+pub const DISCOVERY_PATHS: &[&str] = &[
+    "/",
+    "/openapi.json",
+    "/swagger.json",
+    "/v3/api-docs",
+    "/api-json",
+    "/swagger/v1/swagger.json",
+    "/openapi.yaml",
+    "/.well-known/openapi.json",
+    "/.well-known/openapi.yaml",
+    "/health",
+    "/actuator/health",
+];
+
+/// Per-request timeout
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bodies are cut off here; larger OpenAPI documents are reported but not parsed
+pub const MAX_PROBE_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Response headers reported as-is because they identify the server stack
+const SERVER_HEADERS: &[&str] = &["server", "x-powered-by", "x-aspnet-version", "x-aspnetmvc-version", "x-runtime", "via"];
+
+/// Outcome of one discovery request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProbedPath {
+    pub path: String,
+    pub status: Option<u16>,
+    #[serde(rename = "contentType")]
+    pub content_type: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A framework suggested by the responses, with what suggested it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FrameworkHint {
+    pub framework: String,
+    pub evidence: String,
+}
+
+/// Summary of probing a base URL
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RemoteProbe {
+    #[serde(rename = "baseUrl")]
+    pub base_url: String,
+    pub reachable: bool, // At least one path answered
+    #[serde(rename = "isApi")]
+    pub is_api: bool, // A JSON response or an OpenAPI document was found
+    #[serde(rename = "openapiUrl")]
+    pub openapi_url: Option<String>,
+    #[serde(rename = "openapiEndpointCount")]
+    pub openapi_endpoint_count: Option<usize>,
+    #[serde(rename = "openapiDocument")]
+    pub openapi_document: Option<String>, // Pass to import_openapi to create the endpoints
+    #[serde(rename = "serverHeaders")]
+    pub server_headers: HashMap<String, String>,
+    #[serde(rename = "frameworkHints")]
+    pub framework_hints: Vec<FrameworkHint>,
+    pub paths: Vec<ProbedPath>,
+}
+
+struct Fetched {
+    probed: ProbedPath,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+/// Probe every discovery path under `base_url` (already normalized, without trailing slash)
+pub async fn probe(base_url: &str, verify_tls: bool) -> RemoteProbe {
+    let client = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .danger_accept_invalid_certs(!verify_tls)
+        .user_agent(DEFAULT_USER_AGENT)
+        .build()
+        .unwrap_or_default();
+
+    let fetched = futures::future::join_all(
+        DISCOVERY_PATHS.iter().map(|path| fetch(&client, base_url, path)),
+    )
+    .await;
+
+    let mut result = RemoteProbe {
+        base_url: base_url.to_string(),
+        ..Default::default()
+    };
+    let mut all_headers = Vec::new();
+    for item in &fetched {
+        let ok = item.probed.status.is_some_and(|s| (200..300).contains(&s));
+        result.reachable |= item.probed.status.is_some();
+        result.is_api |= ok && item.probed.content_type.as_deref().is_some_and(|ct| ct.contains("json"));
+
+        if result.openapi_url.is_none() && ok && item.probed.path != "/" {
+            if let Some(count) = item.body.as_deref().and_then(|body| endpoints_from_openapi(body, "").ok()).map(|e| e.len()) {
+                result.openapi_url = Some(format!("{}{}", base_url, item.probed.path));
+                result.openapi_endpoint_count = Some(count);
+                result.openapi_document = item.body.clone();
+                result.is_api = true;
+            }
+        }
+        all_headers.extend(item.headers.iter().cloned());
+    }
+
+    for (name, value) in &all_headers {
+        if SERVER_HEADERS.contains(&name.as_str()) {
+            result.server_headers.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    let answered: Vec<&str> = fetched
+        .iter()
+        .filter(|f| f.probed.status.is_some_and(|s| (200..300).contains(&s)))
+        .map(|f| f.probed.path.as_str())
+        .collect();
+    result.framework_hints = framework_hints(&all_headers, &answered);
+    result.paths = fetched.into_iter().map(|f| f.probed).collect();
+    result
+}
+
+async fn fetch(client: &reqwest::Client, base_url: &str, path: &str) -> Fetched {
+    let mut fetched = Fetched {
+        probed: ProbedPath { path: path.to_string(), status: None, content_type: None, error: None },
+        headers: Vec::new(),
+        body: None,
+    };
+    let mut response = match client.get(format!("{}{}", base_url, path)).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            fetched.probed.error = Some(if e.is_timeout() {
+                format!("Timed out after {}s", PROBE_TIMEOUT.as_secs())
+            } else {
+                format!("Request failed: {}", e)
+            });
+            return fetched;
+        }
+    };
+
+    fetched.probed.status = Some(response.status().as_u16());
+    fetched.headers = response
+        .headers()
+        .iter()
+        .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.as_str().to_lowercase(), v.to_string())))
+        .collect();
+    fetched.probed.content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let mut body = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) if body.len() + chunk.len() <= MAX_PROBE_BODY_BYTES => body.extend_from_slice(&chunk),
+            Ok(Some(_)) => {
+                fetched.probed.error = Some(format!("Body larger than {} bytes; not parsed", MAX_PROBE_BODY_BYTES));
+                return fetched;
+            }
+            Ok(None) => break,
+            Err(e) => {
+                fetched.probed.error = Some(format!("Failed to read body: {}", e));
+                return fetched;
+            }
+        }
+    }
+    fetched.body = String::from_utf8(body).ok();
+    fetched
+}
+
+/// Frameworks suggested by response headers/cookies and by which discovery paths answered
+pub fn framework_hints(headers: &[(String, String)], answered_paths: &[&str]) -> Vec<FrameworkHint> {
+    // (header, lowercase value fragment, framework)
+    const HEADER_RULES: &[(&str, &str, &str)] = &[
+        ("x-powered-by", "express", "express"),
+        ("x-powered-by", "next.js", "nextjs"),
+        ("x-powered-by", "php", "php"),
+        ("x-powered-by", "asp.net", "aspnet"),
+        ("x-aspnet-version", "", "aspnet"),
+        ("server", "kestrel", "aspnet"),
+        ("server", "uvicorn", "python"),
+        ("server", "gunicorn", "python"),
+        ("server", "werkzeug", "flask"),
+        ("server", "jetty", "java"),
+        ("server", "apache-coyote", "java"),
+        ("server", "puma", "rails"),
+        ("x-runtime", "", "rails"),
+    ];
+    // Session cookie name prefix -> framework
+    const COOKIE_RULES: &[(&str, &str)] = &[
+        ("laravel_session", "laravel"),
+        ("xsrf-token", "laravel"),
+        ("phpsessid", "php"),
+        ("jsessionid", "java"),
+        ("connect.sid", "express"),
+        ("csrftoken", "django"),
+        ("sessionid", "django"),
+        ("_rails", "rails"),
+    ];
+    const PATH_RULES: &[(&str, &str)] = &[("/actuator/health", "spring"), ("/v3/api-docs", "spring"), ("/api-json", "nestjs")];
+
+    let mut hints: Vec<FrameworkHint> = Vec::new();
+    let mut add = |framework: &str, evidence: String| {
+        if !hints.iter().any(|h| h.framework == framework) {
+            hints.push(FrameworkHint { framework: framework.to_string(), evidence });
+        }
+    };
+
+    for (name, value) in headers {
+        let lower = value.to_lowercase();
+        for (header, fragment, framework) in HEADER_RULES {
+            if name == header && lower.contains(fragment) {
+                add(framework, format!("{}: {}", name, value));
+            }
+        }
+        if name == "set-cookie" {
+            let cookie = lower.split('=').next().unwrap_or("").trim();
+            for (prefix, framework) in COOKIE_RULES {
+                if cookie.starts_with(prefix) {
+                    add(framework, format!("Cookie {}", cookie));
+                }
+            }
+        }
+    }
+    for (path, framework) in PATH_RULES {
+        if answered_paths.contains(path) {
+            add(framework, format!("{} responds", path));
+        }
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framework_hints() {
+        let headers = vec![
+            ("server".to_string(), "nginx/1.25".to_string()),
+            ("x-powered-by".to_string(), "Express".to_string()),
+            ("set-cookie".to_string(), "connect.sid=s%3Aabc; Path=/; HttpOnly".to_string()),
+            ("set-cookie".to_string(), "laravel_session=xyz; path=/".to_string()),
+        ];
+        let hints = framework_hints(&headers, &["/", "/actuator/health"]);
+        let frameworks: Vec<&str> = hints.iter().map(|h| h.framework.as_str()).collect();
+        assert_eq!(frameworks, vec!["express", "laravel", "spring"]);
+        assert_eq!(hints[0].evidence, "x-powered-by: Express");
+        assert_eq!(hints[1].evidence, "Cookie laravel_session");
+        assert!(framework_hints(&[], &["/"]).is_empty());
+    }
+
+    #[test]
+    fn test_probe_finds_openapi_document() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut stream = stream;
+                let mut buf = [0u8; 1024];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let (status, body) = if request.starts_with("GET /openapi.json ") {
+                    ("200 OK", r#"{"openapi":"3.0.0","info":{"title":"Shop"},"paths":{"/orders":{"get":{},"post":{}}}}"#)
+                } else if request.starts_with("GET /health ") {
+                    ("200 OK", r#"{"status":"up"}"#)
+                } else {
+                    ("404 Not Found", r#"{"message":"Not found"}"#)
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nX-Powered-By: Express\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        let base_url = format!("http://{}", addr);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(probe(&base_url, true));
+        assert!(result.reachable);
+        assert!(result.is_api);
+        assert_eq!(result.openapi_url, Some(format!("{}/openapi.json", base_url)));
+        assert_eq!(result.openapi_endpoint_count, Some(2));
+        assert_eq!(result.server_headers.get("x-powered-by").map(String::as_str), Some("Express"));
+        assert_eq!(result.framework_hints[0].framework, "express");
+        assert_eq!(result.paths.len(), DISCOVERY_PATHS.len());
+        assert_eq!(result.paths[0].status, Some(404));
+    }
+}
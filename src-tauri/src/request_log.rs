@@ -0,0 +1,189 @@
+//! Per-project request/response capture for scenario runs
+//!
+//! Independent of the global `RUST_LOG` level: projects with request logging enabled get
+//! every scenario request step appended to `<app data>/api-tester/logs/<project_id>.log`.
+//! The file is rotated by size, keeping a few older files as `<project_id>.log.1`, `.2`, ...
+//! Credentials in headers (see `redact_headers`) are masked before anything is written.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Size at which the current log file is rotated (5 MB)
+pub const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated files kept alongside the current one
+pub const MAX_ROTATED_FILES: usize = 3;
+
+/// Headers whose values are always masked in the log
+pub const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+    "x-auth-token",
+    "x-csrf-token",
+    "x-xsrf-token",
+];
+
+/// Name fragments that mark other credential-carrying headers, e.g. `X-Shop-Api-Key`
+const SENSITIVE_FRAGMENTS: &[&str] = &["api-key", "apikey", "token", "secret", "password"];
+
+/// Value written in place of a masked header
+pub const REDACTED: &str = "***";
+
+/// Headers sorted by name, with credential values replaced by `***`
+pub fn redact_headers(headers: &HashMap<String, String>) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let lower = name.to_ascii_lowercase();
+            let sensitive = SENSITIVE_HEADERS.contains(&lower.as_str())
+                || SENSITIVE_FRAGMENTS.iter().any(|fragment| lower.contains(fragment));
+            let value = if sensitive { REDACTED.to_string() } else { value.clone() };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+pub fn get_log_dir() -> PathBuf {
+    let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("api-tester");
+    path.push("logs");
+    fs::create_dir_all(&path).ok();
+    path
+}
+
+/// Appends timestamped entries to a size-rotated log file
+#[derive(Debug, Clone)]
+pub struct RequestLogger {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl RequestLogger {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_bytes: MAX_LOG_BYTES,
+        }
+    }
+
+    /// Logger writing to the project's file in the app data dir
+    pub fn for_project(project_id: &str) -> Self {
+        let file_name: String = project_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        Self::new(get_log_dir().join(format!("{}.log", file_name)))
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an entry; write failures are reported through `log` and never fail the run
+    pub fn write(&self, entry: &str) {
+        if let Err(e) = self.try_write(entry) {
+            log::warn!("[RequestLog] Failed to write {}: {}", self.path.display(), e);
+        }
+    }
+
+    fn try_write(&self, entry: &str) -> std::io::Result<()> {
+        let line = format!("[{}] {}\n", chrono::Utc::now().to_rfc3339(), entry);
+        let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Last `lines` lines of the current log file (empty if nothing was captured yet)
+    pub fn tail(&self, lines: usize) -> Result<Vec<String>, String> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read request log: {}", e)),
+        };
+        let all: Vec<&str> = content.lines().collect();
+        Ok(all[all.len().saturating_sub(lines)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_and_tail() {
+        let dir = std::env::temp_dir().join(format!("lookapi-request-log-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let logger = RequestLogger::new(dir.join("project.log")).with_max_bytes(120);
+
+        for i in 0..10 {
+            logger.write(&format!("GET /items/{} -> 200", i));
+        }
+
+        let tail = logger.tail(2).unwrap();
+        assert_eq!(tail.len(), 2);
+        assert!(tail[1].ends_with("GET /items/9 -> 200"));
+        assert!(fs::metadata(logger.path()).unwrap().len() <= 120);
+        assert!(logger.rotated_path(1).exists());
+        assert!(!logger.rotated_path(MAX_ROTATED_FILES + 1).exists());
+
+        let missing = RequestLogger::new(dir.join("missing.log"));
+        assert!(missing.tail(10).unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let headers: HashMap<String, String> = [
+            ("Authorization", "Bearer abc"),
+            ("cookie", "sid=1"),
+            ("Set-Cookie", "sid=2; HttpOnly"),
+            ("X-API-Key", "k-1"),
+            ("X-Shop-Api-Key", "k-2"),
+            ("X-Refresh-Token", "t-1"),
+            ("Content-Type", "application/json"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let redacted = redact_headers(&headers);
+        assert_eq!(redacted["Content-Type"], "application/json");
+        for name in ["Authorization", "cookie", "Set-Cookie", "X-API-Key", "X-Shop-Api-Key", "X-Refresh-Token"] {
+            assert_eq!(redacted[name], REDACTED, "{}", name);
+        }
+    }
+}
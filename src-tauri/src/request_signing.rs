@@ -0,0 +1,206 @@
+//! HMAC request signing for scenario request steps
+//!
+//! A project can carry a `RequestSigningConfig`; every request step then gets a signature
+//! header computed over a canonical string built from a template such as
+//! `"{method}\n{path}\n{timestamp}\n{body}"`. The secret never appears in logs, in the
+//! `Debug` output of the config or in serialized responses, where it is masked; only the
+//! database copy (`to_stored_json`) keeps it.
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha512};
+
+pub const SUPPORTED_ALGORITHMS: &[&str] = &["hmac-sha256", "hmac-sha512"];
+
+/// Stands in for the secret in serialized configs; sending it back keeps the stored secret
+pub const MASKED_SECRET: &str = "***";
+
+fn default_canonical_template() -> String {
+    "{method}\n{path}\n{timestamp}\n{body}".to_string()
+}
+
+fn default_encoding() -> String {
+    "hex".to_string()
+}
+
+#[derive(Clone, Deserialize, PartialEq)]
+pub struct RequestSigningConfig {
+    pub algorithm: String, // "hmac-sha256" or "hmac-sha512"
+    pub secret: String,
+    #[serde(rename = "headerName")]
+    pub header_name: String,
+    #[serde(rename = "canonicalTemplate", default = "default_canonical_template")]
+    pub canonical_template: String, // Placeholders: {method} {path} {query} {body} {timestamp}
+    #[serde(default = "default_encoding")]
+    pub encoding: String, // "hex" or "base64"
+    #[serde(rename = "timestampHeader", default)]
+    pub timestamp_header: Option<String>, // Also send the signed timestamp in this header
+}
+
+impl std::fmt::Debug for RequestSigningConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestSigningConfig")
+            .field("algorithm", &self.algorithm)
+            .field("secret", &MASKED_SECRET)
+            .field("header_name", &self.header_name)
+            .field("canonical_template", &self.canonical_template)
+            .field("encoding", &self.encoding)
+            .field("timestamp_header", &self.timestamp_header)
+            .finish()
+    }
+}
+
+impl Serialize for RequestSigningConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RequestSigningConfig", 6)?;
+        state.serialize_field("algorithm", &self.algorithm)?;
+        state.serialize_field("secret", MASKED_SECRET)?;
+        state.serialize_field("headerName", &self.header_name)?;
+        state.serialize_field("canonicalTemplate", &self.canonical_template)?;
+        state.serialize_field("encoding", &self.encoding)?;
+        state.serialize_field("timestampHeader", &self.timestamp_header)?;
+        state.end()
+    }
+}
+
+impl RequestSigningConfig {
+    /// JSON for the database column, secret included
+    pub fn to_stored_json(&self) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        value["secret"] = serde_json::Value::String(self.secret.clone());
+        serde_json::to_string(&value)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !SUPPORTED_ALGORITHMS.contains(&self.algorithm.as_str()) {
+            return Err(format!(
+                "Unsupported signing algorithm: {} (expected one of {})",
+                self.algorithm,
+                SUPPORTED_ALGORITHMS.join(", ")
+            ));
+        }
+        if !matches!(self.encoding.as_str(), "hex" | "base64") {
+            return Err(format!("Unsupported signature encoding: {}", self.encoding));
+        }
+        if self.header_name.trim().is_empty() {
+            return Err("Missing signature header name".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parts of an outgoing request that can appear in the canonical string
+pub struct SigningInput<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub query: &'a str,
+    pub body: &'a str,
+    pub timestamp: i64,
+}
+
+/// Fill the canonical template; `\n` escapes written literally in the template become newlines
+pub fn canonical_string(template: &str, input: &SigningInput) -> String {
+    template
+        .replace("\\n", "\n")
+        .replace("{method}", input.method)
+        .replace("{path}", input.path)
+        .replace("{query}", input.query)
+        .replace("{timestamp}", &input.timestamp.to_string())
+        .replace("{body}", input.body)
+}
+
+/// Signature header value for a request
+pub fn sign(config: &RequestSigningConfig, input: &SigningInput) -> Result<String, String> {
+    config.validate()?;
+    let message = canonical_string(&config.canonical_template, input);
+    let key = config.secret.as_bytes();
+    let mac = match config.algorithm.as_str() {
+        "hmac-sha512" => {
+            let mut mac = Hmac::<Sha512>::new_from_slice(key).map_err(|e| format!("Invalid signing secret: {}", e))?;
+            mac.update(message.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+        _ => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| format!("Invalid signing secret: {}", e))?;
+            mac.update(message.as_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+    Ok(match config.encoding.as_str() {
+        "base64" => base64::engine::general_purpose::STANDARD.encode(mac),
+        _ => mac.iter().map(|b| format!("{:02x}", b)).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(algorithm: &str, encoding: &str) -> RequestSigningConfig {
+        RequestSigningConfig {
+            algorithm: algorithm.to_string(),
+            secret: "Jefe".to_string(),
+            header_name: "X-Signature".to_string(),
+            canonical_template: "{body}".to_string(),
+            encoding: encoding.to_string(),
+            timestamp_header: None,
+        }
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_vectors() {
+        let input = SigningInput {
+            method: "POST",
+            path: "/",
+            query: "",
+            body: "what do ya want for nothing?",
+            timestamp: 0,
+        };
+        assert_eq!(
+            sign(&config("hmac-sha256", "hex"), &input).unwrap(),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            sign(&config("hmac-sha512", "hex"), &input).unwrap(),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+        assert_eq!(
+            sign(&config("hmac-sha256", "base64"), &input).unwrap(),
+            "W9zBRr9gdU5qBCQmCJV1x1oAPwidJzmDnexYuWTsOEM="
+        );
+        assert!(sign(&config("md5", "hex"), &input).is_err());
+    }
+
+    #[test]
+    fn test_canonical_string_and_redacted_debug() {
+        let input = SigningInput {
+            method: "POST",
+            path: "/orders",
+            query: "page=2",
+            body: "{\"id\":1}",
+            timestamp: 1700000000,
+        };
+        assert_eq!(
+            canonical_string("{method}\\n{path}?{query}\\n{timestamp}\\n{body}", &input),
+            "POST\n/orders?page=2\n1700000000\n{\"id\":1}"
+        );
+
+        let debug = format!("{:?}", config("hmac-sha256", "hex"));
+        assert!(!debug.contains("Jefe"));
+        assert!(debug.contains("***"));
+    }
+
+    #[test]
+    fn test_serialized_config_masks_secret() {
+        let config = config("hmac-sha256", "hex");
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["secret"], MASKED_SECRET);
+        assert_eq!(json["headerName"], "X-Signature");
+
+        let stored: RequestSigningConfig = serde_json::from_str(&config.to_stored_json().unwrap()).unwrap();
+        assert_eq!(stored, config);
+    }
+}
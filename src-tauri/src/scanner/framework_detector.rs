@@ -136,6 +136,36 @@ impl FrameworkDetector {
             });
         }
 
+        // Check for Fastify
+        if deps.get("fastify").is_some() || dev_deps.get("fastify").is_some() {
+            let version = deps
+                .get("fastify")
+                .or_else(|| dev_deps.get("fastify"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
+            return Ok(FrameworkInfo {
+                framework_type: "node".to_string(),
+                framework: "fastify".to_string(),
+                version,
+                patterns: FrameworkPatterns {
+                    routing: vec![
+                        "fastify.get".to_string(),
+                        "fastify.post".to_string(),
+                        "fastify.route".to_string(),
+                    ],
+                    controllers: vec!["**/*.js".to_string(), "**/*.ts".to_string()],
+                    decorators: vec![],
+                    middleware: vec!["fastify.register".to_string(), "addHook".to_string()],
+                },
+                structure: FrameworkStructure {
+                    controllers_path: vec!["src".to_string(), "routes".to_string(), "plugins".to_string()],
+                    routes_path: vec!["src".to_string(), "routes".to_string()],
+                    models_path: vec!["src/models".to_string(), "models".to_string()],
+                },
+            });
+        }
+
         Err("No supported framework found".to_string())
     }
 
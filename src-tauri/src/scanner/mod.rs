@@ -1,24 +1,64 @@
 pub mod framework_detector;
+pub mod openapi;
 pub mod parsers;
+pub mod schema_inference;
+pub mod schema_validation;
 pub mod service_detector;
 pub mod static_scanner;
 pub mod types;
 
 pub use framework_detector::FrameworkDetector;
+pub use schema_inference::infer_schema_from_sample;
 pub use service_detector::ServiceDetector;
 pub use static_scanner::StaticScanner;
 pub use types::*;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Unified scanner entry point
 pub struct UnifiedScanner {
     project_path: PathBuf,
+    max_file_size: u64,
+    workers: usize,
+    dedupe: types::DedupeKeyOptions,
+    category_fn: parsers::CategoryFn,
 }
 
 impl UnifiedScanner {
     pub fn new(project_path: PathBuf) -> Self {
-        Self { project_path }
+        Self {
+            project_path,
+            max_file_size: parsers::DEFAULT_MAX_FILE_SIZE,
+            workers: parsers::default_worker_count(),
+            dedupe: types::DedupeKeyOptions::default(),
+            category_fn: parsers::default_category,
+        }
+    }
+
+    /// Override the size above which source files are skipped (default 4 MB)
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Cap the threads used to read and parse source files (default: the machine's available
+    /// parallelism). Values below 1 are treated as 1.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Choose what identifies duplicate routes (default: method + path)
+    pub fn with_dedupe(mut self, dedupe: types::DedupeKeyOptions) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Replace the heuristic that assigns endpoint categories (default: first meaningful path
+    /// segment, else the controller name)
+    pub fn with_category_fn(mut self, category_fn: parsers::CategoryFn) -> Self {
+        self.category_fn = category_fn;
+        self
     }
 
     pub async fn scan(&self) -> Result<types::ScanResult, String> {
@@ -27,15 +67,116 @@ impl UnifiedScanner {
         let framework_info = detector.detect_framework_info().await?;
 
         // Step 2: Perform static scan
-        let scanner = StaticScanner::new(self.project_path.clone(), framework_info.clone());
-        let endpoints = scanner.scan_endpoints().await?;
+        let scanner = StaticScanner::new(self.project_path.clone(), framework_info.clone())
+            .with_max_file_size(self.max_file_size)
+            .with_workers(self.workers)
+            .with_dedupe(self.dedupe)
+            .with_category_fn(self.category_fn);
+        let (endpoints, warnings) = scanner.scan_endpoints().await?;
 
         // Step 3: Return unified result
         Ok(types::ScanResult {
             framework_info,
             endpoints,
             scan_method: "static".to_string(),
+            warnings,
         })
     }
 }
 
+/// Turn a scan into endpoints ready to store: ids are `{project}-{METHOD}-{path}` (numbered when
+/// a wider dedupe key keeps several routes with the same method and path), services come from
+/// the project layout and categories from the parser, falling back to the first path segment
+pub fn api_endpoints_from_scan(
+    project_id: &str,
+    project_path: &Path,
+    scan_result: types::ScanResult,
+) -> Vec<crate::types::ApiEndpoint> {
+    let service_detector = ServiceDetector::new(
+        project_path.to_path_buf(),
+        Some(scan_result.framework_info.clone()),
+    );
+
+    let mut api_endpoints = Vec::new();
+    let mut used_ids = std::collections::HashSet::new();
+    
+    for scanned_endpoint in scan_result.endpoints {
+        let file_path = PathBuf::from(&scanned_endpoint.file_path);
+        let service = service_detector.detect_service_from_path(&file_path);
+        
+        // Generate ID from project_id, method and path
+        let id = format!("{}-{}-{}", 
+            project_id,
+            scanned_endpoint.method.to_uppercase(),
+            scanned_endpoint.path.replace('/', "-").replace(['{', '}'], "")
+        );
+        // Routes kept apart by a wider dedupe key share method and path; number the extras
+        let mut unique_id = id.clone();
+        let mut suffix = 2;
+        while used_ids.contains(&unique_id) {
+            unique_id = format!("{}-{}", id, suffix);
+            suffix += 1;
+        }
+        used_ids.insert(unique_id.clone());
+        let id = unique_id;
+        
+        // Convert parameters
+        let parameters: Vec<crate::types::ApiParameter> = scanned_endpoint.parameters
+            .into_iter()
+            .map(|p| crate::types::ApiParameter {
+                name: p.name,
+                param_type: p.param_type,
+                required: p.required,
+                description: String::new(),
+                example: p.example,
+                default_value: p.default_value,
+                validation: p.validation,
+            })
+            .collect();
+
+        // Convert responses
+        let responses: Vec<crate::types::ApiResponseDefinition> = scanned_endpoint.responses
+            .into_iter()
+            .map(|r| crate::types::ApiResponseDefinition {
+                status_code: r.status_code,
+                description: r.description,
+                content_type: r.content_type,
+                schema: r.schema.map(|s| serde_json::to_value(&s).unwrap_or_default()),
+                example: r.example,
+            })
+            .collect();
+
+        // Keep the parser's category, else generate one from the path
+        let category = scanned_endpoint.category.clone().unwrap_or_else(|| {
+            scanned_endpoint.path
+                .split('/')
+                .find(|s| !s.is_empty())
+                .unwrap_or("api")
+                .to_string()
+        });
+
+        let api_endpoint = crate::types::ApiEndpoint {
+            id,
+            project_id: Some(project_id.to_string()),
+            name: format!("{} {}", scanned_endpoint.method, scanned_endpoint.path),
+            method: scanned_endpoint.method,
+            path: scanned_endpoint.path,
+            service,
+            description: scanned_endpoint.business_logic.description,
+            parameters,
+            category,
+            explanation: Some(scanned_endpoint.business_logic.summary),
+            responses: Some(responses),
+            version: scanned_endpoint.version,
+            user_modified: false,
+            stale: false,
+            auth_required: scanned_endpoint.authentication.required,
+            auth_type: scanned_endpoint.authentication.auth_type,
+            warnings: scanned_endpoint.warnings,
+        };
+
+        api_endpoints.push(api_endpoint);
+    }
+
+    api_endpoints
+}
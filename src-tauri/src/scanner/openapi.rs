@@ -0,0 +1,507 @@
+//! Import of OpenAPI 3 / Swagger 2 documents as endpoints
+//!
+//! Parameter and request body examples come from the spec when it has them: the singular
+//! `example`, otherwise the first entry of `examples`, otherwise the schema's `example` or
+//! `default`. `ExampleGenerator` only fills in values the spec doesn't provide. The document
+//! is read through `serde_yaml` (JSON is valid YAML) so `examples` keep their document order.
+
+use serde_yaml::Value as Yaml;
+
+use super::parsers::{version_from_path, ExampleGenerator};
+use crate::types::{ApiEndpoint, ApiParameter, ApiResponseDefinition};
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "head", "options"];
+
+/// `$ref` chains longer than this are treated as unresolvable
+const MAX_REF_DEPTH: usize = 16;
+
+/// Build endpoints for every operation of an OpenAPI 3 or Swagger 2 document
+pub fn endpoints_from_openapi(content: &str, project_id: &str) -> Result<Vec<ApiEndpoint>, String> {
+    let doc: Yaml = serde_yaml::from_str(content)
+        .map_err(|e| format!("Failed to parse OpenAPI document: {}", e))?;
+    if doc.get("openapi").is_none() && doc.get("swagger").is_none() {
+        return Err("Not an OpenAPI document: missing 'openapi' or 'swagger' version".to_string());
+    }
+    let paths = match doc.get("paths").and_then(Yaml::as_mapping) {
+        Some(paths) => paths,
+        None => return Err("OpenAPI document has no paths".to_string()),
+    };
+
+    // Swagger 2 paths are relative to basePath
+    let base_path = doc.get("basePath")
+        .and_then(Yaml::as_str)
+        .map(|p| p.trim_end_matches('/'))
+        .unwrap_or("");
+    let service = doc.get("info")
+        .and_then(|info| info.get("title"))
+        .and_then(Yaml::as_str)
+        .unwrap_or("openapi")
+        .to_string();
+
+    let mut endpoints = Vec::new();
+    for (path_key, path_item) in paths {
+        let path = match path_key.as_str() {
+            Some(path) => format!("{}{}", base_path, path),
+            None => continue,
+        };
+        let path_item = resolve(&doc, path_item);
+        let shared_params = path_item.get("parameters");
+
+        for method in HTTP_METHODS {
+            let operation = match path_item.get(*method) {
+                Some(operation) => resolve(&doc, operation),
+                None => continue,
+            };
+            endpoints.push(endpoint_for_operation(&doc, project_id, &service, &path, method, operation, shared_params));
+        }
+    }
+
+    Ok(endpoints)
+}
+
+fn endpoint_for_operation(
+    doc: &Yaml,
+    project_id: &str,
+    service: &str,
+    path: &str,
+    method: &str,
+    operation: &Yaml,
+    shared_params: Option<&Yaml>,
+) -> ApiEndpoint {
+    let method = method.to_uppercase();
+    let mut parameters = Vec::new();
+
+    // Operation parameters override path-level ones with the same name and location
+    let mut declared: Vec<&Yaml> = Vec::new();
+    for list in [shared_params, operation.get("parameters")].into_iter().flatten() {
+        for param in list.as_sequence().into_iter().flatten() {
+            let param = resolve(doc, param);
+            let key = (param.get("name"), param.get("in"));
+            declared.retain(|p| (p.get("name"), p.get("in")) != key);
+            declared.push(param);
+        }
+    }
+
+    for param in declared {
+        let location = param.get("in").and_then(Yaml::as_str).unwrap_or("query");
+        if location == "body" {
+            // Swagger 2 request body
+            if let Some(schema) = param.get("schema") {
+                let example = first_example(doc, param)
+                    .or_else(|| param.get("x-example").and_then(to_json))
+                    .or_else(|| schema_example(doc, schema));
+                parameters.extend(body_parameters(doc, schema, example));
+            }
+            continue;
+        }
+        parameters.push(parameter(doc, param, location));
+    }
+
+    // OpenAPI 3 request body, JSON content preferred
+    if let Some(request_body) = operation.get("requestBody").map(|b| resolve(doc, b)) {
+        if let Some((_, media)) = preferred_media(request_body) {
+            let schema = media.get("schema").map(|s| resolve(doc, s));
+            let example = first_example(doc, media).or_else(|| schema.and_then(|s| schema_example(doc, s)));
+            match schema {
+                Some(schema) => parameters.extend(body_parameters(doc, schema, example)),
+                None => parameters.push(ApiParameter {
+                    name: "body".to_string(),
+                    param_type: "object".to_string(),
+                    required: true,
+                    description: "Request body".to_string(),
+                    example,
+                    default_value: None,
+                    validation: None,
+                }),
+            }
+        }
+    }
+
+    let summary = operation.get("summary").and_then(Yaml::as_str);
+    let description = operation.get("description").and_then(Yaml::as_str);
+    let category = operation.get("tags")
+        .and_then(|tags| tags.get(0))
+        .and_then(Yaml::as_str)
+        .map(|tag| tag.to_string())
+        .unwrap_or_else(|| path.split('/').find(|s| !s.is_empty()).unwrap_or("api").to_string());
+
+    ApiEndpoint {
+        id: format!("{}-{}-{}", project_id, method, path.replace('/', "-").replace(['{', '}'], "")),
+        project_id: Some(project_id.to_string()),
+        name: format!("{} {}", method, path),
+        method,
+        path: path.to_string(),
+        service: service.to_string(),
+        description: summary.or(description).unwrap_or_default().to_string(),
+        parameters,
+        category,
+        explanation: summary.and(description).map(|d| d.to_string()),
+        responses: Some(responses(doc, operation)),
+        version: version_from_path(path),
+        user_modified: false,
+        stale: false,
+        auth_required: false,
+        auth_type: None,
+        warnings: Vec::new(),
+    }
+}
+
+/// A path, query, header or form parameter
+fn parameter(doc: &Yaml, param: &Yaml, location: &str) -> ApiParameter {
+    let name = param.get("name").and_then(Yaml::as_str).unwrap_or_default().to_string();
+    // OpenAPI 3 nests the type in `schema`; Swagger 2 puts it on the parameter itself
+    let schema = param.get("schema").map(|s| resolve(doc, s)).unwrap_or(param);
+    let param_type = schema_type(doc, schema);
+    let validation = schema_rules(schema);
+    let example = first_example(doc, param)
+        .or_else(|| param.get("x-example").and_then(to_json))
+        .or_else(|| schema_example(doc, schema))
+        .or_else(|| ExampleGenerator::generate_example(&param_type, &name, &validation));
+
+    ApiParameter {
+        description: param.get("description")
+            .and_then(Yaml::as_str)
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| format!("{} parameter", capitalize(location))),
+        required: param.get("required").and_then(Yaml::as_bool).unwrap_or(location == "path"),
+        default_value: schema.get("default").and_then(to_json),
+        name,
+        param_type,
+        example,
+        validation,
+    }
+}
+
+/// Body fields of an object schema, with examples taken from the body-level example first
+fn body_parameters(doc: &Yaml, schema: &Yaml, body_example: Option<serde_json::Value>) -> Vec<ApiParameter> {
+    let schema = resolve(doc, schema);
+    let properties = match schema.get("properties").and_then(Yaml::as_mapping) {
+        Some(properties) => properties,
+        None => {
+            return vec![ApiParameter {
+                name: "body".to_string(),
+                param_type: schema_type(doc, schema),
+                required: true,
+                description: "Request body".to_string(),
+                example: body_example,
+                default_value: None,
+                validation: None,
+            }];
+        }
+    };
+    let required: Vec<&str> = schema.get("required")
+        .and_then(Yaml::as_sequence)
+        .map(|names| names.iter().filter_map(Yaml::as_str).collect())
+        .unwrap_or_default();
+
+    properties
+        .iter()
+        .filter_map(|(name, property)| {
+            let name = name.as_str()?;
+            let property = resolve(doc, property);
+            let param_type = schema_type(doc, property);
+            let validation = schema_rules(property);
+            let example = body_example.as_ref()
+                .and_then(|body| body.get(name))
+                .cloned()
+                .or_else(|| schema_example(doc, property))
+                .or_else(|| ExampleGenerator::generate_example(&param_type, name, &validation));
+            Some(ApiParameter {
+                name: name.to_string(),
+                param_type,
+                required: required.contains(&name),
+                description: property.get("description")
+                    .and_then(Yaml::as_str)
+                    .unwrap_or("Body field")
+                    .to_string(),
+                example,
+                default_value: property.get("default").and_then(to_json),
+                validation,
+            })
+        })
+        .collect()
+}
+
+fn responses(doc: &Yaml, operation: &Yaml) -> Vec<ApiResponseDefinition> {
+    let responses = match operation.get("responses").and_then(Yaml::as_mapping) {
+        Some(responses) => responses,
+        None => return Vec::new(),
+    };
+
+    responses
+        .iter()
+        .filter_map(|(code, response)| {
+            // Keys may be numbers (200) or strings ("200"); "default" and "2XX" are skipped
+            let status_code = match code {
+                Yaml::Number(n) => n.as_u64().and_then(|n| u16::try_from(n).ok()),
+                Yaml::String(s) => s.parse().ok(),
+                _ => None,
+            }?;
+            let response = resolve(doc, response);
+            let (content_type, example) = match preferred_media(response) {
+                Some((content_type, media)) => {
+                    let schema = media.get("schema").map(|s| resolve(doc, s));
+                    let example = first_example(doc, media).or_else(|| schema.and_then(|s| schema_example(doc, s)));
+                    (content_type, example)
+                }
+                // Swagger 2: `examples` keyed by MIME type, schema on the response
+                None => (
+                    "application/json".to_string(),
+                    first_example(doc, response)
+                        .or_else(|| response.get("schema").and_then(|s| schema_example(doc, s))),
+                ),
+            };
+            Some(ApiResponseDefinition {
+                status_code,
+                description: response.get("description").and_then(Yaml::as_str).unwrap_or_default().to_string(),
+                content_type,
+                schema: None,
+                example,
+            })
+        })
+        .collect()
+}
+
+/// The `application/json` entry of a `content` map, else its first entry
+fn preferred_media(node: &Yaml) -> Option<(String, &Yaml)> {
+    let content = node.get("content")?.as_mapping()?;
+    content
+        .iter()
+        .find(|(key, _)| key.as_str().is_some_and(|k| k.contains("json")))
+        .or_else(|| content.iter().next())
+        .and_then(|(key, media)| Some((key.as_str()?.to_string(), media)))
+}
+
+/// The singular `example`, else the first entry of `examples` (an Example object's `value`)
+fn first_example(doc: &Yaml, node: &Yaml) -> Option<serde_json::Value> {
+    if let Some(example) = node.get("example") {
+        return to_json(example);
+    }
+    let first = match node.get("examples")? {
+        Yaml::Mapping(examples) => examples.values().next()?,
+        Yaml::Sequence(examples) => examples.first()?,
+        _ => return None,
+    };
+    let first = resolve(doc, first);
+    match first.get("value") {
+        Some(value) => to_json(value),
+        // Swagger 2 response examples hold the raw value
+        None if first.get("externalValue").is_none() => to_json(first),
+        None => None,
+    }
+}
+
+/// Example declared on a schema (or its default), including object examples built from
+/// property examples
+fn schema_example(doc: &Yaml, schema: &Yaml) -> Option<serde_json::Value> {
+    let schema = resolve(doc, schema);
+    if let Some(example) = first_example(doc, schema).or_else(|| schema.get("default").and_then(to_json)) {
+        return Some(example);
+    }
+    let properties = schema.get("properties")?.as_mapping()?;
+    let object: serde_json::Map<String, serde_json::Value> = properties
+        .iter()
+        .filter_map(|(name, property)| Some((name.as_str()?.to_string(), schema_example(doc, property)?)))
+        .collect();
+    if object.is_empty() {
+        None
+    } else {
+        Some(serde_json::Value::Object(object))
+    }
+}
+
+fn schema_type(doc: &Yaml, schema: &Yaml) -> String {
+    let schema = resolve(doc, schema);
+    let declared = match schema.get("type") {
+        Some(Yaml::String(t)) => Some(t.as_str()),
+        // OpenAPI 3.1 type arrays, e.g. ["string", "null"]
+        Some(Yaml::Sequence(types)) => types.iter().filter_map(Yaml::as_str).find(|t| *t != "null"),
+        _ => None,
+    };
+    match declared {
+        Some(t) => t.to_string(),
+        None if schema.get("properties").is_some() => "object".to_string(),
+        None if schema.get("items").is_some() => "array".to_string(),
+        None => "string".to_string(),
+    }
+}
+
+/// Validation rules in the scanners' format: "min:N", "max:N", "email", "uuid", ...
+fn schema_rules(schema: &Yaml) -> Option<Vec<String>> {
+    let mut rules = Vec::new();
+    for (key, prefix) in [("minLength", "min:"), ("minimum", "min:"), ("maxLength", "max:"), ("maximum", "max:")] {
+        if let Some(n) = schema.get(key).and_then(|v| v.as_f64()) {
+            rules.push(format!("{}{}", prefix, n));
+        }
+    }
+    match schema.get("format").and_then(Yaml::as_str) {
+        Some("email") => rules.push("email".to_string()),
+        Some("uuid") => rules.push("uuid".to_string()),
+        Some("date") | Some("date-time") => rules.push("date".to_string()),
+        Some("uri") | Some("url") => rules.push("url".to_string()),
+        _ => {}
+    }
+    if let Some(values) = schema.get("enum").and_then(Yaml::as_sequence) {
+        let values: Vec<String> = values.iter()
+            .filter_map(|v| to_json(v).map(|j| j.as_str().map(|s| s.to_string()).unwrap_or_else(|| j.to_string())))
+            .collect();
+        rules.push(format!("in:{}", values.join(",")));
+    }
+    if rules.is_empty() {
+        None
+    } else {
+        Some(rules)
+    }
+}
+
+/// Follow `$ref` pointers within the document
+fn resolve<'a>(doc: &'a Yaml, node: &'a Yaml) -> &'a Yaml {
+    let mut node = node;
+    for _ in 0..MAX_REF_DEPTH {
+        match node.get("$ref").and_then(Yaml::as_str).and_then(|r| pointer(doc, r)) {
+            Some(target) => node = target,
+            None => break,
+        }
+    }
+    node
+}
+
+fn pointer<'a>(doc: &'a Yaml, reference: &str) -> Option<&'a Yaml> {
+    let path = reference.strip_prefix("#/")?;
+    path.split('/').try_fold(doc, |node, segment| {
+        node.get(segment.replace("~1", "/").replace("~0", "~").as_str())
+    })
+}
+
+fn to_json(value: &Yaml) -> Option<serde_json::Value> {
+    serde_json::to_value(value).ok()
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+info:
+  title: Shop API
+paths:
+  /v1/users/{id}:
+    parameters:
+      - name: id
+        in: path
+        schema: { type: integer }
+        example: 42
+    get:
+      tags: [users]
+      summary: Get a user
+      parameters:
+        - name: status
+          in: query
+          schema: { type: string }
+          examples:
+            zeta: { value: archived }
+            alpha: { value: active }
+        - name: limit
+          in: query
+          schema: { type: integer, minimum: 1, maximum: 100 }
+      responses:
+        "200":
+          description: The user
+          content:
+            application/json:
+              example: { id: 42, email: jane@example.com }
+        default:
+          description: Error
+  /v1/users:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/NewUser' }
+            examples:
+              basic:
+                value: { name: Jane, role: admin }
+      responses:
+        201: { description: Created }
+components:
+  schemas:
+    NewUser:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string }
+        role: { type: string }
+        email: { type: string, format: email }
+        age: { type: integer, example: 30 }
+"#;
+
+    fn param<'a>(endpoint: &'a ApiEndpoint, name: &str) -> &'a ApiParameter {
+        endpoint.parameters.iter().find(|p| p.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_openapi_examples_are_captured() {
+        let endpoints = endpoints_from_openapi(SPEC, "p1").unwrap();
+        assert_eq!(endpoints.len(), 2);
+
+        let get = &endpoints[0];
+        assert_eq!(get.name, "GET /v1/users/{id}");
+        assert_eq!(get.category, "users");
+        assert_eq!(get.version.as_deref(), Some("v1"));
+        assert_eq!(param(get, "id").example, Some(json!(42)));
+        assert!(param(get, "id").required);
+        // First entry of `examples` in document order
+        assert_eq!(param(get, "status").example, Some(json!("archived")));
+        // No spec example: falls back to the generator
+        assert_eq!(param(get, "limit").example, Some(json!(1)));
+        assert_eq!(param(get, "limit").validation, Some(vec!["min:1".to_string(), "max:100".to_string()]));
+
+        let responses = get.responses.as_ref().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].example, Some(json!({ "id": 42, "email": "jane@example.com" })));
+
+        let post = &endpoints[1];
+        assert_eq!(param(post, "name").example, Some(json!("Jane")));
+        assert!(param(post, "name").required);
+        assert_eq!(param(post, "role").example, Some(json!("admin")));
+        assert_eq!(param(post, "age").example, Some(json!(30)));
+        assert_eq!(param(post, "email").example, Some(json!("user@example.com")));
+        assert_eq!(post.responses.as_ref().unwrap()[0].status_code, 201);
+    }
+
+    #[test]
+    fn test_swagger2_body_parameter() {
+        let spec = r#"{
+            "swagger": "2.0",
+            "basePath": "/api",
+            "paths": {
+                "/orders": {
+                    "post": {
+                        "parameters": [{
+                            "name": "order", "in": "body",
+                            "schema": { "type": "object", "properties": { "sku": { "type": "string" } } },
+                            "x-example": { "sku": "A-1" }
+                        }],
+                        "responses": { "200": { "description": "ok", "examples": { "application/json": { "id": 7 } } } }
+                    }
+                }
+            }
+        }"#;
+        let endpoints = endpoints_from_openapi(spec, "p1").unwrap();
+        assert_eq!(endpoints[0].path, "/api/orders");
+        assert_eq!(param(&endpoints[0], "sku").example, Some(json!("A-1")));
+        assert_eq!(endpoints[0].responses.as_ref().unwrap()[0].example, Some(json!({ "id": 7 })));
+
+        assert!(endpoints_from_openapi("name: not a spec", "p1").is_err());
+    }
+}
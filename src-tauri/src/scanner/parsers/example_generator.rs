@@ -13,6 +13,19 @@ impl ExampleGenerator {
         
         // Check for specific validation rules first
         if !rules.is_empty() {
+            // An enumerated field only accepts its listed values; use the first
+            if let Some(value) = Self::extract_in_values(rules).and_then(|values| values.into_iter().next()) {
+                return match param_type {
+                    "number" | "integer" => value
+                        .parse::<i64>()
+                        .map(|n| Value::Number(n.into()))
+                        .or_else(|_| value.parse::<f64>().map(|n| serde_json::json!(n)))
+                        .ok()
+                        .or(Some(Value::String(value))),
+                    _ => Some(Value::String(value)),
+                };
+            }
+
             if Self::has_rule(rules, "email") {
                 return Some(Value::String("user@example.com".to_string()));
             }
@@ -109,6 +122,20 @@ impl ExampleGenerator {
         })
     }
     
+    /// Extract allowed values from validation rules (e.g., "in:draft,published" -> ["draft", "published"])
+    fn extract_in_values(rules: &[String]) -> Option<Vec<String>> {
+        rules.iter().find_map(|rule| {
+            let values: Vec<String> = rule
+                .trim()
+                .strip_prefix("in:")?
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect();
+            (!values.is_empty()).then_some(values)
+        })
+    }
+
     /// Extract min value from validation rules (e.g., "min:5" -> 5)
     fn extract_min(rules: &[String]) -> Option<usize> {
         for rule in rules {
@@ -160,6 +187,16 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_generate_enum_example() {
+        let rules = Some(vec!["enum".to_string(), "in:pending,paid".to_string()]);
+        let example = ExampleGenerator::generate_example("string", "status", &rules);
+        assert_eq!(example, Some(Value::String("pending".to_string())));
+
+        let rules = Some(vec!["in:3,5".to_string()]);
+        assert_eq!(ExampleGenerator::generate_example("number", "priority", &rules), Some(Value::from(3)));
+    }
+
     #[test]
     fn test_generate_boolean_example() {
         let example = ExampleGenerator::generate_example("boolean", "active", &None);
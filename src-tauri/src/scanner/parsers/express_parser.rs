@@ -0,0 +1,954 @@
+//! Express and Fastify route discovery
+//!
+//! Finds `app.get('/path', handler)`-style calls on apps and routers, Express
+//! `router.route('/path').get(...)` chains and Fastify `fastify.route({ method, url })` objects,
+//! then resolves `app.use('/prefix', router)` and Fastify `register(plugin, { prefix })` mounts
+//! into full paths, following `require`/`import` of relative files. Parameters come from the
+//! path and from `req.params`/`req.query`/`req.body` usage in the handler: the inline function,
+//! or a function of the handler's name defined in the same file.
+
+use crate::scanner::parsers::example_generator::ExampleGenerator;
+use crate::scanner::parsers::query_smells::{block_after, matching_close};
+use crate::scanner::parsers::{
+    deduplicate_endpoints, default_category, default_worker_count, read_source_files, version_from_path, CategoryFn,
+    DEFAULT_MAX_FILE_SIZE,
+};
+use crate::scanner::types::{
+    Authentication, Authorization, BusinessLogic, DedupeKeyOptions, EndpointParameter, ScannedEndpoint,
+};
+use glob::glob;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// Directories never scanned for routes
+const EXCLUDED_DIRS: &[&str] = &["node_modules", "dist", "build", "coverage", ".git", ".next"];
+
+const SOURCE_EXTENSIONS: &[&str] = &["js", "ts", "mjs", "cjs"];
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+/// Receivers treated as an app or router even without a visible `express()`/`Router()` call
+const ROUTER_NAMES: &[&str] = &["app", "router", "server", "fastify", "instance", "api"];
+
+/// Nested mounts followed before giving up, in case of a cycle
+const MAX_MOUNT_DEPTH: usize = 8;
+
+/// What a `use`/`register` call mounts: a router of this file, or one exported by another file
+/// (its default export, or the named one)
+#[derive(Debug, Clone, PartialEq)]
+enum MountTarget {
+    Local(String),
+    Import(PathBuf, Option<String>),
+}
+
+#[derive(Debug, Clone)]
+struct Mount {
+    parent: String,
+    prefix: String,
+    target: MountTarget,
+}
+
+#[derive(Debug, Clone)]
+struct RawRoute {
+    router: String,
+    method: String,
+    path: String,
+    offset: usize,
+    line: u32,
+    action: String,
+    handler: String, // Handler source searched for request parameters
+}
+
+/// Routes and mounts of one file. Routers are named by variable, or by function name for a
+/// Fastify plugin / `module.exports = function (app) {...}` (anonymous default exports are "default").
+#[derive(Debug, Default)]
+struct FileRoutes {
+    routes: Vec<RawRoute>,
+    mounts: Vec<Mount>,
+    default_export: Option<String>,
+}
+
+struct FunctionDef {
+    name: String,
+    param: String,
+    start: usize,
+    end: usize,
+}
+
+/// A router within the scanned files: (file index, name)
+type Node = (usize, String);
+
+pub struct ExpressParser {
+    project_path: PathBuf,
+    max_file_size: u64,
+    workers: usize,
+    dedupe: DedupeKeyOptions,
+    category_fn: CategoryFn,
+    warnings: Vec<String>,
+}
+
+impl ExpressParser {
+    pub fn new(project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            workers: default_worker_count(),
+            dedupe: DedupeKeyOptions::default(),
+            category_fn: default_category,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Override the size above which source files are skipped
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Cap the threads used to read source files (default: available parallelism)
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Choose what identifies duplicate routes (default: method + path)
+    pub fn with_dedupe(mut self, dedupe: DedupeKeyOptions) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Replace the heuristic that assigns endpoint categories (default: `default_category`)
+    pub fn with_category_fn(mut self, category_fn: CategoryFn) -> Self {
+        self.category_fn = category_fn;
+        self
+    }
+
+    /// Warnings collected during the last parse, e.g. skipped oversized files
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub async fn parse_endpoints(&mut self) -> Result<Vec<ScannedEndpoint>, String> {
+        self.warnings.clear();
+
+        let paths = self.source_paths();
+        let files: Vec<(PathBuf, FileRoutes)> =
+            read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings)
+                .into_iter()
+                .map(|(path, content)| {
+                    let routes = parse_file(&content, &path);
+                    (normalize_path(&path), routes)
+                })
+                .collect();
+
+        Ok(deduplicate_endpoints(self.build_endpoints(&files), self.dedupe))
+    }
+
+    /// JavaScript/TypeScript sources outside dependency and build folders, sorted by path
+    fn source_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = SOURCE_EXTENSIONS
+            .iter()
+            .filter_map(|ext| glob(&format!("{}/**/*.{}", self.project_path.to_string_lossy(), ext)).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|path| {
+                let relative = path.strip_prefix(&self.project_path).unwrap_or(path);
+                let excluded = relative
+                    .components()
+                    .any(|c| EXCLUDED_DIRS.contains(&c.as_os_str().to_string_lossy().as_ref()));
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                !excluded && !name.ends_with(".d.ts") && !name.contains(".test.") && !name.contains(".spec.")
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// Resolve mounts into prefixes and emit one endpoint per route and prefix
+    fn build_endpoints(&self, files: &[(PathBuf, FileRoutes)]) -> Vec<ScannedEndpoint> {
+        let index: HashMap<&Path, usize> = files.iter().enumerate().map(|(i, (path, _))| (path.as_path(), i)).collect();
+
+        let mut edges: HashMap<Node, Vec<(String, Node)>> = HashMap::new();
+        let mut children: HashSet<Node> = HashSet::new();
+        let mut nodes: Vec<Node> = Vec::new();
+        for (file_index, (_, file)) in files.iter().enumerate() {
+            let parents = file.routes.iter().map(|r| &r.router).chain(file.mounts.iter().map(|m| &m.parent));
+            for parent in parents {
+                let node = (file_index, parent.clone());
+                if !nodes.contains(&node) {
+                    nodes.push(node);
+                }
+            }
+            for mount in &file.mounts {
+                let targets = match &mount.target {
+                    MountTarget::Local(name) => vec![(file_index, name.clone())],
+                    MountTarget::Import(base, export) => resolve_import(files, &index, base, export.as_deref()),
+                };
+                for target in targets {
+                    children.insert(target.clone());
+                    edges
+                        .entry((file_index, mount.parent.clone()))
+                        .or_default()
+                        .push((mount.prefix.clone(), target));
+                }
+            }
+        }
+
+        let mut prefixes: HashMap<Node, Vec<String>> = HashMap::new();
+        for root in nodes.iter().filter(|node| !children.contains(*node)) {
+            collect_prefixes(root, String::new(), &edges, &mut prefixes, 0);
+        }
+
+        let mut endpoints = Vec::new();
+        for (file_index, (path, file)) in files.iter().enumerate() {
+            let controller = controller_name(path);
+            for route in &file.routes {
+                let node = (file_index, route.router.clone());
+                let route_prefixes = prefixes.get(&node).cloned().unwrap_or_else(|| vec![String::new()]);
+                for prefix in route_prefixes {
+                    endpoints.push(self.create_endpoint(&join_paths(&prefix, &route.path), route, &controller, path));
+                }
+            }
+        }
+        endpoints
+    }
+
+    fn create_endpoint(&self, full_path: &str, route: &RawRoute, controller: &str, file_path: &Path) -> ScannedEndpoint {
+        let mut parameters = path_parameters(full_path);
+        for (source, name) in request_parameters(&route.handler) {
+            if !parameters.iter().any(|p| p.source == source && p.name == name) {
+                parameters.push(parameter(&name, source, source == "path"));
+            }
+        }
+
+        ScannedEndpoint {
+            path: full_path.to_string(),
+            method: route.method.clone(),
+            controller: controller.to_string(),
+            action: route.action.clone(),
+            file_path: file_path.to_string_lossy().to_string(),
+            line_number: route.line,
+            parameters,
+            business_logic: BusinessLogic {
+                summary: format!("{} {}", route.method, full_path),
+                description: format!("{}@{}", controller, route.action),
+                purpose: String::new(),
+                dependencies: Vec::new(),
+            },
+            authentication: Authentication::default(),
+            authorization: Authorization::default(),
+            responses: Vec::new(),
+            version: version_from_path(full_path),
+            category: (self.category_fn)(full_path, controller),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Record `prefix` for `node` and push it down through the node's mounts
+fn collect_prefixes(
+    node: &Node,
+    prefix: String,
+    edges: &HashMap<Node, Vec<(String, Node)>>,
+    prefixes: &mut HashMap<Node, Vec<String>>,
+    depth: usize,
+) {
+    let known = prefixes.entry(node.clone()).or_default();
+    if known.contains(&prefix) {
+        return;
+    }
+    known.push(prefix.clone());
+    if depth >= MAX_MOUNT_DEPTH {
+        return;
+    }
+    for (mount_prefix, child) in edges.get(node).into_iter().flatten() {
+        collect_prefixes(child, join_paths(&prefix, mount_prefix), edges, prefixes, depth + 1);
+    }
+}
+
+/// Routers an import of `base` refers to: the named export, else the file's default export,
+/// else every router of the file that isn't mounted inside it
+fn resolve_import(
+    files: &[(PathBuf, FileRoutes)],
+    index: &HashMap<&Path, usize>,
+    base: &Path,
+    export: Option<&str>,
+) -> Vec<Node> {
+    let mut candidates = vec![base.to_path_buf(), base.with_extension("ts")];
+    for ext in SOURCE_EXTENSIONS {
+        candidates.push(PathBuf::from(format!("{}.{}", base.to_string_lossy(), ext)));
+        candidates.push(base.join(format!("index.{}", ext)));
+    }
+    let file_index = match candidates.iter().find_map(|c| index.get(c.as_path()).copied()) {
+        Some(file_index) => file_index,
+        None => return Vec::new(),
+    };
+
+    let file = &files[file_index].1;
+    match export.map(str::to_string).or_else(|| file.default_export.clone()) {
+        Some(name) => vec![(file_index, name)],
+        None => {
+            let mounted: Vec<&String> = file
+                .mounts
+                .iter()
+                .filter_map(|m| match &m.target {
+                    MountTarget::Local(name) => Some(name),
+                    MountTarget::Import(..) => None,
+                })
+                .collect();
+            let mut roots: Vec<Node> = Vec::new();
+            for name in file.routes.iter().map(|r| &r.router).chain(file.mounts.iter().map(|m| &m.parent)) {
+                let node = (file_index, name.clone());
+                if !mounted.contains(&name) && !roots.contains(&node) {
+                    roots.push(node);
+                }
+            }
+            roots
+        }
+    }
+}
+
+/// Routes, mounts and the default export of one source file
+fn parse_file(content: &str, file_path: &Path) -> FileRoutes {
+    let dir = file_path.parent().unwrap_or(Path::new(""));
+    let functions = function_defs(content);
+    let declared = declared_routers(content);
+    let imports = import_targets(content, dir);
+
+    let is_router = |name: &str| {
+        declared.contains(name)
+            || ROUTER_NAMES.contains(&name)
+            || ["Router", "router", "Routes", "routes"].iter().any(|suffix| name.ends_with(suffix))
+    };
+    // The router a call on `receiver` at `offset` belongs to: the enclosing function when the
+    // receiver is its first parameter (a Fastify plugin), else the receiver itself
+    let owner = |receiver: &str, offset: usize| -> Option<String> {
+        let scope = functions
+            .iter()
+            .filter(|f| f.start <= offset && offset < f.end && f.param == receiver)
+            .min_by_key(|f| f.end - f.start);
+        match scope {
+            Some(function) => Some(function.name.clone()),
+            None if is_router(receiver) => Some(receiver.to_string()),
+            None => None,
+        }
+    };
+    // (action, source searched for parameters) for a handler argument
+    let handler_source = |handler: &str, inline: &str| -> (String, String) {
+        let handler = handler.trim();
+        if !handler.is_empty() && handler.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.') {
+            let name = handler.rsplit('.').next().unwrap_or(handler);
+            let body = functions
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| &content[f.start..f.end])
+                .unwrap_or("");
+            (name.to_string(), format!("{}\n{}", inline, body))
+        } else {
+            ("handler".to_string(), inline.to_string())
+        }
+    };
+
+    let mut file = FileRoutes {
+        default_export: default_export(content, &functions),
+        ..Default::default()
+    };
+    let mut push_route = |router: String, method: &str, path: String, offset: usize, handler: (String, String)| {
+        file.routes.push(RawRoute {
+            router,
+            method: method.to_uppercase(),
+            path,
+            offset,
+            line: content[..offset].matches('\n').count() as u32 + 1,
+            action: handler.0,
+            handler: handler.1,
+        });
+    };
+
+    // app.get('/path', ...handlers)
+    if let Ok(route_re) = Regex::new(r"\b(\w+)\s*\.\s*(get|post|put|patch|delete)\s*\(") {
+        for cap in route_re.captures_iter(content) {
+            let whole = cap.get(0).expect("match");
+            let router = match owner(&cap[1], whole.start()) {
+                Some(router) => router,
+                None => continue,
+            };
+            let open = whole.end() - 1;
+            let close = match matching_close(content, open, '(', ')') {
+                Some(close) => close,
+                None => continue,
+            };
+            let args = split_args(&content[open + 1..close]);
+            let path = match args.first().and_then(|(_, arg)| string_literal(arg)) {
+                Some(path) if path.starts_with('/') && args.len() >= 2 => path,
+                _ => continue,
+            };
+            let inline = &content[open + 1 + args[1].0..close];
+            let handler = handler_source(args[args.len() - 1].1, inline);
+            push_route(router, &cap[2], path, whole.start(), handler);
+        }
+    }
+
+    // router.route('/path').get(...).post(...)
+    if let Ok(chain_re) = Regex::new(r#"\b(\w+)\s*\.\s*route\s*\(\s*(?:'([^']*)'|"([^"]*)"|`([^`]*)`)\s*\)"#) {
+        for cap in chain_re.captures_iter(content) {
+            let whole = cap.get(0).expect("match");
+            let router = match owner(&cap[1], whole.start()) {
+                Some(router) => router,
+                None => continue,
+            };
+            let path = cap.get(2).or_else(|| cap.get(3)).or_else(|| cap.get(4)).map(|m| m.as_str()).unwrap_or("");
+            let mut pos = whole.end();
+            while let Some(after_dot) = content[pos..].trim_start().strip_prefix('.') {
+                let after_dot = after_dot.trim_start();
+                let name_len = after_dot.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(after_dot.len());
+                let method = &after_dot[..name_len];
+                let after_name = after_dot[name_len..].trim_start();
+                if !after_name.starts_with('(') {
+                    break;
+                }
+                let open = content.len() - after_name.len();
+                let close = match matching_close(content, open, '(', ')') {
+                    Some(close) => close,
+                    None => break,
+                };
+                if HTTP_METHODS.contains(&method) {
+                    let inner = &content[open + 1..close];
+                    let handler = split_args(inner).last().map(|(_, arg)| *arg).unwrap_or("");
+                    push_route(router.clone(), method, path.to_string(), whole.start(), handler_source(handler, inner));
+                }
+                pos = close + 1;
+            }
+        }
+    }
+
+    // fastify.route({ method: 'GET', url: '/path', handler })
+    if let (Ok(object_re), Ok(method_re), Ok(url_re), Ok(handler_re)) = (
+        Regex::new(r"\b(\w+)\s*\.\s*route\s*\(\s*\{"),
+        Regex::new(r#"\bmethod\s*:\s*(?:'(\w+)'|"(\w+)"|\[([^\]]*)\])"#),
+        Regex::new(r#"\b(?:url|path)\s*:\s*(?:'([^']*)'|"([^"]*)"|`([^`]*)`)"#),
+        Regex::new(r"\bhandler\s*:\s*([\w$.]+)\s*[,}\n]"),
+    ) {
+        for cap in object_re.captures_iter(content) {
+            let whole = cap.get(0).expect("match");
+            let router = match owner(&cap[1], whole.start()) {
+                Some(router) => router,
+                None => continue,
+            };
+            let open = whole.end() - 1;
+            let object = match matching_close(content, open, '{', '}') {
+                Some(close) => &content[open + 1..close],
+                None => continue,
+            };
+            let path = match url_re.captures(object) {
+                Some(url) => url.get(1).or_else(|| url.get(2)).or_else(|| url.get(3)).map(|m| m.as_str().to_string()).unwrap_or_default(),
+                None => continue,
+            };
+            let methods: Vec<String> = match method_re.captures(object) {
+                Some(m) => match m.get(1).or_else(|| m.get(2)) {
+                    Some(single) => vec![single.as_str().to_lowercase()],
+                    None => m[3]
+                        .split(',')
+                        .map(|s| s.trim().trim_matches(['\'', '"']).to_lowercase())
+                        .collect(),
+                },
+                None => continue,
+            };
+            let handler = handler_re.captures(object).map(|h| h[1].to_string()).unwrap_or_default();
+            for method in methods.iter().filter(|m| HTTP_METHODS.contains(&m.as_str())) {
+                push_route(router.clone(), method, path.clone(), whole.start(), handler_source(&handler, object));
+            }
+        }
+    }
+
+    // app.use('/prefix', ...middleware, router) and fastify.register(plugin, { prefix: '/prefix' })
+    if let (Ok(mount_re), Ok(prefix_re)) = (
+        Regex::new(r"\b(\w+)\s*\.\s*(use|register)\s*\("),
+        Regex::new(r#"\bprefix\s*:\s*(?:'([^']*)'|"([^"]*)"|`([^`]*)`)"#),
+    ) {
+        for cap in mount_re.captures_iter(content) {
+            let whole = cap.get(0).expect("match");
+            let parent = match owner(&cap[1], whole.start()) {
+                Some(parent) => parent,
+                None => continue,
+            };
+            let open = whole.end() - 1;
+            let close = match matching_close(content, open, '(', ')') {
+                Some(close) => close,
+                None => continue,
+            };
+            let args = split_args(&content[open + 1..close]);
+            let (prefix, target) = if &cap[2] == "use" {
+                match args.first().and_then(|(_, arg)| string_literal(arg)) {
+                    Some(prefix) => (prefix, args.iter().skip(1).last()),
+                    None => (String::new(), args.last()),
+                }
+            } else {
+                let prefix = args
+                    .get(1)
+                    .and_then(|(_, options)| prefix_re.captures(options))
+                    .and_then(|p| p.get(1).or_else(|| p.get(2)).or_else(|| p.get(3)))
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_default();
+                (prefix, args.first())
+            };
+            let target = target.and_then(|(arg_offset, arg)| {
+                let start = open + 1 + arg_offset;
+                mount_target(arg, start..start + arg.len(), &imports, &functions, dir)
+            });
+            if let Some(target) = target {
+                file.mounts.push(Mount { parent, prefix, target });
+            }
+        }
+    }
+
+    file.routes.sort_by_key(|r| r.offset);
+    file
+}
+
+/// What a mount argument refers to: an imported or local router, a `require('./x')`, or an
+/// inline plugin function
+fn mount_target(
+    arg: &str,
+    span: std::ops::Range<usize>,
+    imports: &HashMap<String, MountTarget>,
+    functions: &[FunctionDef],
+    dir: &Path,
+) -> Option<MountTarget> {
+    let arg = arg.trim();
+    if let Some(cap) = Regex::new(r#"^(?:require|import)\s*\(\s*['"](\.[^'"]+)['"]\s*\)$"#).ok()?.captures(arg) {
+        return Some(MountTarget::Import(normalize_path(&dir.join(&cap[1])), None));
+    }
+    if !arg.is_empty() && arg.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+        return Some(imports.get(arg).cloned().unwrap_or_else(|| MountTarget::Local(arg.to_string())));
+    }
+    functions
+        .iter()
+        .find(|f| span.contains(&f.start))
+        .map(|f| MountTarget::Local(f.name.clone()))
+}
+
+/// Variables assigned an Express app/router or a Fastify instance
+fn declared_routers(content: &str) -> HashSet<String> {
+    let re = match Regex::new(
+        r#"(?:const|let|var)\s+(\w+)\s*=\s*(?:await\s+)?(?:new\s+)?(?:express\s*\(|express\s*\.\s*Router\s*\(|Router\s*\(|[Ff]astify\s*\(|require\s*\(\s*['"](?:express|fastify)['"]\s*\)\s*(?:\.\s*Router\s*)?\()"#,
+    ) {
+        Ok(re) => re,
+        Err(_) => return HashSet::new(),
+    };
+    re.captures_iter(content).map(|cap| cap[1].to_string()).collect()
+}
+
+/// Function definitions with their first parameter and body span. Anonymous functions are
+/// named "default" when they are the default export, else by position.
+fn function_defs(content: &str) -> Vec<FunctionDef> {
+    let patterns = [
+        r"(export\s+default\s+)?(?:async\s+)?function\s*\*?\s*(\w*)\s*\(\s*(\w*)",
+        r"(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s+)?(?:function\s*\w*\s*\(\s*(\w*)|\(\s*(\w*)|(\w+)\s*=>)",
+        r"(?:module\.exports|export\s+default)\s*=?\s*(?:async\s+)?(?:function\s*\w*\s*\(\s*(\w*)|\(\s*(\w*)|(\w+)\s*=>)",
+        r"(?:async\s+)?\(\s*(\w*)[^()]*\)\s*(?::\s*[\w<>\[\]]+\s*)?=>\s*\{",
+    ];
+    let mut defs: Vec<FunctionDef> = Vec::new();
+    for (kind, pattern) in patterns.iter().enumerate() {
+        let re = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(_) => continue,
+        };
+        for cap in re.captures_iter(content) {
+            let whole = cap.get(0).expect("match");
+            let group = |i: usize| cap.get(i).map(|m| m.as_str().to_string());
+            let (name, param) = match kind {
+                0 => {
+                    let name = group(2).filter(|n| !n.is_empty()).unwrap_or_else(|| {
+                        if cap.get(1).is_some() { "default".to_string() } else { format!("fn@{}", whole.start()) }
+                    });
+                    (name, group(3))
+                }
+                1 => (group(1).unwrap_or_default(), group(2).or_else(|| group(3)).or_else(|| group(4))),
+                2 => ("default".to_string(), group(1).or_else(|| group(2)).or_else(|| group(3))),
+                _ => (format!("fn@{}", whole.start()), group(1)),
+            };
+            let from = if kind == 3 { whole.end() - 1 } else { whole.end() };
+            let body = match block_after(content, from) {
+                Some(body) => body,
+                None => continue,
+            };
+            let start = body.as_ptr() as usize - content.as_ptr() as usize;
+            // The same body found by a later, less specific pattern keeps the earlier name
+            if defs.iter().any(|d| d.start == start) {
+                continue;
+            }
+            defs.push(FunctionDef { name, param: param.unwrap_or_default(), start, end: start + body.len() });
+        }
+    }
+    defs
+}
+
+/// `module.exports = router`, `export default router`, or a default-exported function
+fn default_export(content: &str, functions: &[FunctionDef]) -> Option<String> {
+    let re = Regex::new(r"(?m)(?:module\.exports\s*=|export\s+default)\s*([A-Za-z_$][\w$]*)\s*;?\s*$").ok()?;
+    if let Some(cap) = re.captures(content) {
+        return Some(cap[1].to_string());
+    }
+    let named_re = Regex::new(r"export\s+default\s+(?:async\s+)?function\s*\*?\s*(\w+)").ok()?;
+    if let Some(cap) = named_re.captures(content) {
+        return Some(cap[1].to_string());
+    }
+    functions.iter().any(|f| f.name == "default").then(|| "default".to_string())
+}
+
+/// Relative imports by local name: `const x = require('./x')`, `import x from './x'`,
+/// `import { a as b } from './x'` and `const { a: b } = require('./x')`
+fn import_targets(content: &str, dir: &Path) -> HashMap<String, MountTarget> {
+    let mut imports = HashMap::new();
+    let resolve = |spec: &str| normalize_path(&dir.join(spec));
+    let add_named = |list: &str, spec: &str, imports: &mut HashMap<String, MountTarget>| {
+        for part in list.split(',') {
+            let mut names = part.split(':').flat_map(|s| s.split(" as ")).map(str::trim);
+            let export = names.next().unwrap_or("");
+            let local = names.next().unwrap_or(export);
+            if !export.is_empty() {
+                imports.insert(local.to_string(), MountTarget::Import(resolve(spec), Some(export.to_string())));
+            }
+        }
+    };
+
+    let spec = r#"['"](\.[^'"]+)['"]"#;
+    let patterns = [
+        format!(r"(?:const|let|var)\s+(\w+)\s*=\s*require\s*\(\s*{}\s*\)", spec),
+        format!(r"import\s+(\w+)\s*(?:,\s*\{{[^}}]*\}})?\s*from\s*{}", spec),
+        format!(r"(?:const|let|var)\s*\{{([^}}]*)\}}\s*=\s*require\s*\(\s*{}\s*\)", spec),
+        format!(r"import\s*(?:\w+\s*,\s*)?\{{([^}}]*)\}}\s*from\s*{}", spec),
+    ];
+    for (kind, pattern) in patterns.iter().enumerate() {
+        let re = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(_) => continue,
+        };
+        for cap in re.captures_iter(content) {
+            if kind < 2 {
+                imports.insert(cap[1].to_string(), MountTarget::Import(resolve(&cap[2]), None));
+            } else {
+                add_named(&cap[1], &cap[2], &mut imports);
+            }
+        }
+    }
+    imports
+}
+
+/// Top-level comma-separated arguments with their offsets, skipping nested brackets and strings
+fn split_args(args: &str) -> Vec<(usize, &str)> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (pos, ch) in args.char_indices() {
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == quote {
+                in_string = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => in_string = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push((start, &args[start..pos]));
+                start = pos + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push((start, &args[start..]));
+    parts
+        .into_iter()
+        .filter(|(_, part)| !part.trim().is_empty())
+        .map(|(offset, part)| {
+            let leading = part.len() - part.trim_start().len();
+            (offset + leading, part.trim())
+        })
+        .collect()
+}
+
+/// Contents of a quoted string literal without interpolation
+fn string_literal(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    let quote = arg.chars().next().filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let inner = arg.strip_prefix(quote)?.strip_suffix(quote)?;
+    (!inner.contains(quote) && !inner.contains("${")).then(|| inner.to_string())
+}
+
+/// Parameters read from the request in a handler: `req.query.page`, `req.body['email']`,
+/// `const { name, email } = req.body` (Fastify's `request` too), in order of first use
+fn request_parameters(source: &str) -> Vec<(&'static str, String)> {
+    let kind = |k: &str| match k {
+        "params" => "path",
+        "query" => "query",
+        _ => "body",
+    };
+    let mut found: Vec<(usize, &'static str, String)> = Vec::new();
+
+    if let Ok(access_re) =
+        Regex::new(r#"\b(?:req|request)\s*\.\s*(params|query|body)\s*(?:\??\.\s*(\w+)|\[\s*['"]([^'"]+)['"]\s*\])"#)
+    {
+        for cap in access_re.captures_iter(source) {
+            if let Some(name) = cap.get(2).or_else(|| cap.get(3)) {
+                found.push((cap.get(0).expect("match").start(), kind(&cap[1]), name.as_str().to_string()));
+            }
+        }
+    }
+    if let Ok(destructure_re) = Regex::new(r"\{([^{}]*)\}\s*=\s*(?:req|request)\s*\.\s*(params|query|body)\b") {
+        for cap in destructure_re.captures_iter(source) {
+            let offset = cap.get(0).expect("match").start();
+            for binding in cap[1].split(',') {
+                let name = binding.split([':', '=']).next().unwrap_or("").trim();
+                if !name.is_empty() && !name.starts_with("...") && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    found.push((offset, kind(&cap[2]), name.to_string()));
+                }
+            }
+        }
+    }
+
+    found.sort_by_key(|(offset, _, _)| *offset);
+    let mut params: Vec<(&'static str, String)> = Vec::new();
+    for (_, source, name) in found {
+        if !params.iter().any(|(s, n)| *s == source && *n == name) {
+            params.push((source, name));
+        }
+    }
+    params
+}
+
+/// `:id` segments of a route path; `:id?` is optional
+fn path_parameters(path: &str) -> Vec<EndpointParameter> {
+    Regex::new(r":(\w+)(\?)?")
+        .map(|re| {
+            re.captures_iter(path)
+                .map(|cap| parameter(&cap[1], "path", cap.get(2).is_none()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parameter(name: &str, source: &str, required: bool) -> EndpointParameter {
+    let param_type = "string".to_string();
+    EndpointParameter {
+        name: name.to_string(),
+        example: ExampleGenerator::generate_example(&param_type, name, &None),
+        default_value: ExampleGenerator::generate_default(&param_type),
+        param_type,
+        source: source.to_string(),
+        required,
+        validation: None,
+    }
+}
+
+/// Join route path pieces into `/a/b`, dropping empty and duplicate slashes
+fn join_paths(prefix: &str, path: &str) -> String {
+    let segments: Vec<&str> = prefix.split('/').chain(path.split('/')).filter(|s| !s.is_empty()).collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Routes file name without extensions (`users.routes.js` → "users"); `index` files use their folder
+fn controller_name(path: &Path) -> String {
+    let stem = path
+        .file_name()
+        .map(|n| n.to_string_lossy().split('.').next().unwrap_or("").to_string())
+        .unwrap_or_default();
+    if stem == "index" {
+        if let Some(folder) = path.parent().and_then(|p| p.file_name()) {
+            return folder.to_string_lossy().to_string();
+        }
+    }
+    stem
+}
+
+/// Resolve `.` and `..` without touching the filesystem, so import paths match scanned paths
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn routes(content: &str) -> Vec<(String, String, String, String)> {
+        parse_file(content, Path::new("/project/src/routes/users.js"))
+            .routes
+            .into_iter()
+            .map(|r| (r.router, r.method, r.path, r.action))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_file_routes() {
+        let content = r#"
+const express = require('express');
+const users = express.Router();
+app.set('view engine', 'pug');
+const port = app.get('port');
+
+users.get('/', (req, res) => res.json(list(req.query.page)));
+users.post('/', validate, createUser);
+users.route('/:id').get(showUser).put(async (req, res) => { const { name, email } = req.body; });
+res.get('/not-a-route', 1);
+
+async function createUser(req, res) {
+  const { name, email: address, ...rest } = req.body;
+  await save(name, address, req.body['role']);
+}
+
+module.exports = users;
+"#;
+        assert_eq!(routes(content), vec![
+            ("users".into(), "GET".into(), "/".into(), "handler".into()),
+            ("users".into(), "POST".into(), "/".into(), "createUser".into()),
+            ("users".into(), "GET".into(), "/:id".into(), "showUser".into()),
+            ("users".into(), "PUT".into(), "/:id".into(), "handler".into()),
+        ]);
+
+        let file = parse_file(content, Path::new("/project/src/routes/users.js"));
+        assert_eq!(file.default_export.as_deref(), Some("users"));
+        assert_eq!(file.routes[0].line, 7);
+        assert_eq!(request_parameters(&file.routes[0].handler), vec![("query", "page".to_string())]);
+        assert_eq!(
+            request_parameters(&file.routes[1].handler),
+            vec![("body", "name".to_string()), ("body", "email".to_string()), ("body", "role".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_fastify_plugin() {
+        let content = r#"
+export default async function (fastify, opts) {
+  fastify.get('/items/:id', async (request, reply) => getItem(request.params.id, request.query.expand));
+  fastify.route({
+    method: ['POST', 'PUT'],
+    url: '/items',
+    handler: async (request) => save(request.body.name),
+  });
+}
+"#;
+        let file = parse_file(content, Path::new("/project/src/routes/items.ts"));
+        let found: Vec<(String, String, String)> =
+            file.routes.iter().map(|r| (r.router.clone(), r.method.clone(), r.path.clone())).collect();
+        assert_eq!(found, vec![
+            ("default".into(), "GET".into(), "/items/:id".into()),
+            ("default".into(), "POST".into(), "/items".into()),
+            ("default".into(), "PUT".into(), "/items".into()),
+        ]);
+        assert_eq!(file.default_export.as_deref(), Some("default"));
+        assert_eq!(request_parameters(&file.routes[1].handler), vec![("body", "name".to_string())]);
+    }
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_scan_resolves_mounted_routers() {
+        let root = std::env::temp_dir().join(format!("lookapi-express-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root, "package.json", r#"{ "dependencies": { "express": "^4.19.0" } }"#);
+        write(&root, "src/app.js", r#"
+const express = require('express');
+const usersRouter = require('./routes/users');
+const { adminRouter } = require('./routes/admin');
+const app = express();
+app.use(express.json());
+app.use('/api/v1/users', authenticate, usersRouter);
+app.use('/api/v1', adminRouter);
+app.get('/health', (req, res) => res.send('ok'));
+"#);
+        write(&root, "src/routes/users.js", r#"
+const router = require('express').Router();
+router.get('/', (req, res) => res.json(find(req.query.role)));
+router.get('/:id', (req, res) => res.json(findOne(req.params.id)));
+router.post('/', (req, res) => { const { email } = req.body; });
+module.exports = router;
+"#);
+        write(&root, "src/routes/admin.js", r#"
+const express = require('express');
+const adminRouter = express.Router();
+const reports = express.Router();
+reports.get('/daily', (req, res) => res.json([]));
+adminRouter.use('/admin/reports', reports);
+exports.adminRouter = adminRouter;
+"#);
+        write(&root, "node_modules/lib/index.js", "app.get('/ignored', (req, res) => {});");
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(crate::scanner::UnifiedScanner::new(root.clone()).scan()).unwrap();
+        assert_eq!(result.framework_info.framework, "express");
+
+        let found: Vec<String> = result.endpoints.iter().map(|e| format!("{} {}", e.method, e.path)).collect();
+        assert_eq!(found, vec![
+            "GET /health",
+            "GET /api/v1/admin/reports/daily",
+            "GET /api/v1/users",
+            "GET /api/v1/users/:id",
+            "POST /api/v1/users",
+        ]);
+
+        let show = &result.endpoints[3];
+        assert_eq!(show.controller, "users");
+        assert_eq!(show.version.as_deref(), Some("v1"));
+        assert_eq!(show.category.as_deref(), Some("users"));
+        assert_eq!((show.parameters[0].name.as_str(), show.parameters[0].source.as_str()), ("id", "path"));
+        assert_eq!(show.parameters.len(), 1);
+        let create = &result.endpoints[4];
+        assert_eq!((create.parameters[0].name.as_str(), create.parameters[0].source.as_str()), ("email", "body"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_scan_fastify_register_prefix() {
+        let root = std::env::temp_dir().join(format!("lookapi-fastify-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(&root, "package.json", r#"{ "dependencies": { "fastify": "^4.26.0" } }"#);
+        write(&root, "src/server.ts", r#"
+import Fastify from 'fastify';
+import itemRoutes from './routes/items';
+const server = Fastify({ logger: true });
+server.register(itemRoutes, { prefix: '/api' });
+server.register(async (instance) => {
+  instance.get('/status', async () => ({ ok: true }));
+}, { prefix: '/internal' });
+"#);
+        write(&root, "src/routes/items.ts", r#"
+export default async function itemRoutes(fastify) {
+  fastify.get('/items', async (request) => list(request.query.limit));
+}
+"#);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(crate::scanner::UnifiedScanner::new(root.clone()).scan()).unwrap();
+        assert_eq!(result.framework_info.framework, "fastify");
+        let found: Vec<String> = result.endpoints.iter().map(|e| format!("{} {}", e.method, e.path)).collect();
+        assert_eq!(found, vec!["GET /api/items", "GET /internal/status"]);
+        assert_eq!(result.endpoints[0].parameters[0].name, "limit");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
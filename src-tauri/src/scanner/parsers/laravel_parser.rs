@@ -1,6 +1,11 @@
 use crate::scanner::parsers::example_generator::ExampleGenerator;
+use crate::scanner::parsers::{
+    deduplicate_endpoints, default_category, default_worker_count, query_smells, read_source_files, version_from_path,
+    CategoryFn, DEFAULT_MAX_FILE_SIZE,
+};
 use crate::scanner::types::{
-    Authentication, Authorization, BusinessLogic, EndpointParameter, ScannedEndpoint,
+    Authentication, Authorization, BusinessLogic, DedupeKeyOptions, EndpointParameter,
+    ScannedEndpoint,
 };
 use glob::glob;
 use log::{debug, error, info, warn};
@@ -15,6 +20,11 @@ pub struct LaravelParser {
     endpoint_metadata: HashMap<String, EndpointMetadata>,
     controller_files_cache: HashMap<String, String>,
     form_request_files_cache: HashMap<String, String>,
+    max_file_size: u64,
+    workers: usize,
+    dedupe: DedupeKeyOptions,
+    category_fn: CategoryFn,
+    warnings: Vec<String>,
 }
 
 struct EndpointMetadata {
@@ -29,11 +39,46 @@ impl LaravelParser {
             endpoint_metadata: HashMap::new(),
             controller_files_cache: HashMap::new(),
             form_request_files_cache: HashMap::new(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            workers: default_worker_count(),
+            dedupe: DedupeKeyOptions::default(),
+            category_fn: default_category,
+            warnings: Vec::new(),
         }
     }
 
+    /// Override the size above which source files are skipped
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Cap the threads used to read and parse source files (default: available parallelism)
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Choose what identifies duplicate routes (default: method + path)
+    pub fn with_dedupe(mut self, dedupe: DedupeKeyOptions) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Replace the heuristic that assigns endpoint categories (default: `default_category`)
+    pub fn with_category_fn(mut self, category_fn: CategoryFn) -> Self {
+        self.category_fn = category_fn;
+        self
+    }
+
+    /// Warnings collected during the last parse, e.g. skipped oversized files
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     pub async fn parse_endpoints(&mut self) -> Result<Vec<ScannedEndpoint>, String> {
         let mut endpoints = Vec::new();
+        self.warnings.clear();
 
         // Step 1: Parse routes files
         let routes_endpoints = self.parse_routes_files().await?;
@@ -51,7 +96,7 @@ impl LaravelParser {
         }
 
         // Step 4: Remove duplicates
-        let unique_endpoints = self.deduplicate_endpoints(endpoints);
+        let unique_endpoints = deduplicate_endpoints(endpoints, self.dedupe);
 
         Ok(unique_endpoints)
     }
@@ -81,11 +126,10 @@ impl LaravelParser {
             };
 
             if let Ok(entries) = glob(&pattern_str) {
-                for entry in entries.flatten() {
-                    if let Ok(content) = fs::read_to_string(&entry) {
-                        let file_endpoints = self.parse_routes_content(&content, &entry)?;
-                        endpoints.extend(file_endpoints);
-                    }
+                let paths = entries.flatten().collect();
+                for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                    let file_endpoints = self.parse_routes_content(&content, &entry)?;
+                    endpoints.extend(file_endpoints);
                 }
             }
         }
@@ -100,6 +144,20 @@ impl LaravelParser {
     ) -> Result<Vec<ScannedEndpoint>, String> {
         let mut endpoints = Vec::new();
 
+        // Versioned prefix groups, falling back to a versioned file name like routes/api_v1.php
+        let version_scopes = self.find_version_scopes(content);
+        let file_version = file_path
+            .file_stem()
+            .and_then(|stem| version_from_path(&stem.to_string_lossy()));
+        let version_at = |pos: usize| -> Option<String> {
+            version_scopes
+                .iter()
+                .filter(|(start, end, _)| *start <= pos && pos < *end)
+                .min_by_key(|(start, end, _)| end - start)
+                .map(|(_, _, version)| version.clone())
+                .or_else(|| file_version.clone())
+        };
+
         // Pattern 1: Route::get('path', [Controller::class, 'method'])
         let route_patterns = vec![
             (r#"Route::get\s*\(\s*['"]([^'"]+)['"]\s*,\s*\[\s*([\w\\]+)::class\s*,\s*['"]([^'"]+)['"]\s*\]\s*\)"#, "GET"),
@@ -119,13 +177,16 @@ impl LaravelParser {
                         let controller_class = controller_match.as_str();
                         let method_name = action_match.as_str();
 
-                        let endpoint = self.create_endpoint(
+                        let mut endpoint = self.create_endpoint(
                             route_path,
                             method,
                             controller_class,
                             method_name,
                             file_path,
                         )?;
+                        if let Some(version) = version_at(cap.get(0).map(|m| m.start()).unwrap_or(0)) {
+                            endpoint.version = Some(version);
+                        }
 
                         // Store metadata
                         let key = format!("{}:{}", method, route_path);
@@ -158,8 +219,13 @@ impl LaravelParser {
                         let resource_path = resource_match.as_str();
                         let controller_class = controller_match.as_str();
 
-                        let resource_endpoints =
+                        let mut resource_endpoints =
                             self.generate_resource_endpoints(resource_path, controller_class, is_api)?;
+                        if let Some(version) = version_at(cap.get(0).map(|m| m.start()).unwrap_or(0)) {
+                            for endpoint in resource_endpoints.iter_mut() {
+                                endpoint.version = Some(version.clone());
+                            }
+                        }
                         endpoints.extend(resource_endpoints);
                     }
                 }
@@ -169,6 +235,51 @@ impl LaravelParser {
         Ok(endpoints)
     }
 
+    /// Byte ranges of route groups whose prefix is a version, e.g. Route::prefix('v1')->group(...)
+    fn find_version_scopes(&self, content: &str) -> Vec<(usize, usize, String)> {
+        let mut scopes = Vec::new();
+        let group_patterns = vec![
+            r#"Route::prefix\s*\(\s*['"]([^'"]+)['"]\s*\)[^;{]*?->group\s*\("#,
+            r#"Route::group\s*\(\s*\[[^\]]*['"]prefix['"]\s*=>\s*['"]([^'"]+)['"][^\]]*\]"#,
+        ];
+
+        for pattern in group_patterns {
+            if let Ok(re) = Regex::new(pattern) {
+                for cap in re.captures_iter(content) {
+                    let (whole, version) = match (cap.get(0), cap.get(1).and_then(|m| version_from_path(m.as_str()))) {
+                        (Some(whole), Some(version)) => (whole, version),
+                        _ => continue,
+                    };
+
+                    // The group closure body runs from its first '{' to the matching '}'
+                    let open = match content[whole.end()..].find('{') {
+                        Some(pos) => whole.end() + pos,
+                        None => continue,
+                    };
+                    let mut depth = 0;
+                    let mut end = content.len();
+                    for (offset, ch) in content[open..].char_indices() {
+                        match ch {
+                            '{' => depth += 1,
+                            '}' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    end = open + offset;
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    scopes.push((open, end, version));
+                }
+            }
+        }
+
+        scopes
+    }
+
     fn generate_resource_endpoints(
         &mut self,
         resource_path: &str,
@@ -233,12 +344,11 @@ impl LaravelParser {
         let pattern_str = format!("{}/**/app/Http/Controllers/**/*.php", self.project_path.to_string_lossy());
 
         if let Ok(entries) = glob(&pattern_str) {
-            for entry in entries.flatten() {
-                if let Ok(content) = fs::read_to_string(&entry) {
-                    if let Some(controller_class) = self.extract_controller_class(&content, &entry) {
-                        self.controller_files_cache
-                            .insert(controller_class, entry.to_string_lossy().to_string());
-                    }
+            let paths = entries.flatten().collect();
+            for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                if let Some(controller_class) = self.extract_controller_class(&content, &entry) {
+                    self.controller_files_cache
+                        .insert(controller_class, entry.to_string_lossy().to_string());
                 }
             }
         }
@@ -250,20 +360,19 @@ impl LaravelParser {
         let pattern_str = format!("{}/**/app/Http/Requests/**/*.php", self.project_path.to_string_lossy());
 
         if let Ok(entries) = glob(&pattern_str) {
-            for entry in entries.flatten() {
-                if let Ok(content) = fs::read_to_string(&entry) {
-                    if let Some(form_request_class) = self.extract_form_request_class(&content) {
-                        let file_path = entry.to_string_lossy().to_string();
-                        
-                        // Store with full namespace
+            let paths = entries.flatten().collect();
+            for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                if let Some(form_request_class) = self.extract_form_request_class(&content) {
+                    let file_path = entry.to_string_lossy().to_string();
+                    
+                    // Store with full namespace
+                    self.form_request_files_cache
+                        .insert(form_request_class.clone(), file_path.clone());
+                    
+                    // Also store with simple class name for lookup
+                    if let Some(simple_name) = form_request_class.split('\\').last() {
                         self.form_request_files_cache
-                            .insert(form_request_class.clone(), file_path.clone());
-                        
-                        // Also store with simple class name for lookup
-                        if let Some(simple_name) = form_request_class.split('\\').last() {
-                            self.form_request_files_cache
-                                .insert(simple_name.to_string(), file_path);
-                        }
+                            .insert(simple_name.to_string(), file_path);
                     }
                 }
             }
@@ -353,12 +462,27 @@ impl LaravelParser {
                         // Continue if extraction fails
                     }
                 }
+
+                endpoint.warnings.extend(self.method_query_warnings(&controller_content, &metadata.method_name));
             }
         }
 
         Ok(())
     }
 
+    /// Possible N+1 queries in a controller method body
+    fn method_query_warnings(&self, controller_content: &str, method_name: &str) -> Vec<String> {
+        let method_re = match Regex::new(&format!(r"public\s+function\s+{}\s*\(", regex::escape(method_name))) {
+            Ok(re) => re,
+            Err(_) => return Vec::new(),
+        };
+        method_re
+            .find(controller_content)
+            .and_then(|m| query_smells::block_after(controller_content, m.end() - 1))
+            .map(query_smells::laravel_warnings)
+            .unwrap_or_default()
+    }
+
     fn parse_path_parameters(&self, path: &str) -> Vec<EndpointParameter> {
         let mut params = Vec::new();
         // Laravel path parameter pattern: {id}, {id?}, {id:\d+}
@@ -941,22 +1065,12 @@ impl LaravelParser {
             authentication: Authentication::default(),
             authorization: Authorization::default(),
             responses: Vec::new(),
+            version: version_from_path(&normalized_path),
+            category: (self.category_fn)(&normalized_path, &controller_name),
+            warnings: Vec::new(),
         })
     }
 
-    fn deduplicate_endpoints(&self, endpoints: Vec<ScannedEndpoint>) -> Vec<ScannedEndpoint> {
-        let mut seen = HashMap::new();
-
-        for endpoint in endpoints {
-            let key = format!("{}:{}", endpoint.method, endpoint.path);
-            if !seen.contains_key(&key) {
-                seen.insert(key, endpoint);
-            }
-        }
-
-        seen.into_values().collect()
-    }
-
     /// Extract validation rules from FormRequest content
     fn extract_validation_rules(&self, form_request_content: &str) -> HashMap<String, Vec<String>> {
         let mut rules = HashMap::new();
@@ -1185,3 +1299,21 @@ impl LaravelParser {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_categories() {
+        let parser = LaravelParser::new(PathBuf::new());
+        let endpoint = parser
+            .create_endpoint("api/v1/orders/{order}", "GET", "App\\Http\\Controllers\\OrderController", "show", Path::new(""))
+            .unwrap();
+        assert_eq!(endpoint.category.as_deref(), Some("orders"));
+
+        let endpoint = parser
+            .create_endpoint("/", "GET", "App\\Http\\Controllers\\HomeController", "index", Path::new(""))
+            .unwrap();
+        assert_eq!(endpoint.category.as_deref(), Some("home"));
+    }
+}
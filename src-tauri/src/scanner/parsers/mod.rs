@@ -1,8 +1,323 @@
 pub mod example_generator;
+pub mod express_parser;
 pub mod laravel_parser;
 pub mod nestjs_parser;
+pub mod query_smells;
 
 pub use example_generator::ExampleGenerator;
+pub use express_parser::ExpressParser;
 pub use laravel_parser::LaravelParser;
 pub use nestjs_parser::NestJSParser;
 
+use crate::scanner::types::{DedupeKeyOptions, ScannedEndpoint};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Default cap on source file size (4 MB). Larger files are usually generated or minified
+/// and can stall the regex-based parsers, so they are skipped with a scan warning.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Whether a file is small enough to be parsed
+pub fn within_size_limit(path: &Path, max_size: u64) -> bool {
+    fs::metadata(path).map(|m| m.len() <= max_size).unwrap_or(false)
+}
+
+/// Read a source file, recording a warning instead when it exceeds `max_size` bytes
+pub fn read_source_file(path: &Path, max_size: u64, warnings: &mut Vec<String>) -> Option<String> {
+    let size = fs::metadata(path).ok()?.len();
+    if size > max_size {
+        let warning = format!(
+            "Skipped {}: {} bytes exceeds the {} byte source file limit",
+            path.display(),
+            size,
+            max_size
+        );
+        log::warn!("[Scanner] {}", warning);
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+        return None;
+    }
+    fs::read_to_string(path).ok()
+}
+
+/// Stream a source file line by line instead of reading it into memory at once. Used for DTO
+/// and entity files, whose properties are extracted one line at a time; reading stops at the
+/// first line that isn't valid UTF-8.
+pub fn source_lines(path: impl AsRef<Path>) -> Option<impl Iterator<Item = String>> {
+    let file = fs::File::open(path).ok()?;
+    Some(BufReader::new(file).lines().map_while(Result::ok))
+}
+
+/// Threads used to read and parse source files unless overridden: the machine's available
+/// parallelism, or 4 when that can't be determined
+pub fn default_worker_count() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
+/// A source file that was read: its path and contents
+pub type SourceFile = (PathBuf, String);
+
+/// What one reader thread returns: the files it read and the warnings it recorded
+type ChunkResult = (Vec<SourceFile>, Vec<String>);
+
+/// Read source files on up to `workers` scoped threads, returning the readable ones in input
+/// order. Oversized files are skipped with a warning, as in `read_source_file`.
+pub fn read_source_files(
+    paths: Vec<PathBuf>,
+    max_size: u64,
+    workers: usize,
+    warnings: &mut Vec<String>,
+) -> Vec<SourceFile> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = paths.len().div_ceil(workers.clamp(1, paths.len()));
+
+    let chunks: Vec<ChunkResult> = std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut chunk_warnings = Vec::new();
+                    let files = chunk
+                        .iter()
+                        .filter_map(|path| {
+                            read_source_file(path, max_size, &mut chunk_warnings).map(|content| (path.clone(), content))
+                        })
+                        .collect();
+                    (files, chunk_warnings)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+
+    let mut files = Vec::new();
+    for (chunk_files, chunk_warnings) in chunks {
+        for warning in chunk_warnings {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+        files.extend(chunk_files);
+    }
+    files
+}
+
+/// Identity of an endpoint for deduplication: `METHOD:path`, optionally followed by the
+/// auth requirement and a sorted `source.name` signature of non-path parameters (`!` = required)
+pub fn dedupe_key(endpoint: &ScannedEndpoint, options: DedupeKeyOptions) -> String {
+    let mut key = format!("{}:{}", endpoint.method, endpoint.path);
+    if options.include_auth {
+        let auth = if endpoint.authentication.required {
+            endpoint.authentication.auth_type.as_deref().unwrap_or("required")
+        } else {
+            "none"
+        };
+        key.push_str(&format!("|auth={}", auth.to_lowercase()));
+    }
+    if options.include_params {
+        let mut params: Vec<String> = endpoint.parameters
+            .iter()
+            .filter(|p| p.source != "path")
+            .map(|p| format!("{}.{}{}", p.source, p.name.to_lowercase(), if p.required { "!" } else { "" }))
+            .collect();
+        params.sort();
+        params.dedup();
+        key.push_str(&format!("|params={}", params.join(",")));
+    }
+    key
+}
+
+/// Keep the first endpoint for each dedupe key, preserving input order
+pub fn deduplicate_endpoints(endpoints: Vec<ScannedEndpoint>, options: DedupeKeyOptions) -> Vec<ScannedEndpoint> {
+    let mut seen = HashSet::new();
+
+    endpoints
+        .into_iter()
+        .filter(|endpoint| seen.insert(dedupe_key(endpoint, options)))
+        .collect()
+}
+
+/// Find an API version segment such as `v1` or `v2` in a route path or file name
+pub fn version_from_path(path: &str) -> Option<String> {
+    path.split(['/', '_', '-', '.'])
+        .find(|segment| {
+            let rest = match segment.strip_prefix('v').or_else(|| segment.strip_prefix('V')) {
+                Some(rest) => rest,
+                None => return false,
+            };
+            rest.starts_with(|c: char| c.is_ascii_digit())
+                && rest.chars().all(|c| c.is_ascii_digit())
+        })
+        .map(|segment| segment.to_lowercase())
+}
+
+/// Derives an endpoint's category from its route path and controller name
+pub type CategoryFn = fn(path: &str, controller: &str) -> Option<String>;
+
+/// Default category heuristic: the first path segment that isn't `api`, a version or a path
+/// parameter (`/api/v1/orders/{id}` → "orders"), else the controller name without its
+/// `Controller` suffix (`OrderItemsController` → "orderitems")
+pub fn default_category(path: &str, controller: &str) -> Option<String> {
+    let from_path = path
+        .split('/')
+        .map(str::trim)
+        .find(|segment| {
+            !segment.is_empty()
+                && !segment.eq_ignore_ascii_case("api")
+                && !segment.starts_with(['{', ':'])
+                && version_from_path(segment).is_none()
+        })
+        .map(|segment| segment.to_lowercase());
+
+    from_path.or_else(|| {
+        let name = controller.rsplit(['\\', '/']).next().unwrap_or(controller);
+        let name = name.strip_suffix("Controller").unwrap_or(name);
+        (!name.is_empty()).then(|| name.to_lowercase())
+    })
+}
+
+/// Normalize a declared version ("2", "v2") to its label form ("v2")
+pub fn normalize_version(version: &str) -> String {
+    let version = version.trim();
+    if version.starts_with('v') || version.starts_with('V') {
+        version.to_lowercase()
+    } else {
+        format!("v{}", version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_from_path() {
+        assert_eq!(version_from_path("/api/v1/users"), Some("v1".to_string()));
+        assert_eq!(version_from_path("/api/V2/orders/{id}"), Some("v2".to_string()));
+        assert_eq!(version_from_path("routes/api_v3"), Some("v3".to_string()));
+        assert_eq!(version_from_path("/api/videos"), None);
+        assert_eq!(normalize_version("2"), "v2");
+    }
+
+    #[test]
+    fn test_default_category() {
+        assert_eq!(default_category("/api/orders/{id}", ""), Some("orders".to_string()));
+        assert_eq!(default_category("/api/v2/Invoices/:id/lines", ""), Some("invoices".to_string()));
+        assert_eq!(default_category("users", "UserController"), Some("users".to_string()));
+        assert_eq!(default_category("/api/{id}", "App\\Http\\Controllers\\OrderItemsController"), Some("orderitems".to_string()));
+        assert_eq!(default_category("/", "HealthController"), Some("health".to_string()));
+        assert_eq!(default_category("/api", ""), None);
+    }
+
+    #[test]
+    fn test_read_source_files_keeps_order_and_skips_oversized() {
+        let dir = std::env::temp_dir().join(format!("lookapi-read-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let paths: Vec<PathBuf> = (0..5)
+            .map(|i| {
+                let path = dir.join(format!("file{}.ts", i));
+                fs::write(&path, if i == 3 { "x".repeat(64) } else { format!("// {}", i) }).unwrap();
+                path
+            })
+            .collect();
+
+        for workers in [1, 2, 16] {
+            let mut warnings = Vec::new();
+            let files = read_source_files(paths.clone(), 32, workers, &mut warnings);
+            let contents: Vec<&str> = files.iter().map(|(_, content)| content.as_str()).collect();
+            assert_eq!(contents, vec!["// 0", "// 1", "// 2", "// 4"]);
+            assert_eq!(files[3].0, paths[4]);
+            assert_eq!(warnings.len(), 1);
+        }
+        assert!(default_worker_count() >= 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    fn endpoint(auth_required: bool, query: &[&str]) -> ScannedEndpoint {
+        use crate::scanner::types::{Authentication, Authorization, BusinessLogic, EndpointParameter};
+        ScannedEndpoint {
+            path: "/reports".to_string(),
+            method: "GET".to_string(),
+            controller: "ReportsController".to_string(),
+            action: "index".to_string(),
+            file_path: "reports.controller.ts".to_string(),
+            line_number: 1,
+            parameters: query
+                .iter()
+                .map(|name| EndpointParameter {
+                    name: name.to_string(),
+                    param_type: "string".to_string(),
+                    source: "query".to_string(),
+                    required: true,
+                    validation: None,
+                    example: None,
+                    default_value: None,
+                })
+                .collect(),
+            business_logic: BusinessLogic {
+                summary: String::new(),
+                description: String::new(),
+                purpose: String::new(),
+                dependencies: vec![],
+            },
+            authentication: Authentication {
+                required: auth_required,
+                auth_type: auth_required.then(|| "bearer".to_string()),
+            },
+            authorization: Authorization { roles: vec![], permissions: vec![] },
+            responses: vec![],
+            version: None,
+            category: None,
+            warnings: vec![],
+        }
+    }
+
+    #[test]
+    fn test_dedupe_key_options() {
+        let routes = || vec![endpoint(false, &[]), endpoint(true, &[])];
+
+        assert_eq!(deduplicate_endpoints(routes(), DedupeKeyOptions::default()).len(), 1);
+
+        let with_auth = DedupeKeyOptions { include_auth: true, include_params: false };
+        let kept = deduplicate_endpoints(routes(), with_auth);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().any(|e| e.authentication.required));
+        assert_eq!(dedupe_key(&endpoint(true, &[]), with_auth), "GET:/reports|auth=bearer");
+
+        let with_params = DedupeKeyOptions { include_auth: false, include_params: true };
+        assert_eq!(
+            dedupe_key(&endpoint(false, &["to", "from"]), with_params),
+            "GET:/reports|params=query.from!,query.to!"
+        );
+        let variants = vec![endpoint(false, &["from"]), endpoint(false, &["page"]), endpoint(false, &["from"])];
+        assert_eq!(deduplicate_endpoints(variants, with_params).len(), 2);
+    }
+
+    #[test]
+    fn test_read_source_file_skips_oversized_files() {
+        let path = std::env::temp_dir().join(format!("lookapi-size-{}.ts", std::process::id()));
+        fs::write(&path, "x".repeat(64)).unwrap();
+
+        let mut warnings = Vec::new();
+        assert!(read_source_file(&path, 64, &mut warnings).is_some());
+        assert!(warnings.is_empty());
+
+        assert!(read_source_file(&path, 63, &mut warnings).is_none());
+        assert!(!within_size_limit(&path, 63));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("64 bytes exceeds the 63 byte"));
+
+        let _ = fs::remove_file(&path);
+    }
+}
+
@@ -1,7 +1,12 @@
+use crate::http_client::auth_header_templates;
 use crate::scanner::parsers::example_generator::ExampleGenerator;
+use crate::scanner::parsers::{
+    deduplicate_endpoints, default_category, default_worker_count, normalize_version, query_smells, read_source_files, source_lines,
+    version_from_path, within_size_limit, CategoryFn, DEFAULT_MAX_FILE_SIZE,
+};
 use crate::scanner::types::{
-    Authentication, Authorization, BusinessLogic, EndpointParameter, EndpointResponse,
-    ResponseProperty, ResponseSchema, ScannedEndpoint,
+    Authentication, Authorization, BusinessLogic, DedupeKeyOptions, EndpointParameter,
+    EndpointResponse, ResponseProperty, ResponseSchema, ScannedEndpoint,
 };
 use glob::glob;
 use regex::Regex;
@@ -16,6 +21,15 @@ struct MethodInfo {
     method_auth: Authentication,
     return_type: Option<String>,
     http_code: Option<u16>,
+    version: Option<String>, // From @Version('2')
+    start: usize, // Offset of the method name in the controller source
+}
+
+/// App-level versioning from app.enableVersioning({...})
+struct VersioningConfig {
+    uri: bool,              // VersioningType.URI puts the version in the path
+    prefix: String,         // URI prefix, "v" by default
+    default_version: Option<String>,
 }
 
 pub struct NestJSParser {
@@ -24,8 +38,15 @@ pub struct NestJSParser {
     dto_files_cache: HashMap<String, String>,
     response_dto_files_cache: HashMap<String, String>,
     entity_files_cache: HashMap<String, String>,
+    enum_values_cache: HashMap<String, Vec<Value>>, // TypeScript enum name -> member values
     global_prefix: Option<String>,
     has_global_wrapper: bool,
+    versioning: Option<VersioningConfig>,
+    max_file_size: u64,
+    workers: usize,
+    dedupe: DedupeKeyOptions,
+    category_fn: CategoryFn,
+    warnings: Vec<String>,
 }
 
 impl NestJSParser {
@@ -36,9 +57,86 @@ impl NestJSParser {
             dto_files_cache: HashMap::new(),
             response_dto_files_cache: HashMap::new(),
             entity_files_cache: HashMap::new(),
+            enum_values_cache: HashMap::new(),
             global_prefix: None,
             has_global_wrapper: false,
+            versioning: None,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            workers: default_worker_count(),
+            dedupe: DedupeKeyOptions::default(),
+            category_fn: default_category,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Override the size above which source files are skipped
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Cap the threads used to read and parse source files (default: available parallelism)
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Choose what identifies duplicate routes (default: method + path)
+    pub fn with_dedupe(mut self, dedupe: DedupeKeyOptions) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Replace the heuristic that assigns endpoint categories (default: `default_category`)
+    pub fn with_category_fn(mut self, category_fn: CategoryFn) -> Self {
+        self.category_fn = category_fn;
+        self
+    }
+
+    /// Warnings collected during the last parse, e.g. skipped oversized files
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Read an entry point file, ignoring files above the size limit
+    fn read_main_file(&self, path: &Path) -> Option<String> {
+        if !within_size_limit(path, self.max_file_size) {
+            return None;
         }
+        fs::read_to_string(path).ok()
+    }
+
+    fn extract_versioning_config(&self) -> Option<VersioningConfig> {
+        let possible_paths = vec![
+            self.project_path.join("src/main.ts"),
+            self.project_path.join("src/main.js"),
+        ];
+
+        let versioning_re = Regex::new(r"app\.enableVersioning\s*\(\s*\{([^}]*)\}").ok()?;
+        let prefix_re = Regex::new(r#"prefix\s*:\s*(?:'([^']*)'|"([^"]*)"|(false))"#).ok()?;
+        let default_re = Regex::new(r#"defaultVersion\s*:\s*\[?\s*(?:'([^']+)'|"([^"]+)")"#).ok()?;
+
+        for main_path in possible_paths {
+            if let Some(content) = self.read_main_file(&main_path) {
+                if let Some(cap) = versioning_re.captures(&content) {
+                    let options = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+                    let prefix = prefix_re.captures(options)
+                        .map(|c| c.get(1).or_else(|| c.get(2)).map(|m| m.as_str().to_string()).unwrap_or_default())
+                        .unwrap_or_else(|| "v".to_string());
+                    let default_version = default_re.captures(options)
+                        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+                        .map(|m| m.as_str().to_string());
+
+                    return Some(VersioningConfig {
+                        uri: options.contains("VersioningType.URI"),
+                        prefix,
+                        default_version,
+                    });
+                }
+            }
+        }
+
+        None
     }
 
     fn extract_global_prefix(&self) -> Option<String> {
@@ -49,7 +147,7 @@ impl NestJSParser {
         ];
 
         for main_path in possible_paths {
-            if let Ok(content) = fs::read_to_string(&main_path) {
+            if let Some(content) = self.read_main_file(&main_path) {
                 // Match pattern: app.setGlobalPrefix('...') or app.setGlobalPrefix("...")
                 let prefix_re = Regex::new(r#"app\.setGlobalPrefix\s*\(\s*(?:'([^']+)'|"([^"]+)")\s*\)"#).ok()?;
                 
@@ -69,8 +167,11 @@ impl NestJSParser {
     }
 
     pub async fn parse_endpoints(&mut self) -> Result<Vec<ScannedEndpoint>, String> {
+        self.warnings.clear();
+
         // Step 0: Extract global prefix from main.ts
         self.global_prefix = self.extract_global_prefix();
+        self.versioning = self.extract_versioning_config();
         
         // Step 0.5: Detect global response wrapper (TransformInterceptor)
         self.has_global_wrapper = self.detect_global_wrapper();
@@ -80,32 +181,64 @@ impl NestJSParser {
         self.build_dto_files_cache().await?;
         self.build_response_dto_files_cache().await?;
         self.build_entity_files_cache().await?;
+        self.build_enum_values_cache().await?;
 
-        // Step 2: Parse all controller files
+        // Step 2: Parse all controller files, sorted by path so the output order is stable
+        let mut controller_paths: Vec<&String> = self.controller_files_cache.values().collect();
+        controller_paths.sort();
+        controller_paths.dedup();
         let mut endpoints = Vec::new();
-        for (_, file_path) in &self.controller_files_cache {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                let file_endpoints = self.parse_controller_content(&content, Path::new(file_path))?;
-                endpoints.extend(file_endpoints);
-            }
+        for file_endpoints in self.parse_controller_files(&controller_paths) {
+            endpoints.extend(file_endpoints?);
         }
 
         // Step 3: Remove duplicates
-        let unique_endpoints = self.deduplicate_endpoints(endpoints);
+        let unique_endpoints = deduplicate_endpoints(endpoints, self.dedupe);
 
         Ok(unique_endpoints)
     }
 
+    /// Parse controller files on up to `workers` scoped threads. The caches are only read during
+    /// this phase; each worker takes a contiguous chunk so results come back in input order.
+    fn parse_controller_files(&self, paths: &[&String]) -> Vec<Result<Vec<ScannedEndpoint>, String>> {
+        if paths.is_empty() {
+            return Vec::new();
+        }
+        let workers = self.workers.clamp(1, paths.len());
+        let chunk_size = paths.len().div_ceil(workers);
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = paths
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|file_path| match fs::read_to_string(file_path) {
+                                Ok(content) => self.parse_controller_content(&content, Path::new(file_path.as_str())),
+                                Err(_) => Ok(Vec::new()),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        })
+    }
+
     async fn build_controller_files_cache(&mut self) -> Result<(), String> {
         let pattern_str = format!("{}/**/*.controller.ts", self.project_path.to_string_lossy());
 
         if let Ok(entries) = glob(&pattern_str) {
-            for entry in entries.flatten() {
-                if let Ok(content) = fs::read_to_string(&entry) {
-                    if let Some(controller_class) = self.extract_controller_class(&content, &entry) {
-                        self.controller_files_cache
-                            .insert(controller_class, entry.to_string_lossy().to_string());
-                    }
+            let paths = entries.flatten().collect();
+            for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                if let Some(controller_class) = self.extract_controller_class(&content, &entry) {
+                    self.controller_files_cache
+                        .insert(controller_class, entry.to_string_lossy().to_string());
                 }
             }
         }
@@ -117,20 +250,19 @@ impl NestJSParser {
         let pattern_str = format!("{}/**/dto/*.dto.ts", self.project_path.to_string_lossy());
 
         if let Ok(entries) = glob(&pattern_str) {
-            for entry in entries.flatten() {
-                if let Ok(content) = fs::read_to_string(&entry) {
-                    if let Some(dto_class) = self.extract_dto_class(&content) {
-                        let file_path = entry.to_string_lossy().to_string();
-                        
-                        // Store with full class name
+            let paths = entries.flatten().collect();
+            for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                if let Some(dto_class) = self.extract_dto_class(&content) {
+                    let file_path = entry.to_string_lossy().to_string();
+                    
+                    // Store with full class name
+                    self.dto_files_cache
+                        .insert(dto_class.clone(), file_path.clone());
+                    
+                    // Also store with simple class name for lookup
+                    if let Some(simple_name) = dto_class.split('.').last() {
                         self.dto_files_cache
-                            .insert(dto_class.clone(), file_path.clone());
-                        
-                        // Also store with simple class name for lookup
-                        if let Some(simple_name) = dto_class.split('.').last() {
-                            self.dto_files_cache
-                                .insert(simple_name.to_string(), file_path);
-                        }
+                            .insert(simple_name.to_string(), file_path);
                     }
                 }
             }
@@ -139,6 +271,32 @@ impl NestJSParser {
         Ok(())
     }
 
+    /// Index `enum` declarations from the files DTOs usually import them from
+    async fn build_enum_values_cache(&mut self) -> Result<(), String> {
+        let root = self.project_path.to_string_lossy().to_string();
+        let patterns = [
+            format!("{}/**/*.enum.ts", root),
+            format!("{}/**/enums/**/*.ts", root),
+            format!("{}/**/dto/*.dto.ts", root),
+            format!("{}/**/*.entity.ts", root),
+        ];
+
+        let mut paths: Vec<PathBuf> = patterns
+            .iter()
+            .filter_map(|pattern| glob(pattern).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|path| !path.components().any(|c| c.as_os_str() == "node_modules"))
+            .collect();
+        paths.sort();
+        paths.dedup();
+
+        for (_, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+            self.enum_values_cache.extend(parse_enum_declarations(&content));
+        }
+
+        Ok(())
+    }
+
     fn extract_controller_class(&self, content: &str, _file_path: &Path) -> Option<String> {
         // Try to extract class name: export class CartController
         let class_re = Regex::new(r"export\s+class\s+(\w+Controller)\s*(?:extends|implements|\{)").ok()?;
@@ -174,6 +332,8 @@ impl NestJSParser {
 
         // Extract controller base path from @Controller('path')
         let base_path = self.extract_controller_base_path(content);
+        let controller_name = self.extract_controller_name(content);
+        let controller_version = self.extract_controller_version(content);
 
         // Extract authentication from controller level @UseGuards
         let controller_auth = self.detect_authentication(content, true);
@@ -202,19 +362,26 @@ impl NestJSParser {
                     // Find the method definition after this decorator
                     // Look for method in the next 20 lines after decorator
                     if let Some(method_info) = self.find_method_after_decorator(content, decorator_start, method) {
-                        let full_path = self.build_full_path(&base_path, method_path);
+                        // Method @Version overrides controller version, which overrides the app default
+                        let declared_version = method_info.version.clone()
+                            .or_else(|| controller_version.clone())
+                            .or_else(|| self.versioning.as_ref().and_then(|v| v.default_version.clone()));
+                        let full_path = self.build_full_path(&base_path, method_path, declared_version.as_deref());
                         
-                        let endpoint = self.create_endpoint(
+                        let mut endpoint = self.create_endpoint(
                             &full_path,
                             method,
-                            &method_info.method_name,
-                            &method_info.params,
+                            &controller_name,
+                            &method_info,
                             file_path,
                             &controller_auth,
-                            &method_info.method_auth,
-                            method_info.return_type.as_deref(),
-                            method_info.http_code,
                         )?;
+                        endpoint.version = declared_version
+                            .map(|v| normalize_version(&v))
+                            .or_else(|| version_from_path(&full_path));
+                        endpoint.warnings = query_smells::block_after(content, method_info.start)
+                            .map(query_smells::nestjs_warnings)
+                            .unwrap_or_default();
 
                         endpoints.push(endpoint);
                     }
@@ -225,9 +392,29 @@ impl NestJSParser {
         Ok(endpoints)
     }
 
+    fn extract_controller_name(&self, content: &str) -> String {
+        // export class UsersController { ... }
+        Regex::new(r"class\s+(\w+)")
+            .ok()
+            .and_then(|re| re.captures(content))
+            .and_then(|cap| cap.get(1))
+            .map(|m| m.as_str().to_string())
+            .unwrap_or_default()
+    }
+
+    fn extract_controller_version(&self, content: &str) -> Option<String> {
+        // @Controller({ path: 'users', version: '1' })
+        let controller_re = Regex::new(r"@Controller\s*\(\s*\{([^}]*)\}\s*\)").ok()?;
+        let version_re = Regex::new(r#"version\s*:\s*\[?\s*(?:'([^']+)'|"([^"]+)")"#).ok()?;
+
+        let options = controller_re.captures(content)?.get(1)?.as_str().to_string();
+        let cap = version_re.captures(&options)?;
+        cap.get(1).or_else(|| cap.get(2)).map(|m| m.as_str().to_string())
+    }
+
     fn extract_controller_base_path(&self, content: &str) -> String {
-        // Match both single and double quotes
-        let controller_re = Regex::new(r#"@Controller\s*\(\s*(?:'([^']+)'|"([^"]+)")\s*\)"#).ok();
+        // Match both single and double quotes, or the object form @Controller({ path: '...' })
+        let controller_re = Regex::new(r#"@Controller\s*\(\s*(?:\{[^}]*?path\s*:\s*)?(?:'([^']+)'|"([^"]+)")"#).ok();
         
         if let Some(re) = controller_re {
             if let Some(cap) = re.captures(content) {
@@ -260,9 +447,17 @@ impl NestJSParser {
         // Find the next method definition: async methodName(...): Promise<Type> or methodName(...)
         // Skip comments and other decorators
         let method_re = Regex::new(r"(?:async\s+)?(\w+)\s*\(([^)]*)\)(?:\s*:\s*Promise\s*<\s*(\w+)\s*>)?").ok()?;
+        let return_type_re = Regex::new(r"^\s*:\s*Promise\s*<\s*(\w+)\s*>").ok()?;
         
         // Extract @HttpCode decorator if present
         let http_code = self.extract_http_code(remaining);
+
+        // @Version may sit above or below the HTTP method decorator
+        let decorators_start = content[..decorator_start]
+            .rfind(['}', ';'])
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        let version_re = Regex::new(r#"@Version\s*\(\s*\[?\s*(?:'([^']+)'|"([^"]+)")"#).ok()?;
         
         // Try to find method, but skip if it looks like it's part of a decorator or comment
         for method_cap in method_re.captures_iter(remaining) {
@@ -278,22 +473,37 @@ impl NestJSParser {
             
             if let (Some(name_match), Some(params_match)) = (method_cap.get(1), method_cap.get(2)) {
                 let method_name = name_match.as_str().to_string();
-                let params = params_match.as_str().to_string();
+                // The pattern stops at the first ')'; take the whole list so decorators with
+                // arguments (`@Param('id')`, `@Headers('authorization')`) are kept
+                let open = decorator_start + params_match.start() - 1;
+                let close = query_smells::matching_close(content, open, '(', ')');
+                let params = close
+                    .map(|close| content[open + 1..close].to_string())
+                    .unwrap_or_else(|| params_match.as_str().to_string());
                 
                 // Extract return type from Promise<Type>
-                let return_type = method_cap.get(3).map(|m| m.as_str().to_string());
+                let return_type = match close {
+                    Some(close) => return_type_re.captures(&content[close + 1..]).map(|c| c[1].to_string()),
+                    None => method_cap.get(3).map(|m| m.as_str().to_string()),
+                };
 
                 // Extract method-level authentication
                 // Look for @UseGuards between decorator and method
                 let method_start = decorator_start + method_pos;
                 let method_auth = self.extract_method_auth(content, method_start);
 
+                let version = version_re.captures(&content[decorators_start..method_start])
+                    .and_then(|c| c.get(1).or_else(|| c.get(2)))
+                    .map(|m| m.as_str().to_string());
+
                 return Some(MethodInfo {
                     method_name,
                     params,
                     method_auth,
                     return_type,
                     http_code,
+                    version,
+                    start: method_start,
                 });
             }
         }
@@ -308,7 +518,7 @@ impl NestJSParser {
         method_auth
     }
 
-    fn build_full_path(&self, base_path: &str, method_path: &str) -> String {
+    fn build_full_path(&self, base_path: &str, method_path: &str, version: Option<&str>) -> String {
         let base = if base_path.is_empty() {
             String::new()
         } else {
@@ -335,6 +545,18 @@ impl NestJSParser {
             format!("{}{}", base, method)
         };
 
+        // URI versioning inserts /v{version} between the global prefix and the route
+        if let (Some(versioning), Some(version)) = (self.versioning.as_ref(), version) {
+            if versioning.uri {
+                let segment = format!("/{}{}", versioning.prefix, version.trim_start_matches(['v', 'V']));
+                full_path = if full_path == "/" {
+                    segment
+                } else {
+                    format!("{}{}", segment, full_path)
+                };
+            }
+        }
+
         // Add global prefix if present
         if let Some(ref prefix) = self.global_prefix {
             if !prefix.is_empty() {
@@ -359,38 +581,42 @@ impl NestJSParser {
         &self,
         path: &str,
         method: &str,
-        action: &str,
-        params_str: &str,
+        controller: &str,
+        method_info: &MethodInfo,
         file_path: &Path,
         controller_auth: &Authentication,
-        method_auth: &Authentication,
-        return_type: Option<&str>,
-        http_code: Option<u16>,
     ) -> Result<ScannedEndpoint, String> {
+        let action = method_info.method_name.as_str();
+
         // Use method-level auth if present, otherwise use controller-level
-        let auth = if method_auth.required {
-            method_auth.clone()
+        let auth = if method_info.method_auth.required {
+            method_info.method_auth.clone()
         } else {
             controller_auth.clone()
         };
 
         // Extract parameters from method signature
-        let parameters = self.extract_method_parameters(params_str, method)?;
+        let parameters = self.extract_method_parameters(&method_info.params, method)?;
 
         // Extract path parameters from path string
         let path_params = self.parse_path_parameters(path);
         
-        // Combine all parameters
+        // Combine all parameters; a typed @Param replaces the path placeholder of the same name
         let mut all_params = path_params;
-        all_params.extend(parameters);
+        for param in parameters {
+            match all_params.iter_mut().find(|p| p.source == "path" && param.source == "path" && p.name == param.name) {
+                Some(existing) => *existing = param,
+                None => all_params.push(param),
+            }
+        }
 
         // Build response definitions
-        let responses = self.build_responses(method, return_type, http_code, &auth);
+        let responses = self.build_responses(method, method_info.return_type.as_deref(), method_info.http_code, &auth);
 
         Ok(ScannedEndpoint {
             path: path.to_string(),
             method: method.to_string(),
-            controller: String::new(), // Will be filled later if needed
+            controller: controller.to_string(),
             action: action.to_string(),
             file_path: file_path.to_string_lossy().to_string(),
             line_number: 0,
@@ -404,6 +630,9 @@ impl NestJSParser {
             authentication: auth,
             authorization: Authorization::default(),
             responses,
+            version: None,
+            category: (self.category_fn)(path, controller),
+            warnings: Vec::new(),
         })
     }
 
@@ -414,6 +643,9 @@ impl NestJSParser {
     ) -> Result<Vec<EndpointParameter>, String> {
         let mut params = Vec::new();
 
+        let params_str = strip_request_objects(params_str)?;
+        let params_str = params_str.as_str();
+
         // Parse parameters: @Body() dto: DtoType, @Param('id') id: number, etc.
         
         // Pattern 1: @Body() dto: DtoType
@@ -504,6 +736,39 @@ impl NestJSParser {
             }
         }
 
+        // Pattern 4: @Headers('authorization') auth: string; a bare @Headers() takes all headers
+        let header_pattern = r#"@Headers\s*\(\s*(?:'([^']+)'|"([^"]+)")?\s*\)\s+(\w+)\s*(\?)?\s*:\s*(\w+)"#;
+        if let Ok(header_re) = Regex::new(header_pattern) {
+            for cap in header_re.captures_iter(params_str) {
+                let optional = cap.get(4).is_some();
+                match cap.get(1).or_else(|| cap.get(2)) {
+                    Some(header_name) => {
+                        let header_name = header_name.as_str();
+                        let api_type = self.map_typescript_type(&cap[5]);
+                        params.push(EndpointParameter {
+                            name: header_name.to_string(),
+                            example: header_example(header_name)
+                                .or_else(|| ExampleGenerator::generate_example(&api_type, header_name, &None)),
+                            default_value: ExampleGenerator::generate_default(&api_type),
+                            param_type: api_type,
+                            source: "header".to_string(),
+                            required: !optional,
+                            validation: None,
+                        });
+                    }
+                    None => params.push(EndpointParameter {
+                        name: cap[3].to_string(),
+                        param_type: "object".to_string(),
+                        source: "header".to_string(),
+                        required: false,
+                        validation: None,
+                        example: ExampleGenerator::generate_example("object", &cap[3], &None),
+                        default_value: ExampleGenerator::generate_default("object"),
+                    }),
+                }
+            }
+        }
+
         Ok(params)
     }
 
@@ -512,48 +777,36 @@ impl NestJSParser {
         let dto_file_path = self.dto_files_cache.get(dto_class_name).cloned();
 
         if let Some(file_path) = dto_file_path {
-            if let Ok(dto_content) = fs::read_to_string(&file_path) {
-                return self.extract_dto_properties(&dto_content);
+            if let Some(lines) = source_lines(&file_path) {
+                return self.extract_dto_properties(lines);
             }
         }
 
         Ok(Vec::new())
     }
 
-    fn extract_dto_properties(&self, dto_content: &str) -> Result<Vec<EndpointParameter>, String> {
+    fn extract_dto_properties(&self, lines: impl Iterator<Item = String>) -> Result<Vec<EndpointParameter>, String> {
         let mut params = Vec::new();
 
-        // Find all class properties with decorators
-        // Better approach: find property declarations and their decorators
-        let lines: Vec<&str> = dto_content.lines().collect();
-        let mut i = 0;
-        
-        while i < lines.len() {
-            let line = lines[i].trim();
-            
+        // Decorators (possibly spanning several lines, with blank or comment lines between them)
+        // seen since the last other line; they belong to the next property declaration
+        let mut decorators: Vec<String> = Vec::new();
+        for line in lines {
+            let line = line.trim();
+
             // Check if this line starts a property declaration (has : and is not a comment)
             if line.contains(':') && !line.starts_with("//") && !line.starts_with("*") && !line.starts_with("/**") {
-                // Look backwards for decorators (can span multiple lines)
-                let mut decorators = Vec::new();
-                let mut j = i.saturating_sub(1);
-                while j < lines.len() && (lines[j].trim().starts_with('@') || lines[j].trim().is_empty() || lines[j].trim().starts_with("//")) {
-                    let trimmed = lines[j].trim();
-                    if trimmed.starts_with('@') {
-                        decorators.insert(0, trimmed);
-                    }
-                    if j == 0 {
-                        break;
-                    }
-                    j = j.saturating_sub(1);
-                }
-                
-                // Extract property name and type
-                if let Some(property_info) = self.parse_property_line(line, &decorators) {
+                let decorator_refs: Vec<&str> = decorators.iter().map(String::as_str).collect();
+                if let Some(property_info) = self.parse_property_line(line, &decorator_refs) {
                     params.push(property_info);
                 }
             }
-            
-            i += 1;
+
+            if line.starts_with('@') {
+                decorators.push(line.to_string());
+            } else if !line.is_empty() && !line.starts_with("//") {
+                decorators.clear();
+            }
         }
 
         Ok(params)
@@ -579,6 +832,9 @@ impl NestJSParser {
         let mut required = !is_optional_ts;
         let mut validation_rules = Vec::new();
         let mut example_value: Option<Value> = None;
+        let mut enum_values: Option<Vec<Value>> = None;
+        let is_enum_re = Regex::new(r"@IsEnum\s*\(\s*(\w+)").ok()?;
+        let api_enum_re = Regex::new(r"\benum\s*:\s*(?:\[([^\]]*)\]|(\w+))").ok()?;
 
         for decorator in decorators {
             if decorator.contains("@IsOptional") {
@@ -604,6 +860,10 @@ impl NestJSParser {
             } else if decorator.contains("@IsEnum") {
                 param_type = "string".to_string();
                 validation_rules.push("enum".to_string());
+                // Resolve the members: @IsEnum(OrderStatus)
+                if let Some(enum_cap) = is_enum_re.captures(decorator) {
+                    enum_values = enum_values.or_else(|| self.enum_values_cache.get(&enum_cap[1]).cloned());
+                }
             } else if decorator.contains("@Min(") {
                 // Extract min value: @Min(1)
                 if let Ok(min_re) = Regex::new(r"@Min\s*\(\s*(\d+)\s*\)") {
@@ -652,6 +912,23 @@ impl NestJSParser {
                     }
                 }
                 
+                // Extract enum values: @ApiProperty({ enum: ['PENDING', 'PAID'] }) or { enum: OrderStatus }
+                if let Some(enum_cap) = api_enum_re.captures(decorator) {
+                    let values = match (enum_cap.get(1), enum_cap.get(2)) {
+                        (Some(list), _) => Some(
+                            list.as_str()
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|v| !v.is_empty())
+                                .filter_map(|v| self.parse_example_value(v))
+                                .collect(),
+                        ),
+                        (None, Some(name)) => self.enum_values_cache.get(name.as_str()).cloned(),
+                        _ => None,
+                    };
+                    enum_values = enum_values.or(values);
+                }
+
                 // Check if it's ApiPropertyOptional
                 if decorator.contains("@ApiPropertyOptional") {
                     required = false;
@@ -659,6 +936,18 @@ impl NestJSParser {
             }
         }
 
+        // Carry the allowed values as an `in:` rule, the same shape the OpenAPI import produces
+        if let Some(values) = enum_values.filter(|values| !values.is_empty()) {
+            if values.iter().all(Value::is_number) {
+                param_type = "number".to_string();
+            }
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+                .collect();
+            validation_rules.push(format!("in:{}", values.join(",")));
+        }
+
         let validation = if validation_rules.is_empty() {
             None
         } else {
@@ -750,39 +1039,50 @@ impl NestJSParser {
     }
 
     fn detect_authentication(&self, content: &str, _is_controller_level: bool) -> Authentication {
-        // Check for @UseGuards(JwtAuthGuard) or similar
-        let guard_pattern = r"@UseGuards\s*\(\s*(\w+AuthGuard)\s*\)";
-        if let Ok(guard_re) = Regex::new(guard_pattern) {
-            if guard_re.is_match(content) {
-                return Authentication {
-                    required: true,
-                    auth_type: Some("JWT".to_string()),
-                };
+        let required = |auth_type: &str| Authentication {
+            required: true,
+            auth_type: Some(auth_type.to_string()),
+        };
+
+        // Check guards: @UseGuards(ApiKeyAuthGuard), @UseGuards(AuthGuard('basic'), RolesGuard), ...
+        let mut generic_guard = false;
+        if let (Ok(guards_re), Ok(guard_re)) = (
+            Regex::new(r"@UseGuards\s*\(([^)]*\)?)\s*\)"),
+            Regex::new(r#"(\w*Guard)\b(?:\s*\(\s*['"]([^'"]+)['"]\s*\))?"#),
+        ) {
+            for guards in guards_re.captures_iter(content) {
+                let list = guards.get(1).map(|m| m.as_str()).unwrap_or("");
+                for guard in guard_re.captures_iter(list) {
+                    let name = guard.get(1).map(|m| m.as_str()).unwrap_or("");
+                    // Passport's AuthGuard('strategy') names the scheme in its argument
+                    let hint = guard.get(2).map(|m| m.as_str()).unwrap_or(name);
+                    if let Some(auth_type) = auth_type_from_name(hint) {
+                        return required(auth_type);
+                    }
+                    if name.contains("Auth") {
+                        generic_guard = true;
+                    }
+                }
             }
         }
 
-        // Check for @ApiBearerAuth() which also indicates auth
-        if content.contains("@ApiBearerAuth") {
-            return Authentication {
-                required: true,
-                auth_type: Some("JWT".to_string()),
-            };
+        // Swagger security decorators
+        if content.contains("@ApiBasicAuth") {
+            return required("basic");
+        }
+        if content.contains("@ApiSecurity") {
+            return required("api_key");
+        }
+        if content.contains("@ApiOAuth2") {
+            return required("oauth2");
         }
 
-        Authentication::default()
-    }
-
-    fn deduplicate_endpoints(&self, endpoints: Vec<ScannedEndpoint>) -> Vec<ScannedEndpoint> {
-        let mut seen = HashMap::new();
-
-        for endpoint in endpoints {
-            let key = format!("{}:{}", endpoint.method, endpoint.path);
-            if !seen.contains_key(&key) {
-                seen.insert(key, endpoint);
-            }
+        // A generic auth guard or @ApiBearerAuth() defaults to JWT
+        if generic_guard || content.contains("@ApiBearerAuth") {
+            return required("JWT");
         }
 
-        seen.into_values().collect()
+        Authentication::default()
     }
 
     // ============================================================================
@@ -797,7 +1097,7 @@ impl NestJSParser {
         ];
 
         for main_path in main_paths {
-            if let Ok(content) = fs::read_to_string(&main_path) {
+            if let Some(content) = self.read_main_file(&main_path) {
                 // Check for useGlobalInterceptors with TransformInterceptor
                 if content.contains("useGlobalInterceptors") && 
                    (content.contains("TransformInterceptor") || content.contains("transform")) {
@@ -929,23 +1229,23 @@ impl NestJSParser {
     fn build_response_schema(&self, type_name: &str) -> Option<ResponseSchema> {
         // Try to find in response DTO cache first
         if let Some(file_path) = self.response_dto_files_cache.get(type_name) {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                return self.parse_response_dto_content(&content, type_name);
+            if let Some(lines) = source_lines(file_path) {
+                return self.parse_response_dto_content(lines, type_name);
             }
         }
 
         // Try to find in entity cache
         if let Some(file_path) = self.entity_files_cache.get(type_name) {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                return self.parse_entity_content(&content, type_name);
+            if let Some(lines) = source_lines(file_path) {
+                return self.parse_entity_content(lines, type_name);
             }
         }
 
         // Try without "Dto" suffix
         let type_without_dto = type_name.trim_end_matches("Dto").trim_end_matches("Response");
         if let Some(file_path) = self.entity_files_cache.get(type_without_dto) {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                return self.parse_entity_content(&content, type_without_dto);
+            if let Some(lines) = source_lines(file_path) {
+                return self.parse_entity_content(lines, type_without_dto);
             }
         }
 
@@ -981,8 +1281,8 @@ impl NestJSParser {
         let result = {
             // Try to find in response DTO cache first
             if let Some(file_path) = self.response_dto_files_cache.get(type_name) {
-                if let Ok(content) = fs::read_to_string(file_path) {
-                    if let Some(mut schema) = self.parse_response_dto_content(&content, type_name) {
+                if let Some(lines) = source_lines(file_path) {
+                    if let Some(mut schema) = self.parse_response_dto_content(lines, type_name) {
                         // Recursively parse nested properties
                         schema.properties = self.parse_nested_properties(
                             &schema.properties,
@@ -997,8 +1297,8 @@ impl NestJSParser {
 
             // Try to find in entity cache
             if let Some(file_path) = self.entity_files_cache.get(type_name) {
-                if let Ok(content) = fs::read_to_string(file_path) {
-                    if let Some(mut schema) = self.parse_entity_content(&content, type_name) {
+                if let Some(lines) = source_lines(file_path) {
+                    if let Some(mut schema) = self.parse_entity_content(lines, type_name) {
                         // Recursively parse nested properties
                         schema.properties = self.parse_nested_properties(
                             &schema.properties,
@@ -1013,8 +1313,8 @@ impl NestJSParser {
 
             // Try in DTO cache (for request DTOs that might be used in responses)
             if let Some(file_path) = self.dto_files_cache.get(type_name) {
-                if let Ok(content) = fs::read_to_string(file_path) {
-                    if let Some(mut schema) = self.parse_response_dto_content(&content, type_name) {
+                if let Some(lines) = source_lines(file_path) {
+                    if let Some(mut schema) = self.parse_response_dto_content(lines, type_name) {
                         schema.properties = self.parse_nested_properties(
                             &schema.properties,
                             max_depth - 1,
@@ -1030,8 +1330,8 @@ impl NestJSParser {
             let type_without_dto = type_name.trim_end_matches("Dto").trim_end_matches("Response");
             if type_without_dto != type_name {
                 if let Some(file_path) = self.entity_files_cache.get(type_without_dto) {
-                    if let Ok(content) = fs::read_to_string(file_path) {
-                        if let Some(mut schema) = self.parse_entity_content(&content, type_without_dto) {
+                    if let Some(lines) = source_lines(file_path) {
+                        if let Some(mut schema) = self.parse_entity_content(lines, type_without_dto) {
                             schema.properties = self.parse_nested_properties(
                                 &schema.properties,
                                 max_depth - 1,
@@ -1183,11 +1483,10 @@ impl NestJSParser {
 
         for pattern_str in patterns {
             if let Ok(entries) = glob(&pattern_str) {
-                for entry in entries.flatten() {
-                    if let Ok(content) = fs::read_to_string(&entry) {
-                        // Extract all class names from file (can have multiple)
-                        self.extract_all_dto_classes(&content, &entry.to_string_lossy().to_string());
-                    }
+                let paths = entries.flatten().collect();
+                for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                    // Extract all class names from file (can have multiple)
+                    self.extract_all_dto_classes(&content, &entry.to_string_lossy().to_string());
                 }
             }
         }
@@ -1214,12 +1513,11 @@ impl NestJSParser {
         let pattern_str = format!("{}/**/*.entity.ts", self.project_path.to_string_lossy());
 
         if let Ok(entries) = glob(&pattern_str) {
-            for entry in entries.flatten() {
-                if let Ok(content) = fs::read_to_string(&entry) {
-                    if let Some(entity_class) = self.extract_entity_class(&content) {
-                        self.entity_files_cache
-                            .insert(entity_class, entry.to_string_lossy().to_string());
-                    }
+            let paths = entries.flatten().collect();
+            for (entry, content) in read_source_files(paths, self.max_file_size, self.workers, &mut self.warnings) {
+                if let Some(entity_class) = self.extract_entity_class(&content) {
+                    self.entity_files_cache
+                        .insert(entity_class, entry.to_string_lossy().to_string());
                 }
             }
         }
@@ -1247,8 +1545,8 @@ impl NestJSParser {
     }
 
     /// Parse response DTO content to extract schema
-    fn parse_response_dto_content(&self, content: &str, type_name: &str) -> Option<ResponseSchema> {
-        let properties = self.extract_properties_from_content(content);
+    fn parse_response_dto_content(&self, lines: impl Iterator<Item = String>, type_name: &str) -> Option<ResponseSchema> {
+        let properties = self.extract_properties_from_lines(lines);
         
         // Parse nested properties with depth limit
         let mut visited = vec![type_name.to_string()];
@@ -1264,8 +1562,8 @@ impl NestJSParser {
     }
 
     /// Parse entity content to extract schema
-    fn parse_entity_content(&self, content: &str, type_name: &str) -> Option<ResponseSchema> {
-        let properties = self.extract_properties_from_content(content);
+    fn parse_entity_content(&self, lines: impl Iterator<Item = String>, type_name: &str) -> Option<ResponseSchema> {
+        let properties = self.extract_properties_from_lines(lines);
         
         // Parse nested properties with depth limit
         let mut visited = vec![type_name.to_string()];
@@ -1280,39 +1578,31 @@ impl NestJSParser {
         })
     }
 
-    /// Extract properties from DTO or Entity content
-    fn extract_properties_from_content(&self, content: &str) -> Vec<ResponseProperty> {
+    /// Extract properties from the lines of a DTO or Entity file
+    fn extract_properties_from_lines(&self, lines: impl Iterator<Item = String>) -> Vec<ResponseProperty> {
         let mut properties = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-        
-        let mut i = 0;
-        while i < lines.len() {
-            let line = lines[i].trim();
+
+        // Decorators seen since the last non-blank, non-decorator line
+        let mut decorators: Vec<String> = Vec::new();
+        for line in lines {
+            let line = line.trim();
             
             // Check for property declaration with type
             if line.contains(':') && !line.starts_with("//") && !line.starts_with("*") 
                && !line.starts_with("/**") && !line.starts_with("constructor") 
                && !line.starts_with("async") && !line.starts_with("private") 
                && !line.starts_with("protected") && !line.starts_with("@") {
-                
-                // Look backwards for decorators
-                let mut decorators = Vec::new();
-                let mut j = i.saturating_sub(1);
-                while j < lines.len() && (lines[j].trim().starts_with('@') || lines[j].trim().is_empty()) {
-                    let trimmed = lines[j].trim();
-                    if trimmed.starts_with('@') {
-                        decorators.insert(0, trimmed);
-                    }
-                    if j == 0 { break; }
-                    j = j.saturating_sub(1);
-                }
-                
-                if let Some(prop) = self.parse_response_property_line(line, &decorators) {
+                let decorator_refs: Vec<&str> = decorators.iter().map(String::as_str).collect();
+                if let Some(prop) = self.parse_response_property_line(line, &decorator_refs) {
                     properties.push(prop);
                 }
             }
-            
-            i += 1;
+
+            if line.starts_with('@') {
+                decorators.push(line.to_string());
+            } else if !line.is_empty() {
+                decorators.clear();
+            }
         }
         
         properties
@@ -1459,3 +1749,332 @@ impl NestJSParser {
         (json_type, None, format, type_name)
     }
 }
+
+/// Auth header template (`Bearer {{ authToken }}`, `{{ apiKey }}`) for a header the handler reads
+fn header_example(name: &str) -> Option<Value> {
+    ["bearer", "apikey"]
+        .iter()
+        .flat_map(|kind| auth_header_templates(Some(kind)))
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, template)| Value::String(template))
+}
+
+/// Remove @Req()/@Res()/@Next() parameters, with or without decorator arguments such as
+/// `@Res({ passthrough: true })`; they hand over framework objects, not request inputs
+fn strip_request_objects(params_str: &str) -> Result<String, String> {
+    let request_objects_re = Regex::new(r"@(?:Req|Request|Res|Response|Next)\s*\([^)]*\)\s*\w+\s*\??\s*(?::\s*[\w.]+)?")
+        .map_err(|e| format!("Regex error: {}", e))?;
+    Ok(request_objects_re.replace_all(params_str, "").into_owned())
+}
+
+/// Member values of every `enum Name { ... }` in `content`. Members without an initializer
+/// count up from the previous numeric value, as TypeScript numbers them.
+fn parse_enum_declarations(content: &str) -> Vec<(String, Vec<Value>)> {
+    let mut enums = Vec::new();
+    let Ok(enum_re) = Regex::new(r"\benum\s+(\w+)\s*\{") else {
+        return enums;
+    };
+    let Ok(member_re) = Regex::new(r#"^['"]?\w+['"]?\s*(?:=\s*(?:'([^']*)'|"([^"]*)"|(-?\d+)))?$"#) else {
+        return enums;
+    };
+
+    for cap in enum_re.captures_iter(content) {
+        let open = cap.get(0).map_or(0, |m| m.end() - 1);
+        let Some(close) = query_smells::matching_close(content, open, '{', '}') else {
+            continue;
+        };
+        let body: String = content[open + 1..close]
+            .lines()
+            .map(|line| line.split("//").next().unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut next_number: i64 = 0;
+        let mut values = Vec::new();
+        for member in body.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+            let Some(member_cap) = member_re.captures(member) else {
+                continue;
+            };
+            if let Some(text) = member_cap.get(1).or_else(|| member_cap.get(2)) {
+                values.push(Value::String(text.as_str().to_string()));
+            } else {
+                let number = member_cap.get(3).and_then(|n| n.as_str().parse().ok()).unwrap_or(next_number);
+                values.push(Value::Number(number.into()));
+                next_number = number + 1;
+            }
+        }
+        enums.push((cap[1].to_string(), values));
+    }
+    enums
+}
+
+/// Map a guard class or passport strategy name to an auth type
+fn auth_type_from_name(name: &str) -> Option<&'static str> {
+    let lower = name.to_lowercase().replace(['-', '_'], "");
+    if lower.contains("apikey") {
+        Some("api_key")
+    } else if lower.contains("basic") {
+        Some("basic")
+    } else if lower.contains("local") {
+        Some("local")
+    } else if lower.contains("oauth") {
+        Some("oauth2")
+    } else if lower.contains("jwt") || lower.contains("bearer") {
+        Some("JWT")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_type(content: &str) -> Option<String> {
+        NestJSParser::new(PathBuf::new())
+            .detect_authentication(content, true)
+            .auth_type
+    }
+
+    #[test]
+    fn test_detect_authentication_by_guard() {
+        assert_eq!(auth_type("@UseGuards(JwtAuthGuard)\n@Controller('users')").as_deref(), Some("JWT"));
+        assert_eq!(auth_type("@UseGuards(ApiKeyAuthGuard)\n@Controller('keys')").as_deref(), Some("api_key"));
+        assert_eq!(auth_type("@UseGuards(ApiKeyGuard, RolesGuard)").as_deref(), Some("api_key"));
+        assert_eq!(auth_type("@UseGuards(BasicAuthGuard)").as_deref(), Some("basic"));
+        assert_eq!(auth_type("@UseGuards(LocalAuthGuard)\n@Post('login')").as_deref(), Some("local"));
+        assert_eq!(auth_type("@UseGuards(AuthGuard('api-key'))").as_deref(), Some("api_key"));
+        assert_eq!(auth_type("@UseGuards(AuthGuard('google-oauth'))").as_deref(), Some("oauth2"));
+        assert_eq!(auth_type("@UseGuards(SessionAuthGuard)").as_deref(), Some("JWT"));
+        assert_eq!(auth_type("@UseGuards(ThrottlerGuard)"), None);
+    }
+
+    #[test]
+    fn test_detect_authentication_by_swagger_decorator() {
+        assert_eq!(auth_type("@ApiBearerAuth()\n@Controller('me')").as_deref(), Some("JWT"));
+        assert_eq!(auth_type("@ApiSecurity('x-api-key')").as_deref(), Some("api_key"));
+        assert_eq!(auth_type("@ApiBasicAuth()").as_deref(), Some("basic"));
+        assert_eq!(auth_type("@UseGuards(AuthGuard)\n@ApiBasicAuth()").as_deref(), Some("basic"));
+        assert_eq!(auth_type("@Controller('public')"), None);
+    }
+
+    #[test]
+    fn test_endpoint_categories() {
+        let content = "@Controller('api/v1/orders')\nexport class OrdersController {\n  @Get(':id')\n  findOne() {}\n}\n\n";
+        let endpoints = NestJSParser::new(PathBuf::new())
+            .parse_controller_content(content, Path::new("orders.controller.ts"))
+            .unwrap();
+        assert_eq!(endpoints[0].controller, "OrdersController");
+        assert_eq!(endpoints[0].category.as_deref(), Some("orders"));
+
+        let root = "@Controller()\nexport class HealthController {\n  @Get()\n  check() {}\n}\n";
+        let endpoints = NestJSParser::new(PathBuf::new())
+            .with_category_fn(|_, controller| Some(format!("custom-{}", controller)))
+            .parse_controller_content(root, Path::new("health.controller.ts"))
+            .unwrap();
+        assert_eq!(endpoints[0].category.as_deref(), Some("custom-HealthController"));
+    }
+
+    #[test]
+    fn test_n_plus_one_warnings_per_method() {
+        let content = r#"@Controller('orders')
+export class OrdersController {
+  @Get()
+  async findAll(@Query('ids') ids: string[]): Promise<Order[]> {
+    return Promise.all(ids.map((id) => this.ordersRepository.findOneBy({ id })));
+  }
+
+  @Get(':id')
+  async findOne(@Param('id') id: string): Promise<Order> {
+    return this.ordersRepository.findOneBy({ id });
+  }
+}
+"#;
+        let endpoints = NestJSParser::new(PathBuf::new())
+            .parse_controller_content(content, Path::new("orders.controller.ts"))
+            .unwrap();
+        let warnings = |path: &str| endpoints.iter().find(|e| e.path == path).unwrap().warnings.clone();
+        assert_eq!(warnings("/orders").len(), 1);
+        assert!(warnings("/orders/:id").is_empty());
+    }
+
+    #[test]
+    fn test_header_and_request_object_parameters() {
+        let content = r#"@Controller('orders')
+export class OrdersController {
+  @Put(':id')
+  async update(
+    @Param('id') id: number,
+    @Headers('authorization') auth: string,
+    @Headers('x-request-id') requestId?: string,
+    @Req() req: Request,
+    @Res({ passthrough: true }) res: Response,
+  ): Promise<Order> {
+    return this.ordersService.update(id);
+  }
+
+  @Get()
+  findAll(@Headers() headers: Record<string, string>, @Request() request) {
+    return [];
+  }
+}
+"#;
+        let endpoints = NestJSParser::new(PathBuf::new())
+            .parse_controller_content(content, Path::new("orders.controller.ts"))
+            .unwrap();
+
+        let update = endpoints.iter().find(|e| e.method == "PUT").unwrap();
+        let names: Vec<(&str, &str)> = update.parameters.iter().map(|p| (p.name.as_str(), p.source.as_str())).collect();
+        assert_eq!(names, vec![("id", "path"), ("authorization", "header"), ("x-request-id", "header")]);
+        assert_eq!(update.parameters[0].param_type, "number");
+        assert_eq!(update.parameters[1].example, Some(Value::String("Bearer {{ authToken }}".to_string())));
+        assert!(update.parameters[1].required);
+        assert!(!update.parameters[2].required);
+        assert_eq!(update.action, "update");
+
+        let find_all = endpoints.iter().find(|e| e.method == "GET").unwrap();
+        assert_eq!(find_all.parameters.len(), 1);
+        assert_eq!(find_all.parameters[0].name, "headers");
+        assert_eq!(find_all.parameters[0].source, "header");
+        assert_eq!(find_all.parameters[0].param_type, "object");
+        assert!(!find_all.parameters[0].required);
+    }
+
+    #[test]
+    fn test_strip_request_objects_with_arguments() {
+        let stripped = strip_request_objects(
+            "@Param('id') id: number, @Req() req: Request, @Res({ passthrough: true }) res: Response, @Next( ) next",
+        )
+        .unwrap();
+        assert_eq!(stripped, "@Param('id') id: number, , , ");
+    }
+
+    #[test]
+    fn test_parse_enum_declarations() {
+        let content = r#"export enum OrderStatus {
+  PENDING = 'pending', // awaiting payment
+  PAID = "paid",
+}
+
+export const enum Priority { Low, Normal = 5, High }
+"#;
+        let enums = parse_enum_declarations(content);
+        assert_eq!(enums[0], ("OrderStatus".to_string(), vec![Value::from("pending"), Value::from("paid")]));
+        assert_eq!(enums[1], ("Priority".to_string(), vec![Value::from(0), Value::from(5), Value::from(6)]));
+    }
+
+    #[test]
+    fn test_enum_values_from_dto_decorators() {
+        let root = std::env::temp_dir().join(format!("lookapi-nest-enum-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/orders/dto")).unwrap();
+        fs::write(
+            root.join("src/orders/order-status.enum.ts"),
+            "export enum OrderStatus {\n  PENDING = 'pending',\n  PAID = 'paid',\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/orders/dto/update-order.dto.ts"),
+            r#"export class UpdateOrderDto {
+  @IsEnum(OrderStatus)
+  status: OrderStatus;
+
+  @ApiProperty({ enum: ['EXPRESS', 'STANDARD'] })
+  shipping: string;
+
+  @ApiProperty({ enum: [1, 2, 3] })
+  @IsOptional()
+  priority?: number;
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/orders/orders.controller.ts"),
+            "@Controller('orders')\nexport class OrdersController {\n  @Patch(':id')\n  update(@Param('id') id: string, @Body() dto: UpdateOrderDto) {\n    return dto;\n  }\n}\n",
+        )
+        .unwrap();
+
+        let endpoints = parse(&root);
+        let param = |name: &str| endpoints[0].parameters.iter().find(|p| p.name == name).unwrap().clone();
+
+        let status = param("status");
+        assert_eq!(status.validation, Some(vec!["enum".to_string(), "in:pending,paid".to_string()]));
+        assert_eq!(status.example, Some(Value::from("pending")));
+        assert_eq!(param("shipping").example, Some(Value::from("EXPRESS")));
+        let priority = param("priority");
+        assert_eq!(priority.param_type, "number");
+        assert_eq!(priority.example, Some(Value::from(1)));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_dto_properties_from_streamed_lines() {
+        let dto = "export class CreateUserDto {\n  @IsEmail()\n  // Login name\n\n  email: string;\n\n  nickname?: string;\n}\n";
+        let params = NestJSParser::new(PathBuf::new())
+            .extract_dto_properties(dto.lines().map(str::to_string))
+            .unwrap();
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "email");
+        assert!(params[0].required);
+        assert_eq!(params[0].validation, Some(vec!["email".to_string()]));
+        assert_eq!(params[1].name, "nickname");
+        assert!(!params[1].required);
+        assert_eq!(params[1].validation, None);
+    }
+
+    /// Project with `count` controllers, each exposing GET and POST routes
+    fn controller_fixture(name: &str, count: usize) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("lookapi-nest-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for i in 0..count {
+            let dir = root.join(format!("src/items{:03}", i));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join(format!("items{:03}.controller.ts", i)),
+                format!(
+                    "@Controller('items{i:03}')\nexport class Items{i:03}Controller {{\n  @Get()\n  findAll() {{\n    return [];\n  }}\n\n  @Post()\n  create() {{\n    return {{}};\n  }}\n}}\n",
+                    i = i
+                ),
+            )
+            .unwrap();
+        }
+        root
+    }
+
+    fn parse(root: &Path) -> Vec<ScannedEndpoint> {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(NestJSParser::new(root.to_path_buf()).parse_endpoints()).unwrap()
+    }
+
+    #[test]
+    fn test_parallel_parse_is_deterministic() {
+        let root = controller_fixture("order", 24);
+
+        let first = parse(&root);
+        let routes: Vec<String> = first.iter().map(|e| format!("{} {}", e.method, e.path)).collect();
+        assert_eq!(routes.len(), 48);
+        assert_eq!(routes[0], "GET /items000");
+        assert_eq!(routes[47], "POST /items023");
+
+        let again: Vec<String> = parse(&root).iter().map(|e| format!("{} {}", e.method, e.path)).collect();
+        assert_eq!(routes, again);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with --ignored --nocapture"]
+    fn bench_parse_500_controllers() {
+        let root = controller_fixture("bench", 500);
+
+        let start = std::time::Instant::now();
+        let endpoints = parse(&root);
+        println!("parsed {} endpoints from 500 controllers in {:?}", endpoints.len(), start.elapsed());
+        assert_eq!(endpoints.len(), 1000);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
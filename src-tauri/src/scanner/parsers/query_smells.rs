@@ -0,0 +1,239 @@
+//! Rough N+1 query detection for controller method bodies
+//!
+//! Flags a loop whose body makes a query call that uses the loop variable, e.g.
+//! `foreach ($users as $user) { $user->posts()->get(); }` in Laravel or
+//! `for (const id of ids) { await this.usersRepository.findOne(...id...) }` in NestJS.
+//! Purely informational; requiring the loop variable in the call keeps false positives low
+//! (a query that doesn't depend on the iteration is not flagged).
+
+use regex::Regex;
+
+/// Byte offset of the delimiter closing the one at `open`, skipping string literals
+pub fn matching_close(content: &str, open: usize, open_char: char, close_char: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+
+    for (pos, ch) in content[open..].char_indices() {
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == quote {
+                in_string = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => in_string = Some(ch),
+            c if c == open_char => depth += 1,
+            c if c == close_char => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + pos);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Body of the block opened by the first `{` at or after `from` (outside parentheses/generics)
+pub fn block_after(content: &str, from: usize) -> Option<&str> {
+    let mut depth = 0i32;
+    for (pos, ch) in content[from..].char_indices() {
+        match ch {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ';' if depth <= 0 => return None, // Abstract method or interface signature
+            '{' if depth <= 0 => {
+                let open = from + pos;
+                let close = matching_close(content, open, '{', '}')?;
+                return Some(&content[open + 1..close]);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Body of a loop whose header ends at `header_end` (just past its closing paren):
+/// a braced block, or the single statement that follows
+fn loop_body(content: &str, header_end: usize) -> &str {
+    let rest = &content[header_end..];
+    let trimmed = rest.trim_start();
+    if trimmed.starts_with('{') {
+        let open = header_end + (rest.len() - trimmed.len());
+        if let Some(close) = matching_close(content, open, '{', '}') {
+            return &content[open + 1..close];
+        }
+    }
+    &rest[..rest.find(';').unwrap_or(rest.len())]
+}
+
+/// Query calls (`Post::where()`) in statements of `body` that also mention a loop variable
+fn flag_statements(body: &str, loop_vars: &[String], query_re: &Regex, var_re: impl Fn(&str) -> Regex) -> Vec<String> {
+    let var_res: Vec<Regex> = loop_vars.iter().map(|v| var_re(v)).collect();
+    body.split(';')
+        .filter_map(|statement| {
+            let call = query_re.find(statement)?;
+            let name = call.as_str().trim_end_matches(|c: char| c == '(' || c == ')' || c.is_whitespace());
+            var_res.iter().any(|re| re.is_match(statement)).then(|| format!("{}()", name))
+        })
+        .collect()
+}
+
+fn push_warning(warnings: &mut Vec<String>, call: &str, loop_desc: &str) {
+    let warning = format!("Possible N+1 query: {} inside {}", call, loop_desc);
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
+
+/// N+1 warnings for a Laravel (PHP) method body: `foreach` loops whose body calls
+/// `::find*`, `::where` or `->get()` with the loop variable
+pub fn laravel_warnings(method_body: &str) -> Vec<String> {
+    let (foreach_re, query_re) = match (
+        Regex::new(r"foreach\s*\(([^;{]*?)\s+as\s+(?:\$(\w+)\s*=>\s*)?\$(\w+)\s*\)"),
+        Regex::new(r"\w+::(?:find\w*|where\w*)\s*\(|->get\s*\(\s*\)"),
+    ) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return Vec::new(),
+    };
+
+    let mut warnings = Vec::new();
+    for cap in foreach_re.captures_iter(method_body) {
+        let header_end = cap.get(0).map(|m| m.end()).unwrap_or(0);
+        let loop_vars: Vec<String> = [cap.get(2), cap.get(3)].into_iter().flatten().map(|m| m.as_str().to_string()).collect();
+        let body = loop_body(method_body, header_end);
+        let collection = cap.get(1).map(|m| m.as_str().trim()).unwrap_or("");
+        for call in flag_statements(body, &loop_vars, &query_re, |v| {
+            Regex::new(&format!(r"\${}\b", regex::escape(v))).expect("escaped identifier")
+        }) {
+            push_warning(&mut warnings, &call, &format!("foreach over {}", collection));
+        }
+    }
+    warnings
+}
+
+/// Identifiers bound by a loop or callback parameter list (`x`, `{ id, name }`, `[k, v]`, `x: Dto`)
+fn binding_names(binding: &str) -> Vec<String> {
+    binding
+        .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+        .split(',')
+        .flat_map(|part| {
+            // `x: Type` binds x; `{ id: alias }` binds alias
+            let part = part.trim().trim_start_matches(['{', '[']).trim_end_matches(['}', ']']).trim();
+            let name = if binding.contains('{') { part.rsplit(':').next() } else { part.split(':').next() };
+            name.map(|n| n.trim().trim_start_matches("...").to_string())
+        })
+        .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$'))
+        .collect()
+}
+
+/// N+1 warnings for a NestJS (TypeScript) method body: `for` loops and `map`/`forEach`
+/// callbacks whose body calls a repository (`fooRepository.x(`, `this.repo.x(`,
+/// `this.prisma.model.x(`) with the loop variable
+pub fn nestjs_warnings(method_body: &str) -> Vec<String> {
+    let (for_re, callback_re, query_re) = match (
+        Regex::new(r"for\s*\(\s*(?:const|let|var)\s+(\w+|\{[^}]*\}|\[[^\]]*\])\s*(?:of|in|=)"),
+        Regex::new(r"\.(map|forEach|flatMap)\s*\(\s*(?:async\s+)?(\([^)]*\)|\w+)\s*(?::\s*[\w<>\[\]]+\s*)?=>"),
+        Regex::new(r"(?:\bthis\.)?\w*(?:[Rr]epository|[Rr]epo|prisma\.\w+)\s*\.\s*\w+\s*\("),
+    ) {
+        (Ok(a), Ok(b), Ok(c)) => (a, b, c),
+        _ => return Vec::new(),
+    };
+    let var_re = |v: &str| Regex::new(&format!(r"(?:^|[^\w$.]){}\b", regex::escape(v))).expect("escaped identifier");
+
+    let mut warnings = Vec::new();
+    for cap in for_re.captures_iter(method_body) {
+        let whole = cap.get(0).expect("match");
+        let open = match method_body[whole.start()..].find('(') {
+            Some(offset) => whole.start() + offset,
+            None => continue,
+        };
+        let close = match matching_close(method_body, open, '(', ')') {
+            Some(close) => close,
+            None => continue,
+        };
+        let loop_vars = binding_names(cap.get(1).map(|m| m.as_str()).unwrap_or(""));
+        let body = loop_body(method_body, close + 1);
+        for call in flag_statements(body, &loop_vars, &query_re, var_re) {
+            push_warning(&mut warnings, &call, "a for loop");
+        }
+    }
+
+    for cap in callback_re.captures_iter(method_body) {
+        let whole = cap.get(0).expect("match");
+        let open = match method_body[whole.start()..].find('(') {
+            Some(offset) => whole.start() + offset,
+            None => continue,
+        };
+        let close = match matching_close(method_body, open, '(', ')') {
+            Some(close) => close,
+            None => continue,
+        };
+        let loop_vars = binding_names(cap.get(2).map(|m| m.as_str()).unwrap_or(""));
+        let body = &method_body[whole.end()..close];
+        let kind = cap.get(1).map(|m| m.as_str()).unwrap_or("map");
+        for call in flag_statements(body, &loop_vars, &query_re, var_re) {
+            push_warning(&mut warnings, &call, &format!("a .{}() callback", kind));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_laravel_warnings() {
+        let body = r#"
+            $users = User::all();
+            foreach ($users as $user) {
+                $user->posts_count = Post::where('user_id', $user->id)->count();
+                $settings = Setting::find(1);
+            }
+            foreach ($ids as $key => $id) $items[] = Item::findOrFail($id);
+            foreach ($rows as $row) { $total += $row['amount']; }
+        "#;
+        assert_eq!(
+            laravel_warnings(body),
+            vec![
+                "Possible N+1 query: Post::where() inside foreach over $users".to_string(),
+                "Possible N+1 query: Item::findOrFail() inside foreach over $ids".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nestjs_warnings() {
+        let body = r#"
+            const orders = await this.ordersRepository.find();
+            for (const order of orders) {
+                order.items = await this.itemsRepository.find({ where: { orderId: order.id } });
+                const config = await this.configRepository.findOne({ where: { key: 'x' } });
+            }
+            return Promise.all(ids.map(async (id) => this.usersRepository.findOneBy({ id })));
+            const names = orders.map(({ customer }) => customer.name);
+        "#;
+        assert_eq!(
+            nestjs_warnings(body),
+            vec![
+                "Possible N+1 query: this.itemsRepository.find() inside a for loop".to_string(),
+                "Possible N+1 query: this.usersRepository.findOneBy() inside a .map() callback".to_string(),
+            ]
+        );
+        assert!(nestjs_warnings("for (const o of orders) { total += o.amount; }").is_empty());
+    }
+
+    #[test]
+    fn test_block_after() {
+        let content = "async list(@Query() q: Dto): Promise<{ items: Item[] }> { return this.svc.list(q); } other() {}";
+        assert_eq!(block_after(content, 0), Some(" return this.svc.list(q); "));
+        assert_eq!(block_after("abstract find(id: string): Item;", 0), None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Response schema inference from a sample JSON body
+//!
+//! Used when the static parsers can't resolve an endpoint's return type: the user pastes a
+//! real response and every field found in it is recorded as a required property.
+
+use serde_json::{Map, Value};
+
+use super::types::{ResponseProperty, ResponseSchema};
+
+/// Infer a response schema from a sample JSON document
+pub fn infer_schema_from_sample(json: &str) -> Result<ResponseSchema, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse sample response: {}", e))?;
+    Ok(schema_for_value(&value))
+}
+
+fn schema_for_value(value: &Value) -> ResponseSchema {
+    match value {
+        Value::Object(map) => ResponseSchema {
+            schema_type: "object".to_string(),
+            properties: object_properties(map),
+            is_wrapped: map.contains_key("success") && map.contains_key("data"),
+            items_schema: None,
+            ref_name: None,
+        },
+        Value::Array(items) => ResponseSchema {
+            schema_type: "array".to_string(),
+            properties: vec![],
+            is_wrapped: false,
+            items_schema: merged_item(items).map(|item| Box::new(schema_for_value(&item))),
+            ref_name: None,
+        },
+        other => ResponseSchema {
+            schema_type: value_type(other).to_string(),
+            ..ResponseSchema::default()
+        },
+    }
+}
+
+fn object_properties(map: &Map<String, Value>) -> Vec<ResponseProperty> {
+    map.iter().map(|(name, value)| property_for_value(name, value)).collect()
+}
+
+fn property_for_value(name: &str, value: &Value) -> ResponseProperty {
+    let mut property = ResponseProperty {
+        name: name.to_string(),
+        property_type: value_type(value).to_string(),
+        required: true,
+        ..ResponseProperty::default()
+    };
+
+    match value {
+        Value::Object(map) => {
+            property.nested_properties = Some(object_properties(map));
+        }
+        Value::Array(items) => {
+            if let Some(item) = merged_item(items) {
+                property.items_type = Some(value_type(&item).to_string());
+                if let Value::Object(map) = &item {
+                    property.nested_properties = Some(object_properties(map));
+                }
+            }
+        }
+        Value::String(s) => {
+            property.format = string_format(s).map(|f| f.to_string());
+            property.example = Some(value.clone());
+        }
+        _ => {
+            property.example = Some(value.clone());
+        }
+    }
+
+    property
+}
+
+/// Representative element of an array: object elements are merged so fields that only
+/// appear in some items are still described; otherwise the first non-null element
+fn merged_item(items: &[Value]) -> Option<Value> {
+    let mut merged: Option<Value> = None;
+    for item in items.iter().filter(|v| !v.is_null()) {
+        match (&mut merged, item) {
+            (None, _) => merged = Some(item.clone()),
+            (Some(Value::Object(acc)), Value::Object(map)) => {
+                for (key, value) in map {
+                    let slot = acc.entry(key.clone()).or_insert(Value::Null);
+                    if slot.is_null() {
+                        *slot = value.clone();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    merged.or_else(|| items.first().cloned())
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Detect well-known string formats ("date-time", "date", "email", "uuid")
+fn string_format(s: &str) -> Option<&'static str> {
+    if chrono::DateTime::parse_from_rfc3339(s).is_ok()
+        || chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+        || chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").is_ok()
+    {
+        return Some("date-time");
+    }
+    if s.len() == 10 && chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        return Some("date");
+    }
+    if s.len() == 36 && uuid::Uuid::parse_str(s).is_ok() {
+        return Some("uuid");
+    }
+    if is_email(s) {
+        return Some("email");
+    }
+    None
+}
+
+fn is_email(s: &str) -> bool {
+    let mut parts = s.split('@');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(local), Some(domain), None) => {
+            !local.is_empty()
+                && !s.contains(char::is_whitespace)
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_nested_schema_and_formats() {
+        let sample = r#"{
+            "success": true,
+            "data": {
+                "id": "6f1c2a4e-8b7d-4c3a-9e2f-1a2b3c4d5e6f",
+                "email": "jane@example.com",
+                "birthday": "1990-04-12",
+                "createdAt": "2024-03-01T10:15:00Z",
+                "score": 4.5,
+                "tags": ["a", "b"],
+                "orders": [{"total": 10}, {"total": 12, "coupon": "SPRING"}]
+            }
+        }"#;
+
+        let schema = infer_schema_from_sample(sample).unwrap();
+        assert_eq!(schema.schema_type, "object");
+        assert!(schema.is_wrapped);
+
+        let data = schema.properties.iter().find(|p| p.name == "data").unwrap();
+        assert_eq!(data.property_type, "object");
+        let fields = data.nested_properties.as_ref().unwrap();
+        let field = |name: &str| fields.iter().find(|p| p.name == name).unwrap();
+
+        assert!(fields.iter().all(|p| p.required));
+        assert_eq!(field("id").format.as_deref(), Some("uuid"));
+        assert_eq!(field("email").format.as_deref(), Some("email"));
+        assert_eq!(field("birthday").format.as_deref(), Some("date"));
+        assert_eq!(field("createdAt").format.as_deref(), Some("date-time"));
+        assert_eq!(field("score").property_type, "number");
+        assert_eq!(field("tags").items_type.as_deref(), Some("string"));
+
+        let orders = field("orders");
+        assert_eq!(orders.items_type.as_deref(), Some("object"));
+        let mut order_fields: Vec<&str> = orders
+            .nested_properties
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        order_fields.sort();
+        assert_eq!(order_fields, vec!["coupon", "total"]);
+    }
+
+    #[test]
+    fn test_infer_top_level_array() {
+        let schema = infer_schema_from_sample(r#"[{"id": 1}]"#).unwrap();
+        assert_eq!(schema.schema_type, "array");
+        let items = schema.items_schema.unwrap();
+        assert_eq!(items.properties[0].property_type, "integer");
+
+        assert!(infer_schema_from_sample("not json").is_err());
+    }
+}
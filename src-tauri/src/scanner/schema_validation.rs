@@ -0,0 +1,205 @@
+//! Validation of a live response body against a scanned `ResponseSchema`
+//!
+//! A quick "does the API still match the code" check for manual requests: reports required
+//! fields that are missing and values whose JSON type differs from the schema. Types the
+//! validator doesn't know (DTO names, "any") are not checked.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::types::{ResponseProperty, ResponseSchema};
+use crate::types::ApiEndpoint;
+
+/// One way a response body deviates from its schema
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SchemaDeviation {
+    pub path: String, // e.g. "data.items[0].id"; "$" for the body itself
+    pub kind: String, // "missing" or "typeMismatch"
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+/// Result of validating a response against the linked endpoint's stored schema
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SchemaValidationResult {
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: String,
+    #[serde(rename = "statusCode")]
+    pub status_code: u16,
+    #[serde(rename = "schemaFound")]
+    pub schema_found: bool, // False when the endpoint has no stored schema for this status
+    pub valid: bool,
+    pub deviations: Vec<SchemaDeviation>,
+}
+
+const KNOWN_TYPES: &[&str] = &["string", "number", "integer", "boolean", "object", "array", "null"];
+
+/// Validate a response against the endpoint's stored schema for the same status code
+pub fn validate_endpoint_response(endpoint: &ApiEndpoint, status_code: u16, body: &Value) -> SchemaValidationResult {
+    let schema = endpoint.responses.iter()
+        .flatten()
+        .find(|r| r.status_code == status_code)
+        .and_then(|r| r.schema.clone())
+        .and_then(|schema| serde_json::from_value::<ResponseSchema>(schema).ok());
+
+    let deviations = match &schema {
+        Some(schema) => validate_against_schema(body, schema),
+        None => Vec::new(),
+    };
+    SchemaValidationResult {
+        endpoint_id: endpoint.id.clone(),
+        status_code,
+        schema_found: schema.is_some(),
+        valid: deviations.is_empty(),
+        deviations,
+    }
+}
+
+/// Collect deviations of `body` from `schema`
+pub fn validate_against_schema(body: &Value, schema: &ResponseSchema) -> Vec<SchemaDeviation> {
+    let mut deviations = Vec::new();
+    validate_schema_at(body, schema, "$", &mut deviations);
+    deviations
+}
+
+fn validate_schema_at(value: &Value, schema: &ResponseSchema, path: &str, deviations: &mut Vec<SchemaDeviation>) {
+    if !check_type(value, &schema.schema_type, path, deviations) {
+        return;
+    }
+    match value {
+        Value::Object(_) => validate_properties(value, &schema.properties, path, deviations),
+        Value::Array(items) => {
+            if let Some(items_schema) = &schema.items_schema {
+                for (i, item) in items.iter().enumerate() {
+                    validate_schema_at(item, items_schema, &format!("{}[{}]", path, i), deviations);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_properties(object: &Value, properties: &[ResponseProperty], path: &str, deviations: &mut Vec<SchemaDeviation>) {
+    for property in properties {
+        let property_path = if path == "$" {
+            property.name.clone()
+        } else {
+            format!("{}.{}", path, property.name)
+        };
+
+        let value = match object.get(&property.name) {
+            Some(Value::Null) if !property.required => continue,
+            Some(value) => value,
+            None => {
+                if property.required {
+                    deviations.push(SchemaDeviation {
+                        path: property_path,
+                        kind: "missing".to_string(),
+                        expected: property.property_type.clone(),
+                        actual: None,
+                    });
+                }
+                continue;
+            }
+        };
+
+        if !check_type(value, &property.property_type, &property_path, deviations) {
+            continue;
+        }
+        match value {
+            Value::Object(_) => {
+                if let Some(nested) = &property.nested_properties {
+                    validate_properties(value, nested, &property_path, deviations);
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let item_path = format!("{}[{}]", property_path, i);
+                    if let Some(items_type) = &property.items_type {
+                        if !check_type(item, items_type, &item_path, deviations) {
+                            continue;
+                        }
+                    }
+                    if let (Value::Object(_), Some(nested)) = (item, &property.nested_properties) {
+                        validate_properties(item, nested, &item_path, deviations);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Record a type mismatch; false when the value doesn't have the expected type
+fn check_type(value: &Value, expected: &str, path: &str, deviations: &mut Vec<SchemaDeviation>) -> bool {
+    let expected = expected.to_lowercase();
+    if !KNOWN_TYPES.contains(&expected.as_str()) || type_matches(value, &expected) {
+        return true;
+    }
+    deviations.push(SchemaDeviation {
+        path: path.to_string(),
+        kind: "typeMismatch".to_string(),
+        expected,
+        actual: Some(json_type(value).to_string()),
+    });
+    false
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        other => json_type(value) == other,
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::infer_schema_from_sample;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_against_schema_reports_missing_and_mismatched_fields() {
+        let mut schema = infer_schema_from_sample(
+            r#"{"success":true,"data":{"id":1,"email":"a@b.c","tags":["x"],"items":[{"sku":"A1"}]}}"#,
+        )
+        .unwrap();
+        schema.properties.push(ResponseProperty {
+            name: "meta".to_string(),
+            property_type: "object".to_string(),
+            required: false,
+            ..ResponseProperty::default()
+        });
+
+        let matching = json!({"success":true,"data":{"id":2,"email":"x@y.z","tags":[],"items":[{"sku":"B2"}]},"meta":null});
+        assert!(validate_against_schema(&matching, &schema).is_empty());
+
+        let drifted = json!({"success":"yes","data":{"id":"2","tags":[1],"items":[{}]}});
+        let deviations = validate_against_schema(&drifted, &schema);
+        let summary: Vec<(&str, &str)> = deviations.iter().map(|d| (d.path.as_str(), d.kind.as_str())).collect();
+        assert_eq!(summary, vec![
+            ("data.email", "missing"),
+            ("data.id", "typeMismatch"),
+            ("data.items[0].sku", "missing"),
+            ("data.tags[0]", "typeMismatch"),
+            ("success", "typeMismatch"),
+        ]);
+        assert_eq!(deviations[1].expected, "integer");
+        assert_eq!(deviations[1].actual.as_deref(), Some("string"));
+
+        let not_an_object = validate_against_schema(&json!([1]), &schema);
+        assert_eq!(not_an_object[0].path, "$");
+    }
+}
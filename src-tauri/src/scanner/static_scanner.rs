@@ -1,11 +1,20 @@
-use crate::scanner::types::{FrameworkInfo, ScannedEndpoint};
+use crate::scanner::types::{DedupeKeyOptions, FrameworkInfo, ScannedEndpoint};
+use crate::scanner::parsers::express_parser::ExpressParser;
 use crate::scanner::parsers::laravel_parser::LaravelParser;
 use crate::scanner::parsers::nestjs_parser::NestJSParser;
+use crate::scanner::parsers::{default_category, default_worker_count, CategoryFn, DEFAULT_MAX_FILE_SIZE};
 use std::path::PathBuf;
 
+/// Endpoints found by a scan plus any warnings (e.g. skipped files)
+pub type ScanOutput = (Vec<ScannedEndpoint>, Vec<String>);
+
 pub struct StaticScanner {
     project_path: PathBuf,
     framework_info: FrameworkInfo,
+    max_file_size: u64,
+    workers: usize,
+    dedupe: DedupeKeyOptions,
+    category_fn: CategoryFn,
 }
 
 impl StaticScanner {
@@ -13,36 +22,80 @@ impl StaticScanner {
         Self {
             project_path,
             framework_info,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            workers: default_worker_count(),
+            dedupe: DedupeKeyOptions::default(),
+            category_fn: default_category,
         }
     }
 
-    pub async fn scan_endpoints(&self) -> Result<Vec<ScannedEndpoint>, String> {
+    /// Override the size above which source files are skipped
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Cap the threads used to read and parse source files
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Choose what identifies duplicate routes (default: method + path)
+    pub fn with_dedupe(mut self, dedupe: DedupeKeyOptions) -> Self {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Replace the heuristic that assigns endpoint categories
+    pub fn with_category_fn(mut self, category_fn: CategoryFn) -> Self {
+        self.category_fn = category_fn;
+        self
+    }
+
+    pub async fn scan_endpoints(&self) -> Result<ScanOutput, String> {
         match self.framework_info.framework.as_str() {
             "laravel" => self.scan_laravel_endpoints().await,
             "nestjs" => self.scan_nestjs_endpoints().await,
             "rails" => {
                 // Placeholder for Rails
-                Ok(vec![])
-            }
-            "express" => {
-                // Placeholder for Express
-                Ok(vec![])
+                Ok((vec![], vec![]))
             }
+            "express" | "fastify" => self.scan_express_endpoints().await,
             _ => {
                 // Unknown or unsupported framework
-                Ok(vec![])
+                Ok((vec![], vec![]))
             }
         }
     }
 
-    async fn scan_laravel_endpoints(&self) -> Result<Vec<ScannedEndpoint>, String> {
-        let mut parser = LaravelParser::new(self.project_path.clone());
-        parser.parse_endpoints().await
+    async fn scan_laravel_endpoints(&self) -> Result<ScanOutput, String> {
+        let mut parser = LaravelParser::new(self.project_path.clone())
+            .with_max_file_size(self.max_file_size)
+            .with_workers(self.workers)
+            .with_dedupe(self.dedupe)
+            .with_category_fn(self.category_fn);
+        let endpoints = parser.parse_endpoints().await?;
+        Ok((endpoints, parser.warnings().to_vec()))
     }
 
-    async fn scan_nestjs_endpoints(&self) -> Result<Vec<ScannedEndpoint>, String> {
-        let mut parser = NestJSParser::new(self.project_path.clone());
-        parser.parse_endpoints().await
+    async fn scan_nestjs_endpoints(&self) -> Result<ScanOutput, String> {
+        let mut parser = NestJSParser::new(self.project_path.clone())
+            .with_max_file_size(self.max_file_size)
+            .with_workers(self.workers)
+            .with_dedupe(self.dedupe)
+            .with_category_fn(self.category_fn);
+        let endpoints = parser.parse_endpoints().await?;
+        Ok((endpoints, parser.warnings().to_vec()))
     }
-}
 
+    async fn scan_express_endpoints(&self) -> Result<ScanOutput, String> {
+        let mut parser = ExpressParser::new(self.project_path.clone())
+            .with_max_file_size(self.max_file_size)
+            .with_workers(self.workers)
+            .with_dedupe(self.dedupe)
+            .with_category_fn(self.category_fn);
+        let endpoints = parser.parse_endpoints().await?;
+        Ok((endpoints, parser.warnings().to_vec()))
+    }
+}
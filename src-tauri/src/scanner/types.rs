@@ -4,7 +4,7 @@ use serde_json::Value;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameworkInfo {
     pub framework_type: String, // 'go', 'node', 'ruby', 'php', 'unknown'
-    pub framework: String,      // 'nestjs', 'express', 'rails', 'laravel', 'custom', 'unknown'
+    pub framework: String,      // 'nestjs', 'express', 'fastify', 'rails', 'laravel', 'custom', 'unknown'
     pub version: Option<String>,
     pub patterns: FrameworkPatterns,
     pub structure: FrameworkStructure,
@@ -38,6 +38,12 @@ pub struct ScannedEndpoint {
     pub authentication: Authentication,
     pub authorization: Authorization,
     pub responses: Vec<EndpointResponse>,
+    #[serde(default)]
+    pub version: Option<String>, // API version label, e.g. "v1"
+    #[serde(default)]
+    pub category: Option<String>, // Grouping derived by the parser, e.g. "orders"
+    #[serde(default)]
+    pub warnings: Vec<String>, // Code smells found in the handler, e.g. possible N+1 queries
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,11 +120,22 @@ pub struct ResponseProperty {
     pub format: Option<String>, // "email", "uuid", "date-time", etc.
 }
 
+/// What makes two scanned endpoints "the same" when deduplicating (default: method + path)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DedupeKeyOptions {
+    #[serde(rename = "includeAuth", default)]
+    pub include_auth: bool, // Keep routes that differ only in whether auth is required
+    #[serde(rename = "includeParams", default)]
+    pub include_params: bool, // Keep routes that differ in their query/body parameter contract
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanResult {
     pub framework_info: FrameworkInfo,
     pub endpoints: Vec<ScannedEndpoint>,
     pub scan_method: String,
+    #[serde(default)]
+    pub warnings: Vec<String>, // e.g. source files skipped for exceeding the size limit
 }
 
 impl Default for FrameworkPatterns {
@@ -0,0 +1,112 @@
+//! Request body templating beyond `{{ var }}` substitution
+//!
+//! An object of the shape `{ "__repeat": <count>, "item": <template> }` anywhere in a JSON body
+//! is replaced by an array of `count` copies of `item`. Inside the item, `{{ $index }}` is the
+//! 0-based position: a string that is exactly `{{ $index }}` becomes a number, otherwise the
+//! index is spliced into the text. `count` is a number or a string such as `"{{ batchSize }}"`,
+//! resolved like any other value. Repeats can nest; an inner item sees its own `$index`.
+//!
+//! ```json
+//! { "users": { "__repeat": 3, "item": { "id": "{{ $index }}", "email": "user{{ $index }}@example.com" } } }
+//! ```
+
+use serde_json::Value;
+
+/// Key marking a repeat directive
+pub const REPEAT_KEY: &str = "__repeat";
+
+/// Upper bound on generated items, so a bad count can't exhaust memory
+pub const MAX_REPEAT_COUNT: u64 = 10_000;
+
+/// Expand a repeat directive into its items (with `$index` filled, other variables untouched),
+/// or None when `map` is not a directive. `resolve` resolves variables in a string `count`.
+pub fn expand_repeat(map: &serde_json::Map<String, Value>, resolve: impl Fn(&str) -> String) -> Option<Vec<Value>> {
+    let count = map.get(REPEAT_KEY)?;
+    let item = map.get("item").cloned().unwrap_or(Value::Null);
+
+    let count = match count {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => resolve(s).trim().parse::<u64>().ok(),
+        _ => None,
+    };
+    let count = match count {
+        Some(count) if count > MAX_REPEAT_COUNT => {
+            log::warn!("[BodyTemplate] __repeat count {} capped at {}", count, MAX_REPEAT_COUNT);
+            MAX_REPEAT_COUNT
+        }
+        Some(count) => count,
+        None => {
+            log::warn!("[BodyTemplate] __repeat count is not a non-negative integer; generating no items");
+            0
+        }
+    };
+
+    Some((0..count).map(|index| fill_index(&item, index)).collect())
+}
+
+/// Replace `{{ $index }}` in every string of `value`, leaving nested repeat items to their own index
+fn fill_index(value: &Value, index: u64) -> Value {
+    match value {
+        Value::String(s) => {
+            let trimmed = s.trim();
+            if is_index_placeholder(trimmed) {
+                return Value::Number(index.into());
+            }
+            Value::String(s.replace("{{ $index }}", &index.to_string()).replace("{{$index}}", &index.to_string()))
+        }
+        Value::Object(map) if map.contains_key(REPEAT_KEY) => {
+            // The count may use the outer index; the inner item keeps its placeholders
+            let mut map = map.clone();
+            if let Some(count) = map.get(REPEAT_KEY).map(|c| fill_index(c, index)) {
+                map.insert(REPEAT_KEY.to_string(), count);
+            }
+            Value::Object(map)
+        }
+        Value::Object(map) => Value::Object(map.iter().map(|(k, v)| (k.clone(), fill_index(v, index))).collect()),
+        Value::Array(items) => Value::Array(items.iter().map(|v| fill_index(v, index)).collect()),
+        _ => value.clone(),
+    }
+}
+
+fn is_index_placeholder(s: &str) -> bool {
+    s.strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        .is_some_and(|inner| inner.trim() == "$index")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(body: Value) -> Vec<Value> {
+        expand_repeat(body.as_object().unwrap(), |s| s.replace("{{ size }}", "2")).unwrap()
+    }
+
+    #[test]
+    fn test_repeat_builds_indexed_items() {
+        let items = expand(serde_json::json!({
+            "__repeat": 3,
+            "item": { "id": "{{ $index }}", "email": "user{{$index}}@example.com", "tags": ["t{{ $index }}"], "role": "{{ role }}" }
+        }));
+        assert_eq!(items, vec![
+            serde_json::json!({ "id": 0, "email": "user0@example.com", "tags": ["t0"], "role": "{{ role }}" }),
+            serde_json::json!({ "id": 1, "email": "user1@example.com", "tags": ["t1"], "role": "{{ role }}" }),
+            serde_json::json!({ "id": 2, "email": "user2@example.com", "tags": ["t2"], "role": "{{ role }}" }),
+        ]);
+    }
+
+    #[test]
+    fn test_repeat_count_and_nesting() {
+        assert_eq!(expand(serde_json::json!({ "__repeat": "{{ size }}", "item": "x" })).len(), 2);
+        assert!(expand(serde_json::json!({ "__repeat": "many", "item": "x" })).is_empty());
+        assert_eq!(expand(serde_json::json!({ "__repeat": 50_000, "item": 1 })).len(), MAX_REPEAT_COUNT as usize);
+        assert!(expand_repeat(serde_json::json!({ "item": 1 }).as_object().unwrap(), |s| s.to_string()).is_none());
+
+        // The inner directive survives the outer expansion with its own placeholders
+        let outer = expand(serde_json::json!({
+            "__repeat": 2,
+            "item": { "group": "{{ $index }}", "members": { "__repeat": 1, "item": "m{{ $index }}" } }
+        }));
+        assert_eq!(outer[1], serde_json::json!({ "group": 1, "members": { "__repeat": 1, "item": "m{{ $index }}" } }));
+    }
+}
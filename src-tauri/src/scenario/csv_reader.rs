@@ -8,6 +8,7 @@ use std::error::Error;
 use std::fs::File;
 use std::path::Path;
 use csv::ReaderBuilder;
+use regex::Regex;
 use super::types::{CsvConfig, CsvPreview};
 
 /// Read CSV file and return as a vector of HashMaps
@@ -107,6 +108,72 @@ pub fn preview_csv_file(
     })
 }
 
+/// Read only the header row of a CSV file
+pub fn read_csv_headers(
+    file_path: &str,
+    config: &CsvConfig,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    log::debug!("[CSV] Reading headers from: {}", file_path);
+
+    let path = Path::new(file_path);
+    if !path.exists() {
+        let error = format!("CSV file not found: {}", file_path);
+        log::error!("[CSV] {}", error);
+        return Err(error.into());
+    }
+
+    let file = File::open(path)?;
+    let mut reader = ReaderBuilder::new()
+        .delimiter(config.delimiter.unwrap_or(',') as u8)
+        .quote(config.quote_char.unwrap_or('"') as u8)
+        .from_reader(file);
+
+    let headers: Vec<String> = reader.headers()?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+
+    log::debug!("[CSV] Headers: {:?}", headers);
+    Ok(headers)
+}
+
+/// Find all column names referenced as `{{ item.<column> }}` in the given text
+pub fn find_item_references(text: &str) -> Vec<String> {
+    let re = Regex::new(r"\{\{\s*item\.(\w+)[\w.]*\s*\}\}").unwrap();
+    let mut columns: Vec<String> = Vec::new();
+    for cap in re.captures_iter(text) {
+        let column = cap[1].to_string();
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    columns
+}
+
+/// Check that every referenced column exists in the CSV headers
+pub fn validate_item_columns(
+    file_path: &str,
+    headers: &[String],
+    referenced: &[String],
+) -> Result<(), String> {
+    let missing: Vec<&str> = referenced
+        .iter()
+        .filter(|col| !headers.contains(col))
+        .map(|col| col.as_str())
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "CSV file '{}' is missing referenced column(s): {} (available: {})",
+            file_path,
+            missing.join(", "),
+            headers.join(", ")
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +183,43 @@ mod tests {
         // This test would require a test CSV file
         // In production, you would create a temporary CSV for testing
     }
+
+    #[test]
+    fn test_find_item_references() {
+        let text = r#"{"email": "{{ item.email }}", "name": "{{item.name}}", "again": "{{ item.email }}", "token": "{{ token }}"}"#;
+        let refs = find_item_references(text);
+        assert_eq!(refs, vec!["email".to_string(), "name".to_string()]);
+    }
+
+    #[test]
+    fn test_validate_item_columns_reports_missing() {
+        let headers = vec!["email".to_string(), "password".to_string()];
+        assert!(validate_item_columns("users.csv", &headers, &["email".to_string()]).is_ok());
+
+        let err = validate_item_columns(
+            "users.csv",
+            &headers,
+            &["email".to_string(), "name".to_string(), "age".to_string()],
+        )
+        .unwrap_err();
+        assert!(err.contains("name, age"));
+    }
+
+    #[test]
+    fn test_read_csv_headers() {
+        let path = std::env::temp_dir().join(format!("lookapi-headers-{}.csv", std::process::id()));
+        std::fs::write(&path, "email,password\nuser@example.com,secret\n").unwrap();
+        let config = CsvConfig {
+            file_name: path.to_string_lossy().to_string(),
+            quote_char: None,
+            delimiter: None,
+        };
+
+        let headers = read_csv_headers(&config.file_name, &config).unwrap();
+        assert_eq!(headers, vec!["email".to_string(), "password".to_string()]);
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
 
 
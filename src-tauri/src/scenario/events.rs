@@ -0,0 +1,30 @@
+//! Progress events emitted while a scenario runs
+//!
+//! The executor only knows this trait, so it can run outside the app: the Tauri `AppHandle`
+//! forwards events to the frontend, other embedders can log or collect them, or pass `None`.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Receiver for run progress events ("scenario-started", "step-completed", ...)
+pub trait RunEvents {
+    fn emit_json(&self, event: &str, payload: serde_json::Value);
+}
+
+impl dyn RunEvents + '_ {
+    /// Serialize `payload` and pass it to the receiver
+    pub fn emit<T: Serialize>(&self, event: &str, payload: T) -> Result<(), String> {
+        let payload = serde_json::to_value(payload)
+            .map_err(|e| format!("Failed to serialize {} event: {}", event, e))?;
+        self.emit_json(event, payload);
+        Ok(())
+    }
+}
+
+impl RunEvents for AppHandle {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) {
+        if let Err(e) = Emitter::emit(self, event, payload) {
+            log::warn!("[Events] Failed to emit {}: {}", event, e);
+        }
+    }
+}
@@ -1,25 +1,263 @@
 use super::types::*;
+use super::events::RunEvents;
+use super::grpc;
+use super::body_template;
+use super::rng::SeededRng;
+use super::run_log::{RunLog, RunLogEntry};
+use super::server_timing::{parse_server_timing, server_timing_value};
+use crate::http_client::ClientDefaults;
+use crate::request_log::{redact_headers, RequestLogger};
+use crate::request_signing::{self, RequestSigningConfig, SigningInput};
+use crate::types::{BodyLimits, IdempotencyConfig, ProjectService};
 use reqwest::blocking::Client;
-use std::collections::HashMap;
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::redirect::Policy;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 use regex::Regex;
-use tauri::{AppHandle, Emitter};
+use std::io::Read;
+
+/// Characters of response text recorded as an assertion's actual value
+const RAW_TEXT_ACTUAL_LIMIT: usize = 1024;
+
+/// Default size limits for bodies stored with a run
+pub const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 64 * 1024;
+pub const DEFAULT_MAX_RESPONSE_BODY_BYTES: usize = 256 * 1024;
+
+/// Safety cap on response bytes held in memory. Extraction and assertions see the body up to
+/// this size; the stored copy is trimmed separately to the project's response limit.
+const MAX_RESPONSE_READ_BYTES: usize = 64 * 1024 * 1024;
+
+/// Compressions the executor can decode, advertised on every request
+const ACCEPT_ENCODING: &str = "gzip, deflate, br";
+
+/// Backstop on step executions per run (CSV rows count individually)
+pub const DEFAULT_MAX_EXECUTED_STEPS: usize = 10_000;
+
+/// Stream id of the RNG that rolls chaos faults, derived from the run seed
+const CHAOS_RNG_STREAM: u64 = 0xC4A0_5EED;
+
+/// How often a Delay step wakes up to check for a stop request
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Cancellation flags of scenario runs in progress, keyed by run id
+fn running_scenarios() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static RUNNING: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Ask a running scenario to stop after its current step; false if the run is not active
+pub fn stop_scenario_run(run_id: &str) -> bool {
+    let running = running_scenarios().lock().unwrap_or_else(|e| e.into_inner());
+    match running.get(run_id) {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Keeps a run registered as stoppable until it finishes (or panics)
+struct RunRegistration(String);
+
+impl RunRegistration {
+    fn new(run_id: &str, flag: Arc<AtomicBool>) -> Self {
+        running_scenarios()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(run_id.to_string(), flag);
+        Self(run_id.to_string())
+    }
+}
+
+impl Drop for RunRegistration {
+    fn drop(&mut self) {
+        running_scenarios()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.0);
+    }
+}
 
 /// Scenario Executor - Executes test scenarios step by step
 pub struct ScenarioExecutor {
     client: Client,
+    verify_tls: bool,
+    client_defaults: ClientDefaults,
     variables: HashMap<String, serde_json::Value>,
     base_url: Option<String>,
-    #[allow(dead_code)]
+    services: HashMap<String, String>, // Service name -> base URL for steps with `service`
     timeout: Duration,
+    seed: u64,
+    rng: RefCell<SeededRng>,
+    chaos_rng: RefCell<SeededRng>, // Fault rolls; separate so chaos doesn't shift the dynamic tokens
+    max_request_body_bytes: usize,
+    max_response_body_bytes: usize,
+    assertion_templates: HashMap<String, AssertionTemplate>,
+    chaos: Option<ChaosConfig>,
+    request_logger: Option<RequestLogger>,
+    run_log: Option<RunLog>, // Trace stored with the run, independent of the global logger
+    request_signing: Option<RequestSigningConfig>,
+    idempotency: Option<IdempotencyConfig>,
+    cookies: Arc<Jar>, // Cookie jar shared by all clients of a run; honours Domain, Path, Secure and expiry
+    scenario_id: String,
+    snapshots: HashMap<(String, String), serde_json::Value>, // (step id, name) -> stored body
+    update_snapshots: bool,
+    snapshot_updates: Vec<AssertionSnapshot>,
+    dry_run: bool, // Resolve request steps but never send them
+    cancelled: Arc<AtomicBool>,
+    redirect_hops: Arc<Mutex<Vec<RedirectHop>>>, // Filled by the redirect policy during a send
+    redirect_clients: RefCell<HashMap<Option<usize>, Client>>, // Step redirect policy (None: don't follow, Some(n): up to n hops) -> client
+    max_executed_steps: usize,
 }
 
 impl ScenarioExecutor {
     pub fn new() -> Self {
         log::info!("[Executor] Creating ScenarioExecutor with timeout: 30s");
+        let client_defaults = ClientDefaults::default();
+        let redirect_hops = Arc::new(Mutex::new(Vec::new()));
+        let cookies = Arc::new(Jar::default());
+        let client = Self::build_client(true, recording_policy(Policy::default(), &redirect_hops), &client_defaults, &cookies);
+        let seed = SeededRng::generate_seed();
+        Self {
+            client,
+            verify_tls: true,
+            client_defaults,
+            variables: HashMap::new(),
+            base_url: None,
+            services: HashMap::new(),
+            timeout: Duration::from_secs(30),
+            seed,
+            rng: RefCell::new(SeededRng::new(seed)),
+            chaos_rng: RefCell::new(SeededRng::derived(seed, CHAOS_RNG_STREAM)),
+            max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
+            max_response_body_bytes: DEFAULT_MAX_RESPONSE_BODY_BYTES,
+            assertion_templates: HashMap::new(),
+            chaos: None,
+            request_logger: None,
+            run_log: None,
+            request_signing: None,
+            idempotency: None,
+            cookies,
+            scenario_id: String::new(),
+            snapshots: HashMap::new(),
+            update_snapshots: false,
+            snapshot_updates: Vec::new(),
+            dry_run: false,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            redirect_hops,
+            redirect_clients: RefCell::new(HashMap::new()),
+            max_executed_steps: DEFAULT_MAX_EXECUTED_STEPS,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Stored snapshots for `snapshot` assertions; in update mode mismatches rewrite them
+    pub fn with_snapshots(mut self, snapshots: Vec<AssertionSnapshot>, update_snapshots: bool) -> Self {
+        self.snapshots = snapshots
+            .into_iter()
+            .map(|s| ((s.step_id, s.name), s.body))
+            .collect();
+        self.update_snapshots = update_snapshots;
+        self
+    }
+
+    pub fn with_variables(mut self, variables: HashMap<String, serde_json::Value>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: Option<String>) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Resolve a request step exactly as a run would send it, without sending it
+    pub fn resolve_request_step(&mut self, step: &TestScenarioStep) -> Result<StepRequest, String> {
+        let dry_run = std::mem::replace(&mut self.dry_run, true);
+        let result = self.execute_request_step(step);
+        self.dry_run = dry_run;
+        match result.request {
+            Some(request) if result.status == StepResultStatus::Skipped => Ok(request),
+            _ => Err(result.error.unwrap_or_else(|| format!("Could not resolve step: {}", step.name))),
+        }
+    }
+
+    /// Stop the run with an error once this many step executions have happened
+    pub fn with_max_executed_steps(mut self, max_executed_steps: usize) -> Self {
+        self.max_executed_steps = max_executed_steps.max(1);
+        self
+    }
+
+    /// Resolve every request step and record it as skipped instead of sending it
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        if dry_run {
+            log::info!("[Executor] Dry run: requests will be resolved but not sent");
+        }
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// HMAC-sign every request step with the project's signing config
+    pub fn with_request_signing(mut self, request_signing: Option<RequestSigningConfig>) -> Self {
+        self.request_signing = request_signing;
+        self
+    }
+
+    /// Attach a generated idempotency key to request steps with the configured methods
+    pub fn with_idempotency(mut self, idempotency: Option<IdempotencyConfig>) -> Self {
+        self.idempotency = idempotency;
+        self
+    }
+
+    /// Named base URLs that request steps select with `service`
+    pub fn with_services(mut self, services: Vec<ProjectService>) -> Self {
+        self.services = services.into_iter().map(|s| (s.name, s.base_url)).collect();
+        self
+    }
+
+    /// Toggle TLS certificate verification (disable only for self-signed dev hosts)
+    pub fn with_tls_verification(mut self, verify_tls: bool) -> Self {
+        if !verify_tls {
+            log::warn!("[Executor] TLS certificate verification disabled");
+        }
+        self.client = Self::build_client(verify_tls, recording_policy(Policy::default(), &self.redirect_hops), &self.client_defaults, &self.cookies);
+        self.redirect_clients.get_mut().clear();
+        self.verify_tls = verify_tls;
+        self
+    }
+
+    /// Project User-Agent and always-sent headers; per-step headers still override them
+    pub fn with_client_defaults(mut self, client_defaults: ClientDefaults) -> Self {
+        self.client = Self::build_client(self.verify_tls, recording_policy(Policy::default(), &self.redirect_hops), &client_defaults, &self.cookies);
+        self.redirect_clients.get_mut().clear();
+        self.client_defaults = client_defaults;
+        self
+    }
+
+    fn build_client(verify_tls: bool, redirect_policy: Policy, defaults: &ClientDefaults, cookies: &Arc<Jar>) -> Client {
+        // Bodies are decoded in `read_response_body` so the original Content-Encoding is
+        // still visible; reqwest strips it when it decompresses itself
+        let mut headers = defaults.header_map();
+        headers.entry(reqwest::header::ACCEPT_ENCODING)
+            .or_insert(reqwest::header::HeaderValue::from_static(ACCEPT_ENCODING));
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
-            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_certs(!verify_tls)
+            .redirect(redirect_policy)
+            .user_agent(defaults.user_agent())
+            .default_headers(headers)
+            .cookie_provider(cookies.clone())
+            .no_gzip()
+            .no_brotli()
+            .no_deflate()
             .build()
             .unwrap_or_else(|e| {
                 log::error!("[Executor] Failed to create client: {}", e);
@@ -28,21 +266,302 @@ impl ScenarioExecutor {
                 Client::new()
             });
         log::info!("[Executor] Client created successfully");
-        Self {
-            client,
-            variables: HashMap::new(),
-            base_url: None,
-            timeout: Duration::from_secs(30),
+        client
+    }
+
+    /// Client honoring a step's redirect settings; the shared client already follows up to 10 hops.
+    /// Clients for other policies are built once per run and share its cookie jar.
+    fn client_for_redirects(&self, follow_redirects: Option<bool>, max_redirects: Option<usize>) -> Client {
+        let limit = match (follow_redirects, max_redirects) {
+            (Some(false), _) => None,
+            (_, Some(max)) => Some(max),
+            _ => return self.client.clone(),
+        };
+        self.redirect_clients
+            .borrow_mut()
+            .entry(limit)
+            .or_insert_with(|| {
+                let policy = match limit {
+                    Some(max) => recording_policy(Policy::limited(max), &self.redirect_hops),
+                    None => Policy::none(),
+                };
+                Self::build_client(self.verify_tls, policy, &self.client_defaults, &self.cookies)
+            })
+            .clone()
+    }
+
+    /// Redirect hops recorded since the last call
+    fn take_redirect_hops(&self) -> Vec<RedirectHop> {
+        self.redirect_hops
+            .lock()
+            .map(|mut hops| std::mem::take(&mut *hops))
+            .unwrap_or_default()
+    }
+
+    /// Limit how much of each request/response body is kept in stored results
+    pub fn with_body_limits(mut self, limits: BodyLimits) -> Self {
+        self.max_request_body_bytes = limits.max_request_bytes;
+        self.max_response_body_bytes = limits.max_response_bytes;
+        self
+    }
+
+    /// Capture every request/response exchange to a project log file
+    pub fn with_request_logger(mut self, request_logger: Option<RequestLogger>) -> Self {
+        self.request_logger = request_logger;
+        self
+    }
+
+    /// Capture this executor's run trace (requests, responses, variable resolution, warnings)
+    /// into `run_log`; the entries are returned in `TestScenarioRun::logs`
+    pub fn with_run_log(mut self, run_log: Option<RunLog>) -> Self {
+        self.run_log = run_log;
+        self
+    }
+
+    /// Log through `log` and, when capturing, into the run's own log
+    fn trace(&self, level: log::Level, message: String) {
+        log::log!(level, "[Executor] {}", message);
+        if let Some(run_log) = &self.run_log {
+            run_log.push(level, message);
         }
     }
 
-    pub fn with_variables(mut self, variables: HashMap<String, serde_json::Value>) -> Self {
-        self.variables = variables;
+    fn run_log_entries(&self) -> Vec<RunLogEntry> {
+        self.run_log.as_ref().map(RunLog::entries).unwrap_or_default()
+    }
+
+    /// Cookie header the jar holds for `url`, as the client will send it
+    fn cookie_header(&self, url: &str) -> Option<String> {
+        let url = reqwest::Url::parse(url).ok()?;
+        self.cookies.cookies(&url)?.to_str().ok().map(str::to_string)
+    }
+
+    /// Log in with a form (or JSON) POST and check that the session cookie was set
+    fn perform_session_login(&mut self, login: &SessionLoginConfig) -> TestStepResult {
+        let url = self.resolve_url(&self.resolve_url_variables(&login.url), None);
+        let method = login.method.to_uppercase();
+        let username = self.resolve_variables(&login.username);
+        let password = self.resolve_variables(&login.password);
+        self.trace(log::Level::Info, format!("Session login: {} {} as {}", method, url, username));
+
+        // Redirects are not followed so the Set-Cookie on a 302 after login is kept
+        let client = Self::build_client(self.verify_tls, Policy::none(), &self.client_defaults, &self.cookies);
+        let req = match method.as_str() {
+            "PUT" => client.put(&url),
+            _ => client.post(&url),
+        };
+        let req = if login.json {
+            req.json(&serde_json::json!({
+                login.username_field.clone(): username,
+                login.password_field.clone(): password,
+            }))
+        } else {
+            req.form(&[(login.username_field.as_str(), username.as_str()), (login.password_field.as_str(), password.as_str())])
+        };
+
+        let step_request = StepRequest {
+            method: method.clone(),
+            url: url.clone(),
+            headers: HashMap::new(),
+            body: Some(serde_json::json!({
+                login.username_field.clone(): username,
+                login.password_field.clone(): "***",
+            })),
+            fault: None,
+        };
+        let mut result = TestStepResult {
+            step_id: "session-login".to_string(),
+            name: "Session login".to_string(),
+            step_type: TestStepType::Request,
+            status: StepResultStatus::Error,
+            duration_ms: None,
+            request: Some(step_request),
+            response: None,
+            assertions: None,
+            error: None,
+            extracted_variables: None,
+            group: Some("Setup".to_string()),
+            assertion_summary: None,
+        };
+
+        let start = Instant::now();
+        let response = match req.send() {
+            Ok(resp) => resp,
+            Err(e) => {
+                result.duration_ms = Some(start.elapsed().as_millis() as u64);
+                result.error = Some(format!("Session login request failed: {}", e));
+                log::error!("[Executor] {}", result.error.as_deref().unwrap_or(""));
+                return result;
+            }
+        };
+        result.duration_ms = Some(start.elapsed().as_millis() as u64);
+
+        let status = response.status();
+        let mut headers = HashMap::new();
+        for (k, v) in response.headers() {
+            if let Ok(value) = v.to_str() {
+                headers.insert(k.to_string(), value.to_string());
+            }
+        }
+        let read = read_response_body(response, MAX_RESPONSE_READ_BYTES);
+        let body_text = read.text;
+        let body = serde_json::from_str(&body_text).unwrap_or(serde_json::Value::String(body_text.clone()));
+        let (body, stored_truncated) = truncate_body(&body, self.max_response_body_bytes);
+        result.response = Some(StepResponse {
+            status: status.as_u16(),
+            status_text: status.to_string(),
+            content_type: find_header(&headers, "content-type").cloned(),
+            server_timing: find_header(&headers, "server-timing")
+                .map(|v| parse_server_timing(v))
+                .unwrap_or_default(),
+            headers,
+            body,
+            raw_body: body_text,
+            duration_ms: result.duration_ms.unwrap_or(0),
+            truncated: read.truncated || stored_truncated,
+            final_url: Some(url.clone()),
+            redirects: Vec::new(),
+            content_encoding: read.content_encoding,
+            decoded_size: read.decoded_size,
+        });
+
+        // Only cookies the jar would send back to the login URL count
+        let jar_cookies = self.cookie_header(&url).unwrap_or_default();
+        let received: Vec<&str> = jar_cookies
+            .split("; ")
+            .filter_map(|pair| pair.split_once('=').map(|(name, _)| name))
+            .collect();
+        let error = if status.is_client_error() || status.is_server_error() {
+            Some(format!("Session login returned HTTP {}", status))
+        } else if !received.iter().any(|name| *name == login.expected_cookie) {
+            Some(format!(
+                "Session login did not set the expected cookie '{}' (cookies received: {})",
+                login.expected_cookie,
+                if received.is_empty() { "none".to_string() } else { received.join(", ") }
+            ))
+        } else {
+            None
+        };
+
+        match error {
+            Some(e) => {
+                log::error!("[Executor] {}", e);
+                result.status = StepResultStatus::Failed;
+                result.error = Some(e);
+            }
+            None => {
+                log::info!("[Executor] Session login succeeded, cookie '{}' set", login.expected_cookie);
+                result.status = StepResultStatus::Passed;
+            }
+        }
+        result
+    }
+
+    /// Write one request exchange to the request log, if capture is enabled
+    fn capture_exchange(&self, step_name: &str, request: &StepRequest, response: Option<&StepResponse>, error: Option<&str>) {
+        let logger = match &self.request_logger {
+            Some(logger) => logger,
+            None => return,
+        };
+        let mut entry = format!("[{}] {} {}", step_name, request.method, request.url);
+        entry.push_str(&format!("\n  request headers: {}", serde_json::to_string(&redact_headers(&request.headers)).unwrap_or_default()));
+        if let Some(body) = &request.body {
+            entry.push_str(&format!("\n  request body: {}", body));
+        }
+        if let Some(response) = response {
+            entry.push_str(&format!(
+                "\n  response: {} ({}ms)\n  response headers: {}\n  response body: {}",
+                response.status_text,
+                response.duration_ms,
+                serde_json::to_string(&redact_headers(&response.headers)).unwrap_or_default(),
+                response.body
+            ));
+        }
+        if let Some(error) = error {
+            entry.push_str(&format!("\n  error: {}", error));
+        }
+        logger.write(&entry);
+    }
+
+    /// Make project assertion templates available to steps that reference them by name
+    pub fn with_assertion_templates(mut self, templates: Vec<AssertionTemplate>) -> Self {
+        self.assertion_templates = templates
+            .into_iter()
+            .map(|t| (t.name.clone(), t))
+            .collect();
         self
     }
 
-    pub fn with_base_url(mut self, base_url: Option<String>) -> Self {
-        self.base_url = base_url;
+    /// Replace template references with the template's assertions
+    fn expand_assertions(&self, assertions: &[Assertion]) -> Vec<Assertion> {
+        let mut expanded = Vec::new();
+        for assertion in assertions {
+            match &assertion.template {
+                Some(name) => match self.assertion_templates.get(name) {
+                    Some(template) => expanded.extend(template.instantiate(assertion.params.as_ref())),
+                    None => {
+                        self.trace(log::Level::Warn, format!("Unknown assertion template: {}", name));
+                        expanded.push(Assertion {
+                            name: format!("template: {}", name),
+                            passed: Some(false),
+                            error: Some(format!("Assertion template not found: {}", name)),
+                            ..assertion.clone()
+                        });
+                    }
+                },
+                None => expanded.push(assertion.clone()),
+            }
+        }
+        expanded
+    }
+
+    /// Roll for a chaos fault on this request; only kinds that apply to it are candidates
+    fn pick_fault(&self, config: &RequestStepConfig, method: &str) -> Option<(ChaosFaultKind, Option<String>)> {
+        let chaos = self.chaos.as_ref()?;
+        let mut rng = self.chaos_rng.borrow_mut();
+        if rng.next_range(0, 99) >= chaos.percentage as i64 {
+            return None;
+        }
+
+        let header = config.headers.as_ref().and_then(|headers| {
+            let mut names: Vec<&String> = headers.keys().collect();
+            names.sort();
+            match &chaos.drop_header {
+                Some(wanted) => names.into_iter().find(|n| n.eq_ignore_ascii_case(wanted)).cloned(),
+                None => names.first().map(|n| (*n).clone()),
+            }
+        });
+        let has_body = method != "GET" && (config.body.is_some() || config.params.is_some());
+
+        let kinds = if chaos.faults.is_empty() {
+            vec![ChaosFaultKind::DropHeader, ChaosFaultKind::Latency, ChaosFaultKind::TruncateBody]
+        } else {
+            chaos.faults.clone()
+        };
+        let candidates: Vec<ChaosFaultKind> = kinds
+            .into_iter()
+            .filter(|kind| match kind {
+                ChaosFaultKind::DropHeader => header.is_some(),
+                ChaosFaultKind::TruncateBody => has_body,
+                ChaosFaultKind::Latency => true,
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let kind = candidates[rng.next_range(0, candidates.len() as i64 - 1) as usize];
+        let header = if kind == ChaosFaultKind::DropHeader { header } else { None };
+        Some((kind, header))
+    }
+
+    /// Use a fixed RNG seed so dynamic tokens replay identically
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        if let Some(seed) = seed {
+            self.seed = seed;
+            self.rng = RefCell::new(SeededRng::new(seed));
+            self.chaos_rng = RefCell::new(SeededRng::derived(seed, CHAOS_RNG_STREAM));
+        }
         self
     }
 
@@ -51,14 +570,25 @@ impl ScenarioExecutor {
         &mut self,
         scenario: &TestScenario,
         steps: &[TestScenarioStep],
-        app_handle: Option<&AppHandle>,
+        events: Option<&dyn RunEvents>,
     ) -> TestScenarioRun {
         let run_id = uuid::Uuid::new_v4().to_string();
         let started_at = chrono::Utc::now().timestamp();
         let start_time = Instant::now();
+        self.scenario_id = scenario.id.clone();
+        let _registration = RunRegistration::new(&run_id, self.cancelled.clone());
 
-        log::info!("[Executor] Starting scenario execution: {} (ID: {})", scenario.name, scenario.id);
+        self.trace(log::Level::Info, format!("Starting scenario execution: {} (ID: {}, seed: {})", scenario.name, scenario.id, self.seed));
         log::debug!("[Executor] Scenario ID: {}, Run ID: {}", scenario.id, run_id);
+        if let Some(logger) = &self.request_logger {
+            logger.write(&format!("=== Scenario '{}' run {} started (seed {})", scenario.name, run_id, self.seed));
+        }
+
+        // Chaos mode only applies when explicitly enabled on the scenario
+        self.chaos = scenario.chaos.clone().filter(|c| c.enabled && c.percentage > 0);
+        if let Some(chaos) = &self.chaos {
+            self.trace(log::Level::Warn, format!("Chaos mode enabled: faulting {}% of requests", chaos.percentage));
+        }
 
         // Initialize variables from scenario
         if let Some(vars) = scenario.variables.as_object() {
@@ -90,9 +620,43 @@ impl ScenarioExecutor {
         let total_steps = enabled_steps.len() as u32;
         log::info!("[Executor] Total enabled steps: {} (out of {})", total_steps, steps.len());
 
+        // Validate CSV bindings and step references before firing any request
+        let validation = self.validate_csv_bindings(&enabled_steps)
+            .and_then(|_| validate_step_references(steps));
+        if let Err(e) = validation {
+            self.trace(log::Level::Error, format!("Scenario validation failed: {}", e));
+            let run = TestScenarioRun {
+                id: run_id.clone(),
+                scenario_id: scenario.id.clone(),
+                status: ScenarioRunStatus::Error,
+                total_steps: 0,
+                passed_steps: 0,
+                failed_steps: 0,
+                skipped_steps: 0,
+                duration_ms: Some(start_time.elapsed().as_millis() as u64),
+                started_at,
+                completed_at: Some(chrono::Utc::now().timestamp()),
+                error_message: Some(e),
+                results: Vec::new(),
+                variables: self.variables.clone(),
+                seed: Some(self.seed),
+                groups: Vec::new(),
+                snapshot_updates: Vec::new(),
+                logs: self.run_log_entries(),
+                trigger: RunTrigger::Manual,
+            };
+            if let Some(events) = events {
+                let _ = events.emit("scenario-completed", ScenarioCompletedEvent {
+                    run_id: run_id.clone(),
+                    run: run.clone(),
+                });
+            }
+            return run;
+        }
+
         // Emit scenario started event
-        if let Some(app) = app_handle {
-            let _ = app.emit(
+        if let Some(events) = events {
+            let _ = events.emit(
                 "scenario-started",
                 ScenarioStartedEvent {
                     run_id: run_id.clone(),
@@ -109,9 +673,77 @@ impl ScenarioExecutor {
         let mut skipped_steps = 0u32;
         let mut error_message: Option<String> = None;
 
+        // Session login runs first; every step is skipped when it fails
+        let mut login_failed = false;
+        if let Some(login) = scenario.session_login.as_ref().filter(|_| !self.dry_run) {
+            let login_result = self.perform_session_login(login);
+            if login_result.status == StepResultStatus::Passed {
+                passed_steps += 1;
+            } else {
+                login_failed = true;
+                failed_steps += 1;
+                error_message = login_result.error.clone();
+            }
+            results.push(login_result);
+        }
+
+        let mut step_limit_reached = false;
         for (index, step) in enabled_steps.iter().enumerate() {
             let step_index = index as u32;
 
+            if self.is_cancelled() {
+                log::info!("[Executor] Run {} stopped before step {}", run_id, step.name);
+                break;
+            }
+            if results.len() >= self.max_executed_steps {
+                step_limit_reached = true;
+                break;
+            }
+
+            // Runtime guard: skip the step when its enabled_if expression is false or login failed
+            let guard_failed = step.enabled_if.as_ref()
+                .is_some_and(|expression| !self.evaluate_enabled_if(expression));
+            if login_failed || guard_failed {
+                if login_failed {
+                    self.trace(log::Level::Info, format!("Skipping step {}: session login failed", step.name));
+                } else {
+                    self.trace(log::Level::Info, format!("Skipping step {}: enabled_if '{}' is false", step.name,
+                        step.enabled_if.as_deref().unwrap_or("")));
+                }
+                skipped_steps += 1;
+                let step_result = TestStepResult {
+                    step_id: step.id.clone(),
+                    name: step.name.clone(),
+                    step_type: step.step_type.clone(),
+                    status: StepResultStatus::Skipped,
+                    duration_ms: Some(0),
+                    request: None,
+                    response: None,
+                    assertions: None,
+                    error: None,
+                    extracted_variables: None,
+                    group: step.group.clone(),
+                    assertion_summary: None,
+                };
+                results.push(step_result.clone());
+
+                if let Some(events) = events {
+                    let progress_percentage = ((index + 1) as f64 / total_steps as f64) * 100.0;
+                    let _ = events.emit(
+                        "step-completed",
+                        StepCompletedEvent {
+                            run_id: run_id.clone(),
+                            step_id: step.id.clone(),
+                            step_index,
+                            status: step_result.status.as_str().to_string(),
+                            result: step_result,
+                            progress_percentage,
+                        },
+                    );
+                }
+                continue;
+            }
+
             // Check if step has CSV config for expansion
             let csv_records = if step.step_type == TestStepType::Request {
                 if let Ok(config) = serde_json::from_value::<RequestStepConfig>(step.config.clone()) {
@@ -124,7 +756,7 @@ impl ScenarioExecutor {
                                 Some(records)
                             },
                             Err(e) => {
-                                log::error!("[Executor] Failed to read CSV: {}", e);
+                                self.trace(log::Level::Error, format!("Failed to read CSV: {}", e));
                                 error_message = Some(format!("Failed to read CSV: {}", e));
                                 None
                             }
@@ -143,8 +775,16 @@ impl ScenarioExecutor {
             if let Some(records) = csv_records {
                 // Execute step for each CSV row
                 for (csv_index, record) in records.iter().enumerate() {
-                    log::info!("[Executor] Executing step {}/{} (CSV row {}): {} ({})", 
-                        step_index + 1, total_steps, csv_index, step.name, step.step_type.as_str());
+                    if self.is_cancelled() {
+                        break;
+                    }
+                    if results.len() >= self.max_executed_steps {
+                        step_limit_reached = true;
+                        break;
+                    }
+
+                    self.trace(log::Level::Info, format!("Executing step {}/{} (CSV row {}): {} ({})", 
+                        step_index + 1, total_steps, csv_index, step.name, step.step_type.as_str()));
 
                     // Set CSV-specific variables
                     let mut item_obj = serde_json::Map::new();
@@ -155,8 +795,8 @@ impl ScenarioExecutor {
                     self.variables.insert("index".to_string(), serde_json::Value::Number(csv_index.into()));
 
                     // Emit step started event
-                    if let Some(app) = app_handle {
-                        let _ = app.emit(
+                    if let Some(events) = events {
+                        let _ = events.emit(
                             "step-started",
                             StepStartedEvent {
                                 run_id: run_id.clone(),
@@ -164,6 +804,7 @@ impl ScenarioExecutor {
                                 step_index,
                                 step_name: format!("{} (row {})", step.name, csv_index),
                                 step_type: step.step_type.as_str().to_string(),
+                                group: step.group.clone(),
                             },
                         );
                     }
@@ -173,25 +814,25 @@ impl ScenarioExecutor {
                     match step_result.status {
                         StepResultStatus::Passed => {
                             passed_steps += 1;
-                            log::info!("[Executor] Step {} (CSV row {}) passed (duration: {}ms)", 
-                                step.name, csv_index, step_result.duration_ms.unwrap_or(0));
+                            self.trace(log::Level::Info, format!("Step {} (CSV row {}) passed (duration: {}ms)", 
+                                step.name, csv_index, step_result.duration_ms.unwrap_or(0)));
                         },
                         StepResultStatus::Failed => {
                             failed_steps += 1;
-                            log::warn!("[Executor] Step {} (CSV row {}) failed: {:?}", 
-                                step.name, csv_index, step_result.error);
+                            self.trace(log::Level::Warn, format!("Step {} (CSV row {}) failed: {:?}", 
+                                step.name, csv_index, step_result.error));
                             if error_message.is_none() {
                                 error_message = step_result.error.clone();
                             }
                         }
                         StepResultStatus::Skipped => {
                             skipped_steps += 1;
-                            log::info!("[Executor] Step {} (CSV row {}) skipped", step.name, csv_index);
+                            self.trace(log::Level::Info, format!("Step {} (CSV row {}) skipped", step.name, csv_index));
                         },
                         StepResultStatus::Error => {
                             failed_steps += 1;
-                            log::error!("[Executor] Step {} (CSV row {}) error: {:?}", 
-                                step.name, csv_index, step_result.error);
+                            self.trace(log::Level::Error, format!("Step {} (CSV row {}) error: {:?}", 
+                                step.name, csv_index, step_result.error));
                             if error_message.is_none() {
                                 error_message = step_result.error.clone();
                             }
@@ -209,10 +850,10 @@ impl ScenarioExecutor {
                     results.push(step_result.clone());
 
                     // Emit step completed event
-                    if let Some(app) = app_handle {
+                    if let Some(events) = events {
                         let completed_count = results.len() as u32;
                         let progress_percentage = (completed_count as f64 / total_steps as f64) * 100.0;
-                        let _ = app.emit(
+                        let _ = events.emit(
                             "step-completed",
                             StepCompletedEvent {
                                 run_id: run_id.clone(),
@@ -231,12 +872,12 @@ impl ScenarioExecutor {
                 self.variables.remove("index");
             } else {
                 // Execute step normally (no CSV)
-                log::info!("[Executor] Executing step {}/{}: {} ({})", 
-                    step_index + 1, total_steps, step.name, step.step_type.as_str());
+                self.trace(log::Level::Info, format!("Executing step {}/{}: {} ({})", 
+                    step_index + 1, total_steps, step.name, step.step_type.as_str()));
 
                 // Emit step started event
-                if let Some(app) = app_handle {
-                    let _ = app.emit(
+                if let Some(events) = events {
+                    let _ = events.emit(
                         "step-started",
                         StepStartedEvent {
                             run_id: run_id.clone(),
@@ -244,6 +885,7 @@ impl ScenarioExecutor {
                             step_index,
                             step_name: step.name.clone(),
                             step_type: step.step_type.as_str().to_string(),
+                            group: step.group.clone(),
                         },
                     );
                 }
@@ -253,23 +895,23 @@ impl ScenarioExecutor {
                 match step_result.status {
                     StepResultStatus::Passed => {
                         passed_steps += 1;
-                        log::info!("[Executor] Step {} passed (duration: {}ms)", step.name, 
-                            step_result.duration_ms.unwrap_or(0));
+                        self.trace(log::Level::Info, format!("Step {} passed (duration: {}ms)", step.name, 
+                            step_result.duration_ms.unwrap_or(0)));
                     },
                     StepResultStatus::Failed => {
                         failed_steps += 1;
-                        log::warn!("[Executor] Step {} failed: {:?}", step.name, step_result.error);
+                        self.trace(log::Level::Warn, format!("Step {} failed: {:?}", step.name, step_result.error));
                         if error_message.is_none() {
                             error_message = step_result.error.clone();
                         }
                     }
                     StepResultStatus::Skipped => {
                         skipped_steps += 1;
-                        log::info!("[Executor] Step {} skipped", step.name);
+                        self.trace(log::Level::Info, format!("Step {} skipped", step.name));
                     },
                     StepResultStatus::Error => {
                         failed_steps += 1;
-                        log::error!("[Executor] Step {} error: {:?}", step.name, step_result.error);
+                        self.trace(log::Level::Error, format!("Step {} error: {:?}", step.name, step_result.error));
                         if error_message.is_none() {
                             error_message = step_result.error.clone();
                         }
@@ -287,10 +929,10 @@ impl ScenarioExecutor {
                 results.push(step_result.clone());
 
                 // Emit step completed event
-                if let Some(app) = app_handle {
+                if let Some(events) = events {
                     let completed_count = (index + 1) as u32;
                     let progress_percentage = (completed_count as f64 / total_steps as f64) * 100.0;
-                    let _ = app.emit(
+                    let _ = events.emit(
                         "step-completed",
                         StepCompletedEvent {
                             run_id: run_id.clone(),
@@ -308,16 +950,31 @@ impl ScenarioExecutor {
         let duration_ms = start_time.elapsed().as_millis() as u64;
         let completed_at = chrono::Utc::now().timestamp();
 
-        let status = if failed_steps > 0 {
-            log::warn!("[Executor] Scenario completed with failures: {}/{} passed, {}/{} failed", 
-                passed_steps, total_steps, failed_steps, total_steps);
+        let status = if step_limit_reached {
+            self.trace(log::Level::Error, format!("Run {} hit the limit of {} executed steps", run_id, self.max_executed_steps));
+            error_message = Some(format!(
+                "Run stopped: exceeded the limit of {} executed steps",
+                self.max_executed_steps
+            ));
+            ScenarioRunStatus::Error
+        } else if self.is_cancelled() {
+            self.trace(log::Level::Warn, format!("Scenario stopped after {} result(s) ({}ms)", results.len(), duration_ms));
+            if error_message.is_none() {
+                error_message = Some("Run stopped".to_string());
+            }
+            ScenarioRunStatus::Stopped
+        } else if failed_steps > 0 {
+            self.trace(log::Level::Warn, format!("Scenario completed with failures: {}/{} passed, {}/{} failed", 
+                passed_steps, total_steps, failed_steps, total_steps));
             ScenarioRunStatus::Failed
         } else {
-            log::info!("[Executor] Scenario completed successfully: {}/{} passed ({}ms)", 
-                passed_steps, total_steps, duration_ms);
+            self.trace(log::Level::Info, format!("Scenario completed successfully: {}/{} passed ({}ms)", 
+                passed_steps, total_steps, duration_ms));
             ScenarioRunStatus::Passed
         };
 
+        let groups = StepGroupSummary::from_results(&results);
+
         let run = TestScenarioRun {
             id: run_id.clone(),
             scenario_id: scenario.id.clone(),
@@ -332,11 +989,16 @@ impl ScenarioExecutor {
             error_message,
             results,
             variables: self.variables.clone(),
+            seed: Some(self.seed),
+            groups,
+            snapshot_updates: std::mem::take(&mut self.snapshot_updates),
+            logs: self.run_log_entries(),
+            trigger: RunTrigger::Manual,
         };
 
         // Emit scenario completed event
-        if let Some(app) = app_handle {
-            let _ = app.emit("scenario-completed", ScenarioCompletedEvent {
+        if let Some(events) = events {
+            let _ = events.emit("scenario-completed", ScenarioCompletedEvent {
                 run_id: run_id.clone(),
                 run: run.clone(),
             });
@@ -345,6 +1007,59 @@ impl ScenarioExecutor {
         run
     }
 
+    /// Check that every `{{ item.<col> }}` used by a CSV-driven step exists in the CSV header row
+    fn validate_csv_bindings(&self, steps: &[&TestScenarioStep]) -> Result<(), String> {
+        let mut errors = Vec::new();
+
+        for step in steps {
+            if step.step_type != TestStepType::Request {
+                continue;
+            }
+            let config: RequestStepConfig = match serde_json::from_value(step.config.clone()) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let csv_config = match config.with_items_from_csv {
+                Some(ref c) => c,
+                None => continue,
+            };
+
+            let mut text = config.url.clone();
+            if let Some(ref headers) = config.headers {
+                for (k, v) in headers {
+                    text.push_str(k);
+                    text.push_str(v);
+                }
+            }
+            if let Some(ref params) = config.params {
+                text.push_str(&params.to_string());
+            }
+            if let Some(ref body) = config.body {
+                text.push_str(&body.to_string());
+            }
+
+            let referenced = super::csv_reader::find_item_references(&text);
+            if referenced.is_empty() {
+                continue;
+            }
+
+            let headers = super::csv_reader::read_csv_headers(&csv_config.file_name, csv_config)
+                .map_err(|e| format!("Step '{}': failed to read CSV headers: {}", step.name, e))?;
+            log::debug!("[Executor] Step {} references CSV columns {:?}, headers {:?}",
+                step.name, referenced, headers);
+
+            if let Err(e) = super::csv_reader::validate_item_columns(&csv_config.file_name, &headers, &referenced) {
+                errors.push(format!("Step '{}': {}", step.name, e));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
     /// Execute a single step
     fn execute_step(&mut self, step: &TestScenarioStep) -> TestStepResult {
         let start_time = Instant::now();
@@ -371,6 +1086,10 @@ impl ScenarioExecutor {
                 log::debug!("[Executor] Step type: Loop");
                 self.execute_loop_step(step)
             },
+            TestStepType::Grpc => {
+                log::debug!("[Executor] Step type: Grpc");
+                self.execute_grpc_step(step)
+            },
         };
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
@@ -381,6 +1100,7 @@ impl ScenarioExecutor {
             name: step.name.clone(),
             step_type: step.step_type.clone(),
             duration_ms: Some(duration_ms),
+            group: step.group.clone(),
             ..result
         }
     }
@@ -409,40 +1129,87 @@ impl ScenarioExecutor {
                     assertions: None,
                     error: Some(error_msg),
                     extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
+                };
+            }
+        };
+
+        // Load the body file up front so a bad path fails before any request is sent
+        let file_body = match load_body_file(&config) {
+            Ok(body) => body,
+            Err(e) => {
+                log::error!("[Executor] {}", e);
+                return TestStepResult {
+                    step_id: step.id.clone(),
+                    name: step.name.clone(),
+                    step_type: step.step_type.clone(),
+                    status: StepResultStatus::Error,
+                    duration_ms: None,
+                    request: None,
+                    response: None,
+                    assertions: None,
+                    error: Some(e),
+                    extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
                 };
             }
         };
 
         // Resolve variables in URL
         let original_url = config.url.clone();
-        let url_after_vars = self.resolve_variables(&config.url);
+        let url_after_vars = self.resolve_url_variables(&config.url);
         
-        // Resolve URL with base URL if needed
-        let url = self.resolve_url(&url_after_vars);
+        if let Some(service) = config.service.as_deref() {
+            if !self.services.contains_key(service) {
+                let error = format!("Unknown service: {}", service);
+                self.trace(log::Level::Error, error.to_string());
+                return TestStepResult {
+                    step_id: step.id.clone(),
+                    name: step.name.clone(),
+                    step_type: step.step_type.clone(),
+                    status: StepResultStatus::Error,
+                    duration_ms: None,
+                    request: None,
+                    response: None,
+                    assertions: None,
+                    error: Some(error),
+                    extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
+                };
+            }
+        }
+
+        // Resolve URL with base URL (or the step's service base URL) if needed
+        let url = self.resolve_url(&url_after_vars, config.service.as_deref());
         let method = config.method.to_uppercase();
         
         if original_url != url_after_vars {
-            log::debug!("[Executor] URL after variable resolution: {} -> {}", original_url, url_after_vars);
+            self.trace(log::Level::Debug, format!("URL after variable resolution: {} -> {}", original_url, url_after_vars));
         }
         if url_after_vars != url {
-            log::debug!("[Executor] URL after base URL resolution: {} -> {}", url_after_vars, url);
+            self.trace(log::Level::Debug, format!("URL after base URL resolution: {} -> {}", url_after_vars, url));
         }
-        log::info!("[Executor] Request: {} {}", method, url);
+        self.trace(log::Level::Info, format!("Request: {} {}", method, url));
 
         let mut request_headers = HashMap::new();
         let mut request_body = None;
+        let mut sent_body = String::new(); // Exact body text sent, for request signing
 
-        // Build request
+        // Build request (3xx responses are returned as-is when redirects are disabled)
         log::debug!("[Executor] Building {} request", method);
+        let client = self.client_for_redirects(config.follow_redirects, config.max_redirects);
         let mut req = match method.as_str() {
-            "GET" => self.client.get(&url),
-            "POST" => self.client.post(&url),
-            "PUT" => self.client.put(&url),
-            "DELETE" => self.client.delete(&url),
-            "PATCH" => self.client.patch(&url),
+            "GET" => client.get(&url),
+            "POST" => client.post(&url),
+            "PUT" => client.put(&url),
+            "DELETE" => client.delete(&url),
+            "PATCH" => client.patch(&url),
             _ => {
                 let error_msg = format!("Unsupported method: {}", method);
-                log::error!("[Executor] {}", error_msg);
+                self.trace(log::Level::Error, error_msg.to_string());
                 return TestStepResult {
                     step_id: step.id.clone(),
                     name: step.name.clone(),
@@ -454,14 +1221,40 @@ impl ScenarioExecutor {
                     assertions: None,
                     error: Some(error_msg),
                     extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
                 };
             }
         };
 
-        // Add headers with variable resolution
-        if let Some(headers) = &config.headers {
+        // Structured query parameters, sorted by name and URL-encoded by reqwest
+        let query: Vec<(String, String)> = config.query.iter().flatten()
+            .map(|(k, v)| (k.clone(), self.resolve_variables(v)))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect();
+        if !query.is_empty() {
+            log::debug!("[Executor] Adding {} query parameters", query.len());
+            req = req.query(&query);
+        }
+        let url = url_with_query(&url, &query);
+
+        let chaos_fault = self.pick_fault(&config, &method);
+        let mut injected_fault = None;
+
+        // Add headers with variable resolution
+        if let Some(headers) = &config.headers {
             log::debug!("[Executor] Adding {} headers", headers.len());
             for (k, v) in headers {
+                if let Some((ChaosFaultKind::DropHeader, Some(dropped))) = &chaos_fault {
+                    if k == dropped {
+                        injected_fault = Some(InjectedFault {
+                            kind: ChaosFaultKind::DropHeader,
+                            detail: format!("dropped header {}", k),
+                        });
+                        continue;
+                    }
+                }
                 let resolved_value = self.resolve_variables(v);
                 log::debug!("[Executor] Header: {} = {}", k, resolved_value);
                 req = req.header(k, &resolved_value);
@@ -471,35 +1264,149 @@ impl ScenarioExecutor {
             log::debug!("[Executor] No custom headers provided");
         }
 
+        // The client adds the jar's cookies for this URL unless the step sets its own Cookie
+        // header; record what it will send
+        let has_cookie_header = config.headers.as_ref()
+            .is_some_and(|h| h.keys().any(|k| k.eq_ignore_ascii_case("cookie")));
+        if let (false, Some(cookie)) = (has_cookie_header, self.cookie_header(&url)) {
+            request_headers.insert("Cookie".to_string(), cookie);
+        }
+
+        // One key per step execution, recorded so it can be traced; a key set by the step wins
+        if let Some(idempotency) = self.idempotency.as_ref().filter(|i| i.applies_to(&method)) {
+            let has_key_header = request_headers.keys().any(|k| k.eq_ignore_ascii_case(&idempotency.header_name));
+            if !has_key_header {
+                let key = uuid::Uuid::new_v4().to_string();
+                req = req.header(&idempotency.header_name, &key);
+                request_headers.insert(idempotency.header_name.clone(), key);
+            }
+        }
+
         // Add body with variable resolution
         if method != "GET" {
-            if let Some(body) = &config.body {
+            if let Some(body) = file_body.as_ref().or(config.body.as_ref()) {
                 let resolved_body = self.resolve_variables_in_json(body);
                 log::debug!("[Executor] Adding JSON body: {}", 
                     serde_json::to_string(&resolved_body).unwrap_or_else(|_| "invalid json".to_string()));
                 req = req.json(&resolved_body);
+                sent_body = serde_json::to_string(&resolved_body).unwrap_or_default();
                 request_body = Some(resolved_body);
             } else if let Some(params) = &config.params {
                 let resolved_params = self.resolve_variables_in_json(params);
                 log::debug!("[Executor] Adding JSON params: {}", 
                     serde_json::to_string(&resolved_params).unwrap_or_else(|_| "invalid json".to_string()));
                 req = req.json(&resolved_params);
+                sent_body = serde_json::to_string(&resolved_params).unwrap_or_default();
                 request_body = Some(resolved_params);
             } else {
                 log::debug!("[Executor] No body or params for {} request", method);
             }
         }
 
-        // Create StepRequest object
+        // Chaos: send only the first half of the serialized body
+        if let Some((ChaosFaultKind::TruncateBody, _)) = &chaos_fault {
+            if let Some(resolved) = &request_body {
+                let full = serde_json::to_string(resolved).unwrap_or_default();
+                let mut cut = full.len() / 2;
+                while !full.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                let partial = full[..cut].to_string();
+                req = req.body(partial.clone());
+                sent_body = partial.clone();
+                injected_fault = Some(InjectedFault {
+                    kind: ChaosFaultKind::TruncateBody,
+                    detail: format!("sent {} of {} body bytes", cut, full.len()),
+                });
+                request_body = Some(serde_json::Value::String(partial));
+            }
+        }
+
+        // Sign the finalized request; the secret itself is never logged or recorded
+        if let Some(signing) = &self.request_signing {
+            let parsed_url = reqwest::Url::parse(&url).ok();
+            let timestamp = chrono::Utc::now().timestamp();
+            let input = SigningInput {
+                method: &method,
+                path: parsed_url.as_ref().map(|u| u.path()).unwrap_or(""),
+                query: parsed_url.as_ref().and_then(|u| u.query()).unwrap_or(""),
+                body: &sent_body,
+                timestamp,
+            };
+            match request_signing::sign(signing, &input) {
+                Ok(signature) => {
+                    if let Some(timestamp_header) = &signing.timestamp_header {
+                        req = req.header(timestamp_header, timestamp.to_string());
+                        request_headers.insert(timestamp_header.clone(), timestamp.to_string());
+                    }
+                    log::debug!("[Executor] Signed request with {} ({})", signing.header_name, signing.algorithm);
+                    req = req.header(&signing.header_name, &signature);
+                    request_headers.insert(signing.header_name.clone(), signature);
+                }
+                Err(e) => {
+                    self.trace(log::Level::Error, format!("Request signing failed: {}", e));
+                    return TestStepResult {
+                        step_id: step.id.clone(),
+                        name: step.name.clone(),
+                        step_type: step.step_type.clone(),
+                        status: StepResultStatus::Error,
+                        duration_ms: None,
+                        request: None,
+                        response: None,
+                        assertions: None,
+                        error: Some(format!("Request signing failed: {}", e)),
+                        extracted_variables: None,
+                        group: None,
+                        assertion_summary: None,
+                    };
+                }
+            }
+        }
+
+        // Chaos: hold the request back before sending (not counted in the step duration)
+        if let Some((ChaosFaultKind::Latency, _)) = chaos_fault.as_ref().filter(|_| !self.dry_run) {
+            let latency_ms = self.chaos.as_ref().map(|c| c.latency_ms).unwrap_or(0);
+            std::thread::sleep(Duration::from_millis(latency_ms));
+            injected_fault = Some(InjectedFault {
+                kind: ChaosFaultKind::Latency,
+                detail: format!("delayed request by {}ms", latency_ms),
+            });
+        }
+
+        if let Some(fault) = &injected_fault {
+            self.trace(log::Level::Warn, format!("Chaos fault injected into '{}': {}", step.name, fault.detail));
+        }
+
+        // Create StepRequest object (body trimmed for storage, the request itself is already built)
         let step_request = StepRequest {
             method: method.clone(),
             url: url.clone(),
             headers: request_headers,
-            body: request_body,
+            body: request_body.map(|b| truncate_body(&b, self.max_request_body_bytes).0),
+            fault: injected_fault,
         };
 
+        if self.dry_run {
+            self.trace(log::Level::Info, format!("Dry run: not sending {} request to {}", method, url));
+            return TestStepResult {
+                step_id: step.id.clone(),
+                name: step.name.clone(),
+                step_type: step.step_type.clone(),
+                status: StepResultStatus::Skipped,
+                duration_ms: None,
+                request: Some(step_request),
+                response: None,
+                assertions: None,
+                error: None,
+                extracted_variables: None,
+                group: None,
+                assertion_summary: None,
+            };
+        }
+
         // Execute request
         log::info!("[Executor] Sending {} request to {}", method, url);
+        self.take_redirect_hops();
         let start = Instant::now();
         let response = match req.send() {
             Ok(resp) => {
@@ -509,18 +1416,33 @@ impl ScenarioExecutor {
             },
             Err(e) => {
                 let duration_ms = start.elapsed().as_millis() as u64;
-                let error_msg = format!("Request failed: {}", e);
-                log::error!("[Executor] Request failed after {}ms: {}", duration_ms, error_msg);
+                let error_msg = if is_tls_error(&e) {
+                    format!(
+                        "TLS certificate verification failed for {}: {}. Fix the server certificate, or disable TLS verification for this project if the host uses a self-signed certificate.",
+                        url, get_error_chain(&e)
+                    )
+                } else {
+                    format!("Request failed: {}", e)
+                };
+                // Keep the hops of a failed redirect chain (e.g. a login loop) in the message
+                let hops = self.take_redirect_hops();
+                let error_msg = if hops.is_empty() {
+                    error_msg
+                } else {
+                    format!("{} (redirect chain: {})", error_msg, format_redirect_chain(&hops))
+                };
+                self.capture_exchange(&step.name, &step_request, None, Some(&error_msg));
+                self.trace(log::Level::Error, format!("Request failed after {}ms: {}", duration_ms, error_msg));
                 log::error!("[Executor] Error chain: {}", get_error_chain(&e));
                 log::error!("[Executor] Request URL: {}", url);
                 log::error!("[Executor] Request method: {}", method);
                 
                 // Check if it's a timeout
                 if e.is_timeout() {
-                    log::warn!("[Executor] Request timeout after {}ms", duration_ms);
+                    self.trace(log::Level::Warn, format!("Request timeout after {}ms", duration_ms));
                 }
                 if e.is_connect() {
-                    log::error!("[Executor] Connection error - server may be unreachable");
+                    self.trace(log::Level::Error, "Connection error - server may be unreachable".to_string());
                 }
                 
                 return TestStepResult {
@@ -534,15 +1456,22 @@ impl ScenarioExecutor {
                     assertions: None,
                     error: Some(error_msg),
                     extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
                 };
             }
         };
         let duration_ms = start.elapsed().as_millis() as u64;
+        let redirects = self.take_redirect_hops();
 
         let status_code = response.status().as_u16();
         let status_text = response.status().to_string();
+        let final_url = response.url().to_string();
         
-        log::info!("[Executor] Response received: {} {} (duration: {}ms)", status_code, status_text, duration_ms);
+        self.trace(log::Level::Info, format!("Response received: {} {} (duration: {}ms)", status_code, status_text, duration_ms));
+        if final_url != url {
+            self.trace(log::Level::Info, format!("Redirected to final URL: {}", final_url));
+        }
         
         let mut response_headers = HashMap::new();
         for (k, v) in response.headers() {
@@ -553,7 +1482,19 @@ impl ScenarioExecutor {
         }
 
         log::debug!("[Executor] Reading response body");
-        let body_text = response.text().unwrap_or_default();
+        let read = read_response_body(response, MAX_RESPONSE_READ_BYTES);
+        if let Some(content_encoding) = &read.content_encoding {
+            log::debug!("[Executor] Decoded {} body: {} -> {} bytes",
+                content_encoding, read.encoded_size, read.decoded_size);
+        }
+        let body_text = read.text;
+        let body_truncated = read.truncated;
+        if body_truncated {
+            self.trace(log::Level::Warn, format!(
+                "Response body exceeds {} bytes; only the first {} bytes were read",
+                MAX_RESPONSE_READ_BYTES, MAX_RESPONSE_READ_BYTES
+            ));
+        }
         let body_text_for_preview = body_text.clone();
         let body: serde_json::Value = serde_json::from_str(&body_text)
             .unwrap_or_else(|_| serde_json::Value::String(body_text.clone()));
@@ -568,39 +1509,46 @@ impl ScenarioExecutor {
             headers: response_headers.clone(),
             body: body.clone(),
             duration_ms,
+            truncated: body_truncated,
+            final_url: Some(final_url),
+            content_type: find_header(&response_headers, "content-type").cloned(),
+            redirects,
+            server_timing: find_header(&response_headers, "server-timing")
+                .map(|v| parse_server_timing(v))
+                .unwrap_or_default(),
+            content_encoding: read.content_encoding,
+            decoded_size: read.decoded_size,
+            raw_body: body_text,
         };
 
-        // Extract variables
-        let mut extracted_variables = HashMap::new();
-        if let Some(extractors) = &config.extract_variables {
-            log::debug!("[Executor] Extracting {} variables", extractors.len());
-            for extractor in extractors {
-                let value = self.extract_variable(extractor, &step_response);
-                log::debug!("[Executor] Extracted variable: {} = {:?}", extractor.name, value);
-                extracted_variables.insert(extractor.name.clone(), value);
-            }
-        }
+        let (extracted_variables, assertions_results) = self.check_response(
+            step,
+            config.extract_variables.as_deref(),
+            config.assertions.as_deref(),
+            &step_response,
+        );
 
-        // Run assertions
-        let mut assertions_results = Vec::new();
-        let mut all_passed = true;
-        
-        if let Some(assertions) = &config.assertions {
-            for assertion in assertions {
-                let result = self.evaluate_assertion(assertion, &step_response, duration_ms);
-                if result.passed != Some(true) {
-                    all_passed = false;
-                }
-                assertions_results.push(result);
-            }
+        // Failed `warn` assertions are counted but leave the step passing
+        let summary = AssertionSummary::from_assertions(&assertions_results);
+        if summary.failed_warnings > 0 {
+            self.trace(log::Level::Info, format!("Step {}: {} warning assertion(s) failed", step.name, summary.failed_warnings));
         }
-
-        let status = if all_passed {
+        let status = if summary.failed_errors == 0 {
             StepResultStatus::Passed
         } else {
             StepResultStatus::Failed
         };
 
+        // Extraction and assertions ran on the full body; only the stored copy is trimmed
+        let mut step_response = step_response;
+        let (stored_body, truncated) = truncate_body(&step_response.body, self.max_response_body_bytes);
+        if truncated {
+            log::info!("[Executor] Response body truncated to {} bytes for storage", self.max_response_body_bytes);
+            step_response.body = stored_body;
+            step_response.truncated = true;
+        }
+        self.capture_exchange(&step.name, &step_request, Some(&step_response), None);
+
         TestStepResult {
             step_id: step.id.clone(),
             name: step.name.clone(),
@@ -609,10 +1557,40 @@ impl ScenarioExecutor {
             duration_ms: Some(duration_ms),
             request: Some(step_request),
             response: Some(step_response),
+            assertion_summary: (!assertions_results.is_empty()).then_some(summary),
             assertions: Some(assertions_results),
             error: None,
             extracted_variables: Some(extracted_variables),
+            group: None,
+        }
+    }
+
+    /// Run a step's extractors, then its assertions against the full response
+    fn check_response(
+        &mut self,
+        step: &TestScenarioStep,
+        extractors: Option<&[VariableExtractor]>,
+        assertions: Option<&[Assertion]>,
+        response: &StepResponse,
+    ) -> (HashMap<String, serde_json::Value>, Vec<Assertion>) {
+        let mut extracted_variables = HashMap::new();
+        if let Some(extractors) = extractors {
+            log::debug!("[Executor] Extracting {} variables", extractors.len());
+            for extractor in extractors {
+                let value = self.extract_variable(extractor, response);
+                self.trace(log::Level::Debug, format!("Extracted variable: {} = {:?}", extractor.name, value));
+                extracted_variables.insert(extractor.name.clone(), value);
+            }
+        }
+
+        let mut assertions_results = Vec::new();
+        if let Some(assertions) = assertions {
+            for assertion in self.expand_assertions(assertions) {
+                let result = self.run_assertion(assertion, response, response.duration_ms, &extracted_variables, &step.id);
+                assertions_results.push(result);
+            }
         }
+        (extracted_variables, assertions_results)
     }
 
     /// Execute a delay step
@@ -631,23 +1609,37 @@ impl ScenarioExecutor {
                     assertions: None,
                     error: Some(format!("Invalid delay config: {}", e)),
                     extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
                 };
             }
         };
 
-        std::thread::sleep(Duration::from_millis(config.duration_ms));
+        // Sleep in slices so a stop request does not wait out the whole delay
+        let delay = Duration::from_millis(config.duration_ms);
+        let started = Instant::now();
+        while !self.is_cancelled() {
+            let remaining = delay.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                break;
+            }
+            std::thread::sleep(remaining.min(CANCEL_POLL_INTERVAL));
+        }
+        let stopped = self.is_cancelled() && started.elapsed() < delay;
 
         TestStepResult {
             step_id: step.id.clone(),
             name: step.name.clone(),
             step_type: step.step_type.clone(),
-            status: StepResultStatus::Passed,
-            duration_ms: Some(config.duration_ms),
+            status: if stopped { StepResultStatus::Skipped } else { StepResultStatus::Passed },
+            duration_ms: Some(started.elapsed().as_millis() as u64),
             request: None,
             response: None,
             assertions: None,
             error: None,
             extracted_variables: None,
+            group: None,
+            assertion_summary: None,
         }
     }
 
@@ -667,6 +1659,8 @@ impl ScenarioExecutor {
                     assertions: None,
                     error: Some(format!("Invalid script config: {}", e)),
                     extracted_variables: None,
+                    group: None,
+                    assertion_summary: None,
                 };
             }
         };
@@ -685,6 +1679,151 @@ impl ScenarioExecutor {
             assertions: None,
             error: None,
             extracted_variables: None,
+            group: None,
+            assertion_summary: None,
+        }
+    }
+
+    /// Execute a unary gRPC step. The response maps onto a StepResponse: `status` is the gRPC
+    /// code (0 = OK), `statusText` its name, `headers` the response metadata and trailers
+    /// (including `grpc-status`/`grpc-message`) and `body` the response message as JSON.
+    /// A non-OK status fails the step unless the step asserts on the outcome itself.
+    fn execute_grpc_step(&mut self, step: &TestScenarioStep) -> TestStepResult {
+        let error_result = |error: String, request: Option<StepRequest>| TestStepResult {
+            step_id: step.id.clone(),
+            name: step.name.clone(),
+            step_type: step.step_type.clone(),
+            status: StepResultStatus::Error,
+            duration_ms: None,
+            request,
+            response: None,
+            assertions: None,
+            error: Some(error),
+            extracted_variables: None,
+            group: None,
+            assertion_summary: None,
+        };
+
+        let config: GrpcStepConfig = match serde_json::from_value(step.config.clone()) {
+            Ok(c) => c,
+            Err(e) => return error_result(format!("Invalid gRPC config: {}", e), None),
+        };
+        if config.proto_file.is_none() && config.descriptor_set.is_none() {
+            return error_result("gRPC step needs either protoFile or descriptorSet".to_string(), None);
+        }
+
+        let endpoint = self.resolve_variables(&config.endpoint);
+        let message = self.resolve_variables_in_json(&config.message);
+        let metadata: HashMap<String, String> = config.metadata.iter().flatten()
+            .map(|(k, v)| (k.clone(), self.resolve_variables(v)))
+            .collect();
+        let step_request = StepRequest {
+            method: "GRPC".to_string(),
+            url: format!("{}/{}/{}", endpoint.trim_end_matches('/'), config.service, config.method),
+            headers: metadata.clone(),
+            body: Some(truncate_body(&message, self.max_request_body_bytes).0),
+            fault: None,
+        };
+        self.trace(log::Level::Info, format!("gRPC call: {}", step_request.url));
+
+        if self.dry_run {
+            self.trace(log::Level::Info, format!("Dry run: not calling {}", step_request.url));
+            return TestStepResult {
+                status: StepResultStatus::Skipped,
+                error: None,
+                ..error_result(String::new(), Some(step_request))
+            };
+        }
+
+        let proto_file = config.proto_file.as_deref().map(|p| self.resolve_variables(p));
+        let descriptor_set = config.descriptor_set.as_deref().map(|p| self.resolve_variables(p));
+        let method = match grpc::load_descriptors(proto_file.as_deref(), descriptor_set.as_deref())
+            .and_then(|pool| grpc::find_method(&pool, &config.service, &config.method))
+        {
+            Ok(method) => method,
+            Err(e) => {
+                self.trace(log::Level::Error, e.clone());
+                return error_result(e, Some(step_request));
+            }
+        };
+
+        let start = Instant::now();
+        let call = grpc::GrpcCall {
+            endpoint: &endpoint,
+            message: &message,
+            metadata: &metadata,
+            timeout: self.timeout,
+        };
+        let outcome = match grpc::call(&method, &call) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.capture_exchange(&step.name, &step_request, None, Some(&e));
+                self.trace(log::Level::Error, format!("gRPC call failed: {}", e));
+                return TestStepResult {
+                    duration_ms: Some(start.elapsed().as_millis() as u64),
+                    ..error_result(e, Some(step_request))
+                };
+            }
+        };
+        let duration_ms = start.elapsed().as_millis() as u64;
+        self.trace(log::Level::Info, format!("gRPC status {} ({}ms)", outcome.code_name, duration_ms));
+
+        let raw_body = match &outcome.body {
+            serde_json::Value::Null => outcome.message.clone(),
+            body => body.to_string(),
+        };
+        let step_response = StepResponse {
+            status: outcome.code as u16,
+            status_text: outcome.code_name.clone(),
+            headers: outcome.metadata.clone(),
+            body: outcome.body.clone(),
+            duration_ms,
+            truncated: false,
+            final_url: None,
+            content_type: find_header(&outcome.metadata, "content-type").cloned(),
+            redirects: Vec::new(),
+            server_timing: Vec::new(),
+            content_encoding: None,
+            decoded_size: 0,
+            raw_body,
+        };
+
+        let (extracted_variables, assertions_results) = self.check_response(
+            step,
+            config.extract_variables.as_deref(),
+            config.assertions.as_deref(),
+            &step_response,
+        );
+        let summary = AssertionSummary::from_assertions(&assertions_results);
+        let error = (outcome.code != 0 && assertions_results.is_empty())
+            .then(|| format!("gRPC status {}: {}", outcome.code_name, outcome.message));
+        let status = if summary.failed_errors == 0 && error.is_none() {
+            StepResultStatus::Passed
+        } else {
+            StepResultStatus::Failed
+        };
+
+        let mut step_response = step_response;
+        let (stored_body, truncated) = truncate_body(&step_response.body, self.max_response_body_bytes);
+        if truncated {
+            step_response.body = stored_body;
+            step_response.truncated = true;
+        }
+        self.capture_exchange(&step.name, &step_request, Some(&step_response), None);
+
+        TestStepResult {
+            step_id: step.id.clone(),
+            name: step.name.clone(),
+            step_type: step.step_type.clone(),
+            status,
+            duration_ms: Some(duration_ms),
+            request: Some(step_request),
+            response: Some(step_response),
+            assertion_summary: (!assertions_results.is_empty()).then_some(summary),
+            assertions: Some(assertions_results),
+            error,
+            extracted_variables: Some(extracted_variables),
+            group: None,
         }
     }
 
@@ -702,6 +1841,8 @@ impl ScenarioExecutor {
             assertions: None,
             error: None,
             extracted_variables: None,
+            group: None,
+            assertion_summary: None,
         }
     }
 
@@ -719,11 +1860,13 @@ impl ScenarioExecutor {
             assertions: None,
             error: None,
             extracted_variables: None,
+            group: None,
+            assertion_summary: None,
         }
     }
 
     /// Resolve URL with base URL if needed
-    fn resolve_url(&self, url: &str) -> String {
+    fn resolve_url(&self, url: &str, service: Option<&str>) -> String {
         // If URL is already absolute, use it as-is
         if url.starts_with("http://") || url.starts_with("https://") {
             log::debug!("[Executor] URL is already absolute: {}", url);
@@ -732,7 +1875,11 @@ impl ScenarioExecutor {
 
         // If URL is relative (starts with /), prepend base_url
         if url.starts_with("/") {
-            let base = match &self.base_url {
+            let base_url = match service {
+                Some(name) => self.services.get(name),
+                None => self.base_url.as_ref(),
+            };
+            let base = match base_url {
                 Some(base) => {
                     // Remove trailing slash from base_url
                     let clean_base = base.trim_end_matches('/');
@@ -759,58 +1906,152 @@ impl ScenarioExecutor {
     /// - {{ var }} - variable with spaces
     /// - {{ item.column }} - CSV row column access
     /// - {{ index }} - CSV row index
+    /// - {{$randomInt}}, {{$randomString}}, {{$randomUuid}}, {{$randomEmail}}, {{$randomBool}} - seeded dynamic tokens
     fn resolve_variables(&self, input: &str) -> String {
+        let input = &self.resolve_dynamic_tokens(input);
+
         // Support both {{ item.column }} and {{ variable }} patterns
         let re = Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap();
         let mut result = input.to_string();
 
         for cap in re.captures_iter(input) {
-            let var_path = &cap[1];
-            
-            // Check if it's a dotted path (e.g., item.column)
-            if var_path.contains('.') {
-                let parts: Vec<&str> = var_path.split('.').collect();
-                if parts.len() == 2 {
-                    let parent = parts[0];
-                    let child = parts[1];
-                    
-                    // Try to resolve item.column
-                    if let Some(parent_value) = self.variables.get(parent) {
-                        if let Some(obj) = parent_value.as_object() {
-                            if let Some(child_value) = obj.get(child) {
-                                let replacement = match child_value {
-                                    serde_json::Value::String(s) => s.clone(),
-                                    serde_json::Value::Number(n) => n.to_string(),
-                                    serde_json::Value::Bool(b) => b.to_string(),
-                                    _ => child_value.to_string(),
-                                };
-                                log::debug!("[Executor] Resolving nested variable {}: {} -> {}", var_path, cap[0].to_string(), replacement);
-                                result = result.replace(&cap[0], &replacement);
-                                continue;
-                            }
-                        }
-                    }
+            match self.variable_text(&cap[1]) {
+                Some(replacement) => {
+                    log::debug!("[Executor] Resolving variable {}: {} -> {}", &cap[1], &cap[0], replacement);
+                    result = result.replace(&cap[0], &replacement);
                 }
-            }
-            
-            // Simple variable lookup
-            if let Some(value) = self.variables.get(var_path) {
-                let replacement = match value {
-                    serde_json::Value::String(s) => s.clone(),
-                    serde_json::Value::Number(n) => n.to_string(),
-                    serde_json::Value::Bool(b) => b.to_string(),
-                    _ => value.to_string(),
-                };
-                log::debug!("[Executor] Resolving variable {}: {} -> {}", var_path, cap[0].to_string(), replacement);
-                result = result.replace(&cap[0], &replacement);
-            } else {
-                log::warn!("[Executor] Variable {} not found in context", var_path);
+                None => self.trace(log::Level::Warn, format!("Variable {} not found in context", &cap[1])),
             }
         }
 
         result
     }
 
+    /// Resolve variables in a request URL. Values placed in the path are percent-encoded as a
+    /// single segment, so ids with `/`, spaces or `?` can't change the route; placeholders
+    /// before the path (`{{ baseUrl }}`, a host) or in the query string are inserted as-is.
+    fn resolve_url_variables(&self, url: &str) -> String {
+        let input = self.resolve_dynamic_tokens(url);
+        let (path_start, path_end) = url_path_bounds(&input);
+
+        let re = Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap();
+        re.replace_all(&input, |cap: &regex::Captures| {
+            let placeholder = cap.get(0).unwrap();
+            match self.variable_text(&cap[1]) {
+                Some(text) if (path_start..path_end).contains(&placeholder.start()) => encode_path_segment(&text),
+                Some(text) => text,
+                None => {
+                    self.trace(log::Level::Warn, format!("Variable {} not found in context", &cap[1]));
+                    placeholder.as_str().to_string()
+                }
+            }
+        })
+        .into_owned()
+    }
+
+    /// Text of `{{ var }}` or `{{ item.column }}`: strings as-is, numbers and booleans in their
+    /// literal form, anything else as JSON
+    fn variable_text(&self, var_path: &str) -> Option<String> {
+        // A dotted path (e.g. item.column) reads a field of an object variable, falling back to
+        // a variable whose name contains the dot
+        let nested = var_path
+            .split_once('.')
+            .filter(|(_, child)| !child.contains('.'))
+            .and_then(|(parent, child)| self.variables.get(parent)?.as_object()?.get(child));
+
+        nested.or_else(|| self.variables.get(var_path)).map(|value| match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            _ => value.to_string(),
+        })
+    }
+
+    /// Evaluate a step's enabled_if guard against current variables.
+    /// Supports `lhs <op> rhs` with ==, !=, >, <, >=, <=, a leading `!`, or a bare truthy operand.
+    fn evaluate_enabled_if(&self, expression: &str) -> bool {
+        let expression = expression.trim();
+        if let Some(inner) = expression.strip_prefix('!') {
+            if !inner.starts_with('=') {
+                return !self.evaluate_enabled_if(inner);
+            }
+        }
+
+        let re = Regex::new(r"^(.+?)\s*(==|!=|>=|<=|>|<)\s*(.+)$").unwrap();
+        let caps = match re.captures(expression) {
+            Some(caps) => caps,
+            None => return is_truthy(&self.resolve_operand(expression)),
+        };
+
+        let left = self.resolve_operand(&caps[1]);
+        let right = self.resolve_operand(&caps[3]);
+        let as_number = |v: &serde_json::Value| match v {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => s.parse::<f64>().ok(),
+            _ => None,
+        };
+
+        match &caps[2] {
+            "==" => left == right || value_to_plain_string(&left) == value_to_plain_string(&right),
+            "!=" => left != right && value_to_plain_string(&left) != value_to_plain_string(&right),
+            op => match (as_number(&left), as_number(&right)) {
+                (Some(l), Some(r)) => match op {
+                    ">" => l > r,
+                    "<" => l < r,
+                    ">=" => l >= r,
+                    _ => l <= r,
+                },
+                _ => false,
+            },
+        }
+    }
+
+    /// Resolve one side of an enabled_if expression to a JSON value
+    fn resolve_operand(&self, token: &str) -> serde_json::Value {
+        let token = token.trim();
+
+        if token.contains("{{") {
+            let resolved = self.resolve_variables(token);
+            // Unresolved placeholders mean the variable does not exist
+            if resolved.contains("{{") {
+                return serde_json::Value::Null;
+            }
+            return serde_json::from_str(&resolved)
+                .unwrap_or(serde_json::Value::String(resolved));
+        }
+
+        if token.len() >= 2 && ((token.starts_with('"') && token.ends_with('"')) || (token.starts_with('\'') && token.ends_with('\''))) {
+            return serde_json::Value::String(token[1..token.len() - 1].to_string());
+        }
+
+        if let Some(value) = self.variables.get(token) {
+            return value.clone();
+        }
+
+        serde_json::from_str(token).unwrap_or(serde_json::Value::String(token.to_string()))
+    }
+
+    /// Replace {{$token}} placeholders with values drawn from the run's seeded RNG
+    fn resolve_dynamic_tokens(&self, input: &str) -> String {
+        let re = Regex::new(r"\{\{\s*\$(\w+)\s*\}\}").unwrap();
+        let mut rng = self.rng.borrow_mut();
+
+        re.replace_all(input, |cap: &regex::Captures| {
+            match &cap[1] {
+                "randomInt" => rng.next_range(0, 1000).to_string(),
+                "randomString" => rng.next_string(10),
+                "randomUuid" => rng.next_uuid(),
+                "randomEmail" => format!("user_{}@example.com", rng.next_string(8).to_lowercase()),
+                "randomBool" => rng.next_bool().to_string(),
+                other => {
+                    self.trace(log::Level::Warn, format!("Unknown dynamic token ${}", other));
+                    cap[0].to_string()
+                }
+            }
+        })
+        .into_owned()
+    }
+
     /// Resolve variables in a JSON value
     fn resolve_variables_in_json(&self, value: &serde_json::Value) -> serde_json::Value {
         match value {
@@ -818,6 +2059,9 @@ impl ScenarioExecutor {
                 serde_json::Value::String(self.resolve_variables(s))
             }
             serde_json::Value::Object(map) => {
+                if let Some(items) = body_template::expand_repeat(map, |s| self.resolve_variables(s)) {
+                    return serde_json::Value::Array(items.iter().map(|item| self.resolve_variables_in_json(item)).collect());
+                }
                 let mut new_map = serde_json::Map::new();
                 for (k, v) in map {
                     new_map.insert(k.clone(), self.resolve_variables_in_json(v));
@@ -838,8 +2082,16 @@ impl ScenarioExecutor {
     fn extract_variable(&self, extractor: &VariableExtractor, response: &StepResponse) -> serde_json::Value {
         match extractor.source.as_str() {
             "status" => serde_json::Value::Number(response.status.into()),
+            "finalUrl" => response.final_url.clone()
+                .map(serde_json::Value::String)
+                .unwrap_or_else(|| extractor.default_value.clone().unwrap_or(serde_json::Value::Null)),
             "header" => {
-                response.headers.get(&extractor.path)
+                let value = if extractor.path.ends_with('*') {
+                    headers_with_prefix(&response.headers, &extractor.path).first().map(|(_, v)| *v)
+                } else {
+                    find_header(&response.headers, &extractor.path)
+                };
+                value
                     .map(|v| serde_json::Value::String(v.clone()))
                     .unwrap_or_else(|| extractor.default_value.clone().unwrap_or(serde_json::Value::Null))
             }
@@ -877,14 +2129,49 @@ impl ScenarioExecutor {
         Some(current)
     }
 
-    /// Evaluate an assertion
-    fn evaluate_assertion(&self, assertion: &Assertion, response: &StepResponse, duration_ms: u64) -> Assertion {
+    /// Evaluate an assertion. `extracted` holds the variables this step just extracted, which
+    /// `variable` assertions see ahead of the running variable map.
+    fn evaluate_assertion(
+        &self,
+        assertion: &Assertion,
+        response: &StepResponse,
+        duration_ms: u64,
+        extracted: &HashMap<String, serde_json::Value>,
+    ) -> Assertion {
         let actual = match assertion.source.as_str() {
             "status" => serde_json::Value::Number(response.status.into()),
             "duration" => serde_json::Value::Number(serde_json::Number::from(duration_ms)),
+            "finalUrl" => response.final_url.clone()
+                .map(serde_json::Value::String)
+                .unwrap_or(serde_json::Value::Null),
+            "contentType" => match &response.content_type {
+                Some(content_type) => {
+                    // Charset and other parameters only count when the expected value names them
+                    let with_params = assertion.expected.as_str().is_some_and(|e| e.contains(';'));
+                    let value = if with_params {
+                        content_type.trim().to_string()
+                    } else {
+                        content_type.split(';').next().unwrap_or("").trim().to_lowercase()
+                    };
+                    serde_json::Value::String(value)
+                }
+                None => serde_json::Value::Null,
+            },
+            "serverTiming" => match &assertion.path {
+                Some(path) => server_timing_value(&response.server_timing, path),
+                None => serde_json::Value::Null,
+            },
             "header" => {
-                if let Some(path) = &assertion.path {
-                    response.headers.get(path)
+                if let Some(path) = assertion.path.as_ref().filter(|p| p.ends_with('*')) {
+                    // A prefix pattern like "X-RateLimit-*" asserts on the number of matching headers
+                    let count = headers_with_prefix(&response.headers, path).len();
+                    if assertion.operator == "exists" && count == 0 {
+                        serde_json::Value::Null
+                    } else {
+                        serde_json::Value::Number(count.into())
+                    }
+                } else if let Some(path) = &assertion.path {
+                    find_header(&response.headers, path)
                         .map(|v| serde_json::Value::String(v.clone()))
                         .unwrap_or(serde_json::Value::Null)
                 } else {
@@ -899,10 +2186,54 @@ impl ScenarioExecutor {
                     response.body.clone()
                 }
             }
+            // The original text, for HTML/CSV/plain-text bodies; path "lines" or "length" counts it
+            "rawBody" | "text" => match assertion.path.as_deref() {
+                Some("lines") => serde_json::Value::Number(response.raw_body.lines().count().into()),
+                Some("length") => serde_json::Value::Number(response.raw_body.chars().count().into()),
+                _ => serde_json::Value::String(response.raw_body.clone()),
+            },
+            // A variable by name, optionally followed by a path into its value ("user.id")
+            "variable" => match &assertion.path {
+                Some(path) => {
+                    let split = path.find(['.', '[']).unwrap_or(path.len());
+                    let (name, rest) = (&path[..split], path[split..].trim_start_matches('.'));
+                    match extracted.get(name).or_else(|| self.variables.get(name)) {
+                        Some(value) if rest.is_empty() => value.clone(),
+                        Some(value) => self.extract_json_path(value, rest).unwrap_or(serde_json::Value::Null),
+                        None => serde_json::Value::Null,
+                    }
+                }
+                None => serde_json::Value::Null,
+            },
             _ => serde_json::Value::Null,
         };
 
-        let (passed, error) = self.compare_values(&actual, &assertion.expected, &assertion.operator);
+        // Numeric operators compare "42" as 42; record the coerced value as the actual
+        let actual = match (assertion.operator.as_str(), &actual) {
+            ("greaterThan" | "lessThan" | "between", serde_json::Value::String(_)) => coerce_number(&actual)
+                .and_then(serde_json::Number::from_f64)
+                .map(serde_json::Value::Number)
+                .unwrap_or(actual),
+            _ => actual,
+        };
+
+        let (passed, mut error) = self.compare_values(&actual, &assertion.expected, &assertion.operator);
+
+        // Long response text is recorded (and quoted in the error) only up to a limit
+        let actual = match &actual {
+            serde_json::Value::String(text) if text.chars().count() > RAW_TEXT_ACTUAL_LIMIT => {
+                let shown = serde_json::Value::String(format!(
+                    "{}… ({} chars)",
+                    text.chars().take(RAW_TEXT_ACTUAL_LIMIT).collect::<String>(),
+                    text.chars().count()
+                ));
+                if !passed {
+                    error = self.compare_values(&shown, &assertion.expected, &assertion.operator).1.or(error);
+                }
+                shown
+            }
+            _ => actual,
+        };
 
         Assertion {
             name: assertion.name.clone(),
@@ -913,6 +2244,168 @@ impl ScenarioExecutor {
             actual: Some(actual),
             passed: Some(passed),
             error,
+            template: None,
+            params: None,
+            ignore_paths: assertion.ignore_paths.clone(),
+            only_if_status: assertion.only_if_status.clone(),
+            severity: assertion.severity.clone(),
+            skipped: false,
+            logic: None,
+            assertions: None,
+        }
+    }
+
+    /// Result for an assertion whose `only_if_status` excludes this response status:
+    /// skipped when the status is outside the spec, failed when the spec is invalid
+    fn status_gate(&self, assertion: &Assertion, status: u16) -> Option<Assertion> {
+        let spec = assertion.only_if_status.as_deref()?;
+        match status_matches_spec(spec, status) {
+            Ok(true) => None,
+            Ok(false) => {
+                log::debug!("[Executor] Skipping assertion '{}': status {} not in {}", assertion.name, status, spec);
+                Some(Assertion {
+                    actual: Some(serde_json::Value::Number(status.into())),
+                    passed: None,
+                    error: None,
+                    skipped: true,
+                    ..assertion.clone()
+                })
+            }
+            Err(e) => Some(Assertion {
+                passed: Some(false),
+                error: Some(e),
+                ..assertion.clone()
+            }),
+        }
+    }
+
+    /// Evaluate one (already template-expanded) assertion or group against the response
+    fn run_assertion(
+        &mut self,
+        assertion: Assertion,
+        response: &StepResponse,
+        duration_ms: u64,
+        extracted: &HashMap<String, serde_json::Value>,
+        step_id: &str,
+    ) -> Assertion {
+        // Unresolved template references are reported as already failed
+        if assertion.template.is_some() {
+            assertion
+        } else if let Some(gated) = self.status_gate(&assertion, response.status) {
+            gated
+        } else if assertion.is_group() {
+            self.evaluate_group(assertion, response, duration_ms, extracted, step_id)
+        } else if assertion.operator == "snapshot" {
+            self.evaluate_snapshot(&assertion, response, step_id)
+        } else {
+            self.evaluate_assertion(&assertion, response, duration_ms, extracted)
+        }
+    }
+
+    /// Evaluate every member of an `and`/`or` group, keeping their results on the group.
+    /// Skipped members don't count; a group whose members were all skipped is skipped too.
+    fn evaluate_group(
+        &mut self,
+        assertion: Assertion,
+        response: &StepResponse,
+        duration_ms: u64,
+        extracted: &HashMap<String, serde_json::Value>,
+        step_id: &str,
+    ) -> Assertion {
+        let logic = assertion.logic.clone().unwrap_or_default().to_lowercase();
+        if logic != "and" && logic != "or" {
+            return Assertion {
+                passed: Some(false),
+                error: Some(format!("Invalid assertion group logic '{}': expected \"and\" or \"or\"", logic)),
+                ..assertion
+            };
+        }
+
+        let members = self.expand_assertions(assertion.assertions.as_deref().unwrap_or_default());
+        let results: Vec<Assertion> = members
+            .into_iter()
+            .map(|member| self.run_assertion(member, response, duration_ms, extracted, step_id))
+            .collect();
+        let evaluated: Vec<&Assertion> = results.iter().filter(|r| !r.skipped).collect();
+        let failed: Vec<String> = evaluated
+            .iter()
+            .filter(|r| r.failed())
+            .map(|r| if r.name.is_empty() { r.operator.clone() } else { r.name.clone() })
+            .collect();
+
+        let (passed, skipped, error) = if results.is_empty() {
+            (Some(false), false, Some("Assertion group has no assertions".to_string()))
+        } else if evaluated.is_empty() {
+            (None, true, None)
+        } else if logic == "and" {
+            let error = (!failed.is_empty()).then(|| format!("Failed: {}", failed.join(", ")));
+            (Some(failed.is_empty()), false, error)
+        } else {
+            let any_passed = failed.len() < evaluated.len();
+            let error = (!any_passed).then(|| format!("None of {} assertions passed", evaluated.len()));
+            (Some(any_passed), false, error)
+        };
+
+        Assertion {
+            actual: Some(serde_json::json!({ "passed": evaluated.len() - failed.len(), "failed": failed.len() })),
+            passed,
+            error,
+            skipped,
+            assertions: Some(results),
+            ..assertion
+        }
+    }
+
+    /// Compare the body (or `path` within it) to the snapshot named by `expected`.
+    /// A missing snapshot, or any snapshot in update mode, is (re)written from the live body.
+    fn evaluate_snapshot(&mut self, assertion: &Assertion, response: &StepResponse, step_id: &str) -> Assertion {
+        let actual = match &assertion.path {
+            Some(path) => self.extract_json_path(&response.body, path).unwrap_or(serde_json::Value::Null),
+            None => response.body.clone(),
+        };
+        let ignore_paths = assertion.ignore_paths.clone().unwrap_or_default();
+        let masked_actual = mask_json_paths(&actual, &ignore_paths);
+
+        let (passed, error) = match assertion.expected.as_str().filter(|name| !name.is_empty()) {
+            None => (false, Some("Snapshot assertions need a snapshot name in expected".to_string())),
+            Some(name) => {
+                let key = (step_id.to_string(), name.to_string());
+                match self.snapshots.get(&key).cloned() {
+                    Some(stored) if !self.update_snapshots => {
+                        let masked_stored = mask_json_paths(&stored, &ignore_paths);
+                        if masked_stored == masked_actual {
+                            (true, None)
+                        } else {
+                            let mut lines = Vec::new();
+                            json_diff(&masked_stored, &masked_actual, "", &mut lines);
+                            (false, Some(format!("Response differs from snapshot '{}':\n{}", name, lines.join("\n"))))
+                        }
+                    }
+                    stored => {
+                        if stored.as_ref() != Some(&actual) {
+                            log::info!("[Executor] Writing snapshot '{}' for step {}", name, step_id);
+                            self.snapshots.insert(key, actual.clone());
+                            self.snapshot_updates.push(AssertionSnapshot {
+                                scenario_id: self.scenario_id.clone(),
+                                step_id: step_id.to_string(),
+                                name: name.to_string(),
+                                body: actual.clone(),
+                                updated_at: chrono::Utc::now().timestamp(),
+                            });
+                        }
+                        (true, None)
+                    }
+                }
+            }
+        };
+
+        Assertion {
+            actual: Some(masked_actual),
+            passed: Some(passed),
+            error,
+            template: None,
+            params: None,
+            ..assertion.clone()
         }
     }
 
@@ -921,10 +2414,14 @@ impl ScenarioExecutor {
         match operator {
             "equals" => {
                 let passed = actual == expected;
-                let error = if !passed {
-                    Some(format!("Expected {:?} but got {:?}", expected, actual))
-                } else {
+                let error = if passed {
                     None
+                } else if expected.is_object() || expected.is_array() {
+                    let mut lines = Vec::new();
+                    json_diff(expected, actual, "", &mut lines);
+                    Some(format!("Value differs from expected:\n{}", lines.join("\n")))
+                } else {
+                    Some(format!("Expected {:?} but got {:?}", expected, actual))
                 };
                 (passed, error)
             }
@@ -960,36 +2457,74 @@ impl ScenarioExecutor {
                     _ => actual.to_string(),
                 };
                 let pattern = match expected {
-                    serde_json::Value::String(s) => s.clone(),
+                    serde_json::Value::String(s) => regex_with_flags(s),
                     _ => expected.to_string(),
                 };
-                let passed = Regex::new(&pattern)
-                    .map(|re| re.is_match(&actual_str))
-                    .unwrap_or(false);
+                match Regex::new(&pattern) {
+                    Ok(re) => {
+                        let passed = re.is_match(&actual_str);
+                        let error = if !passed {
+                            Some(format!("Expected {:?} to match pattern {:?}", actual_str, pattern))
+                        } else {
+                            None
+                        };
+                        (passed, error)
+                    }
+                    Err(e) => (false, Some(format!("Invalid regex {:?}: {}", pattern, e))),
+                }
+            }
+            "greaterThan" | "lessThan" => {
+                let (actual_num, expected_num) = match (coerce_number(actual), coerce_number(expected)) {
+                    (Some(a), Some(e)) => (a, e),
+                    (None, _) => return (false, Some(format!("Expected a numeric value but got {}", actual))),
+                    (_, None) => return (false, Some(format!("Expected value {} is not numeric", expected))),
+                };
+                let (passed, relation) = if operator == "greaterThan" {
+                    (actual_num > expected_num, "greater than")
+                } else {
+                    (actual_num < expected_num, "less than")
+                };
                 let error = if !passed {
-                    Some(format!("Expected {:?} to match pattern {:?}", actual_str, pattern))
+                    Some(format!("Expected {} to be {} {}", actual_num, relation, expected_num))
                 } else {
                     None
                 };
                 (passed, error)
             }
-            "greaterThan" => {
-                let actual_num = actual.as_f64().unwrap_or(0.0);
-                let expected_num = expected.as_f64().unwrap_or(0.0);
-                let passed = actual_num > expected_num;
+            "between" => {
+                let (min, max) = match expected.as_array().map(|bounds| bounds.as_slice()) {
+                    Some([min, max]) => match (coerce_number(min), coerce_number(max)) {
+                        (Some(min), Some(max)) => (min, max),
+                        _ => return (false, Some(format!("Range bounds {} are not numeric", expected))),
+                    },
+                    _ => return (false, Some(format!("Expected a [min, max] range but got {}", expected))),
+                };
+                let actual_num = match coerce_number(actual) {
+                    Some(n) => n,
+                    None => return (false, Some(format!("Expected a numeric value but got {}", actual))),
+                };
+                let passed = min <= actual_num && actual_num <= max;
                 let error = if !passed {
-                    Some(format!("Expected {} to be greater than {}", actual_num, expected_num))
+                    Some(format!("Expected {} to be between {} and {} (inclusive)", actual_num, min, max))
                 } else {
                     None
                 };
                 (passed, error)
             }
-            "lessThan" => {
-                let actual_num = actual.as_f64().unwrap_or(0.0);
-                let expected_num = expected.as_f64().unwrap_or(0.0);
-                let passed = actual_num < expected_num;
+            "length" => {
+                let actual_len = match actual {
+                    serde_json::Value::String(s) => s.chars().count(),
+                    serde_json::Value::Array(items) => items.len(),
+                    serde_json::Value::Object(map) => map.len(),
+                    _ => return (false, Some(format!("Expected a string, array or object but got {}", actual))),
+                };
+                let expected_len = match coerce_number(expected) {
+                    Some(n) => n,
+                    None => return (false, Some(format!("Expected length {} is not numeric", expected))),
+                };
+                let passed = actual_len as f64 == expected_len;
                 let error = if !passed {
-                    Some(format!("Expected {} to be less than {}", actual_num, expected_num))
+                    Some(format!("Expected length {} but got {}", expected_len, actual_len))
                 } else {
                     None
                 };
@@ -1004,26 +2539,447 @@ impl ScenarioExecutor {
                 };
                 (passed, error)
             }
+            "type" => {
+                let actual_type = json_type_name(actual);
+                let expected_type = expected.as_str().unwrap_or_default();
+                // Integers also satisfy "number"
+                let passed = actual_type == expected_type
+                    || (expected_type == "number" && actual_type == "integer");
+                let error = if passed {
+                    None
+                } else {
+                    Some(format!("Expected type {} but got {}", expected_type, actual_type))
+                };
+                (passed, error)
+            }
             _ => (false, Some(format!("Unknown operator: {}", operator))),
         }
     }
 }
 
-/// Run a test scenario
-pub fn run_scenario(
-    scenario: &TestScenario,
+/// JSON type name used by the `type` operator
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+/// Run a test scenario with default settings; configure a `ScenarioExecutor` through its
+/// `with_*` builders for project settings
+pub fn run_scenario(
+    scenario: &TestScenario,
     steps: &[TestScenarioStep],
-    app_handle: Option<&AppHandle>,
+    events: Option<&dyn RunEvents>,
     base_url: Option<String>,
 ) -> TestScenarioRun {
     log::info!("[Executor] run_scenario called for scenario: {}", scenario.name);
     log::info!("[Executor] Base URL: {:?}", base_url);
     let mut executor = ScenarioExecutor::new()
         .with_base_url(base_url);
-    executor.execute_scenario(scenario, steps, app_handle)
+    executor.execute_scenario(scenario, steps, events)
+}
+
+/// Check a status against a comma-separated spec of exact codes (`201`), classes (`2xx`)
+/// and inclusive ranges (`200-299`)
+fn status_matches_spec(spec: &str, status: u16) -> Result<bool, String> {
+    let invalid = || format!("Invalid onlyIfStatus '{}': use codes, classes like 2xx or ranges like 200-299", spec);
+    let mut matched = false;
+    for part in spec.split(',').map(str::trim) {
+        let hit = if let Some(class) = part.strip_suffix("xx").or_else(|| part.strip_suffix("XX")) {
+            let class: u16 = class.parse().map_err(|_| invalid())?;
+            status / 100 == class
+        } else if let Some((low, high)) = part.split_once('-') {
+            let low: u16 = low.trim().parse().map_err(|_| invalid())?;
+            let high: u16 = high.trim().parse().map_err(|_| invalid())?;
+            (low..=high).contains(&status)
+        } else {
+            part.parse::<u16>().map_err(|_| invalid())? == status
+        };
+        matched |= hit;
+    }
+    Ok(matched)
+}
+
+/// Numeric view of a JSON value, accepting string-encoded numbers like "42" or " 3.5 "
+fn coerce_number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse::<f64>().ok().filter(|n| n.is_finite()),
+        _ => None,
+    }
+}
+
+/// Turn `/pattern/i` into an inline-flag pattern (`(?i)pattern`); other strings pass through
+fn regex_with_flags(pattern: &str) -> String {
+    if pattern.len() > 1 && pattern.starts_with('/') {
+        if let Some(end) = pattern.rfind('/').filter(|&end| end > 0) {
+            // Require a flag so literal path patterns like "/api/" keep their slashes
+            let flags = &pattern[end + 1..];
+            if !flags.is_empty() && flags.chars().all(|c| matches!(c, 'i' | 'm' | 's' | 'x')) {
+                return format!("(?{}){}", flags, &pattern[1..end]);
+            }
+        }
+    }
+    pattern.to_string()
+}
+
+/// Headers whose name starts with `pattern` minus its trailing `*`, ignoring case, sorted by name
+fn headers_with_prefix<'a>(headers: &'a HashMap<String, String>, pattern: &str) -> Vec<(&'a String, &'a String)> {
+    let prefix = pattern.trim_end_matches('*').to_ascii_lowercase();
+    let mut matches: Vec<(&String, &String)> = headers
+        .iter()
+        .filter(|(key, _)| key.to_ascii_lowercase().starts_with(&prefix))
+        .collect();
+    matches.sort_by_key(|(key, _)| key.to_ascii_lowercase());
+    matches
+}
+
+/// Look up a header by name, ignoring case (HTTP header names are case-insensitive)
+fn find_header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a String> {
+    headers.get(name).or_else(|| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    })
+}
+
+/// Truthiness used by enabled_if: null, false, 0, "" and empty collections are false
+fn is_truthy(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => false,
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::Number(n) => n.as_f64().map(|f| f != 0.0).unwrap_or(false),
+        serde_json::Value::String(s) => !s.is_empty() && s != "false" && s != "0",
+        serde_json::Value::Array(a) => !a.is_empty(),
+        serde_json::Value::Object(o) => !o.is_empty(),
+    }
+}
+
+/// Byte range of a URL template's path: after any `scheme://authority` (or leading
+/// placeholder such as `{{ baseUrl }}`) and before the query string or fragment
+fn url_path_bounds(url: &str) -> (usize, usize) {
+    let end = url.find(['?', '#']).unwrap_or(url.len());
+    let start = match url[..end].find("://") {
+        Some(scheme_end) => url[scheme_end + 3..end].find('/').map(|i| scheme_end + 3 + i).unwrap_or(end),
+        None => url[..end].find('/').unwrap_or(end),
+    };
+    (start, end)
+}
+
+/// Percent-encode a value for use as one URL path segment (RFC 3986 `pchar`s are kept)
+fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn value_to_plain_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Collect readable differences between two JSON values, one line per differing path
+fn json_diff(expected: &serde_json::Value, actual: &serde_json::Value, path: &str, lines: &mut Vec<String>) {
+    let label = if path.is_empty() { "(root)" } else { path };
+    match (expected, actual) {
+        (serde_json::Value::Object(exp), serde_json::Value::Object(act)) => {
+            for (key, exp_value) in exp {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                match act.get(key) {
+                    Some(act_value) => json_diff(exp_value, act_value, &child, lines),
+                    None => lines.push(format!("- {}: missing (expected {})", child, exp_value)),
+                }
+            }
+            for (key, act_value) in act {
+                if !exp.contains_key(key) {
+                    let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                    lines.push(format!("+ {}: unexpected {}", child, act_value));
+                }
+            }
+        }
+        (serde_json::Value::Array(exp), serde_json::Value::Array(act)) => {
+            for i in 0..exp.len().max(act.len()) {
+                let child = format!("{}[{}]", path, i);
+                match (exp.get(i), act.get(i)) {
+                    (Some(e), Some(a)) => json_diff(e, a, &child, lines),
+                    (Some(e), None) => lines.push(format!("- {}: missing (expected {})", child, e)),
+                    (None, Some(a)) => lines.push(format!("+ {}: unexpected {}", child, a)),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if expected != actual => {
+            lines.push(format!("~ {}: expected {} but got {}", label, expected, actual));
+        }
+        _ => {}
+    }
+}
+
+/// Segment of an `ignorePaths` entry
+enum MaskSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// Copy of `value` with every `ignorePaths` match removed (`$.a.b`, `items[0].id`, `items[*].id`)
+fn mask_json_paths(value: &serde_json::Value, paths: &[String]) -> serde_json::Value {
+    let mut masked = value.clone();
+    for path in paths {
+        let mut segments = Vec::new();
+        let trimmed = path.trim().trim_start_matches('$').trim_start_matches('.');
+        for part in trimmed.split('.').filter(|p| !p.is_empty()) {
+            let (key, indexes) = match part.find('[') {
+                Some(pos) => (&part[..pos], &part[pos..]),
+                None => (part, ""),
+            };
+            if key == "*" {
+                segments.push(MaskSegment::Wildcard);
+            } else if !key.is_empty() {
+                segments.push(MaskSegment::Key(key.to_string()));
+            }
+            for index in indexes.split('[').filter(|i| !i.is_empty()) {
+                let index = index.trim_end_matches(']');
+                if index == "*" {
+                    segments.push(MaskSegment::Wildcard);
+                } else if let Ok(i) = index.parse::<usize>() {
+                    segments.push(MaskSegment::Index(i));
+                }
+            }
+        }
+        remove_json_path(&mut masked, &segments);
+    }
+    masked
+}
+
+fn remove_json_path(value: &mut serde_json::Value, segments: &[MaskSegment]) {
+    let (first, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    match (first, value) {
+        (MaskSegment::Key(key), serde_json::Value::Object(map)) => {
+            if rest.is_empty() {
+                map.remove(key);
+            } else if let Some(child) = map.get_mut(key) {
+                remove_json_path(child, rest);
+            }
+        }
+        (MaskSegment::Index(index), serde_json::Value::Array(items)) => {
+            if let Some(child) = items.get_mut(*index) {
+                if rest.is_empty() {
+                    *child = serde_json::Value::Null;
+                } else {
+                    remove_json_path(child, rest);
+                }
+            }
+        }
+        (MaskSegment::Wildcard, serde_json::Value::Array(items)) => {
+            for child in items.iter_mut() {
+                if rest.is_empty() {
+                    *child = serde_json::Value::Null;
+                } else {
+                    remove_json_path(child, rest);
+                }
+            }
+        }
+        (MaskSegment::Wildcard, serde_json::Value::Object(map)) => {
+            if rest.is_empty() {
+                map.clear();
+            } else {
+                for child in map.values_mut() {
+                    remove_json_path(child, rest);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `url` with `query` appended the way reqwest's `.query()` encodes it, for reporting
+fn url_with_query(url: &str, query: &[(String, String)]) -> String {
+    if query.is_empty() {
+        return url.to_string();
+    }
+    match reqwest::Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed.query_pairs_mut().extend_pairs(query);
+            parsed.to_string()
+        }
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Read the JSON body referenced by `bodyFile`; the path is used as given, like CSV file names
+fn load_body_file(config: &RequestStepConfig) -> Result<Option<serde_json::Value>, String> {
+    let file_name = match &config.body_file {
+        Some(file_name) => file_name,
+        None => return Ok(None),
+    };
+    if config.body.is_some() {
+        return Err("Request step cannot set both body and bodyFile".to_string());
+    }
+
+    let path = std::path::Path::new(file_name);
+    if !path.exists() {
+        return Err(format!("Body file not found: {}", file_name));
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read body file {}: {}", file_name, e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Body file {} is not valid JSON: {}", file_name, e))
+}
+
+/// Trim a body to `max_bytes` of its serialized form, appending a truncation marker
+fn truncate_body(body: &serde_json::Value, max_bytes: usize) -> (serde_json::Value, bool) {
+    let text = match body {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.len() <= max_bytes {
+        return (body.clone(), false);
+    }
+
+    let mut cut = max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let marker = format!("...[truncated {} bytes]", text.len() - cut);
+    (serde_json::Value::String(format!("{}{}", &text[..cut], marker)), true)
+}
+
+/// A response body as read by the executor
+struct ResponseBody {
+    text: String,
+    truncated: bool,                  // Cut at the read cap
+    content_encoding: Option<String>, // As sent by the server, e.g. "gzip"
+    encoded_size: usize,
+    decoded_size: u64,
+}
+
+/// Read a response body as text, decoding any gzip/deflate/br Content-Encoding and stopping
+/// after `max_bytes` of decoded text (a safety cap, not the stored-body limit), so a small
+/// compressed body cannot expand without bound. A body that fails to decode is kept as received.
+fn read_response_body(response: reqwest::blocking::Response, max_bytes: usize) -> ResponseBody {
+    let content_encoding = response.headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty() && v != "identity");
+    let mut raw = Vec::new();
+    if let Err(e) = response.take(max_bytes as u64 + 1).read_to_end(&mut raw) {
+        log::warn!("[Executor] Failed to read response body: {}", e);
+    }
+    let encoded_size = raw.len();
+
+    let mut data = match &content_encoding {
+        Some(content_encoding) => decode_content(&raw, content_encoding, max_bytes).unwrap_or_else(|e| {
+            log::warn!("[Executor] Failed to decode {} response body: {}", content_encoding, e);
+            raw
+        }),
+        None => raw,
+    };
+    let truncated = data.len() > max_bytes;
+    data.truncate(max_bytes);
+    ResponseBody {
+        decoded_size: data.len() as u64,
+        text: String::from_utf8_lossy(&data).into_owned(),
+        truncated,
+        content_encoding,
+        encoded_size,
+    }
+}
+
+/// Undo a Content-Encoding list such as "gzip" or "deflate, br" (applied in order, so
+/// decoded last-to-first), keeping at most `max_bytes` + 1 bytes of each stage
+fn decode_content(bytes: &[u8], content_encoding: &str, max_bytes: usize) -> Result<Vec<u8>, String> {
+    let limit = max_bytes as u64 + 1;
+    let mut data = bytes.to_vec();
+    for coding in content_encoding.split(',').map(str::trim).rev() {
+        let mut decoded = Vec::new();
+        let result = match coding {
+            "gzip" | "x-gzip" => flate2::read::MultiGzDecoder::new(data.as_slice()).take(limit).read_to_end(&mut decoded),
+            // Spec'd as zlib-wrapped, but some servers send raw deflate
+            "deflate" => flate2::read::ZlibDecoder::new(data.as_slice()).take(limit).read_to_end(&mut decoded)
+                .or_else(|_| {
+                    decoded.clear();
+                    flate2::read::DeflateDecoder::new(data.as_slice()).take(limit).read_to_end(&mut decoded)
+                }),
+            "br" => brotli::Decompressor::new(data.as_slice(), 4096).take(limit).read_to_end(&mut decoded),
+            "identity" | "" => continue,
+            other => return Err(format!("unsupported encoding '{}'", other)),
+        };
+        result.map_err(|e| format!("{}: {}", coding, e))?;
+        data = decoded;
+    }
+    Ok(data)
+}
+
+/// Wrap a redirect policy so every redirect it is asked about is recorded in `hops`
+fn recording_policy(inner: Policy, hops: &Arc<Mutex<Vec<RedirectHop>>>) -> Policy {
+    let hops = Arc::clone(hops);
+    Policy::custom(move |attempt| {
+        let hop = RedirectHop {
+            status: attempt.status().as_u16(),
+            location: attempt.url().to_string(),
+        };
+        log::debug!("[Executor] Redirect {} -> {}", hop.status, hop.location);
+        if let Ok(mut hops) = hops.lock() {
+            hops.push(hop);
+        }
+        inner.redirect(attempt)
+    })
+}
+
+/// "302 -> https://a/login, 302 -> https://a/sso"
+fn format_redirect_chain(hops: &[RedirectHop]) -> String {
+    hops.iter()
+        .map(|hop| format!("{} -> {}", hop.status, hop.location))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check whether a base URL points at a local development host
+pub fn is_local_base_url(base_url: Option<&str>) -> bool {
+    let host = match base_url.and_then(|url| reqwest::Url::parse(url).ok()) {
+        Some(url) => url.host_str().unwrap_or_default().to_lowercase(),
+        None => return false,
+    };
+
+    host == "localhost"
+        || host == "0.0.0.0"
+        || host == "[::1]"
+        || host.starts_with("127.")
+        || host.ends_with(".localhost")
+        || host.ends_with(".local")
+        || host.ends_with(".test")
+}
+
+/// Detect certificate/handshake failures anywhere in a request error chain
+fn is_tls_error(error: &reqwest::Error) -> bool {
+    let chain = get_error_chain(error).to_lowercase();
+    ["certificate", "self signed", "self-signed", "unknownissuer", "handshake", "ssl"]
+        .iter()
+        .any(|needle| chain.contains(needle))
 }
 
-fn get_error_chain(error: &dyn std::error::Error) -> String {
+pub(crate) fn get_error_chain(error: &dyn std::error::Error) -> String {
     let mut chain = vec![error.to_string()];
     let mut source = error.source();
     while let Some(err) = source {
@@ -1033,3 +2989,1428 @@ fn get_error_chain(error: &dyn std::error::Error) -> String {
     chain.join(" -> ")
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_base_url() {
+        assert!(is_local_base_url(Some("http://localhost:3000")));
+        assert!(is_local_base_url(Some("https://127.0.0.1:8443/api")));
+        assert!(is_local_base_url(Some("https://myapp.test")));
+        assert!(!is_local_base_url(Some("https://staging.example.com")));
+        assert!(!is_local_base_url(None));
+    }
+
+    #[test]
+    fn test_evaluate_enabled_if() {
+        let mut variables = HashMap::new();
+        variables.insert("userId".to_string(), serde_json::json!(42));
+        variables.insert("created".to_string(), serde_json::json!(false));
+        let executor = ScenarioExecutor::new().with_variables(variables);
+
+        assert!(executor.evaluate_enabled_if("{{userId}} != null"));
+        assert!(executor.evaluate_enabled_if("userId >= 40"));
+        assert!(executor.evaluate_enabled_if("!created"));
+        assert!(!executor.evaluate_enabled_if("{{missing}} != null"));
+        assert!(!executor.evaluate_enabled_if("created"));
+        assert!(executor.evaluate_enabled_if("{{userId}} == \"42\""));
+    }
+
+    #[test]
+    fn test_repeat_directive_in_body() {
+        let mut variables = HashMap::new();
+        variables.insert("batchSize".to_string(), serde_json::json!(3));
+        variables.insert("team".to_string(), serde_json::json!("qa"));
+        let executor = ScenarioExecutor::new().with_variables(variables);
+
+        let body = serde_json::json!({
+            "users": {
+                "__repeat": "{{ batchSize }}",
+                "item": { "name": "{{ team }}-user-{{ $index }}", "position": "{{ $index }}" }
+            }
+        });
+        assert_eq!(
+            executor.resolve_variables_in_json(&body),
+            serde_json::json!({
+                "users": [
+                    { "name": "qa-user-0", "position": 0 },
+                    { "name": "qa-user-1", "position": 1 },
+                    { "name": "qa-user-2", "position": 2 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_resolve_url_variables_types_and_encoding() {
+        let mut variables = HashMap::new();
+        variables.insert("baseUrl".to_string(), serde_json::json!("http://localhost:3000/api"));
+        variables.insert("userId".to_string(), serde_json::json!(42));
+        variables.insert("active".to_string(), serde_json::json!(true));
+        variables.insert("sku".to_string(), serde_json::json!("a/b c?d"));
+        variables.insert("item".to_string(), serde_json::json!({"orderId": 7, "code": "x y"}));
+        let executor = ScenarioExecutor::new().with_variables(variables);
+
+        assert_eq!(
+            executor.resolve_url_variables("/users/{{ userId }}/orders/{{ item.orderId }}"),
+            "/users/42/orders/7"
+        );
+        assert_eq!(executor.resolve_url_variables("/flags/{{active}}"), "/flags/true");
+        assert_eq!(
+            executor.resolve_url_variables("{{ baseUrl }}/products/{{ sku }}/codes/{{ item.code }}"),
+            "http://localhost:3000/api/products/a%2Fb%20c%3Fd/codes/x%20y"
+        );
+        // The query string keeps the plain substitution
+        assert_eq!(
+            executor.resolve_url_variables("http://host/users/{{userId}}?sku={{sku}}"),
+            "http://host/users/42?sku=a/b c?d"
+        );
+        assert_eq!(executor.resolve_url_variables("/users/{{ missing }}"), "/users/{{ missing }}");
+    }
+
+    fn response_with_headers() -> StepResponse {
+        let mut headers = HashMap::new();
+        headers.insert("content-type".to_string(), "application/json".to_string());
+        headers.insert("X-Request-Id".to_string(), "abc-123".to_string());
+        StepResponse {
+            status: 200,
+            status_text: "200 OK".to_string(),
+            headers,
+            body: serde_json::Value::Null,
+            duration_ms: 5,
+            truncated: false,
+            final_url: None,
+            content_type: None,
+            redirects: Vec::new(),
+            server_timing: Vec::new(),
+            content_encoding: None,
+            decoded_size: 0,
+            raw_body: String::new(),
+        }
+    }
+
+    fn header_assertion(path: &str, operator: &str, expected: serde_json::Value) -> Assertion {
+        Assertion {
+            name: format!("{} {}", path, operator),
+            source: "header".to_string(),
+            path: Some(path.to_string()),
+            operator: operator.to_string(),
+            expected,
+            actual: None,
+            passed: None,
+            error: None,
+            template: None,
+            params: None,
+            ignore_paths: None,
+            only_if_status: None,
+            severity: None,
+            skipped: false,
+            logic: None,
+            assertions: None,
+        }
+    }
+
+    #[test]
+    fn test_header_assertions_ignore_case() {
+        let executor = ScenarioExecutor::new();
+        let response = response_with_headers();
+
+        let equals = header_assertion("Content-Type", "equals", serde_json::json!("application/json"));
+        assert_eq!(executor.evaluate_assertion(&equals, &response, 5, &HashMap::new()).passed, Some(true));
+
+        let exists = header_assertion("x-request-id", "exists", serde_json::Value::Null);
+        assert_eq!(executor.evaluate_assertion(&exists, &response, 5, &HashMap::new()).passed, Some(true));
+
+        let missing = header_assertion("X-Missing", "exists", serde_json::Value::Null);
+        assert_eq!(executor.evaluate_assertion(&missing, &response, 5, &HashMap::new()).passed, Some(false));
+    }
+
+    #[test]
+    fn test_raw_body_assertions() {
+        let executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.raw_body = "id,name\n1,Ada\n2,Linus\n".to_string();
+        response.body = serde_json::Value::String(response.raw_body.clone());
+        let raw = |path: Option<&str>, operator: &str, expected: serde_json::Value| Assertion {
+            source: "rawBody".to_string(),
+            path: path.map(|p| p.to_string()),
+            ..header_assertion("", operator, expected)
+        };
+
+        let checks = [
+            raw(None, "contains", serde_json::json!("1,Ada")),
+            raw(None, "matches", serde_json::json!("^id,name\\n")),
+            raw(None, "equals", serde_json::json!("id,name\n1,Ada\n2,Linus\n")),
+            raw(None, "length", serde_json::json!(22)),
+            raw(Some("lines"), "equals", serde_json::json!(3)),
+            Assertion { source: "text".to_string(), ..raw(Some("length"), "greaterThan", serde_json::json!(20)) },
+        ];
+        for check in &checks {
+            let result = executor.evaluate_assertion(check, &response, 5, &HashMap::new());
+            assert_eq!(result.passed, Some(true), "{} {:?}", check.operator, result.error);
+        }
+
+        response.raw_body = format!("<html>{}</html>", "x".repeat(5000));
+        let failed = executor.evaluate_assertion(&raw(None, "contains", serde_json::json!("<title>")), &response, 5, &HashMap::new());
+        assert_eq!(failed.passed, Some(false));
+        let actual = failed.actual.unwrap();
+        assert!(actual.as_str().unwrap().ends_with("… (5013 chars)"));
+        assert!(failed.error.unwrap().len() < 1200);
+    }
+
+    #[test]
+    fn test_assertion_template_expansion() {
+        let mut status = header_assertion("", "equals", serde_json::json!("{{status}}"));
+        status.name = "status is {{status}}".to_string();
+        status.source = "status".to_string();
+        status.path = None;
+        let template = AssertionTemplate {
+            id: "t1".to_string(),
+            project_id: "p1".to_string(),
+            name: "ok_response".to_string(),
+            description: None,
+            assertions: vec![status],
+            parameters: HashMap::from([("status".to_string(), serde_json::json!(200))]),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let executor = ScenarioExecutor::new().with_assertion_templates(vec![template]);
+
+        let mut reference = header_assertion("", "", serde_json::Value::Null);
+        reference.template = Some("ok_response".to_string());
+        let expanded = executor.expand_assertions(&[reference.clone()]);
+        assert_eq!(expanded.len(), 1);
+        assert_eq!(expanded[0].expected, serde_json::json!(200));
+        assert_eq!(expanded[0].name, "status is 200");
+        assert_eq!(executor.evaluate_assertion(&expanded[0], &response_with_headers(), 5, &HashMap::new()).passed, Some(true));
+
+        reference.params = Some(HashMap::from([("status".to_string(), serde_json::json!(201))]));
+        assert_eq!(executor.expand_assertions(&[reference.clone()])[0].expected, serde_json::json!(201));
+
+        reference.template = Some("missing".to_string());
+        let unknown = executor.expand_assertions(&[reference]);
+        assert_eq!(unknown[0].passed, Some(false));
+    }
+
+    /// Local HTTP server for tests: reads each request in full (head, then as much body as
+    /// Content-Length announces) and answers with whatever `handler` returns for its text.
+    /// Returns the server's base URL.
+    fn serve(handler: impl Fn(&str) -> Vec<u8> + Send + 'static) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut data = Vec::new();
+                let mut buf = [0u8; 1024];
+                loop {
+                    let n = stream.read(&mut buf).unwrap_or(0);
+                    data.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&data);
+                    let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                        let length = head.lines()
+                            .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                            .unwrap_or(0);
+                        body.len() >= length
+                    });
+                    if complete || n == 0 {
+                        break;
+                    }
+                }
+                let _ = stream.write_all(&handler(&String::from_utf8_lossy(&data)));
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    /// `HTTP/1.1 {status_and_headers}` with Content-Length, `Connection: close` and `body`
+    fn http_response(status_and_headers: &str, body: impl AsRef<[u8]>) -> Vec<u8> {
+        let body = body.as_ref();
+        let mut response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status_and_headers,
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    /// Local server answering /old with a 302 to /new and everything else with 200
+    fn spawn_redirect_fixture() -> String {
+        serve(|request| {
+            if request.starts_with("GET /old ") {
+                http_response("302 Found\r\nLocation: /new", "")
+            } else {
+                http_response("200 OK\r\nContent-Type: application/json", r#"{"ok":true}"#)
+            }
+        })
+    }
+
+    fn redirect_step(follow_redirects: Option<bool>) -> TestScenarioStep {
+        TestScenarioStep {
+            id: "step-1".to_string(),
+            scenario_id: "scenario-1".to_string(),
+            step_order: 0,
+            step_type: TestStepType::Request,
+            name: "Old URL".to_string(),
+            config: serde_json::json!({
+                "url": "/old",
+                "method": "GET",
+                "followRedirects": follow_redirects,
+            }),
+            enabled: true,
+            enabled_if: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_query_parameters_are_encoded() {
+        let base_url = spawn_redirect_fixture();
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url));
+        executor.variables.insert("name".to_string(), serde_json::json!("José/ü"));
+
+        let mut step = redirect_step(None);
+        step.config["url"] = serde_json::json!("/new?page=1");
+        step.config["query"] = serde_json::json!({ "q": "a b&c=d", "name": "{{name}}" });
+
+        let result = executor.execute_request_step(&step);
+        let expected = "/new?page=1&name=Jos%C3%A9%2F%C3%BC&q=a+b%26c%3Dd";
+        assert!(result.request.as_ref().unwrap().url.ends_with(expected));
+        assert!(result.response.as_ref().unwrap().final_url.as_deref().unwrap().ends_with(expected));
+    }
+
+    #[test]
+    fn test_request_log_masks_credentials() {
+        let base_url = spawn_redirect_fixture();
+        let log_path = std::env::temp_dir().join(format!("lookapi-exchange-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&log_path);
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some(base_url))
+            .with_request_logger(Some(RequestLogger::new(log_path.clone())));
+
+        let mut step = redirect_step(None);
+        step.config["url"] = serde_json::json!("/new");
+        step.config["headers"] = serde_json::json!({ "Authorization": "Bearer s3cret", "X-API-Key": "k3y", "Accept": "application/json" });
+        executor.execute_request_step(&step);
+
+        let logged = std::fs::read_to_string(&log_path).unwrap();
+        assert!(logged.contains(r#""Authorization":"***""#));
+        assert!(logged.contains(r#""Accept":"application/json""#));
+        assert!(!logged.contains("s3cret") && !logged.contains("k3y"));
+
+        let _ = std::fs::remove_file(&log_path);
+    }
+
+    #[test]
+    fn test_service_base_urls() {
+        let executor = ScenarioExecutor::new()
+            .with_base_url(Some("http://gateway.local".to_string()))
+            .with_services(vec![ProjectService {
+                name: "orders".to_string(),
+                base_url: "http://orders.local/".to_string(),
+            }]);
+        assert_eq!(executor.resolve_url("/items", None), "http://gateway.local/items");
+        assert_eq!(executor.resolve_url("/items", Some("orders")), "http://orders.local/items");
+        assert_eq!(executor.resolve_url("https://x.test/a", Some("orders")), "https://x.test/a");
+
+        let mut step = redirect_step(None);
+        step.config["service"] = serde_json::json!("billing");
+        let mut executor = executor;
+        let result = executor.execute_request_step(&step);
+        assert_eq!(result.status, StepResultStatus::Error);
+        assert_eq!(result.error.as_deref(), Some("Unknown service: billing"));
+    }
+
+    #[test]
+    fn test_only_if_status_skips_body_assertion_on_503() {
+        let base_url = serve(|_| http_response("503 Service Unavailable\r\nContent-Type: application/json", r#"{"error":"down"}"#));
+
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url));
+        let mut step = redirect_step(None);
+        step.config["assertions"] = serde_json::json!([
+            { "name": "status", "source": "status", "operator": "equals", "expected": 503 },
+            { "name": "has id", "source": "body", "path": "data.id", "operator": "exists", "onlyIfStatus": "2xx" },
+            { "name": "bad spec", "source": "status", "operator": "exists", "onlyIfStatus": "teapot" }
+        ]);
+        let result = executor.execute_request_step(&step);
+        let assertions = result.assertions.unwrap();
+
+        assert!(assertions[1].skipped);
+        assert_eq!(assertions[1].passed, None);
+        assert!(assertions[2].error.as_deref().unwrap().starts_with("Invalid onlyIfStatus"));
+        assert_eq!(result.status, StepResultStatus::Failed);
+
+        step.config["assertions"].as_array_mut().unwrap().pop();
+        let result = executor.execute_request_step(&step);
+        assert_eq!(result.status, StepResultStatus::Passed);
+
+        // A failed warn-level assertion is counted but keeps the step passing
+        step.config["assertions"].as_array_mut().unwrap().push(serde_json::json!(
+            { "name": "is 200", "source": "status", "operator": "equals", "expected": 200, "severity": "warn" }
+        ));
+        let result = executor.execute_request_step(&step);
+        assert_eq!(result.status, StepResultStatus::Passed);
+        assert_eq!(result.assertion_summary, Some(AssertionSummary { passed: 1, failed_errors: 0, failed_warnings: 1, skipped: 1 }));
+
+        assert_eq!(status_matches_spec("200-299, 304", 304), Ok(true));
+        assert_eq!(status_matches_spec("2xx", 204), Ok(true));
+        assert_eq!(status_matches_spec("200,201", 500), Ok(false));
+    }
+
+    #[test]
+    fn test_top_level_array_body_resolves_variables_in_elements() {
+        // Echoes the request's Content-Type and body back as JSON
+        let base_url = serve(|request| {
+            let (head, body) = request.split_once("\r\n\r\n").unwrap_or((request, ""));
+            let head = head.to_lowercase();
+            let echo = serde_json::json!({
+                "contentType": head.lines().find_map(|l| l.strip_prefix("content-type:").map(|v| v.trim().to_string())),
+                "body": serde_json::from_str::<serde_json::Value>(body).unwrap_or(serde_json::Value::Null),
+            });
+            http_response("200 OK\r\nContent-Type: application/json", echo.to_string())
+        });
+
+        let mut variables = HashMap::new();
+        variables.insert("tenant".to_string(), serde_json::json!("acme"));
+        variables.insert("user".to_string(), serde_json::json!({ "name": "Jane" }));
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some(base_url))
+            .with_variables(variables);
+        let mut step = redirect_step(None);
+        step.config = serde_json::json!({
+            "url": "/users/batch",
+            "method": "POST",
+            "body": [
+                { "name": "{{user.name}}", "tenant": "{{tenant}}" },
+                { "name": "Bob", "tags": ["{{tenant}}-admin", 1] },
+                "{{tenant}}"
+            ]
+        });
+        let result = executor.execute_request_step(&step);
+
+        let expected = serde_json::json!([
+            { "name": "Jane", "tenant": "acme" },
+            { "name": "Bob", "tags": ["acme-admin", 1] },
+            "acme"
+        ]);
+        assert_eq!(result.status, StepResultStatus::Passed);
+        assert_eq!(result.request.unwrap().body, Some(expected.clone()));
+        let echo = result.response.unwrap().body;
+        assert_eq!(echo["contentType"], "application/json");
+        assert_eq!(echo["body"], expected);
+    }
+
+    #[test]
+    fn test_gzip_response_is_decoded_for_assertions() {
+        // gzip of {"data":{"id":7,"name":"compressed"}}
+        let gzipped: &[u8] = &[
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xab, 0x56, 0x4a, 0x49, 0x2c, 0x49,
+            0x54, 0xb2, 0xaa, 0x56, 0xca, 0x4c, 0x51, 0xb2, 0x32, 0xd7, 0x51, 0xca, 0x4b, 0xcc, 0x4d, 0x55,
+            0xb2, 0x52, 0x4a, 0xce, 0xcf, 0x2d, 0x28, 0x4a, 0x2d, 0x2e, 0x4e, 0x4d, 0x51, 0xaa, 0xad, 0x05,
+            0x00, 0xf5, 0x41, 0x2e, 0x87, 0x25, 0x00, 0x00, 0x00,
+        ];
+
+        let accept_encoding = Arc::new(Mutex::new(String::new()));
+        let seen = Arc::clone(&accept_encoding);
+        let base_url = serve(move |request| {
+            if let Some(line) = request.to_lowercase().lines().find(|l| l.starts_with("accept-encoding:")) {
+                *seen.lock().unwrap() = line.to_string();
+            }
+            http_response("200 OK\r\nContent-Type: application/json\r\nContent-Encoding: gzip", gzipped)
+        });
+
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url));
+        let mut step = redirect_step(None);
+        step.config["assertions"] = serde_json::json!([
+            { "name": "name", "source": "body", "path": "data.name", "operator": "equals", "expected": "compressed" }
+        ]);
+        let result = executor.execute_request_step(&step);
+
+        assert_eq!(result.status, StepResultStatus::Passed);
+        let response = result.response.unwrap();
+        assert_eq!(response.body["data"]["id"], 7);
+        assert!(!response.truncated);
+        assert_eq!(response.content_encoding.as_deref(), Some("gzip"));
+        assert_eq!(response.decoded_size, r#"{"data":{"id":7,"name":"compressed"}}"#.len() as u64);
+        assert!(accept_encoding.lock().unwrap().contains("gzip"));
+    }
+
+    #[test]
+    fn test_server_timing_assertions() {
+        let base_url = serve(|_| {
+            http_response("200 OK\r\nServer-Timing: db;dur=53.5;desc=\"Query\", cache;desc=miss, app;dur=120", "{}")
+        });
+
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url));
+        let mut step = redirect_step(None);
+        step.config["assertions"] = serde_json::json!([
+            { "name": "db fast", "source": "serverTiming", "path": "db;dur", "operator": "lessThan", "expected": 100 },
+            { "name": "cache", "source": "serverTiming", "path": "cache;desc", "operator": "equals", "expected": "miss" },
+            { "name": "app fast", "source": "serverTiming", "path": "app", "operator": "lessThan", "expected": 100 }
+        ]);
+        let result = executor.execute_request_step(&step);
+
+        let assertions = result.assertions.unwrap();
+        assert_eq!(assertions[0].passed, Some(true));
+        assert_eq!(assertions[1].passed, Some(true));
+        assert_eq!(assertions[2].passed, Some(false));
+        assert_eq!(assertions[2].actual, Some(serde_json::json!(120.0)));
+        let timing = result.response.unwrap().server_timing;
+        assert_eq!(timing.len(), 3);
+        assert_eq!(timing[0].desc.as_deref(), Some("Query"));
+    }
+
+    #[test]
+    fn test_dry_run_resolves_without_sending() {
+        // Nothing listens on the discard port, so a real send would fail with an Error step
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some("http://127.0.0.1:9".to_string()))
+            .with_dry_run(true);
+        executor.variables.insert("token".to_string(), serde_json::json!("abc123"));
+
+        let mut step = redirect_step(None);
+        step.config["method"] = serde_json::json!("DELETE");
+        step.config["url"] = serde_json::json!("/orders/{{ token }}");
+        step.config["headers"] = serde_json::json!({ "Authorization": "Bearer {{ token }}" });
+        let result = executor.execute_request_step(&step);
+
+        assert_eq!(result.status, StepResultStatus::Skipped);
+        assert!(result.response.is_none());
+        assert!(result.error.is_none());
+        let request = result.request.unwrap();
+        assert_eq!(request.method, "DELETE");
+        assert_eq!(request.url, "http://127.0.0.1:9/orders/abc123");
+        assert_eq!(request.headers.get("Authorization").map(|s| s.as_str()), Some("Bearer abc123"));
+    }
+
+    #[test]
+    fn test_run_log_captures_trace() {
+        let scenario = TestScenario {
+            id: "scenario-1".to_string(),
+            project_id: "project-1".to_string(),
+            name: "Traced".to_string(),
+            description: None,
+            priority: "medium".to_string(),
+            variables: serde_json::json!({}),
+            pre_script: None,
+            post_script: None,
+            chaos: None,
+            session_login: None,
+            environments: Default::default(),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let mut step = redirect_step(None);
+        step.config["url"] = serde_json::json!("/orders/{{ orderId }}");
+
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some("http://127.0.0.1:9".to_string()))
+            .with_dry_run(true)
+            .with_run_log(Some(RunLog::new()));
+        let run = executor.execute_scenario(&scenario, std::slice::from_ref(&step), None);
+
+        let messages: Vec<String> = run.logs.iter().map(|e| format!("{} {}", e.level, e.message)).collect();
+        assert!(messages[0].starts_with("info Starting scenario execution: Traced"), "{:?}", messages);
+        assert!(messages.contains(&"warn Variable orderId not found in context".to_string()), "{:?}", messages);
+        assert!(messages.iter().any(|m| m.starts_with("info Request: GET http://127.0.0.1:9/orders/")), "{:?}", messages);
+
+        let run = ScenarioExecutor::new().with_dry_run(true).execute_scenario(&scenario, &[step], None);
+        assert!(run.logs.is_empty());
+    }
+
+    #[test]
+    fn test_idempotency_key_injection() {
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some("http://127.0.0.1:9".to_string()))
+            .with_dry_run(true)
+            .with_idempotency(Some(IdempotencyConfig {
+                header_name: "X-Idempotency-Key".to_string(),
+                ..IdempotencyConfig::default()
+            }));
+
+        let mut step = redirect_step(None);
+        step.config["method"] = serde_json::json!("POST");
+        let first = executor.execute_request_step(&step).request.unwrap();
+        let second = executor.execute_request_step(&step).request.unwrap();
+        let key = first.headers.get("X-Idempotency-Key").unwrap();
+        assert!(uuid::Uuid::parse_str(key).is_ok());
+        assert_ne!(Some(key), second.headers.get("X-Idempotency-Key"));
+
+        step.config["headers"] = serde_json::json!({ "x-idempotency-key": "fixed-key" });
+        let explicit = executor.execute_request_step(&step).request.unwrap();
+        assert_eq!(explicit.headers.get("x-idempotency-key").map(|s| s.as_str()), Some("fixed-key"));
+        assert!(!explicit.headers.contains_key("X-Idempotency-Key"));
+
+        step.config["method"] = serde_json::json!("GET");
+        step.config["headers"] = serde_json::json!({});
+        let get = executor.execute_request_step(&step).request.unwrap();
+        assert!(!get.headers.contains_key("X-Idempotency-Key"));
+
+        assert!(IdempotencyConfig { header_name: "Bad Header".to_string(), ..IdempotencyConfig::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_follow_redirects_policy() {
+        let base_url = spawn_redirect_fixture();
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url));
+
+        let followed = executor.execute_request_step(&redirect_step(None));
+        assert_eq!(followed.response.as_ref().unwrap().status, 200);
+        assert!(followed.response.as_ref().unwrap().final_url.as_deref().unwrap().ends_with("/new"));
+        let redirects = &followed.response.as_ref().unwrap().redirects;
+        assert_eq!(redirects.len(), 1);
+        assert_eq!(redirects[0].status, 302);
+        assert!(redirects[0].location.ends_with("/new"));
+
+        let mut step = redirect_step(Some(false));
+        let mut config = step.config.clone();
+        config["assertions"] = serde_json::json!([
+            { "name": "status", "source": "status", "operator": "equals", "expected": 302 },
+            { "name": "location", "source": "header", "path": "location", "operator": "equals", "expected": "/new" }
+        ]);
+        step.config = config;
+        let stopped = executor.execute_request_step(&step);
+        let response = stopped.response.as_ref().unwrap();
+        assert_eq!(response.status, 302);
+        assert!(response.redirects.is_empty());
+        assert_eq!(find_header(&response.headers, "Location").map(|s| s.as_str()), Some("/new"));
+        assert_eq!(stopped.status, StepResultStatus::Passed);
+
+        let mut limited = redirect_step(None);
+        limited.config["maxRedirects"] = serde_json::json!(0);
+        let failed = executor.execute_request_step(&limited);
+        assert_eq!(failed.status, StepResultStatus::Error);
+        assert!(failed.error.unwrap().contains("(redirect chain: 302 -> http://"));
+
+        // One client per redirect policy, reused by later steps
+        assert_eq!(executor.execute_request_step(&step).status, StepResultStatus::Passed);
+        assert_eq!(executor.redirect_clients.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_final_url_source() {
+        let base_url = spawn_redirect_fixture();
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url.clone()));
+
+        let mut step = redirect_step(None);
+        step.config["assertions"] = serde_json::json!([
+            { "name": "landed", "source": "finalUrl", "operator": "contains", "expected": "/new" }
+        ]);
+        step.config["extractVariables"] = serde_json::json!([
+            { "name": "landingUrl", "source": "finalUrl", "path": "" }
+        ]);
+        let result = executor.execute_request_step(&step);
+        assert_eq!(result.status, StepResultStatus::Passed);
+        assert_eq!(
+            result.extracted_variables.unwrap()["landingUrl"],
+            serde_json::json!(format!("{}/new", base_url))
+        );
+    }
+
+    #[test]
+    fn test_matches_operator() {
+        let executor = ScenarioExecutor::new();
+        let matches = |actual: serde_json::Value, pattern: &str| {
+            executor.compare_values(&actual, &serde_json::json!(pattern), "matches")
+        };
+
+        assert!(matches(serde_json::json!("user-42"), r"^user-\d+$").0);
+        assert!(!matches(serde_json::json!("admin-user-42"), r"^user-\d+$").0);
+        assert!(matches(serde_json::json!(201), r"^2\d\d$").0);
+
+        assert!(!matches(serde_json::json!("HELLO"), "^hello$").0);
+        assert!(matches(serde_json::json!("HELLO"), "(?i)^hello$").0);
+        assert!(matches(serde_json::json!("HELLO"), "/^hello$/i").0);
+
+        let (passed, error) = matches(serde_json::json!("abc"), "([a-z");
+        assert!(!passed);
+        assert!(error.unwrap().starts_with("Invalid regex"));
+    }
+
+    #[test]
+    fn test_chaos_drops_header() {
+        let base_url = spawn_redirect_fixture();
+        let mut executor = ScenarioExecutor::new().with_base_url(Some(base_url)).with_seed(Some(7));
+        executor.chaos = Some(ChaosConfig {
+            enabled: true,
+            percentage: 100,
+            faults: vec![ChaosFaultKind::DropHeader],
+            latency_ms: 0,
+            drop_header: Some("x-api-key".to_string()),
+        });
+
+        let mut step = redirect_step(None);
+        step.config["url"] = serde_json::json!("/new");
+        step.config["headers"] = serde_json::json!({ "X-Api-Key": "secret", "Accept": "application/json" });
+        let result = executor.execute_request_step(&step);
+
+        let request = result.request.unwrap();
+        assert_eq!(request.fault.unwrap().kind, ChaosFaultKind::DropHeader);
+        assert!(!request.headers.contains_key("X-Api-Key"));
+        assert!(request.headers.contains_key("Accept"));
+        assert_eq!(result.response.unwrap().status, 200);
+
+        executor.chaos = None;
+        let result = executor.execute_request_step(&step);
+        assert!(result.request.unwrap().fault.is_none());
+    }
+
+    #[test]
+    fn test_chaos_keeps_seeded_tokens() {
+        let tokens = "{{$randomInt}} {{$randomUuid}}";
+        let plain = ScenarioExecutor::new().with_seed(Some(7));
+        let expected = [plain.resolve_dynamic_tokens(tokens), plain.resolve_dynamic_tokens(tokens)];
+
+        let mut chaotic = ScenarioExecutor::new().with_seed(Some(7));
+        chaotic.chaos = Some(ChaosConfig {
+            enabled: true,
+            percentage: 100,
+            faults: vec![ChaosFaultKind::Latency],
+            latency_ms: 0,
+            drop_header: None,
+        });
+        let config: RequestStepConfig = serde_json::from_value(serde_json::json!({ "method": "GET", "url": "/" })).unwrap();
+        let first = chaotic.resolve_dynamic_tokens(tokens);
+        assert_eq!(chaotic.pick_fault(&config, "GET").unwrap().0, ChaosFaultKind::Latency);
+        assert_eq!([first, chaotic.resolve_dynamic_tokens(tokens)], expected);
+    }
+
+    /// Serves POST /login (sets `sid` for password "secret") and GET /me (200 only with the cookie)
+    fn spawn_login_fixture() -> String {
+        serve(|request| {
+            if request.starts_with("POST /login ") && request.contains("password=secret") {
+                http_response("302 Found\r\nLocation: /me\r\nSet-Cookie: sid=abc; Path=/; HttpOnly", "")
+            } else if request.starts_with("GET /me ") && request.to_lowercase().contains("cookie: sid=abc") {
+                http_response("200 OK\r\nContent-Type: application/json", r#"{"ok":true}"#)
+            } else {
+                http_response("401 Unauthorized", "")
+            }
+        })
+    }
+
+    #[test]
+    fn test_session_login() {
+        let base_url = spawn_login_fixture();
+        let login = |password: &str| SessionLoginConfig {
+            url: "/login".to_string(),
+            method: "POST".to_string(),
+            username_field: "username".to_string(),
+            password_field: "password".to_string(),
+            username: "{{username}}".to_string(),
+            password: password.to_string(),
+            json: false,
+            expected_cookie: "sid".to_string(),
+        };
+        let scenario = |password: &str| TestScenario {
+            id: "scenario-1".to_string(),
+            project_id: "project-1".to_string(),
+            name: "Cookie auth".to_string(),
+            description: None,
+            priority: "medium".to_string(),
+            variables: serde_json::json!({ "username": "ann", "password": "secret" }),
+            pre_script: None,
+            post_script: None,
+            chaos: None,
+            session_login: Some(login(password)),
+            environments: Default::default(),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let mut step = redirect_step(None);
+        step.config["url"] = serde_json::json!("/me");
+        step.config["assertions"] = serde_json::json!([
+            { "name": "status", "source": "status", "operator": "equals", "expected": 200 }
+        ]);
+
+        let run = ScenarioExecutor::new()
+            .with_base_url(Some(base_url.clone()))
+            .execute_scenario(&scenario("{{password}}"), std::slice::from_ref(&step), None);
+        assert_eq!(run.status, ScenarioRunStatus::Passed);
+        assert_eq!(run.results[0].step_id, "session-login");
+        assert_eq!(run.results[0].request.as_ref().unwrap().body.as_ref().unwrap()["password"], "***");
+        assert_eq!(run.results[1].request.as_ref().unwrap().headers.get("Cookie").map(|s| s.as_str()), Some("sid=abc"));
+
+        let run = ScenarioExecutor::new()
+            .with_base_url(Some(base_url))
+            .execute_scenario(&scenario("wrong"), std::slice::from_ref(&step), None);
+        assert_eq!(run.status, ScenarioRunStatus::Failed);
+        assert_eq!(run.error_message.as_deref(), Some("Session login returned HTTP 401 Unauthorized"));
+        assert_eq!(run.results[1].status, StepResultStatus::Skipped);
+    }
+
+    /// Serves GET /set (sets a site-wide and an /api-scoped cookie) and echoes the Cookie header
+    /// it received on any other path
+    fn spawn_cookie_fixture() -> u16 {
+        let base_url = serve(|request| {
+            if request.starts_with("GET /set ") {
+                http_response("200 OK\r\nSet-Cookie: sid=abc; Path=/\r\nSet-Cookie: scoped=1; Path=/api", "")
+            } else {
+                let cookie = request.lines()
+                    .find_map(|l| l.to_lowercase().strip_prefix("cookie:").map(|v| v.trim().to_string()))
+                    .unwrap_or_default();
+                http_response("200 OK\r\nContent-Type: text/plain", cookie)
+            }
+        });
+        reqwest::Url::parse(&base_url).unwrap().port().unwrap()
+    }
+
+    #[test]
+    fn test_cookies_are_scoped_to_host_and_path() {
+        let port = spawn_cookie_fixture();
+        let mut executor = ScenarioExecutor::new();
+        let send = |executor: &mut ScenarioExecutor, url: String| {
+            let mut step = redirect_step(None);
+            step.config["url"] = serde_json::json!(url);
+            let result = executor.execute_request_step(&step);
+            let sent = result.request.unwrap().headers.get("Cookie").cloned();
+            (sent, result.response.unwrap().raw_body)
+        };
+
+        send(&mut executor, format!("http://127.0.0.1:{}/set", port));
+        let (sent, received) = send(&mut executor, format!("http://127.0.0.1:{}/me", port));
+        assert_eq!(sent.as_deref(), Some("sid=abc"));
+        assert_eq!(received, "sid=abc");
+
+        let (_, received) = send(&mut executor, format!("http://127.0.0.1:{}/api/orders", port));
+        assert!(received.contains("scoped=1") && received.contains("sid=abc"));
+
+        // Same server under another host name: nothing from 127.0.0.1 is sent
+        let (sent, received) = send(&mut executor, format!("http://localhost:{}/me", port));
+        assert_eq!(sent, None);
+        assert_eq!(received, "");
+    }
+
+    #[test]
+    fn test_step_limit_and_reference_cycle_stop_run() {
+        let scenario = TestScenario {
+            id: "scenario-1".to_string(),
+            project_id: "project-1".to_string(),
+            name: "Pathological".to_string(),
+            description: None,
+            priority: "medium".to_string(),
+            variables: serde_json::json!({}),
+            pre_script: None,
+            post_script: None,
+            chaos: None,
+            session_login: None,
+            environments: Default::default(),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let delay = |id: &str| TestScenarioStep {
+            id: id.to_string(),
+            step_type: TestStepType::Delay,
+            name: id.to_string(),
+            config: serde_json::json!({ "durationMs": 1 }),
+            ..redirect_step(None)
+        };
+
+        let mut executor = ScenarioExecutor::new().with_max_executed_steps(2);
+        let run = executor.execute_scenario(&scenario, &[delay("a"), delay("b"), delay("c")], None);
+        assert_eq!(run.status, ScenarioRunStatus::Error);
+        assert_eq!(run.results.len(), 2);
+        assert_eq!(run.error_message.as_deref(), Some("Run stopped: exceeded the limit of 2 executed steps"));
+
+        let condition = |id: &str, target: &str| TestScenarioStep {
+            step_type: TestStepType::Condition,
+            config: serde_json::json!({ "condition": "true", "trueSteps": [target], "falseSteps": [] }),
+            ..delay(id)
+        };
+        let run = ScenarioExecutor::new().execute_scenario(&scenario, &[condition("a", "b"), condition("b", "a")], None);
+        assert_eq!(run.status, ScenarioRunStatus::Error);
+        assert!(run.results.is_empty());
+        assert_eq!(run.error_message.as_deref(), Some("Step reference cycle: a -> b -> a"));
+    }
+
+    #[test]
+    fn test_stop_scenario_run() {
+        assert!(!stop_scenario_run("unknown-run"));
+
+        let delay = TestScenarioStep {
+            id: "delay-1".to_string(),
+            step_type: TestStepType::Delay,
+            name: "Long wait".to_string(),
+            config: serde_json::json!({ "durationMs": 30_000 }),
+            ..redirect_step(None)
+        };
+        let scenario = TestScenario {
+            id: "scenario-1".to_string(),
+            project_id: "project-1".to_string(),
+            name: "Slow".to_string(),
+            description: None,
+            priority: "medium".to_string(),
+            variables: serde_json::json!({}),
+            pre_script: None,
+            post_script: None,
+            chaos: None,
+            session_login: None,
+            environments: Default::default(),
+            created_at: 0,
+            updated_at: 0,
+        };
+
+        let mut executor = ScenarioExecutor::new();
+        let flag = executor.cancelled.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        let started = Instant::now();
+        let run = executor.execute_scenario(&scenario, &[delay, redirect_step(None)], None);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(run.status, ScenarioRunStatus::Stopped);
+        assert_eq!(run.results.len(), 1);
+        assert_eq!(run.results[0].status, StepResultStatus::Skipped);
+        assert!(!running_scenarios().lock().unwrap().contains_key(&run.id));
+    }
+
+    #[test]
+    fn test_content_type_source() {
+        let executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.content_type = Some("Application/JSON; charset=utf-8".to_string());
+
+        let assertion = |operator: &str, expected: &str| Assertion {
+            name: "content type".to_string(),
+            source: "contentType".to_string(),
+            path: None,
+            operator: operator.to_string(),
+            expected: serde_json::json!(expected),
+            actual: None,
+            passed: None,
+            error: None,
+            template: None,
+            params: None,
+            ignore_paths: None,
+            only_if_status: None,
+            severity: None,
+            skipped: false,
+            logic: None,
+            assertions: None,
+        };
+
+        let result = executor.evaluate_assertion(&assertion("equals", "application/json"), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+
+        let result = executor.evaluate_assertion(&assertion("equals", "Application/JSON; charset=utf-8"), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+
+        response.content_type = Some("text/html; charset=utf-8".to_string());
+        let result = executor.evaluate_assertion(&assertion("equals", "application/json"), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(false));
+        assert_eq!(result.actual, Some(serde_json::json!("text/html")));
+
+        response.content_type = None;
+        let result = executor.evaluate_assertion(&assertion("equals", "application/json"), &response, 5, &HashMap::new());
+        assert_eq!(result.actual, Some(serde_json::Value::Null));
+    }
+
+    #[test]
+    fn test_snapshot_assertions() {
+        let mut executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.body = serde_json::json!({
+            "id": 7, "name": "Ann", "createdAt": "2026-01-01", "items": [{ "id": 1, "sku": "A" }]
+        });
+        let assertion = Assertion {
+            name: "user snapshot".to_string(),
+            source: "body".to_string(),
+            path: None,
+            operator: "snapshot".to_string(),
+            expected: serde_json::json!("user"),
+            actual: None,
+            passed: None,
+            error: None,
+            template: None,
+            params: None,
+            ignore_paths: Some(vec!["$.id".to_string(), "createdAt".to_string(), "items[*].id".to_string()]),
+            only_if_status: None,
+            severity: None,
+            skipped: false,
+            logic: None,
+            assertions: None,
+        };
+
+        // First run records the snapshot
+        assert_eq!(executor.evaluate_snapshot(&assertion, &response, "step-1").passed, Some(true));
+        assert_eq!(executor.snapshot_updates.len(), 1);
+
+        // Volatile fields are masked, real changes fail with a diff
+        response.body["id"] = serde_json::json!(8);
+        response.body["createdAt"] = serde_json::json!("2026-02-02");
+        response.body["items"][0]["id"] = serde_json::json!(2);
+        assert_eq!(executor.evaluate_snapshot(&assertion, &response, "step-1").passed, Some(true));
+        response.body["name"] = serde_json::json!("Bob");
+        let result = executor.evaluate_snapshot(&assertion, &response, "step-1");
+        assert_eq!(result.passed, Some(false));
+        assert!(result.error.unwrap().contains("~ name: expected \"Ann\" but got \"Bob\""));
+        assert_eq!(executor.snapshot_updates.len(), 1);
+
+        // Update mode rewrites instead of failing
+        executor.update_snapshots = true;
+        assert_eq!(executor.evaluate_snapshot(&assertion, &response, "step-1").passed, Some(true));
+        assert_eq!(executor.snapshot_updates.len(), 2);
+        assert_eq!(executor.snapshot_updates[1].body["name"], "Bob");
+    }
+
+    #[test]
+    fn test_golden_response_assertions() {
+        let executor = ScenarioExecutor::new();
+        let golden = GoldenResponse {
+            endpoint_id: "project-1-GET-api-users-id".to_string(),
+            status_code: 200,
+            body: serde_json::json!({ "id": 1, "name": "Ann" }),
+            updated_at: 0,
+        };
+        let mut response = response_with_headers();
+        response.body = serde_json::json!({ "id": 1, "name": "Ann" });
+
+        let results: Vec<Assertion> = golden.to_assertions()
+            .iter()
+            .map(|a| executor.evaluate_assertion(a, &response, 5, &HashMap::new()))
+            .collect();
+        assert!(results.iter().all(|a| a.passed == Some(true)));
+
+        response.body = serde_json::json!({ "id": 1, "name": "Bob" });
+        let body = executor.evaluate_assertion(&golden.to_assertions()[1], &response, 5, &HashMap::new());
+        assert_eq!(body.passed, Some(false));
+        assert!(body.error.unwrap().contains("~ name: expected \"Ann\" but got \"Bob\""));
+    }
+
+    #[test]
+    fn test_equals_object_diff() {
+        let executor = ScenarioExecutor::new();
+        let expected = serde_json::json!({
+            "user": { "id": 1, "name": "Ann", "roles": ["admin", "dev"] },
+            "active": true
+        });
+        let actual = serde_json::json!({
+            "user": { "id": 1, "name": "Bob", "roles": ["admin"], "email": "bob@example.com" }
+        });
+
+        let (passed, error) = executor.compare_values(&actual, &expected, "equals");
+        assert!(!passed);
+        let error = error.unwrap();
+        let lines: Vec<&str> = error.lines().collect();
+        assert_eq!(lines[0], "Value differs from expected:");
+        assert!(lines.contains(&"~ user.name: expected \"Ann\" but got \"Bob\""));
+        assert!(lines.contains(&"- user.roles[1]: missing (expected \"dev\")"));
+        assert!(lines.contains(&"+ user.email: unexpected \"bob@example.com\""));
+        assert!(lines.contains(&"- active: missing (expected true)"));
+        assert_eq!(lines.len(), 5);
+
+        let (_, error) = executor.compare_values(&serde_json::json!("x"), &expected, "equals");
+        assert_eq!(error.unwrap(), format!("Value differs from expected:\n~ (root): expected {} but got \"x\"", expected));
+    }
+
+    #[test]
+    fn test_body_file() {
+        let base_url = spawn_redirect_fixture();
+        let mut variables = HashMap::new();
+        variables.insert("email".to_string(), serde_json::json!("a@example.com"));
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some(base_url))
+            .with_variables(variables);
+
+        let path = std::env::temp_dir().join(format!("lookapi-body-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"email": "{{email}}", "tags": ["a", "b"]}"#).unwrap();
+
+        let mut step = redirect_step(None);
+        step.config["url"] = serde_json::json!("/new");
+        step.config["method"] = serde_json::json!("POST");
+        step.config["bodyFile"] = serde_json::json!(path.to_string_lossy());
+        let result = executor.execute_request_step(&step);
+        assert_eq!(result.status, StepResultStatus::Passed);
+        assert_eq!(
+            result.request.unwrap().body,
+            Some(serde_json::json!({ "email": "a@example.com", "tags": ["a", "b"] }))
+        );
+
+        step.config["body"] = serde_json::json!({ "inline": true });
+        let result = executor.execute_request_step(&step);
+        assert_eq!(result.status, StepResultStatus::Error);
+        assert!(result.error.unwrap().contains("both body and bodyFile"));
+
+        let _ = std::fs::remove_file(&path);
+        step.config["body"] = serde_json::Value::Null;
+        let result = executor.execute_request_step(&step);
+        assert!(result.error.unwrap().starts_with("Body file not found"));
+    }
+
+    #[test]
+    fn test_numeric_operators_coerce_strings() {
+        let executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.body = serde_json::json!({ "count": "42", "name": "n/a" });
+
+        let assertion = |path: &str, operator: &str, expected: serde_json::Value| Assertion {
+            name: path.to_string(),
+            source: "body".to_string(),
+            path: Some(path.to_string()),
+            operator: operator.to_string(),
+            expected,
+            actual: None,
+            passed: None,
+            error: None,
+            template: None,
+            params: None,
+            ignore_paths: None,
+            only_if_status: None,
+            severity: None,
+            skipped: false,
+            logic: None,
+            assertions: None,
+        };
+
+        let result = executor.evaluate_assertion(&assertion("count", "greaterThan", serde_json::json!(10)), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+        assert_eq!(result.actual, Some(serde_json::json!(42.0)));
+
+        let result = executor.evaluate_assertion(&assertion("count", "lessThan", serde_json::json!("100")), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+
+        let result = executor.evaluate_assertion(&assertion("name", "greaterThan", serde_json::json!(10)), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(false));
+        assert!(result.error.unwrap().contains("numeric"));
+        assert_eq!(result.actual, Some(serde_json::json!("n/a")));
+    }
+
+    #[test]
+    fn test_between_operator() {
+        let executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.status = 204;
+        response.body = serde_json::json!({ "price": "19.5", "name": "n/a" });
+
+        let assertion = |source: &str, path: Option<&str>, expected: serde_json::Value| Assertion {
+            name: source.to_string(),
+            source: source.to_string(),
+            path: path.map(str::to_string),
+            operator: "between".to_string(),
+            expected,
+            actual: None,
+            passed: None,
+            error: None,
+            template: None,
+            params: None,
+            ignore_paths: None,
+            only_if_status: None,
+            severity: None,
+            skipped: false,
+            logic: None,
+            assertions: None,
+        };
+
+        // Inclusive bounds
+        let result = executor.evaluate_assertion(&assertion("status", None, serde_json::json!([200, 299])), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+        let result = executor.evaluate_assertion(&assertion("duration", None, serde_json::json!([50, 500])), &response, 50, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+        let result = executor.evaluate_assertion(&assertion("body", Some("price"), serde_json::json!([10, "20"])), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(true));
+        assert_eq!(result.actual, Some(serde_json::json!(19.5)));
+
+        // Out of range reports the actual value and the range
+        let result = executor.evaluate_assertion(&assertion("duration", None, serde_json::json!([50, 500])), &response, 501, &HashMap::new());
+        assert_eq!(result.passed, Some(false));
+        assert_eq!(result.actual, Some(serde_json::json!(501)));
+        assert_eq!(result.error.as_deref(), Some("Expected 501 to be between 50 and 500 (inclusive)"));
+        let result = executor.evaluate_assertion(&assertion("status", None, serde_json::json!([400, 499])), &response, 5, &HashMap::new());
+        assert_eq!(result.passed, Some(false));
+
+        // Malformed ranges and non-numeric values fail with a reason
+        let result = executor.evaluate_assertion(&assertion("status", None, serde_json::json!([200])), &response, 5, &HashMap::new());
+        assert!(result.error.unwrap().contains("[min, max]"));
+        let result = executor.evaluate_assertion(&assertion("body", Some("name"), serde_json::json!([0, 1])), &response, 5, &HashMap::new());
+        assert!(result.error.unwrap().contains("numeric"));
+    }
+
+    #[test]
+    fn test_variable_assertions() {
+        let mut variables = HashMap::new();
+        variables.insert("userId".to_string(), serde_json::json!(7));
+        variables.insert("authToken".to_string(), serde_json::json!("stale"));
+        let executor = ScenarioExecutor::new().with_variables(variables);
+        let response = response_with_headers();
+
+        let assertion = |path: &str, operator: &str, expected: serde_json::Value| Assertion {
+            name: path.to_string(),
+            source: "variable".to_string(),
+            path: Some(path.to_string()),
+            operator: operator.to_string(),
+            expected,
+            actual: None,
+            passed: None,
+            error: None,
+            template: None,
+            params: None,
+            ignore_paths: None,
+            only_if_status: None,
+            severity: None,
+            skipped: false,
+            logic: None,
+            assertions: None,
+        };
+
+        // Just-extracted values shadow the running map
+        let mut extracted = HashMap::new();
+        extracted.insert("authToken".to_string(), serde_json::json!("eyJ.abc.def"));
+        extracted.insert("user".to_string(), serde_json::json!({ "roles": ["admin"] }));
+        let check = |a: Assertion| executor.evaluate_assertion(&a, &response, 5, &extracted);
+
+        assert_eq!(check(assertion("authToken", "matches", serde_json::json!("^eyJ"))).passed, Some(true));
+        assert_eq!(check(assertion("userId", "equals", serde_json::json!(7))).passed, Some(true));
+        assert_eq!(check(assertion("user.roles[0]", "equals", serde_json::json!("admin"))).passed, Some(true));
+
+        // An extractor that found nothing stores null, so `exists` fails the step
+        extracted.insert("refreshToken".to_string(), serde_json::Value::Null);
+        let result = executor.evaluate_assertion(&assertion("refreshToken", "exists", serde_json::Value::Null), &response, 5, &extracted);
+        assert_eq!(result.passed, Some(false));
+        let result = executor.evaluate_assertion(&assertion("unknown", "exists", serde_json::Value::Null), &response, 5, &extracted);
+        assert_eq!(result.passed, Some(false));
+    }
+
+    #[test]
+    fn test_type_operator() {
+        let executor = ScenarioExecutor::new();
+        let check = |actual: serde_json::Value, expected: &str| {
+            executor.compare_values(&actual, &serde_json::json!(expected), "type").0
+        };
+        assert!(check(serde_json::json!(3), "integer"));
+        assert!(check(serde_json::json!(3), "number"));
+        assert!(!check(serde_json::json!(3.5), "integer"));
+        assert!(check(serde_json::json!([1]), "array"));
+        assert!(!check(serde_json::Value::Null, "object"));
+    }
+
+    #[test]
+    fn test_header_prefix_matching() {
+        let executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.headers.insert("x-ratelimit-remaining".to_string(), "99".to_string());
+        response.headers.insert("X-RateLimit-Limit".to_string(), "100".to_string());
+
+        let count = header_assertion("X-RateLimit-*", "equals", serde_json::json!(2));
+        assert_eq!(executor.evaluate_assertion(&count, &response, 5, &HashMap::new()).passed, Some(true));
+        let exists = header_assertion("x-ratelimit-*", "exists", serde_json::Value::Null);
+        assert_eq!(executor.evaluate_assertion(&exists, &response, 5, &HashMap::new()).passed, Some(true));
+        let missing = header_assertion("X-Trace-*", "exists", serde_json::Value::Null);
+        assert_eq!(executor.evaluate_assertion(&missing, &response, 5, &HashMap::new()).passed, Some(false));
+
+        let extractor = VariableExtractor {
+            name: "limit".to_string(),
+            source: "header".to_string(),
+            path: "X-RATELIMIT-*".to_string(),
+            default_value: None,
+        };
+        assert_eq!(executor.extract_variable(&extractor, &response), serde_json::json!("100"));
+    }
+
+    #[test]
+    fn test_header_extraction_ignores_case() {
+        let executor = ScenarioExecutor::new();
+        let extractor = VariableExtractor {
+            name: "requestId".to_string(),
+            source: "header".to_string(),
+            path: "X-REQUEST-ID".to_string(),
+            default_value: None,
+        };
+        assert_eq!(
+            executor.extract_variable(&extractor, &response_with_headers()),
+            serde_json::json!("abc-123")
+        );
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        let small = serde_json::json!({"ok": true});
+        assert_eq!(truncate_body(&small, 1024), (small.clone(), false));
+
+        let large = serde_json::Value::String("a".repeat(100));
+        let (stored, truncated) = truncate_body(&large, 10);
+        assert!(truncated);
+        assert_eq!(stored, serde_json::Value::String(format!("{}...[truncated 90 bytes]", "a".repeat(10))));
+    }
+
+    #[test]
+    fn test_project_body_limits_apply_to_stored_bodies() {
+        let limits: BodyLimits = serde_json::from_value(serde_json::json!({ "maxRequestBytes": 8, "maxResponseBytes": 4 })).unwrap();
+        let mut executor = ScenarioExecutor::new()
+            .with_base_url(Some(spawn_redirect_fixture()))
+            .with_body_limits(limits);
+        let mut step = redirect_step(None);
+        step.config = serde_json::json!({
+            "url": "/new",
+            "method": "POST",
+            "body": { "name": "a long name" },
+            "assertions": [{ "name": "ok", "source": "body", "path": "ok", "operator": "equals", "expected": true }],
+        });
+        let result = executor.execute_request_step(&step);
+
+        // Assertions see the whole 11-byte response; only the stored copy is cut to 4 bytes
+        assert_eq!(result.status, StepResultStatus::Passed);
+        let request_body = result.request.unwrap().body.unwrap();
+        assert_eq!(request_body, serde_json::json!(r#"{"name":...[truncated 14 bytes]"#));
+        let response = result.response.unwrap();
+        assert!(response.truncated);
+        assert_eq!(response.body, serde_json::json!(r#"{"ok...[truncated 7 bytes]"#));
+
+        let defaults: BodyLimits = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(defaults, BodyLimits::default());
+        assert_eq!(defaults.max_response_bytes, DEFAULT_MAX_RESPONSE_BODY_BYTES);
+    }
+
+    #[test]
+    fn test_assertion_groups() {
+        let mut executor = ScenarioExecutor::new();
+        let mut response = response_with_headers();
+        response.body = serde_json::json!({ "role": "owner" });
+        let role_check = |role: &str| serde_json::json!({ "name": role, "source": "body", "path": "role", "operator": "equals", "expected": role });
+        let group = |logic: &str, members: serde_json::Value| -> Assertion {
+            serde_json::from_value(serde_json::json!({ "name": "group", "logic": logic, "assertions": members })).unwrap()
+        };
+        let mut run = |assertion: Assertion| executor.run_assertion(assertion, &response, 5, &HashMap::new(), "step-1");
+
+        // status == 200 AND (role == admin OR role == owner)
+        let result = run(group("and", serde_json::json!([
+            { "name": "ok", "source": "status", "operator": "equals", "expected": 200 },
+            { "name": "privileged", "logic": "or", "assertions": [role_check("admin"), role_check("owner")] }
+        ])));
+        assert_eq!(result.passed, Some(true));
+        let members = result.assertions.unwrap();
+        let nested = members[1].assertions.as_ref().unwrap();
+        assert_eq!((nested[0].passed, nested[1].passed), (Some(false), Some(true)));
+
+        let result = run(group("and", serde_json::json!([role_check("admin"), role_check("owner")])));
+        assert_eq!(result.passed, Some(false));
+        assert_eq!(result.error.as_deref(), Some("Failed: admin"));
+
+        let result = run(group("or", serde_json::json!([role_check("admin"), role_check("guest")])));
+        assert_eq!(result.passed, Some(false));
+        assert_eq!(result.error.as_deref(), Some("None of 2 assertions passed"));
+
+        // Members gated out by status don't count; an all-skipped group is skipped
+        let mut gated = role_check("admin");
+        gated["onlyIfStatus"] = serde_json::json!("4xx");
+        assert_eq!(run(group("or", serde_json::json!([gated.clone(), role_check("owner")]))).passed, Some(true));
+        assert!(run(group("and", serde_json::json!([gated]))).skipped);
+
+        assert_eq!(run(group("xor", serde_json::json!([role_check("owner")]))).passed, Some(false));
+        assert_eq!(run(group("or", serde_json::json!([]))).passed, Some(false));
+    }
+
+    /// Health service on a local port plus a descriptor set describing it
+    fn spawn_grpc_health_fixture(runtime: &tokio::runtime::Runtime) -> (String, String) {
+        use tonic_health::ServingStatus;
+
+        let listener = runtime.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (reporter, service) = tonic_health::server::health_reporter();
+        runtime.block_on(reporter.set_service_status("orders", ServingStatus::Serving));
+        runtime.spawn(
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        let descriptor_set = std::env::temp_dir().join(format!("lookapi-grpc-health-{}.pb", std::process::id()));
+        std::fs::write(&descriptor_set, tonic_health::pb::FILE_DESCRIPTOR_SET).unwrap();
+        (format!("http://{}", addr), descriptor_set.to_string_lossy().to_string())
+    }
+
+    fn grpc_step(endpoint: &str, descriptor_set: &str, service: &str) -> TestScenarioStep {
+        TestScenarioStep {
+            id: "grpc-1".to_string(),
+            scenario_id: "scenario-1".to_string(),
+            step_order: 0,
+            step_type: TestStepType::Grpc,
+            name: "Health check".to_string(),
+            config: serde_json::json!({
+                "endpoint": endpoint,
+                "descriptorSet": descriptor_set,
+                "service": "grpc.health.v1.Health",
+                "method": "Check",
+                "message": { "service": service },
+                "metadata": { "x-request-id": "{{ requestId }}" },
+            }),
+            enabled: true,
+            enabled_if: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_grpc_step_calls_unary_method() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (endpoint, descriptor_set) = spawn_grpc_health_fixture(&runtime);
+        let mut executor = ScenarioExecutor::new();
+        executor.variables.insert("requestId".to_string(), serde_json::json!("r-1"));
+
+        let mut step = grpc_step(&endpoint, &descriptor_set, "{{ svc }}");
+        executor.variables.insert("svc".to_string(), serde_json::json!("orders"));
+        step.config["extractVariables"] = serde_json::json!([{ "name": "health", "source": "body", "path": "status" }]);
+        step.config["assertions"] = serde_json::json!([
+            { "name": "ok", "source": "status", "operator": "equals", "expected": 0 },
+            { "name": "serving", "source": "body", "path": "status", "operator": "equals", "expected": "SERVING" },
+            { "name": "trailer", "source": "header", "path": "grpc-status", "operator": "equals", "expected": "0" }
+        ]);
+
+        let result = executor.execute_grpc_step(&step);
+        assert_eq!(result.status, StepResultStatus::Passed, "{:?}", result.error);
+        let request = result.request.unwrap();
+        assert_eq!(request.url, format!("{}/grpc.health.v1.Health/Check", endpoint));
+        assert_eq!(request.headers.get("x-request-id").map(String::as_str), Some("r-1"));
+        assert_eq!(request.body, Some(serde_json::json!({ "service": "orders" })));
+        let response = result.response.unwrap();
+        assert_eq!((response.status, response.status_text.as_str()), (0, "OK"));
+        assert_eq!(result.extracted_variables.unwrap().get("health"), Some(&serde_json::json!("SERVING")));
+
+        let _ = std::fs::remove_file(&descriptor_set);
+    }
+
+    #[test]
+    fn test_grpc_step_reports_failures() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (endpoint, descriptor_set) = spawn_grpc_health_fixture(&runtime);
+        let mut executor = ScenarioExecutor::new();
+
+        // A non-OK status without assertions fails the step with the status in the error
+        let step = grpc_step(&endpoint, &descriptor_set, "billing");
+        let result = executor.execute_grpc_step(&step);
+        assert_eq!(result.status, StepResultStatus::Failed);
+        assert!(result.error.as_deref().unwrap().starts_with("gRPC status NOT_FOUND"));
+        let response = result.response.unwrap();
+        assert_eq!((response.status, response.status_text.as_str()), (5, "NOT_FOUND"));
+        assert_eq!(response.headers.get("grpc-status").map(String::as_str), Some("5"));
+
+        // ...unless the step expects it
+        let mut step = grpc_step(&endpoint, &descriptor_set, "billing");
+        step.config["assertions"] = serde_json::json!([{ "name": "not found", "source": "status", "operator": "equals", "expected": 5 }]);
+        assert_eq!(executor.execute_grpc_step(&step).status, StepResultStatus::Passed);
+
+        // Messages that don't fit the input type, unknown methods and unreachable servers are errors
+        let mut step = grpc_step(&endpoint, &descriptor_set, "orders");
+        step.config["message"] = serde_json::json!({ "service": 42, "extra": true });
+        let result = executor.execute_grpc_step(&step);
+        assert_eq!(result.status, StepResultStatus::Error);
+        assert!(result.error.unwrap().starts_with("Request message does not match grpc.health.v1.HealthCheckRequest"));
+
+        let mut step = grpc_step(&endpoint, &descriptor_set, "orders");
+        step.config["method"] = serde_json::json!("Probe");
+        assert_eq!(executor.execute_grpc_step(&step).error.as_deref(), Some("Method not found on grpc.health.v1.Health: Probe"));
+
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let step = grpc_step(&format!("http://{}", closed), &descriptor_set, "orders");
+        let result = executor.execute_grpc_step(&step);
+        assert_eq!(result.status, StepResultStatus::Error);
+        assert!(result.error.unwrap().starts_with("Failed to connect to"));
+
+        let _ = std::fs::remove_file(&descriptor_set);
+    }
+}
@@ -0,0 +1,252 @@
+//! Unary gRPC calls for scenario steps
+//!
+//! Messages are built at run time from a descriptor pool: a compiled `FileDescriptorSet`
+//! (`protoc --include_imports --descriptor_set_out=api.pb`) or a `.proto` file compiled
+//! in-process with its own directory as the import root. Request and response messages go
+//! through the proto3 JSON mapping, so they are written in YAML and checked with the usual
+//! assertions and extractors. Plaintext (`http://`) endpoints use HTTP/2 without TLS;
+//! `https://` endpoints verify against the system roots.
+
+use super::executor::get_error_chain;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor, SerializeOptions};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tonic::client::Grpc;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::metadata::{AsciiMetadataKey, AsciiMetadataValue, MetadataMap};
+use tonic::transport::{ClientTlsConfig, Endpoint};
+use tonic::{Code, Request, Status};
+
+/// One unary call, with variables already resolved
+pub struct GrpcCall<'a> {
+    pub endpoint: &'a str,
+    pub message: &'a serde_json::Value,
+    pub metadata: &'a HashMap<String, String>,
+    pub timeout: Duration,
+}
+
+/// What the server answered: a status, its metadata (headers and trailers) and, on OK, the
+/// response message as JSON
+#[derive(Debug, Clone)]
+pub struct GrpcOutcome {
+    pub code: i32,
+    pub code_name: String, // Canonical name, e.g. "OK", "NOT_FOUND"
+    pub message: String,   // grpc-message of a non-OK status
+    pub metadata: HashMap<String, String>,
+    pub body: serde_json::Value,
+}
+
+/// Load the descriptor pool from a compiled descriptor set or a `.proto` file
+pub fn load_descriptors(proto_file: Option<&str>, descriptor_set: Option<&str>) -> Result<DescriptorPool, String> {
+    if let Some(path) = descriptor_set {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read descriptor set {}: {}", path, e))?;
+        return DescriptorPool::decode(bytes.as_slice())
+            .map_err(|e| format!("Invalid descriptor set {}: {}", path, e));
+    }
+    let path = proto_file.ok_or("gRPC step needs either protoFile or descriptorSet")?;
+    let path = Path::new(path);
+    let include = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let file_name = path.file_name().ok_or_else(|| format!("Invalid proto file path: {}", path.display()))?;
+    let mut compiler = protox::Compiler::new([include])
+        .map_err(|e| format!("Failed to compile {}: {}", path.display(), e))?;
+    compiler
+        .open_file(file_name)
+        .map_err(|e| format!("Failed to compile {}: {}", path.display(), e))?;
+    Ok(compiler.descriptor_pool())
+}
+
+/// Find a unary method by fully-qualified service name and method name
+pub fn find_method(pool: &DescriptorPool, service: &str, method: &str) -> Result<MethodDescriptor, String> {
+    let service_desc = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| format!("Service not found in descriptors: {}", service))?;
+    let method_desc = service_desc
+        .methods()
+        .find(|m| m.name() == method)
+        .ok_or_else(|| format!("Method not found on {}: {}", service, method))?;
+    if method_desc.is_client_streaming() || method_desc.is_server_streaming() {
+        return Err(format!("{}/{} is a streaming method; only unary calls are supported", service, method));
+    }
+    Ok(method_desc)
+}
+
+/// Invoke `method` and wait for the answer. Errors are failures to build the request or reach
+/// the server; a status returned by the server is an `Ok` outcome.
+pub fn call(method: &MethodDescriptor, call: &GrpcCall) -> Result<GrpcOutcome, String> {
+    let message = match call.message {
+        serde_json::Value::Null => serde_json::json!({}),
+        other => other.clone(),
+    };
+    let request_message = DynamicMessage::deserialize(method.input(), message)
+        .map_err(|e| format!("Request message does not match {}: {}", method.input().full_name(), e))?;
+
+    let mut metadata = MetadataMap::new();
+    for (key, value) in call.metadata {
+        let key = AsciiMetadataKey::from_bytes(key.to_ascii_lowercase().as_bytes())
+            .map_err(|e| format!("Invalid metadata key '{}': {}", key, e))?;
+        let value = AsciiMetadataValue::try_from(value.as_str())
+            .map_err(|e| format!("Invalid metadata value for '{}': {}", key, e))?;
+        metadata.insert(key, value);
+    }
+    let path = format!("/{}/{}", method.parent_service().full_name(), method.name());
+    let path = http::uri::PathAndQuery::try_from(path.as_str()).map_err(|e| format!("Invalid method path {}: {}", path, e))?;
+
+    // The executor is synchronous; run the call on its own runtime so this also works when the
+    // caller is already inside one
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| format!("Failed to start gRPC runtime: {}", e))?;
+                runtime.block_on(invoke(call, path, request_message, metadata, method.output()))
+            })
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e))
+    })
+}
+
+async fn invoke(
+    call: &GrpcCall<'_>,
+    path: http::uri::PathAndQuery,
+    message: DynamicMessage,
+    metadata: MetadataMap,
+    output: MessageDescriptor,
+) -> Result<GrpcOutcome, String> {
+    let mut endpoint = Endpoint::from_shared(call.endpoint.to_string())
+        .map_err(|e| format!("Invalid gRPC endpoint {}: {}", call.endpoint, e))?
+        .connect_timeout(call.timeout)
+        .timeout(call.timeout);
+    if call.endpoint.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| format!("TLS setup failed for {}: {}", call.endpoint, e))?;
+    }
+    let channel = endpoint
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", call.endpoint, get_error_chain(&e)))?;
+
+    let mut client = Grpc::new(channel);
+    client.ready().await.map_err(|e| format!("gRPC channel not ready: {}", e))?;
+
+    let mut request = Request::new(message);
+    *request.metadata_mut() = metadata;
+    match client.unary(request, path, DynamicCodec(output)).await {
+        Ok(response) => {
+            let (metadata, message, _) = response.into_parts();
+            let body = message
+                .serialize_with_options(serde_json::value::Serializer, &SerializeOptions::new().skip_default_fields(false))
+                .map_err(|e| format!("Failed to convert response to JSON: {}", e))?;
+            Ok(outcome(Code::Ok, String::new(), &metadata, body))
+        }
+        Err(status) => Ok(outcome(status.code(), status.message().to_string(), status.metadata(), serde_json::Value::Null)),
+    }
+}
+
+fn outcome(code: Code, message: String, metadata: &MetadataMap, body: serde_json::Value) -> GrpcOutcome {
+    let mut headers: HashMap<String, String> = metadata
+        .clone()
+        .into_headers()
+        .iter()
+        .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.to_string(), v.to_string())))
+        .collect();
+    headers.insert("grpc-status".to_string(), (code as i32).to_string());
+    if !message.is_empty() {
+        headers.insert("grpc-message".to_string(), message.clone());
+    }
+    GrpcOutcome {
+        code: code as i32,
+        code_name: code_name(code),
+        message,
+        metadata: headers,
+        body,
+    }
+}
+
+/// `NotFound` -> `NOT_FOUND`, as the gRPC spec spells status codes
+pub fn code_name(code: Code) -> String {
+    let mut name = String::new();
+    for (i, c) in format!("{:?}", code).chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+    name
+}
+
+/// Encodes and decodes messages described at run time
+#[derive(Clone)]
+struct DynamicCodec(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicCodec;
+    type Decoder = DynamicCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        self.clone()
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        self.clone()
+    }
+}
+
+impl Encoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: DynamicMessage, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        item.encode(dst).map_err(|e| Status::internal(format!("Failed to encode request: {}", e)))
+    }
+}
+
+impl Decoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<DynamicMessage>, Status> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("Failed to decode response: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_name() {
+        assert_eq!(code_name(Code::Ok), "OK");
+        assert_eq!(code_name(Code::NotFound), "NOT_FOUND");
+        assert_eq!(code_name(Code::DeadlineExceeded), "DEADLINE_EXCEEDED");
+    }
+
+    #[test]
+    fn test_load_descriptors_from_proto_file() {
+        let dir = std::env::temp_dir().join(format!("lookapi-grpc-proto-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let proto = dir.join("orders.proto");
+        std::fs::write(
+            &proto,
+            "syntax = \"proto3\";\npackage orders.v1;\nmessage GetOrderRequest { string id = 1; }\nmessage Order { string id = 1; int32 total = 2; }\nservice OrderService {\n  rpc GetOrder(GetOrderRequest) returns (Order);\n  rpc Watch(GetOrderRequest) returns (stream Order);\n}\n",
+        )
+        .unwrap();
+
+        let pool = load_descriptors(proto.to_str(), None).unwrap();
+        let method = find_method(&pool, "orders.v1.OrderService", "GetOrder").unwrap();
+        assert_eq!(method.output().full_name(), "orders.v1.Order");
+        assert!(find_method(&pool, "orders.v1.OrderService", "Watch").unwrap_err().contains("streaming"));
+        assert!(find_method(&pool, "orders.v1.Missing", "GetOrder").unwrap_err().contains("Service not found"));
+        assert!(load_descriptors(None, Some("/nonexistent/api.pb")).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
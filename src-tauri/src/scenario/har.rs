@@ -0,0 +1,309 @@
+//! Import of HAR (HTTP Archive) captures as scenarios
+//!
+//! Each HAR entry becomes a request step with its method, URL, headers and body.
+//! Headers that only describe the captured exchange (date, content-length, HTTP/2
+//! pseudo-headers, ...) are dropped so the replay computes its own. Auth header values
+//! repeated across requests can be lifted into scenario variables, using the same names
+//! as endpoint auth scaffolding (`authToken`, `apiKey`, `basicAuth`).
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use super::types::*;
+
+/// Headers that describe the captured exchange rather than the request, dropped on import
+const VOLATILE_HEADERS: &[&str] = &[
+    "date", "content-length", "host", "connection", "keep-alive", "transfer-encoding",
+    "if-none-match", "if-modified-since",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarLog {
+    #[serde(default)]
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarEntry {
+    pub request: HarRequest,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<HarNameValue>,
+    #[serde(rename = "postData", default)]
+    pub post_data: Option<HarPostData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarPostData {
+    #[serde(rename = "mimeType", default)]
+    pub mime_type: String,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Parse HAR JSON
+pub fn parse_har(content: &str) -> Result<Har, String> {
+    serde_json::from_str(content).map_err(|e| format!("Invalid HAR file: {}", e))
+}
+
+/// Check a request URL against a host filter (`api.example.com` or `api.example.com:8443`)
+fn matches_host(url: &str, filter_host: &str) -> bool {
+    let url = match reqwest::Url::parse(url) {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+    let host = url.host_str().unwrap_or("");
+    let filter = filter_host.trim();
+    match url.port() {
+        Some(port) if filter.contains(':') => format!("{}:{}", host, port).eq_ignore_ascii_case(filter),
+        _ => host.eq_ignore_ascii_case(filter.split(':').next().unwrap_or(filter)),
+    }
+}
+
+/// Variable name and header prefix for an auth header value, if it is one we parameterize
+fn auth_variable(header: &str, value: &str) -> Option<(&'static str, String)> {
+    match header.to_lowercase().as_str() {
+        "authorization" => {
+            let (scheme, _) = value.split_once(' ')?;
+            match scheme.to_lowercase().as_str() {
+                "bearer" => Some(("authToken", format!("{} ", scheme))),
+                "basic" => Some(("basicAuth", format!("{} ", scheme))),
+                _ => None,
+            }
+        }
+        "x-api-key" => Some(("apiKey", String::new())),
+        _ => None,
+    }
+}
+
+/// Build a scenario with one request step per HAR entry (optionally only those for `filter_host`).
+/// With `parameterize`, an auth value sent by more than one request becomes a scenario variable.
+pub fn har_to_scenario(
+    har: &Har,
+    project_id: &str,
+    name: &str,
+    filter_host: Option<&str>,
+    parameterize: bool,
+) -> Result<(TestScenario, Vec<TestScenarioStep>), String> {
+    let requests: Vec<&HarRequest> = har
+        .log
+        .entries
+        .iter()
+        .map(|e| &e.request)
+        .filter(|r| filter_host.is_none_or(|host| matches_host(&r.url, host)))
+        .collect();
+    if requests.is_empty() {
+        return Err(match filter_host {
+            Some(host) => format!("No HAR entries found for host {}", host),
+            None => "No entries found in HAR file".to_string(),
+        });
+    }
+
+    // Most frequent value per auth variable; only values seen at least twice are lifted
+    let mut variables = serde_json::Map::new();
+    if parameterize {
+        let mut counts: HashMap<(&'static str, String), usize> = HashMap::new();
+        for request in &requests {
+            for header in &request.headers {
+                if let Some((variable, prefix)) = auth_variable(&header.name, &header.value) {
+                    let secret = header.value[prefix.len()..].to_string();
+                    *counts.entry((variable, secret)).or_insert(0) += 1;
+                }
+            }
+        }
+        let mut candidates: Vec<((&'static str, String), usize)> = counts.into_iter().filter(|(_, n)| *n > 1).collect();
+        candidates.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        for ((variable, secret), _) in candidates {
+            variables.entry(variable.to_string()).or_insert(serde_json::Value::String(secret));
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let scenario = TestScenario {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id: project_id.to_string(),
+        name: name.to_string(),
+        description: Some(format!("Imported from HAR capture ({} requests)", requests.len())),
+        priority: "medium".to_string(),
+        variables: serde_json::Value::Object(variables.clone()),
+        pre_script: None,
+        post_script: None,
+        chaos: None,
+        session_login: None,
+        environments: Default::default(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    let steps = requests
+        .iter()
+        .enumerate()
+        .map(|(i, request)| {
+            let path = reqwest::Url::parse(&request.url)
+                .map(|u| u.path().to_string())
+                .unwrap_or_else(|_| request.url.clone());
+            TestScenarioStep {
+                id: uuid::Uuid::new_v4().to_string(),
+                scenario_id: scenario.id.clone(),
+                step_order: i as i32,
+                step_type: TestStepType::Request,
+                name: format!("{} {}", request.method.to_uppercase(), path),
+                config: request_to_config(request, &variables),
+                enabled: true,
+                enabled_if: None,
+                group: None,
+            }
+        })
+        .collect();
+
+    Ok((scenario, steps))
+}
+
+fn request_to_config(request: &HarRequest, variables: &serde_json::Map<String, serde_json::Value>) -> serde_json::Value {
+    let mut headers = HashMap::new();
+    for header in &request.headers {
+        let lower = header.name.to_lowercase();
+        if header.name.starts_with(':') || VOLATILE_HEADERS.contains(&lower.as_str()) {
+            continue;
+        }
+        let value = match auth_variable(&header.name, &header.value) {
+            Some((variable, prefix))
+                if variables.get(variable).and_then(|v| v.as_str()) == Some(&header.value[prefix.len()..]) =>
+            {
+                format!("{}{{{{{}}}}}", prefix, variable)
+            }
+            _ => header.value.clone(),
+        };
+        headers.insert(header.name.clone(), value);
+    }
+
+    // JSON bodies are kept structured; anything else is sent as captured
+    let body = request
+        .post_data
+        .as_ref()
+        .and_then(|data| data.text.as_ref().filter(|t| !t.is_empty()).map(|text| (data, text)))
+        .map(|(data, text)| match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(value) if data.mime_type.contains("json") || data.mime_type.is_empty() => value,
+            _ => serde_json::Value::String(text.clone()),
+        });
+
+    let config = RequestStepConfig {
+        endpoint_id: None,
+        url: request.url.clone(),
+        method: request.method.to_uppercase(),
+        headers: if headers.is_empty() { None } else { Some(headers) },
+        params: None,
+        query: None,
+        service: None,
+        body,
+        body_file: None,
+        extract_variables: None,
+        assertions: None,
+        with_items_from_csv: None,
+        follow_redirects: None,
+        max_redirects: None,
+    };
+    serde_json::to_value(&config).unwrap_or(serde_json::json!({}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+      "log": {
+        "version": "1.2",
+        "entries": [
+          {
+            "startedDateTime": "2026-01-05T10:00:00.000Z",
+            "request": {
+              "method": "POST",
+              "url": "https://api.example.com/orders?notify=1",
+              "headers": [
+                { "name": ":authority", "value": "api.example.com" },
+                { "name": "Authorization", "value": "Bearer eyJ.abc" },
+                { "name": "Content-Type", "value": "application/json" },
+                { "name": "Content-Length", "value": "14" },
+                { "name": "Date", "value": "Mon, 05 Jan 2026 10:00:00 GMT" }
+              ],
+              "postData": { "mimeType": "application/json", "text": "{\"sku\":\"A-1\"}" }
+            },
+            "response": { "status": 201 }
+          },
+          {
+            "request": {
+              "method": "get",
+              "url": "https://cdn.example.com/app.js",
+              "headers": []
+            }
+          },
+          {
+            "request": {
+              "method": "GET",
+              "url": "https://api.example.com/orders/42",
+              "headers": [
+                { "name": "authorization", "value": "Bearer eyJ.abc" },
+                { "name": "X-Trace", "value": "t-1" }
+              ]
+            }
+          }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn test_har_to_scenario() {
+        let har = parse_har(SAMPLE).unwrap();
+        let (scenario, steps) = har_to_scenario(&har, "project-1", "Checkout", Some("API.example.com"), true).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(scenario.variables, serde_json::json!({ "authToken": "eyJ.abc" }));
+
+        assert_eq!(steps[0].name, "POST /orders");
+        let create: RequestStepConfig = serde_json::from_value(steps[0].config.clone()).unwrap();
+        assert_eq!(create.url, "https://api.example.com/orders?notify=1");
+        assert_eq!(create.body, Some(serde_json::json!({ "sku": "A-1" })));
+        let headers = create.headers.unwrap();
+        let mut names: Vec<&str> = headers.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, vec!["Authorization", "Content-Type"]);
+        assert_eq!(headers["Authorization"], "Bearer {{authToken}}");
+
+        let get: RequestStepConfig = serde_json::from_value(steps[1].config.clone()).unwrap();
+        assert_eq!(get.headers.unwrap()["authorization"], "Bearer {{authToken}}");
+    }
+
+    #[test]
+    fn test_har_import_options() {
+        let har = parse_har(SAMPLE).unwrap();
+
+        // Without parameterizing, tokens stay literal; without a filter every entry is kept
+        let (scenario, steps) = har_to_scenario(&har, "project-1", "All", None, false).unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[1].name, "GET /app.js");
+        assert_eq!(scenario.variables, serde_json::json!({}));
+        let create: RequestStepConfig = serde_json::from_value(steps[0].config.clone()).unwrap();
+        assert_eq!(create.headers.unwrap()["Authorization"], "Bearer eyJ.abc");
+
+        // A token sent only once is not worth a variable
+        let (scenario, _) = har_to_scenario(&har, "project-1", "CDN", Some("cdn.example.com"), true).unwrap();
+        assert_eq!(scenario.variables, serde_json::json!({}));
+
+        assert!(har_to_scenario(&har, "project-1", "None", Some("other.example.com"), true).is_err());
+        assert!(parse_har("{\"entries\": []}").is_err());
+    }
+}
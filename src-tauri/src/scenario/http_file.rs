@@ -0,0 +1,458 @@
+//! Import and export of `.http` request files (VS Code REST Client / JetBrains HTTP Client)
+//!
+//! Requests are separated by `###` lines. Each request has a request line
+//! (`METHOD url [HTTP/1.1]`), header lines, a blank line and an optional body.
+//! File-level `@name = value` declarations become scenario variables; `{{name}}`
+//! references already match our variable syntax and are kept as they are.
+//! On export, steps that have no `.http` equivalent (delay, script, ...) become comments.
+
+use regex::Regex;
+use std::collections::HashMap;
+use super::types::*;
+
+const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// A single request parsed from a `.http` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpFileRequest {
+    pub name: Option<String>,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// File-level variable declarations and requests of a `.http` file
+#[derive(Debug, Clone, Default)]
+pub struct HttpFile {
+    pub variables: Vec<(String, String)>,
+    pub requests: Vec<HttpFileRequest>,
+}
+
+enum Section {
+    Preamble,
+    Headers,
+    Body,
+}
+
+/// Parse `.http` content into its variables and requests
+pub fn parse_http_file(content: &str) -> Result<HttpFile, String> {
+    let variable_re = Regex::new(r"^@([\w.\-]+)\s*=\s*(.*)$").unwrap();
+    let name_re = Regex::new(r"^(?:#|//)\s*@name\s+(.+)$").unwrap();
+
+    let mut file = HttpFile::default();
+    let mut current: Option<HttpFileRequest> = None;
+    let mut block_name: Option<String> = None;
+    let mut body_lines: Vec<&str> = Vec::new();
+    let mut section = Section::Preamble;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+
+        if line.starts_with("###") {
+            if let Some(request) = current.take() {
+                file.requests.push(finish_request(request, &body_lines));
+            }
+            body_lines.clear();
+            section = Section::Preamble;
+            let title = line.trim_start_matches('#').trim();
+            block_name = if title.is_empty() { None } else { Some(title.to_string()) };
+            continue;
+        }
+
+        match section {
+            Section::Preamble => {
+                if line.is_empty() {
+                    continue;
+                }
+                if let Some(caps) = name_re.captures(line) {
+                    block_name = Some(caps[1].trim().to_string());
+                } else if let Some(caps) = variable_re.captures(line) {
+                    file.variables.push((caps[1].to_string(), caps[2].trim().to_string()));
+                } else if !line.starts_with('#') && !line.starts_with("//") {
+                    let (method, url) = parse_request_line(line);
+                    current = Some(HttpFileRequest {
+                        name: block_name.take(),
+                        method,
+                        url,
+                        headers: Vec::new(),
+                        body: None,
+                    });
+                    section = Section::Headers;
+                }
+            }
+            Section::Headers => {
+                let request = match current.as_mut() {
+                    Some(request) => request,
+                    None => continue,
+                };
+                if line.is_empty() {
+                    section = Section::Body;
+                } else if (line.starts_with('?') || line.starts_with('&')) && request.headers.is_empty() {
+                    // Query string continued on the following lines
+                    request.url.push_str(line);
+                } else if line.starts_with('#') || line.starts_with("//") {
+                    continue;
+                } else if let Some((name, value)) = line.split_once(':') {
+                    request.headers.push((name.trim().to_string(), value.trim().to_string()));
+                } else {
+                    return Err(format!("Invalid header on line {}: {}", index + 1, line));
+                }
+            }
+            Section::Body => {
+                // Response handlers and output redirects are not part of the request
+                if line.starts_with("> ") || line.starts_with(">>") || line.starts_with("<> ") {
+                    continue;
+                }
+                body_lines.push(raw_line);
+            }
+        }
+    }
+
+    if let Some(request) = current.take() {
+        file.requests.push(finish_request(request, &body_lines));
+    }
+
+    if file.requests.is_empty() {
+        return Err("No requests found in .http file".to_string());
+    }
+    Ok(file)
+}
+
+/// Split `METHOD url HTTP/1.1` (method and version optional, GET by default)
+fn parse_request_line(line: &str) -> (String, String) {
+    let mut parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() > 1 && parts[parts.len() - 1].starts_with("HTTP/") {
+        parts.pop();
+    }
+    match parts.first() {
+        Some(first) if parts.len() > 1 && HTTP_METHODS.contains(&first.to_uppercase().as_str()) => {
+            (first.to_uppercase(), parts[1..].join(" "))
+        }
+        _ => ("GET".to_string(), parts.join(" ")),
+    }
+}
+
+fn finish_request(mut request: HttpFileRequest, body_lines: &[&str]) -> HttpFileRequest {
+    let body = body_lines.join("\n");
+    let body = body.trim();
+    if !body.is_empty() {
+        request.body = Some(body.to_string());
+    }
+    request
+}
+
+/// Map REST Client system variables to our dynamic tokens; other `{{...}}` are left as is
+fn convert_variables(input: &str) -> String {
+    let re = Regex::new(r"\{\{\s*\$([\w.]+)[^}]*\}\}").unwrap();
+    re.replace_all(input, |caps: &regex::Captures| {
+        match &caps[1] {
+            "guid" | "uuid" | "random.uuid" => "{{$randomUuid}}".to_string(),
+            "randomInt" | "random.integer" => "{{$randomInt}}".to_string(),
+            "random.email" => "{{$randomEmail}}".to_string(),
+            _ => caps[0].to_string(),
+        }
+    })
+    .into_owned()
+}
+
+/// Build a scenario with one request step per `.http` request
+pub fn http_file_to_scenario(
+    file: &HttpFile,
+    project_id: &str,
+    name: &str,
+) -> (TestScenario, Vec<TestScenarioStep>) {
+    let now = chrono::Utc::now().timestamp();
+    let variables: serde_json::Map<String, serde_json::Value> = file
+        .variables
+        .iter()
+        .map(|(k, v)| (k.clone(), serde_json::Value::String(convert_variables(v))))
+        .collect();
+
+    let scenario = TestScenario {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id: project_id.to_string(),
+        name: name.to_string(),
+        description: Some(format!("Imported from .http file ({} requests)", file.requests.len())),
+        priority: "medium".to_string(),
+        variables: serde_json::Value::Object(variables),
+        pre_script: None,
+        post_script: None,
+        chaos: None,
+        session_login: None,
+        environments: Default::default(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    let steps = file
+        .requests
+        .iter()
+        .enumerate()
+        .map(|(i, request)| TestScenarioStep {
+            id: uuid::Uuid::new_v4().to_string(),
+            scenario_id: scenario.id.clone(),
+            step_order: i as i32,
+            step_type: TestStepType::Request,
+            name: request
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{} {}", request.method, request.url)),
+            config: request_to_config(request),
+            enabled: true,
+            enabled_if: None,
+            group: None,
+        })
+        .collect();
+
+    (scenario, steps)
+}
+
+fn request_to_config(request: &HttpFileRequest) -> serde_json::Value {
+    let headers: HashMap<String, String> = request
+        .headers
+        .iter()
+        .map(|(k, v)| (k.clone(), convert_variables(v)))
+        .collect();
+
+    // `< ./file.json` loads the body from a file
+    let (body, body_file) = match request.body.as_deref() {
+        Some(body) if body.starts_with("< ") && !body.contains('\n') => {
+            (None, Some(body[2..].trim().to_string()))
+        }
+        Some(body) => {
+            let body = convert_variables(body);
+            let value = serde_json::from_str(&body).unwrap_or(serde_json::Value::String(body));
+            (Some(value), None)
+        }
+        None => (None, None),
+    };
+
+    let config = RequestStepConfig {
+        endpoint_id: None,
+        url: convert_variables(&request.url),
+        method: request.method.clone(),
+        headers: if headers.is_empty() { None } else { Some(headers) },
+        params: None,
+        query: None,
+        service: None,
+        body,
+        body_file,
+        extract_variables: None,
+        assertions: None,
+        with_items_from_csv: None,
+        follow_redirects: None,
+        max_redirects: None,
+    };
+    serde_json::to_value(&config).unwrap_or(serde_json::json!({}))
+}
+
+/// Render a scenario as `.http` content: scenario variables as `@var` lines, one entry per step
+pub fn scenario_to_http_file(scenario: &TestScenario, steps: &[TestScenarioStep]) -> String {
+    let mut out = format!("# {}\n", scenario.name);
+    if let Some(description) = scenario.description.as_deref().filter(|d| !d.is_empty()) {
+        for line in description.lines() {
+            out.push_str(&format!("# {}\n", line));
+        }
+    }
+
+    if let Some(vars) = scenario.variables.as_object() {
+        if !vars.is_empty() {
+            out.push('\n');
+        }
+        for (name, value) in vars {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            out.push_str(&format!("@{} = {}\n", name, value));
+        }
+    }
+
+    let mut ordered: Vec<&TestScenarioStep> = steps.iter().collect();
+    ordered.sort_by_key(|s| s.step_order);
+
+    for step in ordered {
+        out.push_str(&format!("\n### {}\n", step.name));
+        if !step.enabled {
+            out.push_str("# Disabled in the scenario\n");
+        }
+        match step.step_type {
+            TestStepType::Request => match serde_json::from_value::<RequestStepConfig>(step.config.clone()) {
+                Ok(config) => out.push_str(&render_request(&config)),
+                Err(e) => out.push_str(&format!("# Invalid request config: {}\n", e)),
+            },
+            TestStepType::Delay => {
+                let duration = serde_json::from_value::<DelayStepConfig>(step.config.clone())
+                    .map(|c| c.duration_ms)
+                    .unwrap_or(0);
+                out.push_str(&format!("# Delay step: wait {} ms\n", duration));
+            }
+            TestStepType::Script => {
+                out.push_str("# Script step:\n");
+                if let Ok(config) = serde_json::from_value::<ScriptStepConfig>(step.config.clone()) {
+                    for line in config.code.lines() {
+                        out.push_str(&format!("#   {}\n", line));
+                    }
+                }
+            }
+            _ => {
+                out.push_str(&format!("# {} step (not supported in .http files)\n", step.step_type.as_str()));
+            }
+        }
+    }
+
+    out
+}
+
+fn render_request(config: &RequestStepConfig) -> String {
+    let method = config.method.to_uppercase();
+    let mut out = format!("{} {}\n", method, config.url);
+
+    // Query parameters go on continuation lines, values kept unencoded like the url
+    let mut query: Vec<(&String, &String)> = config.query.iter().flatten().collect();
+    query.sort();
+    for (i, (name, value)) in query.iter().enumerate() {
+        let separator = if i == 0 && !config.url.contains('?') { '?' } else { '&' };
+        out.push_str(&format!("    {}{}={}\n", separator, name, value));
+    }
+
+    let mut headers: Vec<(&String, &String)> = config.headers.iter().flatten().collect();
+    headers.sort();
+    for (name, value) in &headers {
+        out.push_str(&format!("{}: {}\n", name, value));
+    }
+
+    // Same precedence as the executor: GET sends no body, body file, body, then params
+    if method == "GET" {
+        return out;
+    }
+    let body = match (&config.body_file, config.body.as_ref().or(config.params.as_ref())) {
+        (Some(path), _) => Some(format!("< {}", path)),
+        (None, Some(body)) => Some(serde_json::to_string_pretty(body).unwrap_or_else(|_| body.to_string())),
+        (None, None) => None,
+    };
+    if let Some(body) = body {
+        // Bodies are always sent as JSON
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")) {
+            out.push_str("Content-Type: application/json\n");
+        }
+        out.push('\n');
+        out.push_str(&body);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+@baseUrl = http://localhost:3000
+@token = abc
+
+### Login
+POST {{baseUrl}}/auth/login HTTP/1.1
+Content-Type: application/json
+
+{
+  "username": "ann",
+  "password": "{{password}}"
+}
+
+###
+# @name listUsers
+GET {{baseUrl}}/users
+    ?page=1
+    &size=20
+Authorization: Bearer {{token}}
+X-Request-Id: {{$guid}}
+
+> {% client.global.set("count", response.body.length); %}
+
+###
+{{baseUrl}}/health
+"#;
+
+    #[test]
+    fn test_parse_http_file() {
+        let file = parse_http_file(SAMPLE).unwrap();
+        assert_eq!(file.variables, vec![
+            ("baseUrl".to_string(), "http://localhost:3000".to_string()),
+            ("token".to_string(), "abc".to_string()),
+        ]);
+        assert_eq!(file.requests.len(), 3);
+
+        let login = &file.requests[0];
+        assert_eq!(login.name.as_deref(), Some("Login"));
+        assert_eq!(login.method, "POST");
+        assert_eq!(login.url, "{{baseUrl}}/auth/login");
+        assert_eq!(login.headers, vec![("Content-Type".to_string(), "application/json".to_string())]);
+
+        let list = &file.requests[1];
+        assert_eq!(list.name.as_deref(), Some("listUsers"));
+        assert_eq!(list.url, "{{baseUrl}}/users?page=1&size=20");
+        assert_eq!(list.headers.len(), 2);
+        assert!(list.body.is_none());
+
+        assert_eq!(file.requests[2].method, "GET");
+        assert_eq!(file.requests[2].name, None);
+
+        assert!(parse_http_file("# only a comment\n@a = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_http_file_to_scenario() {
+        let file = parse_http_file(SAMPLE).unwrap();
+        let (scenario, steps) = http_file_to_scenario(&file, "project-1", "Imported");
+        assert_eq!(scenario.variables["baseUrl"], "http://localhost:3000");
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[2].name, "GET {{baseUrl}}/health");
+
+        let login: RequestStepConfig = serde_json::from_value(steps[0].config.clone()).unwrap();
+        assert_eq!(login.body.unwrap()["password"], "{{password}}");
+
+        let list: RequestStepConfig = serde_json::from_value(steps[1].config.clone()).unwrap();
+        assert_eq!(list.headers.unwrap()["X-Request-Id"], "{{$randomUuid}}");
+
+        let file = parse_http_file("POST /upload\nContent-Type: application/json\n\n< ./payload.json\n").unwrap();
+        let (_, steps) = http_file_to_scenario(&file, "project-1", "Imported");
+        let upload: RequestStepConfig = serde_json::from_value(steps[0].config.clone()).unwrap();
+        assert_eq!(upload.body_file.as_deref(), Some("./payload.json"));
+        assert!(upload.body.is_none());
+    }
+
+    #[test]
+    fn test_export_roundtrip() {
+        let file = parse_http_file(SAMPLE).unwrap();
+        let (scenario, mut steps) = http_file_to_scenario(&file, "project-1", "Imported");
+        steps.push(TestScenarioStep {
+            id: "delay".to_string(),
+            scenario_id: scenario.id.clone(),
+            step_order: 1,
+            step_type: TestStepType::Delay,
+            name: "Wait".to_string(),
+            config: serde_json::json!({ "durationMs": 500 }),
+            enabled: true,
+            enabled_if: None,
+            group: None,
+        });
+        steps.sort_by_key(|s| s.step_order);
+
+        let content = scenario_to_http_file(&scenario, &steps);
+        assert!(content.contains("@baseUrl = http://localhost:3000\n"));
+        assert!(content.contains("### Wait\n# Delay step: wait 500 ms\n"));
+        assert!(content.contains("POST {{baseUrl}}/auth/login\nContent-Type: application/json\n\n{"));
+
+        let reparsed = parse_http_file(&content).unwrap();
+        assert_eq!(reparsed.variables, file.variables);
+        assert_eq!(reparsed.requests.len(), 3);
+        assert_eq!(reparsed.requests[0].name.as_deref(), Some("Login"));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(reparsed.requests[0].body.as_deref().unwrap()).unwrap(),
+            serde_json::json!({ "username": "ann", "password": "{{password}}" })
+        );
+        assert_eq!(reparsed.requests[1].url, "{{baseUrl}}/users?page=1&size=20");
+    }
+}
@@ -0,0 +1,370 @@
+//! Semantic checks on a stored scenario before it is run
+//!
+//! YAML validation only proves a scenario parses; these checks catch authoring mistakes
+//! that would otherwise surface as a failed run: undefined `{{variables}}`, extracted
+//! variables nobody reads, unknown assertion operators/sources, condition and loop steps
+//! pointing at missing steps, and request steps without a URL.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::types::{
+    Assertion, AssertionTemplate, ConditionStepConfig, GrpcStepConfig, LoopStepConfig, RequestStepConfig,
+    TestScenario, TestScenarioStep, TestStepType, VariableExtractor, validate_step_references,
+};
+
+pub const ASSERTION_OPERATORS: &[&str] = &[
+    "equals", "notEquals", "contains", "matches", "greaterThan", "lessThan", "between", "exists", "type", "length", "snapshot",
+];
+pub const ASSERTION_SOURCES: &[&str] = &["status", "body", "header", "duration", "finalUrl", "contentType", "serverTiming", "rawBody", "text", "variable"];
+pub const ASSERTION_LOGIC: &[&str] = &["and", "or"];
+pub const DYNAMIC_TOKENS: &[&str] = &["randomInt", "randomString", "randomUuid", "randomEmail", "randomBool"];
+
+/// Variables the executor defines itself
+const BUILTIN_VARIABLES: &[&str] = &["baseUrl"];
+/// Variables set only while a step runs over CSV rows
+const CSV_VARIABLES: &[&str] = &["item", "index"];
+
+/// One problem found by `lint_scenario`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LintIssue {
+    pub severity: String, // "error" or "warning"
+    pub message: String,
+    #[serde(rename = "stepId")]
+    pub step_id: Option<String>,
+    #[serde(rename = "stepName")]
+    pub step_name: Option<String>,
+}
+
+impl LintIssue {
+    fn error(step: Option<&TestScenarioStep>, message: String) -> Self {
+        Self::new("error", step, message)
+    }
+
+    fn warning(step: Option<&TestScenarioStep>, message: String) -> Self {
+        Self::new("warning", step, message)
+    }
+
+    fn new(severity: &str, step: Option<&TestScenarioStep>, message: String) -> Self {
+        Self {
+            severity: severity.to_string(),
+            message,
+            step_id: step.map(|s| s.id.clone()),
+            step_name: step.map(|s| s.name.clone()),
+        }
+    }
+}
+
+/// Lint a scenario's enabled steps, in step order
+pub fn lint_scenario(
+    scenario: &TestScenario,
+    steps: &[TestScenarioStep],
+    project_variables: &HashMap<String, serde_json::Value>,
+    templates: &[AssertionTemplate],
+) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    if let Err(e) = validate_step_references(steps) {
+        issues.push(LintIssue::error(None, e));
+    }
+    let mut steps: Vec<&TestScenarioStep> = steps.iter().filter(|s| s.enabled).collect();
+    steps.sort_by_key(|s| s.step_order);
+
+    // Every name a `{{variable}}` can resolve to
+    let mut defined: HashSet<String> = BUILTIN_VARIABLES.iter().map(|v| v.to_string()).collect();
+    defined.extend(project_variables.keys().cloned());
+    if let Some(vars) = scenario.variables.as_object() {
+        defined.extend(vars.keys().cloned());
+    }
+    let mut extracted: Vec<(&TestScenarioStep, VariableExtractor)> = Vec::new();
+    for &step in &steps {
+        for extractor in step_extractors(step) {
+            defined.insert(extractor.name.clone());
+            extracted.push((step, extractor));
+        }
+        if let Ok(config) = serde_json::from_value::<LoopStepConfig>(step.config.clone()) {
+            defined.extend(config.iterator_variable);
+        }
+    }
+
+    let step_refs: HashSet<&str> = steps.iter().flat_map(|s| [s.id.as_str(), s.name.as_str()]).collect();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut free_text: Vec<String> = Vec::new(); // Conditions and scripts may read variables without {{ }}
+
+    for &step in &steps {
+        let mut texts = Vec::new();
+        collect_strings(&step.config, &mut texts);
+        texts.extend(step.enabled_if.iter().cloned());
+        let uses_csv = step.config.get("withItemsFromCsv").is_some_and(|c| !c.is_null());
+
+        for text in &texts {
+            for reference in variable_references(text) {
+                let root = reference.split('.').next().unwrap_or_default().to_string();
+                let is_defined = defined.contains(&root) || (uses_csv && CSV_VARIABLES.contains(&root.as_str()));
+                if !is_defined {
+                    issues.push(LintIssue::error(Some(step), format!("Undefined variable '{{{{{}}}}}'", reference)));
+                }
+                used.insert(root);
+            }
+            for token in dynamic_tokens(text) {
+                if !DYNAMIC_TOKENS.contains(&token.as_str()) {
+                    issues.push(LintIssue::warning(Some(step), format!("Unknown dynamic token '{{{{${}}}}}'", token)));
+                }
+            }
+        }
+
+        match step.step_type {
+            TestStepType::Request => match serde_json::from_value::<RequestStepConfig>(step.config.clone()) {
+                Ok(config) => {
+                    if config.url.trim().is_empty() {
+                        issues.push(LintIssue::error(Some(step), "Request step has an empty URL".to_string()));
+                    }
+                    lint_assertions(step, config.assertions.as_deref(), templates, &mut issues);
+                }
+                Err(e) => issues.push(LintIssue::error(Some(step), format!("Invalid request config: {}", e))),
+            },
+            TestStepType::Grpc => {
+                if let Ok(config) = serde_json::from_value::<GrpcStepConfig>(step.config.clone()) {
+                    lint_assertions(step, config.assertions.as_deref(), templates, &mut issues);
+                }
+            }
+            TestStepType::Condition => match serde_json::from_value::<ConditionStepConfig>(step.config.clone()) {
+                Ok(config) => {
+                    free_text.push(config.condition.clone());
+                    for target in config.true_steps.iter().chain(config.false_steps.iter()) {
+                        if !step_refs.contains(target.as_str()) {
+                            issues.push(LintIssue::error(Some(step), format!("Condition references missing step '{}'", target)));
+                        }
+                    }
+                }
+                Err(e) => issues.push(LintIssue::error(Some(step), format!("Invalid condition config: {}", e))),
+            },
+            TestStepType::Loop => match serde_json::from_value::<LoopStepConfig>(step.config.clone()) {
+                Ok(config) => {
+                    free_text.extend(config.data_source);
+                    for target in &config.steps {
+                        if !step_refs.contains(target.as_str()) {
+                            issues.push(LintIssue::error(Some(step), format!("Loop references missing step '{}'", target)));
+                        }
+                    }
+                }
+                Err(e) => issues.push(LintIssue::error(Some(step), format!("Invalid loop config: {}", e))),
+            },
+            TestStepType::Script => {
+                if let Some(code) = step.config.get("code").and_then(|c| c.as_str()) {
+                    free_text.push(code.to_string());
+                }
+            }
+            TestStepType::Delay => {}
+        }
+    }
+    free_text.extend(scenario.pre_script.iter().chain(scenario.post_script.iter()).cloned());
+
+    for (step, extractor) in &extracted {
+        if !used.contains(&extractor.name) && !free_text.iter().any(|text| contains_word(text, &extractor.name)) {
+            issues.push(LintIssue::warning(
+                Some(*step),
+                format!("Extracted variable '{}' is never used", extractor.name),
+            ));
+        }
+    }
+
+    issues
+}
+
+fn lint_assertions(
+    step: &TestScenarioStep,
+    assertions: Option<&[Assertion]>,
+    templates: &[AssertionTemplate],
+    issues: &mut Vec<LintIssue>,
+) {
+    for assertion in assertions.unwrap_or_default() {
+        let label = if assertion.name.is_empty() { &assertion.operator } else { &assertion.name };
+        if let Some(template) = &assertion.template {
+            if !templates.iter().any(|t| &t.name == template) {
+                issues.push(LintIssue::error(Some(step), format!("Unknown assertion template '{}'", template)));
+            }
+            continue;
+        }
+        if let Some(logic) = &assertion.logic {
+            let label = if assertion.name.is_empty() { logic } else { &assertion.name };
+            if !ASSERTION_LOGIC.contains(&logic.to_lowercase().as_str()) {
+                issues.push(LintIssue::error(
+                    Some(step),
+                    format!("Assertion group '{}' has unknown logic '{}'", label, logic),
+                ));
+            }
+            match assertion.assertions.as_deref() {
+                Some(members) if !members.is_empty() => lint_assertions(step, Some(members), templates, issues),
+                _ => issues.push(LintIssue::error(Some(step), format!("Assertion group '{}' has no assertions", label))),
+            }
+            continue;
+        }
+        if !ASSERTION_OPERATORS.contains(&assertion.operator.as_str()) {
+            issues.push(LintIssue::error(
+                Some(step),
+                format!("Assertion '{}' has unknown operator '{}'", label, assertion.operator),
+            ));
+        }
+        if !ASSERTION_SOURCES.contains(&assertion.source.as_str()) {
+            issues.push(LintIssue::error(
+                Some(step),
+                format!("Assertion '{}' has unknown source '{}'", label, assertion.source),
+            ));
+        }
+    }
+}
+
+fn step_extractors(step: &TestScenarioStep) -> Vec<VariableExtractor> {
+    let extractors = match step.step_type {
+        TestStepType::Request => serde_json::from_value::<RequestStepConfig>(step.config.clone())
+            .ok()
+            .and_then(|c| c.extract_variables),
+        TestStepType::Grpc => serde_json::from_value::<GrpcStepConfig>(step.config.clone())
+            .ok()
+            .and_then(|c| c.extract_variables),
+        _ => None,
+    };
+    extractors.unwrap_or_default()
+}
+
+/// Every string in a JSON value, including object keys
+fn collect_strings(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_strings(v, out)),
+        serde_json::Value::Object(map) => {
+            for (key, v) in map {
+                out.push(key.clone());
+                collect_strings(v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `{{ name }}` and `{{ item.column }}` references, in the executor's syntax
+fn variable_references(text: &str) -> Vec<String> {
+    let re = Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap();
+    re.captures_iter(text).map(|cap| cap[1].to_string()).collect()
+}
+
+/// `{{$token}}` dynamic values
+fn dynamic_tokens(text: &str) -> Vec<String> {
+    let re = Regex::new(r"\{\{\s*\$(\w+)\s*\}\}").unwrap();
+    re.captures_iter(text).map(|cap| cap[1].to_string()).collect()
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scenario() -> TestScenario {
+        TestScenario {
+            id: "s1".to_string(),
+            project_id: "p1".to_string(),
+            name: "Checkout".to_string(),
+            description: None,
+            priority: "medium".to_string(),
+            variables: json!({ "email": "a@example.com" }),
+            pre_script: None,
+            post_script: None,
+            chaos: None,
+            session_login: None,
+            environments: Default::default(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn step(id: &str, order: i32, step_type: TestStepType, config: serde_json::Value) -> TestScenarioStep {
+        TestScenarioStep {
+            id: id.to_string(),
+            scenario_id: "s1".to_string(),
+            step_order: order,
+            step_type,
+            name: format!("Step {}", id),
+            config,
+            enabled: true,
+            enabled_if: None,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn test_lint_scenario_reports_authoring_mistakes() {
+        let steps = vec![
+            step("login", 0, TestStepType::Request, json!({
+                "url": "{{baseUrl}}/login",
+                "method": "POST",
+                "body": { "email": "{{email}}", "tenant": "{{tenantId}}", "nonce": "{{$randomUuid}}" },
+                "extractVariables": [
+                    { "name": "token", "source": "body", "path": "token" },
+                    { "name": "unused", "source": "body", "path": "id" }
+                ],
+                "assertions": [
+                    { "name": "ok", "source": "status", "operator": "equals", "expected": 200 },
+                    { "name": "odd", "source": "cookies", "operator": "approximately", "expected": 1 }
+                ]
+            })),
+            step("orders", 1, TestStepType::Request, json!({
+                "url": "  ",
+                "method": "GET",
+                "headers": { "Authorization": "Bearer {{ token }}" },
+                "extractVariables": [{ "name": "orderId", "source": "body", "path": "id" }]
+            })),
+            step("check", 2, TestStepType::Condition, json!({
+                "condition": "orderId != null",
+                "trueSteps": ["Step orders"],
+                "falseSteps": ["missing-step"]
+            })),
+        ];
+
+        let issues = lint_scenario(&scenario(), &steps, &HashMap::new(), &[]);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+
+        assert_eq!(messages, vec![
+            "Undefined variable '{{tenantId}}'",
+            "Assertion 'odd' has unknown operator 'approximately'",
+            "Assertion 'odd' has unknown source 'cookies'",
+            "Request step has an empty URL",
+            "Condition references missing step 'missing-step'",
+            "Extracted variable 'unused' is never used",
+        ]);
+        assert_eq!(issues[0].step_id.as_deref(), Some("login"));
+        assert_eq!(issues[5].severity, "warning");
+    }
+
+    #[test]
+    fn test_lint_assertion_groups() {
+        let steps = vec![step("login", 0, TestStepType::Request, json!({
+            "url": "{{baseUrl}}/login",
+            "method": "POST",
+            "assertions": [
+                { "name": "role", "logic": "or", "assertions": [
+                    { "source": "body", "path": "role", "operator": "equals", "expected": "admin" },
+                    { "name": "nested", "source": "body", "path": "role", "operator": "is", "expected": "owner" }
+                ] },
+                { "name": "either", "logic": "xor", "assertions": [] }
+            ]
+        }))];
+
+        let issues = lint_scenario(&scenario(), &steps, &HashMap::new(), &[]);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, vec![
+            "Assertion 'nested' has unknown operator 'is'",
+            "Assertion group 'either' has unknown logic 'xor'",
+            "Assertion group 'either' has no assertions",
+        ]);
+    }
+}
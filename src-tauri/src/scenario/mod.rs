@@ -1,6 +1,19 @@
 pub mod types;
+pub mod events;
 pub mod executor;
+pub mod grpc;
+pub mod body_template;
 pub mod yaml;
+pub mod http_file;
+pub mod har;
 pub mod csv_reader;
+pub mod variable_import;
+pub mod step_export;
+pub mod run_export;
+pub mod run_log;
+pub mod rng;
+pub mod schedule;
+pub mod lint;
+pub mod server_timing;
 pub mod performance;
 
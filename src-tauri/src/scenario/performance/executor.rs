@@ -1,26 +1,63 @@
 use super::types::*;
-use super::metrics::MetricsCollector;
+use super::metrics::{ConnectionTracker, MetricsCollector, VuMetricsBuffer};
 use super::stages::StageScheduler;
+use crate::http_client::ClientDefaults;
+use crate::scenario::body_template;
+use crate::scenario::server_timing::parse_server_timing;
 use crate::scenario::types::{
     TestScenario, TestScenarioStep, TestStepType, RequestStepConfig,
-    VariableExtractor,
+    ScriptStepConfig, VariableExtractor,
 };
 use reqwest::Client;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 use tokio::time::interval;
 use regex::Regex;
 
+/// Upper bound for the default in-flight limit; higher VU counts queue instead of opening more sockets
+pub const DEFAULT_MAX_IN_FLIGHT: u32 = 256;
+
+/// One scenario a VU can pick for an iteration, with its enabled steps and starting variables
+#[derive(Clone)]
+struct MixEntry {
+    scenario_id: String,
+    scenario_name: String,
+    steps: Vec<TestScenarioStep>,
+    variables: HashMap<String, serde_json::Value>,
+    weight: u32,
+}
+
+/// Shared state for one run, handed to the progress reporter, the VU manager and every VU
+#[derive(Clone)]
+struct VuContext {
+    app_handle: Option<AppHandle>,
+    run_id: String,
+    client: Client,
+    connection_tracker: Arc<ConnectionTracker>,
+    request_slots: Arc<Semaphore>,
+    scenarios: Arc<Vec<MixEntry>>,
+    base_url: Option<String>,
+    metrics_collector: Arc<Mutex<MetricsCollector>>,
+    scheduler: Arc<StageScheduler>,
+    stop_signal: Arc<AtomicBool>,
+    current_vus: Arc<AtomicU32>,
+    max_vus_reached: Arc<AtomicU32>,
+    iteration_counter: Arc<AtomicU64>,
+    iterations_limit: Option<u64>,
+}
+
 /// PerformanceExecutor - Runs performance tests with multiple VUs
 pub struct PerformanceExecutor {
     scenario: TestScenario,
     steps: Vec<TestScenarioStep>,
     config: PerformanceTestConfig,
     base_url: Option<String>,
+    client_defaults: ClientDefaults,
+    scenario_mix: Vec<(TestScenario, Vec<TestScenarioStep>, u32)>,
 }
 
 impl PerformanceExecutor {
@@ -35,9 +72,23 @@ impl PerformanceExecutor {
             steps,
             config,
             base_url,
+            client_defaults: ClientDefaults::default(),
+            scenario_mix: Vec::new(),
         }
     }
 
+    /// Weighted scenarios picked per iteration instead of the single scenario
+    pub fn with_scenario_mix(mut self, scenario_mix: Vec<(TestScenario, Vec<TestScenarioStep>, u32)>) -> Self {
+        self.scenario_mix = scenario_mix;
+        self
+    }
+
+    /// Project User-Agent and always-sent headers; per-step headers still override them
+    pub fn with_client_defaults(mut self, client_defaults: ClientDefaults) -> Self {
+        self.client_defaults = client_defaults;
+        self
+    }
+
     /// Run the performance test
     pub async fn run(&self, app_handle: Option<AppHandle>) -> PerformanceTestRun {
         let run_id = uuid::Uuid::new_v4().to_string();
@@ -64,59 +115,90 @@ impl PerformanceExecutor {
         }
 
         // Create shared state
-        let metrics_collector = Arc::new(Mutex::new(MetricsCollector::new()));
+        let warmup_secs = self.config.warmup_secs.unwrap_or(0);
+        let collector = MetricsCollector::new()
+            .with_warmup(warmup_secs)
+            .with_raw_samples(self.config.store_raw_samples);
+        let metrics_collector = Arc::new(Mutex::new(collector));
         let stop_signal = Arc::new(AtomicBool::new(false));
         let current_vus = Arc::new(AtomicU32::new(0));
         let max_vus_reached = Arc::new(AtomicU32::new(0));
         let iteration_counter = Arc::new(AtomicU64::new(0));
+        let connection_tracker = Arc::new(ConnectionTracker::new(self.config.http2_prior_knowledge));
+
+        // One pooled client shared by all VUs so connections are kept alive and reused
+        let client = self.build_http_client();
 
         // Create stage scheduler
         let scheduler = self.create_stage_scheduler();
+        if warmup_secs > 0 {
+            log::info!("[PerfExecutor] Warmup phase: first {}s excluded from metrics", warmup_secs);
+            if warmup_secs >= scheduler.get_total_duration_secs() {
+                log::warn!(
+                    "[PerfExecutor] Warmup ({}s) covers the whole run ({}s), no metrics will be collected",
+                    warmup_secs,
+                    scheduler.get_total_duration_secs()
+                );
+            }
+        }
+        let max_in_flight = resolve_max_in_flight(self.config.max_in_flight, scheduler.get_max_vus());
+        let request_slots = Arc::new(Semaphore::new(max_in_flight as usize));
+        log::info!("[PerfExecutor] Max in-flight requests: {}", max_in_flight);
         let scheduler = Arc::new(scheduler);
 
-        // Prepare scenario variables
-        let scenario_vars = self.prepare_scenario_variables();
+        // Prepare the scenarios VUs pick from, each with its variables and enabled steps
+        let mix_entries = self.build_mix_entries();
+        if mix_entries.len() > 1 {
+            log::info!(
+                "[PerfExecutor] Scenario mix: {}",
+                mix_entries
+                    .iter()
+                    .map(|e| format!("{} (weight {})", e.scenario_name, e.weight))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
 
-        // Filter enabled steps
-        let enabled_steps: Vec<TestScenarioStep> = self
-            .steps
-            .iter()
-            .filter(|s| s.enabled)
-            .cloned()
-            .collect();
+        let context = VuContext {
+            app_handle: app_handle.clone(),
+            run_id: run_id.clone(),
+            client,
+            connection_tracker: connection_tracker.clone(),
+            request_slots,
+            scenarios: Arc::new(mix_entries.clone()),
+            base_url: self.base_url.clone(),
+            metrics_collector: metrics_collector.clone(),
+            scheduler: scheduler.clone(),
+            stop_signal: stop_signal.clone(),
+            current_vus: current_vus.clone(),
+            max_vus_reached: max_vus_reached.clone(),
+            iteration_counter: iteration_counter.clone(),
+            iterations_limit: self.config.iterations,
+        };
 
         // Spawn progress reporter task
-        let progress_handle = self.spawn_progress_reporter(
-            app_handle.clone(),
-            run_id.clone(),
-            metrics_collector.clone(),
-            scheduler.clone(),
-            current_vus.clone(),
-            stop_signal.clone(),
-        );
+        let progress_handle = spawn_progress_reporter(context.clone(), warmup_secs);
 
         // Spawn VU manager task
-        let vu_manager_handle = self.spawn_vu_manager(
-            app_handle.clone(),
-            run_id.clone(),
-            enabled_steps.clone(),
-            scenario_vars.clone(),
-            metrics_collector.clone(),
-            scheduler.clone(),
-            stop_signal.clone(),
-            current_vus.clone(),
-            max_vus_reached.clone(),
-            iteration_counter.clone(),
-        );
+        let vu_manager_handle = spawn_vu_manager(context);
 
         // Wait for completion
         let _ = tokio::join!(progress_handle, vu_manager_handle);
 
         // Calculate final metrics
-        let final_metrics = {
+        let (mut final_metrics, time_series, raw_samples) = {
             let collector = metrics_collector.lock().await;
-            collector.calculate_aggregates()
+            (collector.calculate_aggregates(), collector.get_time_series(), collector.raw_samples())
         };
+        let (new_connections, reused_connections) = connection_tracker.estimated_connections();
+        final_metrics.estimated_new_connections = new_connections;
+        final_metrics.estimated_reused_connections = reused_connections;
+        final_metrics.max_in_flight = max_in_flight;
+        for (scenario_id, scenario_metrics) in final_metrics.scenario_metrics.iter_mut() {
+            if let Some(entry) = mix_entries.iter().find(|e| &e.scenario_id == scenario_id) {
+                scenario_metrics.scenario_name = entry.scenario_name.clone();
+            }
+        }
 
         // Evaluate thresholds
         let threshold_results = {
@@ -143,7 +225,7 @@ impl PerformanceExecutor {
             final_metrics.duration_p95
         );
 
-        let run = PerformanceTestRun {
+        let mut run = PerformanceTestRun {
             id: run_id.clone(),
             config_id: self.config.id.clone(),
             scenario_id: self.scenario.id.clone(),
@@ -155,6 +237,8 @@ impl PerformanceExecutor {
             metrics: Some(final_metrics),
             threshold_results,
             error_message: None,
+            time_series,
+            raw_samples: None,
         };
 
         // Emit completed event
@@ -168,9 +252,35 @@ impl PerformanceExecutor {
             );
         }
 
+        // Attached after the event so the samples are not cloned into it
+        run.raw_samples = raw_samples;
         run
     }
 
+    /// Build the shared HTTP client for this run
+    fn build_http_client(&self) -> Client {
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .danger_accept_invalid_certs(true)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .tcp_keepalive(Duration::from_secs(60))
+            .user_agent(self.client_defaults.user_agent())
+            .default_headers(self.client_defaults.header_map())
+            .gzip(true)
+            .brotli(true)
+            .deflate(true);
+
+        if self.config.http2_prior_knowledge {
+            log::info!("[PerfExecutor] Forcing HTTP/2 (prior knowledge)");
+            builder = builder.http2_prior_knowledge();
+        }
+
+        builder.build().unwrap_or_else(|e| {
+            log::error!("[PerfExecutor] Failed to build client: {}, using default", e);
+            Client::new()
+        })
+    }
+
     /// Create stage scheduler based on config
     fn create_stage_scheduler(&self) -> StageScheduler {
         if let Some(ref stages) = self.config.stages {
@@ -185,12 +295,31 @@ impl PerformanceExecutor {
         StageScheduler::fixed(vus, duration)
     }
 
+    /// The configured scenario mix, or the single scenario when no mix is set
+    fn build_mix_entries(&self) -> Vec<MixEntry> {
+        let entry = |scenario: &TestScenario, steps: &[TestScenarioStep], weight: u32| MixEntry {
+            scenario_id: scenario.id.clone(),
+            scenario_name: scenario.name.clone(),
+            steps: steps.iter().filter(|s| s.enabled).cloned().collect(),
+            variables: self.prepare_scenario_variables(scenario),
+            weight,
+        };
+
+        if self.scenario_mix.is_empty() {
+            return vec![entry(&self.scenario, &self.steps, 1)];
+        }
+        self.scenario_mix
+            .iter()
+            .map(|(scenario, steps, weight)| entry(scenario, steps, *weight))
+            .collect()
+    }
+
     /// Prepare scenario variables
-    fn prepare_scenario_variables(&self) -> HashMap<String, serde_json::Value> {
+    fn prepare_scenario_variables(&self, scenario: &TestScenario) -> HashMap<String, serde_json::Value> {
         let mut vars = HashMap::new();
 
         // Add variables from scenario
-        if let Some(obj) = self.scenario.variables.as_object() {
+        if let Some(obj) = scenario.variables.as_object() {
             for (k, v) in obj {
                 vars.insert(k.clone(), v.clone());
             }
@@ -202,191 +331,213 @@ impl PerformanceExecutor {
 
         vars
     }
+}
 
-    /// Spawn progress reporter task
-    fn spawn_progress_reporter(
-        &self,
-        app_handle: Option<AppHandle>,
-        run_id: String,
-        metrics_collector: Arc<Mutex<MetricsCollector>>,
-        scheduler: Arc<StageScheduler>,
-        current_vus: Arc<AtomicU32>,
-        stop_signal: Arc<AtomicBool>,
-    ) -> tokio::task::JoinHandle<()> {
-        tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(1));
-            let mut last_stage_index: Option<usize> = None;
-
-            loop {
-                interval.tick().await;
-
-                if stop_signal.load(Ordering::SeqCst) || scheduler.is_completed() {
-                    break;
+/// Spawn progress reporter task
+fn spawn_progress_reporter(context: VuContext, warmup_secs: u64) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let VuContext {
+            app_handle,
+            run_id,
+            metrics_collector,
+            scheduler,
+            current_vus,
+            stop_signal,
+            ..
+        } = context;
+
+        let mut interval = interval(Duration::from_secs(1));
+        let mut last_stage_index: Option<usize> = None;
+        let mut warming_up = warmup_secs > 0;
+
+        loop {
+            interval.tick().await;
+
+            if stop_signal.load(Ordering::SeqCst) || scheduler.is_completed() {
+                break;
+            }
+
+            // Check for warmup -> measurement transition
+            if warming_up && scheduler.get_elapsed_secs() >= warmup_secs {
+                warming_up = false;
+                log::info!("[PerfExecutor] Warmup finished, starting measurement");
+
+                if let Some(ref app) = app_handle {
+                    let _ = app.emit(
+                        "perf-stage-changed",
+                        PerfStageChangedEvent {
+                            run_id: run_id.clone(),
+                            stage_index: scheduler.get_current_stage_index().unwrap_or(0),
+                            from_vus: scheduler.get_current_vus(),
+                            target_vus: scheduler.get_current_vus(),
+                            duration_secs: scheduler.get_remaining_secs(),
+                            phase: "measurement".to_string(),
+                        },
+                    );
                 }
+            }
 
-                // Check for stage transition
-                if let Some(new_stage_idx) = scheduler.check_stage_transition(last_stage_index) {
-                    last_stage_index = Some(new_stage_idx);
-                    
-                    if let Some(ref app) = app_handle {
-                        if let Some(stage) = scheduler.get_current_stage() {
+            // Check for stage transition; short or zero-length stages passed between
+            // ticks still get their own event so every boundary is reported
+            if let Some(new_stage_idx) = scheduler.check_stage_transition(last_stage_index) {
+                let first_new = last_stage_index.map(|i| i + 1).unwrap_or(0);
+                last_stage_index = Some(new_stage_idx);
+
+                if let Some(ref app) = app_handle {
+                    for index in first_new..=new_stage_idx {
+                        if let Some(stage) = scheduler.get_stage(index) {
                             let _ = app.emit(
                                 "perf-stage-changed",
                                 PerfStageChangedEvent {
                                     run_id: run_id.clone(),
-                                    stage_index: new_stage_idx,
+                                    stage_index: index,
+                                    from_vus: scheduler.stage_start_vus(index),
                                     target_vus: stage.target_vus,
                                     duration_secs: stage.duration_secs,
+                                    phase: if warming_up { "warmup" } else { "measurement" }.to_string(),
                                 },
                             );
                         }
                     }
                 }
+            }
 
-                // Emit progress event
-                if let Some(ref app) = app_handle {
-                    let collector = metrics_collector.lock().await;
-                    let _ = app.emit(
-                        "perf-progress",
-                        PerfProgressEvent {
-                            run_id: run_id.clone(),
-                            elapsed_secs: scheduler.get_elapsed_secs(),
-                            current_vus: current_vus.load(Ordering::SeqCst),
-                            total_requests: collector.get_metrics_count() as u64,
-                            failed_requests: collector.get_failed_count(),
-                            rps: collector.get_current_rps(),
-                            error_rate: collector.get_error_rate(),
-                            p95_duration: collector.get_p95_duration(),
-                            iterations_completed: collector.get_total_iterations(),
-                        },
-                    );
-                }
+            // Keep the active VU count current for time-series buckets
+            {
+                let mut collector = metrics_collector.lock().await;
+                collector.set_active_vus(current_vus.load(Ordering::SeqCst));
             }
 
-            log::debug!("[PerfExecutor] Progress reporter stopped");
-        })
-    }
+            // Emit progress event
+            if let Some(ref app) = app_handle {
+                let collector = metrics_collector.lock().await;
+                let _ = app.emit(
+                    "perf-progress",
+                    PerfProgressEvent {
+                        run_id: run_id.clone(),
+                        elapsed_secs: scheduler.get_elapsed_secs(),
+                        current_vus: current_vus.load(Ordering::SeqCst),
+                        total_requests: collector.get_metrics_count() as u64,
+                        failed_requests: collector.get_failed_count(),
+                        rps: collector.get_current_rps(),
+                        error_rate: collector.get_error_rate(),
+                        p95_duration: collector.get_p95_duration(),
+                        iterations_completed: collector.get_total_iterations(),
+                    },
+                );
+            }
+        }
 
-    /// Spawn VU manager task that manages virtual users
-    fn spawn_vu_manager(
-        &self,
-        app_handle: Option<AppHandle>,
-        run_id: String,
-        steps: Vec<TestScenarioStep>,
-        scenario_vars: HashMap<String, serde_json::Value>,
-        metrics_collector: Arc<Mutex<MetricsCollector>>,
-        scheduler: Arc<StageScheduler>,
-        stop_signal: Arc<AtomicBool>,
-        current_vus: Arc<AtomicU32>,
-        max_vus_reached: Arc<AtomicU32>,
-        iteration_counter: Arc<AtomicU64>,
-    ) -> tokio::task::JoinHandle<()> {
-        let base_url = self.base_url.clone();
-        let iterations_limit = self.config.iterations;
-
-        tokio::spawn(async move {
-            let mut vu_handles: Vec<tokio::task::JoinHandle<()>> = Vec::new();
-            let mut next_vu_id: u32 = 0;
-            let mut check_interval = interval(Duration::from_millis(100));
-
-            loop {
-                check_interval.tick().await;
-
-                // Check if we should stop
-                if stop_signal.load(Ordering::SeqCst) {
-                    break;
-                }
+        log::debug!("[PerfExecutor] Progress reporter stopped");
+    })
+}
+
+/// Spawn VU manager task that manages virtual users
+fn spawn_vu_manager(context: VuContext) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let VuContext {
+            ref scheduler,
+            ref stop_signal,
+            ref current_vus,
+            ref max_vus_reached,
+            ref iteration_counter,
+            iterations_limit,
+            ..
+        } = context;
+
+        let mut vu_handles: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+        let mut next_vu_id: u32 = 0;
+        let mut check_interval = interval(Duration::from_millis(100));
 
-                // Check if scheduler is done
-                if scheduler.is_completed() {
+        loop {
+            check_interval.tick().await;
+
+            // Check if we should stop
+            if stop_signal.load(Ordering::SeqCst) {
+                break;
+            }
+
+            // Check if scheduler is done
+            if scheduler.is_completed() {
+                break;
+            }
+
+            // Check iteration limit
+            if let Some(limit) = iterations_limit {
+                if iteration_counter.load(Ordering::SeqCst) >= limit {
                     break;
                 }
+            }
 
-                // Check iteration limit
-                if let Some(limit) = iterations_limit {
-                    if iteration_counter.load(Ordering::SeqCst) >= limit {
-                        break;
-                    }
-                }
+            // Get target VUs from scheduler
+            let target_vus = scheduler.get_current_vus();
+            let active_vus = current_vus.load(Ordering::SeqCst);
 
-                // Get target VUs from scheduler
-                let target_vus = scheduler.get_current_vus();
-                let active_vus = current_vus.load(Ordering::SeqCst);
+            // Update max VUs
+            if active_vus > max_vus_reached.load(Ordering::SeqCst) {
+                max_vus_reached.store(active_vus, Ordering::SeqCst);
+            }
 
-                // Update max VUs
-                if active_vus > max_vus_reached.load(Ordering::SeqCst) {
-                    max_vus_reached.store(active_vus, Ordering::SeqCst);
-                }
+            // Spawn new VUs if needed
+            if active_vus < target_vus {
+                let vus_to_spawn = target_vus - active_vus;
+                for _ in 0..vus_to_spawn {
+                    let vu_id = next_vu_id;
+                    next_vu_id += 1;
 
-                // Spawn new VUs if needed
-                if active_vus < target_vus {
-                    let vus_to_spawn = target_vus - active_vus;
-                    for _ in 0..vus_to_spawn {
-                        let vu_id = next_vu_id;
-                        next_vu_id += 1;
+                    let handle = spawn_vu(vu_id, context.clone());
 
-                        let handle = spawn_vu(
-                            vu_id,
-                            steps.clone(),
-                            scenario_vars.clone(),
-                            base_url.clone(),
-                            metrics_collector.clone(),
-                            stop_signal.clone(),
-                            current_vus.clone(),
-                            iteration_counter.clone(),
-                            iterations_limit,
-                            app_handle.clone(),
-                            run_id.clone(),
-                        );
-
-                        vu_handles.push(handle);
-                        current_vus.fetch_add(1, Ordering::SeqCst);
-                    }
+                    vu_handles.push(handle);
+                    current_vus.fetch_add(1, Ordering::SeqCst);
                 }
-
-                // Clean up finished VU handles
-                vu_handles.retain(|h| !h.is_finished());
             }
 
-            // Signal all VUs to stop
-            stop_signal.store(true, Ordering::SeqCst);
+            // Clean up finished VU handles
+            vu_handles.retain(|h| !h.is_finished());
+        }
 
-            // Wait for all VUs to finish (with timeout)
-            let shutdown_timeout = Duration::from_secs(10);
-            let _ = tokio::time::timeout(
-                shutdown_timeout,
-                futures::future::join_all(vu_handles),
-            )
-            .await;
+        // Signal all VUs to stop
+        stop_signal.store(true, Ordering::SeqCst);
 
-            log::debug!("[PerfExecutor] VU manager stopped");
-        })
-    }
+        // Wait for all VUs to finish (with timeout)
+        let shutdown_timeout = Duration::from_secs(10);
+        let _ = tokio::time::timeout(
+            shutdown_timeout,
+            futures::future::join_all(vu_handles),
+        )
+        .await;
+
+        log::debug!("[PerfExecutor] VU manager stopped");
+    })
 }
 
 /// Spawn a single VU (Virtual User) task
-fn spawn_vu(
-    vu_id: u32,
-    steps: Vec<TestScenarioStep>,
-    scenario_vars: HashMap<String, serde_json::Value>,
-    base_url: Option<String>,
-    metrics_collector: Arc<Mutex<MetricsCollector>>,
-    stop_signal: Arc<AtomicBool>,
-    current_vus: Arc<AtomicU32>,
-    iteration_counter: Arc<AtomicU64>,
-    iterations_limit: Option<u64>,
-    app_handle: Option<AppHandle>,
-    run_id: String,
-) -> tokio::task::JoinHandle<()> {
+fn spawn_vu(vu_id: u32, context: VuContext) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .danger_accept_invalid_certs(true)
-            .build()
-            .unwrap_or_else(|_| Client::new());
-
-        let mut local_vars = scenario_vars.clone();
+        let VuContext {
+            app_handle,
+            run_id,
+            client,
+            connection_tracker,
+            request_slots,
+            scenarios,
+            base_url,
+            metrics_collector,
+            stop_signal,
+            current_vus,
+            iteration_counter,
+            iterations_limit,
+            ..
+        } = context;
+
+        // Each scenario keeps its own variables across this VU's iterations
+        let mut scenario_vars: Vec<HashMap<String, serde_json::Value>> =
+            scenarios.iter().map(|e| e.variables.clone()).collect();
+        let weights: Vec<u32> = scenarios.iter().map(|e| e.weight).collect();
+        let total_weight: u64 = weights.iter().map(|&w| w as u64).sum();
+        let mut rng = MixRng::new();
         let mut iteration: u64 = 0;
+        let mut buffer = VuMetricsBuffer::new();
 
         log::debug!("[VU-{}] Started", vu_id);
 
@@ -403,33 +554,64 @@ fn spawn_vu(
             }
 
             iteration += 1;
-            log::trace!("[VU-{}] Starting iteration {}", vu_id, iteration);
+            let picked = if scenarios.len() > 1 {
+                pick_weighted(&weights, rng.next_below(total_weight))
+            } else {
+                0
+            };
+            let scenario = &scenarios[picked];
+            let local_vars = &mut scenario_vars[picked];
+            log::trace!("[VU-{}] Starting iteration {} ({})", vu_id, iteration, scenario.scenario_name);
 
-            // Execute all steps in the scenario
-            for step in &steps {
+            // Execute all steps in the picked scenario
+            for step in &scenario.steps {
                 if stop_signal.load(Ordering::SeqCst) {
                     break;
                 }
 
+                if step.step_type == TestStepType::Script {
+                    // Script steps only feed custom metrics during load
+                    if let Ok(script) = serde_json::from_value::<ScriptStepConfig>(step.config.clone()) {
+                        for (name, value) in parse_metric_calls(&script.code, local_vars) {
+                            buffer.push_custom(&name, value);
+                        }
+                    }
+                    continue;
+                }
+
                 if step.step_type != TestStepType::Request {
                     // Only execute request steps for performance testing
                     continue;
                 }
 
-                let metric = execute_request_step(
+                // Wait for an in-flight slot; the wait is reported separately from the request duration
+                let queued_at = Instant::now();
+                let permit = match request_slots.acquire().await {
+                    Ok(permit) => permit,
+                    Err(_) => break,
+                };
+                let queue_wait_ms = queued_at.elapsed().as_millis() as u64;
+
+                connection_tracker.begin();
+                let mut metric = execute_request_step(
                     &client,
                     step,
-                    &mut local_vars,
+                    local_vars,
                     base_url.as_deref(),
                     vu_id,
                     iteration,
                 )
                 .await;
-
-                // Record metric
-                {
+                connection_tracker.end();
+                drop(permit);
+                metric.queue_wait_ms = queue_wait_ms;
+                metric.scenario_id = scenario.scenario_id.clone();
+
+                // Buffer the metric locally; the shared collector is only locked per batch
+                buffer.push(metric.clone());
+                if buffer.should_flush() {
                     let mut collector = metrics_collector.lock().await;
-                    collector.record(metric.clone());
+                    buffer.flush_into(&mut collector);
                 }
 
                 // Emit request completed event
@@ -455,12 +637,54 @@ fn spawn_vu(
             tokio::time::sleep(Duration::from_millis(10)).await;
         }
 
+        if !buffer.is_empty() {
+            let mut collector = metrics_collector.lock().await;
+            buffer.flush_into(&mut collector);
+        }
+
         // Decrement current VUs count
         current_vus.fetch_sub(1, Ordering::SeqCst);
         log::debug!("[VU-{}] Stopped after {} iterations", vu_id, iteration);
     })
 }
 
+/// Small xorshift generator for per-iteration scenario picks; statistical spread is all that's needed
+struct MixRng(u64);
+
+impl MixRng {
+    fn new() -> Self {
+        Self(uuid::Uuid::new_v4().as_u64_pair().0 | 1)
+    }
+
+    /// Uniform-enough value in `0..bound`
+    fn next_below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound.max(1)
+    }
+}
+
+/// Index of the weight bucket `roll` (in `0..sum(weights)`) falls into
+fn pick_weighted(weights: &[u32], roll: u64) -> usize {
+    let mut cumulative: u64 = 0;
+    for (index, &weight) in weights.iter().enumerate() {
+        cumulative += weight as u64;
+        if roll < cumulative {
+            return index;
+        }
+    }
+    weights.len().saturating_sub(1)
+}
+
+/// In-flight request limit: the configured value, or the peak VU count capped at `DEFAULT_MAX_IN_FLIGHT`
+fn resolve_max_in_flight(configured: Option<u32>, max_vus: u32) -> u32 {
+    match configured {
+        Some(limit) if limit > 0 => limit,
+        _ => max_vus.clamp(1, DEFAULT_MAX_IN_FLIGHT),
+    }
+}
+
 /// Execute a single request step and return metrics
 async fn execute_request_step(
     client: &Client,
@@ -488,6 +712,9 @@ async fn execute_request_step(
                 vu_id,
                 iteration,
                 timestamp,
+                queue_wait_ms: 0,
+                server_timing: HashMap::new(),
+                scenario_id: String::new(),
             };
         }
     };
@@ -513,6 +740,15 @@ async fn execute_request_step(
         }
     }
 
+    // Add query parameters
+    if let Some(ref query) = config.query {
+        let resolved: Vec<(&String, String)> = query
+            .iter()
+            .map(|(k, v)| (k, resolve_variables(v, variables)))
+            .collect();
+        req = req.query(&resolved);
+    }
+
     // Add body
     if method != "GET" {
         if let Some(ref body) = config.body {
@@ -530,6 +766,14 @@ async fn execute_request_step(
         Ok(resp) => {
             let status = resp.status().as_u16();
             let success = resp.status().is_success();
+            let server_timing: HashMap<String, f64> = resp
+                .headers()
+                .get_all("server-timing")
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(parse_server_timing)
+                .filter_map(|m| m.dur.map(|dur| (m.name, dur)))
+                .collect();
 
             // Extract variables if needed
             if let Some(ref extractors) = config.extract_variables {
@@ -556,6 +800,9 @@ async fn execute_request_step(
                 vu_id,
                 iteration,
                 timestamp,
+                queue_wait_ms: 0,
+                server_timing,
+                scenario_id: String::new(),
             }
         }
         Err(e) => {
@@ -571,14 +818,60 @@ async fn execute_request_step(
                 vu_id,
                 iteration,
                 timestamp,
+                queue_wait_ms: 0,
+                server_timing: HashMap::new(),
+                scenario_id: String::new(),
             }
         }
     }
 }
 
+/// Collect `metrics.add(name, value)` calls from script code
+///
+/// There is no JS engine in the perf runner: calls are found with a regex over the source
+/// text, so control flow is ignored. A call inside an `if`, a loop body or a comment is
+/// recorded exactly once per iteration regardless. Only literal numbers, bare variable
+/// names and `{{variable}}` templates are accepted as values; a value computed in JS
+/// (`metrics.add("x", a + b)`, a function call, a name built at runtime) does not resolve
+/// to a number and is dropped with only a log warning.
+fn parse_metric_calls(
+    code: &str,
+    variables: &HashMap<String, serde_json::Value>,
+) -> Vec<(String, f64)> {
+    static METRIC_CALL: OnceLock<Regex> = OnceLock::new();
+    let re = METRIC_CALL.get_or_init(|| {
+        Regex::new(r#"metrics\.add\(\s*['"]([\w.\-]+)['"]\s*,\s*([^)]+?)\s*\)"#).unwrap()
+    });
+    let mut samples = Vec::new();
+
+    for cap in re.captures_iter(code) {
+        let name = cap[1].to_string();
+        let raw = cap[2].trim().trim_matches(|c| c == '"' || c == '\'');
+
+        let value = if let Ok(n) = raw.parse::<f64>() {
+            Some(n)
+        } else {
+            let resolved = if raw.contains("{{") {
+                resolve_variables(raw, variables)
+            } else {
+                variables.get(raw).map(value_to_string).unwrap_or_default()
+            };
+            resolved.trim().parse::<f64>().ok()
+        };
+
+        match value {
+            Some(v) => samples.push((name, v)),
+            None => log::warn!("[PerfExecutor] Ignoring non-numeric metric value for {}: {}", name, raw),
+        }
+    }
+
+    samples
+}
+
 /// Resolve variables in a string ({{variable}} syntax)
 fn resolve_variables(input: &str, variables: &HashMap<String, serde_json::Value>) -> String {
-    let re = Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap();
+    static VARIABLE: OnceLock<Regex> = OnceLock::new();
+    let re = VARIABLE.get_or_init(|| Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap());
     let mut result = input.to_string();
 
     for cap in re.captures_iter(input) {
@@ -620,6 +913,9 @@ fn resolve_variables_in_json(
             serde_json::Value::String(resolve_variables(s, variables))
         }
         serde_json::Value::Object(map) => {
+            if let Some(items) = body_template::expand_repeat(map, |s| resolve_variables(s, variables)) {
+                return serde_json::Value::Array(items.iter().map(|item| resolve_variables_in_json(item, variables)).collect());
+            }
             let mut new_map = serde_json::Map::new();
             for (k, v) in map {
                 new_map.insert(k.clone(), resolve_variables_in_json(v, variables));
@@ -702,13 +998,83 @@ fn extract_json_path(value: &serde_json::Value, path: &str) -> Option<serde_json
 }
 
 /// Run a performance test (public function for use in commands)
+///
+/// `scenario_mix` holds each mixed scenario with its steps and weight; leave it empty to
+/// run `scenario` alone.
 pub async fn run_performance_test(
     scenario: TestScenario,
     steps: Vec<TestScenarioStep>,
     config: PerformanceTestConfig,
     base_url: Option<String>,
+    client_defaults: ClientDefaults,
+    scenario_mix: Vec<(TestScenario, Vec<TestScenarioStep>, u32)>,
     app_handle: Option<AppHandle>,
 ) -> PerformanceTestRun {
-    let executor = PerformanceExecutor::new(scenario, steps, config, base_url);
+    let executor = PerformanceExecutor::new(scenario, steps, config, base_url)
+        .with_client_defaults(client_defaults)
+        .with_scenario_mix(scenario_mix);
     executor.run(app_handle).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_max_in_flight() {
+        assert_eq!(resolve_max_in_flight(Some(20), 500), 20);
+        assert_eq!(resolve_max_in_flight(None, 50), 50);
+        assert_eq!(resolve_max_in_flight(None, 5_000), DEFAULT_MAX_IN_FLIGHT);
+        assert_eq!(resolve_max_in_flight(Some(0), 0), 1);
+    }
+
+    #[test]
+    fn test_pick_weighted() {
+        let weights = [70, 20, 10];
+        assert_eq!(pick_weighted(&weights, 0), 0);
+        assert_eq!(pick_weighted(&weights, 69), 0);
+        assert_eq!(pick_weighted(&weights, 70), 1);
+        assert_eq!(pick_weighted(&weights, 89), 1);
+        assert_eq!(pick_weighted(&weights, 90), 2);
+        assert_eq!(pick_weighted(&weights, 99), 2);
+
+        let mut rng = MixRng::new();
+        let mut counts = [0u32; 3];
+        for _ in 0..10_000 {
+            counts[pick_weighted(&weights, rng.next_below(100))] += 1;
+        }
+        assert!(counts[0] > counts[1] && counts[1] > counts[2], "{:?}", counts);
+    }
+
+    #[test]
+    fn test_validate_scenario_mix() {
+        let entry = |id: &str, weight| WeightedScenario { scenario_id: id.to_string(), weight };
+        assert!(validate_scenario_mix(&[]).is_ok());
+        assert!(validate_scenario_mix(&[entry("browse", 70), entry("search", 20), entry("checkout", 10)]).is_ok());
+        assert!(validate_scenario_mix(&[entry("browse", 0)]).is_err());
+        assert!(validate_scenario_mix(&[entry("browse", MAX_SCENARIO_WEIGHT + 1)]).is_err());
+        assert!(validate_scenario_mix(&[entry(" ", 1)]).is_err());
+        assert!(validate_scenario_mix(&[entry("browse", 1), entry("browse", 2)]).is_err());
+    }
+
+    #[test]
+    fn test_parse_metric_calls() {
+        let mut vars = HashMap::new();
+        vars.insert("latency".to_string(), serde_json::json!(120));
+        vars.insert("total".to_string(), serde_json::json!("3.5"));
+
+        let code = r#"
+            metrics.add("orders_placed", 1);
+            metrics.add('checkout_latency', latency);
+            metrics.add("cart_total", "{{total}}");
+            metrics.add("ignored", notDefined);
+        "#;
+
+        let samples = parse_metric_calls(code, &vars);
+        assert_eq!(samples, vec![
+            ("orders_placed".to_string(), 1.0),
+            ("checkout_latency".to_string(), 120.0),
+            ("cart_total".to_string(), 3.5),
+        ]);
+    }
+}
@@ -1,37 +1,519 @@
 use super::types::*;
+use crate::scenario::rng::SeededRng;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use regex::Regex;
 
-/// MetricsCollector - Thread-safe collector for performance metrics
+/// Width of a time-series bucket in seconds
+const BUCKET_SECS: u64 = 1;
+
+/// Metrics a VU buffers before taking the shared collector lock
+pub const FLUSH_BATCH_SIZE: usize = 64;
+
+/// Longest a VU keeps metrics buffered, so live progress stays current
+pub const FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Durations below this are counted exactly by `DurationHistogram`
+const HISTOGRAM_EXACT_MS: u64 = 1024;
+
+/// Sub-buckets per power of two above the exact range (~1.5% relative error)
+const HISTOGRAM_SUB_BUCKETS: u64 = 64;
+
+/// Most raw samples kept for `store_raw_samples`; longer runs keep a uniform sample
+pub const MAX_RAW_SAMPLES: usize = 100_000;
+
+/// Most values kept per custom metric for its percentiles; count, sum, min and max stay exact
+pub const MAX_CUSTOM_SAMPLES: usize = 10_000;
+
+/// Fixed-size duration histogram used for live percentiles
+///
+/// Recording is O(1) and reading a percentile walks a few thousand counters, so the
+/// progress reporter no longer sorts every recorded duration while holding the lock.
+struct DurationHistogram {
+    counts: Vec<u64>,
+    total: u64,
+}
+
+impl DurationHistogram {
+    fn new() -> Self {
+        let exponents = 64 - HISTOGRAM_EXACT_MS.trailing_zeros() as u64;
+        Self {
+            counts: vec![0; (HISTOGRAM_EXACT_MS + exponents * HISTOGRAM_SUB_BUCKETS) as usize],
+            total: 0,
+        }
+    }
+
+    fn index_of(value: u64) -> usize {
+        if value < HISTOGRAM_EXACT_MS {
+            return value as usize;
+        }
+        let exp = 63 - value.leading_zeros() as u64;
+        let shift = exp - HISTOGRAM_SUB_BUCKETS.trailing_zeros() as u64;
+        let sub = (value >> shift) - HISTOGRAM_SUB_BUCKETS;
+        let exact_exp = HISTOGRAM_EXACT_MS.trailing_zeros() as u64;
+        (HISTOGRAM_EXACT_MS + (exp - exact_exp) * HISTOGRAM_SUB_BUCKETS + sub) as usize
+    }
+
+    /// Lowest duration that falls into bucket `index`
+    fn value_of(index: usize) -> u64 {
+        let index = index as u64;
+        if index < HISTOGRAM_EXACT_MS {
+            return index;
+        }
+        let offset = index - HISTOGRAM_EXACT_MS;
+        let exp = HISTOGRAM_EXACT_MS.trailing_zeros() as u64 + offset / HISTOGRAM_SUB_BUCKETS;
+        let shift = exp - HISTOGRAM_SUB_BUCKETS.trailing_zeros() as u64;
+        (HISTOGRAM_SUB_BUCKETS + offset % HISTOGRAM_SUB_BUCKETS) << shift
+    }
+
+    fn record(&mut self, value: u64) {
+        self.counts[Self::index_of(value)] += 1;
+        self.total += 1;
+    }
+
+    /// Same rank rule as `percentile`, exact for durations under `HISTOGRAM_EXACT_MS`
+    fn percentile(&self, p: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let rank = (p / 100.0 * (self.total - 1) as f64).round() as u64;
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen > rank {
+                return Self::value_of(index);
+            }
+        }
+        0
+    }
+}
+
+/// Per-VU metric buffer, drained into the shared collector in batches
+///
+/// VUs push every request here and only lock the collector once `should_flush`
+/// says so, instead of serializing on the collector mutex for every request.
+pub struct VuMetricsBuffer {
+    metrics: Vec<(Instant, RequestMetric)>,
+    custom: Vec<(Instant, String, f64)>,
+    last_flush: Instant,
+}
+
+impl VuMetricsBuffer {
+    pub fn new() -> Self {
+        Self {
+            metrics: Vec::with_capacity(FLUSH_BATCH_SIZE),
+            custom: Vec::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    pub fn push(&mut self, metric: RequestMetric) {
+        self.metrics.push((Instant::now(), metric));
+    }
+
+    pub fn push_custom(&mut self, name: &str, value: f64) {
+        self.custom.push((Instant::now(), name.to_string(), value));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty() && self.custom.is_empty()
+    }
+
+    /// Batch is full or has been held for longer than `FLUSH_INTERVAL`
+    pub fn should_flush(&self) -> bool {
+        self.metrics.len() + self.custom.len() >= FLUSH_BATCH_SIZE
+            || (!self.is_empty() && self.last_flush.elapsed() >= FLUSH_INTERVAL)
+    }
+
+    /// Move everything buffered into the collector, keeping the recording times
+    pub fn flush_into(&mut self, collector: &mut MetricsCollector) {
+        for (at, metric) in self.metrics.drain(..) {
+            collector.record_at(metric, at);
+        }
+        for (at, name, value) in self.custom.drain(..) {
+            collector.add_custom_at(&name, value, at);
+        }
+        self.last_flush = Instant::now();
+    }
+}
+
+impl Default for VuMetricsBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Requests accumulated for the bucket currently being filled
+struct TimeBucket {
+    index: u64,
+    requests: u64,
+    failed: u64,
+    durations: Vec<u64>,
+}
+
+impl TimeBucket {
+    fn new(index: u64) -> Self {
+        Self {
+            index,
+            requests: 0,
+            failed: 0,
+            durations: Vec::new(),
+        }
+    }
+
+    fn to_point(&self, active_vus: u32) -> TimeSeriesPoint {
+        let mut durations = self.durations.clone();
+        durations.sort();
+        TimeSeriesPoint {
+            t: self.index * BUCKET_SECS,
+            rps: self.requests as f64 / BUCKET_SECS as f64,
+            p95: percentile(&durations, 95.0),
+            error_rate: if self.requests > 0 {
+                self.failed as f64 / self.requests as f64
+            } else {
+                0.0
+            },
+            active_vus,
+        }
+    }
+}
+
+/// Running totals for one group of requests (the whole run, a step or a scenario)
+struct RequestStats {
+    requests: u64,
+    failed: u64,
+    duration_sum: u64,
+    duration_min: u64,
+    duration_max: u64,
+    durations: DurationHistogram,
+}
+
+impl RequestStats {
+    fn new() -> Self {
+        Self {
+            requests: 0,
+            failed: 0,
+            duration_sum: 0,
+            duration_min: u64::MAX,
+            duration_max: 0,
+            durations: DurationHistogram::new(),
+        }
+    }
+
+    fn record(&mut self, metric: &RequestMetric) {
+        self.requests += 1;
+        if !metric.success {
+            self.failed += 1;
+        }
+        self.duration_sum += metric.duration_ms;
+        self.duration_min = self.duration_min.min(metric.duration_ms);
+        self.duration_max = self.duration_max.max(metric.duration_ms);
+        self.durations.record(metric.duration_ms);
+    }
+
+    fn error_rate(&self) -> f64 {
+        if self.requests > 0 {
+            self.failed as f64 / self.requests as f64
+        } else {
+            0.0
+        }
+    }
+
+    fn duration_min(&self) -> u64 {
+        if self.requests > 0 {
+            self.duration_min
+        } else {
+            0
+        }
+    }
+
+    fn duration_avg(&self) -> f64 {
+        if self.requests > 0 {
+            self.duration_sum as f64 / self.requests as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Totals for one step, plus the Server-Timing entries its responses reported
+struct StepStats {
+    step_name: String,
+    stats: RequestStats,
+    server_timing: HashMap<String, (f64, u64)>, // name -> (sum, responses reporting it)
+}
+
+/// Totals for one scenario of a mix
+struct ScenarioStats {
+    stats: RequestStats,
+    iterations: u64,
+    last_iteration: HashMap<u32, u64>, // vu_id -> last iteration seen running this scenario
+}
+
+/// Uniform sample of at most `capacity` values (reservoir sampling)
+struct SampleReservoir<T> {
+    samples: Vec<T>,
+    capacity: usize,
+    seen: u64,
+    rng: SeededRng,
+}
+
+impl<T: Clone> SampleReservoir<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: Vec::new(),
+            capacity,
+            seen: 0,
+            rng: SeededRng::new(SeededRng::generate_seed()),
+        }
+    }
+
+    fn record(&mut self, value: &T) {
+        self.seen += 1;
+        if self.samples.len() < self.capacity {
+            self.samples.push(value.clone());
+            return;
+        }
+        let slot = (self.rng.next_u64() % self.seen) as usize;
+        if slot < self.capacity {
+            self.samples[slot] = value.clone();
+        }
+    }
+}
+
+/// Running totals for one custom metric; percentiles come from a bounded sample
+struct CustomMetricStats {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    samples: SampleReservoir<f64>,
+}
+
+impl CustomMetricStats {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            samples: SampleReservoir::new(MAX_CUSTOM_SAMPLES),
+        }
+    }
+
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.samples.record(&value);
+    }
+}
+
+/// MetricsCollector - Aggregates performance metrics; shared behind a mutex and fed
+/// in batches through `VuMetricsBuffer`
+///
+/// Every request and custom metric value is folded into running totals as it arrives, so
+/// memory stays flat however long the run is: requests into histograms, custom metrics into
+/// a sample of at most `MAX_CUSTOM_SAMPLES` values each. Individual request metrics are only
+/// kept when raw samples were requested, and then at most `MAX_RAW_SAMPLES` of them.
 pub struct MetricsCollector {
-    metrics: Vec<RequestMetric>,
+    overall: RequestStats,
+    queue_wait_sum: u64,
+    queue_wait_max: u64,
+    queue_waits: DurationHistogram,
+    steps: HashMap<String, StepStats>,
+    scenarios: HashMap<String, ScenarioStats>,
+    raw_samples: Option<SampleReservoir<RequestMetric>>,
     start_time: Instant,
     iterations_completed: HashMap<u32, u64>, // vu_id -> iteration count
+    warmup: Duration,
+    warmup_iterations: HashMap<u32, u64>, // vu_id -> last iteration seen during warmup
+    custom_metrics: HashMap<String, CustomMetricStats>, // custom metric name -> running stats
+    time_series: Vec<TimeSeriesPoint>,
+    current_bucket: Option<TimeBucket>,
+    active_vus: u32,
 }
 
 impl MetricsCollector {
     pub fn new() -> Self {
         Self {
-            metrics: Vec::new(),
+            overall: RequestStats::new(),
+            queue_wait_sum: 0,
+            queue_wait_max: 0,
+            queue_waits: DurationHistogram::new(),
+            steps: HashMap::new(),
+            scenarios: HashMap::new(),
+            raw_samples: None,
             start_time: Instant::now(),
             iterations_completed: HashMap::new(),
+            warmup: Duration::ZERO,
+            warmup_iterations: HashMap::new(),
+            custom_metrics: HashMap::new(),
+            time_series: Vec::new(),
+            current_bucket: None,
+            active_vus: 0,
         }
     }
 
+    /// Discard metrics recorded during the first `warmup_secs` of the run
+    pub fn with_warmup(mut self, warmup_secs: u64) -> Self {
+        self.warmup = Duration::from_secs(warmup_secs);
+        self
+    }
+
+    /// Keep a sample of individual request metrics for `raw_samples`
+    pub fn with_raw_samples(mut self, enabled: bool) -> Self {
+        self.raw_samples = enabled.then(|| SampleReservoir::new(MAX_RAW_SAMPLES));
+        self
+    }
+
+    /// Check if the run is still in its warmup phase
+    pub fn is_warming_up(&self) -> bool {
+        self.start_time.elapsed() < self.warmup
+    }
+
+    /// Time spent in the measurement phase (after warmup)
+    fn measurement_elapsed(&self) -> Duration {
+        self.start_time.elapsed().saturating_sub(self.warmup)
+    }
+
+    /// Check if `at` falls into the warmup phase
+    fn is_warmup_at(&self, at: Instant) -> bool {
+        at.saturating_duration_since(self.start_time) < self.warmup
+    }
+
     /// Record a new request metric
     pub fn record(&mut self, metric: RequestMetric) {
+        self.record_at(metric, Instant::now());
+    }
+
+    /// Record a request metric that completed at `at` (buffered metrics arrive late)
+    pub fn record_at(&mut self, metric: RequestMetric, at: Instant) {
+        if self.is_warmup_at(at) {
+            let warmup_iterations = self.warmup_iterations.entry(metric.vu_id).or_insert(0);
+            if metric.iteration > *warmup_iterations {
+                *warmup_iterations = metric.iteration;
+            }
+            return;
+        }
+
+        self.record_in_bucket(&metric, at);
+        self.overall.record(&metric);
+        self.queue_wait_sum += metric.queue_wait_ms;
+        self.queue_wait_max = self.queue_wait_max.max(metric.queue_wait_ms);
+        self.queue_waits.record(metric.queue_wait_ms);
+
+        let step = self.steps.entry(metric.step_id.clone()).or_insert_with(|| StepStats {
+            step_name: metric.step_name.clone(),
+            stats: RequestStats::new(),
+            server_timing: HashMap::new(),
+        });
+        step.stats.record(&metric);
+        for (name, dur) in &metric.server_timing {
+            let total = step.server_timing.entry(name.clone()).or_insert((0.0, 0));
+            total.0 += dur;
+            total.1 += 1;
+        }
+
+        if !metric.scenario_id.is_empty() {
+            let scenario = self.scenarios.entry(metric.scenario_id.clone()).or_insert_with(|| ScenarioStats {
+                stats: RequestStats::new(),
+                iterations: 0,
+                last_iteration: HashMap::new(),
+            });
+            scenario.stats.record(&metric);
+            // An iteration runs exactly one scenario and a VU's metrics arrive in order,
+            // so a new (vu, iteration) pair is a new iteration of this scenario
+            if scenario.last_iteration.insert(metric.vu_id, metric.iteration) != Some(metric.iteration) {
+                scenario.iterations += 1;
+            }
+        }
+
+        if let Some(ref mut raw_samples) = self.raw_samples {
+            raw_samples.record(&metric);
+        }
+
         // Track iteration completion
         let vu_iterations = self.iterations_completed.entry(metric.vu_id).or_insert(0);
         if metric.iteration > *vu_iterations {
             *vu_iterations = metric.iteration;
         }
-        self.metrics.push(metric);
+    }
+
+    /// Add a metric to the current time bucket, closing finished buckets as time moves on.
+    /// Batches from other VUs can arrive after their bucket closed; those go to the open one.
+    fn record_in_bucket(&mut self, metric: &RequestMetric, at: Instant) {
+        let index = at.saturating_duration_since(self.start_time).as_secs() / BUCKET_SECS;
+
+        let needs_new_bucket = match self.current_bucket {
+            Some(ref bucket) => bucket.index < index,
+            None => true,
+        };
+        if needs_new_bucket {
+            self.flush_bucket(index);
+        }
+
+        if let Some(ref mut bucket) = self.current_bucket {
+            bucket.requests += 1;
+            if !metric.success {
+                bucket.failed += 1;
+            }
+            bucket.durations.push(metric.duration_ms);
+        }
+    }
+
+    /// Write the current bucket (and empty gaps up to `next_index`) to the series
+    fn flush_bucket(&mut self, next_index: u64) {
+        if let Some(bucket) = self.current_bucket.take() {
+            self.time_series.push(bucket.to_point(self.active_vus));
+            for gap in (bucket.index + 1)..next_index {
+                self.time_series.push(TimeBucket::new(gap).to_point(self.active_vus));
+            }
+        }
+        self.current_bucket = Some(TimeBucket::new(next_index));
+    }
+
+    /// Update the active VU count reported in time-series points
+    pub fn set_active_vus(&mut self, vus: u32) {
+        self.active_vus = vus;
+    }
+
+    /// Get the time series including the bucket still being filled
+    pub fn get_time_series(&self) -> Vec<TimeSeriesPoint> {
+        let mut series = self.time_series.clone();
+        if let Some(ref bucket) = self.current_bucket {
+            series.push(bucket.to_point(self.active_vus));
+        }
+        series
+    }
+
+    /// Record a custom metric sample pushed from a script step
+    pub fn add_custom(&mut self, name: &str, value: f64) {
+        self.add_custom_at(name, value, Instant::now());
+    }
+
+    /// Record a custom metric sample taken at `at`
+    pub fn add_custom_at(&mut self, name: &str, value: f64, at: Instant) {
+        if self.is_warmup_at(at) {
+            return;
+        }
+        self.custom_metrics
+            .entry(name.to_string())
+            .or_insert_with(CustomMetricStats::new)
+            .record(value);
     }
 
     /// Get total iterations completed across all VUs
     pub fn get_total_iterations(&self) -> u64 {
-        self.iterations_completed.values().sum()
+        self.iterations_completed
+            .iter()
+            .map(|(vu_id, iterations)| {
+                let warmup = self.warmup_iterations.get(vu_id).copied().unwrap_or(0);
+                iterations.saturating_sub(warmup)
+            })
+            .sum()
     }
 
     /// Get elapsed time in seconds
@@ -44,21 +526,31 @@ impl MetricsCollector {
         self.start_time.elapsed().as_millis() as u64
     }
 
+    /// Recorded request metrics (post-warmup) in timestamp order, when raw samples are on.
+    /// Runs with more than `MAX_RAW_SAMPLES` requests return a uniform sample of them.
+    pub fn raw_samples(&self) -> Option<Vec<RequestMetric>> {
+        self.raw_samples.as_ref().map(|reservoir| {
+            let mut samples = reservoir.samples.clone();
+            samples.sort_by_key(|m| m.timestamp);
+            samples
+        })
+    }
+
     /// Get current metrics count
     pub fn get_metrics_count(&self) -> usize {
-        self.metrics.len()
+        self.overall.requests as usize
     }
 
     /// Get failed requests count
     pub fn get_failed_count(&self) -> u64 {
-        self.metrics.iter().filter(|m| !m.success).count() as u64
+        self.overall.failed
     }
 
     /// Calculate current RPS (requests per second)
     pub fn get_current_rps(&self) -> f64 {
-        let elapsed = self.start_time.elapsed().as_secs_f64();
+        let elapsed = self.measurement_elapsed().as_secs_f64();
         if elapsed > 0.0 {
-            self.metrics.len() as f64 / elapsed
+            self.overall.requests as f64 / elapsed
         } else {
             0.0
         }
@@ -66,65 +558,53 @@ impl MetricsCollector {
 
     /// Calculate current error rate
     pub fn get_error_rate(&self) -> f64 {
-        let total = self.metrics.len();
-        if total > 0 {
-            let failed = self.metrics.iter().filter(|m| !m.success).count();
-            failed as f64 / total as f64
-        } else {
-            0.0
-        }
+        self.overall.error_rate()
     }
 
-    /// Calculate p95 duration from current metrics
+    /// Live p95 duration from the histogram
     pub fn get_p95_duration(&self) -> u64 {
-        if self.metrics.is_empty() {
-            return 0;
-        }
-        let mut durations: Vec<u64> = self.metrics.iter().map(|m| m.duration_ms).collect();
-        durations.sort();
-        percentile(&durations, 95.0)
+        self.overall.durations.percentile(95.0)
     }
 
     /// Calculate all aggregated metrics
     pub fn calculate_aggregates(&self) -> AggregatedMetrics {
-        if self.metrics.is_empty() {
-            return AggregatedMetrics::default();
+        if self.overall.requests == 0 {
+            return AggregatedMetrics {
+                custom_metrics: self.calculate_custom_metrics(),
+                ..Default::default()
+            };
         }
 
-        let total_requests = self.metrics.len() as u64;
-        let failed_requests = self.metrics.iter().filter(|m| !m.success).count() as u64;
-        let error_rate = if total_requests > 0 {
-            failed_requests as f64 / total_requests as f64
-        } else {
-            0.0
-        };
+        let total_requests = self.overall.requests;
+        let failed_requests = self.overall.failed;
+        let error_rate = self.overall.error_rate();
 
-        // Calculate duration percentiles
-        let mut durations: Vec<u64> = self.metrics.iter().map(|m| m.duration_ms).collect();
-        durations.sort();
+        // Duration percentiles come from the histogram: exact below HISTOGRAM_EXACT_MS,
+        // within ~1.5% above it
+        let duration_min = self.overall.duration_min();
+        let duration_max = self.overall.duration_max;
+        let duration_avg = self.overall.duration_avg();
+        let duration_med = self.overall.durations.percentile(50.0);
+        let duration_p90 = self.overall.durations.percentile(90.0);
+        let duration_p95 = self.overall.durations.percentile(95.0);
+        let duration_p99 = self.overall.durations.percentile(99.0);
 
-        let duration_min = *durations.first().unwrap_or(&0);
-        let duration_max = *durations.last().unwrap_or(&0);
-        let duration_avg = if !durations.is_empty() {
-            durations.iter().sum::<u64>() as f64 / durations.len() as f64
-        } else {
-            0.0
-        };
-        let duration_med = percentile(&durations, 50.0);
-        let duration_p90 = percentile(&durations, 90.0);
-        let duration_p95 = percentile(&durations, 95.0);
-        let duration_p99 = percentile(&durations, 99.0);
+        // Time requests spent queued behind the in-flight limit
+        let queue_wait_avg = self.queue_wait_sum as f64 / total_requests as f64;
+        let queue_wait_p95 = self.queue_waits.percentile(95.0);
+        let queue_wait_max = self.queue_wait_max;
 
-        // Calculate throughput
-        let total_duration_ms = self.start_time.elapsed().as_millis() as u64;
+        // Calculate throughput (measurement phase only)
+        let total_duration_ms = self.measurement_elapsed().as_millis() as u64;
         let requests_per_second = if total_duration_ms > 0 {
             total_requests as f64 / (total_duration_ms as f64 / 1000.0)
         } else {
             0.0
         };
 
-        // Calculate per-step metrics
+        // Calculate per-step and per-scenario metrics
         let step_metrics = self.calculate_step_metrics();
+        let scenario_metrics = self.calculate_scenario_metrics();
 
         // Calculate iterations completed
         let iterations_completed = self.get_total_iterations();
@@ -144,64 +624,98 @@ impl MetricsCollector {
             iterations_completed,
             total_duration_ms,
             step_metrics,
+            scenario_metrics,
+            estimated_new_connections: 0,
+            estimated_reused_connections: 0,
+            max_in_flight: 0,
+            queue_wait_avg,
+            queue_wait_p95,
+            queue_wait_max,
+            custom_metrics: self.calculate_custom_metrics(),
         }
     }
 
-    /// Calculate metrics per step
-    fn calculate_step_metrics(&self) -> HashMap<String, StepMetrics> {
-        let mut step_groups: HashMap<String, Vec<&RequestMetric>> = HashMap::new();
-
-        // Group metrics by step
-        for metric in &self.metrics {
-            step_groups
-                .entry(metric.step_id.clone())
-                .or_insert_with(Vec::new)
-                .push(metric);
-        }
-
+    /// Summarize custom metrics; percentiles are read from each metric's sample
+    fn calculate_custom_metrics(&self) -> HashMap<String, CustomMetricSummary> {
         let mut result = HashMap::new();
 
-        for (step_id, metrics) in step_groups {
-            if metrics.is_empty() {
+        for (name, stats) in &self.custom_metrics {
+            if stats.count == 0 {
                 continue;
             }
 
-            let step_name = metrics.first().map(|m| m.step_name.clone()).unwrap_or_default();
-            let total_requests = metrics.len() as u64;
-            let failed_requests = metrics.iter().filter(|m| !m.success).count() as u64;
-            let error_rate = if total_requests > 0 {
-                failed_requests as f64 / total_requests as f64
-            } else {
-                0.0
-            };
+            let mut sorted = stats.samples.samples.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
-            let mut durations: Vec<u64> = metrics.iter().map(|m| m.duration_ms).collect();
-            durations.sort();
-
-            let step_metrics = StepMetrics {
-                step_name,
-                total_requests,
-                failed_requests,
-                error_rate,
-                duration_min: *durations.first().unwrap_or(&0),
-                duration_max: *durations.last().unwrap_or(&0),
-                duration_avg: if !durations.is_empty() {
-                    durations.iter().sum::<u64>() as f64 / durations.len() as f64
-                } else {
-                    0.0
-                },
-                duration_med: percentile(&durations, 50.0),
-                duration_p90: percentile(&durations, 90.0),
-                duration_p95: percentile(&durations, 95.0),
-                duration_p99: percentile(&durations, 99.0),
-            };
-
-            result.insert(step_id, step_metrics);
+            result.insert(name.clone(), CustomMetricSummary {
+                name: name.clone(),
+                count: stats.count,
+                sum: stats.sum,
+                min: stats.min,
+                max: stats.max,
+                avg: stats.sum / stats.count as f64,
+                med: percentile_f64(&sorted, 50.0),
+                p90: percentile_f64(&sorted, 90.0),
+                p95: percentile_f64(&sorted, 95.0),
+                p99: percentile_f64(&sorted, 99.0),
+            });
         }
 
         result
     }
 
+    /// Calculate metrics per scenario; names are filled in by the executor, which knows them
+    fn calculate_scenario_metrics(&self) -> HashMap<String, ScenarioMetrics> {
+        self.scenarios
+            .iter()
+            .map(|(scenario_id, scenario)| {
+                let stats = &scenario.stats;
+                (scenario_id.clone(), ScenarioMetrics {
+                    scenario_name: String::new(),
+                    iterations: scenario.iterations,
+                    total_requests: stats.requests,
+                    failed_requests: stats.failed,
+                    error_rate: stats.error_rate(),
+                    duration_avg: stats.duration_avg(),
+                    duration_med: stats.durations.percentile(50.0),
+                    duration_p95: stats.durations.percentile(95.0),
+                    duration_p99: stats.durations.percentile(99.0),
+                })
+            })
+            .collect()
+    }
+
+    /// Calculate metrics per step
+    fn calculate_step_metrics(&self) -> HashMap<String, StepMetrics> {
+        self.steps
+            .iter()
+            .map(|(step_id, step)| {
+                let stats = &step.stats;
+                // Average each Server-Timing entry over the responses that reported it
+                let server_timing = step
+                    .server_timing
+                    .iter()
+                    .map(|(name, (sum, count))| (name.clone(), sum / *count as f64))
+                    .collect();
+
+                (step_id.clone(), StepMetrics {
+                    step_name: step.step_name.clone(),
+                    total_requests: stats.requests,
+                    failed_requests: stats.failed,
+                    error_rate: stats.error_rate(),
+                    duration_min: stats.duration_min(),
+                    duration_max: stats.duration_max,
+                    duration_avg: stats.duration_avg(),
+                    duration_med: stats.durations.percentile(50.0),
+                    duration_p90: stats.durations.percentile(90.0),
+                    duration_p95: stats.durations.percentile(95.0),
+                    duration_p99: stats.durations.percentile(99.0),
+                    server_timing,
+                })
+            })
+            .collect()
+    }
+
     /// Evaluate thresholds against collected metrics
     pub fn evaluate_thresholds(&self, thresholds: &[Threshold]) -> Vec<ThresholdResult> {
         let metrics = self.calculate_aggregates();
@@ -216,6 +730,58 @@ impl MetricsCollector {
     }
 }
 
+/// ConnectionTracker - Estimates connection reuse for the shared pooled client
+///
+/// reqwest does not expose pool events, so the number of opened connections is
+/// approximated, never measured: with keep-alive the pool only grows when more requests
+/// are in flight than idle connections exist, so the peak concurrency is taken as the
+/// number of connections opened. With HTTP/2 all requests are assumed to share one
+/// connection. Idle timeouts, server-side closes and reconnects after errors are not seen,
+/// so real numbers of new connections can be higher.
+pub struct ConnectionTracker {
+    in_flight: AtomicU64,
+    peak_in_flight: AtomicU64,
+    total_requests: AtomicU64,
+    http2: bool,
+}
+
+impl ConnectionTracker {
+    pub fn new(http2: bool) -> Self {
+        Self {
+            in_flight: AtomicU64::new(0),
+            peak_in_flight: AtomicU64::new(0),
+            total_requests: AtomicU64::new(0),
+            http2,
+        }
+    }
+
+    /// Mark a request as sent
+    pub fn begin(&self) {
+        let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak_in_flight.fetch_max(current, Ordering::SeqCst);
+        self.total_requests.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Mark a request as finished
+    pub fn end(&self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Returns the estimated (new connections, reused connections)
+    pub fn estimated_connections(&self) -> (u64, u64) {
+        let total = self.total_requests.load(Ordering::SeqCst);
+        if total == 0 {
+            return (0, 0);
+        }
+        let new = if self.http2 {
+            1
+        } else {
+            self.peak_in_flight.load(Ordering::SeqCst).max(1)
+        };
+        (new, total.saturating_sub(new))
+    }
+}
+
 /// Calculate percentile from a sorted slice
 fn percentile(sorted_data: &[u64], p: f64) -> u64 {
     if sorted_data.is_empty() {
@@ -227,6 +793,17 @@ fn percentile(sorted_data: &[u64], p: f64) -> u64 {
     sorted_data[index]
 }
 
+/// Calculate percentile from a sorted slice of floats
+fn percentile_f64(sorted_data: &[f64], p: f64) -> f64 {
+    if sorted_data.is_empty() {
+        return 0.0;
+    }
+
+    let index = (p / 100.0 * (sorted_data.len() - 1) as f64).round() as usize;
+    let index = index.min(sorted_data.len() - 1);
+    sorted_data[index]
+}
+
 /// Evaluate a single threshold against metrics
 fn evaluate_single_threshold(threshold: &Threshold, metrics: &AggregatedMetrics) -> ThresholdResult {
     let condition = &threshold.condition;
@@ -254,9 +831,10 @@ fn evaluate_single_threshold(threshold: &Threshold, metrics: &AggregatedMetrics)
             let (passed, msg) = parse_numeric_condition(condition, actual);
             (actual, passed, msg)
         }
-        _ => {
-            (0.0, false, format!("Unknown metric: {}", threshold.metric))
-        }
+        name => match metrics.custom_metrics.get(name) {
+            Some(custom) => parse_custom_condition(condition, custom),
+            None => (0.0, false, format!("Unknown metric: {}", threshold.metric)),
+        },
     };
 
     ThresholdResult {
@@ -314,6 +892,48 @@ fn parse_duration_condition(condition: &str, metrics: &AggregatedMetrics) -> (f6
     (metrics.duration_avg, passed, message)
 }
 
+/// Parse custom metric conditions like "p(95)<500", "sum>10", "count>=1", "<200" (avg).
+/// Only the percentiles kept in the summary (50, 90, 95, 99) can be checked; any other
+/// p(N) fails the threshold with an error instead of being compared against a different one.
+fn parse_custom_condition(condition: &str, custom: &CustomMetricSummary) -> (f64, bool, String) {
+    let re = Regex::new(r"^\s*(p\((\d+)\)|avg|min|max|med|sum|count)?\s*([<>=!]+)\s*(-?\d+\.?\d*)").unwrap();
+    if let Some(caps) = re.captures(condition) {
+        let stat = caps.get(1).map(|m| m.as_str()).unwrap_or("avg");
+        let op = caps.get(3).unwrap().as_str();
+        let expected: f64 = caps.get(4).unwrap().as_str().parse().unwrap_or(0.0);
+
+        let actual = if let Some(p) = caps.get(2) {
+            match p.as_str() {
+                "50" => custom.med,
+                "90" => custom.p90,
+                "95" => custom.p95,
+                "99" => custom.p99,
+                other => {
+                    return (0.0, false, format!(
+                        "Unsupported percentile p({}) for {}: use p(50), p(90), p(95) or p(99)",
+                        other, custom.name
+                    ));
+                }
+            }
+        } else {
+            match stat {
+                "min" => custom.min,
+                "max" => custom.max,
+                "med" => custom.med,
+                "sum" => custom.sum,
+                "count" => custom.count as f64,
+                _ => custom.avg,
+            }
+        };
+
+        let passed = compare_values(actual, op, expected);
+        let message = format!("{} {} = {} {} {}", custom.name, stat, actual, op, expected);
+        return (actual, passed, message);
+    }
+
+    (0.0, false, format!("Invalid condition: {}", condition))
+}
+
 /// Parse error rate conditions like "rate<0.05", "<0.01"
 fn parse_error_rate_condition(condition: &str, metrics: &AggregatedMetrics) -> (f64, bool, String) {
     let actual = metrics.error_rate;
@@ -379,6 +999,301 @@ mod tests {
         assert!(compare_values(0.01, "<", 0.05));
     }
 
+    fn sample_metric(vu_id: u32, iteration: u64) -> RequestMetric {
+        RequestMetric {
+            step_id: "step-1".to_string(),
+            step_name: "Step 1".to_string(),
+            method: "GET".to_string(),
+            url: "http://localhost/health".to_string(),
+            status: 200,
+            duration_ms: 10,
+            success: true,
+            vu_id,
+            iteration,
+            timestamp: 0,
+            queue_wait_ms: 0,
+            server_timing: HashMap::new(),
+            scenario_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_warmup_metrics_are_discarded() {
+        let mut collector = MetricsCollector::new().with_warmup(60);
+        assert!(collector.is_warming_up());
+        collector.record(sample_metric(0, 1));
+        assert_eq!(collector.get_metrics_count(), 0);
+        assert_eq!(collector.calculate_aggregates().total_requests, 0);
+
+        let mut collector = MetricsCollector::new();
+        assert!(!collector.is_warming_up());
+        collector.record(sample_metric(0, 1));
+        assert_eq!(collector.get_metrics_count(), 1);
+    }
+
+    #[test]
+    fn test_queue_wait_aggregates() {
+        let mut collector = MetricsCollector::new();
+        for wait in [0, 0, 10, 30] {
+            let mut metric = sample_metric(0, 1);
+            metric.queue_wait_ms = wait;
+            collector.record(metric);
+        }
+
+        let aggregates = collector.calculate_aggregates();
+        assert_eq!(aggregates.queue_wait_avg, 10.0);
+        assert_eq!(aggregates.queue_wait_max, 30);
+        assert_eq!(aggregates.duration_max, 10);
+    }
+
+    #[test]
+    fn test_step_server_timing_average() {
+        let mut collector = MetricsCollector::new();
+        for dur in [40.0, 60.0] {
+            let mut metric = sample_metric(0, 1);
+            metric.server_timing.insert("db".to_string(), dur);
+            collector.record(metric);
+        }
+        collector.record(sample_metric(0, 2));
+
+        let steps = collector.calculate_aggregates().step_metrics;
+        assert_eq!(steps["step-1"].server_timing.get("db"), Some(&50.0));
+    }
+
+    #[test]
+    fn test_raw_samples_are_capped() {
+        let mut collector = MetricsCollector::new();
+        collector.record(sample_metric(0, 1));
+        assert!(collector.raw_samples().is_none());
+
+        let mut collector = MetricsCollector::new().with_raw_samples(true);
+        let total = MAX_RAW_SAMPLES as u64 + 500;
+        for i in 0..total {
+            let mut metric = sample_metric(0, i);
+            metric.timestamp = i as i64;
+            collector.record(metric);
+        }
+        let samples = collector.raw_samples().unwrap();
+        assert_eq!(samples.len(), MAX_RAW_SAMPLES);
+        assert!(samples.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+        assert_eq!(collector.calculate_aggregates().total_requests, total);
+    }
+
+    #[test]
+    fn test_time_series_buckets() {
+        let mut collector = MetricsCollector::new();
+        collector.set_active_vus(3);
+        collector.record(sample_metric(0, 1));
+        let mut failed = sample_metric(1, 1);
+        failed.success = false;
+        failed.duration_ms = 50;
+        collector.record(failed);
+
+        let series = collector.get_time_series();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].t, 0);
+        assert_eq!(series[0].rps, 2.0);
+        assert_eq!(series[0].p95, 50);
+        assert_eq!(series[0].error_rate, 0.5);
+        assert_eq!(series[0].active_vus, 3);
+    }
+
+    #[test]
+    fn test_duration_histogram_percentiles() {
+        let mut histogram = DurationHistogram::new();
+        for value in 1..=100 {
+            histogram.record(value);
+        }
+        assert_eq!(histogram.percentile(50.0), 51);
+        assert_eq!(histogram.percentile(95.0), 95);
+
+        // Above the exact range values land within one sub-bucket
+        let mut histogram = DurationHistogram::new();
+        histogram.record(5_000);
+        let p = histogram.percentile(95.0);
+        assert!(p <= 5_000 && 5_000 - p < 5_000 / HISTOGRAM_SUB_BUCKETS);
+        histogram.record(u64::MAX);
+        assert!(histogram.percentile(100.0) > 1 << 62);
+    }
+
+    #[test]
+    fn test_vu_buffer_flushes_in_batches() {
+        let mut collector = MetricsCollector::new();
+        let mut buffer = VuMetricsBuffer::new();
+        for i in 0..(FLUSH_BATCH_SIZE - 1) {
+            buffer.push(sample_metric(0, i as u64));
+        }
+        assert!(!buffer.should_flush());
+
+        let mut failed = sample_metric(0, 64);
+        failed.success = false;
+        buffer.push(failed);
+        buffer.push_custom("orders_placed", 1.0);
+        assert!(buffer.should_flush());
+
+        buffer.flush_into(&mut collector);
+        assert!(buffer.is_empty());
+        assert_eq!(collector.get_metrics_count(), FLUSH_BATCH_SIZE);
+        assert_eq!(collector.get_failed_count(), 1);
+        assert_eq!(collector.get_p95_duration(), 10);
+        assert_eq!(collector.calculate_aggregates().custom_metrics["orders_placed"].count, 1);
+    }
+
+    /// Throughput of 500 VUs recording through per-VU buffers versus locking per request.
+    /// Run with `cargo test --release bench_500_vus -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_500_vus_record_throughput() {
+        use std::sync::{Arc, Mutex};
+
+        const VUS: usize = 500;
+        const REQUESTS_PER_VU: usize = 2_000;
+
+        let run = |buffered: bool| {
+            let collector = Arc::new(Mutex::new(MetricsCollector::new()));
+            let start = Instant::now();
+            let handles: Vec<_> = (0..VUS)
+                .map(|vu| {
+                    let collector = collector.clone();
+                    std::thread::spawn(move || {
+                        let mut buffer = VuMetricsBuffer::new();
+                        for i in 0..REQUESTS_PER_VU {
+                            let metric = sample_metric(vu as u32, i as u64);
+                            if buffered {
+                                buffer.push(metric);
+                                if buffer.should_flush() {
+                                    buffer.flush_into(&mut collector.lock().unwrap());
+                                }
+                            } else {
+                                collector.lock().unwrap().record(metric);
+                            }
+                        }
+                        buffer.flush_into(&mut collector.lock().unwrap());
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            let elapsed = start.elapsed();
+            let collector = collector.lock().unwrap();
+            assert_eq!(collector.get_metrics_count(), VUS * REQUESTS_PER_VU);
+            assert_eq!(collector.get_total_iterations(), (VUS * (REQUESTS_PER_VU - 1)) as u64);
+            elapsed
+        };
+
+        let locked = run(false);
+        let buffered = run(true);
+        let total = (VUS * REQUESTS_PER_VU) as f64;
+        println!(
+            "{} VUs x {} requests: per-request lock {:.0} req/s, buffered {:.0} req/s",
+            VUS,
+            REQUESTS_PER_VU,
+            total / locked.as_secs_f64(),
+            total / buffered.as_secs_f64()
+        );
+        assert!(buffered <= locked * 2);
+    }
+
+    #[test]
+    fn test_custom_metric_threshold() {
+        let mut collector = MetricsCollector::new();
+        for value in [100.0, 200.0, 300.0] {
+            collector.add_custom("checkout_latency", value);
+        }
+        collector.add_custom("orders_placed", 1.0);
+
+        let results = collector.evaluate_thresholds(&[
+            Threshold { metric: "checkout_latency".to_string(), condition: "avg<250".to_string() },
+            Threshold { metric: "checkout_latency".to_string(), condition: "max<250".to_string() },
+            Threshold { metric: "orders_placed".to_string(), condition: "count>=1".to_string() },
+            Threshold { metric: "missing_metric".to_string(), condition: "<1".to_string() },
+            Threshold { metric: "checkout_latency".to_string(), condition: "p(99)<=300".to_string() },
+            Threshold { metric: "checkout_latency".to_string(), condition: "p(75)<1000".to_string() },
+        ]);
+
+        assert!(results[0].passed);
+        assert_eq!(results[0].actual_value, 200.0);
+        assert!(!results[1].passed);
+        assert!(results[2].passed);
+        assert!(!results[3].passed);
+        assert!(results[4].passed);
+        assert!(!results[5].passed);
+        assert!(results[5].message.starts_with("Unsupported percentile p(75)"), "{}", results[5].message);
+    }
+
+    #[test]
+    fn test_custom_metric_samples_are_capped() {
+        let mut collector = MetricsCollector::new();
+        let total = MAX_CUSTOM_SAMPLES as u64 + 500;
+        for i in 0..total {
+            collector.add_custom("cart_size", i as f64);
+        }
+
+        let stats = &collector.custom_metrics["cart_size"];
+        assert_eq!(stats.samples.samples.len(), MAX_CUSTOM_SAMPLES);
+        let summary = &collector.calculate_aggregates().custom_metrics["cart_size"];
+        assert_eq!(summary.count, total);
+        assert_eq!(summary.min, 0.0);
+        assert_eq!(summary.max, (total - 1) as f64);
+        assert_eq!(summary.avg, (total - 1) as f64 / 2.0);
+    }
+
+    #[test]
+    fn test_connection_tracker_estimates() {
+        let tracker = ConnectionTracker::new(false);
+        assert_eq!(tracker.estimated_connections(), (0, 0));
+
+        // Two concurrent requests, then three sequential ones reusing the pool
+        tracker.begin();
+        tracker.begin();
+        tracker.end();
+        tracker.end();
+        for _ in 0..3 {
+            tracker.begin();
+            tracker.end();
+        }
+        assert_eq!(tracker.estimated_connections(), (2, 3));
+
+        let http2 = ConnectionTracker::new(true);
+        http2.begin();
+        http2.begin();
+        http2.end();
+        http2.end();
+        assert_eq!(http2.estimated_connections(), (1, 1));
+    }
+
+    #[test]
+    fn test_scenario_metrics() {
+        let mut collector = MetricsCollector::new();
+        for (scenario_id, vu_id, iteration, duration_ms, success) in [
+            ("browse", 0, 1, 10, true),
+            ("browse", 0, 1, 30, true),
+            ("checkout", 0, 2, 100, false),
+            ("browse", 1, 1, 20, true),
+        ] {
+            let mut metric = sample_metric(vu_id, iteration);
+            metric.scenario_id = scenario_id.to_string();
+            metric.duration_ms = duration_ms;
+            metric.success = success;
+            collector.record(metric);
+        }
+
+        let aggregates = collector.calculate_aggregates();
+        assert_eq!(aggregates.total_requests, 4);
+
+        let browse = &aggregates.scenario_metrics["browse"];
+        assert_eq!(browse.iterations, 2);
+        assert_eq!(browse.total_requests, 3);
+        assert_eq!(browse.duration_avg, 20.0);
+        assert_eq!(browse.error_rate, 0.0);
+
+        let checkout = &aggregates.scenario_metrics["checkout"];
+        assert_eq!(checkout.iterations, 1);
+        assert_eq!(checkout.failed_requests, 1);
+        assert_eq!(checkout.error_rate, 1.0);
+    }
+
     #[test]
     fn test_parse_duration_condition() {
         let metrics = AggregatedMetrics {
@@ -8,6 +8,7 @@
 //! - **Multiple VUs**: Run scenarios with configurable virtual users
 //! - **Ramping Stages**: Gradually increase/decrease VUs over time
 //! - **Metrics Collection**: Track response times, error rates, throughput
+//! - **Scenario Mix**: Pick a scenario per iteration by weight to model mixed traffic
 //! - **Thresholds**: Define pass/fail criteria
 //! - **Real-time Events**: Stream progress updates to frontend
 //!
@@ -36,17 +37,22 @@
 //!     vus: Some(50),
 //!     duration_secs: Some(300),
 //!     stages: None,
+//!     warmup_secs: Some(10),
 //!     thresholds: vec![
 //!         Threshold {
 //!             metric: "http_req_duration".to_string(),
 //!             condition: "p(95)<500".to_string(),
 //!         },
 //!     ],
+//!     http2_prior_knowledge: false,
+//!     store_raw_samples: false,
+//!     max_in_flight: None,
+//!     scenario_mix: vec![],
 //!     created_at: 0,
 //!     updated_at: 0,
 //! };
 //!
-//! let result = run_performance_test(scenario, steps, config, base_url, app_handle).await;
+//! let result = run_performance_test(scenario, steps, config, base_url, client_defaults, vec![], app_handle).await;
 //! ```
 
 pub mod types;
@@ -59,14 +65,21 @@ pub use types::{
     PerformanceTestType,
     PerformanceTestConfig,
     CreatePerformanceTestInput,
+    UpdatePerformanceTestInput,
     PerformanceTestRun,
     PerformanceRunStatus,
     Stage,
     Threshold,
     ThresholdResult,
+    WeightedScenario,
+    validate_scenario_mix,
     RequestMetric,
     AggregatedMetrics,
     StepMetrics,
+    ScenarioMetrics,
+    CustomMetricSummary,
+    TimeSeriesPoint,
+    RawSamplePage,
     // Events
     PerfStartedEvent,
     PerfProgressEvent,
@@ -75,7 +88,7 @@ pub use types::{
     PerfCompletedEvent,
 };
 
-pub use metrics::MetricsCollector;
+pub use metrics::{MetricsCollector, ConnectionTracker, VuMetricsBuffer};
 pub use stages::{
     StageScheduler,
     create_smoke_test_stages,
@@ -1,9 +1,11 @@
 use super::types::Stage;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// StageScheduler - Manages VU ramping according to configured stages
 /// 
 /// Handles linear interpolation between stages to smoothly ramp VUs up/down.
+/// Each stage ramps from the previous stage's target (0 for the first) to its own
+/// target over its duration; a zero-length stage jumps straight to its target.
 /// For example, with stages:
 ///   - Stage 1: 2 minutes, target 50 VUs
 ///   - Stage 2: 10 minutes, target 50 VUs (maintain)
@@ -50,15 +52,15 @@ impl StageScheduler {
 
     /// Get the current stage index (0-based)
     pub fn get_current_stage_index(&self) -> Option<usize> {
-        if self.stages.is_empty() {
-            return None;
-        }
+        self.stage_index_at(self.start_time.elapsed())
+    }
 
-        let elapsed = self.start_time.elapsed().as_secs();
-        let mut accumulated = 0u64;
+    /// Index of the stage running `elapsed` into the run; None once all stages are done
+    pub fn stage_index_at(&self, elapsed: Duration) -> Option<usize> {
+        let mut accumulated = Duration::ZERO;
 
         for (index, stage) in self.stages.iter().enumerate() {
-            accumulated += stage.duration_secs;
+            accumulated += Duration::from_secs(stage.duration_secs);
             if elapsed < accumulated {
                 return Some(index);
             }
@@ -74,50 +76,48 @@ impl StageScheduler {
             .and_then(|i| self.stages.get(i))
     }
 
+    /// Get a stage by index
+    pub fn get_stage(&self, index: usize) -> Option<&Stage> {
+        self.stages.get(index)
+    }
+
+    /// VUs a stage ramps from: the previous stage's target, or 0 for the first stage
+    pub fn stage_start_vus(&self, index: usize) -> u32 {
+        index
+            .checked_sub(1)
+            .and_then(|prev| self.stages.get(prev))
+            .map(|s| s.target_vus)
+            .unwrap_or(0)
+    }
+
     /// Calculate the number of VUs that should be running at the current time
     /// Uses linear interpolation between stage targets
     pub fn get_current_vus(&self) -> u32 {
-        if self.stages.is_empty() {
-            return 0;
-        }
-
-        let elapsed = self.start_time.elapsed().as_secs();
-
-        // If test hasn't started yet
-        if elapsed == 0 {
-            // Return first stage target or interpolate from 0
-            return self.stages.first().map(|_| {
-                // Start from 0 and ramp up
-                0
-            }).unwrap_or(0);
-        }
-
-        // Find which stage we're in
-        let mut accumulated = 0u64;
-        let mut prev_target_vus = 0u32; // Start from 0 VUs
-
-        for stage in &self.stages {
-            let stage_start = accumulated;
-            let stage_end = accumulated + stage.duration_secs;
-
-            if elapsed < stage_end {
-                // We're in this stage - interpolate
-                let stage_elapsed = elapsed - stage_start;
-                let stage_progress = stage_elapsed as f64 / stage.duration_secs as f64;
-                
-                // Linear interpolation between prev target and current target
-                let vus = prev_target_vus as f64 
-                    + (stage.target_vus as f64 - prev_target_vus as f64) * stage_progress;
-                
-                return vus.round() as u32;
-            }
+        self.vus_at(self.start_time.elapsed())
+    }
 
-            prev_target_vus = stage.target_vus;
-            accumulated = stage_end;
-        }
+    /// Number of VUs that should be running `elapsed` into the run, interpolated linearly
+    /// within the current stage (sub-second, so ramps grow with every VU manager tick)
+    pub fn vus_at(&self, elapsed: Duration) -> u32 {
+        let index = match self.stage_index_at(elapsed) {
+            Some(index) => index,
+            // Past all stages - hold the last stage's target
+            None => return self.stages.last().map(|s| s.target_vus).unwrap_or(0),
+        };
+
+        let stage_start: u64 = self.stages[..index].iter().map(|s| s.duration_secs).sum();
+        let stage = &self.stages[index];
+        let from = self.stage_start_vus(index) as f64;
+        let to = stage.target_vus as f64;
+
+        // stage_index_at never lands on a zero-length stage, so the duration is positive
+        let progress = (elapsed.as_secs_f64() - stage_start as f64) / stage.duration_secs as f64;
+        (from + (to - from) * progress.clamp(0.0, 1.0)).round() as u32
+    }
 
-        // Past all stages - return last stage's target
-        self.stages.last().map(|s| s.target_vus).unwrap_or(0)
+    /// Highest VU target across all stages
+    pub fn get_max_vus(&self) -> u32 {
+        self.stages.iter().map(|s| s.target_vus).max().unwrap_or(0)
     }
 
     /// Get the remaining time in seconds
@@ -204,6 +204,7 @@ mod tests {
         let scheduler = StageScheduler::fixed(10, 60);
         assert_eq!(scheduler.get_total_duration_secs(), 60);
         assert_eq!(scheduler.get_current_vus(), 0); // Starts from 0
+        assert_eq!(scheduler.get_max_vus(), 10);
     }
 
     #[test]
@@ -231,6 +232,54 @@ mod tests {
         assert_eq!(scheduler.get_current_stage_index(), Some(1));
     }
 
+    #[test]
+    fn test_vus_at_interpolates_within_stages() {
+        let scheduler = StageScheduler::new(vec![
+            Stage { duration_secs: 60, target_vus: 100 }, // 0 -> 100
+            Stage { duration_secs: 30, target_vus: 100 }, // hold
+            Stage { duration_secs: 0, target_vus: 40 },   // instant drop
+            Stage { duration_secs: 20, target_vus: 0 },   // 40 -> 0
+        ]);
+        let at = |secs: f64| scheduler.vus_at(Duration::from_secs_f64(secs));
+
+        assert_eq!(at(0.0), 0);
+        assert_eq!(at(0.3), 1); // Gradual even within the first second
+        assert_eq!(at(6.0), 10);
+        assert_eq!(at(30.0), 50);
+        assert_eq!(at(59.9), 100);
+        assert_eq!(at(60.0), 100);
+        assert_eq!(at(75.0), 100);
+        assert_eq!(at(90.0), 40);
+        assert_eq!(at(95.0), 30);
+        assert_eq!(at(109.0), 2);
+        assert_eq!(at(110.0), 0);
+        assert_eq!(at(500.0), 0);
+
+        assert_eq!(scheduler.stage_index_at(Duration::from_secs(89)), Some(1));
+        assert_eq!(scheduler.stage_index_at(Duration::from_secs(90)), Some(3));
+        assert_eq!(scheduler.stage_index_at(Duration::from_secs(110)), None);
+        assert_eq!(scheduler.stage_start_vus(0), 0);
+        assert_eq!(scheduler.stage_start_vus(3), 40);
+    }
+
+    #[test]
+    fn test_vus_at_ramps_down_and_up_between_targets() {
+        let scheduler = StageScheduler::new(vec![
+            Stage { duration_secs: 10, target_vus: 20 },
+            Stage { duration_secs: 10, target_vus: 5 },
+            Stage { duration_secs: 40, target_vus: 85 },
+        ]);
+        let at = |secs: u64| scheduler.vus_at(Duration::from_secs(secs));
+
+        assert_eq!(at(5), 10);
+        assert_eq!(at(10), 20);
+        assert_eq!(at(15), 13); // 20 -> 5, halfway is 12.5
+        assert_eq!(at(20), 5);
+        assert_eq!(at(40), 45);
+        assert_eq!(at(60), 85);
+        assert_eq!(StageScheduler::new(Vec::new()).vus_at(Duration::from_secs(1)), 0);
+    }
+
     #[test]
     fn test_create_load_test_stages() {
         let stages = create_load_test_stages(50, 10);
@@ -48,6 +48,37 @@ pub struct Stage {
     pub target_vus: u32,       // Target VUs at the end of this stage
 }
 
+/// One scenario in a weighted traffic mix; weights are relative shares of iterations
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WeightedScenario {
+    #[serde(rename = "scenarioId")]
+    pub scenario_id: String,
+    pub weight: u32,
+}
+
+/// Upper bound for a single mix weight, keeps the summed weights far from overflow
+pub const MAX_SCENARIO_WEIGHT: u32 = 1_000_000;
+
+/// Check a scenario mix: positive weights, no duplicate scenarios
+pub fn validate_scenario_mix(mix: &[WeightedScenario]) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for entry in mix {
+        if entry.scenario_id.trim().is_empty() {
+            return Err("Scenario mix entry is missing a scenario id".to_string());
+        }
+        if entry.weight == 0 || entry.weight > MAX_SCENARIO_WEIGHT {
+            return Err(format!(
+                "Weight for scenario {} must be between 1 and {}, got {}",
+                entry.scenario_id, MAX_SCENARIO_WEIGHT, entry.weight
+            ));
+        }
+        if !seen.insert(entry.scenario_id.as_str()) {
+            return Err(format!("Scenario {} appears more than once in the mix", entry.scenario_id));
+        }
+    }
+    Ok(())
+}
+
 /// Threshold definition for pass/fail criteria
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Threshold {
@@ -79,7 +110,17 @@ pub struct PerformanceTestConfig {
     pub duration_secs: Option<u64>,        // Fixed duration in seconds
     pub iterations: Option<u64>,           // Or number of iterations
     pub stages: Option<Vec<Stage>>,        // Ramping stages
+    #[serde(rename = "warmupSecs", default)]
+    pub warmup_secs: Option<u64>,          // Leading seconds excluded from metrics
     pub thresholds: Vec<Threshold>,
+    #[serde(rename = "http2PriorKnowledge", default)]
+    pub http2_prior_knowledge: bool,       // Force HTTP/2 without ALPN negotiation
+    #[serde(rename = "storeRawSamples", default)]
+    pub store_raw_samples: bool,           // Keep every RequestMetric for post-hoc analysis
+    #[serde(rename = "maxInFlight", default)]
+    pub max_in_flight: Option<u32>,        // Cap on concurrent requests; defaults from peak VUs
+    #[serde(rename = "scenarioMix", default)]
+    pub scenario_mix: Vec<WeightedScenario>, // Weighted scenarios picked per iteration; empty runs scenario_id only
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
@@ -99,7 +140,41 @@ pub struct CreatePerformanceTestInput {
     pub duration_secs: Option<u64>,
     pub iterations: Option<u64>,
     pub stages: Option<Vec<Stage>>,
+    #[serde(rename = "warmupSecs")]
+    pub warmup_secs: Option<u64>,
+    pub thresholds: Option<Vec<Threshold>>,
+    #[serde(rename = "http2PriorKnowledge")]
+    pub http2_prior_knowledge: Option<bool>,
+    #[serde(rename = "storeRawSamples")]
+    pub store_raw_samples: Option<bool>,
+    #[serde(rename = "maxInFlight")]
+    pub max_in_flight: Option<u32>,
+    #[serde(rename = "scenarioMix")]
+    pub scenario_mix: Option<Vec<WeightedScenario>>,
+}
+
+/// Input for updating a performance test config; omitted fields keep their stored value
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdatePerformanceTestInput {
+    pub name: Option<String>,
+    #[serde(rename = "testType")]
+    pub test_type: Option<String>,
+    pub vus: Option<u32>,
+    #[serde(rename = "durationSecs")]
+    pub duration_secs: Option<u64>,
+    pub iterations: Option<u64>,
+    pub stages: Option<Vec<Stage>>,
     pub thresholds: Option<Vec<Threshold>>,
+    #[serde(rename = "http2PriorKnowledge")]
+    pub http2_prior_knowledge: Option<bool>,
+    #[serde(rename = "warmupSecs")]
+    pub warmup_secs: Option<u64>,
+    #[serde(rename = "storeRawSamples")]
+    pub store_raw_samples: Option<bool>,
+    #[serde(rename = "maxInFlight")]
+    pub max_in_flight: Option<u32>,
+    #[serde(rename = "scenarioMix")]
+    pub scenario_mix: Option<Vec<WeightedScenario>>,
 }
 
 /// Metrics for a single HTTP request
@@ -119,6 +194,12 @@ pub struct RequestMetric {
     pub vu_id: u32,
     pub iteration: u64,
     pub timestamp: i64,
+    #[serde(rename = "queueWaitMs", default)]
+    pub queue_wait_ms: u64, // Time spent waiting for an in-flight slot, excluded from duration_ms
+    #[serde(rename = "serverTiming", default, skip_serializing_if = "HashMap::is_empty")]
+    pub server_timing: HashMap<String, f64>, // Server-Timing durations (ms) by metric name
+    #[serde(rename = "scenarioId", default)]
+    pub scenario_id: String, // Scenario whose iteration issued the request
 }
 
 /// Per-step aggregated metrics
@@ -146,6 +227,45 @@ pub struct StepMetrics {
     pub duration_p95: u64,
     #[serde(rename = "durationP99")]
     pub duration_p99: u64,
+    #[serde(rename = "serverTiming", default, skip_serializing_if = "HashMap::is_empty")]
+    pub server_timing: HashMap<String, f64>, // Average Server-Timing duration (ms) by metric name
+}
+
+/// Per-scenario aggregated metrics for mixed-scenario runs
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScenarioMetrics {
+    #[serde(rename = "scenarioName")]
+    pub scenario_name: String,
+    pub iterations: u64,
+    #[serde(rename = "totalRequests")]
+    pub total_requests: u64,
+    #[serde(rename = "failedRequests")]
+    pub failed_requests: u64,
+    #[serde(rename = "errorRate")]
+    pub error_rate: f64,
+    #[serde(rename = "durationAvg")]
+    pub duration_avg: f64,
+    #[serde(rename = "durationMed")]
+    pub duration_med: u64,
+    #[serde(rename = "durationP95")]
+    pub duration_p95: u64,
+    #[serde(rename = "durationP99")]
+    pub duration_p99: u64,
+}
+
+/// Summary of a custom metric pushed from script steps via `metrics.add(name, value)`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomMetricSummary {
+    pub name: String,
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub med: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
 }
 
 /// Aggregated metrics for the entire performance test
@@ -187,6 +307,43 @@ pub struct AggregatedMetrics {
     // Per-step metrics
     #[serde(rename = "stepMetrics")]
     pub step_metrics: HashMap<String, StepMetrics>,
+
+    // Per-scenario metrics, keyed by scenario id
+    #[serde(rename = "scenarioMetrics", default)]
+    pub scenario_metrics: HashMap<String, ScenarioMetrics>,
+
+    // Connection reuse. Estimates, not counts: reqwest does not report pool events, so these
+    // are derived from the shared client's in-flight peak (see `ConnectionTracker`)
+    #[serde(rename = "estimatedNewConnections", alias = "connectionsNew", default)]
+    pub estimated_new_connections: u64,
+    #[serde(rename = "estimatedReusedConnections", alias = "connectionsReused", default)]
+    pub estimated_reused_connections: u64,
+
+    // Request queuing behind the in-flight limit; high values mean the generator is the bottleneck
+    #[serde(rename = "maxInFlight", default)]
+    pub max_in_flight: u32,
+    #[serde(rename = "queueWaitAvg", default)]
+    pub queue_wait_avg: f64,
+    #[serde(rename = "queueWaitP95", default)]
+    pub queue_wait_p95: u64,
+    #[serde(rename = "queueWaitMax", default)]
+    pub queue_wait_max: u64,
+
+    // Custom metrics from script steps
+    #[serde(rename = "customMetrics", default)]
+    pub custom_metrics: HashMap<String, CustomMetricSummary>,
+}
+
+/// One fixed time window of a performance run, used for charting
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TimeSeriesPoint {
+    pub t: u64,                 // Seconds since run start
+    pub rps: f64,
+    pub p95: u64,
+    #[serde(rename = "errorRate")]
+    pub error_rate: f64,
+    #[serde(rename = "activeVus")]
+    pub active_vus: u32,
 }
 
 /// Performance run status
@@ -253,6 +410,21 @@ pub struct PerformanceTestRun {
     pub threshold_results: Vec<ThresholdResult>,
     #[serde(rename = "errorMessage")]
     pub error_message: Option<String>,
+    #[serde(rename = "timeSeries", default)]
+    pub time_series: Vec<TimeSeriesPoint>,
+    #[serde(skip)]
+    pub raw_samples: Option<Vec<RequestMetric>>, // Handed to storage when store_raw_samples is on
+}
+
+/// One page of raw request samples for a performance run
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RawSamplePage {
+    #[serde(rename = "runId")]
+    pub run_id: String,
+    pub samples: Vec<RequestMetric>,
+    pub total: u64,
+    pub offset: usize,
+    pub limit: usize,
 }
 
 // ============================================================================
@@ -316,10 +488,13 @@ pub struct PerfStageChangedEvent {
     pub run_id: String,
     #[serde(rename = "stageIndex")]
     pub stage_index: usize,
+    #[serde(rename = "fromVus", default)]
+    pub from_vus: u32, // VUs the stage ramps from (previous stage's target)
     #[serde(rename = "targetVus")]
     pub target_vus: u32,
     #[serde(rename = "durationSecs")]
     pub duration_secs: u64,
+    pub phase: String,          // "warmup" | "measurement"
 }
 
 /// Event emitted when performance test completes
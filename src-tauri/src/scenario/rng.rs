@@ -0,0 +1,96 @@
+/// Seeds are kept below 2^53 so they survive a round trip through JavaScript numbers
+const MAX_SEED: u64 = (1 << 53) - 1;
+
+const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// SeededRng - Deterministic SplitMix64 generator used for reproducible scenario runs
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Pick a fresh seed for runs that did not request one
+    pub fn generate_seed() -> u64 {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let entropy = uuid::Uuid::new_v4().as_u128() as u64;
+        (nanos ^ entropy) & MAX_SEED
+    }
+
+    /// Independent generator derived from `seed` for one purpose (e.g. fault injection), so
+    /// drawing from it leaves the sequence of `SeededRng::new(seed)` untouched
+    pub fn derived(seed: u64, stream: u64) -> Self {
+        Self::new(Self::new(seed ^ stream).next_u64())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Random integer in the inclusive range [min, max]
+    pub fn next_range(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+        let span = (max - min) as u64 + 1;
+        min + (self.next_u64() % span) as i64
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Random alphanumeric string of the given length
+    pub fn next_string(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| ALPHANUMERIC[(self.next_u64() % ALPHANUMERIC.len() as u64) as usize] as char)
+            .collect()
+    }
+
+    /// Random RFC 4122 version 4 UUID
+    pub fn next_uuid(&mut self) -> String {
+        let bytes = ((self.next_u64() as u128) << 64 | self.next_u64() as u128).to_be_bytes();
+        uuid::Builder::from_random_bytes(bytes).into_uuid().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        assert_eq!(a.next_range(0, 1000), b.next_range(0, 1000));
+        assert_eq!(a.next_string(10), b.next_string(10));
+        assert_eq!(a.next_uuid(), b.next_uuid());
+
+        let mut c = SeededRng::new(43);
+        assert_ne!(SeededRng::new(42).next_u64(), c.next_u64());
+    }
+
+    #[test]
+    fn test_derived_stream_differs_from_seed_stream() {
+        let mut derived = SeededRng::derived(42, 1);
+        assert_eq!(derived.next_u64(), SeededRng::derived(42, 1).next_u64());
+        assert_ne!(SeededRng::derived(42, 1).next_u64(), SeededRng::new(42).next_u64());
+        assert_ne!(SeededRng::derived(42, 1).next_u64(), SeededRng::derived(42, 2).next_u64());
+    }
+
+    #[test]
+    fn test_generated_seed_fits_js_number() {
+        assert!(SeededRng::generate_seed() <= MAX_SEED);
+    }
+}
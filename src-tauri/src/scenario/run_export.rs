@@ -0,0 +1,134 @@
+//! CSV export of scenario run results for spreadsheet analysis
+//!
+//! One row per step result; steps driven by CSV data produce one row per data row,
+//! numbered in the `iteration` column.
+
+use std::collections::HashMap;
+
+use super::types::{TestScenarioRun, TestStepResult};
+
+const CSV_HEADER: &[&str] = &[
+    "step", "iteration", "method", "url", "httpStatus", "durationMs", "result", "failedAssertions", "error",
+];
+
+/// Render a run's step results as CSV with a header row
+pub fn run_to_csv(run: &TestScenarioRun) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_HEADER)
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    for (result, iteration) in run.results.iter().zip(iterations(&run.results)) {
+        writer.write_record(csv_row(result, iteration))
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+
+    let bytes = writer.into_inner()
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+/// 1-based iteration for steps that appear more than once in the results
+fn iterations(results: &[TestStepResult]) -> Vec<Option<usize>> {
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for result in results {
+        *totals.entry(result.step_id.as_str()).or_default() += 1;
+    }
+
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    results
+        .iter()
+        .map(|result| {
+            let count = seen.entry(result.step_id.as_str()).or_default();
+            *count += 1;
+            (totals[result.step_id.as_str()] > 1).then_some(*count)
+        })
+        .collect()
+}
+
+fn csv_row(result: &TestStepResult, iteration: Option<usize>) -> Vec<String> {
+    let failed_assertions = result.assertions.as_ref()
+        .map(|assertions| assertions.iter().filter(|a| a.failed()).count())
+        .unwrap_or(0);
+
+    vec![
+        result.name.clone(),
+        iteration.map(|i| i.to_string()).unwrap_or_default(),
+        result.request.as_ref().map(|r| r.method.clone()).unwrap_or_default(),
+        result.request.as_ref().map(|r| r.url.clone()).unwrap_or_default(),
+        result.response.as_ref().map(|r| r.status.to_string()).unwrap_or_default(),
+        result.duration_ms.map(|d| d.to_string()).unwrap_or_default(),
+        result.status.as_str().to_string(),
+        failed_assertions.to_string(),
+        result.error.clone().unwrap_or_default(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::types::{RunTrigger, ScenarioRunStatus, StepRequest, StepResultStatus, TestStepType};
+
+    fn step(id: &str, name: &str, status: StepResultStatus, url: &str, error: Option<&str>) -> TestStepResult {
+        TestStepResult {
+            step_id: id.to_string(),
+            name: name.to_string(),
+            step_type: TestStepType::Request,
+            status,
+            duration_ms: Some(12),
+            request: Some(StepRequest {
+                method: "GET".to_string(),
+                url: url.to_string(),
+                headers: HashMap::new(),
+                body: None,
+                fault: None,
+            }),
+            response: None,
+            assertions: None,
+            error: error.map(|e| e.to_string()),
+            extracted_variables: None,
+            group: None,
+            assertion_summary: None,
+        }
+    }
+
+    #[test]
+    fn test_run_to_csv_escapes_and_numbers_iterations() {
+        let run = TestScenarioRun {
+            id: "run-1".to_string(),
+            scenario_id: "s1".to_string(),
+            status: ScenarioRunStatus::Failed,
+            total_steps: 3,
+            passed_steps: 2,
+            failed_steps: 1,
+            skipped_steps: 0,
+            duration_ms: Some(36),
+            started_at: 0,
+            completed_at: None,
+            error_message: None,
+            results: vec![
+                step("login", "Login", StepResultStatus::Passed, "http://api/login", None),
+                step("get", "Get user", StepResultStatus::Passed, "http://api/users?ids=1,2", None),
+                step("get", "Get user", StepResultStatus::Failed, "http://api/users?ids=3", Some("said \"no\"\nthen quit")),
+            ],
+            variables: HashMap::new(),
+            seed: None,
+            groups: Vec::new(),
+            snapshot_updates: Vec::new(),
+            logs: Vec::new(),
+            trigger: RunTrigger::Manual,
+        };
+
+        let csv = run_to_csv(&run).unwrap();
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+
+        assert!(csv.starts_with("step,iteration,method,url,httpStatus,durationMs,result,failedAssertions,error\n"));
+        assert_eq!(rows.len(), 3);
+        assert_eq!(&rows[0][1], "");
+        assert_eq!(&rows[1][1], "1");
+        assert_eq!(&rows[1][3], "http://api/users?ids=1,2");
+        assert_eq!(&rows[2][1], "2");
+        assert_eq!(&rows[2][6], "failed");
+        assert_eq!(&rows[2][8], "said \"no\"\nthen quit");
+    }
+}
@@ -0,0 +1,92 @@
+//! Per-run log capture for scenario runs
+//!
+//! The executor mirrors its run-relevant `log` output (requests, responses, variable
+//! resolution, warnings) into a `RunLog`, which is stored with the run so any historical run
+//! carries its own trace regardless of the global `RUST_LOG` level.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Entries kept per run; later entries are counted but not stored
+pub const MAX_RUN_LOG_ENTRIES: usize = 5000;
+
+/// One captured log line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunLogEntry {
+    pub timestamp: i64, // Unix milliseconds
+    pub level: String,  // "error", "warn", "info", "debug"
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+struct RunLogState {
+    entries: Vec<RunLogEntry>,
+    dropped: usize,
+}
+
+/// Shared, capped log buffer for one run; clones append to the same buffer
+#[derive(Debug, Clone, Default)]
+pub struct RunLog {
+    state: Arc<Mutex<RunLogState>>,
+    max_entries: usize,
+}
+
+impl RunLog {
+    pub fn new() -> Self {
+        Self::with_max_entries(MAX_RUN_LOG_ENTRIES)
+    }
+
+    pub fn with_max_entries(max_entries: usize) -> Self {
+        Self {
+            state: Arc::default(),
+            max_entries,
+        }
+    }
+
+    pub fn push(&self, level: log::Level, message: impl Into<String>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.entries.len() >= self.max_entries {
+            state.dropped += 1;
+            return;
+        }
+        state.entries.push(RunLogEntry {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            level: level.as_str().to_lowercase(),
+            message: message.into(),
+        });
+    }
+
+    /// Captured entries, ending with a note of how many were dropped past the cap
+    pub fn entries(&self) -> Vec<RunLogEntry> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries = state.entries.clone();
+        if state.dropped > 0 {
+            entries.push(RunLogEntry {
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                level: "warn".to_string(),
+                message: format!("{} more log entries dropped (limit {})", state.dropped, self.max_entries),
+            });
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_log_caps_entries() {
+        let run_log = RunLog::with_max_entries(2);
+        let shared = run_log.clone();
+        run_log.push(log::Level::Info, "Request: GET /users");
+        shared.push(log::Level::Warn, "Variable token not found in context");
+        shared.push(log::Level::Info, "dropped");
+
+        let entries = run_log.entries();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].level, "info");
+        assert_eq!(entries[1].message, "Variable token not found in context");
+        assert_eq!(entries[2].message, "1 more log entries dropped (limit 2)");
+    }
+}
@@ -0,0 +1,224 @@
+//! Repeating scenario runs for local synthetic monitoring
+//!
+//! A schedule runs one scenario every `interval_minutes`. Definitions are stored in the
+//! database and resumed at startup; the background tasks themselves are tracked here so
+//! they can be stopped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::types::{ScenarioRunStatus, StepResultStatus, TestScenarioRun};
+
+/// Runs considered by the summary when no limit is given
+pub const DEFAULT_SUMMARY_WINDOW: usize = 50;
+
+/// A repeating run of one scenario
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScenarioSchedule {
+    #[serde(rename = "scenarioId")]
+    pub scenario_id: String,
+    #[serde(rename = "intervalMinutes")]
+    pub interval_minutes: u32,
+    pub enabled: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+    #[serde(default)]
+    pub active: bool, // A background task is running for this schedule
+}
+
+/// Pass rate and uptime over a scenario's recent runs, newest first
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ScheduleSummary {
+    #[serde(rename = "totalRuns")]
+    pub total_runs: u32,
+    #[serde(rename = "passedRuns")]
+    pub passed_runs: u32,
+    #[serde(rename = "passRate")]
+    pub pass_rate: f64, // Percentage of runs with every step passing
+    pub uptime: f64, // Percentage of runs where every request got a response
+    #[serde(rename = "avgDurationMs")]
+    pub avg_duration_ms: Option<u64>,
+    #[serde(rename = "consecutiveFailures")]
+    pub consecutive_failures: u32,
+    #[serde(rename = "lastRunAt")]
+    pub last_run_at: Option<i64>,
+    #[serde(rename = "lastStatus")]
+    pub last_status: Option<ScenarioRunStatus>,
+}
+
+/// Emitted after each scheduled run is saved
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduledRunEvent {
+    #[serde(rename = "scenarioId")]
+    pub scenario_id: String,
+    #[serde(rename = "runId")]
+    pub run_id: Option<String>,
+    pub status: Option<ScenarioRunStatus>,
+    pub error: Option<String>,
+}
+
+/// Validate a schedule interval
+pub fn validate_interval(interval_minutes: u32) -> Result<(), String> {
+    if interval_minutes == 0 {
+        return Err("Invalid interval: must be at least 1 minute".to_string());
+    }
+    Ok(())
+}
+
+/// Summarize runs ordered newest first, as returned by `get_test_scenario_runs`
+pub fn summarize_runs(runs: &[TestScenarioRun]) -> ScheduleSummary {
+    let finished: Vec<&TestScenarioRun> = runs
+        .iter()
+        .filter(|run| !matches!(run.status, ScenarioRunStatus::Pending | ScenarioRunStatus::Running))
+        .collect();
+    if finished.is_empty() {
+        return ScheduleSummary::default();
+    }
+
+    let total = finished.len() as u32;
+    let passed = finished.iter().filter(|run| run.status == ScenarioRunStatus::Passed).count() as u32;
+    let reachable = finished.iter().filter(|run| is_reachable(run)).count() as u32;
+    let durations: Vec<u64> = finished.iter().filter_map(|run| run.duration_ms).collect();
+
+    ScheduleSummary {
+        total_runs: total,
+        passed_runs: passed,
+        pass_rate: percentage(passed, total),
+        uptime: percentage(reachable, total),
+        avg_duration_ms: (!durations.is_empty())
+            .then(|| durations.iter().sum::<u64>() / durations.len() as u64),
+        consecutive_failures: finished
+            .iter()
+            .take_while(|run| run.status != ScenarioRunStatus::Passed)
+            .count() as u32,
+        last_run_at: Some(finished[0].started_at),
+        last_status: Some(finished[0].status.clone()),
+    }
+}
+
+/// A run counts as "up" when it didn't error out and no request failed to get a response
+fn is_reachable(run: &TestScenarioRun) -> bool {
+    run.status != ScenarioRunStatus::Error
+        && !run.results.iter().any(|r| r.status == StepResultStatus::Error && r.response.is_none())
+}
+
+fn percentage(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64 / total as f64 * 1000.0).round() / 10.0
+    }
+}
+
+/// Background tasks of active schedules, keyed by scenario id
+fn active_schedules() -> &'static Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>> {
+    static ACTIVE: OnceLock<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Track the task for a schedule, aborting any task it replaces
+pub fn register_task(scenario_id: &str, handle: tauri::async_runtime::JoinHandle<()>) {
+    let previous = active_schedules()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(scenario_id.to_string(), handle);
+    if let Some(previous) = previous {
+        previous.abort();
+    }
+}
+
+/// Abort a schedule's task; false if none was running
+pub fn stop_task(scenario_id: &str) -> bool {
+    let handle = active_schedules()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(scenario_id);
+    match handle {
+        Some(handle) => {
+            handle.abort();
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn is_task_active(scenario_id: &str) -> bool {
+    active_schedules()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(scenario_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::types::{RunTrigger, TestStepResult, TestStepType};
+
+    fn run(status: ScenarioRunStatus, started_at: i64, duration_ms: u64, unreachable: bool) -> TestScenarioRun {
+        let results = if unreachable {
+            vec![TestStepResult {
+                step_id: "s1".to_string(),
+                name: "Ping".to_string(),
+                step_type: TestStepType::Request,
+                status: StepResultStatus::Error,
+                duration_ms: Some(duration_ms),
+                request: None,
+                response: None,
+                assertions: None,
+                error: Some("Request failed: connection refused".to_string()),
+                extracted_variables: None,
+                group: None,
+                assertion_summary: None,
+            }]
+        } else {
+            Vec::new()
+        };
+        TestScenarioRun {
+            id: format!("run-{}", started_at),
+            scenario_id: "scenario-1".to_string(),
+            status,
+            total_steps: 1,
+            passed_steps: 0,
+            failed_steps: 0,
+            skipped_steps: 0,
+            duration_ms: Some(duration_ms),
+            started_at,
+            completed_at: Some(started_at + 1),
+            error_message: None,
+            results,
+            variables: HashMap::new(),
+            seed: None,
+            groups: Vec::new(),
+            snapshot_updates: Vec::new(),
+            logs: Vec::new(),
+            trigger: RunTrigger::Manual,
+        }
+    }
+
+    #[test]
+    fn test_summarize_runs() {
+        let runs = vec![
+            run(ScenarioRunStatus::Running, 500, 0, false),
+            run(ScenarioRunStatus::Failed, 400, 300, true),
+            run(ScenarioRunStatus::Failed, 300, 100, false),
+            run(ScenarioRunStatus::Passed, 200, 100, false),
+            run(ScenarioRunStatus::Passed, 100, 100, false),
+        ];
+        let summary = summarize_runs(&runs);
+
+        assert_eq!(summary.total_runs, 4);
+        assert_eq!(summary.passed_runs, 2);
+        assert_eq!(summary.pass_rate, 50.0);
+        assert_eq!(summary.uptime, 75.0);
+        assert_eq!(summary.avg_duration_ms, Some(150));
+        assert_eq!(summary.consecutive_failures, 2);
+        assert_eq!(summary.last_run_at, Some(400));
+        assert_eq!(summary.last_status, Some(ScenarioRunStatus::Failed));
+
+        assert_eq!(summarize_runs(&[]), ScheduleSummary::default());
+        assert!(validate_interval(0).is_err());
+    }
+}
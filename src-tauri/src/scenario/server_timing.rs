@@ -0,0 +1,112 @@
+//! Parsing of the `Server-Timing` response header
+//!
+//! `Server-Timing: cache;desc="Cache Read";dur=23.2, db;dur=53, miss` lists server-side
+//! phases with optional durations in milliseconds, so client latency can be broken down.
+
+use super::types::ServerTimingMetric;
+
+/// Parse a `Server-Timing` header value; malformed entries are skipped
+pub fn parse_server_timing(header: &str) -> Vec<ServerTimingMetric> {
+    split_outside_quotes(header, ',')
+        .into_iter()
+        .filter_map(|entry| {
+            let mut parts = split_outside_quotes(entry, ';').into_iter();
+            let name = parts.next()?.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+
+            let mut metric = ServerTimingMetric {
+                name: name.to_string(),
+                dur: None,
+                desc: None,
+            };
+            for param in parts {
+                let (key, value) = match param.split_once('=') {
+                    Some((key, value)) => (key.trim(), unquote(value.trim())),
+                    None => continue,
+                };
+                // The first occurrence of a parameter wins, per the spec
+                if key.eq_ignore_ascii_case("dur") && metric.dur.is_none() {
+                    metric.dur = value.parse::<f64>().ok();
+                } else if key.eq_ignore_ascii_case("desc") && metric.desc.is_none() {
+                    metric.desc = Some(value);
+                }
+            }
+            Some(metric)
+        })
+        .collect()
+}
+
+/// Look up a metric for an assertion path like `db` or `db;dur` (duration), or `db;desc`
+pub fn server_timing_value(metrics: &[ServerTimingMetric], path: &str) -> serde_json::Value {
+    let (name, param) = match path.split_once(';') {
+        Some((name, param)) => (name.trim(), param.trim()),
+        None => (path.trim(), "dur"),
+    };
+
+    let metric = match metrics.iter().find(|m| m.name.eq_ignore_ascii_case(name)) {
+        Some(metric) => metric,
+        None => return serde_json::Value::Null,
+    };
+    if param.eq_ignore_ascii_case("desc") {
+        metric.desc.clone().map(serde_json::Value::String).unwrap_or(serde_json::Value::Null)
+    } else {
+        metric.dur
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null)
+    }
+}
+
+fn split_outside_quotes(input: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_timing() {
+        let metrics = parse_server_timing(r#"miss, db;dur=53, app;dur=47.2;desc="App, logic", cache;desc=hit;dur=1;dur=9"#);
+
+        assert_eq!(metrics.len(), 4);
+        assert_eq!(metrics[0], ServerTimingMetric { name: "miss".to_string(), dur: None, desc: None });
+        assert_eq!(metrics[1].dur, Some(53.0));
+        assert_eq!(metrics[2].desc.as_deref(), Some("App, logic"));
+        assert_eq!(metrics[3].dur, Some(1.0));
+        assert_eq!(metrics[3].desc.as_deref(), Some("hit"));
+
+        assert_eq!(server_timing_value(&metrics, "db"), serde_json::json!(53.0));
+        assert_eq!(server_timing_value(&metrics, "DB;dur"), serde_json::json!(53.0));
+        assert_eq!(server_timing_value(&metrics, "app;desc"), serde_json::json!("App, logic"));
+        assert_eq!(server_timing_value(&metrics, "miss"), serde_json::Value::Null);
+        assert_eq!(server_timing_value(&metrics, "render"), serde_json::Value::Null);
+        assert!(parse_server_timing("").is_empty());
+    }
+}
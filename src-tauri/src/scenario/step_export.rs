@@ -0,0 +1,94 @@
+//! "Copy as" rendering of a single resolved request step
+//!
+//! The request comes from a dry-run resolution, so URLs, headers and bodies already have
+//! variables substituted and the output can be pasted into a terminal or bug report as-is.
+
+use super::types::StepRequest;
+use crate::http_client;
+
+pub const STEP_EXPORT_FORMATS: &[&str] = &["curl", "http", "json"];
+
+/// Render a resolved step request as `curl`, a `.http` entry, or JSON
+pub fn render_step_request(name: &str, request: &StepRequest, format: &str) -> Result<String, String> {
+    match format.to_lowercase().as_str() {
+        "curl" => Ok(http_client::generate_curl_with_headers(
+            &request.url,
+            &request.method,
+            &sorted_headers(request),
+            request.body.as_ref(),
+        )),
+        "http" => Ok(render_http(name, request)),
+        "json" => serde_json::to_string_pretty(request)
+            .map_err(|e| format!("Serialization error: {}", e)),
+        other => Err(format!(
+            "Unsupported export format: {} (expected one of {})",
+            other,
+            STEP_EXPORT_FORMATS.join(", ")
+        )),
+    }
+}
+
+fn sorted_headers(request: &StepRequest) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = request.headers.clone().into_iter().collect();
+    headers.sort();
+    headers
+}
+
+fn render_http(name: &str, request: &StepRequest) -> String {
+    let headers = sorted_headers(request);
+    let mut out = format!("### {}\n{} {}\n", name, request.method, request.url);
+    for (name, value) in &headers {
+        out.push_str(&format!("{}: {}\n", name, value));
+    }
+
+    if let Some(body) = &request.body {
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")) {
+            out.push_str("Content-Type: application/json\n");
+        }
+        out.push('\n');
+        out.push_str(&serde_json::to_string_pretty(body).unwrap_or_else(|_| body.to_string()));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn request() -> StepRequest {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), "Bearer abc123".to_string());
+        StepRequest {
+            method: "POST".to_string(),
+            url: "http://localhost:3000/orders?dry=1".to_string(),
+            headers,
+            body: Some(serde_json::json!({ "note": "it's ready" })),
+            fault: None,
+        }
+    }
+
+    #[test]
+    fn test_render_step_request_formats() {
+        let curl = render_step_request("Create order", &request(), "curl").unwrap();
+        assert_eq!(
+            curl,
+            "curl -X POST 'http://localhost:3000/orders?dry=1' -H 'Authorization: Bearer abc123' \
+             -H 'Content-Type: application/json' -d '{\"note\":\"it'\\''s ready\"}'"
+        );
+        let parsed = http_client::parse_curl(&curl).unwrap();
+        assert_eq!(parsed.url, "http://localhost:3000/orders?dry=1");
+        assert_eq!(parsed.body.as_deref(), Some("{\"note\":\"it's ready\"}"));
+
+        let http = render_step_request("Create order", &request(), "http").unwrap();
+        assert!(http.starts_with("### Create order\nPOST http://localhost:3000/orders?dry=1\nAuthorization: Bearer abc123\n"));
+        assert!(http.contains("Content-Type: application/json\n\n{\n  \"note\": \"it's ready\"\n}\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_step_request("Create order", &request(), "JSON").unwrap()).unwrap();
+        assert_eq!(json["headers"]["Authorization"], "Bearer abc123");
+
+        assert!(render_step_request("Create order", &request(), "postman").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use super::run_log::RunLogEntry;
 
 /// Test Scenario - A collection of test steps that can be executed sequentially
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -15,12 +16,134 @@ pub struct TestScenario {
     pub pre_script: Option<String>,
     #[serde(rename = "postScript")]
     pub post_script: Option<String>,
+    #[serde(default)]
+    pub chaos: Option<ChaosConfig>, // Fault injection for resilience runs, off unless enabled
+    #[serde(rename = "sessionLogin", default)]
+    pub session_login: Option<SessionLoginConfig>, // Cookie login performed before the steps
+    #[serde(default)]
+    pub environments: HashMap<String, ScenarioEnvironment>, // Named overrides picked at run time
     #[serde(rename = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "updatedAt")]
     pub updated_at: i64,
 }
 
+impl TestScenario {
+    /// The scenario and base URL to run in `environment`: its variables overlay the scenario's
+    /// and its base URL, when set, replaces `base_url`. `None` leaves both unchanged.
+    pub fn resolve_environment(
+        &self,
+        environment: Option<&str>,
+        base_url: Option<String>,
+    ) -> Result<(TestScenario, Option<String>), String> {
+        let name = match environment.map(str::trim).filter(|name| !name.is_empty()) {
+            Some(name) => name,
+            None => return Ok((self.clone(), base_url)),
+        };
+        let env = self.environments.get(name).ok_or_else(|| {
+            let mut known: Vec<&str> = self.environments.keys().map(String::as_str).collect();
+            known.sort();
+            format!("Unknown environment '{}' for scenario '{}' (defined: {})", name, self.name,
+                if known.is_empty() { "none".to_string() } else { known.join(", ") })
+        })?;
+
+        let mut scenario = self.clone();
+        let mut variables = scenario.variables.as_object().cloned().unwrap_or_default();
+        for (key, value) in &env.variables {
+            variables.insert(key.clone(), value.clone());
+        }
+        scenario.variables = serde_json::Value::Object(variables);
+        Ok((scenario, env.base_url.clone().or(base_url)))
+    }
+}
+
+/// Scenario Environment - Base URL and variables that apply when a run selects this environment
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ScenarioEnvironment {
+    #[serde(rename = "baseUrl", default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, serde_json::Value>,
+}
+
+/// Session Login - Form login run before a scenario's steps; the session cookie it sets
+/// stays in the run's cookie jar and is sent with every later request
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionLoginConfig {
+    pub url: String,
+    #[serde(default = "default_login_method")]
+    pub method: String,
+    #[serde(rename = "usernameField", default = "default_username_field")]
+    pub username_field: String,
+    #[serde(rename = "passwordField", default = "default_password_field")]
+    pub password_field: String,
+    #[serde(default = "default_login_username")]
+    pub username: String, // Supports {{variables}} from the scenario/project settings
+    #[serde(default = "default_login_password")]
+    pub password: String,
+    #[serde(default)]
+    pub json: bool, // Send credentials as JSON instead of a urlencoded form
+    #[serde(rename = "expectedCookie")]
+    pub expected_cookie: String, // Login fails unless the response sets this cookie
+}
+
+fn default_login_method() -> String {
+    "POST".to_string()
+}
+
+fn default_username_field() -> String {
+    "username".to_string()
+}
+
+fn default_password_field() -> String {
+    "password".to_string()
+}
+
+fn default_login_username() -> String {
+    "{{username}}".to_string()
+}
+
+fn default_login_password() -> String {
+    "{{password}}".to_string()
+}
+
+/// Chaos Config - Deliberately corrupt a share of request steps to check server behavior
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ChaosConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub percentage: u8, // Share of request steps to fault, 0-100
+    #[serde(default)]
+    pub faults: Vec<ChaosFaultKind>, // Empty means every kind
+    #[serde(rename = "latencyMs", default = "default_chaos_latency_ms")]
+    pub latency_ms: u64,
+    #[serde(rename = "dropHeader", default)]
+    pub drop_header: Option<String>, // Header to drop, otherwise the first one sent
+}
+
+fn default_chaos_latency_ms() -> u64 {
+    2000
+}
+
+/// Fault kinds for chaos runs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ChaosFaultKind {
+    #[serde(rename = "dropHeader")]
+    DropHeader,
+    #[serde(rename = "latency")]
+    Latency,
+    #[serde(rename = "truncateBody")]
+    TruncateBody,
+}
+
+/// Fault applied to a single request during a chaos run
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InjectedFault {
+    pub kind: ChaosFaultKind,
+    pub detail: String, // e.g. "dropped header Authorization"
+}
+
 /// Step types for test scenarios
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum TestStepType {
@@ -34,6 +157,8 @@ pub enum TestStepType {
     Delay,
     #[serde(rename = "script")]
     Script,
+    #[serde(rename = "grpc")]
+    Grpc,
 }
 
 impl TestStepType {
@@ -44,6 +169,7 @@ impl TestStepType {
             TestStepType::Loop => "loop",
             TestStepType::Delay => "delay",
             TestStepType::Script => "script",
+            TestStepType::Grpc => "grpc",
         }
     }
 
@@ -54,6 +180,7 @@ impl TestStepType {
             "loop" => TestStepType::Loop,
             "delay" => TestStepType::Delay,
             "script" => TestStepType::Script,
+            "grpc" => TestStepType::Grpc,
             _ => TestStepType::Request,
         }
     }
@@ -72,6 +199,102 @@ pub struct TestScenarioStep {
     pub name: String,
     pub config: serde_json::Value, // Step-specific configuration
     pub enabled: bool,
+    #[serde(rename = "enabledIf", default)]
+    pub enabled_if: Option<String>, // Runtime guard, e.g. "{{createdId}} != null"
+    #[serde(default)]
+    pub group: Option<String>, // Reporting section, e.g. "Setup" or "Teardown"
+}
+
+/// Reject step names used more than once in a scenario (condition and loop steps refer to steps by name)
+pub fn validate_unique_step_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for name in names {
+        let name = name.trim();
+        match counts.iter_mut().find(|(seen, _)| *seen == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+
+    let duplicates: Vec<String> = counts
+        .iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(name, count)| format!("'{}' ({} times)", name, count))
+        .collect();
+    if duplicates.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Duplicate step names in scenario: {}", duplicates.join(", ")))
+    }
+}
+
+/// Reject condition and loop steps whose step references (by id or name) form a cycle,
+/// e.g. a loop repeating itself or two conditions branching to each other
+pub fn validate_step_references(steps: &[TestScenarioStep]) -> Result<(), String> {
+    let targets: Vec<Vec<usize>> = steps
+        .iter()
+        .map(|step| {
+            let refs: Vec<String> = match step.step_type {
+                TestStepType::Condition => serde_json::from_value::<ConditionStepConfig>(step.config.clone())
+                    .map(|c| c.true_steps.into_iter().chain(c.false_steps).collect())
+                    .unwrap_or_default(),
+                TestStepType::Loop => serde_json::from_value::<LoopStepConfig>(step.config.clone())
+                    .map(|c| c.steps)
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            };
+            refs.iter()
+                .filter_map(|r| steps.iter().position(|s| s.id == *r || s.name.trim() == r.trim()))
+                .collect()
+        })
+        .collect();
+
+    let mut state = vec![VisitState::Unvisited; steps.len()];
+    let mut path = Vec::new();
+    for start in 0..steps.len() {
+        if let Some(cycle) = find_reference_cycle(start, &targets, &mut state, &mut path) {
+            let names: Vec<&str> = cycle.iter().map(|&i| steps[i].name.as_str()).collect();
+            return Err(format!("Step reference cycle: {}", names.join(" -> ")));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum VisitState {
+    Unvisited,
+    OnPath,
+    Done,
+}
+
+/// Depth-first search returning the first cycle reachable from `node`, closed on its first step
+fn find_reference_cycle(
+    node: usize,
+    targets: &[Vec<usize>],
+    state: &mut [VisitState],
+    path: &mut Vec<usize>,
+) -> Option<Vec<usize>> {
+    match state[node] {
+        VisitState::Done => return None,
+        VisitState::OnPath => {
+            let start = path.iter().position(|&n| n == node).unwrap_or(0);
+            let mut cycle = path[start..].to_vec();
+            cycle.push(node);
+            return Some(cycle);
+        }
+        VisitState::Unvisited => {}
+    }
+
+    state[node] = VisitState::OnPath;
+    path.push(node);
+    for &next in &targets[node] {
+        if let Some(cycle) = find_reference_cycle(next, targets, state, path) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    state[node] = VisitState::Done;
+    None
 }
 
 /// Request Step Configuration
@@ -84,11 +307,21 @@ pub struct RequestStepConfig {
     pub headers: Option<HashMap<String, String>>,
     pub params: Option<serde_json::Value>,
     pub body: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<HashMap<String, String>>, // Query string parameters, URL-encoded when sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>, // Project service whose base URL resolves a relative url
+    #[serde(rename = "bodyFile", default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>, // JSON file loaded at execution time, exclusive with body
     #[serde(rename = "extractVariables")]
     pub extract_variables: Option<Vec<VariableExtractor>>,
     pub assertions: Option<Vec<Assertion>>,
     #[serde(rename = "withItemsFromCsv")]
     pub with_items_from_csv: Option<CsvConfig>,
+    #[serde(rename = "followRedirects", default, skip_serializing_if = "Option::is_none")]
+    pub follow_redirects: Option<bool>, // Defaults to following redirects
+    #[serde(rename = "maxRedirects", default, skip_serializing_if = "Option::is_none")]
+    pub max_redirects: Option<usize>,
 }
 
 /// Condition Step Configuration
@@ -127,6 +360,24 @@ pub struct ScriptStepConfig {
     pub code: String, // JavaScript code
 }
 
+/// gRPC Step Configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrpcStepConfig {
+    pub endpoint: String, // e.g. "http://localhost:50051"
+    #[serde(rename = "protoFile")]
+    pub proto_file: Option<String>,
+    #[serde(rename = "descriptorSet")]
+    pub descriptor_set: Option<String>, // Compiled FileDescriptorSet (.pb)
+    pub service: String, // Fully-qualified, e.g. "orders.v1.OrderService"
+    pub method: String,
+    #[serde(default)]
+    pub message: serde_json::Value, // Request message as JSON
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(rename = "extractVariables")]
+    pub extract_variables: Option<Vec<VariableExtractor>>,
+    pub assertions: Option<Vec<Assertion>>,
+}
+
 /// Variable Extractor - Extract data from response
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VariableExtractor {
@@ -140,14 +391,195 @@ pub struct VariableExtractor {
 /// Assertion - Validate response
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Assertion {
+    #[serde(default)]
     pub name: String,
-    pub source: String,   // "status", "body", "header", "duration"
+    #[serde(default)]
+    pub source: String,   // "status", "body", "header", "duration", "finalUrl", "serverTiming", "variable"
     pub path: Option<String>, // JSONPath for body, header name for header
-    pub operator: String, // "equals", "contains", "matches", "greaterThan", "lessThan", "notEquals", "exists"
+    #[serde(default)]
+    pub operator: String, // "equals", "contains", "matches", "greaterThan", "lessThan", "between", "notEquals", "exists", "type"
+    #[serde(default)]
     pub expected: serde_json::Value,
     pub actual: Option<serde_json::Value>,
     pub passed: Option<bool>,
     pub error: Option<String>,
+    /// Name of an assertion template to expand in place of this assertion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Values for {{param}} placeholders in the referenced template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<HashMap<String, serde_json::Value>>,
+    /// Paths masked before a `snapshot` comparison, e.g. `$.data.createdAt` or `items[*].id`
+    #[serde(rename = "ignorePaths", default, skip_serializing_if = "Option::is_none")]
+    pub ignore_paths: Option<Vec<String>>,
+    /// Only evaluate when the response status matches, e.g. `2xx`, `200-299` or `200,201`
+    #[serde(rename = "onlyIfStatus", default, skip_serializing_if = "Option::is_none")]
+    pub only_if_status: Option<String>,
+    /// "error" (default) or "warn"; failed `warn` assertions are reported but don't fail the step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// Set when `only_if_status` didn't match; skipped assertions neither pass nor fail the step
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+    /// "and" or "or" makes this a group over the nested `assertions`; source, operator and
+    /// expected are then unused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logic: Option<String>,
+    /// Members of a group; after a run each carries its own outcome
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assertions: Option<Vec<Assertion>>,
+}
+
+impl Assertion {
+    pub fn is_group(&self) -> bool {
+        self.logic.is_some()
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.severity.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("warn") || s.eq_ignore_ascii_case("warning"))
+    }
+
+    /// Evaluated and not passed; skipped assertions never count as failures
+    pub fn failed(&self) -> bool {
+        !self.skipped && self.passed != Some(true)
+    }
+}
+
+/// Assertion Template - A named, reusable set of assertions for a project
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssertionTemplate {
+    pub id: String,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub assertions: Vec<Assertion>,
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>, // Default values for {{param}} placeholders
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+impl AssertionTemplate {
+    /// Concrete assertions with placeholders filled from defaults overridden by `params`
+    pub fn instantiate(&self, params: Option<&HashMap<String, serde_json::Value>>) -> Vec<Assertion> {
+        let mut values = self.parameters.clone();
+        if let Some(params) = params {
+            values.extend(params.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        self.assertions
+            .iter()
+            .map(|a| Assertion {
+                name: substitute_template_string(&a.name, &values),
+                path: a.path.as_ref().map(|p| substitute_template_string(p, &values)),
+                expected: substitute_template_params(&a.expected, &values),
+                actual: None,
+                passed: None,
+                error: None,
+                template: None,
+                params: None,
+                ..a.clone()
+            })
+            .collect()
+    }
+}
+
+/// Golden Response - Expected example response for an endpoint, keyed by endpoint and status
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GoldenResponse {
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: String,
+    #[serde(rename = "statusCode")]
+    pub status_code: u16,
+    pub body: serde_json::Value, // Captured from a real call or hand-edited
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+impl GoldenResponse {
+    /// Assertions that check the live status and diff the live body against this golden
+    pub fn to_assertions(&self) -> Vec<Assertion> {
+        vec![
+            Assertion {
+                name: format!("Status is {}", self.status_code),
+                source: "status".to_string(),
+                path: None,
+                operator: "equals".to_string(),
+                expected: serde_json::Value::Number(self.status_code.into()),
+                actual: None,
+                passed: None,
+                error: None,
+                template: None,
+                params: None,
+                ignore_paths: None,
+                only_if_status: None,
+                severity: None,
+                skipped: false,
+                logic: None,
+                assertions: None,
+            },
+            Assertion {
+                name: "Body matches golden response".to_string(),
+                source: "body".to_string(),
+                path: None,
+                operator: "equals".to_string(),
+                expected: self.body.clone(),
+                actual: None,
+                passed: None,
+                error: None,
+                template: None,
+                params: None,
+                ignore_paths: None,
+                only_if_status: None,
+                severity: None,
+                skipped: false,
+                logic: None,
+                assertions: None,
+            },
+        ]
+    }
+}
+
+/// Replace {{param}} placeholders; a value that is exactly one placeholder keeps the parameter's JSON type
+fn substitute_template_params(
+    value: &serde_json::Value,
+    params: &HashMap<String, serde_json::Value>,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => {
+            let trimmed = s.trim();
+            if trimmed.starts_with("{{") && trimmed.ends_with("}}") && trimmed.matches("{{").count() == 1 {
+                if let Some(v) = params.get(trimmed[2..trimmed.len() - 2].trim()) {
+                    return v.clone();
+                }
+            }
+            serde_json::Value::String(substitute_template_string(s, params))
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(|v| substitute_template_params(v, params)).collect())
+        }
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), substitute_template_params(v, params)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn substitute_template_string(s: &str, params: &HashMap<String, serde_json::Value>) -> String {
+    let mut result = s.to_string();
+    for (key, v) in params {
+        let replacement = match v {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        result = result.replace(&format!("{{{{{}}}}}", key), &replacement);
+    }
+    result
 }
 
 /// Scenario Run Status
@@ -192,6 +624,33 @@ impl ScenarioRunStatus {
     }
 }
 
+/// What started a scenario run
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum RunTrigger {
+    #[default]
+    #[serde(rename = "manual")]
+    Manual,
+    #[serde(rename = "schedule")]
+    Schedule,
+}
+
+impl RunTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunTrigger::Manual => "manual",
+            RunTrigger::Schedule => "schedule",
+        }
+    }
+
+    /// Runs stored before the trigger was recorded count as manual
+    pub fn parse(s: Option<&str>) -> Self {
+        match s {
+            Some("schedule") => RunTrigger::Schedule,
+            _ => RunTrigger::Manual,
+        }
+    }
+}
+
 /// Step Result Status
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum StepResultStatus {
@@ -259,6 +718,75 @@ pub struct TestScenarioRun {
     pub error_message: Option<String>,
     pub results: Vec<TestStepResult>,
     pub variables: HashMap<String, serde_json::Value>, // Final state of variables
+    #[serde(default)]
+    pub seed: Option<u64>, // RNG seed used for dynamic tokens, replay with the same value
+    #[serde(default)]
+    pub groups: Vec<StepGroupSummary>, // Per-group rollup of results, in first-seen order
+    #[serde(skip)]
+    pub snapshot_updates: Vec<AssertionSnapshot>, // Snapshots written by this run, saved by the caller
+    #[serde(default)]
+    pub logs: Vec<RunLogEntry>, // Executor trace captured for this run
+    #[serde(default)]
+    pub trigger: RunTrigger, // Manual run or one started by the scenario's schedule
+}
+
+/// Assertion Snapshot - Response body stored for `snapshot` assertions, keyed by scenario/step/name
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AssertionSnapshot {
+    #[serde(rename = "scenarioId")]
+    pub scenario_id: String,
+    #[serde(rename = "stepId")]
+    pub step_id: String,
+    pub name: String,
+    pub body: serde_json::Value,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+/// Step Group Summary - Pass/fail/duration rollup for steps sharing a group
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct StepGroupSummary {
+    pub name: String,
+    #[serde(rename = "totalSteps")]
+    pub total_steps: u32,
+    #[serde(rename = "passedSteps")]
+    pub passed_steps: u32,
+    #[serde(rename = "failedSteps")]
+    pub failed_steps: u32,
+    #[serde(rename = "skippedSteps")]
+    pub skipped_steps: u32,
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+}
+
+impl StepGroupSummary {
+    /// Summarize grouped step results; ungrouped steps are left out
+    pub fn from_results(results: &[TestStepResult]) -> Vec<Self> {
+        let mut groups: Vec<Self> = Vec::new();
+        for result in results {
+            let name = match result.group {
+                Some(ref name) => name,
+                None => continue,
+            };
+            let index = match groups.iter().position(|g| &g.name == name) {
+                Some(index) => index,
+                None => {
+                    groups.push(Self { name: name.clone(), ..Default::default() });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.total_steps += 1;
+            group.duration_ms += result.duration_ms.unwrap_or(0);
+            match result.status {
+                StepResultStatus::Passed => group.passed_steps += 1,
+                StepResultStatus::Failed | StepResultStatus::Error => group.failed_steps += 1,
+                StepResultStatus::Skipped => group.skipped_steps += 1,
+                _ => {}
+            }
+        }
+        groups
+    }
 }
 
 /// Test Step Result - Execution result of a single step
@@ -278,6 +806,39 @@ pub struct TestStepResult {
     pub error: Option<String>,
     #[serde(rename = "extractedVariables")]
     pub extracted_variables: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(rename = "assertionSummary", default, skip_serializing_if = "Option::is_none")]
+    pub assertion_summary: Option<AssertionSummary>,
+}
+
+/// Assertion outcome counts for a step, with failures split by severity
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AssertionSummary {
+    pub passed: u32,
+    #[serde(rename = "failedErrors")]
+    pub failed_errors: u32,
+    #[serde(rename = "failedWarnings")]
+    pub failed_warnings: u32,
+    pub skipped: u32,
+}
+
+impl AssertionSummary {
+    pub fn from_assertions(assertions: &[Assertion]) -> Self {
+        let mut summary = Self::default();
+        for assertion in assertions {
+            if assertion.skipped {
+                summary.skipped += 1;
+            } else if !assertion.failed() {
+                summary.passed += 1;
+            } else if assertion.is_warning() {
+                summary.failed_warnings += 1;
+            } else {
+                summary.failed_errors += 1;
+            }
+        }
+        summary
+    }
 }
 
 /// Step Request - HTTP request details sent in a step
@@ -287,6 +848,8 @@ pub struct StepRequest {
     pub url: String,
     pub headers: HashMap<String, String>,
     pub body: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault: Option<InjectedFault>, // Set when chaos mode corrupted this request
 }
 
 /// Step Response - HTTP response from a request step
@@ -299,6 +862,39 @@ pub struct StepResponse {
     pub body: serde_json::Value,
     #[serde(rename = "durationMs")]
     pub duration_ms: u64,
+    #[serde(default)]
+    pub truncated: bool, // Stored body was cut to the configured size limit
+    #[serde(rename = "finalUrl", default)]
+    pub final_url: Option<String>, // URL after following redirects
+    #[serde(rename = "contentType", default)]
+    pub content_type: Option<String>, // Raw Content-Type header, parameters included
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<RedirectHop>, // Redirects followed before the final response, in order
+    #[serde(rename = "serverTiming", default, skip_serializing_if = "Vec::is_empty")]
+    pub server_timing: Vec<ServerTimingMetric>, // Parsed from the Server-Timing header
+    #[serde(rename = "contentEncoding", default, skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>, // Content-Encoding the server sent, e.g. "gzip"
+    #[serde(rename = "decodedSize", default)]
+    pub decoded_size: u64, // Body bytes after undoing any Content-Encoding
+    #[serde(skip)]
+    pub raw_body: String, // Response text before JSON parsing, for rawBody assertions; not stored
+}
+
+/// One entry of a `Server-Timing` header, e.g. `db;dur=53;desc="Query"`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerTimingMetric {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dur: Option<f64>, // Milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+}
+
+/// One redirect response followed on the way to the final response
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RedirectHop {
+    pub status: u16,
+    pub location: String, // Resolved URL the redirect pointed to
 }
 
 /// Event payloads for real-time progress updates
@@ -326,6 +922,7 @@ pub struct StepStartedEvent {
     pub step_name: String,
     #[serde(rename = "stepType")]
     pub step_type: String,
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -371,6 +968,10 @@ pub struct UpdateScenarioRequest {
     pub pre_script: Option<String>,
     #[serde(rename = "postScript")]
     pub post_script: Option<String>,
+    pub chaos: Option<ChaosConfig>,
+    #[serde(rename = "sessionLogin")]
+    pub session_login: Option<SessionLoginConfig>,
+    pub environments: Option<HashMap<String, ScenarioEnvironment>>,
 }
 
 /// Create Step Request
@@ -391,6 +992,30 @@ pub struct UpdateStepRequest {
     pub name: Option<String>,
     pub config: Option<serde_json::Value>,
     pub enabled: Option<bool>,
+    #[serde(rename = "enabledIf")]
+    pub enabled_if: Option<String>, // Empty string clears the guard
+    pub group: Option<String>, // Empty string clears the group
+}
+
+/// Create Assertion Template Request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateAssertionTemplateRequest {
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub assertions: Vec<Assertion>,
+    pub parameters: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Update Assertion Template Request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateAssertionTemplateRequest {
+    pub id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub assertions: Option<Vec<Assertion>>,
+    pub parameters: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Reorder Steps Request
@@ -402,6 +1027,16 @@ pub struct ReorderStepsRequest {
     pub step_ids: Vec<String>, // Ordered list of step IDs
 }
 
+/// Bulk enable/disable steps request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetStepsEnabledRequest {
+    #[serde(rename = "scenarioId")]
+    pub scenario_id: String,
+    #[serde(rename = "stepIds")]
+    pub step_ids: Vec<String>,
+    pub enabled: bool,
+}
+
 /// CSV Configuration for iterating over CSV data
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CsvConfig {
@@ -0,0 +1,172 @@
+//! Import of project variables from `.env` files or JSON objects
+//!
+//! `.env` content supports `#` comments, an optional `export ` prefix, single-quoted
+//! (literal) and double-quoted (with `\n`, `\t`, `\"`, `\\` escapes) values, and inline
+//! `#` comments after unquoted values. Lines that can't be parsed are reported, not fatal.
+
+use serde::{Deserialize, Serialize};
+
+/// Variables parsed from an import file plus the lines that were skipped
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedVariables {
+    pub variables: Vec<(String, serde_json::Value)>,
+    pub skipped: Vec<String>, // "line N: reason"
+}
+
+/// Result of the `import_variables` command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableImportResult {
+    #[serde(rename = "importedCount")]
+    pub imported_count: usize,
+    pub skipped: Vec<String>,
+}
+
+/// Parse variables in the given format ("env"/"dotenv" or "json")
+pub fn parse_variables(content: &str, format: &str) -> Result<ParsedVariables, String> {
+    match format.to_lowercase().trim_start_matches('.') {
+        "env" | "dotenv" => Ok(parse_env(content)),
+        "json" => parse_json(content),
+        other => Err(format!("Unsupported variable format: {} (expected env or json)", other)),
+    }
+}
+
+fn parse_env(content: &str) -> ParsedVariables {
+    let mut parsed = ParsedVariables::default();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => {
+                parsed.skipped.push(format!("line {}: missing '='", index + 1));
+                continue;
+            }
+        };
+        if !is_valid_key(key) {
+            parsed.skipped.push(format!("line {}: invalid variable name '{}'", index + 1, key));
+            continue;
+        }
+
+        match parse_env_value(value) {
+            Ok(value) => parsed.variables.push((key.to_string(), serde_json::Value::String(value))),
+            Err(reason) => parsed.skipped.push(format!("line {}: {}", index + 1, reason)),
+        }
+    }
+
+    parsed
+}
+
+fn parse_env_value(value: &str) -> Result<String, String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        return match rest.find('\'') {
+            Some(end) => Ok(rest[..end].to_string()),
+            None => Err("unterminated single quote".to_string()),
+        };
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Ok(result),
+                '\\' => match chars.next() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some('r') => result.push('\r'),
+                    Some(other) => result.push(other),
+                    None => break,
+                },
+                other => result.push(other),
+            }
+        }
+        return Err("unterminated double quote".to_string());
+    }
+
+    // Unquoted: an inline comment starts at " #"
+    let value = match value.find(" #") {
+        Some(pos) => &value[..pos],
+        None => value,
+    };
+    Ok(value.trim_end().to_string())
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+        }
+        _ => false,
+    }
+}
+
+fn parse_json(content: &str) -> Result<ParsedVariables, String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+    let object = match value {
+        serde_json::Value::Object(object) => object,
+        _ => return Err("Variables JSON must be an object".to_string()),
+    };
+
+    let mut parsed = ParsedVariables::default();
+    for (key, value) in object {
+        if is_valid_key(&key) {
+            parsed.variables.push((key, value));
+        } else {
+            parsed.skipped.push(format!("key '{}': invalid variable name", key));
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = r#"
+# API settings
+export API_URL=https://api.example.com
+TOKEN="abc \"quoted\" \n next"
+RAW='keep \n as is'
+TIMEOUT=30 # seconds
+EMPTY=
+not a variable
+1BAD=x
+UNTERMINATED="oops
+"#;
+        let parsed = parse_variables(content, "env").unwrap();
+        let get = |key: &str| parsed.variables.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+
+        assert_eq!(parsed.variables.len(), 5);
+        assert_eq!(get("API_URL"), Some(json!("https://api.example.com")));
+        assert_eq!(get("TOKEN"), Some(json!("abc \"quoted\" \n next")));
+        assert_eq!(get("RAW"), Some(json!("keep \\n as is")));
+        assert_eq!(get("TIMEOUT"), Some(json!("30")));
+        assert_eq!(get("EMPTY"), Some(json!("")));
+        assert_eq!(parsed.skipped, vec![
+            "line 8: missing '='",
+            "line 9: invalid variable name '1BAD'",
+            "line 10: unterminated double quote",
+        ]);
+    }
+
+    #[test]
+    fn test_parse_json_variables() {
+        let parsed = parse_variables(r#"{"userId": 42, "auth": {"token": "t"}, "bad key": 1}"#, "json").unwrap();
+        assert_eq!(parsed.variables.len(), 2);
+        assert!(parsed.variables.contains(&("userId".to_string(), json!(42))));
+        assert_eq!(parsed.skipped, vec!["key 'bad key': invalid variable name"]);
+
+        assert!(parse_variables("[1, 2]", "json").is_err());
+        assert!(parse_variables("A=1", "yaml").is_err());
+    }
+}
@@ -31,6 +31,14 @@ pub struct ScenarioYaml {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "postScript")]
     pub post_script: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chaos: Option<ChaosConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "sessionLogin")]
+    pub session_login: Option<SessionLoginConfig>,
+    /// Per-environment `baseUrl`/`variables` overrides, chosen when the scenario is run
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub environments: HashMap<String, ScenarioEnvironment>,
     #[serde(default)]
     pub steps: Vec<StepYaml>,
 }
@@ -59,12 +67,18 @@ pub struct StepYaml {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_if: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub request: Option<RequestYaml>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delay: Option<DelayYaml>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub script: Option<ScriptYaml>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc: Option<GrpcYaml>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub condition: Option<ConditionYaml>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "loop")]
@@ -91,8 +105,21 @@ pub struct RequestYaml {
     pub headers: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub params: Option<serde_json::Value>,
+    /// Query string parameters; values are URL-encoded when the request is sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<HashMap<String, String>>,
+    /// Project service whose base URL a relative url resolves against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    /// Path to a JSON file used as the body, resolved like CSV file names
+    #[serde(alias = "bodyFile", skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follow_redirects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_redirects: Option<usize>,
 }
 
 /// YAML format for delay step
@@ -108,6 +135,22 @@ pub struct ScriptYaml {
     pub code: String,
 }
 
+/// YAML format for gRPC step
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrpcYaml {
+    pub endpoint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptor_set: Option<String>,
+    pub service: String,
+    pub method: String,
+    #[serde(default)]
+    pub message: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
 /// YAML format for condition step
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConditionYaml {
@@ -149,13 +192,84 @@ pub struct ExtractorYaml {
 /// YAML format for assertion
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AssertionYaml {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub source: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub operator: String,
-    #[serde(default = "default_assertion_expected")]
+    #[serde(default = "default_assertion_expected", skip_serializing_if = "serde_json::Value::is_null")]
     pub expected: serde_json::Value,
+    /// Reference to a project assertion template, e.g. `- template: ok_response`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<HashMap<String, serde_json::Value>>,
+    #[serde(rename = "ignorePaths", skip_serializing_if = "Option::is_none")]
+    pub ignore_paths: Option<Vec<String>>,
+    #[serde(rename = "onlyIfStatus", default, skip_serializing_if = "Option::is_none")]
+    pub only_if_status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    /// `and` / `or` group over the nested `assertions`, e.g. `- logic: or` with two body checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assertions: Option<Vec<AssertionYaml>>,
+}
+
+impl AssertionYaml {
+    fn from_assertion(a: &Assertion) -> Self {
+        AssertionYaml {
+            name: a.name.clone(),
+            source: a.source.clone(),
+            path: a.path.clone(),
+            operator: a.operator.clone(),
+            expected: a.expected.clone(),
+            template: a.template.clone(),
+            params: a.params.clone(),
+            ignore_paths: a.ignore_paths.clone(),
+            only_if_status: a.only_if_status.clone(),
+            severity: a.severity.clone(),
+            logic: a.logic.clone(),
+            assertions: a.assertions.as_ref().map(|nested| nested.iter().map(AssertionYaml::from_assertion).collect()),
+        }
+    }
+
+    fn to_assertion(&self) -> Assertion {
+        Assertion {
+            name: self.name.clone(),
+            source: self.source.clone(),
+            path: self.path.clone(),
+            operator: self.operator.clone(),
+            expected: self.expected.clone(),
+            actual: None,
+            passed: None,
+            error: None,
+            template: self.template.clone(),
+            params: self.params.clone(),
+            ignore_paths: self.ignore_paths.clone(),
+            only_if_status: self.only_if_status.clone(),
+            severity: self.severity.clone(),
+            skipped: false,
+            logic: self.logic.clone(),
+            assertions: self.assertions.as_ref().map(|nested| {
+                nested.iter().filter(|a| a.is_complete()).map(AssertionYaml::to_assertion).collect()
+            }),
+        }
+    }
+
+    /// False for an assertion with a missing expected field (default null), unless the operator
+    /// is "exists" or it references a template or is a group
+    fn is_complete(&self) -> bool {
+        if self.expected.is_null() && self.operator != "exists" && self.template.is_none() && self.logic.is_none() {
+            log::warn!("Skipping assertion '{}' due to missing 'expected' field", self.name);
+            return false;
+        }
+        true
+    }
 }
 
 /// Default value for assertion expected field when missing
@@ -199,6 +313,9 @@ pub fn scenario_to_yaml(
         variables,
         pre_script: scenario.pre_script.clone(),
         post_script: scenario.post_script.clone(),
+        chaos: scenario.chaos.clone(),
+        session_login: scenario.session_login.clone(),
+        environments: scenario.environments.clone(),
         steps: steps.iter().map(step_to_yaml).collect(),
     }
 }
@@ -208,9 +325,12 @@ fn step_to_yaml(step: &TestScenarioStep) -> StepYaml {
     let mut step_yaml = StepYaml {
         name: step.name.clone(),
         enabled: step.enabled,
+        enabled_if: step.enabled_if.clone(),
+        group: step.group.clone(),
         request: None,
         delay: None,
         script: None,
+        grpc: None,
         condition: None,
         loop_config: None,
         extract: None,
@@ -226,7 +346,12 @@ fn step_to_yaml(step: &TestScenarioStep) -> StepYaml {
                     url: config.url,
                     headers: config.headers,
                     params: config.params,
+                    query: config.query,
+                    service: config.service,
                     body: config.body,
+                    body_file: config.body_file,
+                    follow_redirects: config.follow_redirects,
+                    max_redirects: config.max_redirects,
                 });
 
                 // Extract variables
@@ -252,13 +377,7 @@ fn step_to_yaml(step: &TestScenarioStep) -> StepYaml {
                         step_yaml.assertions = Some(
                             assertions
                                 .iter()
-                                .map(|a| AssertionYaml {
-                                    name: a.name.clone(),
-                                    source: a.source.clone(),
-                                    path: a.path.clone(),
-                                    operator: a.operator.clone(),
-                                    expected: a.expected.clone(),
-                                })
+                                .map(AssertionYaml::from_assertion)
                                 .collect(),
                         );
                     }
@@ -286,6 +405,30 @@ fn step_to_yaml(step: &TestScenarioStep) -> StepYaml {
                 step_yaml.script = Some(ScriptYaml { code: config.code });
             }
         }
+        TestStepType::Grpc => {
+            if let Ok(config) = serde_json::from_value::<GrpcStepConfig>(step.config.clone()) {
+                step_yaml.grpc = Some(GrpcYaml {
+                    endpoint: config.endpoint,
+                    proto_file: config.proto_file,
+                    descriptor_set: config.descriptor_set,
+                    service: config.service,
+                    method: config.method,
+                    message: config.message,
+                    metadata: config.metadata,
+                });
+                step_yaml.extract = config.extract_variables.map(|extractors| {
+                    extractors.into_iter().map(|e| ExtractorYaml {
+                        name: e.name,
+                        source: e.source,
+                        path: e.path,
+                        default_value: e.default_value,
+                    }).collect()
+                });
+                step_yaml.assertions = config.assertions.map(|assertions| {
+                    assertions.iter().map(AssertionYaml::from_assertion).collect()
+                });
+            }
+        }
         TestStepType::Condition => {
             if let Ok(config) = serde_json::from_value::<ConditionStepConfig>(step.config.clone()) {
                 step_yaml.condition = Some(ConditionYaml {
@@ -340,6 +483,79 @@ pub fn project_scenarios_to_yaml_string(
         .map_err(|e| format!("Failed to serialize project to YAML: {}", e))
 }
 
+/// Result of a project YAML export written to disk
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectYamlExport {
+    pub path: String,
+    #[serde(rename = "scenarioCount")]
+    pub scenario_count: usize,
+}
+
+/// Header fields of `ProjectScenariosYaml`, written before the scenarios are streamed
+#[derive(Serialize)]
+struct ProjectYamlHeader<'a> {
+    #[serde(rename = "projectName")]
+    project_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "baseUrl")]
+    base_url: Option<&'a str>,
+    #[serde(rename = "exportedAt")]
+    exported_at: String,
+}
+
+/// Writes a project YAML document one scenario at a time, producing the same layout
+/// as `project_scenarios_to_yaml_string` without holding every scenario in memory
+pub struct ProjectYamlWriter<W: std::io::Write> {
+    writer: W,
+    base_url: Option<String>,
+    scenario_count: usize,
+}
+
+impl<W: std::io::Write> ProjectYamlWriter<W> {
+    pub fn new(mut writer: W, project_name: &str, base_url: Option<String>) -> Result<Self, String> {
+        let header = ProjectYamlHeader {
+            project_name,
+            base_url: base_url.as_deref(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let header = serde_yaml::to_string(&header)
+            .map_err(|e| format!("Failed to serialize project to YAML: {}", e))?;
+        writer
+            .write_all(header.as_bytes())
+            .map_err(|e| format!("Failed to write YAML export: {}", e))?;
+        Ok(Self {
+            writer,
+            base_url,
+            scenario_count: 0,
+        })
+    }
+
+    pub fn write_scenario(&mut self, scenario: &TestScenario, steps: &[TestScenarioStep]) -> Result<(), String> {
+        let yaml = scenario_to_yaml(scenario, steps, self.base_url.clone());
+        // A one-element sequence renders as a "- name: ..." item of the scenarios list
+        let item = serde_yaml::to_string(&[yaml])
+            .map_err(|e| format!("Failed to serialize scenario to YAML: {}", e))?;
+        let prefix = if self.scenario_count == 0 { "scenarios:\n" } else { "" };
+        write!(self.writer, "{}{}", prefix, item)
+            .map_err(|e| format!("Failed to write YAML export: {}", e))?;
+        self.scenario_count += 1;
+        Ok(())
+    }
+
+    /// Close the document and return the number of scenarios written
+    pub fn finish(mut self) -> Result<usize, String> {
+        if self.scenario_count == 0 {
+            self.writer
+                .write_all(b"scenarios: []\n")
+                .map_err(|e| format!("Failed to write YAML export: {}", e))?;
+        }
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to write YAML export: {}", e))?;
+        Ok(self.scenario_count)
+    }
+}
+
 // ============================================================================
 // Conversion Functions: YAML -> Internal Types
 // ============================================================================
@@ -507,6 +723,9 @@ pub fn yaml_to_scenario(yaml: &ScenarioYaml, project_id: &str) -> TestScenario {
         variables: serde_json::to_value(&yaml.variables).unwrap_or(serde_json::json!({})),
         pre_script: yaml.pre_script.clone(),
         post_script: yaml.post_script.clone(),
+        chaos: yaml.chaos.clone(),
+        session_login: yaml.session_login.clone(),
+        environments: yaml.environments.clone(),
         created_at: now,
         updated_at: now,
     }
@@ -524,6 +743,8 @@ pub fn yaml_to_step(yaml: &StepYaml, scenario_id: &str, step_order: i32) -> Test
         name: yaml.name.clone(),
         config,
         enabled: yaml.enabled,
+        enabled_if: yaml.enabled_if.clone(),
+        group: yaml.group.clone(),
     }
 }
 
@@ -536,7 +757,12 @@ fn determine_step_type_and_config(yaml: &StepYaml) -> (TestStepType, serde_json:
             method: request.method.clone(),
             headers: request.headers.clone(),
             params: request.params.clone(),
+            query: request.query.clone(),
+            service: request.service.clone(),
             body: request.body.clone(),
+            body_file: request.body_file.clone(),
+            follow_redirects: request.follow_redirects,
+            max_redirects: request.max_redirects,
             extract_variables: yaml.extract.as_ref().map(|extractors| {
                 extractors
                     .iter()
@@ -551,24 +777,8 @@ fn determine_step_type_and_config(yaml: &StepYaml) -> (TestStepType, serde_json:
             assertions: yaml.assertions.as_ref().map(|assertions| {
                 assertions
                     .iter()
-                    .filter_map(|a| {
-                        // Skip assertions with missing expected field (default null)
-                        // unless operator is "exists" which doesn't need expected
-                        if a.expected.is_null() && a.operator != "exists" {
-                            log::warn!("Skipping assertion '{}' due to missing 'expected' field", a.name);
-                            return None;
-                        }
-                        Some(Assertion {
-                            name: a.name.clone(),
-                            source: a.source.clone(),
-                            path: a.path.clone(),
-                            operator: a.operator.clone(),
-                            expected: a.expected.clone(),
-                            actual: None,
-                            passed: None,
-                            error: None,
-                        })
-                    })
+                    .filter(|a| a.is_complete())
+                    .map(AssertionYaml::to_assertion)
                     .collect()
             }),
             with_items_from_csv: yaml.with_items_from_csv.as_ref().map(|csv_yaml| {
@@ -596,6 +806,30 @@ fn determine_step_type_and_config(yaml: &StepYaml) -> (TestStepType, serde_json:
         return (TestStepType::Script, serde_json::to_value(config).unwrap());
     }
 
+    if let Some(grpc) = &yaml.grpc {
+        let config = GrpcStepConfig {
+            endpoint: grpc.endpoint.clone(),
+            proto_file: grpc.proto_file.clone(),
+            descriptor_set: grpc.descriptor_set.clone(),
+            service: grpc.service.clone(),
+            method: grpc.method.clone(),
+            message: grpc.message.clone(),
+            metadata: grpc.metadata.clone(),
+            extract_variables: yaml.extract.as_ref().map(|extractors| {
+                extractors.iter().map(|e| VariableExtractor {
+                    name: e.name.clone(),
+                    source: e.source.clone(),
+                    path: e.path.clone(),
+                    default_value: e.default_value.clone(),
+                }).collect()
+            }),
+            assertions: yaml.assertions.as_ref().map(|assertions| {
+                assertions.iter().map(AssertionYaml::to_assertion).collect()
+            }),
+        };
+        return (TestStepType::Grpc, serde_json::to_value(config).unwrap());
+    }
+
     if let Some(condition) = &yaml.condition {
         let config = ConditionStepConfig {
             condition: condition.condition.clone(),
@@ -623,19 +857,27 @@ fn determine_step_type_and_config(yaml: &StepYaml) -> (TestStepType, serde_json:
         method: "GET".to_string(),
         headers: None,
         params: None,
+        query: None,
+        service: None,
         body: None,
+        body_file: None,
         extract_variables: None,
         assertions: None,
         with_items_from_csv: None,
+        follow_redirects: None,
+        max_redirects: None,
     };
     (TestStepType::Request, serde_json::to_value(config).unwrap())
 }
 
-/// Convert ScenarioYaml to TestScenario and TestScenarioSteps
+/// Convert ScenarioYaml to TestScenario and TestScenarioSteps (step names must be unique)
 pub fn yaml_to_scenario_with_steps(
     yaml: &ScenarioYaml,
     project_id: &str,
-) -> (TestScenario, Vec<TestScenarioStep>) {
+) -> Result<(TestScenario, Vec<TestScenarioStep>), String> {
+    validate_unique_step_names(yaml.steps.iter().map(|s| s.name.as_str()))
+        .map_err(|e| format!("Scenario '{}': {}", yaml.name, e))?;
+
     let scenario = yaml_to_scenario(yaml, project_id);
     let steps: Vec<TestScenarioStep> = yaml
         .steps
@@ -643,7 +885,38 @@ pub fn yaml_to_scenario_with_steps(
         .enumerate()
         .map(|(i, step_yaml)| yaml_to_step(step_yaml, &scenario.id, i as i32))
         .collect();
-    (scenario, steps)
+    validate_step_references(&steps)
+        .map_err(|e| format!("Scenario '{}': {}", yaml.name, e))?;
+    Ok((scenario, steps))
+}
+
+/// Steps to save and step ids to delete when merging YAML steps into a scenario
+///
+/// Steps are matched by name: matches keep their id (and with it their run history) and
+/// take the YAML content and position, unmatched YAML steps are new, and existing steps
+/// missing from the YAML are removed.
+pub fn merge_yaml_steps(
+    yaml_steps: &[StepYaml],
+    existing: &[TestScenarioStep],
+    scenario_id: &str,
+) -> Result<(Vec<TestScenarioStep>, Vec<String>), String> {
+    validate_unique_step_names(yaml_steps.iter().map(|s| s.name.as_str()))?;
+
+    let mut unmatched: Vec<&TestScenarioStep> = existing.iter().collect();
+    let steps: Vec<TestScenarioStep> = yaml_steps
+        .iter()
+        .enumerate()
+        .map(|(i, step_yaml)| {
+            let mut step = yaml_to_step(step_yaml, scenario_id, i as i32);
+            if let Some(pos) = unmatched.iter().position(|s| s.name.trim() == step_yaml.name.trim()) {
+                step.id = unmatched.remove(pos).id.clone();
+            }
+            step
+        })
+        .collect();
+    validate_step_references(&steps)?;
+    let removed = unmatched.into_iter().map(|s| s.id.clone()).collect();
+    Ok((steps, removed))
 }
 
 // ============================================================================
@@ -707,6 +980,24 @@ fn determine_step_type_name(yaml: &StepYaml) -> String {
     }
 }
 
+/// Progress of a project import, emitted as `scenario-import-progress` after each scenario
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScenarioImportProgress {
+    pub name: String,
+    pub index: usize, // 1-based position of this scenario in the file
+    pub total: usize,
+}
+
+/// Outcome of a project import: the saved scenarios and how many rows were created
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectImportSummary {
+    pub scenarios: Vec<TestScenario>,
+    #[serde(rename = "scenariosCount")]
+    pub scenarios_count: usize,
+    #[serde(rename = "stepsCount")]
+    pub steps_count: usize,
+}
+
 /// Preview for project import
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectImportPreview {
@@ -765,6 +1056,20 @@ steps:
       body:
         email: "test@example.com"
         password: "password123"
+      # For large payloads use a JSON file instead of body (variables are resolved too):
+      # body_file: "fixtures/login.json"
+      # Generate an array of similar items with __repeat; {{ $index }} is the 0-based position:
+      # body:
+      #   users:
+      #     __repeat: 3          # or "{{ batchSize }}"
+      #     item:
+      #       email: "user{{ $index }}@example.com"
+      # Query parameters are URL-encoded for you (no need to build them into the url):
+      # query:
+      #   search: "{{ keyword }}"
+      #   page: "1"
+      # In multi-service projects a relative url can target a named service's base URL:
+      # service: auth-service
     # Extract values from response to use in later steps
     extract:
       - name: accessToken
@@ -773,9 +1078,51 @@ steps:
     # Validate response
     assertions:
       - name: "Status is 200"
-        source: status      # Options: status, body, header, duration
-        operator: equals    # Options: equals, notEquals, contains, matches, greaterThan, lessThan, exists
+        source: status      # Options: status, body, header, duration, contentType, serverTiming, rawBody
+        operator: equals    # Options: equals, notEquals, contains, matches, greaterThan, lessThan, exists, type, length, snapshot
         expected: 200
+      # Snapshot: compare the body to a stored copy named by expected (recorded on first run)
+      # - name: "User matches snapshot"
+      #   source: body
+      #   operator: snapshot
+      #   expected: user
+      #   ignorePaths: ["$.id", "items[*].createdAt"]
+      # onlyIfStatus: evaluate only for matching statuses (e.g. 2xx, 200-299, 200,201); skipped otherwise
+      # - name: "Has user id"
+      #   source: body
+      #   path: data.id
+      #   operator: exists
+      #   onlyIfStatus: 2xx
+      # severity: warn records a failure without failing the step (default: error)
+      # - name: "Responds quickly"
+      #   source: duration
+      #   operator: lessThan
+      #   expected: 500
+      #   severity: warn
+      # serverTiming: a Server-Timing entry's dur in ms (path "db" or "db;dur"), or "db;desc"
+      # - name: "DB time under 100ms"
+      #   source: serverTiming
+      #   path: db
+      #   operator: lessThan
+      #   expected: 100
+      # rawBody: the response text as received, for HTML/CSV/plain text; path "lines" or "length" counts it
+      # - name: "CSV has header and two rows"
+      #   source: rawBody
+      #   path: lines
+      #   operator: equals
+      #   expected: 3
+      # logic: and/or groups nested assertions (which may themselves be groups)
+      # - name: "Privileged role"
+      #   logic: or
+      #   assertions:
+      #     - source: body
+      #       path: role
+      #       operator: equals
+      #       expected: admin
+      #     - source: body
+      #       path: role
+      #       operator: equals
+      #       expected: owner
 
   # Delay Step
   - name: "Wait before next request"
@@ -805,6 +1152,70 @@ steps:
 "#.to_string()
 }
 
+/// Backend recorded in the provenance of AI-generated YAML
+pub const AI_YAML_BACKEND: &str = "copilot-cli";
+
+/// Endpoints included in the AI prompt context, to keep the prompt short
+pub const AI_CONTEXT_ENDPOINT_LIMIT: usize = 20;
+
+/// Tools Copilot CLI denies by default: no directory changes, git, network or extensions
+pub const DEFAULT_COPILOT_DENIED_TOOLS: &[&str] = &[
+    "shell(cd)", "shell(git)", "shell(pwd)", "fetch", "extensions", "websearch", "githubRepo",
+];
+
+/// Which Copilot CLI tools AI generation may use
+///
+/// An empty `allow_tools` keeps `--allow-all-tools`; `deny_tools` always applies on top.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CopilotToolPolicy {
+    #[serde(rename = "allowTools", default)]
+    pub allow_tools: Vec<String>,
+    #[serde(rename = "denyTools", default)]
+    pub deny_tools: Vec<String>,
+}
+
+impl Default for CopilotToolPolicy {
+    fn default() -> Self {
+        Self {
+            allow_tools: Vec::new(),
+            deny_tools: DEFAULT_COPILOT_DENIED_TOOLS.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+impl CopilotToolPolicy {
+    /// Reject tool names that could be read as extra CLI flags or are contradictory
+    pub fn validate(&self) -> Result<(), String> {
+        let pattern = regex::Regex::new(r"^[A-Za-z0-9_.-]+(\([^()\r\n]*\))?$").unwrap();
+        for tool in self.allow_tools.iter().chain(&self.deny_tools) {
+            if tool.starts_with('-') || !pattern.is_match(tool) {
+                return Err(format!("Invalid tool name '{}': expected e.g. fetch or shell(git)", tool));
+            }
+        }
+        if let Some(tool) = self.allow_tools.iter().find(|t| self.deny_tools.contains(t)) {
+            return Err(format!("Invalid tool policy: '{}' is both allowed and denied", tool));
+        }
+        Ok(())
+    }
+
+    /// Copilot CLI arguments for this policy
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.allow_tools.is_empty() {
+            args.push("--allow-all-tools".to_string());
+        }
+        for tool in &self.allow_tools {
+            args.push("--allow-tool".to_string());
+            args.push(tool.clone());
+        }
+        for tool in &self.deny_tools {
+            args.push("--deny-tool".to_string());
+            args.push(tool.clone());
+        }
+        args
+    }
+}
+
 /// Generate a YAML template using AI (Copilot CLI)
 /// 
 /// This function calls the Copilot CLI to generate a test scenario YAML template
@@ -815,6 +1226,7 @@ steps:
 /// * `user_prompt` - User's prompt describing what kind of test scenario to generate
 /// * `endpoints` - Optional list of API endpoints to include in the context
 /// * `base_url` - Optional base URL for the API
+/// * `tool_policy` - Copilot CLI tools to allow and deny
 /// 
 /// # Returns
 /// * `Ok(String)` - Generated YAML template
@@ -824,7 +1236,11 @@ pub async fn generate_yaml_template_with_ai(
     user_prompt: &str,
     endpoints: Option<&[ApiEndpoint]>,
     base_url: Option<&str>,
+    tool_policy: &CopilotToolPolicy,
 ) -> Result<String, String> {
+    tool_policy.validate()?;
+
+
     // Build context from endpoints
     let endpoints_context = build_endpoints_context(endpoints);
     
@@ -832,7 +1248,7 @@ pub async fn generate_yaml_template_with_ai(
     let full_prompt = build_ai_prompt(user_prompt, &endpoints_context, base_url);
     
     // Execute Copilot CLI
-    match execute_copilot_cli(project_path, &full_prompt).await {
+    match execute_copilot_cli(project_path, &full_prompt, tool_policy).await {
         Ok(output) => {
             // Try to extract YAML from the output
             match extract_yaml_from_output(&output) {
@@ -974,6 +1390,107 @@ fn format_property(prop: &ResponseProperty, indent: usize) -> String {
     result
 }
 
+/// JSON types the `type` assertion operator understands
+const ASSERTABLE_TYPES: &[&str] = &["string", "number", "integer", "boolean", "object", "array"];
+
+/// Body paths of required properties, walking nested objects like `format_property`
+fn required_property_paths(prop: &ResponseProperty, parent: &str, paths: &mut Vec<String>) {
+    if !prop.required {
+        return;
+    }
+    let path = if parent.is_empty() {
+        prop.name.clone()
+    } else {
+        format!("{}.{}", parent, prop.name)
+    };
+    paths.push(path.clone());
+
+    // Array items are not addressed by a fixed path, so only object children are followed
+    if prop.property_type == "object" {
+        if let Some(ref nested) = prop.nested_properties {
+            for nested_prop in nested {
+                required_property_paths(nested_prop, &path, paths);
+            }
+        }
+    }
+}
+
+fn suggested_assertion(name: String, source: &str, path: Option<String>, operator: &str, expected: serde_json::Value) -> Assertion {
+    Assertion {
+        name,
+        source: source.to_string(),
+        path,
+        operator: operator.to_string(),
+        expected,
+        actual: None,
+        passed: None,
+        error: None,
+        template: None,
+        params: None,
+        ignore_paths: None,
+        only_if_status: None,
+        severity: None,
+        skipped: false,
+        logic: None,
+        assertions: None,
+    }
+}
+
+/// Candidate assertions for a response: status equals, required fields exist, and
+/// type checks on top-level properties
+pub fn suggest_assertions_from_schema(status_code: u16, schema: Option<&ResponseSchema>) -> Vec<Assertion> {
+    let mut assertions = vec![suggested_assertion(
+        format!("Status is {}", status_code),
+        "status",
+        None,
+        "equals",
+        serde_json::json!(status_code),
+    )];
+
+    let schema = match schema {
+        Some(schema) => schema,
+        None => return assertions,
+    };
+
+    if ASSERTABLE_TYPES.contains(&schema.schema_type.as_str()) {
+        assertions.push(suggested_assertion(
+            format!("Body is {}", schema.schema_type),
+            "body",
+            None,
+            "type",
+            serde_json::json!(schema.schema_type),
+        ));
+    }
+
+    let mut required_paths = Vec::new();
+    for prop in &schema.properties {
+        required_property_paths(prop, "", &mut required_paths);
+    }
+    for path in required_paths {
+        assertions.push(suggested_assertion(
+            format!("{} exists", path),
+            "body",
+            Some(path),
+            "exists",
+            serde_json::Value::Null,
+        ));
+    }
+
+    for prop in &schema.properties {
+        if ASSERTABLE_TYPES.contains(&prop.property_type.as_str()) {
+            assertions.push(suggested_assertion(
+                format!("{} is {}", prop.name, prop.property_type),
+                "body",
+                Some(prop.name.clone()),
+                "type",
+                serde_json::json!(prop.property_type),
+            ));
+        }
+    }
+
+    assertions
+}
+
 /// Format response schema from serde_json::Value to readable text
 fn format_response_schema(response: &ApiResponseDefinition) -> String {
     let mut result = String::new();
@@ -1054,7 +1571,7 @@ fn build_endpoints_context(endpoints: Option<&[ApiEndpoint]>) -> String {
     match endpoints {
         Some(eps) if !eps.is_empty() => {
             let mut context = String::from("Available API endpoints:\n");
-            for ep in eps.iter().take(20) { // Limit to 20 endpoints to avoid prompt being too long
+            for ep in eps.iter().take(AI_CONTEXT_ENDPOINT_LIMIT) {
                 context.push_str(&format!(
                     "- {} {} - {}\n",
                     ep.method,
@@ -1152,8 +1669,8 @@ steps:
         path: json.path.to.value
     assertions:
       - name: "Assertion description"
-        source: status|body|header|duration
-        operator: equals|notEquals|contains|matches|greaterThan|lessThan|exists
+        source: status|body|header|duration|contentType|serverTiming|rawBody
+        operator: equals|notEquals|contains|matches|greaterThan|lessThan|exists|length
         expected: value
 
   # Delay Step
@@ -1183,7 +1700,7 @@ Generate ONLY the YAML content, no explanations. The YAML should be valid and re
 }
 
 /// Execute Copilot CLI command in the project directory
-async fn execute_copilot_cli(project_path: &str, prompt: &str) -> Result<String, String> {
+async fn execute_copilot_cli(project_path: &str, prompt: &str, tool_policy: &CopilotToolPolicy) -> Result<String, String> {
     let path = Path::new(project_path);
     
     if !path.exists() {
@@ -1193,18 +1710,11 @@ async fn execute_copilot_cli(project_path: &str, prompt: &str) -> Result<String,
     // Escape the prompt for shell
     let escaped_prompt = prompt.replace('\'', "'\\''");
     
-    // Build the copilot command with safety flags
+    // Build the copilot command with the tool policy's safety flags
     let output = Command::new("copilot")
         .arg("-p")
         .arg(&escaped_prompt)
-        .arg("--allow-all-tools")
-        .arg("--deny-tool").arg("shell(cd)")
-        .arg("--deny-tool").arg("shell(git)")
-        .arg("--deny-tool").arg("shell(pwd)")
-        .arg("--deny-tool").arg("fetch")
-        .arg("--deny-tool").arg("extensions")
-        .arg("--deny-tool").arg("websearch")
-        .arg("--deny-tool").arg("githubRepo")
+        .args(tool_policy.to_args())
         .current_dir(path)
         .output()
         .await
@@ -1404,6 +1914,121 @@ steps:
         assert_eq!(scenario.steps.len(), 1);
     }
 
+    #[test]
+    fn test_copilot_tool_policy() {
+        let default_args = CopilotToolPolicy::default().to_args();
+        assert_eq!(default_args[0], "--allow-all-tools");
+        assert!(default_args.windows(2).any(|w| w[0] == "--deny-tool" && w[1] == "fetch"));
+
+        let policy = CopilotToolPolicy {
+            allow_tools: vec!["fetch".to_string(), "shell(ls)".to_string()],
+            deny_tools: vec!["shell(git)".to_string()],
+        };
+        assert!(policy.validate().is_ok());
+        assert_eq!(policy.to_args(), vec![
+            "--allow-tool", "fetch", "--allow-tool", "shell(ls)", "--deny-tool", "shell(git)",
+        ]);
+
+        let flag = CopilotToolPolicy { allow_tools: vec!["--allow-all-paths".to_string()], deny_tools: Vec::new() };
+        assert!(flag.validate().is_err());
+        let contradictory = CopilotToolPolicy { allow_tools: vec!["fetch".to_string()], deny_tools: vec!["fetch".to_string()] };
+        assert!(contradictory.validate().is_err());
+    }
+
+    #[test]
+    fn test_merge_yaml_steps_keeps_ids_of_matched_steps() {
+        let yaml = parse_scenario_yaml(r#"
+name: Users
+steps:
+  - name: Create user
+    request:
+      method: POST
+      url: /users
+  - name: Get user
+    request:
+      method: GET
+      url: /users/2
+"#).unwrap();
+        let existing = vec![
+            TestScenarioStep {
+                id: "get-id".to_string(),
+                scenario_id: "s1".to_string(),
+                step_order: 0,
+                step_type: TestStepType::Request,
+                name: "Get user".to_string(),
+                config: serde_json::json!({ "method": "GET", "url": "/users/1" }),
+                enabled: true,
+                enabled_if: None,
+                group: None,
+            },
+            TestScenarioStep {
+                id: "delete-id".to_string(),
+                scenario_id: "s1".to_string(),
+                step_order: 1,
+                step_type: TestStepType::Delay,
+                name: "Cool down".to_string(),
+                config: serde_json::json!({ "durationMs": 100 }),
+                enabled: true,
+                enabled_if: None,
+                group: None,
+            },
+        ];
+
+        let (steps, removed) = merge_yaml_steps(&yaml.steps, &existing, "s1").unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_ne!(steps[0].id, "get-id");
+        assert_eq!(steps[1].id, "get-id");
+        assert_eq!(steps[1].step_order, 1);
+        assert_eq!(steps[1].config["url"], "/users/2");
+        assert_eq!(removed, vec!["delete-id".to_string()]);
+    }
+
+    #[test]
+    fn test_step_reference_cycles_rejected() {
+        let self_loop = parse_scenario_yaml(r#"
+name: Self loop
+steps:
+  - name: Repeat
+    loop:
+      type: for
+      count: 3
+      steps: ["Repeat"]
+"#).unwrap();
+        let err = yaml_to_scenario_with_steps(&self_loop, "p1").unwrap_err();
+        assert!(err.contains("Step reference cycle: Repeat -> Repeat"), "{}", err);
+
+        let two_step = parse_scenario_yaml(r#"
+name: Ping pong
+steps:
+  - name: Ping
+    condition:
+      condition: "true"
+      trueSteps: ["Pong"]
+  - name: Pong
+    condition:
+      condition: "true"
+      falseSteps: ["Ping"]
+  - name: Done
+    delay:
+      duration: 1
+"#).unwrap();
+        let err = yaml_to_scenario_with_steps(&two_step, "p1").unwrap_err();
+        assert!(err.contains("Ping -> Pong -> Ping"), "{}", err);
+
+        let acyclic = parse_scenario_yaml(r#"
+name: Branch
+steps:
+  - name: Check
+    condition:
+      condition: "true"
+      trueSteps: ["Done"]
+  - name: Done
+    delay:
+      duration: 1
+"#).unwrap();
+        assert!(yaml_to_scenario_with_steps(&acyclic, "p1").is_ok());
+    }
+
     #[test]
     fn test_auto_correct_yaml() {
         // YAML with improper indentation and spacing
@@ -1473,7 +2098,7 @@ steps:
         let parsed = parse_scenario_yaml(yaml_content).unwrap();
         
         // Convert to internal types
-        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "test-project-id");
+        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "test-project-id").unwrap();
         
         // Convert back to YAML
         let yaml_output = scenario_to_yaml_string(&scenario, &steps, None).unwrap();
@@ -1485,4 +2110,228 @@ steps:
         assert_eq!(reparsed.name, "Roundtrip Test");
         assert_eq!(reparsed.steps.len(), 1);
     }
+
+    #[test]
+    fn test_environments_roundtrip_and_resolution() {
+        let yaml_content = r#"
+name: Portable
+variables:
+  user: alice
+  token: default
+environments:
+  dev:
+    baseUrl: http://localhost:3000
+  prod:
+    baseUrl: https://api.example.com
+    variables:
+      token: prod-token
+steps:
+  - name: Me
+    request:
+      method: GET
+      url: "{{ baseUrl }}/me"
+"#;
+        let parsed = parse_scenario_yaml(yaml_content).unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "p1").unwrap();
+        assert_eq!(scenario.environments.len(), 2);
+
+        let reparsed = parse_scenario_yaml(&scenario_to_yaml_string(&scenario, &steps, None).unwrap()).unwrap();
+        assert_eq!(reparsed.environments, parsed.environments);
+
+        let project_url = Some("http://project".to_string());
+        let (prod, base_url) = scenario.resolve_environment(Some("prod"), project_url.clone()).unwrap();
+        assert_eq!(base_url.as_deref(), Some("https://api.example.com"));
+        assert_eq!(prod.variables["token"], "prod-token");
+        assert_eq!(prod.variables["user"], "alice");
+
+        let (dev, base_url) = scenario.resolve_environment(Some("dev"), project_url.clone()).unwrap();
+        assert_eq!(base_url.as_deref(), Some("http://localhost:3000"));
+        assert_eq!(dev.variables["token"], "default");
+
+        let (_, base_url) = scenario.resolve_environment(None, project_url.clone()).unwrap();
+        assert_eq!(base_url, project_url);
+        let err = scenario.resolve_environment(Some("staging"), None).unwrap_err();
+        assert!(err.contains("defined: dev, prod"), "{}", err);
+
+        // Files without environments keep exporting without the key
+        let plain = parse_scenario_yaml("name: Plain\nsteps: []\n").unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&plain, "p1").unwrap();
+        assert!(!scenario_to_yaml_string(&scenario, &steps, None).unwrap().contains("environments"));
+    }
+
+    #[test]
+    fn test_suggest_assertions_from_schema() {
+        let schema = crate::scanner::infer_schema_from_sample(
+            r#"{"id": 7, "user": {"email": "a@b.co", "tags": [{"x": 1}]}, "total": 1.5}"#,
+        )
+        .unwrap();
+        let assertions = suggest_assertions_from_schema(200, Some(&schema));
+        let summary: Vec<String> = assertions
+            .iter()
+            .map(|a| format!("{} {} {}", a.path.as_deref().unwrap_or("-"), a.operator, a.expected))
+            .collect();
+
+        assert_eq!(summary[0], "- equals 200");
+        assert_eq!(summary[1], "- type \"object\"");
+        for expected in [
+            "user.email exists null",
+            "user.tags exists null",
+            "id type \"integer\"",
+            "total type \"number\"",
+        ] {
+            assert!(summary.iter().any(|s| s == expected), "missing {}", expected);
+        }
+        assert!(!summary.iter().any(|s| s.starts_with("user.tags.x")));
+
+        assert_eq!(suggest_assertions_from_schema(204, None).len(), 1);
+    }
+
+    #[test]
+    fn test_streamed_project_export_matches_document_format() {
+        let yaml_content = r#"
+name: "Streamed"
+description: |
+  Multi-line
+  description
+steps:
+  - name: "Get items"
+    request:
+      method: GET
+      url: /api/items
+"#;
+        let parsed = parse_scenario_yaml(yaml_content).unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "p1").unwrap();
+
+        let mut buffer = Vec::new();
+        let mut writer = ProjectYamlWriter::new(&mut buffer, "Shop", Some("http://localhost:3000".to_string())).unwrap();
+        writer.write_scenario(&scenario, &steps).unwrap();
+        writer.write_scenario(&scenario, &steps).unwrap();
+        assert_eq!(writer.finish().unwrap(), 2);
+
+        let project = parse_project_scenarios_yaml(&String::from_utf8(buffer).unwrap()).unwrap();
+        assert_eq!(project.project_name, "Shop");
+        assert_eq!(project.base_url.as_deref(), Some("http://localhost:3000"));
+        assert_eq!(project.scenarios.len(), 2);
+        assert_eq!(project.scenarios[1].description.as_deref(), Some("Multi-line\ndescription\n"));
+        assert_eq!(project.scenarios[1].steps.len(), 1);
+
+        let mut empty = Vec::new();
+        assert_eq!(ProjectYamlWriter::new(&mut empty, "Empty", None).unwrap().finish().unwrap(), 0);
+        let project = parse_project_scenarios_yaml(&String::from_utf8(empty).unwrap()).unwrap();
+        assert!(project.scenarios.is_empty());
+    }
+
+    #[test]
+    fn test_enabled_if_roundtrip() {
+        let yaml_content = r#"
+name: "Cleanup"
+steps:
+  - name: "Delete user"
+    enabled_if: "{{userId}} != null"
+    request:
+      method: DELETE
+      url: /api/users/{{userId}}
+"#;
+        let parsed = parse_scenario_yaml(yaml_content).unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "test-project-id").unwrap();
+        assert_eq!(steps[0].enabled_if.as_deref(), Some("{{userId}} != null"));
+
+        let yaml_output = scenario_to_yaml_string(&scenario, &steps, None).unwrap();
+        let reparsed = parse_scenario_yaml(&yaml_output).unwrap();
+        assert_eq!(reparsed.steps[0].enabled_if.as_deref(), Some("{{userId}} != null"));
+    }
+
+    #[test]
+    fn test_duplicate_step_names_rejected() {
+        let yaml_content = r#"
+name: "Users"
+steps:
+  - name: "Get user"
+    request:
+      method: GET
+      url: /api/users/1
+  - name: "Wait"
+    delay:
+      duration: 100
+  - name: "Get user "
+    request:
+      method: GET
+      url: /api/users/2
+"#;
+        let parsed = parse_scenario_yaml(yaml_content).unwrap();
+        let err = yaml_to_scenario_with_steps(&parsed, "test-project-id").unwrap_err();
+        assert_eq!(err, "Scenario 'Users': Duplicate step names in scenario: 'Get user' (2 times)");
+
+        assert!(validate_unique_step_names(["Login", "Get user", "Logout"]).is_ok());
+    }
+
+    #[test]
+    fn test_assertion_template_reference_roundtrip() {
+        let yaml_content = r#"
+name: "Templates"
+steps:
+  - name: "Create user"
+    request:
+      method: POST
+      url: /api/users
+    assertions:
+      - template: ok_response
+        params:
+          status: 201
+"#;
+        let parsed = parse_scenario_yaml(yaml_content).unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "test-project-id").unwrap();
+        let config: RequestStepConfig = serde_json::from_value(steps[0].config.clone()).unwrap();
+        let assertions = config.assertions.unwrap();
+        assert_eq!(assertions.len(), 1);
+        assert_eq!(assertions[0].template.as_deref(), Some("ok_response"));
+
+        let yaml_output = scenario_to_yaml_string(&scenario, &steps, None).unwrap();
+        let reparsed = parse_scenario_yaml(&yaml_output).unwrap();
+        let reference = &reparsed.steps[0].assertions.as_ref().unwrap()[0];
+        assert_eq!(reference.template.as_deref(), Some("ok_response"));
+        assert_eq!(reference.params.as_ref().unwrap()["status"], serde_json::json!(201));
+    }
+
+    #[test]
+    fn test_assertion_group_roundtrip() {
+        let yaml_content = r#"
+name: "Groups"
+steps:
+  - name: "Get me"
+    request:
+      method: GET
+      url: /api/me
+    assertions:
+      - name: "Privileged role"
+        logic: or
+        assertions:
+          - source: body
+            path: role
+            operator: equals
+            expected: admin
+          - source: body
+            path: role
+            operator: equals
+          - source: body
+            path: role
+            operator: equals
+            expected: owner
+"#;
+        let parsed = parse_scenario_yaml(yaml_content).unwrap();
+        let (scenario, steps) = yaml_to_scenario_with_steps(&parsed, "test-project-id").unwrap();
+        let config: RequestStepConfig = serde_json::from_value(steps[0].config.clone()).unwrap();
+        let group = &config.assertions.unwrap()[0];
+        assert_eq!(group.logic.as_deref(), Some("or"));
+        // The member without `expected` is dropped, like a top-level one
+        let members = group.assertions.as_ref().unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].expected, serde_json::json!("owner"));
+
+        let yaml_output = scenario_to_yaml_string(&scenario, &steps, None).unwrap();
+        let reparsed = parse_scenario_yaml(&yaml_output).unwrap();
+        let group = &reparsed.steps[0].assertions.as_ref().unwrap()[0];
+        assert_eq!(group.logic.as_deref(), Some("or"));
+        assert_eq!(group.assertions.as_ref().unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,178 @@
+//! Parameter fuzz inputs derived from an endpoint's parameter metadata
+//!
+//! Each parameter gets boundary and malformed values for its declared type plus values
+//! just outside any `min:`/`max:` rules. A well-behaved API answers them with a 4xx
+//! validation error; a 5xx means the input reached code that didn't expect it.
+
+use crate::types::ApiParameter;
+use serde_json::{json, Value};
+
+/// Length used for the "oversized string" input when no `max:` rule is known
+const OVERSIZED_STRING_LEN: usize = 10_000;
+
+/// A fuzz value for one parameter; `None` means the parameter is left out of the request
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzInput {
+    pub label: String,
+    pub value: Option<Value>,
+}
+
+impl FuzzInput {
+    fn new(label: impl Into<String>, value: Value) -> Self {
+        Self { label: label.into(), value: Some(value) }
+    }
+}
+
+/// Boundary and malformed inputs for a parameter, based on its type and validation rules
+pub fn fuzz_inputs(param: &ApiParameter) -> Vec<FuzzInput> {
+    let rules = param.validation.as_deref().unwrap_or(&[]);
+    let min = rule_number(rules, "min:");
+    let max = rule_number(rules, "max:");
+    let mut inputs = vec![FuzzInput::new("null", Value::Null)];
+    if param.required {
+        inputs.push(FuzzInput { label: "missing".to_string(), value: None });
+    }
+
+    match param.param_type.to_lowercase().as_str() {
+        "integer" | "int" | "number" | "float" => {
+            inputs.push(FuzzInput::new("negative", json!(-1)));
+            inputs.push(FuzzInput::new("zero", json!(0)));
+            inputs.push(FuzzInput::new("i64 max", json!(i64::MAX)));
+            inputs.push(FuzzInput::new("huge number string", json!("1e309")));
+            inputs.push(FuzzInput::new("string", json!("abc")));
+            inputs.push(FuzzInput::new("boolean", json!(true)));
+            if param.param_type.starts_with("int") {
+                inputs.push(FuzzInput::new("fraction", json!(1.5)));
+            }
+            if let Some(min) = min {
+                inputs.push(FuzzInput::new(format!("below min ({})", min), number_value(min - 1.0)));
+            }
+            if let Some(max) = max {
+                inputs.push(FuzzInput::new(format!("above max ({})", max), number_value(max + 1.0)));
+            }
+        }
+        "boolean" | "bool" => {
+            inputs.push(FuzzInput::new("string", json!("yes")));
+            inputs.push(FuzzInput::new("number", json!(2)));
+        }
+        "array" => {
+            inputs.push(FuzzInput::new("string", json!("not-an-array")));
+            inputs.push(FuzzInput::new("object", json!({})));
+            inputs.push(FuzzInput::new("array of null", json!([null])));
+        }
+        "object" => {
+            inputs.push(FuzzInput::new("string", json!("not-an-object")));
+            inputs.push(FuzzInput::new("array", json!([])));
+        }
+        _ => {
+            inputs.push(FuzzInput::new("empty string", json!("")));
+            inputs.push(FuzzInput::new("whitespace", json!("   ")));
+            inputs.push(FuzzInput::new("number", json!(12345)));
+            inputs.push(FuzzInput::new("object", json!({})));
+            inputs.push(FuzzInput::new("array", json!([])));
+            let oversized = max.map(|m| m as usize + 1).unwrap_or(OVERSIZED_STRING_LEN);
+            inputs.push(FuzzInput::new(format!("oversized ({} chars)", oversized), json!("a".repeat(oversized))));
+            if let Some(min) = min.filter(|m| *m >= 1.0) {
+                let short = min as usize - 1;
+                inputs.push(FuzzInput::new(format!("below min length ({})", min), json!("a".repeat(short))));
+            }
+            if has_rule(rules, "email") {
+                inputs.push(FuzzInput::new("invalid email", json!("not-an-email")));
+            }
+            if has_rule(rules, "date") {
+                inputs.push(FuzzInput::new("invalid date", json!("2024-13-45")));
+            }
+            if has_rule(rules, "uuid") {
+                inputs.push(FuzzInput::new("invalid uuid", json!("not-a-uuid")));
+            }
+        }
+    }
+
+    inputs
+}
+
+fn has_rule(rules: &[String], rule_name: &str) -> bool {
+    rules.iter().any(|r| {
+        let r = r.trim().to_lowercase();
+        r == rule_name || r.starts_with(&format!("{}:", rule_name)) || r.starts_with(&format!("is{}", rule_name))
+    })
+}
+
+fn rule_number(rules: &[String], prefix: &str) -> Option<f64> {
+    rules.iter().find_map(|r| r.trim().strip_prefix(prefix).and_then(|v| v.trim().parse().ok()))
+}
+
+/// Whole numbers stay integers so `max:100` yields 101, not 101.0
+fn number_value(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        json!(n as i64)
+    } else {
+        json!(n)
+    }
+}
+
+/// Parameter metadata guessed from a sample value, for requests without a scanned endpoint
+pub fn parameter_from_value(name: &str, value: &Value) -> ApiParameter {
+    let param_type = match value {
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+        _ => "string",
+    };
+    ApiParameter {
+        name: name.to_string(),
+        param_type: param_type.to_string(),
+        required: false,
+        description: String::new(),
+        example: Some(value.clone()),
+        default_value: None,
+        validation: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(param_type: &str, required: bool, validation: &[&str]) -> ApiParameter {
+        ApiParameter {
+            name: "field".to_string(),
+            param_type: param_type.to_string(),
+            required,
+            description: String::new(),
+            example: None,
+            default_value: None,
+            validation: Some(validation.iter().map(|r| r.to_string()).collect()),
+        }
+    }
+
+    fn labels(inputs: &[FuzzInput]) -> Vec<&str> {
+        inputs.iter().map(|i| i.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_string_inputs_follow_validation_rules() {
+        let inputs = fuzz_inputs(&param("string", true, &["min:3", "max:20", "email"]));
+        let labels = labels(&inputs);
+        assert!(labels.contains(&"missing"));
+        assert!(labels.contains(&"invalid email"));
+
+        let oversized = inputs.iter().find(|i| i.label.starts_with("oversized")).unwrap();
+        assert_eq!(oversized.value.as_ref().unwrap().as_str().unwrap().len(), 21);
+        let short = inputs.iter().find(|i| i.label.starts_with("below min length")).unwrap();
+        assert_eq!(short.value, Some(json!("aa")));
+    }
+
+    #[test]
+    fn test_integer_inputs() {
+        let inputs = fuzz_inputs(&param("integer", false, &["min:1", "max:100"]));
+        let labels = labels(&inputs);
+        assert!(!labels.contains(&"missing"));
+        assert!(labels.contains(&"fraction"));
+        assert!(inputs.contains(&FuzzInput::new("below min (1)", json!(0))));
+        assert!(inputs.contains(&FuzzInput::new("above max (100)", json!(101))));
+        assert_eq!(parameter_from_value("id", &json!(7)).param_type, "integer");
+    }
+}
@@ -1,3 +1,4 @@
 pub mod scanner;
 pub mod types;
 pub mod payloads;
+pub mod fuzz;
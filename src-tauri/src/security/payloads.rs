@@ -84,6 +84,8 @@ pub fn get_payloads(scan_type: &ScanType) -> Vec<String> {
             "not-a-uuid".into(),
             "invalid@email".into(),
         ],
+        // Inputs are derived per parameter, see `fuzz::fuzz_inputs`
+        ScanType::ParameterFuzz => vec![],
     }
 }
 
@@ -1,5 +1,7 @@
+use super::fuzz;
 use super::payloads::{get_leak_patterns, get_payloads};
 use super::types::*;
+use crate::types::ApiParameter;
 use reqwest::blocking::Client;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -27,8 +29,13 @@ impl SecurityScanner {
         method: &str,
         original_params: &HashMap<String, serde_json::Value>,
         headers: &HashMap<String, String>,
+        parameters: &[ApiParameter],
         scan_type: &ScanType,
     ) -> SecurityScanResult {
+        if *scan_type == ScanType::ParameterFuzz {
+            return self.run_parameter_fuzz(url, method, original_params, headers, parameters);
+        }
+
         let start = Instant::now();
         let started_at = chrono::Utc::now().timestamp();
         let mut alerts = Vec::new();
@@ -83,6 +90,108 @@ impl SecurityScanner {
             duration_ms,
             started_at,
             completed_at: chrono::Utc::now().timestamp(),
+            parameter_reports: Vec::new(),
+        }
+    }
+
+    /// Send each parameter's boundary/malformed inputs one at a time, keeping the other
+    /// parameters valid, and flag every input answered with a 5xx instead of a 4xx
+    fn run_parameter_fuzz(
+        &self,
+        url: &str,
+        method: &str,
+        original_params: &HashMap<String, serde_json::Value>,
+        headers: &HashMap<String, String>,
+        parameters: &[ApiParameter],
+    ) -> SecurityScanResult {
+        let start = Instant::now();
+        let started_at = chrono::Utc::now().timestamp();
+
+        // Without scanned metadata, guess each parameter's type from its value
+        let parameters: Vec<ApiParameter> = if parameters.is_empty() {
+            let mut names: Vec<&String> = original_params.keys().collect();
+            names.sort();
+            names.into_iter().map(|name| fuzz::parameter_from_value(name, &original_params[name])).collect()
+        } else {
+            parameters.to_vec()
+        };
+
+        // Baseline request: the given values, falling back to each parameter's example
+        let mut baseline = original_params.clone();
+        for param in &parameters {
+            if !baseline.contains_key(&param.name) {
+                if let Some(value) = param.example.clone().or_else(|| param.default_value.clone()) {
+                    baseline.insert(param.name.clone(), value);
+                }
+            }
+        }
+
+        let mut alerts = Vec::new();
+        let mut parameter_reports = Vec::new();
+        let mut requests_sent = 0u32;
+
+        for param in &parameters {
+            let mut report = ParameterFuzzReport {
+                parameter: param.name.clone(),
+                param_type: param.param_type.clone(),
+                ..Default::default()
+            };
+
+            for input in fuzz::fuzz_inputs(param) {
+                let mut test_params = baseline.clone();
+                match &input.value {
+                    Some(value) => {
+                        test_params.insert(param.name.clone(), value.clone());
+                    }
+                    None => {
+                        test_params.remove(&param.name);
+                    }
+                }
+
+                match self.send_request(url, method, &test_params, headers) {
+                    Ok((status, body, _)) => {
+                        requests_sent += 1;
+                        report.inputs_sent += 1;
+                        if status >= 500 {
+                            report.server_errors += 1;
+                            report.failed_inputs.push(input.label.clone());
+                            alerts.push(SecurityAlert {
+                                severity: AlertSeverity::High,
+                                message: format!(
+                                    "Unhandled error (HTTP {}) for {} input in parameter '{}': expected a 4xx validation error",
+                                    status, input.label, param.name
+                                ),
+                                payload: input.value.as_ref()
+                                    .map(|v| v.to_string().chars().take(200).collect())
+                                    .unwrap_or_else(|| "<missing>".to_string()),
+                                response_snippet: Some(body.chars().take(500).collect()),
+                            });
+                        } else if status >= 400 {
+                            report.rejected += 1;
+                        } else {
+                            report.accepted += 1;
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("Fuzz request failed for parameter {} ({}): {}", param.name, input.label, e);
+                    }
+                }
+            }
+
+            parameter_reports.push(report);
+        }
+
+        SecurityScanResult {
+            id: uuid::Uuid::new_v4().to_string(),
+            test_case_id: String::new(),
+            scan_type: ScanType::ParameterFuzz,
+            status: if alerts.is_empty() { ScanStatus::Pass } else { ScanStatus::Fail },
+            requests_sent,
+            alerts,
+            duration_ms: start.elapsed().as_millis() as u64,
+            started_at,
+            completed_at: chrono::Utc::now().timestamp(),
+            parameter_reports,
         }
     }
 
@@ -108,7 +217,18 @@ impl SecurityScanner {
             req = req.header(k, v);
         }
 
-        if method != "GET" {
+        if method.eq_ignore_ascii_case("GET") {
+            // GET has no body; send the parameters as the query string
+            let query: Vec<(&String, String)> = params
+                .iter()
+                .map(|(k, v)| match v {
+                    serde_json::Value::String(s) => (k, s.clone()),
+                    serde_json::Value::Null => (k, String::new()),
+                    other => (k, other.to_string()),
+                })
+                .collect();
+            req = req.query(&query);
+        } else {
             req = req.json(params);
         }
 
@@ -203,6 +323,7 @@ pub fn run_security_test(
     method: &str,
     params: &HashMap<String, serde_json::Value>,
     headers: &HashMap<String, String>,
+    parameters: &[ApiParameter],
 ) -> SecurityTestRun {
     let scanner = SecurityScanner::new();
     let started_at = chrono::Utc::now().timestamp();
@@ -213,7 +334,7 @@ pub fn run_security_test(
     let enabled_scans: Vec<_> = test_case.scans.iter().filter(|s| s.enabled).collect();
 
     for scan_config in &enabled_scans {
-        let mut result = scanner.run_scan(url, method, params, headers, &scan_config.scan_type);
+        let mut result = scanner.run_scan(url, method, params, headers, parameters, &scan_config.scan_type);
         result.test_case_id = test_case.id.clone();
         total_requests += result.requests_sent;
         total_alerts += result.alerts.len() as u32;
@@ -10,6 +10,7 @@ pub enum ScanType {
     FuzzingScan,
     BoundaryScan,
     InvalidTypes,
+    ParameterFuzz,
 }
 
 impl ScanType {
@@ -23,6 +24,7 @@ impl ScanType {
             ScanType::FuzzingScan => "Fuzzing Scan",
             ScanType::BoundaryScan => "Boundary Scan",
             ScanType::InvalidTypes => "Invalid Types",
+            ScanType::ParameterFuzz => "Parameter Fuzzing",
         }
     }
 }
@@ -69,6 +71,20 @@ pub struct SecurityScanResult {
     pub duration_ms: u64,
     pub started_at: i64,
     pub completed_at: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameter_reports: Vec<ParameterFuzzReport>, // Parameter fuzzing only
+}
+
+/// How the server answered the fuzz inputs sent to one parameter
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ParameterFuzzReport {
+    pub parameter: String,
+    pub param_type: String,
+    pub inputs_sent: u32,
+    pub rejected: u32,      // 4xx: handled as a validation error
+    pub accepted: u32,      // 2xx/3xx: invalid input went through
+    pub server_errors: u32, // 5xx: unhandled exception
+    pub failed_inputs: Vec<String>, // Labels of inputs that caused a 5xx
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
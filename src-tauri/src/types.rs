@@ -11,6 +11,116 @@ pub struct Project {
     pub last_scanned: Option<i64>,
     #[serde(rename = "baseUrl")]
     pub base_url: Option<String>,
+    #[serde(rename = "verifyTls", default = "default_verify_tls")]
+    pub verify_tls: bool,
+    #[serde(rename = "userAgent", default)]
+    pub user_agent: Option<String>, // Falls back to LookAPI/<version>
+    #[serde(rename = "defaultHeaders", default)]
+    pub default_headers: std::collections::HashMap<String, String>, // Sent with every scenario/perf request
+    #[serde(rename = "requestLogging", default)]
+    pub request_logging: bool, // Capture scenario requests/responses to the project log file
+    #[serde(default)]
+    pub services: Vec<ProjectService>, // Extra named base URLs for multi-service projects
+    #[serde(rename = "requestSigning", default)]
+    pub request_signing: Option<crate::request_signing::RequestSigningConfig>, // HMAC header added to scenario requests
+    #[serde(default)]
+    pub variables: std::collections::HashMap<String, serde_json::Value>, // Project-wide {{var}} values; scenario variables win
+    #[serde(default)]
+    pub idempotency: Option<IdempotencyConfig>, // Auto-generated idempotency key for unsafe scenario requests
+    #[serde(rename = "bodyLimits", default)]
+    pub body_limits: BodyLimits, // How much of each request/response body scenario runs keep
+}
+
+fn default_max_request_bytes() -> usize {
+    crate::scenario::executor::DEFAULT_MAX_REQUEST_BODY_BYTES
+}
+
+fn default_max_response_bytes() -> usize {
+    crate::scenario::executor::DEFAULT_MAX_RESPONSE_BODY_BYTES
+}
+
+/// Size limits for bodies stored with a scenario run. Responses are also read only up to
+/// `max_response_bytes`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BodyLimits {
+    #[serde(rename = "maxRequestBytes", default = "default_max_request_bytes")]
+    pub max_request_bytes: usize,
+    #[serde(rename = "maxResponseBytes", default = "default_max_response_bytes")]
+    pub max_response_bytes: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            max_request_bytes: default_max_request_bytes(),
+            max_response_bytes: default_max_response_bytes(),
+        }
+    }
+}
+
+impl BodyLimits {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_request_bytes == 0 || self.max_response_bytes == 0 {
+            return Err("Invalid body limits: sizes must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn default_idempotency_header() -> String {
+    "Idempotency-Key".to_string()
+}
+
+fn default_idempotency_methods() -> Vec<String> {
+    vec!["POST".to_string(), "PUT".to_string()]
+}
+
+/// Attach a fresh UUID key header to each execution of a matching request step, so servers
+/// that dedupe on it never create the same resource twice
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IdempotencyConfig {
+    #[serde(rename = "headerName", default = "default_idempotency_header")]
+    pub header_name: String,
+    #[serde(default = "default_idempotency_methods")]
+    pub methods: Vec<String>, // POST and PUT by default
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            header_name: default_idempotency_header(),
+            methods: default_idempotency_methods(),
+        }
+    }
+}
+
+impl IdempotencyConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if reqwest::header::HeaderName::from_bytes(self.header_name.as_bytes()).is_err() {
+            return Err(format!("Invalid idempotency header name: '{}'", self.header_name));
+        }
+        if self.methods.is_empty() {
+            return Err("Invalid idempotency config: at least one method is required".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether requests with this method get a key
+    pub fn applies_to(&self, method: &str) -> bool {
+        self.methods.iter().any(|m| m.eq_ignore_ascii_case(method))
+    }
+}
+
+/// Named base URL a request step can target with `service`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectService {
+    pub name: String,
+    #[serde(rename = "baseUrl")]
+    pub base_url: String,
+}
+
+fn default_verify_tls() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -27,6 +137,18 @@ pub struct ApiEndpoint {
     pub category: String,
     pub explanation: Option<String>,
     pub responses: Option<Vec<ApiResponseDefinition>>,
+    #[serde(default)]
+    pub version: Option<String>, // API version detected by the scanner, e.g. "v1"
+    #[serde(rename = "userModified", default)]
+    pub user_modified: bool, // Edited by hand; re-scans keep its fields
+    #[serde(default)]
+    pub stale: bool, // Not found by the latest scan
+    #[serde(rename = "authRequired", default)]
+    pub auth_required: bool, // Scanner found an auth guard/middleware on the route
+    #[serde(rename = "authType", default)]
+    pub auth_type: Option<String>, // e.g. "JWT", "api_key", "basic"; None means bearer
+    #[serde(default)]
+    pub warnings: Vec<String>, // Scanner heuristics about the handler, e.g. possible N+1 queries
 }
 
 /// Response definition for API documentation (stored in database)
@@ -51,14 +173,26 @@ pub struct ApiParameter {
     pub example: Option<serde_json::Value>,
     #[serde(rename = "defaultValue")]
     pub default_value: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<Vec<String>>, // Rules collected by the scanner, e.g. "min:3", "email"
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApiRequest {
     pub endpoint: String,
     pub method: String,
     pub parameters: serde_json::Value,
     pub headers: Option<std::collections::HashMap<String, String>>,
+    #[serde(rename = "endpointId", default)]
+    pub endpoint_id: Option<String>, // Links the history entry to a scanned endpoint
+    #[serde(rename = "savedRequestId", default)]
+    pub saved_request_id: Option<String>, // Run a saved request instead of the inline fields
+    #[serde(default)]
+    pub variables: Option<std::collections::HashMap<String, serde_json::Value>>, // Values for {{var}} in saved requests
+    #[serde(rename = "validateSchema", default)]
+    pub validate_schema: bool, // Check the response against the linked endpoint's stored schema
+    #[serde(rename = "skipAuth", default)]
+    pub skip_auth: bool, // Don't add auth headers for an endpoint that requires auth
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -70,6 +204,8 @@ pub struct ApiResponse {
     pub headers: std::collections::HashMap<String, String>,
     pub duration: u128,
     pub timestamp: String,
+    #[serde(rename = "schemaValidation", default, skip_serializing_if = "Option::is_none")]
+    pub schema_validation: Option<crate::scanner::schema_validation::SchemaValidationResult>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -88,6 +224,34 @@ pub struct QueryResult {
     pub row_count: usize,
 }
 
+/// Aggregated overview of a project for the dashboard
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectStats {
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "totalEndpoints")]
+    pub total_endpoints: u32,
+    #[serde(rename = "endpointsByMethod")]
+    pub endpoints_by_method: std::collections::HashMap<String, u32>,
+    #[serde(rename = "endpointsRequiringAuth")]
+    pub endpoints_requiring_auth: u32,
+    #[serde(rename = "scenarioCount")]
+    pub scenario_count: u32,
+    #[serde(rename = "securityTestCaseCount")]
+    pub security_test_case_count: u32,
+    #[serde(rename = "lastScanned")]
+    pub last_scanned: Option<i64>,
+    #[serde(rename = "lastRunPassRate")]
+    pub last_run_pass_rate: Option<f64>,
+}
+
+/// Endpoints stored by a project scan, plus the scan's warnings (e.g. skipped source files)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectScanResult {
+    pub endpoints: Vec<ApiEndpoint>,
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct YamlFile {
     pub id: String,
@@ -98,6 +262,21 @@ pub struct YamlFile {
     pub content: String,
     #[serde(rename = "createdAt")]
     pub created_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<YamlProvenance>, // Set for YAML produced by AI generation
+}
+
+/// How an AI-generated YAML file was produced, kept so it can be regenerated with tweaks
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct YamlProvenance {
+    pub prompt: String,
+    #[serde(rename = "endpointIds", default)]
+    pub endpoint_ids: Vec<String>, // Endpoints included in the prompt context
+    pub backend: String, // e.g. "copilot-cli"
+    #[serde(rename = "baseUrl", default)]
+    pub base_url: Option<String>,
+    #[serde(rename = "generatedAt")]
+    pub generated_at: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -140,3 +319,66 @@ pub struct RequestTabState {
     #[serde(rename = "activeTabId")]
     pub active_tab_id: Option<String>,
 }
+
+/// A manually executed request, persisted for the history panel
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RequestHistoryEntry {
+    pub id: String,
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: Option<String>,
+    pub method: String,
+    pub url: String,
+    pub headers: std::collections::HashMap<String, String>,
+    pub body: serde_json::Value,
+    pub status: Option<u16>, // None when the request failed before a response
+    #[serde(rename = "durationMs")]
+    pub duration_ms: u64,
+    pub error: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+}
+
+/// A named, reusable request configuration (like a Postman saved request)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedRequest {
+    pub id: String,
+    #[serde(rename = "projectId")]
+    pub project_id: Option<String>,
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: Option<String>,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub headers: std::collections::HashMap<String, String>,
+    pub params: serde_json::Value,
+    pub body: serde_json::Value,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateSavedRequestInput {
+    #[serde(rename = "projectId")]
+    pub project_id: Option<String>,
+    #[serde(rename = "endpointId")]
+    pub endpoint_id: Option<String>,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub headers: Option<std::collections::HashMap<String, String>>,
+    pub params: Option<serde_json::Value>,
+    pub body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateSavedRequestInput {
+    pub id: String,
+    pub name: Option<String>,
+    pub method: Option<String>,
+    pub url: Option<String>,
+    pub headers: Option<std::collections::HashMap<String, String>>,
+    pub params: Option<serde_json::Value>,
+    pub body: Option<serde_json::Value>,
+}
@@ -95,11 +95,17 @@ export function ProjectSelector() {
     if (!currentProject) return;
     
     try {
-      const endpoints = await scanProject();
+      const { endpoints, warnings } = await scanProject();
       toast({
         title: 'Scan completed',
         description: `Found ${endpoints.length} API endpoints`,
       });
+      if (warnings.length > 0) {
+        toast({
+          title: `Scan finished with ${warnings.length} warning${warnings.length === 1 ? '' : 's'}`,
+          description: warnings.join('\n'),
+        });
+      }
     } catch (err) {
       toast({
         title: 'Scan failed',
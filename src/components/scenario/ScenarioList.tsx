@@ -140,7 +140,7 @@ export function ScenarioList({ projectId, selectedScenario, onSelectScenario }:
       await refetch();
       toast({
         title: 'Imported Successfully',
-        description: `${imported.length} scenario(s) imported.`,
+        description: `${imported.scenariosCount} scenario(s) with ${imported.stepsCount} step(s) imported.`,
       });
     } catch (e) {
       toast({
@@ -1,5 +1,5 @@
 import { createContext, useContext, useState, useEffect, useCallback, ReactNode } from 'react';
-import { Project, APIEndpoint } from '@/types/api';
+import { Project, ProjectScanResult } from '@/types/api';
 import { tauriService } from '@/services/tauri';
 import { useQueryClient } from '@tanstack/react-query';
 
@@ -13,7 +13,7 @@ interface ProjectContextType {
   openFolder: () => Promise<string | null>;
   createProject: (path: string) => Promise<Project>;
   deleteProject: (projectId: string) => Promise<void>;
-  scanProject: () => Promise<APIEndpoint[]>;
+  scanProject: () => Promise<ProjectScanResult>;
   refreshProjects: () => Promise<void>;
   updateProjectBaseUrl: (projectId: string, baseUrl: string | null) => Promise<void>;
   ensureProjectExists: (project: Project) => Promise<void>;
@@ -136,7 +136,7 @@ export function ProjectProvider({ children }: { children: ReactNode }) {
     }
   }, [currentProject, queryClient]);
 
-  const scanProject = useCallback(async (): Promise<APIEndpoint[]> => {
+  const scanProject = useCallback(async (): Promise<ProjectScanResult> => {
     if (!currentProject) {
       throw new Error('No project selected');
     }
@@ -144,7 +144,7 @@ export function ProjectProvider({ children }: { children: ReactNode }) {
     try {
       setIsScanning(true);
       setError(null);
-      const endpoints = await tauriService.scanProject(currentProject.id, currentProject.path);
+      const result = await tauriService.scanProject(currentProject.id, currentProject.path);
       
       // Update project's lastScanned timestamp
       const updatedProject = { ...currentProject, lastScanned: Date.now() };
@@ -154,7 +154,7 @@ export function ProjectProvider({ children }: { children: ReactNode }) {
       // Invalidate endpoints query to refresh the sidebar
       queryClient.invalidateQueries({ queryKey: ['endpoints'] });
       
-      return endpoints;
+      return result;
     } catch (err) {
       const errorMsg = err instanceof Error ? err.message : 'Failed to scan project';
       setError(errorMsg);
@@ -6,8 +6,7 @@ import {
   PerformanceTestConfig,
   PerformanceTestRun,
   CreatePerformanceTestInput,
-  Stage,
-  Threshold,
+  UpdatePerformanceTestInput,
   PerfStartedEvent,
   PerfProgressEvent,
   PerfStageChangedEvent,
@@ -81,33 +80,8 @@ export function usePerformanceTest(configId: string) {
   });
 
   const updateMutation = useMutation({
-    mutationFn: ({
-      name,
-      testType,
-      vus,
-      durationSecs,
-      iterations,
-      stages,
-      thresholds,
-    }: {
-      name?: string;
-      testType?: string;
-      vus?: number;
-      durationSecs?: number;
-      iterations?: number;
-      stages?: Stage[];
-      thresholds?: Threshold[];
-    }) =>
-      tauriService.updatePerformanceTest(
-        configId,
-        name,
-        testType,
-        vus,
-        durationSecs,
-        iterations,
-        stages,
-        thresholds
-      ),
+    mutationFn: (input: UpdatePerformanceTestInput) =>
+      tauriService.updatePerformanceTest(configId, input),
     onSuccess: (updated) => {
       queryClient.setQueryData(['performanceTest', configId], updated);
       queryClient.invalidateQueries({ queryKey: ['performanceTests'] });
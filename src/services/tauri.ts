@@ -1,11 +1,14 @@
-import { invoke } from '@tauri-apps/api/core';
-import { APIEndpoint, APIRequest, APIResponse, TestSuite, QueryResult, Project } from '../types/api';
+import { invoke as tauriInvoke, InvokeArgs } from '@tauri-apps/api/core';
+import { APIEndpoint, APIRequest, APIResponse, TestSuite, QueryResult, Project, EndpointHealth, RemoteProbe, ProjectScanResult } from '../types/api';
 import { SecurityTestCase, SecurityTestRun, ScanConfig } from '../types/security';
 import { RequestTab } from '../types/requestTab';
 import {
   TestScenario,
   TestScenarioStep,
   TestScenarioRun,
+  ScenarioSchedule,
+  ScheduleSummary,
+  LintIssue,
   UpdateScenarioRequest,
   CreateStepRequest,
   UpdateStepRequest,
@@ -14,38 +17,119 @@ import {
 import {
   ScenarioImportPreview,
   ProjectImportPreview,
+  ProjectImportSummary,
 } from '../types/yaml';
 import {
   PerformanceTestConfig,
   PerformanceTestRun,
   CreatePerformanceTestInput,
-  Stage,
-  Threshold,
+  UpdatePerformanceTestInput,
 } from '../types/performance';
 
+/** Error returned by backend commands; `kind` is one of db, io, network, validation, parse, notFound, internal */
+export class AppError extends Error {
+  constructor(public kind: string, message: string) {
+    super(message);
+    this.name = 'AppError';
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  return tauriInvoke<T>(cmd, args).catch((error: unknown) => {
+    if (error && typeof error === 'object' && 'kind' in error && 'message' in error) {
+      const { kind, message } = error as { kind: string; message: string };
+      throw new AppError(kind, message);
+    }
+    throw error;
+  });
+}
+
 export const tauriService = {
   async executeHttpRequest(request: APIRequest): Promise<APIResponse> {
     return invoke('execute_http_request', { request });
   },
 
-  async generateCurlCommand(url: string, method: string, body?: any): Promise<string> {
-    return invoke('generate_curl_command', { url, method, body });
+  /**
+   * Generate a curl command; with `resolved`, placeholders are filled from the project,
+   * scenario and `variables`, and the endpoint's auth header is added
+   */
+  async generateCurlCommand(
+    url: string,
+    method: string,
+    body?: any,
+    options?: {
+      headers?: Record<string, string>;
+      resolved?: boolean;
+      variables?: Record<string, any>;
+      scenarioId?: string;
+      endpointId?: string;
+    }
+  ): Promise<string> {
+    return invoke('generate_curl_command', { url, method, body, ...options });
   },
 
   async getAllEndpoints(): Promise<APIEndpoint[]> {
     return invoke('get_all_endpoints');
   },
 
+  /**
+   * Create endpoints from an OpenAPI 3 / Swagger 2 document (JSON or YAML)
+   */
+  async importOpenapi(projectId: string, content: string): Promise<APIEndpoint[]> {
+    return invoke('import_openapi', { projectId, content });
+  },
+
+  /**
+   * Probe a base URL for discovery documents and framework hints; a found OpenAPI
+   * document can be passed to importOpenapi
+   */
+  async probeRemoteApi(baseUrl: string, verifyTls?: boolean): Promise<RemoteProbe> {
+    return invoke('probe_remote_api', { baseUrl, verifyTls });
+  },
+
+  /**
+   * Send one example request per endpoint and store the results; unsafe methods are
+   * skipped unless includeUnsafeMethods is set
+   */
+  async getEndpointHealth(projectId: string, includeUnsafeMethods?: boolean): Promise<EndpointHealth[]> {
+    return invoke('get_endpoint_health', { projectId, includeUnsafeMethods });
+  },
+
+  async cancelEndpointHealth(projectId: string): Promise<void> {
+    return invoke('cancel_endpoint_health', { projectId });
+  },
+
+  async getEndpointHealthHistory(endpointId: string, limit?: number): Promise<EndpointHealth[]> {
+    return invoke('get_endpoint_health_history', { endpointId, limit });
+  },
+
   async saveEndpoint(endpoint: APIEndpoint): Promise<void> {
     return invoke('save_endpoint', { endpoint });
   },
 
+  // Bulk endpoint edits; each returns the number of endpoints affected
+  async deleteEndpoints(ids: string[]): Promise<number> {
+    return invoke('delete_endpoints', { ids });
+  },
+
+  async updateEndpointsCategory(ids: string[], category: string): Promise<number> {
+    return invoke('update_endpoints_category', { ids, category });
+  },
+
+  async updateEndpointsService(ids: string[], service: string): Promise<number> {
+    return invoke('update_endpoints_service', { ids, service });
+  },
+
   async getAllTestSuites(): Promise<TestSuite[]> {
     return invoke('get_all_test_suites');
   },
 
-  async executeSqlQuery(dbPath: string, query: string): Promise<QueryResult> {
-    return invoke('execute_sql_query', { dbPath, query });
+  async executeSqlQuery(dbPath: string, query: string, readOnly = true): Promise<QueryResult> {
+    return invoke('execute_sql_query', { dbPath, query, readOnly });
   },
 
   async exportResponse(filename: string, content: string): Promise<string> {
@@ -89,8 +173,9 @@ export const tauriService = {
     return invoke('get_endpoints_by_project', { projectId });
   },
 
-  async scanProject(projectId: string, projectPath: string): Promise<APIEndpoint[]> {
-    return invoke('scan_project', { projectId, projectPath });
+  // workers caps scan threads; defaults to the machine's available parallelism
+  async scanProject(projectId: string, projectPath: string, workers?: number): Promise<ProjectScanResult> {
+    return invoke('scan_project', { projectId, projectPath, workers });
   },
 
   // Security testing
@@ -174,14 +259,39 @@ export const tauriService = {
     return invoke('reorder_test_scenario_steps', { request });
   },
 
-  async runTestScenario(scenarioId: string): Promise<TestScenarioRun> {
-    return invoke('run_test_scenario', { scenarioId });
+  // maxSteps caps step executions (loops and CSV rows count each time); defaults to 10,000
+  async runTestScenario(scenarioId: string, dryRun = false, environment?: string, maxSteps?: number): Promise<TestScenarioRun> {
+    return invoke('run_test_scenario', { scenarioId, options: { dryRun, environment, maxSteps } });
   },
 
   async getTestScenarioRuns(scenarioId: string): Promise<TestScenarioRun[]> {
     return invoke('get_test_scenario_runs', { scenarioId });
   },
 
+  async lintScenario(scenarioId: string): Promise<LintIssue[]> {
+    return invoke('lint_scenario', { scenarioId });
+  },
+
+  async exportRunCsv(runId: string): Promise<string> {
+    return invoke('export_run_csv', { runId });
+  },
+
+  async startScenarioSchedule(scenarioId: string, intervalMinutes: number): Promise<ScenarioSchedule> {
+    return invoke('start_scenario_schedule', { scenarioId, intervalMinutes });
+  },
+
+  async stopScenarioSchedule(scenarioId: string): Promise<ScenarioSchedule> {
+    return invoke('stop_scenario_schedule', { scenarioId });
+  },
+
+  async getScenarioSchedules(): Promise<ScenarioSchedule[]> {
+    return invoke('get_scenario_schedules');
+  },
+
+  async getScenarioScheduleSummary(scenarioId: string, limit?: number): Promise<ScheduleSummary> {
+    return invoke('get_scenario_schedule_summary', { scenarioId, limit });
+  },
+
   // ============================================================================
   // YAML Export/Import APIs
   // ============================================================================
@@ -224,10 +334,22 @@ export const tauriService = {
   /**
    * Import multiple scenarios from project YAML
    */
-  async importProjectScenariosYaml(projectId: string, yamlContent: string): Promise<TestScenario[]> {
+  async importProjectScenariosYaml(projectId: string, yamlContent: string): Promise<ProjectImportSummary> {
     return invoke('import_project_scenarios_yaml', { projectId, yamlContent });
   },
 
+  /**
+   * Import a HAR capture as a new scenario; returns the scenario id
+   */
+  async importHar(
+    projectId: string,
+    harJson: string,
+    filterHost?: string,
+    parameterize?: boolean
+  ): Promise<string> {
+    return invoke('import_har', { projectId, harJson, filterHost, parameterize });
+  },
+
   /**
    * Get YAML template for AI tools
    */
@@ -242,19 +364,22 @@ export const tauriService = {
    * @param userPrompt - User's prompt describing what kind of test scenario to generate
    * @param projectId - Optional project ID to include endpoints context
    * @param baseUrl - Optional base URL for the API
+   * @param toolPolicy - Copilot CLI tools to allow/deny; defaults deny shell(cd|git|pwd), fetch, extensions, websearch, githubRepo
    * @returns Generated YAML template string and optionally created scenario
    */
   async generateYamlWithAI(
     projectPath: string,
     userPrompt: string,
     projectId?: string,
-    baseUrl?: string
+    baseUrl?: string,
+    toolPolicy?: { allowTools?: string[]; denyTools?: string[] }
   ): Promise<{ yaml: string; scenario?: any }> {
     return invoke('generate_yaml_with_ai', { 
       projectPath, 
       userPrompt, 
       projectId, 
-      baseUrl 
+      baseUrl,
+      toolPolicy
     });
   },
 
@@ -283,13 +408,15 @@ export const tauriService = {
    * 
    * @param scenarioId - Scenario ID to update
    * @param yamlContent - YAML content to update from
+   * @param mode - 'replace' recreates all steps; 'merge' matches steps by name and keeps their ids
    * @returns Updated scenario
    */
   async updateScenarioFromYaml(
     scenarioId: string,
-    yamlContent: string
+    yamlContent: string,
+    mode: 'replace' | 'merge' = 'replace'
   ): Promise<TestScenario> {
-    return invoke('update_scenario_from_yaml', { scenarioId, yamlContent });
+    return invoke('update_scenario_from_yaml', { scenarioId, yamlContent, mode });
   },
 
   // ============================================================================
@@ -322,24 +449,9 @@ export const tauriService = {
    */
   async updatePerformanceTest(
     configId: string,
-    name?: string,
-    testType?: string,
-    vus?: number,
-    durationSecs?: number,
-    iterations?: number,
-    stages?: Stage[],
-    thresholds?: Threshold[]
+    input: UpdatePerformanceTestInput
   ): Promise<PerformanceTestConfig> {
-    return invoke('update_performance_test', { 
-      configId, 
-      name, 
-      testType, 
-      vus, 
-      durationSecs, 
-      iterations, 
-      stages, 
-      thresholds 
-    });
+    return invoke('update_performance_test', { configId, input });
   },
 
   /**
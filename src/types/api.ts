@@ -19,6 +19,18 @@ export interface APIEndpoint {
   category: string;
   explanation?: string;
   responses?: APIResponseDefinition[];
+  userModified?: boolean;
+  stale?: boolean;
+  authRequired?: boolean;
+  authType?: string;
+  /** Scanner heuristics about the handler, e.g. possible N+1 queries */
+  warnings?: string[];
+}
+
+/** Result of scan_project: the stored endpoints plus scan warnings, e.g. skipped source files */
+export interface ProjectScanResult {
+  endpoints: APIEndpoint[];
+  warnings: string[];
 }
 
 export interface APIResponseDefinition {
@@ -62,6 +74,25 @@ export interface APIRequest {
   method: string;
   parameters: Record<string, any>;
   headers?: Record<string, string>;
+  endpointId?: string;
+  validateSchema?: boolean;
+  // Opt out of the auth headers added for endpoints that require auth
+  skipAuth?: boolean;
+}
+
+export interface SchemaDeviation {
+  path: string;
+  kind: 'missing' | 'typeMismatch';
+  expected: string;
+  actual?: string;
+}
+
+export interface SchemaValidationResult {
+  endpointId: string;
+  statusCode: number;
+  schemaFound: boolean;
+  valid: boolean;
+  deviations: SchemaDeviation[];
 }
 
 export interface APIResponse {
@@ -71,6 +102,35 @@ export interface APIResponse {
   headers: Record<string, string>;
   duration: number;
   timestamp: string;
+  schemaValidation?: SchemaValidationResult;
+}
+
+/** Result of one endpoint health check */
+export interface EndpointHealth {
+  endpointId: string;
+  method: string;
+  path: string;
+  url: string;
+  status?: number;
+  latencyMs?: number;
+  reachable: boolean;        // Answered without a connection error, timeout or 5xx
+  schemaValid?: boolean;     // Unset when there is no stored schema for the status
+  skipped: boolean;          // Unsafe method not sent
+  error?: string;
+  checkedAt: number;
+}
+
+/** Result of probing a base URL without source */
+export interface RemoteProbe {
+  baseUrl: string;
+  reachable: boolean;
+  isApi: boolean;                  // A JSON response or an OpenAPI document was found
+  openapiUrl?: string;
+  openapiEndpointCount?: number;
+  openapiDocument?: string;        // Pass to importOpenapi to create the endpoints
+  serverHeaders: Record<string, string>;
+  frameworkHints: { framework: string; evidence: string }[];
+  paths: { path: string; status?: number; contentType?: string; error?: string }[];
 }
 
 export interface TestSuite {
@@ -20,6 +20,12 @@ export interface Threshold {
   condition: string;       // 'p(95)<500', 'rate<0.05'
 }
 
+/** One scenario in a weighted traffic mix; weights are relative shares of iterations */
+export interface WeightedScenario {
+  scenarioId: string;
+  weight: number;
+}
+
 /** Threshold evaluation result */
 export interface ThresholdResult {
   threshold: Threshold;
@@ -39,6 +45,8 @@ export interface PerformanceTestConfig {
   iterations?: number;             // Or number of iterations
   stages?: Stage[];                // Ramping stages
   thresholds: Threshold[];
+  maxInFlight?: number;            // Cap on concurrent requests; defaults from peak VUs
+  scenarioMix?: WeightedScenario[]; // Weighted scenarios picked per iteration; empty runs scenarioId only
   createdAt: number;
   updatedAt: number;
 }
@@ -53,6 +61,24 @@ export interface CreatePerformanceTestInput {
   iterations?: number;
   stages?: Stage[];
   thresholds?: Threshold[];
+  maxInFlight?: number;
+  scenarioMix?: WeightedScenario[];
+}
+
+/** Input for updating a performance test config; omitted fields keep their stored value */
+export interface UpdatePerformanceTestInput {
+  name?: string;
+  testType?: string;
+  vus?: number;
+  durationSecs?: number;
+  iterations?: number;
+  stages?: Stage[];
+  thresholds?: Threshold[];
+  http2PriorKnowledge?: boolean;
+  warmupSecs?: number;
+  storeRawSamples?: boolean;
+  maxInFlight?: number;
+  scenarioMix?: WeightedScenario[];
 }
 
 /** Metrics for a single HTTP request */
@@ -67,6 +93,9 @@ export interface RequestMetric {
   vuId: number;
   iteration: number;
   timestamp: number;
+  queueWaitMs?: number;     // Wait for an in-flight slot, excluded from durationMs
+  serverTiming?: Record<string, number>; // Server-Timing durations (ms) by metric name
+  scenarioId?: string;      // Scenario whose iteration issued the request
 }
 
 /** Per-step aggregated metrics */
@@ -82,6 +111,20 @@ export interface StepMetrics {
   durationP90: number;
   durationP95: number;
   durationP99: number;
+  serverTiming?: Record<string, number>; // Average Server-Timing duration (ms) by metric name
+}
+
+/** Per-scenario aggregated metrics for mixed-scenario runs */
+export interface ScenarioMetrics {
+  scenarioName: string;
+  iterations: number;
+  totalRequests: number;
+  failedRequests: number;
+  errorRate: number;
+  durationAvg: number;
+  durationMed: number;
+  durationP95: number;
+  durationP99: number;
 }
 
 /** Aggregated metrics for the entire performance test */
@@ -105,9 +148,18 @@ export interface AggregatedMetrics {
   
   // Duration
   totalDurationMs: number;
+
+  // Request queuing behind the in-flight limit
+  maxInFlight?: number;
+  queueWaitAvg?: number;
+  queueWaitP95?: number;
+  queueWaitMax?: number;
   
   // Per-step metrics
   stepMetrics: Record<string, StepMetrics>;
+
+  // Per-scenario metrics, keyed by scenario id
+  scenarioMetrics?: Record<string, ScenarioMetrics>;
 }
 
 /** Performance run status */
@@ -167,6 +219,8 @@ export interface PerfProgressEvent {
 export interface PerfStageChangedEvent {
   runId: string;
   stageIndex: number;
+  /** VUs the stage ramps from (previous stage's target) */
+  fromVus: number;
   targetVus: number;
   durationSecs: number;
 }
@@ -9,11 +9,17 @@ export interface TestScenario {
   variables: Record<string, any>;
   preScript?: string;
   postScript?: string;
+  environments?: Record<string, ScenarioEnvironment>;
   createdAt: number;
   updatedAt: number;
 }
 
-export type TestStepType = 'request' | 'condition' | 'loop' | 'delay' | 'script';
+export interface ScenarioEnvironment {
+  baseUrl?: string;
+  variables?: Record<string, any>;
+}
+
+export type TestStepType = 'request' | 'condition' | 'loop' | 'delay' | 'script' | 'grpc';
 
 export interface TestScenarioStep {
   id: string;
@@ -21,7 +27,7 @@ export interface TestScenarioStep {
   stepOrder: number;
   stepType: TestStepType;
   name: string;
-  config: RequestStepConfig | ConditionStepConfig | LoopStepConfig | DelayStepConfig | ScriptStepConfig;
+  config: RequestStepConfig | ConditionStepConfig | LoopStepConfig | DelayStepConfig | ScriptStepConfig | GrpcStepConfig;
   enabled: boolean;
 }
 
@@ -65,6 +71,19 @@ export interface ScriptStepConfig {
   code: string;
 }
 
+// Unary gRPC call; the response status is the gRPC code (0 = OK) and the body is the message as JSON
+export interface GrpcStepConfig {
+  endpoint: string;
+  protoFile?: string;
+  descriptorSet?: string;
+  service: string;
+  method: string;
+  message?: any;
+  metadata?: Record<string, string>;
+  extractVariables?: VariableExtractor[];
+  assertions?: Assertion[];
+}
+
 export interface VariableExtractor {
   name: string;
   source: 'body' | 'header' | 'status';
@@ -74,13 +93,16 @@ export interface VariableExtractor {
 
 export interface Assertion {
   name: string;
-  source: 'status' | 'body' | 'header' | 'duration';
+  source: 'status' | 'body' | 'header' | 'duration' | 'serverTiming' | 'rawBody' | 'text' | 'variable';
   path?: string;
-  operator: 'equals' | 'contains' | 'matches' | 'greaterThan' | 'lessThan' | 'notEquals' | 'exists';
+  operator: 'equals' | 'contains' | 'matches' | 'greaterThan' | 'lessThan' | 'between' | 'notEquals' | 'exists' | 'length';
   expected: any;
   actual?: any;
   passed?: boolean;
   error?: string;
+  severity?: 'error' | 'warn';
+  logic?: 'and' | 'or'; // Group over the nested assertions
+  assertions?: Assertion[];
 }
 
 export type ScenarioRunStatus = 'pending' | 'running' | 'passed' | 'failed' | 'stopped' | 'error';
@@ -100,6 +122,14 @@ export interface TestScenarioRun {
   errorMessage?: string;
   results: TestStepResult[];
   variables: Record<string, any>;
+  logs?: RunLogEntry[];
+  trigger?: 'manual' | 'schedule';
+}
+
+export interface RunLogEntry {
+  timestamp: number;
+  level: 'error' | 'warn' | 'info' | 'debug' | 'trace';
+  message: string;
 }
 
 export interface TestStepResult {
@@ -113,6 +143,14 @@ export interface TestStepResult {
   assertions?: Assertion[];
   error?: string;
   extractedVariables?: Record<string, any>;
+  assertionSummary?: AssertionSummary;
+}
+
+export interface AssertionSummary {
+  passed: number;
+  failedErrors: number;
+  failedWarnings: number;
+  skipped: number;
 }
 
 export interface StepRequest {
@@ -128,6 +166,16 @@ export interface StepResponse {
   headers: Record<string, string>;
   body: any;
   durationMs: number;
+  serverTiming?: ServerTimingMetric[];
+  contentEncoding?: string;  // Content-Encoding the server sent, e.g. "gzip"
+  decodedSize?: number;      // Body bytes after undoing any Content-Encoding
+}
+
+/** One entry of the Server-Timing response header */
+export interface ServerTimingMetric {
+  name: string;
+  dur?: number;  // Milliseconds
+  desc?: string;
 }
 
 // Request types for API calls
@@ -146,13 +194,14 @@ export interface UpdateScenarioRequest {
   variables?: Record<string, any>;
   preScript?: string;
   postScript?: string;
+  environments?: Record<string, ScenarioEnvironment>;
 }
 
 export interface CreateStepRequest {
   scenarioId: string;
   stepType: TestStepType;
   name: string;
-  config: RequestStepConfig | ConditionStepConfig | LoopStepConfig | DelayStepConfig | ScriptStepConfig;
+  config: RequestStepConfig | ConditionStepConfig | LoopStepConfig | DelayStepConfig | ScriptStepConfig | GrpcStepConfig;
 }
 
 export interface UpdateStepRequest {
@@ -207,6 +256,7 @@ export const ASSERTION_OPERATORS = [
   { value: 'matches', label: 'Matches (Regex)' },
   { value: 'greaterThan', label: 'Greater Than' },
   { value: 'lessThan', label: 'Less Than' },
+  { value: 'between', label: 'Between [min, max]' },
   { value: 'exists', label: 'Exists' },
 ];
 
@@ -215,6 +265,7 @@ export const ASSERTION_SOURCES = [
   { value: 'body', label: 'Response Body' },
   { value: 'header', label: 'Response Header' },
   { value: 'duration', label: 'Response Duration (ms)' },
+  { value: 'variable', label: 'Variable' },
 ];
 
 export const EXTRACTOR_SOURCES = [
@@ -283,3 +334,37 @@ export interface ScenarioCompletedEvent {
   run: TestScenarioRun;
 }
 
+export interface LintIssue {
+  severity: 'error' | 'warning';
+  message: string;
+  stepId?: string;
+  stepName?: string;
+}
+
+export interface ScenarioSchedule {
+  scenarioId: string;
+  intervalMinutes: number;
+  enabled: boolean;
+  createdAt: number;
+  updatedAt: number;
+  active: boolean;
+}
+
+export interface ScheduleSummary {
+  totalRuns: number;
+  passedRuns: number;
+  passRate: number;
+  uptime: number;
+  avgDurationMs?: number;
+  consecutiveFailures: number;
+  lastRunAt?: number;
+  lastStatus?: ScenarioRunStatus;
+}
+
+export interface ScheduledRunEvent {
+  scenarioId: string;
+  runId?: string;
+  status?: ScenarioRunStatus;
+  error?: string;
+}
+
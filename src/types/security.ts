@@ -6,7 +6,8 @@ export type ScanType =
   | 'XmlBomb'
   | 'FuzzingScan'
   | 'BoundaryScan'
-  | 'InvalidTypes';
+  | 'InvalidTypes'
+  | 'ParameterFuzz';
 
 export const SCAN_TYPE_LABELS: Record<ScanType, string> = {
   SqlInjection: 'SQL Injection',
@@ -17,6 +18,7 @@ export const SCAN_TYPE_LABELS: Record<ScanType, string> = {
   FuzzingScan: 'Fuzzing Scan',
   BoundaryScan: 'Boundary Scan',
   InvalidTypes: 'Invalid Types',
+  ParameterFuzz: 'Parameter Fuzzing',
 };
 
 export type AssertionType = 'StatusCodeNot' | 'BodyNotContains' | 'ResponseTime';
@@ -88,4 +90,5 @@ export const DEFAULT_SCANS: ScanConfig[] = [
   { scanType: 'FuzzingScan', enabled: true, assertions: [] },
   { scanType: 'BoundaryScan', enabled: true, assertions: [] },
   { scanType: 'InvalidTypes', enabled: true, assertions: [] },
+  { scanType: 'ParameterFuzz', enabled: false, assertions: [] },
 ];
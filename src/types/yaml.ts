@@ -5,6 +5,8 @@
  * import/export functionality in the frontend.
  */
 
+import { TestScenario } from './scenario';
+
 // ============================================================================
 // Single Scenario YAML Format
 // ============================================================================
@@ -114,6 +116,19 @@ export interface ProjectImportPreview {
   scenarios: ScenarioImportPreview[];
 }
 
+/** Emitted as `scenario-import-progress` after each scenario of a project import */
+export interface ScenarioImportProgress {
+  name: string;
+  index: number;
+  total: number;
+}
+
+export interface ProjectImportSummary {
+  scenarios: TestScenario[];
+  scenariosCount: number;
+  stepsCount: number;
+}
+
 // ============================================================================
 // YAML Editor State Types
 // ============================================================================